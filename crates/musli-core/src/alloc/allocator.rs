@@ -1,6 +1,31 @@
 use super::{Alloc, AllocError};
 
 /// An allocator that can be used in combination with a context.
+///
+/// Every allocation is aligned to the type being allocated. So to allocate
+/// scratch memory with a particular alignment, allocate a type which has that
+/// alignment such as through [`Box::new_in`].
+///
+/// [`Box::new_in`]: super::Box::new_in
+///
+/// ## Examples
+///
+/// ```
+/// use musli::alloc::{AllocError, Box};
+///
+/// #[repr(C, align(16))]
+/// struct Key([u8; 16]);
+///
+/// musli::alloc::default(|alloc| {
+///     let mut key = Box::new_in(Key([0; 16]), alloc)?;
+///     key.0[..5].copy_from_slice(b"hello");
+///
+///     let ptr: *const Key = &*key;
+///     assert_eq!(ptr.align_offset(16), 0);
+///     Ok::<_, AllocError>(())
+/// });
+/// # Ok::<_, AllocError>(())
+/// ```
 pub trait Allocator: Copy {
     /// A raw allocation from the allocator.
     type Alloc<T>: Alloc<T>;
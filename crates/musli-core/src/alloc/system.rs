@@ -11,6 +11,9 @@ use super::{Alloc, AllocError, Allocator};
 ///
 /// This uses the [`System`] allocator.
 ///
+/// The allocator is stateless, so it's [`Send`] and [`Sync`] and can be freely
+/// copied into other threads.
+///
 /// [`System` allocator]: https://doc.rust-lang.org/std/alloc/struct.System.html
 ///
 /// # Examples
@@ -37,6 +40,12 @@ use super::{Alloc, AllocError, Allocator};
 #[non_exhaustive]
 pub struct System;
 
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<System>();
+    assert_send_sync::<SystemAlloc<u8>>();
+};
+
 impl System {
     /// Construct a new system allocator.
     #[inline]
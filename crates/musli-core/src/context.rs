@@ -38,6 +38,178 @@ pub trait Context: Copy {
     /// Access the underlying allocator.
     fn alloc(self) -> Self::Allocator;
 
+    /// Test if the context is only used to validate the input.
+    ///
+    /// When this returns `true`, the decoded value will be discarded, so
+    /// implementations of [`Decode`] for containers such as `String` and `Vec`
+    /// are free to avoid retaining what they decode. They must still
+    /// decode their contents in full so that the same inputs are accepted and
+    /// rejected as when validation is not enabled.
+    ///
+    /// Since the values produced are empty, anything which inspects them while
+    /// decoding has to use [`Context::suspend_validating`] to decode them in
+    /// full.
+    ///
+    /// This defaults to `false`.
+    ///
+    /// [`Decode`]: crate::Decode
+    #[inline]
+    fn is_validating(self) -> bool {
+        false
+    }
+
+    /// Suspend validation, causing [`Context::is_validating`] to return
+    /// `false` until a matching call to [`Context::resume_validating`].
+    ///
+    /// This is used when a decoded value has to be inspected in full even when
+    /// validating, such as the keys of maps which reject duplicate keys or the
+    /// representation of a type which is converted with `TryFrom`.
+    ///
+    /// This defaults to doing nothing.
+    #[inline]
+    fn suspend_validating(self) {}
+
+    /// Resume validation suspended through [`Context::suspend_validating`].
+    #[inline]
+    fn resume_validating(self) {}
+
+    /// Test if the context deduplicates shared allocations.
+    ///
+    /// When this returns `true`, wrappers such as `musli::compat::Shared` will
+    /// encode each shared allocation once and refer back to it by index on
+    /// subsequent occurrences. The same setting must be used when encoding and
+    /// decoding.
+    ///
+    /// This defaults to `false`.
+    #[inline]
+    fn is_sharing(self) -> bool {
+        false
+    }
+
+    /// Look up the index of a shared allocation which has already been
+    /// encoded.
+    ///
+    /// Indexes are assigned in the order in which allocations are registered
+    /// through [`Context::insert_shared_ptr`].
+    ///
+    /// This defaults to returning `None`.
+    #[inline]
+    fn shared_index(self, ptr: *const ()) -> Option<usize> {
+        _ = ptr;
+        None
+    }
+
+    /// Register a shared allocation which has been encoded.
+    ///
+    /// The pointer is only used for its address and is never dereferenced.
+    #[inline]
+    fn insert_shared_ptr(self, ptr: *const ()) {
+        _ = ptr;
+    }
+
+    /// Get a clone of a shared value which has already been decoded.
+    ///
+    /// This returns `None` if the index is unknown or if the stored value is
+    /// not of type `T`.
+    ///
+    /// This defaults to returning `None`.
+    #[inline]
+    fn shared_value<T>(self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        _ = index;
+        None
+    }
+
+    /// Register a shared value which has been decoded, assigning it the next
+    /// available index.
+    #[inline]
+    fn insert_shared_value<T>(self, value: T)
+    where
+        T: 'static + Clone,
+    {
+        _ = value;
+    }
+
+    /// Test if the context interns decoded strings.
+    ///
+    /// When this returns `true`, implementations of [`Decode`] for shared
+    /// string pointers such as `Arc<str>` look up previously decoded strings
+    /// with equal contents through [`Context::interned`] and return a clone of
+    /// them instead of allocating.
+    ///
+    /// This defaults to `false`.
+    ///
+    /// [`Decode`]: crate::Decode
+    #[inline]
+    fn is_interning(self) -> bool {
+        false
+    }
+
+    /// Get a clone of an interned value for the given string.
+    ///
+    /// This returns `None` if the string hasn't been interned or if the stored
+    /// value is not of type `T`.
+    ///
+    /// This defaults to returning `None`.
+    #[inline]
+    fn interned<T>(self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        _ = string;
+        None
+    }
+
+    /// Intern a value which has been decoded from the given string.
+    ///
+    /// The context is free to ignore the value, such as when it has reached
+    /// the maximum number of interned strings.
+    #[inline]
+    fn insert_interned<T>(self, string: &str, value: T)
+    where
+        T: 'static + Clone,
+    {
+        _ = string;
+        _ = value;
+    }
+
+    /// Get a clone of user data of type `T` associated with the context.
+    ///
+    /// This allows applications to thread configuration through encoding and
+    /// decoding, such as to functions used with `#[musli(default_with_cx =
+    /// ..)]`. See [`musli::context::WithUserData`] for a context which carries
+    /// user data.
+    ///
+    /// This defaults to returning `None`.
+    ///
+    /// [`musli::context::WithUserData`]: https://docs.rs/musli/latest/musli/context/struct.WithUserData.html
+    #[inline]
+    fn user_data<T>(self) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        None
+    }
+
+    /// Enter a value which is decoded recursively, such as the value behind a
+    /// `Box<T>`.
+    ///
+    /// This returns an error if doing so would exceed the maximum depth
+    /// configured for the context. Every successful call must be matched by a
+    /// call to [`Context::leave_depth`].
+    ///
+    /// This defaults to not limiting the depth.
+    #[inline]
+    fn enter_depth(self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    /// Leave a value entered through [`Context::enter_depth`].
+    #[inline]
+    fn leave_depth(self) {}
+
     /// Generate a map function which maps an error using the `custom` function.
     #[inline]
     fn map<E>(self) -> impl FnOnce(E) -> Self::Error
@@ -261,4 +433,26 @@ pub trait Context: Copy {
     /// [`enter_sequence_index`]: Context::enter_sequence_index
     #[inline]
     fn leave_sequence_index(self) {}
+
+    /// Annotate errors reported by this context with caller-supplied
+    /// diagnostic metadata, such as the identifier of a record being
+    /// processed.
+    ///
+    /// Annotating with a `key` that has already been used replaces its value.
+    /// Every error reported afterwards keeps a copy of the annotations which
+    /// were active when it was reported. Annotations are cleared together with
+    /// other state in [`clear`].
+    ///
+    /// Annotations are only kept by contexts which trace errors, other
+    /// contexts ignore them.
+    ///
+    /// [`clear`]: Context::clear
+    #[inline]
+    fn annotate<V>(self, key: &'static str, value: V)
+    where
+        V: fmt::Display,
+    {
+        _ = key;
+        _ = value;
+    }
 }
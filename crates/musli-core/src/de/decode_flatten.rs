@@ -0,0 +1,32 @@
+use crate::Allocator;
+
+use super::{Decode, Decoder};
+
+/// Trait governing how a field marked with `#[musli(flatten)]` captures the
+/// entries of a struct which do not correspond to any of its other fields.
+///
+/// This is implemented for maps such as [`HashMap<K, V>`] and
+/// [`BTreeMap<K, V>`], where unknown entries are inserted into the map.
+///
+/// [`HashMap<K, V>`]: std::collections::HashMap
+/// [`BTreeMap<K, V>`]: rust_alloc::collections::BTreeMap
+pub trait DecodeFlatten<'de, M, A>
+where
+    Self: Sized,
+    A: Allocator,
+{
+    /// The key of captured entries.
+    ///
+    /// The names of fields are compared against decoded keys, so this must be
+    /// comparable to them. Fields which are named by string are for example
+    /// compatible with a `String` key.
+    type Key: Decode<'de, M, A>;
+
+    /// Construct an empty collection of entries.
+    fn empty() -> Self;
+
+    /// Decode the value of an entry with the given key and capture it.
+    fn decode_entry<D>(&mut self, key: Self::Key, decoder: D) -> Result<(), D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>;
+}
@@ -3,7 +3,7 @@
 use core::fmt;
 
 use crate::expecting::{self, Expecting};
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::{Allocator, Context};
 
 use super::{
@@ -69,6 +69,18 @@ pub trait Decoder<'de>: Sized {
     #[doc(hidden)]
     type __UseMusliDecoderAttributeMacro;
 
+    /// The capabilities which every instance of this decoder is known to
+    /// support, regardless of how it has been configured.
+    ///
+    /// Unlike [`Decoder::capabilities`] this can be inspected at compile time,
+    /// which is used by types marked with `#[musli(requires(..))]` to reject
+    /// decoders which lack something they depend on with a compile error
+    /// instead of an error while decoding.
+    ///
+    /// This defaults to [`Capabilities::EMPTY`], so decoders which implement
+    /// [`Decoder::capabilities`] should typically define this as well.
+    const CAPABILITIES: Capabilities = Capabilities::EMPTY;
+
     /// Access the context associated with the decoder.
     fn cx(&self) -> Self::Cx;
 
@@ -128,6 +140,45 @@ pub trait Decoder<'de>: Sized {
     /// ```
     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Return the capabilities of the format being decoded.
+    ///
+    /// This permits generic [`Decode`] implementations to adapt their behavior
+    /// to the format, such as decoding a human-friendly representation when
+    /// the format [is human-readable].
+    ///
+    /// The default implementation returns [`Capabilities::EMPTY`].
+    ///
+    /// [is human-readable]: Capabilities::is_human_readable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Allocator, Decode, Decoder};
+    ///
+    /// struct Flag(bool);
+    ///
+    /// impl<'de, M, A> Decode<'de, M, A> for Flag
+    /// where
+    ///     A: Allocator,
+    /// {
+    ///     #[inline]
+    ///     fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    ///     where
+    ///         D: Decoder<'de>,
+    ///     {
+    ///         if decoder.capabilities().is_human_readable() {
+    ///             decoder.decode_unsized(|s: &str| Ok(Flag(s == "yes")))
+    ///         } else {
+    ///             Ok(Flag(decoder.decode_bool()?))
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::EMPTY
+    }
+
     /// Try to quickly decode the specified value.
     ///
     /// The default implementation simply returns the current decoder as
@@ -1644,6 +1695,50 @@ pub trait Decoder<'de>: Sized {
         )))
     }
 
+    /// Decode a value with a semantic tag attached to it, returning the tag
+    /// and a decoder for the value.
+    ///
+    /// See the [`semantic`] module for well-known tags. Formats which don't
+    /// support tags error.
+    ///
+    /// [`semantic`]: crate::semantic
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Allocator, Context, Decode, Decoder};
+    /// use musli::compat::Bytes;
+    /// use musli::mode::Binary;
+    /// use musli::semantic;
+    ///
+    /// struct Uuid([u8; 16]);
+    ///
+    /// impl<'de, A> Decode<'de, Binary, A> for Uuid
+    /// where
+    ///     A: Allocator,
+    /// {
+    ///     #[inline]
+    ///     fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    ///     where
+    ///         D: Decoder<'de, Mode = Binary, Allocator = A>,
+    ///     {
+    ///         let cx = decoder.cx();
+    ///
+    ///         match decoder.decode_tagged()? {
+    ///             (semantic::UUID, decoder) => Ok(Uuid(decoder.decode::<Bytes<_>>()?.0)),
+    ///             (tag, _) => Err(cx.message(format_args!("Expected a UUID, got tag {tag}"))),
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn decode_tagged(self) -> Result<(u32, Self), <Self::Cx as Context>::Error> {
+        Err(self.cx().message(expecting::unsupported_type(
+            &expecting::Tagged,
+            ExpectingWrapper::new(&self),
+        )))
+    }
+
     /// Decode an unknown number using a visitor.
     #[inline]
     fn decode_number<V>(self, visitor: V) -> Result<V::Ok, <Self::Cx as Context>::Error>
@@ -1657,6 +1752,10 @@ pub trait Decoder<'de>: Sized {
     }
 
     /// Decode dynamically through a [`Visitor`].
+    ///
+    /// This is supported by self-describing formats, which call the callback on
+    /// the visitor matching the type of the value that is encountered. See
+    /// [`Visitor`] for how to use this to decode a custom dynamic value.
     #[inline]
     fn decode_any<V>(self, visitor: V) -> Result<V::Ok, <Self::Cx as Context>::Error>
     where
@@ -1667,6 +1766,26 @@ pub trait Decoder<'de>: Sized {
             ExpectingWrapper::new(&self).format()
         )))
     }
+
+    /// Decode the next value as the exact bytes it is encoded as, without
+    /// decoding the value itself.
+    ///
+    /// Formats which support this check that the value is well-formed, so that
+    /// the bytes can be written back out through [`Encoder::encode_raw`] using
+    /// the same format. This is used by types such as `musli::json::RawValue`
+    /// to defer decoding parts of a document.
+    ///
+    /// [`Encoder::encode_raw`]: crate::Encoder::encode_raw
+    #[inline]
+    fn decode_raw<V>(self, visitor: V) -> Result<V::Ok, <Self::Cx as Context>::Error>
+    where
+        V: UnsizedVisitor<'de, Self::Cx, [u8]>,
+    {
+        Err(self.cx().message(format_args!(
+            "Raw values are not supported, expected {}",
+            ExpectingWrapper::new(&self).format()
+        )))
+    }
 }
 
 #[repr(transparent)]
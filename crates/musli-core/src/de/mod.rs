@@ -36,6 +36,9 @@ pub use self::decode_slice_builder::DecodeSliceBuilder;
 mod decode_bytes;
 pub use self::decode_bytes::DecodeBytes;
 
+mod decode_flatten;
+pub use self::decode_flatten::DecodeFlatten;
+
 mod decode_packed;
 pub use self::decode_packed::DecodePacked;
 
@@ -11,6 +11,125 @@ use super::{Decoder, MapDecoder, SequenceDecoder, SizeHint, UnsizedVisitor, Vari
 /// Each callback on this visitor indicates the type that should be decoded from
 /// the passed in decoder. A typical implementation would simply call the
 /// corresponding decoder function for the type being visited.
+///
+/// A visitor is driven by [`Decoder::decode_any`], which lets a self-describing
+/// format report what it contains. This is the extension point to use when
+/// implementing your own dynamic value type, like [`musli::value::Value`] does
+/// internally. Any callback which isn't implemented produces an error
+/// describing what was found and what the visitor was [`expecting`].
+///
+/// Some things to keep in mind:
+/// * Formats report numbers using the type they were encoded with, or for
+///   text-based formats like JSON the smallest type that fits. So a visitor
+///   which wants a single integer representation should forward all widths to
+///   it.
+/// * Nested values are decoded by calling [`Decoder::decode_any`] recursively
+///   on the decoders provided by [`SequenceDecoder::try_decode_next`] and
+///   [`EntryDecoder::decode_value`].
+/// * Implementations must use the [`#[musli::visitor]`][crate::visitor]
+///   attribute macro, which fills in the [`Visitor::String`] and
+///   [`Visitor::Bytes`] types if they are not used.
+///
+/// [`expecting`]: Visitor::expecting
+/// [`EntryDecoder::decode_value`]: super::EntryDecoder::decode_value
+/// [`musli::value::Value`]: https://docs.rs/musli/latest/musli/value/enum.Value.html
+///
+/// # Examples
+///
+/// Decoding into a dynamic value of your own:
+///
+/// ```
+/// use std::fmt;
+///
+/// use musli::{Allocator, Context, Decode, Decoder};
+/// use musli::de::{SequenceDecoder, SizeHint, UnsizedVisitor, Visitor};
+///
+/// trait DynValue: fmt::Debug {}
+///
+/// impl DynValue for u64 {}
+/// impl DynValue for String {}
+/// impl DynValue for Vec<Dyn> {}
+///
+/// #[derive(Debug)]
+/// struct Dyn(Box<dyn DynValue>);
+///
+/// impl<'de, M, A> Decode<'de, M, A> for Dyn
+/// where
+///     A: Allocator,
+/// {
+///     #[inline]
+///     fn decode<D>(decoder: D) -> Result<Self, D::Error>
+///     where
+///         D: Decoder<'de, Mode = M, Allocator = A>,
+///     {
+///         decoder.decode_any(DynVisitor)
+///     }
+/// }
+///
+/// struct DynVisitor;
+///
+/// #[musli::visitor]
+/// impl<'de, C> Visitor<'de, C> for DynVisitor
+/// where
+///     C: Context,
+/// {
+///     type Ok = Dyn;
+///     type String = StringVisitor;
+///
+///     #[inline]
+///     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "a number, string or sequence")
+///     }
+///
+///     #[inline]
+///     fn visit_u8(self, cx: C, value: u8) -> Result<Self::Ok, C::Error> {
+///         self.visit_u64(cx, value.into())
+///     }
+///
+///     #[inline]
+///     fn visit_u64(self, _: C, value: u64) -> Result<Self::Ok, C::Error> {
+///         Ok(Dyn(Box::new(value)))
+///     }
+///
+///     #[inline]
+///     fn visit_sequence<D>(self, seq: &mut D) -> Result<Self::Ok, C::Error>
+///     where
+///         D: ?Sized + SequenceDecoder<'de, Cx = C>,
+///     {
+///         let mut items = Vec::new();
+///
+///         while let Some(item) = seq.try_decode_next()? {
+///             items.push(item.decode_any(DynVisitor)?);
+///         }
+///
+///         Ok(Dyn(Box::new(items)))
+///     }
+///
+///     #[inline]
+///     fn visit_string(self, _: C, _: SizeHint) -> Result<Self::String, C::Error> {
+///         Ok(StringVisitor)
+///     }
+/// }
+///
+/// struct StringVisitor;
+///
+/// impl<C> UnsizedVisitor<'_, C, str> for StringVisitor
+/// where
+///     C: Context,
+/// {
+///     type Ok = Dyn;
+///
+///     #[inline]
+///     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+///         write!(f, "a string")
+///     }
+///
+///     #[inline]
+///     fn visit_ref(self, _: C, string: &str) -> Result<Self::Ok, C::Error> {
+///         Ok(Dyn(Box::new(string.to_owned())))
+///     }
+/// }
+/// ```
 pub trait Visitor<'de, C>
 where
     Self: Sized,
@@ -252,6 +371,18 @@ where
         )))
     }
 
+    /// Indicates that the visited value has a semantic tag attached to it.
+    ///
+    /// By default the tag is ignored and the value is visited as if it didn't
+    /// have one.
+    #[inline]
+    fn visit_tagged<D>(self, _: C, _: u32, decoder: D) -> Result<Self::Ok, C::Error>
+    where
+        D: Decoder<'de, Cx = C, Error = C::Error, Allocator = C::Allocator>,
+    {
+        decoder.decode_any(self)
+    }
+
     /// Indicates that the encoding does not support dynamic types.
     #[inline]
     fn visit_unknown<D>(self, decoder: D) -> Result<Self::Ok, D::Error>
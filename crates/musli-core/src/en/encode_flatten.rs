@@ -0,0 +1,21 @@
+use crate::Context;
+
+use super::MapEncoder;
+
+/// Trait governing how a field marked with `#[musli(flatten)]` emits its
+/// entries alongside the other fields of a struct.
+///
+/// This is implemented for maps such as [`HashMap<K, V>`] and
+/// [`BTreeMap<K, V>`].
+///
+/// [`HashMap<K, V>`]: std::collections::HashMap
+/// [`BTreeMap<K, V>`]: rust_alloc::collections::BTreeMap
+pub trait EncodeFlatten<M> {
+    /// The number of entries that will be encoded.
+    fn size(&self) -> usize;
+
+    /// Encode the entries into the map of the containing struct.
+    fn encode_entries<E>(&self, encoder: &mut E) -> Result<(), <E::Cx as Context>::Error>
+    where
+        E: MapEncoder<Mode = M>;
+}
@@ -3,7 +3,7 @@
 use core::fmt;
 
 use crate::expecting::{self, Expecting};
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::Context;
 
 use super::{utils, Encode, EntriesEncoder, MapEncoder, SequenceEncoder, VariantEncoder};
@@ -78,6 +78,52 @@ pub trait Encoder: Sized {
     /// report that something unexpected happened.
     fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result;
 
+    /// Return the capabilities of the format being encoded.
+    ///
+    /// This permits generic [`Encode`] implementations to adapt their behavior
+    /// to the format, such as encoding a human-friendly representation when
+    /// the format [is human-readable].
+    ///
+    /// Capabilities which only concern decoding, such as
+    /// [`Capabilities::CAN_BORROW`], are not reported by encoders.
+    ///
+    /// The default implementation returns [`Capabilities::EMPTY`].
+    ///
+    /// [is human-readable]: Capabilities::is_human_readable
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    ///
+    /// struct Flag(bool);
+    ///
+    /// impl<M> Encode<M> for Flag {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    ///     where
+    ///         E: Encoder,
+    ///     {
+    ///         if encoder.capabilities().is_human_readable() {
+    ///             encoder.encode_string(if self.0 { "yes" } else { "no" })
+    ///         } else {
+    ///             encoder.encode_bool(self.0)
+    ///         }
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::EMPTY
+    }
+
     /// Try to quickly encode the specified value.
     ///
     /// The default implementation simply returns the current encoder as
@@ -1262,6 +1308,52 @@ pub trait Encoder: Sized {
         )))
     }
 
+    /// Encode a value with a semantic tag attached to it.
+    ///
+    /// The tag tells generic tooling what the value means, see the
+    /// [`semantic`] module for well-known tags. Formats which don't support
+    /// tags error.
+    ///
+    /// [`semantic`]: crate::semantic
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Encode, Encoder};
+    /// use musli::compat::Bytes;
+    /// use musli::mode::Binary;
+    /// use musli::semantic;
+    ///
+    /// struct Uuid([u8; 16]);
+    ///
+    /// impl Encode<Binary> for Uuid {
+    ///     type Encode = Self;
+    ///
+    ///     #[inline]
+    ///     fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    ///     where
+    ///         E: Encoder<Mode = Binary>,
+    ///     {
+    ///         encoder.encode_tagged(semantic::UUID, Bytes(self.0))
+    ///     }
+    ///
+    ///     #[inline]
+    ///     fn as_encode(&self) -> &Self::Encode {
+    ///         self
+    ///     }
+    /// }
+    /// ```
+    #[inline]
+    fn encode_tagged<T>(self, _: u32, _: T) -> Result<Self::Ok, <Self::Cx as Context>::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        Err(self.cx().message(expecting::unsupported_type(
+            &expecting::Tagged,
+            ExpectingWrapper::new(&self),
+        )))
+    }
+
     /// Construct a pack that can encode more than one element at a time.
     ///
     /// This hints to the format that it should attempt to encode all of the
@@ -2143,6 +2235,21 @@ pub trait Encoder: Sized {
             ExpectingWrapper::new(&self),
         )))
     }
+
+    /// Encode bytes which are already encoded in the current format as they
+    /// are, such as bytes captured through [`Decoder::decode_raw`].
+    ///
+    /// The bytes are written verbatim, so the caller is responsible for them
+    /// being a single well-formed value in the format.
+    ///
+    /// [`Decoder::decode_raw`]: crate::Decoder::decode_raw
+    #[inline]
+    fn encode_raw(self, bytes: &[u8]) -> Result<Self::Ok, <Self::Cx as Context>::Error> {
+        Err(self.cx().message(format_args!(
+            "Raw values are not supported, expected {}",
+            ExpectingWrapper::new(&self).format()
+        )))
+    }
 }
 
 #[repr(transparent)]
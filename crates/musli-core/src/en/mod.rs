@@ -30,6 +30,9 @@ pub use self::encode::Encode;
 mod encode_bytes;
 pub use self::encode_bytes::EncodeBytes;
 
+mod encode_flatten;
+pub use self::encode_flatten::EncodeFlatten;
+
 mod encode_packed;
 pub use self::encode_packed::EncodePacked;
 
@@ -145,5 +145,6 @@ expect! {
     pub(crate) UnsizedSequence("unsized sequence");
     pub(crate) SequenceVariant("sequence variant");
     pub(crate) Variant("variant");
+    pub(crate) Tagged("tagged value");
     pub(crate) AnyValue("a value");
 }
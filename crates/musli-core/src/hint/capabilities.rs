@@ -0,0 +1,126 @@
+use core::fmt;
+use core::ops::{BitOr, BitOrAssign};
+
+/// The set of features supported by a format.
+///
+/// This is returned by [`Decoder::capabilities`] and
+/// [`Encoder::capabilities`] and permits generic code to adapt its behavior to
+/// the format being used, such as picking a human-friendly representation for
+/// text formats.
+///
+/// [`Decoder::capabilities`]: crate::Decoder::capabilities
+/// [`Encoder::capabilities`]: crate::Encoder::capabilities
+///
+/// # Examples
+///
+/// ```
+/// use musli::hint::Capabilities;
+///
+/// const TEXT: Capabilities = Capabilities::SELF_DESCRIBING.with(Capabilities::HUMAN_READABLE);
+///
+/// assert!(TEXT.is_self_describing());
+/// assert!(TEXT.is_human_readable());
+/// assert!(!TEXT.can_borrow());
+/// assert!(TEXT.contains(Capabilities::SELF_DESCRIBING));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Capabilities {
+    bits: u8,
+}
+
+impl Capabilities {
+    /// No capabilities.
+    pub const EMPTY: Self = Self { bits: 0 };
+
+    /// The format describes the types of the values it contains, so that they
+    /// can be decoded without knowing what type to expect.
+    pub const SELF_DESCRIBING: Self = Self { bits: 1 << 0 };
+
+    /// The format can provide values borrowed from the input, given that the
+    /// input itself supports it.
+    pub const CAN_BORROW: Self = Self { bits: 1 << 1 };
+
+    /// The format can skip over values it doesn't know about, such as unknown
+    /// fields.
+    pub const CAN_SKIP: Self = Self { bits: 1 << 2 };
+
+    /// The format is intended to be read by humans.
+    pub const HUMAN_READABLE: Self = Self { bits: 1 << 3 };
+
+    /// The decoder has been configured to reject maps which contain the same
+    /// key more than once, instead of letting later entries replace earlier
+    /// ones.
+    pub const REJECT_DUPLICATE_KEYS: Self = Self { bits: 1 << 4 };
+
+    /// Combine this set of capabilities with another.
+    #[inline]
+    pub const fn with(self, other: Self) -> Self {
+        Self {
+            bits: self.bits | other.bits,
+        }
+    }
+
+    /// Test if this set contains all the capabilities in `other`.
+    #[inline]
+    pub const fn contains(self, other: Self) -> bool {
+        self.bits & other.bits == other.bits
+    }
+
+    /// Test if the format is self-describing.
+    #[inline]
+    pub const fn is_self_describing(self) -> bool {
+        self.contains(Self::SELF_DESCRIBING)
+    }
+
+    /// Test if the format can provide borrowed values.
+    #[inline]
+    pub const fn can_borrow(self) -> bool {
+        self.contains(Self::CAN_BORROW)
+    }
+
+    /// Test if the format can skip over unknown values.
+    #[inline]
+    pub const fn can_skip(self) -> bool {
+        self.contains(Self::CAN_SKIP)
+    }
+
+    /// Test if the format is human-readable.
+    #[inline]
+    pub const fn is_human_readable(self) -> bool {
+        self.contains(Self::HUMAN_READABLE)
+    }
+
+    /// Test if maps with duplicate keys should be rejected.
+    #[inline]
+    pub const fn rejects_duplicate_keys(self) -> bool {
+        self.contains(Self::REJECT_DUPLICATE_KEYS)
+    }
+}
+
+impl BitOr for Capabilities {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.with(rhs)
+    }
+}
+
+impl BitOrAssign for Capabilities {
+    #[inline]
+    fn bitor_assign(&mut self, rhs: Self) {
+        *self = self.with(rhs);
+    }
+}
+
+impl fmt::Debug for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Capabilities")
+            .field("self_describing", &self.is_self_describing())
+            .field("can_borrow", &self.can_borrow())
+            .field("can_skip", &self.can_skip())
+            .field("human_readable", &self.is_human_readable())
+            .field("reject_duplicate_keys", &self.rejects_duplicate_keys())
+            .finish()
+    }
+}
@@ -2,6 +2,9 @@
 //!
 //! These are passed when encoding or decoding different types.
 
+mod capabilities;
+pub use self::capabilities::Capabilities;
+
 mod map_hint;
 pub use self::map_hint::MapHint;
 
@@ -1,7 +1,9 @@
 use core::ffi::CStr;
 use core::fmt;
-#[cfg(feature = "std")]
+#[cfg(any(feature = "std", feature = "indexmap"))]
 use core::hash::{BuildHasher, Hash};
+use core::mem::{self, MaybeUninit};
+use core::ptr;
 
 use rust_alloc::borrow::Cow;
 use rust_alloc::boxed::Box;
@@ -12,6 +14,8 @@ use rust_alloc::string::String;
 use rust_alloc::sync::Arc;
 use rust_alloc::vec::Vec;
 
+#[cfg(feature = "indexmap")]
+use indexmap::{IndexMap, IndexSet};
 #[cfg(feature = "std")]
 use std::collections::{HashMap, HashSet};
 #[cfg(all(feature = "std", any(unix, windows)))]
@@ -21,17 +25,18 @@ use std::path::{Path, PathBuf};
 
 use crate::alloc::ToOwned;
 use crate::de::{
-    Decode, DecodeBytes, DecodeTrace, Decoder, EntryDecoder, MapDecoder, SequenceDecoder,
-    UnsizedVisitor,
+    Decode, DecodeBytes, DecodeFlatten, DecodeTrace, Decoder, EntryDecoder, MapDecoder,
+    SequenceDecoder, UnsizedVisitor,
 };
 use crate::en::{
-    Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntryEncoder, MapEncoder,
-    SequenceEncoder,
+    Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntryEncoder,
+    MapEncoder, SequenceEncoder,
 };
 use crate::hint::{MapHint, SequenceHint};
 use crate::internal::size_hint;
 use crate::{Allocator, Context};
 
+use super::check_cstr;
 #[cfg(all(feature = "std", any(unix, windows)))]
 use super::PlatformTag;
 
@@ -84,8 +89,13 @@ where
             }
 
             #[inline]
-            fn visit_ref(self, _: C, string: &str) -> Result<Self::Ok, C::Error> {
+            fn visit_ref(self, cx: C, string: &str) -> Result<Self::Ok, C::Error> {
                 use rust_alloc::borrow::ToOwned;
+
+                if cx.is_validating() {
+                    return Ok(String::new());
+                }
+
                 Ok(string.to_owned())
             }
         }
@@ -125,6 +135,148 @@ where
     }
 }
 
+/// Guard which drops the initialized prefix of a partially decoded slice in
+/// case decoding fails or panics.
+struct InitGuard<'a, T> {
+    slice: &'a mut [MaybeUninit<T>],
+    init: usize,
+}
+
+impl<T> Drop for InitGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: The first `init` elements have been initialized.
+        unsafe {
+            let init = ptr::addr_of_mut!(self.slice[..self.init]);
+            ptr::drop_in_place(init as *mut [T]);
+        }
+    }
+}
+
+macro_rules! shared_slice {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            impl<'de, M, A> Decode<'de, M, A> for $ty<str>
+            where
+                A: Allocator,
+            {
+                const IS_BITWISE_DECODE: bool = false;
+
+                #[inline]
+                fn decode<D>(decoder: D) -> Result<Self, D::Error>
+                where
+                    D: Decoder<'de, Mode = M, Allocator = A>,
+                {
+                    struct Visitor;
+
+                    impl<C> UnsizedVisitor<'_, C, str> for Visitor
+                    where
+                        C: Context,
+                    {
+                        type Ok = $ty<str>;
+
+                        #[inline]
+                        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                            write!(f, "string")
+                        }
+
+                        #[inline]
+                        fn visit_ref(self, cx: C, string: &str) -> Result<Self::Ok, C::Error> {
+                            if !cx.is_interning() {
+                                return Ok($ty::from(string));
+                            }
+
+                            if let Some(value) = cx.interned::<$ty<str>>(string) {
+                                return Ok(value);
+                            }
+
+                            let value = $ty::<str>::from(string);
+                            cx.insert_interned(string, value.clone());
+                            Ok(value)
+                        }
+                    }
+
+                    decoder.decode_string(Visitor)
+                }
+            }
+
+            /// If the decoder provides an exact length up front, the storage of
+            /// the slice is allocated once and values are decoded in place.
+            impl<'de, M, A, T> Decode<'de, M, A> for $ty<[T]>
+            where
+                A: Allocator,
+                T: Decode<'de, M, A>,
+            {
+                const IS_BITWISE_DECODE: bool = false;
+
+                #[inline]
+                fn decode<D>(decoder: D) -> Result<Self, D::Error>
+                where
+                    D: Decoder<'de, Mode = M, Allocator = A>,
+                {
+                    let cx = decoder.cx();
+
+                    decoder.decode_sequence(|seq| {
+                        let Some(len) = size_hint::cautious_exact(seq.size_hint()) else {
+                            let mut out = Vec::with_capacity(size_hint::cautious(seq.size_hint()));
+                            let mut index = 0;
+
+                            while let Some(value) = seq.try_decode_next()? {
+                                cx.enter_sequence_index(index);
+                                out.push(value.decode()?);
+                                cx.leave_sequence_index();
+                                index = index.wrapping_add(1);
+                            }
+
+                            return Ok($ty::from(out));
+                        };
+
+                        // NB: The iterator has a trusted length, so this
+                        // allocates the exact storage needed once.
+                        let mut out = (0..len)
+                            .map(|_| MaybeUninit::<T>::uninit())
+                            .collect::<$ty<[MaybeUninit<T>]>>();
+
+                        let Some(slice) = $ty::get_mut(&mut out) else {
+                            unreachable!("newly allocated pointer is not unique");
+                        };
+
+                        let mut guard = InitGuard { slice, init: 0 };
+
+                        while let Some(value) = seq.try_decode_next()? {
+                            let Some(slot) = guard.slice.get_mut(guard.init) else {
+                                return Err(cx.message(format_args!(
+                                    "Sequence has more than the expected {len} items"
+                                )));
+                            };
+
+                            cx.enter_sequence_index(guard.init);
+                            slot.write(value.decode()?);
+                            cx.leave_sequence_index();
+                            guard.init += 1;
+                        }
+
+                        if guard.init != len {
+                            return Err(cx.message(format_args!(
+                                "Sequence has {} items, but expected {len}",
+                                guard.init
+                            )));
+                        }
+
+                        mem::forget(guard);
+
+                        // SAFETY: Every element has been initialized above, and
+                        // `MaybeUninit<T>` has the same layout as `T`.
+                        Ok(unsafe { $ty::from_raw($ty::into_raw(out) as *const [T]) })
+                    })
+                }
+            }
+        )*
+    };
+}
+
+shared_slice!(Arc, Rc);
+
 macro_rules! cow {
     (
         $encode:ident :: $encode_fn:ident,
@@ -238,6 +390,8 @@ cow! {
     IS_BITWISE_DECODE,
     CStr, [u8], decode_bytes, cx,
     |owned| {
+        check_cstr(cx, &owned)?;
+
         match owned.into_std() {
             Ok(owned) => Cow::Owned(CString::from_vec_with_nul(owned).map_err(cx.map())?),
             Err(reference) => {
@@ -246,8 +400,12 @@ cow! {
             }
         }
     },
-    |borrowed| Cow::Borrowed(CStr::from_bytes_with_nul(borrowed).map_err(cx.map())?),
+    |borrowed| {
+        check_cstr(cx, borrowed)?;
+        Cow::Borrowed(CStr::from_bytes_with_nul(borrowed).map_err(cx.map())?)
+    },
     |reference| {
+        check_cstr(cx, reference)?;
         let value = CStr::from_bytes_with_nul(reference).map_err(cx.map())?;
         Cow::Owned(rust_alloc::borrow::ToOwned::to_owned(value))
     }
@@ -341,7 +499,12 @@ macro_rules! sequence {
 
                     while let Some(value) = $access.try_decode_next()? {
                         $cx.enter_sequence_index(index);
-                        out.$insert(value.decode()?);
+                        let value = value.decode()?;
+
+                        if !$cx.is_validating() {
+                            out.$insert(value);
+                        }
+
                         $cx.leave_sequence_index();
                         index = index.wrapping_add(1);
                     }
@@ -425,6 +588,8 @@ where
     where
         D: Decoder<'de, Mode = M, Allocator = A>,
     {
+        // Elements are encoded front-to-back, so converting from a `Vec`
+        // preserves the logical order of the deque.
         Ok(VecDeque::from(Vec::decode(decoder)?))
     }
 }
@@ -460,9 +625,22 @@ sequence! {
     HashSet<T: Eq + Hash, S: BuildHasher + Default>,
     insert,
     seq,
-    HashSet::with_capacity_and_hasher(size_hint::cautious(seq.size_hint()), S::default())
+    HashSet::with_capacity_and_hasher(size_hint::cautious_of::<T, _>(seq.size_hint()), S::default())
+}
+
+sequence! {
+    #[cfg(feature = "indexmap")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "indexmap")))]
+    cx,
+    IndexSet<T: Eq + Hash, S: BuildHasher + Default>,
+    insert,
+    seq,
+    IndexSet::with_capacity_and_hasher(size_hint::cautious_of::<T, _>(seq.size_hint()), S::default())
 }
 
+// A heap is encoded in its internal order, which is unspecified. Decoding
+// pushes every element, which re-establishes the heap invariant no matter
+// which order the elements were encoded in.
 sequence! {
     cx,
     BinaryHeap<T: Ord>,
@@ -471,13 +649,39 @@ sequence! {
     BinaryHeap::with_capacity(size_hint::cautious(seq.size_hint()))
 }
 
+/// Decode and discard every entry in a map while validating, keeping track of
+/// the keys seen to reject duplicates.
+///
+/// Keys are decoded with validation suspended, since they have to be compared
+/// in full.
+macro_rules! validate_keys {
+    ($cx:ident, $access:ident, $new:expr) => {
+        let mut keys = $new;
+
+        while let Some(mut entry) = $access.decode_entry()? {
+            let key = entry.decode_key()?;
+            $cx.suspend_validating();
+            let key = key.decode::<K>();
+            $cx.resume_validating();
+            let key = key?;
+
+            entry.decode_value()?.decode::<V>()?;
+
+            if keys.insert(key, ()).is_some() {
+                return Err($cx.message("Duplicate key in map"));
+            }
+        }
+    };
+}
+
 macro_rules! map {
     (
         $(#[$($meta:meta)*])*
         $cx:ident,
         $ty:ident<K $(: $key_bound0:ident $(+ $key_bound:ident)*)?, V $(, $extra:ident: $extra_bound0:ident $(+ $extra_bound:ident)*)*>,
         $access:ident,
-        $with_capacity:expr
+        $with_capacity:expr,
+        $new:expr
     ) => {
         $(#[$($meta)*])*
         impl<'de, M, K, V $(, $extra)*> Encode<M> for $ty<K, V $(, $extra)*>
@@ -559,11 +763,28 @@ macro_rules! map {
             where
                 D: Decoder<'de, Mode = M, Allocator = A>,
             {
+                let $cx = decoder.cx();
+                let reject_duplicates = decoder.capabilities().rejects_duplicate_keys();
+
+                if reject_duplicates && $cx.is_validating() {
+                    return decoder.decode_map(|$access| {
+                        validate_keys!($cx, $access, $new);
+                        Ok($new)
+                    });
+                }
+
                 decoder.decode_map(|$access| {
                     let mut out = $with_capacity;
 
+                    // NB: Unless duplicates are rejected, the value of the
+                    // last entry with a given key wins.
                     while let Some((key, value)) = $access.entry()? {
-                        out.insert(key, value);
+                        if !$cx.is_validating()
+                            && out.insert(key, value).is_some()
+                            && reject_duplicates
+                        {
+                            return Err($cx.message("Duplicate key in map"));
+                        }
                     }
 
                     Ok(out)
@@ -585,6 +806,14 @@ macro_rules! map {
                 D: Decoder<'de, Mode = M, Allocator = A>,
             {
                 let $cx = decoder.cx();
+                let reject_duplicates = decoder.capabilities().rejects_duplicate_keys();
+
+                if reject_duplicates && $cx.is_validating() {
+                    return decoder.decode_map(|$access| {
+                        validate_keys!($cx, $access, $new);
+                        Ok($new)
+                    });
+                }
 
                 decoder.decode_map(|$access| {
                     let mut out = $with_capacity;
@@ -593,7 +822,14 @@ macro_rules! map {
                         let key = entry.decode_key()?.decode()?;
                         $cx.enter_map_key(&key);
                         let value = entry.decode_value()?.decode()?;
-                        out.insert(key, value);
+
+                        if !$cx.is_validating()
+                            && out.insert(key, value).is_some()
+                            && reject_duplicates
+                        {
+                            return Err($cx.message("Duplicate key in map"));
+                        }
+
                         $cx.leave_map_key();
                     }
 
@@ -601,10 +837,78 @@ macro_rules! map {
                 })
             }
         }
+
+        $(#[$($meta)*])*
+        impl<M, K, V $(, $extra)*> EncodeFlatten<M> for $ty<K, V $(, $extra)*>
+        where
+            K: Encode<M>,
+            V: Encode<M>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            #[inline]
+            fn size(&self) -> usize {
+                self.len()
+            }
+
+            #[inline]
+            fn encode_entries<E>(&self, encoder: &mut E) -> Result<(), <E::Cx as Context>::Error>
+            where
+                E: MapEncoder<Mode = M>,
+            {
+                for (k, v) in self {
+                    encoder.insert_entry(k, v)?;
+                }
+
+                Ok(())
+            }
+        }
+
+        $(#[$($meta)*])*
+        impl<'de, K, V, A, M $(, $extra)*> DecodeFlatten<'de, M, A> for $ty<K, V $(, $extra)*>
+        where
+            A: Allocator,
+            K: Decode<'de, M, A> $(+ $key_bound0 $(+ $key_bound)*)*,
+            V: Decode<'de, M, A>,
+            $($extra: $extra_bound0 $(+ $extra_bound)*),*
+        {
+            type Key = K;
+
+            #[inline]
+            fn empty() -> Self {
+                $new
+            }
+
+            #[inline]
+            fn decode_entry<D>(&mut self, key: Self::Key, decoder: D) -> Result<(), D::Error>
+            where
+                D: Decoder<'de, Mode = M, Allocator = A>,
+            {
+                let $cx = decoder.cx();
+                let reject_duplicates = decoder.capabilities().rejects_duplicate_keys();
+                let value = decoder.decode()?;
+
+                // NB: While validating, entries are only retained if they are
+                // needed to detect duplicates.
+                if (!$cx.is_validating() || reject_duplicates)
+                    && self.insert(key, value).is_some()
+                    && reject_duplicates
+                {
+                    return Err($cx.message("Duplicate key in map"));
+                }
+
+                Ok(())
+            }
+        }
     }
 }
 
-map!(_cx, BTreeMap<K: Ord, V>, map, BTreeMap::new());
+map!(
+    _cx,
+    BTreeMap<K: Ord, V>,
+    map,
+    BTreeMap::new(),
+    BTreeMap::new()
+);
 
 map!(
     #[cfg(feature = "std")]
@@ -612,7 +916,18 @@ map!(
     _cx,
     HashMap<K: Eq + Hash, V, S: BuildHasher + Default>,
     map,
-    HashMap::with_capacity_and_hasher(size_hint::cautious(map.size_hint()), S::default())
+    HashMap::with_capacity_and_hasher(size_hint::cautious_of::<(K, V), _>(map.size_hint()), S::default()),
+    HashMap::with_hasher(S::default())
+);
+
+map!(
+    #[cfg(feature = "indexmap")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "indexmap")))]
+    _cx,
+    IndexMap<K: Eq + Hash, V, S: BuildHasher + Default>,
+    map,
+    IndexMap::with_capacity_and_hasher(size_hint::cautious_of::<(K, V), _>(map.size_hint()), S::default()),
+    IndexMap::with_hasher(S::default())
 );
 
 impl<M> Encode<M> for CString {
@@ -664,6 +979,8 @@ where
                 cx: C,
                 value: crate::alloc::Vec<u8, C::Allocator>,
             ) -> Result<Self::Ok, C::Error> {
+                check_cstr(cx, &value)?;
+
                 match value.into_std() {
                     Ok(value) => CString::from_vec_with_nul(value).map_err(cx.map()),
                     Err(value) => self.visit_ref(cx, &value),
@@ -677,6 +994,7 @@ where
 
             #[inline]
             fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                check_cstr(cx, bytes)?;
                 let value = CStr::from_bytes_with_nul(bytes).map_err(cx.map())?;
                 Ok(rust_alloc::borrow::ToOwned::to_owned(value))
             }
@@ -723,7 +1041,11 @@ macro_rules! smart_pointer {
                 where
                     D: Decoder<'de, Mode = M, Allocator = A>,
                 {
-                    Ok($ty::new(decoder.decode()?))
+                    let cx = decoder.cx();
+                    cx.enter_depth()?;
+                    let value = decoder.decode();
+                    cx.leave_depth();
+                    Ok($ty::new(value?))
                 }
             }
 
@@ -4,14 +4,20 @@ mod alloc;
 #[cfg(feature = "std")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
 mod net;
+#[cfg(feature = "ordered-float")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "ordered-float")))]
+mod ordered_float;
 mod range;
 mod tuples;
 
+use core::cell::{Cell, RefCell};
+use core::cmp::Reverse;
 use core::ffi::CStr;
 use core::num::{
     NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
-    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Wrapping,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, Saturating, Wrapping,
 };
+use core::ops::Bound;
 use core::{fmt, marker};
 
 use crate::de::{
@@ -106,6 +112,30 @@ where
 macro_rules! atomic_impl {
     ($size:literal $(, $ty:ident)*) => {
         $(
+            /// Atomics are encoded by loading their current value with
+            /// [`Ordering::Relaxed`].
+            ///
+            /// [`Ordering::Relaxed`]: core::sync::atomic::Ordering::Relaxed
+            #[cfg(target_has_atomic = $size)]
+            impl<M> Encode<M> for core::sync::atomic::$ty {
+                const IS_BITWISE_ENCODE: bool = false;
+
+                type Encode = Self;
+
+                #[inline]
+                fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+                where
+                    E: Encoder,
+                {
+                    self.load(core::sync::atomic::Ordering::Relaxed).encode(encoder)
+                }
+
+                #[inline]
+                fn as_encode(&self) -> &Self::Encode {
+                    self
+                }
+            }
+
             #[cfg(target_has_atomic = $size)]
             impl<'de, M, A> Decode<'de, M, A> for core::sync::atomic::$ty
             where
@@ -209,6 +239,24 @@ where
     }
 }
 
+/// Validate that `bytes` is a C string with exactly one trailing nul.
+///
+/// This is used across all C string decoding paths so that they report
+/// identical errors regardless of whether the input is owned or borrowed.
+#[inline]
+pub(crate) fn check_cstr<C>(cx: C, bytes: &[u8]) -> Result<(), C::Error>
+where
+    C: Context,
+{
+    match bytes.iter().position(|&b| b == 0) {
+        Some(n) if n + 1 == bytes.len() => Ok(()),
+        Some(n) => Err(cx.message(format_args!(
+            "C string contains an interior nul byte at position {n}"
+        ))),
+        None => Err(cx.message("C string is not nul terminated")),
+    }
+}
+
 impl<M, T, const N: usize> Encode<M> for [T; N]
 where
     T: Encode<M>,
@@ -788,6 +836,272 @@ where
     }
 }
 
+impl<T, M> Encode<M> for Saturating<T>
+where
+    T: Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = T::IS_BITWISE_ENCODE;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.0.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for Saturating<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = T::IS_BITWISE_DECODE;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Saturating(decoder.decode()?))
+    }
+}
+
+impl<T, M> Encode<M> for Reverse<T>
+where
+    T: Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = T::IS_BITWISE_ENCODE;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.0.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for Reverse<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = T::IS_BITWISE_DECODE;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Reverse(decoder.decode()?))
+    }
+}
+
+impl<M> Encode<M> for marker::PhantomPinned {
+    // PhantomPinned is always packed, since it is a ZST.
+    const IS_BITWISE_ENCODE: bool = true;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder,
+    {
+        encoder.encode_empty()
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for marker::PhantomPinned
+where
+    A: Allocator,
+{
+    // PhantomPinned is always packed, since it is a ZST.
+    const IS_BITWISE_DECODE: bool = true;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de>,
+    {
+        decoder.decode_empty()?;
+        Ok(marker::PhantomPinned)
+    }
+}
+
+/// A [`Cell`] is encoded by copying out its current value.
+impl<T, M> Encode<M> for Cell<T>
+where
+    T: Copy + Encode<M>,
+{
+    // The value is copied out of the cell rather than being read in place.
+    const IS_BITWISE_ENCODE: bool = false;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.get().encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for Cell<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(Cell::new(decoder.decode()?))
+    }
+}
+
+/// A [`RefCell`] is encoded by borrowing its value.
+///
+/// Encoding errors instead of panicking if the value is currently mutably
+/// borrowed.
+impl<T, M> Encode<M> for RefCell<T>
+where
+    T: ?Sized + Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = false;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let Ok(value) = self.try_borrow() else {
+            return Err(encoder.cx().message("RefCell is already mutably borrowed"));
+        };
+
+        value.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, T, A> Decode<'de, M, A> for RefCell<T>
+where
+    T: Decode<'de, M, A>,
+    A: Allocator,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(RefCell::new(decoder.decode()?))
+    }
+}
+
+#[derive(Encode, Decode)]
+#[musli(crate)]
+enum BoundTag {
+    Included,
+    Excluded,
+    Unbounded,
+}
+
+/// A [`Bound`] is encoded as a variant, where [`Bound::Unbounded`] holds an
+/// empty value.
+impl<T, M> Encode<M> for Bound<T>
+where
+    T: Encode<M>,
+    BoundTag: Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = false;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let variant = encoder.encode_variant()?;
+
+        match self {
+            Bound::Included(value) => variant.insert_variant(&BoundTag::Included, value),
+            Bound::Excluded(value) => variant.insert_variant(&BoundTag::Excluded, value),
+            Bound::Unbounded => variant.insert_variant(&BoundTag::Unbounded, ()),
+        }
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A, T> Decode<'de, M, A> for Bound<T>
+where
+    A: Allocator,
+    T: Decode<'de, M, A>,
+    BoundTag: Decode<'de, M, A>,
+{
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        decoder.decode_variant(|variant| {
+            let tag = variant.decode_tag()?.decode()?;
+
+            Ok(match tag {
+                BoundTag::Included => Bound::Included(variant.decode_value()?.decode()?),
+                BoundTag::Excluded => Bound::Excluded(variant.decode_value()?.decode()?),
+                BoundTag::Unbounded => {
+                    variant.decode_value()?.decode::<()>()?;
+                    Bound::Unbounded
+                }
+            })
+        })
+    }
+}
+
 impl<M> Encode<M> for CStr {
     const IS_BITWISE_ENCODE: bool = false;
 
@@ -820,6 +1134,7 @@ where
     {
         let cx = decoder.cx();
         let bytes = decoder.decode()?;
+        check_cstr(cx, bytes)?;
         CStr::from_bytes_with_nul(bytes).map_err(cx.map())
     }
 }
@@ -834,6 +1149,7 @@ impl<'de, M> DecodeUnsized<'de, M> for CStr {
         let cx = decoder.cx();
 
         DecodeUnsizedBytes::decode_unsized_bytes(decoder, |bytes: &[u8]| {
+            check_cstr(cx, bytes)?;
             let cstr = CStr::from_bytes_with_nul(bytes).map_err(cx.map())?;
             f(cstr)
         })
@@ -0,0 +1,91 @@
+//! Implementations for the [`ordered_float`] crate.
+//!
+//! Both [`OrderedFloat`] and [`NotNan`] are encoded as the float they wrap.
+
+use ordered_float::{FloatCore, NotNan, OrderedFloat};
+
+use crate::{Allocator, Context, Decode, Decoder, Encode, Encoder};
+
+impl<M, T> Encode<M> for OrderedFloat<T>
+where
+    T: Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = T::IS_BITWISE_ENCODE;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.0.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A, T> Decode<'de, M, A> for OrderedFloat<T>
+where
+    A: Allocator,
+    T: Decode<'de, M, A>,
+{
+    const IS_BITWISE_DECODE: bool = T::IS_BITWISE_DECODE;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        Ok(OrderedFloat(decoder.decode()?))
+    }
+}
+
+impl<M, T> Encode<M> for NotNan<T>
+where
+    T: FloatCore + Encode<M>,
+{
+    const IS_BITWISE_ENCODE: bool = T::IS_BITWISE_ENCODE;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.as_ref().encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+/// Decoding a NaN results in an error.
+impl<'de, M, A, T> Decode<'de, M, A> for NotNan<T>
+where
+    A: Allocator,
+    T: FloatCore + Decode<'de, M, A>,
+{
+    // NaN bit patterns are not valid, so this can't be decoded bitwise.
+    const IS_BITWISE_DECODE: bool = false;
+
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+        let value = decoder.decode()?;
+
+        match NotNan::new(value) {
+            Ok(value) => Ok(value),
+            Err(error) => Err(cx.message(error)),
+        }
+    }
+}
@@ -1,4 +1,7 @@
 //! Implementations for variously lengthed tuples.
+//!
+//! Tuples are always encoded as sequences, except through [`EncodePacked`] and
+//! [`DecodePacked`] where they are encoded as packs.
 
 use crate::de::{Decode, DecodePacked, Decoder, SequenceDecoder};
 use crate::en::{Encode, EncodePacked, Encoder, SequenceEncoder};
@@ -22,6 +25,22 @@ macro_rules! count {
     (_ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 14 };
     (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 15 };
     (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 16 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 17 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 18 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 19 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 20 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 21 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 22 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 23 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 24 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 25 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 26 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 27 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 28 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 29 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 30 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 31 };
+    (_ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _ _) => { 32 };
 
     (( $($s:tt)* ) $_:ident $($tail:tt)*) => {
         count!(( $($s)* _ ) $($tail)*)
@@ -36,11 +55,9 @@ macro_rules! count {
     };
 }
 
-macro_rules! declare {
-    () => {
-    };
-
-    (($ty0:ident, $ident0:ident) $(, ($ty:ident, $ident:ident))* $(,)?) => {
+macro_rules! declare_tuple {
+    ($(#[$meta:meta])* ($ty0:ident, $ident0:ident) $(, ($ty:ident, $ident:ident))* $(,)?) => {
+        $(#[$meta])*
         impl<M, $ty0 $(, $ty)*> Encode<M> for ($ty0, $($ty),*)
         where
             $ty0: Encode<M>,
@@ -73,6 +90,7 @@ macro_rules! declare {
             }
         }
 
+        $(#[$meta])*
         impl<'de, M, A, $ty0, $($ty,)*> Decode<'de, M, A> for ($ty0, $($ty),*)
         where
             A: Allocator,
@@ -98,6 +116,7 @@ macro_rules! declare {
             }
         }
 
+        $(#[$meta])*
         impl<M, $ty0 $(,$ty)*> EncodePacked<M> for ($ty0, $($ty),*)
         where
             $ty0: Encode<M>,
@@ -117,6 +136,7 @@ macro_rules! declare {
             }
         }
 
+        $(#[$meta])*
         impl<'de, M, A, $ty0, $($ty,)*> DecodePacked<'de, M, A> for ($ty0, $($ty),*)
         where
             A: Allocator,
@@ -135,26 +155,70 @@ macro_rules! declare {
                 })
             }
         }
+    };
+}
+
+/// Declare tuple implementations for every suffix of the given lists of
+/// elements.
+///
+/// The tuples which contain any elements from the first list are larger than
+/// 16 elements and are only implemented if the `large-tuples` feature is
+/// enabled, since they noticeably affect compile times.
+macro_rules! declare {
+    ([] []) => {
+    };
+
+    ([] [$first:tt $(, $rest:tt)* $(,)?]) => {
+        declare_tuple!($first $(, $rest)*);
+        declare!([] [$($rest),*]);
+    };
+
+    ([$first:tt $(, $large:tt)* $(,)?] [$($small:tt),* $(,)?]) => {
+        declare_tuple! {
+            #[cfg(feature = "large-tuples")]
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "large-tuples")))]
+            $first $(, $large)*, $($small),*
+        }
 
-        declare!($(($ty, $ident)),*);
+        declare!([$($large),*] [$($small),*]);
     };
 }
 
 declare! {
-    (T0, t0),
-    (T1, t1),
-    (T2, t2),
-    (T3, t3),
-    (T4, t4),
-    (T5, t5),
-    (T6, t6),
-    (T7, t7),
-    (T8, t8),
-    (T9, t9),
-    (T10, t10),
-    (T11, t11),
-    (T12, t12),
-    (T13, t13),
-    (T14, t14),
-    (T15, t15),
+    [
+        (T0, t0),
+        (T1, t1),
+        (T2, t2),
+        (T3, t3),
+        (T4, t4),
+        (T5, t5),
+        (T6, t6),
+        (T7, t7),
+        (T8, t8),
+        (T9, t9),
+        (T10, t10),
+        (T11, t11),
+        (T12, t12),
+        (T13, t13),
+        (T14, t14),
+        (T15, t15),
+    ]
+    [
+        (T16, t16),
+        (T17, t17),
+        (T18, t18),
+        (T19, t19),
+        (T20, t20),
+        (T21, t21),
+        (T22, t22),
+        (T23, t23),
+        (T24, t24),
+        (T25, t25),
+        (T26, t26),
+        (T27, t27),
+        (T28, t28),
+        (T29, t29),
+        (T30, t30),
+        (T31, t31),
+    ]
 }
@@ -79,6 +79,12 @@ macro_rules! __slice_sequence {
                     where
                         C: $crate::Context<Allocator = A>,
                     {
+                        // There's no need to reserve space for values which
+                        // will be discarded.
+                        if $crate::Context::is_validating($cx) {
+                            return Self::new($cx);
+                        }
+
                         Ok(Builder {
                             vec: $with_capacity,
                             _marker: core::marker::PhantomData
@@ -94,6 +100,10 @@ macro_rules! __slice_sequence {
                     where
                         C: $crate::Context<Allocator = A>,
                     {
+                        if $crate::Context::is_validating($cx) {
+                            return Ok(());
+                        }
+
                         let $vec = &mut self.vec;
                         $insert;
                         Ok(())
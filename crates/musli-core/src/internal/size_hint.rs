@@ -5,5 +5,42 @@ pub(crate) fn cautious<S>(hint: S) -> usize
 where
     SizeHint: From<S>,
 {
-    SizeHint::from(hint).or_default().min(4096)
+    SizeHint::from(hint).or_default().min(CAUTIOUS_LIMIT)
 }
+
+/// Get the number of elements of type `T` to allocate space for based on a
+/// size hint.
+///
+/// Unlike [`cautious`] this bounds the allocation by its size in bytes, so
+/// that collections of small elements can be allocated up front in full.
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "indexmap")))]
+#[inline]
+pub(crate) fn cautious_of<T, S>(hint: S) -> usize
+where
+    SizeHint: From<S>,
+{
+    let limit = CAUTIOUS_BYTES / size_of::<T>().max(1);
+    SizeHint::from(hint)
+        .or_default()
+        .min(limit.max(CAUTIOUS_LIMIT))
+}
+
+/// Get the exact size from a hint, if it is known and small enough to be
+/// trusted for an up-front allocation.
+#[cfg(feature = "alloc")]
+#[inline]
+pub(crate) fn cautious_exact<S>(hint: S) -> Option<usize>
+where
+    SizeHint: From<S>,
+{
+    SizeHint::from(hint)
+        .into_option()
+        .filter(|&len| len <= CAUTIOUS_LIMIT)
+}
+
+/// The maximum number of elements we allocate space for based on a size hint.
+const CAUTIOUS_LIMIT: usize = 4096;
+
+/// The maximum number of bytes we allocate up front based on a size hint.
+#[cfg(all(feature = "alloc", any(feature = "std", feature = "indexmap")))]
+const CAUTIOUS_BYTES: usize = 1 << 20;
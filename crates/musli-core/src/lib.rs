@@ -39,6 +39,7 @@ pub use self::en::{Encode, Encoder};
 
 pub mod hint;
 pub mod mode;
+pub mod semantic;
 
 /// This is an attribute macro that must be used when implementing a
 /// [`Encoder`].
@@ -206,16 +207,18 @@ pub mod __priv {
     use crate::alloc::String;
     pub use crate::context::Context;
     pub use crate::de::{
-        AsDecoder, Decode, DecodeBytes, DecodePacked, DecodeTrace, Decoder, EntryDecoder,
-        MapDecoder, SequenceDecoder, TryFastDecode, VariantDecoder,
+        AsDecoder, Decode, DecodeBytes, DecodeFlatten, DecodePacked, DecodeTrace, Decoder,
+        EntryDecoder, MapDecoder, SequenceDecoder, TryFastDecode, VariantDecoder,
     };
     pub use crate::en::{
-        Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntryEncoder, MapEncoder,
-        SequenceEncoder, TryFastEncode, VariantEncoder,
+        Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntryEncoder,
+        MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
     };
-    pub use crate::hint::MapHint;
+    pub use crate::hint::{Capabilities, MapHint};
     pub use crate::never::Never;
 
+    pub use ::core::clone::Clone;
+    pub use ::core::convert::{From, Into, TryFrom};
     pub use ::core::fmt;
     pub use ::core::mem::{needs_drop, offset_of, size_of};
     pub use ::core::option::Option;
@@ -229,6 +232,90 @@ pub mod __priv {
         ::core::default::Default::default()
     }
 
+    /// Encode a remote type `T` through a local type deriving `Encode` with
+    /// `#[musli(remote = <path>)]`.
+    pub trait EncodeRemote<M, T>
+    where
+        T: ?Sized,
+    {
+        /// Encode the remote value.
+        fn encode_remote<E>(value: &T, encoder: E) -> Result<E::Ok, E::Error>
+        where
+            E: Encoder<Mode = M>;
+    }
+
+    /// Decode a remote type `T` through a local type deriving `Decode` with
+    /// `#[musli(remote = <path>)]`.
+    pub trait DecodeRemote<'de, M, A, T>
+    where
+        A: Allocator,
+    {
+        /// Decode the remote value.
+        fn decode_remote<D>(decoder: D) -> Result<T, D::Error>
+        where
+            D: Decoder<'de, Mode = M, Allocator = A>;
+    }
+
+    /// Assert at compile time that a decoder supports the `required`
+    /// capabilities, panicking with `message` otherwise.
+    ///
+    /// This is evaluated in an inline const block in `Decode::decode`, since a
+    /// trait bound on the decoder can't be added there without making the
+    /// implementation stricter than the `Decode` trait allows.
+    #[inline]
+    pub const fn requires(capabilities: Capabilities, required: Capabilities, message: &str) {
+        if !capabilities.contains(required) {
+            panic!("{}", message);
+        }
+    }
+
+    /// Implemented for types deriving `Encode` with `#[musli(const_packed)]`.
+    #[diagnostic::on_unimplemented(
+        message = "`{Self}` cannot be encoded in a const context",
+        label = "this field cannot be encoded in a const context",
+        note = "fields must be primitives, arrays, or types deriving `Encode` with `#[musli(const_packed)]`"
+    )]
+    pub trait ConstPacked {
+        /// The number of bytes the value is encoded into.
+        const PACKED_SIZE: usize;
+    }
+
+    /// Copy `bytes` into `out` at the offset `at` in a const context, returning
+    /// the buffer and the offset following the written bytes.
+    #[inline]
+    pub const fn const_write<const N: usize, const M: usize>(
+        mut out: [u8; N],
+        at: usize,
+        bytes: [u8; M],
+    ) -> ([u8; N], usize) {
+        let mut n = 0;
+
+        while n < M {
+            out[at + n] = bytes[n];
+            n += 1;
+        }
+
+        (out, at + M)
+    }
+
+    /// Get the bits of an `f32` in a const context.
+    #[inline]
+    #[allow(unknown_lints, unnecessary_transmutes)]
+    pub const fn f32_to_bits(value: f32) -> u32 {
+        // SAFETY: `f32` and `u32` have the same size, and every bit pattern is
+        // a valid `u32`.
+        unsafe { ::core::mem::transmute::<f32, u32>(value) }
+    }
+
+    /// Get the bits of an `f64` in a const context.
+    #[inline]
+    #[allow(unknown_lints, unnecessary_transmutes)]
+    pub const fn f64_to_bits(value: f64) -> u64 {
+        // SAFETY: `f64` and `u64` have the same size, and every bit pattern is
+        // a valid `u64`.
+        unsafe { ::core::mem::transmute::<f64, u64>(value) }
+    }
+
     /// Note that this returns `true` if skipping was unsupported.
     #[inline]
     pub fn skip<'de, D>(decoder: D) -> Result<bool, D::Error>
@@ -247,6 +334,37 @@ pub mod __priv {
         skip(decoder.decode_value()?)
     }
 
+    /// Construct an encoder for an optional value which encodes `None` as the
+    /// given `niche`.
+    #[inline]
+    pub fn niche_encode<T, E>(niche: T) -> impl FnOnce(&Option<T>, E) -> Result<E::Ok, E::Error>
+    where
+        T: PartialEq + Encode<E::Mode>,
+        E: Encoder,
+    {
+        move |value, encoder| match value {
+            Some(value) if *value == niche => Err(encoder
+                .cx()
+                .message("Value is reserved as the niche representing None")),
+            Some(value) => encoder.encode(value),
+            None => encoder.encode(&niche),
+        }
+    }
+
+    /// Construct a decoder for an optional value which decodes the given
+    /// `niche` as `None`.
+    #[inline]
+    pub fn niche_decode<'de, T, D>(niche: T) -> impl FnOnce(D) -> Result<Option<T>, D::Error>
+    where
+        T: PartialEq + Decode<'de, D::Mode, D::Allocator>,
+        D: Decoder<'de>,
+    {
+        move |decoder| {
+            let value = decoder.decode::<T>()?;
+            Ok(if value == niche { None } else { Some(value) })
+        }
+    }
+
     /// Collect and allocate a string from a [`Display`] implementation.
     ///
     /// [`Display`]: fmt::Display
@@ -320,6 +438,33 @@ pub mod __priv {
             ))
         }
 
+        /// Encountered a field which isn't part of a type using
+        /// `#[musli(deny_unknown_fields)]`.
+        #[inline]
+        pub fn unknown_field<C>(cx: C, type_name: &'static str, tag: impl fmt::Debug) -> C::Error
+        where
+            C: Context,
+        {
+            cx.message(format_args!("Type {type_name} has no field {tag:?}"))
+        }
+
+        /// Converting from the representation of a type using
+        /// `#[musli(try_from = ..)]` failed.
+        #[inline]
+        pub fn conversion_failed<C>(
+            cx: C,
+            type_name: &'static str,
+            repr_name: &'static str,
+            error: impl fmt::Display,
+        ) -> C::Error
+        where
+            C: Context,
+        {
+            cx.message(format_args!(
+                "Type {type_name} could not be converted from {repr_name}: {error}"
+            ))
+        }
+
         /// Expected another field to decode.
         #[inline]
         pub fn expected_field_adjacent<C>(
@@ -19,6 +19,7 @@ use crate::de::{
 use crate::en::{
     Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
 };
+use crate::hint::Capabilities;
 use crate::Context;
 
 /// Marker type used for the [`Never`] type.
@@ -96,6 +97,14 @@ where
     type DecodeVariant = Self;
     type __UseMusliDecoderAttributeMacro = ();
 
+    // Since the decoder can never be constructed, it can claim to support
+    // anything.
+    const CAPABILITIES: Capabilities = Capabilities::SELF_DESCRIBING
+        .with(Capabilities::CAN_BORROW)
+        .with(Capabilities::CAN_SKIP)
+        .with(Capabilities::HUMAN_READABLE)
+        .with(Capabilities::REJECT_DUPLICATE_KEYS);
+
     #[inline]
     fn cx(&self) -> Self::Cx {
         match self._never {}
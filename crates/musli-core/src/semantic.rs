@@ -0,0 +1,45 @@
+//! Well-known semantic tags.
+//!
+//! A semantic tag is a number attached to a value through
+//! [`Encoder::encode_tagged`], which tells generic tooling what the value
+//! means in the same way as the semantic tags in CBOR. A UUID for example is
+//! encoded as 16 bytes tagged with [`UUID`], which allows a dump of the data to
+//! render it as a UUID rather than as anonymous bytes.
+//!
+//! Tags below 256 are reserved for the well-known tags defined in this module.
+//! Any other tag can be used by applications, and formats which support tags
+//! preserve them even if they don't know what they mean.
+//!
+//! [`Encoder::encode_tagged`]: crate::Encoder::encode_tagged
+
+/// A point in time, tagging a number of seconds since the Unix epoch.
+pub const TIMESTAMP: u32 = 1;
+
+/// A universally unique identifier, tagging 16 bytes.
+pub const UUID: u32 = 2;
+
+/// A decimal number, tagging a string such as `"-12.50"`.
+pub const DECIMAL: u32 = 3;
+
+/// A URL, tagging a string.
+pub const URL: u32 = 4;
+
+/// Get the name of a well-known tag, or `None` if the tag is not known.
+///
+/// # Examples
+///
+/// ```
+/// use musli::semantic;
+///
+/// assert_eq!(semantic::name(semantic::UUID), Some("uuid"));
+/// assert_eq!(semantic::name(1000), None);
+/// ```
+pub const fn name(tag: u32) -> Option<&'static str> {
+    match tag {
+        TIMESTAMP => Some("timestamp"),
+        UUID => Some("uuid"),
+        DECIMAL => Some("decimal"),
+        URL => Some("url"),
+        _ => None,
+    }
+}
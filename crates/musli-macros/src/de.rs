@@ -5,7 +5,7 @@ use syn::Token;
 
 use crate::expander::{NameMethod, StructKind};
 use crate::internals::apply;
-use crate::internals::attr::{EnumTagging, Packing};
+use crate::internals::attr::{EnumTagging, FromRepr, Packing};
 use crate::internals::build::{Body, Build, BuildData, Enum, Field, Variant};
 use crate::internals::{Import, Result, Tokens};
 
@@ -36,12 +36,16 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
 
     let packed;
 
-    let body = match &e.data {
-        BuildData::Struct(st) => {
+    let body = match (e.from, &e.data) {
+        (Some(from), _) => {
+            packed = syn::parse_quote!(false);
+            decode_from(&cx, &e, from)
+        }
+        (None, BuildData::Struct(st)) => {
             packed = crate::internals::packed(&e, st);
             decode_struct(&cx, &e, st)?
         }
-        BuildData::Enum(en) => {
+        (None, BuildData::Enum(en)) => {
             packed = syn::parse_quote!(false);
             decode_enum(&cx, &e, en)?
         }
@@ -51,6 +55,8 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
         return Err(());
     }
 
+    let opposite_paths = e.opposite_paths();
+
     // Figure out which lifetime to use for what. We use the first lifetime in
     // the type (if any is available) as the decoder lifetime. Else we generate
     // a new anonymous lifetime `'de` to use for the `Decode` impl.
@@ -59,16 +65,39 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
 
     let Tokens {
         allocator_t,
+        capabilities_t,
         context_t,
+        fmt,
+        from_t,
+        requires,
         result,
         decode_t,
         decoder_t,
+        decode_remote_t,
         try_fast_decode,
+        try_from_t,
         ..
     } = e.tokens;
 
     let lt = &e.p.lt;
 
+    // Requirements are checked when the implementation is instantiated with a
+    // specific decoder, which turns them into compile errors. This can't be
+    // expressed as a bound on the decoder, since `Decode::decode` doesn't
+    // permit implementations to add bounds to it.
+    let requirements = e.requires.iter().map(|&(span, requirement)| {
+        let capability = Ident::new(requirement.capability(), Span::call_site());
+
+        let message = format!(
+            "`{type_ident}` requires a decoder which can {}, but the format being used does not support it",
+            requirement.description()
+        );
+
+        quote_spanned! {
+            span => const { #requires(<#d_param as #decoder_t<#lt>>::CAPABILITIES, #capabilities_t::#capability, #message) };
+        }
+    });
+
     if !e.p.lt_exists {
         generics
             .params
@@ -93,7 +122,7 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
             .push(syn::parse_quote!(#allocator_ident: #allocator_t));
     }
 
-    if !e.bounds.is_empty() && !e.decode_bounds.is_empty() {
+    if !e.bounds.is_empty() || !e.decode_bounds.is_empty() {
         generics.make_where_clause().predicates.extend(
             e.bounds
                 .iter()
@@ -102,8 +131,30 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
         );
     }
 
-    let (impl_generics, _, where_clause) = generics.split_for_impl();
     let (_, type_generics, _) = e.input.generics.split_for_impl();
+    let mode_ident = e.expansion.mode_path(e.tokens);
+
+    if let Some(from) = e.from {
+        let repr = from.ty();
+
+        let predicates = &mut generics.make_where_clause().predicates;
+
+        match from {
+            FromRepr::From(..) => {
+                predicates.push(syn::parse_quote!(#type_ident #type_generics: #from_t<#repr>));
+            }
+            FromRepr::TryFrom(..) => {
+                predicates.push(syn::parse_quote!(#type_ident #type_generics: #try_from_t<#repr>));
+                predicates.push(syn::parse_quote! {
+                    <#type_ident #type_generics as #try_from_t<#repr>>::Error: #fmt::Display
+                });
+            }
+        }
+
+        predicates.push(syn::parse_quote!(#repr: #decode_t<#lt, #mode_ident, #allocator_ident>));
+    }
+
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
 
     let mut attributes = Vec::<syn::Attribute>::new();
 
@@ -111,7 +162,28 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
         attributes.push(syn::parse_quote!(#[allow(clippy::just_underscores_and_digits)]));
     }
 
-    let mode_ident = e.expansion.mode_path(e.tokens);
+    if let Some(remote) = e.remote {
+        return Ok(quote! {
+            const _: () = {
+                #[automatically_derived]
+                #(#attributes)*
+                impl #impl_generics #decode_remote_t<#lt, #mode_ident, #allocator_ident, #remote> for #type_ident #type_generics
+                #where_clause
+                {
+                    #[inline]
+                    fn decode_remote<#d_param>(#decoder_var: #d_param) -> #result<#remote, <#d_param as #decoder_t<#lt>>::Error>
+                    where
+                        #d_param: #decoder_t<#lt, Mode = #mode_ident, Allocator = #allocator_ident>,
+                    {
+                        #(#requirements)*
+                        #opposite_paths
+                        let #ctx_var = #decoder_t::cx(&#decoder_var);
+                        #body
+                    }
+                }
+            };
+        });
+    }
 
     Ok(quote! {
         const _: () = {
@@ -127,6 +199,8 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
                 where
                     #d_param: #decoder_t<#lt, Mode = #mode_ident, Allocator = #allocator_ident>,
                 {
+                    #(#requirements)*
+                    #opposite_paths
                     let #ctx_var = #decoder_t::cx(&#decoder_var);
 
                     let #decoder_var = match #decoder_t::try_fast_decode(#decoder_var)? {
@@ -142,6 +216,91 @@ pub(crate) fn expand_decode_entry(e: Build<'_, '_>) -> Result<TokenStream> {
     })
 }
 
+/// Expand the inherent `decode` function of a type using
+/// `#[musli(remote = <path>)]`, which allows it to be used with
+/// `#[musli(with = <path>)]`.
+pub(crate) fn expand_remote_decode(e: &Build<'_, '_>, remote: &syn::Path) -> TokenStream {
+    let Tokens {
+        decode_remote_t,
+        decoder_t,
+        result,
+        ..
+    } = e.tokens;
+
+    let type_ident = &e.input.ident;
+    let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+
+    let decoder_var = e.cx.ident("decoder");
+    let d_param = e.cx.type_with_span("D", Span::call_site());
+    let lt = syn::Lifetime::new("'__de", Span::call_site());
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #type_ident #type_generics
+        #where_clause
+        {
+            /// Decode the remote value.
+            #[inline]
+            pub fn decode<#lt, #d_param>(#decoder_var: #d_param) -> #result<#remote, <#d_param as #decoder_t<#lt>>::Error>
+            where
+                #d_param: #decoder_t<#lt>,
+                Self: #decode_remote_t<#lt, <#d_param as #decoder_t<#lt>>::Mode, <#d_param as #decoder_t<#lt>>::Allocator, #remote>,
+            {
+                <Self as #decode_remote_t<#lt, <#d_param as #decoder_t<#lt>>::Mode, <#d_param as #decoder_t<#lt>>::Allocator, #remote>>::decode_remote(#decoder_var)
+            }
+        }
+    }
+}
+
+/// Decode a type by converting it from its representation with
+/// `#[musli(from = <type>)]` or `#[musli(try_from = <type>)]`.
+fn decode_from(cx: &Ctxt<'_>, b: &Build<'_, '_>, from: &FromRepr) -> TokenStream {
+    let Ctxt {
+        ctx_var,
+        decoder_var,
+        ..
+    } = *cx;
+
+    let Tokens {
+        context_t,
+        decoder_t,
+        from_t,
+        messages,
+        result,
+        try_from_t,
+        ..
+    } = b.tokens;
+
+    let value_var = b.cx.ident("value");
+    let error_var = b.cx.ident("error");
+    let repr = from.ty();
+
+    let convert = match from {
+        FromRepr::From(..) => quote!(#result::Ok(#from_t::from(#value_var))),
+        FromRepr::TryFrom(..) => {
+            let type_name = b.input.ident.to_string();
+            let repr_name = repr.to_token_stream().to_string().replace(' ', "");
+
+            quote! {
+                match #try_from_t::try_from(#value_var) {
+                    #result::Ok(#value_var) => #result::Ok(#value_var),
+                    #result::Err(#error_var) => #result::Err(#messages::conversion_failed(#ctx_var, #type_name, #repr_name, #error_var)),
+                }
+            }
+        }
+    };
+
+    // The conversion observes the decoded representation, so it has to be
+    // decoded in full even when validating.
+    quote! {
+        #context_t::suspend_validating(#ctx_var);
+        let #value_var: #result<#repr, _> = #decoder_t::decode(#decoder_var);
+        #context_t::resume_validating(#ctx_var);
+        let #value_var = #value_var?;
+        #convert
+    }
+}
+
 fn decode_struct(cx: &Ctxt<'_>, b: &Build<'_, '_>, st: &Body<'_>) -> Result<TokenStream> {
     let Tokens { result, .. } = b.tokens;
 
@@ -216,6 +375,8 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
 
     let mut output_arms = Vec::new();
 
+    let self_path = b.self_path();
+
     let mut fallback = match en.fallback {
         Some(ident) => {
             quote! {{
@@ -223,7 +384,7 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                     return #result::Err(#messages::invalid_variant_tag(#ctx_var, #type_name, &#variant_tag_var));
                 }
 
-                Self::#ident {}
+                #self_path::#ident {}
             }}
         }
         None => quote! {
@@ -234,12 +395,18 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
     let decode_name;
     let output_enum;
     let name_type;
+    // Expressions for the tag of each variant, used to map human-readable
+    // names onto regular tags.
+    let mut human_tags = Vec::new();
 
     match en.name_type.method {
         NameMethod::Sized => {
             for v in &en.variants {
-                let arm = output_arm(v.pattern, &v.name, &binding_var);
+                let arm = output_arm(v.pattern, &v.name, v.aliases, &binding_var);
                 output_arms.push((v, arm, &v.name));
+
+                let name = &v.name;
+                human_tags.push((v, quote!(#name)));
             }
 
             let decode_t_decode = &b.decode_t_decode;
@@ -254,9 +421,17 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
             let output_type = b.cx.type_with_span("VariantTag", en.span);
 
             for v in &en.variants {
-                let (pat, variant) =
-                    unsized_arm(b, v.span, v.index, &v.name, v.pattern, &output_type);
-
+                let (pat, variant) = unsized_arm(
+                    b,
+                    v.span,
+                    v.index,
+                    &v.name,
+                    v.pattern,
+                    v.aliases,
+                    &output_type,
+                );
+
+                human_tags.push((v, quote!(#pat)));
                 output_arms.push((v, OutputArm { pat, cond: None }, &v.name));
                 variants.push(variant);
             }
@@ -318,43 +493,96 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
 
             for v in &en.variants {
                 let path = &v.st.path;
-                let pat = output_arm(v.pattern, &v.name, &binding_var);
+                let pat = output_arm(v.pattern, &v.name, v.aliases, &binding_var);
                 arms.push(quote!(#pat => #result::Ok(#path {})));
             }
 
             match en.fallback {
                 Some(ident) => {
-                    arms.push(quote!(_ => #result::Ok(Self::#ident {})));
+                    arms.push(quote!(_ => #result::Ok(#self_path::#ident {})));
                 }
                 None => {
                     arms.push(quote!(#value_var => #result::Err(#messages::invalid_variant_tag(#ctx_var, #type_name, &#value_var))));
                 }
             }
 
-            match en.name_type.method {
+            let decode = match en.name_type.method {
                 NameMethod::Sized => {
                     let decode_t_decode = &b.decode_t_decode;
                     let name_type = &en.name_type.ty;
 
-                    Ok(quote! {{
+                    quote! {{
                         let #value_var: #name_type = #decode_t_decode(#decoder_var)?;
 
                         match #value_var { #(#arms,)* }
-                    }})
+                    }}
                 }
                 NameMethod::Unsized(method) => {
                     let method = method.as_method_name();
                     let visit_type = &en.name_type.ty;
 
-                    Ok(quote! {
+                    quote! {
                         #decoder_t::#method(#decoder_var, |#value_var: &#visit_type| {
                             match #value_var { #(#arms,)* }
                         })
-                    })
+                    }
                 }
+            };
+
+            if !en.human_readable {
+                return Ok(decode);
+            }
+
+            let mut human_arms = Vec::new();
+
+            for v in &en.variants {
+                let path = &v.st.path;
+                let human_name = &v.human_name;
+                human_arms.push(quote!(#human_name => #result::Ok(#path {})));
             }
+
+            match en.fallback {
+                Some(ident) => {
+                    human_arms.push(quote!(_ => #result::Ok(#self_path::#ident {})));
+                }
+                None => {
+                    human_arms.push(quote!(#value_var => #result::Err(#messages::invalid_variant_tag(#ctx_var, #type_name, #value_var))));
+                }
+            }
+
+            Ok(quote! {
+                if #decoder_t::capabilities(&#decoder_var).is_human_readable() {
+                    #decoder_t::decode_unsized(#decoder_var, |#value_var: &str| {
+                        match #value_var { #(#human_arms,)* }
+                    })
+                } else {
+                    #decode
+                }
+            })
         }
         EnumTagging::Default => {
+            let decode_tag = if en.human_readable {
+                let human_arms = human_tags.iter().map(|(v, tag)| {
+                    let human_name = &v.human_name;
+                    quote!(#human_name => #result::Ok(#tag))
+                });
+
+                quote! {
+                    if #decoder_t::capabilities(&#variant_decoder_var).is_human_readable() {
+                        #decoder_t::decode_unsized(#variant_decoder_var, |#value_var: &str| {
+                            match #value_var {
+                                #(#human_arms,)*
+                                #value_var => #result::Err(#messages::invalid_variant_tag(#ctx_var, #type_name, #value_var)),
+                            }
+                        })?
+                    } else {
+                        #decode_name?
+                    }
+                }
+            } else {
+                quote!(#decode_name?)
+            };
+
             let arms = output_arms.iter().flat_map(|(v, pat, tag_value)| {
                 let name = &v.st.name;
 
@@ -406,7 +634,7 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                 let #output_var = #decoder_t::decode_variant(#decoder_var, move |#variant_decoder_var| {
                     let #variant_tag_var: #name_type = {
                         let mut #variant_decoder_var = #variant_decoder_t::decode_tag(#variant_decoder_var)?;
-                        #decode_name?
+                        #decode_tag
                     };
 
                     let #output_var = match #variant_tag_var {
@@ -470,7 +698,7 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                     outcome_enum = None;
 
                     let tag_type = &tag_type.ty;
-                    let tag_arm = output_arm(None, tag_value, &binding_var);
+                    let tag_arm = output_arm(None, tag_value, &[], &binding_var);
 
                     decode_match = quote! {
                         let #value_var: #tag_type = #decode_t_decode(#field_name_var)?;
@@ -495,7 +723,7 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                     let visit_type = &tag_type.ty;
                     let method = method.as_method_name();
 
-                    let tag_arm = output_arm(None, tag_value, &binding_var);
+                    let tag_arm = output_arm(None, tag_value, &[], &binding_var);
 
                     let format_value_var = tag_type.name_format(&value_var);
 
@@ -622,8 +850,8 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                     outcome_enum = None;
 
                     let value_type = &tag_type.ty;
-                    let tag_arm = output_arm(None, tag_value, &binding_var);
-                    let content_arm = output_arm(None, content_value, &binding_var);
+                    let tag_arm = output_arm(None, tag_value, &[], &binding_var);
+                    let content_arm = output_arm(None, content_value, &[], &binding_var);
 
                     decode_match = quote! {
                         let #value_var: #value_type = #decode_t_decode(#field_name_var)?;
@@ -662,8 +890,8 @@ fn decode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum) -> Result<TokenStrea
                     let visit_type = &tag_type.ty;
                     let format_value_var = tag_type.name_format(&value_var);
                     let method = method.as_method_name();
-                    let tag_arm = output_arm(None, tag_value, &binding_var);
-                    let content_arm = output_arm(None, content_value, &binding_var);
+                    let tag_arm = output_arm(None, tag_value, &[], &binding_var);
+                    let content_arm = output_arm(None, content_value, &[], &binding_var);
 
                     decode_match = quote! {
                         let #outcome_var = #decoder_t::#method(#field_name_var, |#value_var: &#visit_type| {
@@ -842,6 +1070,7 @@ fn decode_tagged(
         struct_field_decoder_t,
         map_hint,
         messages,
+        decode_flatten_t,
         ..
     } = b.tokens;
 
@@ -859,20 +1088,16 @@ fn decode_tagged(
 
     let mut fields_with = Vec::new();
 
+    let flatten_var = b.cx.ident("flatten");
+
     for f in &st.all_fields {
         let tag = &f.name;
         let var = &f.var;
         let decode_path = &f.decode_path.1;
 
         let expr = match &f.skip {
-            Some(span) => {
-                let ty = f.ty;
-
-                match &f.default_attr {
-                    Some((_, Some(path))) => syn::Expr::Verbatim(quote_spanned!(*span => #path())),
-                    _ => syn::Expr::Verbatim(quote_spanned!(*span => #default_function::<#ty>())),
-                }
-            }
+            _ if f.flatten.is_some() => syn::Expr::Verbatim(quote!(#flatten_var)),
+            Some(span) => syn::Expr::Verbatim(f.default_value(*span, ctx_var, default_function)),
             None => {
                 let formatted_tag = st.name_type.name_format(&static_name_var);
 
@@ -906,8 +1131,7 @@ fn decode_tagged(
                 fields_with.push((tag, f, decode, (enter, leave)));
 
                 let fallback = match f.default_attr {
-                    Some((span, None)) => quote_spanned!(span => #default_function()),
-                    Some((_, Some(path))) => quote!(#path()),
+                    Some((span, _)) => f.default_value(span, ctx_var, default_function),
                     None => quote! {{
                         static #static_name_var: #static_name_type = #tag;
                         return #result::Err(#messages::expected_tag(#ctx_var, #type_name, #formatted_tag))
@@ -945,21 +1169,79 @@ fn decode_tagged(
         },
     };
 
-    let skip_field = quote! {
-        if #skip_field(#struct_decoder_var)? {
-            return #result::Err(#unsupported);
+    let skip_field = if st.deny_unknown_fields.is_some() {
+        quote! {
+            return #result::Err(#messages::unknown_field(#ctx_var, #type_name, &#name_var));
+        }
+    } else {
+        quote! {
+            if #skip_field(#struct_decoder_var)? {
+                return #result::Err(#unsupported);
+            }
         }
     };
 
     let body;
     let name_type: syn::Type;
 
-    match st.name_type.method {
-        NameMethod::Sized => {
+    let flatten_t = st.flatten.as_ref().map(|f| {
+        let ty = f.ty;
+        let lt = &b.p.lt;
+        let mode_path = b.mode.mode_path;
+        let allocator_ident = &b.p.allocator_ident;
+        (
+            ty,
+            quote!(#decode_flatten_t<#lt, #mode_path, #allocator_ident>),
+        )
+    });
+
+    match (st.name_type.method, &flatten_t) {
+        // Unknown entries are captured, so the name has to be decoded into the
+        // key of the flattened field so that it can be retained.
+        (_, Some((ty, flatten_t))) => {
+            let mut chain = quote! {{
+                let #struct_decoder_var = #struct_field_decoder_t::decode_value(#struct_decoder_var)?;
+                <#ty as #flatten_t>::decode_entry(&mut #flatten_var, #name_var, #struct_decoder_var)?;
+            }};
+
+            for (tag, f, decode, (enter, leave)) in fields_with.into_iter().rev() {
+                let names = [&f.name]
+                    .into_iter()
+                    .chain(f.aliases.iter().map(|(_, alias)| alias))
+                    .map(|name| quote!(#name_var == #name));
+
+                chain = quote! {
+                    if #(#names)||* {
+                        static #static_name_var: #static_name_type = #tag;
+                        #enter
+                        let #struct_decoder_var = #struct_field_decoder_t::decode_value(#struct_decoder_var)?;
+                        #decode
+                        #leave
+                    } else #chain
+                };
+            }
+
+            body = chain;
+
+            let decode_t_decode = &b.decode_t_decode;
+
+            // The name is compared against fields and retained in the
+            // flattened field, so it has to be decoded in full even when
+            // validating.
+            decode_tag = quote! {{
+                #context_t::suspend_validating(#ctx_var);
+                let #name_var = #decode_t_decode(#struct_decoder_var);
+                #context_t::resume_validating(#ctx_var);
+                #name_var?
+            }};
+
+            name_type = syn::parse_quote!(<#ty as #flatten_t>::Key);
+        }
+        (NameMethod::Sized, None) => {
             let mut arms = Vec::with_capacity(fields_with.len());
 
             for (tag, f, decode, (enter, leave)) in fields_with {
-                let arm = output_arm(f.pattern, &f.name, &binding_var);
+                let arm = output_arm(f.pattern, &f.name, f.aliases, &binding_var);
 
                 arms.push(quote! {
                     #arm => {
@@ -982,7 +1264,7 @@ fn decode_tagged(
 
             name_type = st.name_type.ty.clone();
         }
-        NameMethod::Unsized(method) => {
+        (NameMethod::Unsized(method), None) => {
             let output_type =
                 b.cx.type_with_span("TagVisitorOutput", b.input.ident.span());
 
@@ -990,8 +1272,15 @@ fn decode_tagged(
             let mut name_arms = Vec::with_capacity(fields_with.len());
 
             for (tag, f, decode, trace) in fields_with {
-                let (name_pat, name_variant) =
-                    unsized_arm(b, f.span, f.index, &f.name, f.pattern, &output_type);
+                let (name_pat, name_variant) = unsized_arm(
+                    b,
+                    f.span,
+                    f.index,
+                    &f.name,
+                    f.pattern,
+                    f.aliases,
+                    &output_type,
+                );
 
                 outputs.push(name_variant);
                 name_arms.push((tag, name_pat, decode, trace));
@@ -1024,12 +1313,21 @@ fn decode_tagged(
             let visit_type = &st.name_type.ty;
             let method = method.as_method_name();
 
+            // Unknown names are only retained in the visitor, so this is where
+            // they have to be reported.
+            let unknown = match st.deny_unknown_fields {
+                Some(..) => quote! {
+                    return #result::Err(#messages::unknown_field(#ctx_var, #type_name, #value_var));
+                },
+                None => quote!(#option::None),
+            };
+
             decode_tag = quote! {
                 #decoder_t::#method(#struct_decoder_var, |#value_var: &#visit_type| {
                     #result::Ok(match #value_var {
                         #(#arms,)*
                         #value_var => {
-                            #option::None
+                            #unknown
                         }
                     })
                 })?
@@ -1069,6 +1367,9 @@ fn decode_tagged(
         .map(|f| &**f)
         .map(|Field { var, ty, .. }| quote!(let mut #var: #option<#ty> = #option::None));
 
+    let flatten_decl = flatten_t
+        .map(|(ty, flatten_t)| quote!(let mut #flatten_var: #ty = <#ty as #flatten_t>::empty();));
+
     let enter = (cx.trace && cx.trace_body).then(|| {
         quote! {
             #context_t::enter_struct(#ctx_var, #type_name);
@@ -1084,6 +1385,7 @@ fn decode_tagged(
     Ok(quote! {{
         #output_enum
         #(#decls;)*
+        #flatten_decl
 
         #enter
 
@@ -1173,11 +1475,21 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_, '_>, st_: &Body<'_>) -> Result<Tok
 
     let mut assign = Vec::new();
 
-    for f in &st_.unskipped_fields {
-        let mut is_default = false;
+    for f in &st_.all_fields {
+        if let Some(span) = f.skip {
+            let member = &f.member;
+            let expr = f.default_value(span, ctx_var, default_function);
+
+            let value: Box<dyn Fn(&syn::Ident, &mut TokenStream)> =
+                Box::new(move |_: &syn::Ident, tokens: &mut TokenStream| {
+                    tokens.extend(quote!(#member: #expr));
+                });
+
+            assign.push(value);
+            continue;
+        }
 
         if let Some((span, _)) = f.default_attr {
-            is_default = true;
             last = Some(span);
         } else if let Some(span) = last {
             b.packed_default_diagnostics(span);
@@ -1187,8 +1499,8 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_, '_>, st_: &Body<'_>) -> Result<Tok
         let member = &f.member;
         let field_decoder = &field_decoder;
 
-        if is_default {
-            let ty = f.ty;
+        if let Some((span, _)) = f.default_attr {
+            let default_value = f.default_value(span, ctx_var, default_function);
 
             let value: Box<dyn Fn(&syn::Ident, &mut TokenStream)> =
                 Box::new(move |ident: &syn::Ident, tokens: &mut TokenStream| {
@@ -1198,7 +1510,7 @@ fn decode_packed(cx: &Ctxt<'_>, b: &Build<'_, '_>, st_: &Body<'_>) -> Result<Tok
 
                             match #decoder_t::decode_option(#field_decoder)? {
                                 #option::Some(#field_decoder) => #decode_path(#field_decoder)?,
-                                #option::None => #default_function::<#ty>(),
+                                #option::None => #default_value,
                             }
                         }
                     })
@@ -1260,23 +1572,24 @@ pub(crate) struct NameVariant<'a> {
     name: &'a syn::Expr,
     /// The pattern being matched.
     pattern: Option<&'a syn::Pat>,
+    /// Additional names being matched.
+    aliases: &'a [(Span, syn::Expr)],
 }
 
 impl NameVariant<'_> {
     /// Generate the pattern for this output.
     pub(crate) fn as_arm(&self, binding_var: &syn::Ident, option: &Import<'_>) -> syn::Arm {
         let path = &self.path;
-        let arm = output_arm(self.pattern, self.name, binding_var);
+        let arm = output_arm(self.pattern, self.name, self.aliases, binding_var);
 
         syn::Arm {
             attrs: Vec::new(),
             pat: arm.pat,
-            guard: arm.cond.map(|_| {
-                let name = self.name;
-
+            guard: arm.cond.map(|cond| {
+                let exprs = cond.exprs;
                 (
                     <syn::Token![if]>::default(),
-                    syn::parse_quote!(*#binding_var == #name),
+                    syn::parse_quote!(#(*#binding_var == #exprs)||*),
                 )
             }),
             fat_arrow_token: <Token![=>]>::default(),
@@ -1292,6 +1605,7 @@ fn unsized_arm<'a>(
     index: usize,
     name: &'a syn::Expr,
     pattern: Option<&'a syn::Pat>,
+    aliases: &'a [(Span, syn::Expr)],
     output: &Ident,
 ) -> (syn::Pat, NameVariant<'a>) {
     let variant = b.cx.type_with_span(format_args!("Variant{}", index), span);
@@ -1304,6 +1618,7 @@ fn unsized_arm<'a>(
         variant,
         name,
         pattern,
+        aliases,
     };
 
     let option = &b.tokens.option;
@@ -1312,29 +1627,24 @@ fn unsized_arm<'a>(
 
 struct Condition<'a> {
     if_: syn::Token![if],
-    star: syn::Token![*],
     ident: &'a syn::Ident,
-    equals: syn::Token![==],
-    expr: &'a syn::Expr,
+    exprs: Vec<&'a syn::Expr>,
 }
 
 impl ToTokens for Condition<'_> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let ident = self.ident;
+        let exprs = &self.exprs;
         self.if_.to_tokens(tokens);
-        self.star.to_tokens(tokens);
-        self.ident.to_tokens(tokens);
-        self.equals.to_tokens(tokens);
-        self.expr.to_tokens(tokens);
+        tokens.extend(quote!(#(*#ident == #exprs)||*));
     }
 }
 
-fn condition<'a>(ident: &'a syn::Ident, expr: &'a syn::Expr) -> Condition<'a> {
+fn condition<'a>(ident: &'a syn::Ident, exprs: Vec<&'a syn::Expr>) -> Condition<'a> {
     Condition {
         if_: <syn::Token![if]>::default(),
-        star: <syn::Token![*]>::default(),
         ident,
-        equals: <syn::Token![==]>::default(),
-        expr,
+        exprs,
     }
 }
 
@@ -1351,6 +1661,7 @@ fn ref_pattern(ident: &syn::Ident) -> syn::Pat {
 fn output_arm<'a>(
     pat: Option<&'a syn::Pat>,
     name: &'a syn::Expr,
+    aliases: &'a [(Span, syn::Expr)],
     binding: &'a syn::Ident,
 ) -> OutputArm<'a> {
     if let Some(pat) = pat {
@@ -1360,13 +1671,32 @@ fn output_arm<'a>(
         };
     }
 
-    if let Some(pat) = expr_to_pat(name) {
+    let exprs = [name]
+        .into_iter()
+        .chain(aliases.iter().map(|(_, alias)| alias))
+        .collect::<Vec<_>>();
+
+    if let Some(mut cases) = exprs
+        .iter()
+        .map(|e| expr_to_pat(e))
+        .collect::<Option<Vec<_>>>()
+    {
+        let pat = if cases.len() == 1 {
+            cases.remove(0)
+        } else {
+            syn::Pat::Or(syn::PatOr {
+                attrs: Vec::new(),
+                leading_vert: None,
+                cases: cases.into_iter().collect(),
+            })
+        };
+
         return OutputArm { pat, cond: None };
     }
 
     OutputArm {
         pat: ref_pattern(binding),
-        cond: Some(condition(binding, name)),
+        cond: Some(condition(binding, exprs)),
     }
 }
 
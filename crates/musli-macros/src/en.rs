@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::punctuated::Punctuated;
@@ -30,22 +32,29 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
     };
 
     let Tokens {
+        clone_t,
         encode_t,
         encoder_t,
+        into_t,
         result,
         try_fast_encode,
         context_t,
+        encode_remote_t,
         ..
     } = e.tokens;
 
     let packed;
 
-    let body = match &e.data {
-        BuildData::Struct(st) => {
+    let body = match (e.into, &e.data) {
+        (Some(repr), _) => {
+            packed = syn::parse_quote!(false);
+            encode_into(&cx, &e, repr)
+        }
+        (None, BuildData::Struct(st)) => {
             packed = crate::internals::packed(&e, st);
             encode_map(&cx, &e, st)?
         }
-        BuildData::Enum(en) => {
+        (None, BuildData::Enum(en)) => {
             packed = syn::parse_quote!(false);
             encode_enum(&cx, &e, en)?
         }
@@ -55,7 +64,10 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
         return Err(());
     }
 
+    let opposite_paths = e.opposite_paths();
     let mut impl_generics = e.input.generics.clone();
+    let (_, type_generics, _) = e.input.generics.split_for_impl();
+    let mode_ident = e.expansion.mode_path(e.tokens);
 
     if !e.bounds.is_empty() {
         let where_clause = impl_generics.make_where_clause();
@@ -65,8 +77,16 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
             .extend(e.bounds.iter().map(|(_, v)| v.clone()));
     }
 
+    if let Some(repr) = e.into {
+        let where_clause = impl_generics.make_where_clause();
+
+        where_clause.predicates.extend::<[syn::WherePredicate; 2]>([
+            syn::parse_quote!(#type_ident #type_generics: #clone_t + #into_t<#repr>),
+            syn::parse_quote!(#repr: #encode_t<#mode_ident>),
+        ]);
+    }
+
     let (impl_generics, _, where_clause) = impl_generics.split_for_impl();
-    let (_, type_generics, _) = e.input.generics.split_for_impl();
 
     let mut attributes = Vec::<syn::Attribute>::new();
 
@@ -74,7 +94,29 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
         attributes.push(syn::parse_quote!(#[allow(clippy::just_underscores_and_digits)]));
     }
 
-    let mode_ident = e.expansion.mode_path(e.tokens);
+    if let Some(remote) = e.remote {
+        let remote_var = e.self_value();
+
+        return Ok(quote! {
+            const _: () = {
+                #[automatically_derived]
+                #(#attributes)*
+                impl #impl_generics #encode_remote_t<#mode_ident, #remote> for #type_ident #type_generics
+                #where_clause
+                {
+                    #[inline]
+                    fn encode_remote<#e_param>(#remote_var: &#remote, #encoder_var: #e_param) -> #result<<#e_param as #encoder_t>::Ok, <#e_param as #encoder_t>::Error>
+                    where
+                        #e_param: #encoder_t<Mode = #mode_ident>,
+                    {
+                        #opposite_paths
+                        let #ctx_var = #encoder_t::cx(&#encoder_var);
+                        #body
+                    }
+                }
+            };
+        });
+    }
 
     Ok(quote! {
         const _: () = {
@@ -92,6 +134,7 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
                 where
                     #e_param: #encoder_t<Mode = #mode_ident>,
                 {
+                    #opposite_paths
                     let #ctx_var = #encoder_t::cx(&#encoder_var);
 
                     let #encoder_var = match #encoder_t::try_fast_encode(#encoder_var, self)? {
@@ -112,6 +155,62 @@ pub(crate) fn expand_insert_entry(e: Build<'_, '_>) -> Result<TokenStream> {
     })
 }
 
+/// Expand the inherent `encode` function of a type using
+/// `#[musli(remote = <path>)]`, which allows it to be used with
+/// `#[musli(with = <path>)]`.
+pub(crate) fn expand_remote_encode(e: &Build<'_, '_>, remote: &syn::Path) -> TokenStream {
+    let Tokens {
+        encode_remote_t,
+        encoder_t,
+        result,
+        ..
+    } = e.tokens;
+
+    let type_ident = &e.input.ident;
+    let (impl_generics, type_generics, where_clause) = e.input.generics.split_for_impl();
+
+    let value_var = e.cx.ident("value");
+    let encoder_var = e.cx.ident("encoder");
+    let e_param = e.cx.type_with_span("E", Span::call_site());
+
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics #type_ident #type_generics
+        #where_clause
+        {
+            /// Encode the remote value.
+            #[inline]
+            pub fn encode<#e_param>(#value_var: &#remote, #encoder_var: #e_param) -> #result<<#e_param as #encoder_t>::Ok, <#e_param as #encoder_t>::Error>
+            where
+                #e_param: #encoder_t,
+                Self: #encode_remote_t<<#e_param as #encoder_t>::Mode, #remote>,
+            {
+                <Self as #encode_remote_t<<#e_param as #encoder_t>::Mode, #remote>>::encode_remote(#value_var, #encoder_var)
+            }
+        }
+    }
+}
+
+/// Encode a type by converting it into its representation with
+/// `#[musli(into = <type>)]`.
+fn encode_into(cx: &Ctxt<'_>, b: &Build<'_, '_>, repr: &syn::Type) -> TokenStream {
+    let Ctxt { encoder_var, .. } = *cx;
+
+    let Tokens {
+        clone_t,
+        encoder_t,
+        into_t,
+        ..
+    } = b.tokens;
+
+    let value_var = b.cx.ident("value");
+
+    quote! {
+        let #value_var: #repr = #into_t::into(#clone_t::clone(self));
+        #encoder_t::encode(#encoder_var, &#value_var)
+    }
+}
+
 /// Encode a struct.
 fn encode_map(cx: &Ctxt<'_>, b: &Build<'_, '_>, st: &Body<'_>) -> Result<TokenStream> {
     let Ctxt {
@@ -122,6 +221,7 @@ fn encode_map(cx: &Ctxt<'_>, b: &Build<'_, '_>, st: &Body<'_>) -> Result<TokenSt
 
     let Tokens {
         context_t,
+        encode_flatten_t,
         encoder_t,
         result,
         ..
@@ -159,7 +259,20 @@ fn encode_map(cx: &Ctxt<'_>, b: &Build<'_, '_>, st: &Body<'_>) -> Result<TokenSt
         }
         Packing::Tagged => {
             let decls = tests.iter().map(|t| &t.decl);
-            let (build_hint, hint) = length_test(st.unskipped_fields.len(), &tests).build(b);
+            let mut len = length_test(st.unskipped_fields.len(), &tests);
+
+            let flatten = st.flatten.as_ref().map(|f| {
+                let access = &f.self_access;
+                let mode_path = b.mode.mode_path;
+
+                len.kind = LengthTestKind::Dynamic;
+                len.expressions
+                    .push(quote!(#encode_flatten_t::<#mode_path>::size(#access)));
+
+                quote!(#encode_flatten_t::<#mode_path>::encode_entries(#access, #encoder_var)?;)
+            });
+
+            let (build_hint, hint) = len.build(b);
 
             encode = quote! {{
                 #enter
@@ -168,6 +281,7 @@ fn encode_map(cx: &Ctxt<'_>, b: &Build<'_, '_>, st: &Body<'_>) -> Result<TokenSt
 
                 let #output_var = #encoder_t::encode_map_fn(#encoder_var, &#hint, move |#encoder_var| {
                     #(#encoders)*
+                    #flatten
                     #result::Ok(())
                 })?;
                 #leave
@@ -338,34 +452,102 @@ fn encode_enum(cx: &Ctxt<'_>, b: &Build<'_, '_>, en: &Enum<'_>) -> Result<TokenS
         return Err(());
     }
 
-    let mut variants = Vec::with_capacity(en.variants.len());
+    let pack_var = b.cx.ident("pack");
+
+    // Variants whose fields are encoded identically are grouped into a single
+    // match arm, so that their encoding is only generated and monomorphized
+    // once. This significantly reduces the size of enums with many variants.
+    let mut groups = Vec::<VariantGroup<'_, '_>>::new();
+    let mut shapes = HashMap::<String, usize>::new();
 
     for v in &en.variants {
-        let Ok((pattern, encode)) = encode_variant(cx, b, en, v) else {
+        let Ok((encoders, tests)) = insert_fields(cx, b, &v.st, &pack_var) else {
             continue;
         };
 
-        variants.push(quote!(#pattern => #encode));
+        let shape = variant_shape(v, &encoders, &tests);
+
+        if let Some(&index) = shapes.get(&shape) {
+            groups[index].variants.push(v);
+            continue;
+        }
+
+        shapes.insert(shape, groups.len());
+
+        groups.push(VariantGroup {
+            variants: vec![v],
+            encoders,
+            tests,
+        });
+    }
+
+    let mut variants = Vec::with_capacity(groups.len());
+
+    for group in &groups {
+        let Ok(encode) = encode_variant(cx, b, en, group, &pack_var) else {
+            continue;
+        };
+
+        let patterns = group.variants.iter().map(|v| variant_pattern(v));
+        variants.push(quote!(#(#patterns)|* => #encode));
     }
 
     // Special case: uninhabitable types.
     Ok(if variants.is_empty() {
         quote!(#result::Err(#messages::uninhabitable(#ctx_var, #type_name)))
     } else {
-        quote!(#result::Ok(match self { #(#variants),* }))
+        let value = b.self_value();
+        quote!(#result::Ok(match #value { #(#variants),* }))
     })
 }
 
-/// Setup encoding for a single variant. that is externally tagged.
+/// A group of variants which share the same encoding for their fields.
+struct VariantGroup<'st, 'a> {
+    variants: Vec<&'st Variant<'a>>,
+    encoders: Vec<TokenStream>,
+    tests: Vec<FieldTest<'st>>,
+}
+
+/// Calculate a key describing how the fields of a variant are encoded.
+///
+/// Variants with the same key can share a match arm since their fields are
+/// bound and encoded identically.
+fn variant_shape(v: &Variant<'_>, encoders: &[TokenStream], tests: &[FieldTest<'_>]) -> String {
+    let types = v.st.unskipped_fields.iter().map(|f| f.ty);
+    let decls = tests.iter().map(|t| &t.decl);
+    let packing = format!("{:?}", v.st.packing);
+
+    quote!(#packing #(#types)* #(#decls)* #(#encoders)*).to_string()
+}
+
+/// Construct a pattern matching the given variant.
+fn variant_pattern(v: &Variant<'_>) -> syn::PatStruct {
+    syn::PatStruct {
+        attrs: Vec::new(),
+        qself: None,
+        path: v.st.path.clone(),
+        brace_token: syn::token::Brace::default(),
+        fields: v.patterns.clone(),
+        rest: None,
+    }
+}
+
+/// Setup encoding for a group of variants that is externally tagged.
 fn encode_variant(
     cx: &Ctxt<'_>,
     b: &Build<'_, '_>,
     en: &Enum<'_>,
-    v: &Variant<'_>,
-) -> Result<(syn::PatStruct, TokenStream)> {
-    let pack_var = b.cx.ident("pack");
+    group: &VariantGroup<'_, '_>,
+    pack_var: &syn::Ident,
+) -> Result<TokenStream> {
+    let VariantGroup {
+        variants,
+        encoders,
+        tests,
+    } = group;
 
-    let (encoders, tests) = insert_fields(cx, b, &v.st, &pack_var)?;
+    let v = variants[0];
+    let shared = variants.len() > 1;
 
     let Ctxt {
         ctx_var,
@@ -386,25 +568,50 @@ fn encode_variant(
 
     let content_static = b.cx.ident("CONTENT");
     let hint = b.cx.ident("STRUCT_HINT");
-    let name_static = b.cx.ident("NAME");
+    let type_name_var = b.cx.ident("type_name");
+
+    // Shared variants select their name at runtime instead of through a
+    // static.
+    let name_static = if shared {
+        b.cx.ident("name")
+    } else {
+        b.cx.ident("NAME")
+    };
+
     let name_expr = en.name_type.expr(name_static.clone());
+    let name_type = en.name_type.ty();
+    let name_value = &v.name;
+
+    let name_decl = (!shared).then(|| quote!(static #name_static: #name_type = #name_value;));
+
+    let human_name_var = b.cx.ident("human_name");
+
+    let human_name = match &v.human_name {
+        Some(..) if shared => Some(quote!(#human_name_var)),
+        Some(human_name) => Some(quote!(#human_name)),
+        None => None,
+    };
+
     let tag_encoder = b.cx.ident("tag_encoder");
     let tag_static = b.cx.ident("TAG");
     let variant_encoder = b.cx.ident("variant_encoder");
 
-    let type_name = v.st.name;
+    let type_name = if shared {
+        quote!(#type_name_var)
+    } else {
+        let type_name = v.st.name;
+        quote!(#type_name)
+    };
 
     let mut encode;
 
     match &en.enum_tagging {
         EnumTagging::Empty => {
-            let name_type = en.name_type.ty();
-            let encode_t_encode = &b.encode_t_encode;
-            let name = &v.name;
+            let encode_tag = encode_tag(b, &name_expr, human_name.as_ref(), encoder_var);
 
             encode = quote! {{
-                static #name_static: #name_type = #name;
-                #encode_t_encode(#name_expr, #encoder_var)?
+                #name_decl
+                #encode_tag
             }};
         }
         EnumTagging::Default => {
@@ -430,7 +637,7 @@ fn encode_variant(
                 Packing::Tagged => {
                     let decls = tests.iter().map(|t| &t.decl);
                     let (build_hint, hint) =
-                        length_test(v.st.unskipped_fields.len(), &tests).build(b);
+                        length_test(v.st.unskipped_fields.len(), tests).build(b);
 
                     encode = quote! {{
                         #build_hint
@@ -445,16 +652,14 @@ fn encode_variant(
             }
 
             if let Packing::Tagged = en.enum_packing {
-                let encode_t_encode = &b.encode_t_encode;
-                let name = &v.name;
-                let name_type = en.name_type.ty();
+                let encode_tag = encode_tag(b, &name_expr, human_name.as_ref(), &tag_encoder);
 
                 encode = quote! {{
                     #encoder_t::encode_variant_fn(#encoder_var, move |#variant_encoder| {
                         let #tag_encoder = #variant_encoder_t::encode_tag(#variant_encoder)?;
-                        static #name_static: #name_type = #name;
+                        #name_decl
 
-                        #encode_t_encode(#name_expr, #tag_encoder)?;
+                        #encode_tag;
 
                         let #encoder_var = #variant_encoder_t::encode_data(#variant_encoder)?;
                         #encode;
@@ -467,12 +672,8 @@ fn encode_variant(
             tag_value,
             tag_type,
         } => {
-            let name = &v.name;
-
-            let name_type = en.name_type.ty();
-
             let decls = tests.iter().map(|t| &t.decl);
-            let mut len = length_test(v.st.unskipped_fields.len(), &tests);
+            let mut len = length_test(v.st.unskipped_fields.len(), tests);
 
             // Add one for the tag field.
             len.expressions.push(quote!(1));
@@ -486,7 +687,7 @@ fn encode_variant(
 
                 #encoder_t::encode_map_fn(#encoder_var, &#hint, move |#encoder_var| {
                     static #tag_static: #tag_type = #tag_value;
-                    static #name_static: #name_type = #name;
+                    #name_decl
                     #map_encoder_t::insert_entry(#encoder_var, #tag_static, #name_static)?;
                     #(#decls)*
                     #(#encoders)*
@@ -502,13 +703,9 @@ fn encode_variant(
         } => {
             let encode_t_encode = &b.encode_t_encode;
 
-            let name = &v.name;
-            let name_type = en.name_type.ty();
-
             let decls = tests.iter().map(|t| &t.decl);
 
-            let (build_hint, inner_hint) =
-                length_test(v.st.unskipped_fields.len(), &tests).build(b);
+            let (build_hint, inner_hint) = length_test(v.st.unskipped_fields.len(), tests).build(b);
             let struct_encoder = b.cx.ident("struct_encoder");
             let content_struct = b.cx.ident("content_struct");
             let pair = b.cx.ident("pair");
@@ -525,7 +722,7 @@ fn encode_variant(
                 #encoder_t::encode_map_fn(#encoder_var, &#hint, move |#struct_encoder| {
                     static #tag_static: #tag_type = #tag_value;
                     static #content_static: #content_type = #content_value;
-                    static #name_static: #name_type = #name;
+                    #name_decl
 
                     #map_encoder_t::insert_entry(#struct_encoder, #tag_static, #name_static)?;
 
@@ -550,24 +747,13 @@ fn encode_variant(
         }
     }
 
-    let pattern = syn::PatStruct {
-        attrs: Vec::new(),
-        qself: None,
-        path: v.st.path.clone(),
-        brace_token: syn::token::Brace::default(),
-        fields: v.patterns.clone(),
-        rest: None,
-    };
-
     if cx.trace {
         let output_var = b.cx.ident("output");
 
         let formatted_tag = en.name_type.name_format(&name_static);
-        let name_type = en.name_type.ty();
-        let name_value = &v.name;
 
         encode = quote! {{
-            static #name_static: #name_type = #name_value;
+            #name_decl
             #context_t::enter_variant(#ctx_var, #type_name, #formatted_tag);
             let #output_var = #encode;
             #context_t::leave_variant(#ctx_var);
@@ -575,7 +761,72 @@ fn encode_variant(
         }};
     }
 
-    Ok((pattern, encode))
+    if shared {
+        let last = variants.len() - 1;
+
+        let arms = variants.iter().enumerate().map(|(index, v)| {
+            let type_name = v.st.name;
+            let name = &v.name;
+            let human_name = v.human_name.iter();
+
+            // The last variant in the group is matched with a wildcard to keep
+            // the match exhaustive.
+            if index == last {
+                quote!(_ => (#type_name, #name #(, #human_name)*))
+            } else {
+                let path = &v.st.path;
+                quote!(#path { .. } => (#type_name, #name #(, #human_name)*))
+            }
+        });
+
+        let (human_name_var, human_name_type) = if en.human_readable {
+            (
+                Some(quote!(, #human_name_var)),
+                Some(quote!(, &'static str)),
+            )
+        } else {
+            (None, None)
+        };
+
+        let value = b.self_value();
+
+        encode = quote! {{
+            let (#type_name_var, #name_static #human_name_var): (&'static str, #name_type #human_name_type) = match #value {
+                #(#arms,)*
+            };
+
+            #encode
+        }};
+    }
+
+    Ok(encode)
+}
+
+/// Encode the tag of a variant.
+///
+/// If the variant has a human-readable name, it is used instead of the regular
+/// name when the encoder is human-readable.
+fn encode_tag(
+    b: &Build<'_, '_>,
+    name_expr: &syn::Expr,
+    human_name: Option<&TokenStream>,
+    encoder_var: &syn::Ident,
+) -> TokenStream {
+    let encode_t_encode = &b.encode_t_encode;
+
+    let Some(human_name) = human_name else {
+        return quote!(#encode_t_encode(#name_expr, #encoder_var)?);
+    };
+
+    let encoder_t = &b.tokens.encoder_t;
+
+    quote! {
+        if #encoder_t::capabilities(&#encoder_var).is_human_readable() {
+            #encode_t_encode(#human_name, #encoder_var)?
+        } else {
+            #encode_t_encode(#name_expr, #encoder_var)?
+        }
+    }
 }
 
 struct LengthTest {
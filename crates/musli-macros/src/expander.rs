@@ -354,10 +354,26 @@ impl<'a> Expander<'a> {
 
         let mut out = TokenStream::new();
 
+        let mut remote = None;
+        let mut const_packed = None;
+
         for build in builds {
+            if let (None, Some(path)) = (&remote, build.remote) {
+                remote = Some(crate::en::expand_remote_encode(&build, path));
+            }
+
+            if let (ModeKind::Binary, Some(span)) =
+                (&build.expansion.mode_ident.kind, build.const_packed)
+            {
+                const_packed = Some(crate::internals::const_packed(&build, span)?);
+            }
+
             out.extend(crate::en::expand_insert_entry(build)?);
         }
 
+        out.extend(remote);
+        out.extend(const_packed);
+
         Ok(out)
     }
 
@@ -369,10 +385,18 @@ impl<'a> Expander<'a> {
 
         let mut out = TokenStream::new();
 
+        let mut remote = None;
+
         for build in builds {
+            if let (None, Some(path)) = (&remote, build.remote) {
+                remote = Some(crate::de::expand_remote_decode(&build, path));
+            }
+
             out.extend(crate::de::expand_decode_entry(build)?);
         }
 
+        out.extend(remote);
+
         Ok(out)
     }
 }
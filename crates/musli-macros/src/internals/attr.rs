@@ -6,11 +6,12 @@ use quote::ToTokens;
 use syn::meta::ParseNestedMeta;
 use syn::parse::{Parse, ParseStream};
 use syn::spanned::Spanned;
-use syn::Token;
+use syn::{token, Token};
 
 use crate::expander::{NameMethod, NameType};
 
 use super::build;
+use super::tokens::Import;
 use super::ATTR;
 use super::{Ctxt, ImportedMethod, Mode, NameAll};
 
@@ -70,6 +71,49 @@ pub(crate) enum EnumTagging<'a> {
     },
 }
 
+/// A capability which a type requires from the decoder it's decoded with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Requirement {
+    /// `#[musli(requires(skip))]`.
+    Skip,
+}
+
+impl Requirement {
+    /// The name of the corresponding constant in `Capabilities`.
+    pub(crate) fn capability(self) -> &'static str {
+        match self {
+            Requirement::Skip => "CAN_SKIP",
+        }
+    }
+
+    /// A description of what the decoder must be able to do.
+    pub(crate) fn description(self) -> &'static str {
+        match self {
+            Requirement::Skip => "skip over unknown values",
+        }
+    }
+}
+
+/// How a type is converted from its representation through
+/// `#[musli(from = <type>)]` or `#[musli(try_from = <type>)]`.
+#[derive(Debug, Clone)]
+pub(crate) enum FromRepr {
+    /// Convert infallibly through `From`.
+    From(syn::Type),
+    /// Convert fallibly through `TryFrom`.
+    TryFrom(syn::Type),
+}
+
+impl FromRepr {
+    /// The type of the representation being converted from.
+    pub(crate) fn ty(&self) -> &syn::Type {
+        match self {
+            FromRepr::From(ty) => ty,
+            FromRepr::TryFrom(ty) => ty,
+        }
+    }
+}
+
 /// If the type is tagged or not.
 #[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum Packing {
@@ -159,6 +203,14 @@ macro_rules! layer {
             $multiple: Vec<(Span, $multiple_ty)>,)*)*
         }
 
+        impl $new {
+            /// Test if no attributes were specified.
+            #[allow(unused)]
+            fn is_empty(&self) -> bool {
+                true $(&& self.$single.is_empty())* $($(&& self.$multiple.is_empty())*)*
+            }
+        }
+
         #[derive(Default)]
         struct $layer {
             $(
@@ -220,6 +272,8 @@ layer! {
         krate: syn::Path,
         /// `#[musli(name_all = "..")]`.
         name_all: NameAll,
+        /// `#[musli(human_name_all = "..")]`.
+        human_name_all: NameAll,
         /// `#[musli(name(type = <type>))]`.
         #[example = "name(type = <type>)"]
         name_type: syn::Type,
@@ -255,6 +309,19 @@ layer! {
         content_format_with: syn::Path,
         /// `#[musli(packed)]` or `#[musli(transparent)]`.
         packing: Packing,
+        /// `#[musli(const_packed)]`.
+        const_packed: (),
+        /// `#[musli(deny_unknown_fields)]`.
+        deny_unknown_fields: (),
+        /// `#[musli(into = <type>)]`.
+        #[example = "into = <type>"]
+        repr_into: syn::Type,
+        /// `#[musli(from = <type>)]` or `#[musli(try_from = <type>)]`.
+        #[example = "from = <type>"]
+        repr_from: FromRepr,
+        /// `#[musli(remote = <path>)]`.
+        #[example = "remote = <path>"]
+        remote: syn::Path,
         @multiple
         /// Bounds in a where predicate.
         bounds: syn::WherePredicate,
@@ -264,6 +331,8 @@ layer! {
         decode_bounds_lifetimes: syn::Lifetime,
         /// Types specified.
         decode_bounds_types: syn::Ident,
+        /// Capabilities required from the decoder.
+        requires: Requirement,
     }
 }
 
@@ -443,6 +512,68 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
                 return Ok(());
             }
 
+            // #[musli(const_packed)]
+            if meta.path.is_ident("const_packed") {
+                new.const_packed.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // #[musli(deny_unknown_fields)]
+            if meta.path.is_ident("deny_unknown_fields") {
+                new.deny_unknown_fields.push((meta.path.span(), ()));
+                return Ok(());
+            }
+
+            // #[musli(into = <type>)]
+            if meta.path.is_ident("into") {
+                meta.input.parse::<Token![=]>()?;
+                new.repr_into.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // #[musli(from = <type>)]
+            if meta.path.is_ident("from") {
+                meta.input.parse::<Token![=]>()?;
+                let ty = meta.input.parse()?;
+                new.repr_from.push((meta.path.span(), FromRepr::From(ty)));
+                return Ok(());
+            }
+
+            // #[musli(try_from = <type>)]
+            if meta.path.is_ident("try_from") {
+                meta.input.parse::<Token![=]>()?;
+                let ty = meta.input.parse()?;
+                new.repr_from
+                    .push((meta.path.span(), FromRepr::TryFrom(ty)));
+                return Ok(());
+            }
+
+            // #[musli(remote = <path>)]
+            if meta.path.is_ident("remote") {
+                meta.input.parse::<Token![=]>()?;
+                new.remote.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
+            // #[musli(requires(..))]
+            if meta.path.is_ident("requires") {
+                meta.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        new.requires.push((meta.path.span(), Requirement::Skip));
+                        return Ok(());
+                    }
+
+                    Err(syn::Error::new_spanned(
+                        meta.path,
+                        format_args!(
+                            "#[{ATTR}(requires(..))] Unsupported capability, expected `skip`"
+                        ),
+                    ))
+                })?;
+
+                return Ok(());
+            }
+
             // #[musli(name_all = "..")]
             if meta.path.is_ident("name_all") {
                 new.name_all
@@ -450,6 +581,13 @@ pub(crate) fn type_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> TypeAttr {
                 return Ok(());
             }
 
+            // #[musli(human_name_all = "..")]
+            if meta.path.is_ident("human_name_all") {
+                new.human_name_all
+                    .push((meta.path.span(), parse_name_all(&meta)?));
+                return Ok(());
+            }
+
             Err(syn::Error::new_spanned(
                 meta.path,
                 format_args!("#[{ATTR}] Unsupported type attribute"),
@@ -570,6 +708,9 @@ layer! {
         packing: Packing,
         /// `#[musli(default)]`.
         default_variant: (),
+        @multiple
+        /// `#[musli(alias = ..)]`.
+        alias: syn::Expr,
     }
 }
 
@@ -627,6 +768,13 @@ pub(crate) fn variant_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> VariantAttr
                 return Ok(());
             }
 
+            // #[musli(alias = <expr>)]
+            if meta.path.is_ident("alias") {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             // #[musli(default)]
             if meta.path.is_ident("default") {
                 new.default_variant.push((meta.path.span(), ()));
@@ -709,22 +857,47 @@ layer! {
         /// Pattern used to match the given field when decoding.
         pattern: syn::Pat,
         /// Use a default value for the field if it's not available.
-        is_default: Option<syn::Path>,
-        /// Use a default value for the field if it's not available.
-        skip: (),
+        is_default: DefaultValue,
+        /// Skip the field, or include it in a mode which is listed in
+        /// `#[musli(modes(..))]`.
+        skip: bool,
         /// Field encoding to use.
         encoding: FieldEncoding,
+        /// Value used to represent `None` for an optional field.
+        niche: syn::Expr,
+        /// Capture unknown entries into this field.
+        flatten: (),
+        @multiple
+        /// Additional names accepted when decoding the field.
+        alias: syn::Expr,
     }
 }
 
 impl Field {
+    /// Get the span of a `#[musli(skip)]` attribute which only applies to one
+    /// of encoding or decoding.
+    pub(crate) fn one_sided_skip(&self, mode: &Mode<'_>) -> Option<Span> {
+        self.by_mode(mode, |m| {
+            match (&m.skip.any, &m.skip.encode, &m.skip.decode) {
+                (None, Some((span, true)), None) | (None, None, Some((span, true))) => Some(span),
+                _ => None,
+            }
+        })
+        .copied()
+    }
+
     /// Expand encode of the given field.
     pub(crate) fn encode_path_expanded<'a>(
         &self,
         mode: &Mode<'a>,
         span: Span,
     ) -> (Span, DefaultOrCustom<'a>) {
-        if let Some((span, encode_path)) = self.encode_path(mode) {
+        if let Some((span, niche)) = self.niche(mode) {
+            (
+                *span,
+                DefaultOrCustom::Niche(mode.niche_encode, niche.clone()),
+            )
+        } else if let Some((span, encode_path)) = self.encode_path(mode) {
             (*span, DefaultOrCustom::Custom(encode_path.clone()))
         } else {
             let field_encoding = self.encoding(mode).map(|&(_, e)| e).unwrap_or_default();
@@ -740,7 +913,12 @@ impl Field {
         span: Span,
         allocator_ident: &syn::Ident,
     ) -> (Span, DefaultOrCustom<'a>) {
-        if let Some((span, decode_path)) = self.decode_path(mode) {
+        if let Some((span, niche)) = self.niche(mode) {
+            (
+                *span,
+                DefaultOrCustom::Niche(mode.niche_decode, niche.clone()),
+            )
+        } else if let Some((span, decode_path)) = self.decode_path(mode) {
             (*span, DefaultOrCustom::Custom(decode_path.clone()))
         } else {
             let field_encoding = self.encoding(mode).map(|&(_, e)| e).unwrap_or_default();
@@ -762,6 +940,7 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
         let mut new = FieldNew::default();
         let mut mode = None;
         let mut only = None;
+        let mut modes = None;
 
         let result = a.parse_nested_meta(|meta| {
             // #[musli(mode = <path>)]
@@ -771,13 +950,26 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            // #[musli(modes(<path>, ..))]
+            if meta.path.is_ident("modes") {
+                let mut list = Vec::new();
+
+                meta.parse_nested_meta(|meta| {
+                    list.push(mode_ident(meta.path.require_ident()?.clone()));
+                    Ok(())
+                })?;
+
+                modes = Some((meta.path.span(), list));
+                return Ok(());
+            }
+
             if meta.path.is_ident("encode_only") {
-                only = Some(Only::Encode);
+                only = Some((meta.path.span(), Only::Encode));
                 return Ok(());
             }
 
             if meta.path.is_ident("decode_only") {
-                only = Some(Only::Decode);
+                only = Some((meta.path.span(), Only::Decode));
                 return Ok(());
             }
 
@@ -815,6 +1007,13 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            // #[musli(niche = <expr>)]
+            if meta.path.is_ident("niche") {
+                meta.input.parse::<Token![=]>()?;
+                new.niche.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             // #[musli(skip_encoding_if = <path>)]
             if meta.path.is_ident("skip_encoding_if") {
                 meta.input.parse::<Token![=]>()?;
@@ -845,21 +1044,43 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
                 return Ok(());
             }
 
+            // #[musli(alias = <expr>)]
+            if meta.path.is_ident("alias") {
+                meta.input.parse::<Token![=]>()?;
+                new.alias.push((meta.path.span(), meta.input.parse()?));
+                return Ok(());
+            }
+
             // #[musli(default)]
             if meta.path.is_ident("default") {
                 if meta.input.parse::<Option<Token![=]>>()?.is_some() {
                     new.is_default
-                        .push((meta.path.span(), Some(meta.input.parse()?)));
+                        .push((meta.path.span(), DefaultValue::Path(meta.input.parse()?)));
                 } else {
-                    new.is_default.push((meta.path.span(), None));
+                    new.is_default
+                        .push((meta.path.span(), DefaultValue::Default));
                 }
 
                 return Ok(());
             }
 
+            // #[musli(default_with_cx = <path>)]
+            if meta.path.is_ident("default_with_cx") {
+                meta.input.parse::<Token![=]>()?;
+                new.is_default
+                    .push((meta.path.span(), DefaultValue::WithCx(meta.input.parse()?)));
+                return Ok(());
+            }
+
             // #[musli(skip)]
             if meta.path.is_ident("skip") {
-                new.skip.push((meta.path.span(), ()));
+                new.skip.push((meta.path.span(), true));
+                return Ok(());
+            }
+
+            // #[musli(flatten)]
+            if meta.path.is_ident("flatten") {
+                new.flatten.push((meta.path.span(), ()));
                 return Ok(());
             }
 
@@ -891,6 +1112,53 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
             cx.syn_error(error);
         }
 
+        // A field limited with #[musli(modes(..))] is skipped by default, and
+        // then explicitly included in each of the listed modes.
+        let modes = match modes {
+            Some((span, _)) if mode.is_some() => {
+                cx.error_span(
+                    span,
+                    format_args!(
+                        "#[{ATTR}(modes(..))] cannot be combined with #[{ATTR}(mode = ..)]"
+                    ),
+                );
+
+                None
+            }
+            Some((span, list)) => {
+                new.skip.push((span, true));
+                Some((span, list))
+            }
+            None => None,
+        };
+
+        // A bare #[musli(encode_only)] or #[musli(decode_only)] marks a field
+        // which is only used in one direction, by skipping it in the other.
+        let only = match only {
+            Some((span, only)) if new.is_empty() => {
+                new.skip.push((span, true));
+
+                Some(match only {
+                    Only::Encode => Only::Decode,
+                    Only::Decode => Only::Encode,
+                })
+            }
+            only => only.map(|(_, only)| only),
+        };
+
+        if let Some((span, list)) = modes {
+            for mode in list {
+                let include = FieldNew {
+                    skip: vec![(span, false)],
+                    ..FieldNew::default()
+                };
+
+                let out = attr.modes.entry(mode.kind.clone()).or_default();
+                cx.register_mode(mode);
+                out.merge_with(cx, include, only);
+            }
+        }
+
         let attr = match mode {
             Some(mode) => {
                 let out = attr.modes.entry(mode.kind.clone()).or_default();
@@ -907,7 +1175,10 @@ pub(crate) fn field_attrs(cx: &Ctxt, attrs: &[syn::Attribute]) -> Field {
 }
 
 fn parse_mode(meta: &ParseNestedMeta<'_>) -> syn::Result<ModeIdent> {
-    let ident: syn::Ident = meta.input.parse()?;
+    Ok(mode_ident(meta.input.parse()?))
+}
+
+fn mode_ident(ident: syn::Ident) -> ModeIdent {
     let s = ident.to_string();
 
     let kind = match s.as_str() {
@@ -916,7 +1187,7 @@ fn parse_mode(meta: &ParseNestedMeta<'_>) -> syn::Result<ModeIdent> {
         other => ModeKind::Custom(other.into()),
     };
 
-    Ok(ModeIdent { ident, kind })
+    ModeIdent { ident, kind }
 }
 
 #[derive(Clone, Default)]
@@ -1027,12 +1298,25 @@ impl TypeConfig {
     }
 }
 
+/// How the default value of a field is constructed.
+pub(crate) enum DefaultValue {
+    /// `#[musli(default)]`, which uses `Default::default`.
+    Default,
+    /// `#[musli(default = <path>)]`, which calls the given function.
+    Path(syn::Path),
+    /// `#[musli(default_with_cx = <path>)]`, which calls the given function
+    /// with the context.
+    WithCx(syn::Path),
+}
+
 /// A default or custom path to use.
 pub(crate) enum DefaultOrCustom<'a> {
     /// A default method call from [`Tokens`][super::Tokens].
     Default(ImportedMethod<'a>),
     /// A custom specified path.
     Custom(syn::Path),
+    /// A niche helper, called with the value representing `None`.
+    Niche(Import<'a>, syn::Expr),
 }
 
 impl DefaultOrCustom<'_> {
@@ -1048,6 +1332,10 @@ impl ToTokens for DefaultOrCustom<'_> {
         match self {
             DefaultOrCustom::Default(method) => method.to_tokens(tokens),
             DefaultOrCustom::Custom(path) => path.to_tokens(tokens),
+            DefaultOrCustom::Niche(function, niche) => {
+                function.to_tokens(tokens);
+                token::Paren::default().surround(tokens, |tokens| niche.to_tokens(tokens));
+            }
         }
     }
 }
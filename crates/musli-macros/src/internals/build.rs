@@ -1,6 +1,8 @@
+use std::collections::HashMap;
 use std::rc::Rc;
 
-use proc_macro2::Span;
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
 use syn::Token;
@@ -10,11 +12,14 @@ use crate::expander::{
     UnsizedMethod, VariantData,
 };
 
-use super::attr::{DefaultOrCustom, EnumTagging, FieldEncoding, ModeKind, Packing};
+use super::attr::{
+    DefaultOrCustom, DefaultValue, EnumTagging, FieldEncoding, FromRepr, ModeKind, Packing,
+    Requirement,
+};
 use super::mode::ImportedMethod;
 use super::name::NameAll;
 use super::ATTR;
-use super::{Ctxt, Expansion, Mode, Result, Tokens};
+use super::{Ctxt, Expansion, Mode, Only, Result, Tokens};
 
 pub(crate) struct Parameters {
     pub(crate) lt: syn::Lifetime,
@@ -29,6 +34,18 @@ pub(crate) struct Build<'tok, 'a> {
     pub(crate) cx: &'a Ctxt,
     pub(crate) bounds: &'a [(Span, syn::WherePredicate)],
     pub(crate) decode_bounds: &'a [(Span, syn::WherePredicate)],
+    pub(crate) requires: &'a [(Span, Requirement)],
+    /// Encode through a conversion into a representation using
+    /// `#[musli(into = <type>)]`.
+    pub(crate) into: Option<&'a syn::Type>,
+    /// Decode through a conversion from a representation using
+    /// `#[musli(from = <type>)]` or `#[musli(try_from = <type>)]`.
+    pub(crate) from: Option<&'a FromRepr>,
+    /// Implement encoding for the remote type with `#[musli(remote = <path>)]`
+    /// instead of the type itself.
+    pub(crate) remote: Option<&'a syn::Path>,
+    /// Generate a const encoding function with `#[musli(const_packed)]`.
+    pub(crate) const_packed: Option<Span>,
     pub(crate) expansion: Expansion<'a>,
     pub(crate) data: BuildData<'a>,
     pub(crate) decode_t_decode: ImportedMethod<'a>,
@@ -39,6 +56,26 @@ pub(crate) struct Build<'tok, 'a> {
 }
 
 impl Build<'_, '_> {
+    /// The value being encoded, which is `self` unless a remote type is being
+    /// encoded.
+    pub(crate) fn self_value(&self) -> TokenStream {
+        match self.remote {
+            Some(..) => {
+                let ident = syn::Ident::new(REMOTE_VALUE, Span::call_site());
+                quote!(#ident)
+            }
+            None => quote!(self),
+        }
+    }
+
+    /// The path used to construct the type being decoded.
+    pub(crate) fn self_path(&self) -> syn::Path {
+        match self.remote {
+            Some(remote) => remote_path(remote),
+            None => syn::Path::from(syn::Ident::new("Self", Span::call_site())),
+        }
+    }
+
     /// Emit diagnostics for when we try to implement `Decode` for an enum which
     /// is marked as `#[musli(transparent)]`.
     pub(crate) fn encode_transparent_enum_diagnostics(&self, span: Span) {
@@ -68,6 +105,41 @@ impl Build<'_, '_> {
         );
     }
 
+    /// Reference paths from field attributes which are only used by the
+    /// opposite derive, so that they are not reported as unused when only one
+    /// of `Encode` or `Decode` is derived.
+    pub(crate) fn opposite_paths(&self) -> TokenStream {
+        let fields: Vec<&Field<'_>> = match &self.data {
+            BuildData::Struct(st) => st.all_fields.iter().map(|f| &**f).collect(),
+            BuildData::Enum(en) => en
+                .variants
+                .iter()
+                .flat_map(|v| v.st.all_fields.iter().map(|f| &**f))
+                .collect(),
+        };
+
+        let mut out = TokenStream::new();
+
+        for f in fields {
+            let ty = f.ty;
+
+            match self.mode.only {
+                Only::Encode => {
+                    if let Some((_, DefaultValue::Path(path))) = f.default_attr {
+                        out.extend(quote!(let _ = || -> #ty { #path() };));
+                    }
+                }
+                Only::Decode => {
+                    if let Some((_, path)) = f.skip_encoding_if {
+                        out.extend(quote!(let _ = |value: &#ty| -> bool { #path(value) };));
+                    }
+                }
+            }
+        }
+
+        out
+    }
+
     /// Validate encode attributes.
     pub(crate) fn validate_encode(&self) -> Result<()> {
         self.validate()
@@ -110,6 +182,10 @@ pub(crate) struct Body<'a> {
     pub(crate) name: &'a syn::LitStr,
     pub(crate) unskipped_fields: Vec<Rc<Field<'a>>>,
     pub(crate) all_fields: Vec<Rc<Field<'a>>>,
+    /// Field which captures unknown entries through `#[musli(flatten)]`.
+    pub(crate) flatten: Option<Rc<Field<'a>>>,
+    /// Error on unknown fields through `#[musli(deny_unknown_fields)]`.
+    pub(crate) deny_unknown_fields: Option<Span>,
     pub(crate) name_type: NameType<'a>,
     pub(crate) packing: Packing,
     pub(crate) kind: StructKind,
@@ -121,9 +197,57 @@ impl Body<'_> {
         if self.packing == Packing::Transparent && !matches!(&self.unskipped_fields[..], [_]) {
             cx.transparent_diagnostics(self.span, &self.unskipped_fields);
         }
+
+        validate_aliases(
+            cx,
+            "field",
+            self.unskipped_fields.iter().map(|f| (&f.name, f.aliases)),
+        );
+    }
+}
+
+/// Validate that no alias is the same as the name or alias of another field or
+/// variant, since that would make decoding ambiguous.
+fn validate_aliases<'a, I>(cx: &Ctxt, what: &str, items: I)
+where
+    I: IntoIterator<Item = (&'a syn::Expr, &'a [(Span, syn::Expr)])>,
+{
+    let items = items.into_iter().collect::<Vec<_>>();
+    let mut names = HashMap::new();
+
+    for (index, (name, _)) in items.iter().enumerate() {
+        names.insert(name_key(name), index);
+    }
+
+    for (index, (_, aliases)) in items.iter().enumerate() {
+        for (span, alias) in aliases.iter() {
+            if names.insert(name_key(alias), index).is_some() {
+                cx.error_span(
+                    *span,
+                    format_args!(
+                        "#[{ATTR}(alias = ..)] is already used as the name or alias of a {what}"
+                    ),
+                );
+            }
+        }
     }
 }
 
+/// Key used to compare names, so that for example `1` and `1usize` are treated
+/// as the same name.
+fn name_key(expr: &syn::Expr) -> String {
+    if let syn::Expr::Lit(syn::ExprLit { lit, .. }) = expr {
+        match lit {
+            syn::Lit::Str(lit) => return format!("{:?}", lit.value()),
+            syn::Lit::ByteStr(lit) => return format!("{:?}", lit.value()),
+            syn::Lit::Int(lit) => return lit.base10_digits().to_owned(),
+            _ => {}
+        }
+    }
+
+    expr.to_token_stream().to_string()
+}
+
 pub(crate) struct Enum<'a> {
     pub(crate) span: Span,
     pub(crate) name: &'a syn::LitStr,
@@ -133,13 +257,20 @@ pub(crate) struct Enum<'a> {
     pub(crate) fallback: Option<&'a syn::Ident>,
     pub(crate) name_type: NameType<'a>,
     pub(crate) packing_span: Option<&'a (Span, Packing)>,
+    /// If variants are named differently in human-readable formats through
+    /// `#[musli(human_name_all = "..")]`.
+    pub(crate) human_readable: bool,
 }
 
 pub(crate) struct Variant<'a> {
     pub(crate) span: Span,
     pub(crate) index: usize,
     pub(crate) name: syn::Expr,
+    /// The name of the variant used in human-readable formats.
+    pub(crate) human_name: Option<syn::LitStr>,
     pub(crate) pattern: Option<&'a syn::Pat>,
+    /// Additional names accepted when decoding the variant.
+    pub(crate) aliases: &'a [(Span, syn::Expr)],
     pub(crate) st: Body<'a>,
     pub(crate) patterns: Punctuated<syn::FieldPat, Token![,]>,
 }
@@ -151,19 +282,62 @@ pub(crate) struct Field<'a> {
     pub(crate) decode_path: (Span, DefaultOrCustom<'a>),
     pub(crate) name: syn::Expr,
     pub(crate) pattern: Option<&'a syn::Pat>,
+    /// Additional names accepted when decoding the field.
+    pub(crate) aliases: &'a [(Span, syn::Expr)],
     /// Skip field entirely and always initialize with the specified expresion,
     /// or default value through `default_attr`.
     pub(crate) skip: Option<Span>,
     pub(crate) skip_encoding_if: Option<&'a (Span, syn::Path)>,
     /// Fill with default value, if missing.
-    pub(crate) default_attr: Option<(Span, Option<&'a syn::Path>)>,
+    pub(crate) default_attr: Option<(Span, &'a DefaultValue)>,
+    /// Capture unknown entries into this field.
+    pub(crate) flatten: Option<Span>,
     pub(crate) self_access: syn::Expr,
     pub(crate) member: syn::Member,
     pub(crate) packing: Packing,
+    /// Field encoding, as configured through `#[musli(packed)]`,
+    /// `#[musli(bytes)]` or `#[musli(trace)]`.
+    pub(crate) encoding: FieldEncoding,
     pub(crate) var: syn::Ident,
     pub(crate) ty: &'a syn::Type,
 }
 
+impl Field<'_> {
+    /// Construct an expression for the default value of the field, as
+    /// configured through `#[musli(default)]`.
+    pub(crate) fn default_value(
+        &self,
+        span: Span,
+        ctx_var: &syn::Ident,
+        default_function: impl ToTokens,
+    ) -> TokenStream {
+        let ty = self.ty;
+
+        match self.default_attr {
+            Some((_, DefaultValue::Path(path))) => quote_spanned!(span => #path()),
+            Some((_, DefaultValue::WithCx(path))) => quote_spanned!(span => #path(#ctx_var)),
+            Some((_, DefaultValue::Default)) | None => {
+                quote_spanned!(span => #default_function::<#ty>())
+            }
+        }
+    }
+}
+
+/// The name of the argument holding the value of a remote type being encoded.
+const REMOTE_VALUE: &str = "__remote";
+
+/// Construct the path of a remote type as used in expressions and patterns,
+/// where its generic arguments are inferred.
+fn remote_path(remote: &syn::Path) -> syn::Path {
+    let mut path = remote.clone();
+
+    if let Some(last) = path.segments.last_mut() {
+        last.arguments = syn::PathArguments::None;
+    }
+
+    path
+}
+
 /// Setup a build.
 ///
 /// Handles mode decoding, and construction of parameters which might give rise to errors.
@@ -192,7 +366,25 @@ pub(crate) fn setup<'tok, 'a>(
 
     let bounds = e.type_attr.bounds(&mode);
     let decode_bounds = e.type_attr.decode_bounds(&mode);
+    let requires = e.type_attr.requires(&mode);
+    let into = e.type_attr.repr_into(&mode).map(|(_, ty)| ty);
+    let from = e.type_attr.repr_from(&mode).map(|(_, from)| from);
+    let remote = e.type_attr.remote(&mode);
+
+    if let Some(&(span, _)) = remote {
+        if into.is_some() || from.is_some() {
+            e.cx.error_span(
+                span,
+                format_args!("#[{ATTR}(remote)] cannot be combined with #[{ATTR}(into)], #[{ATTR}(from)] or #[{ATTR}(try_from)]"),
+            );
+
+            return Err(());
+        }
+    }
+
+    let remote = remote.map(|(_, path)| path);
     let enum_tagging_span = e.type_attr.enum_tagging_span(&mode);
+    let const_packed = e.type_attr.const_packed(&mode).map(|&(span, ())| span);
 
     Ok(Build {
         mode,
@@ -200,6 +392,11 @@ pub(crate) fn setup<'tok, 'a>(
         cx: &e.cx,
         bounds,
         decode_bounds,
+        requires,
+        into,
+        from,
+        remote,
+        const_packed,
         expansion,
         data,
         decode_t_decode,
@@ -219,6 +416,13 @@ fn setup_struct<'a>(
     let mut unskipped_fields = Vec::with_capacity(data.fields.len());
     let mut all_fields = Vec::with_capacity(data.fields.len());
 
+    if let Some(&(span, _)) = e.type_attr.human_name_all(mode) {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}(human_name_all)] is only supported on enums"),
+        );
+    }
+
     let packing = e
         .type_attr
         .packing(mode)
@@ -230,6 +434,17 @@ fn setup_struct<'a>(
             let name_all = NameAll::Index;
             (name_all, name_all.ty(), NameMethod::Sized)
         }
+        _ if e.type_attr.is_name_type_ambiguous(mode) => {
+            match determine_names_type(data.fields.iter().map(|f| f.attr.name(mode))) {
+                Some(ty) => (NameAll::Index, ty, NameMethod::Sized),
+                None => split_name(
+                    mode.kind,
+                    e.type_attr.name_type(mode),
+                    e.type_attr.name_all(mode),
+                    e.type_attr.name_method(mode),
+                ),
+            }
+        }
         _ => split_name(
             mode.kind,
             e.type_attr.name_type(mode),
@@ -238,7 +453,12 @@ fn setup_struct<'a>(
         ),
     };
 
-    let path = syn::Path::from(syn::Ident::new("Self", e.input.ident.span()));
+    let path = match e.type_attr.remote(mode) {
+        Some((_, remote)) => remote_path(remote),
+        None => syn::Path::from(syn::Ident::new("Self", e.input.ident.span())),
+    };
+
+    let mut flatten = None::<Rc<Field<'a>>>;
 
     for f in &data.fields {
         let field = Rc::new(setup_field(
@@ -251,18 +471,43 @@ fn setup_struct<'a>(
             allocator_ident,
         ));
 
-        if field.skip.is_none() {
+        if let Some(span) = field.flatten {
+            if flatten.is_some() {
+                e.cx.error_span(
+                    span,
+                    format_args!("#[{ATTR}(flatten)] can only be used on one field"),
+                );
+            }
+
+            flatten = Some(field.clone());
+        } else if field.skip.is_none() {
             unskipped_fields.push(field.clone());
         }
 
         all_fields.push(field);
     }
 
+    let deny_unknown_fields = e
+        .type_attr
+        .deny_unknown_fields(mode)
+        .map(|&(span, ())| span);
+
+    if let (Some(span), Some(..)) = (deny_unknown_fields, &flatten) {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "#[{ATTR}(deny_unknown_fields)] cannot be combined with #[{ATTR}(flatten)]"
+            ),
+        );
+    }
+
     let body = Body {
         span: data.span,
         name: &data.name,
         unskipped_fields,
         all_fields,
+        flatten,
+        deny_unknown_fields,
         name_type: NameType {
             ty: name_type,
             method: name_method,
@@ -288,6 +533,13 @@ fn setup_enum<'a>(
 
     let packing_span = e.type_attr.packing(mode);
 
+    if let Some(&(span, ())) = e.type_attr.deny_unknown_fields(mode) {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}(deny_unknown_fields)] is only supported on structs"),
+        );
+    }
+
     let enum_tagging = match e.type_attr.enum_tagging(mode) {
         Some(enum_tagging) => enum_tagging,
         None => {
@@ -322,17 +574,69 @@ fn setup_enum<'a>(
         .map(|&(_, p)| p)
         .unwrap_or_default();
 
-    let (_, name_type, name_method) = split_name(
-        mode.kind,
-        e.type_attr.name_type(mode),
-        e.type_attr.name_all(mode),
-        e.type_attr.name_method(mode),
-    );
+    let names_type = e
+        .type_attr
+        .is_name_type_ambiguous(mode)
+        .then(|| determine_names_type(data.variants.iter().map(|v| v.attr.name_expr(mode))))
+        .flatten();
+
+    let (_, name_type, name_method) = match names_type {
+        Some(ty) => (NameAll::Index, ty, NameMethod::Sized),
+        None => split_name(
+            mode.kind,
+            e.type_attr.name_type(mode),
+            e.type_attr.name_all(mode),
+            e.type_attr.name_method(mode),
+        ),
+    };
+
+    let human_name_all = match e.type_attr.human_name_all(mode) {
+        Some(&(span, NameAll::Index)) => {
+            e.cx.error_span(
+                span,
+                format_args!("#[{ATTR}(human_name_all)] must name variants by string"),
+            );
+
+            None
+        }
+        Some(&(span, _)) if !matches!(enum_tagging, EnumTagging::Default | EnumTagging::Empty) => {
+            e.cx.error_span(
+                span,
+                format_args!("#[{ATTR}(human_name_all)] cannot be combined with #[{ATTR}(tag)] or #[{ATTR}(content)]"),
+            );
+
+            None
+        }
+        Some(&(_, name_all)) => Some(name_all),
+        None => None,
+    };
 
     for v in &data.variants {
-        variants.push(setup_variant(e, mode, v, &mut fallback, allocator_ident));
+        variants.push(setup_variant(
+            e,
+            mode,
+            v,
+            &mut fallback,
+            human_name_all,
+            allocator_ident,
+        ));
     }
 
+    if let (Some((span, _)), Some(..), EnumTagging::Default) =
+        (e.type_attr.human_name_all(mode), fallback, &enum_tagging)
+    {
+        e.cx.error_span(
+            *span,
+            format_args!("#[{ATTR}(human_name_all)] cannot be combined with a #[{ATTR}(default)] variant unless all variants are empty"),
+        );
+    }
+
+    validate_aliases(
+        &e.cx,
+        "variant",
+        variants.iter().map(|v| (&v.name, v.aliases)),
+    );
+
     Enum {
         span: data.span,
         name: &data.name,
@@ -346,6 +650,7 @@ fn setup_enum<'a>(
             format_with: e.type_attr.name_format_with(mode),
         },
         packing_span,
+        human_readable: human_name_all.is_some(),
     }
 }
 
@@ -354,6 +659,7 @@ fn setup_variant<'a>(
     mode: &Mode<'a>,
     data: &'a VariantData<'a>,
     fallback: &mut Option<&'a syn::Ident>,
+    human_name_all: Option<NameAll>,
     allocator_ident: &syn::Ident,
 ) -> Variant<'a> {
     let mut unskipped_fields = Vec::with_capacity(data.fields.len());
@@ -371,6 +677,17 @@ fn setup_variant<'a>(
             let name_all = NameAll::Index;
             (name_all, name_all.ty(), NameMethod::Sized)
         }
+        _ if data.attr.is_name_type_ambiguous(mode) => {
+            match determine_names_type(data.fields.iter().map(|f| f.attr.name(mode))) {
+                Some(ty) => (NameAll::Index, ty, NameMethod::Sized),
+                None => split_name(
+                    mode.kind,
+                    data.attr.name_type(mode),
+                    data.attr.name_all(mode),
+                    data.attr.name_method(mode),
+                ),
+            }
+        }
         _ => split_name(
             mode.kind,
             data.attr.name_type(mode),
@@ -388,9 +705,20 @@ fn setup_variant<'a>(
 
     let name = expander::expand_name(data, mode, type_name_all, Some(data.ident));
 
+    let human_name = human_name_all.and_then(|name_all| {
+        let name = name_all.apply(&data.ident.to_string())?;
+        Some(syn::LitStr::new(&name, data.ident.span()))
+    });
+
     let pattern = data.attr.pattern(mode).map(|(_, p)| p);
+    let aliases = data.attr.alias(mode);
+    validate_alias_pattern(e, pattern, aliases);
+
+    let mut path = match e.type_attr.remote(mode) {
+        Some((_, remote)) => remote_path(remote),
+        None => syn::Path::from(syn::Ident::new("Self", data.span)),
+    };
 
-    let mut path = syn::Path::from(syn::Ident::new("Self", data.span));
     path.segments.push(data.ident.clone().into());
 
     if let Some((span, _)) = data.attr.default_variant(mode) {
@@ -422,6 +750,13 @@ fn setup_variant<'a>(
             allocator_ident,
         ));
 
+        if let Some(span) = field.flatten {
+            e.cx.error_span(
+                span,
+                format_args!("#[{ATTR}(flatten)] is only supported on the fields of structs"),
+            );
+        }
+
         if field.skip.is_none() {
             unskipped_fields.push(field.clone());
         }
@@ -434,6 +769,8 @@ fn setup_variant<'a>(
         name: &data.name,
         unskipped_fields,
         all_fields,
+        flatten: None,
+        deny_unknown_fields: None,
         packing: variant_packing,
         kind: data.kind,
         name_type: NameType {
@@ -450,12 +787,76 @@ fn setup_variant<'a>(
         span: data.span,
         index: data.index,
         name,
+        human_name,
         pattern,
+        aliases,
         patterns,
         st,
     }
 }
 
+/// Aliases are matched alongside the name, which a custom pattern replaces.
+fn validate_alias_pattern(e: &Expander, pattern: Option<&syn::Pat>, aliases: &[(Span, syn::Expr)]) {
+    if let (Some(..), [(span, _), ..]) = (pattern, aliases) {
+        e.cx.error_span(
+            *span,
+            format_args!("#[{ATTR}(alias = ..)] cannot be combined with #[{ATTR}(pattern = ..)]"),
+        );
+    }
+}
+
+/// Validate that `#[musli(flatten)]` isn't combined with attributes which it
+/// doesn't support.
+fn validate_flatten(
+    e: &Expander,
+    data: &FieldData<'_>,
+    mode: &Mode<'_>,
+    span: Span,
+    packing: Packing,
+) {
+    if packing != Packing::Tagged {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "#[{ATTR}(flatten)] cannot be used in #[{ATTR}(packed)] or #[{ATTR}(transparent)] containers"
+            ),
+        );
+    }
+
+    let encoding = match data.attr.encoding(mode) {
+        Some((_, FieldEncoding::Packed)) => Some("packed"),
+        Some((_, FieldEncoding::Bytes)) => Some("bytes"),
+        Some((_, FieldEncoding::Trace)) => Some("trace"),
+        Some((_, FieldEncoding::Default)) | None => None,
+    };
+
+    let conflicts = [
+        data.attr
+            .skip(mode)
+            .is_some_and(|&(_, skip)| skip)
+            .then_some("skip"),
+        data.attr.is_default(mode).is_some().then_some("default"),
+        data.attr
+            .skip_encoding_if(mode)
+            .is_some()
+            .then_some("skip_encoding_if"),
+        data.attr.name(mode).is_some().then_some("name"),
+        data.attr.pattern(mode).is_some().then_some("pattern"),
+        (!data.attr.alias(mode).is_empty()).then_some("alias"),
+        (data.attr.encode_path(mode).is_some() || data.attr.decode_path(mode).is_some())
+            .then_some("with"),
+        data.attr.niche(mode).is_some().then_some("niche"),
+        encoding,
+    ];
+
+    for what in conflicts.into_iter().flatten() {
+        e.cx.error_span(
+            span,
+            format_args!("#[{ATTR}(flatten)] cannot be combined with #[{ATTR}({what})]"),
+        );
+    }
+}
+
 fn setup_field<'a>(
     e: &'a Expander,
     mode: &Mode<'a>,
@@ -472,13 +873,38 @@ fn setup_field<'a>(
 
     let name = expander::expand_name(data, mode, name_all, data.ident);
     let pattern = data.attr.pattern(mode).map(|(_, p)| p);
+    let aliases = data.attr.alias(mode);
+    validate_alias_pattern(e, pattern, aliases);
 
-    let skip = data.attr.skip(mode).map(|&(s, ())| s);
+    let skip = data
+        .attr
+        .skip(mode)
+        .filter(|&&(_, skip)| skip)
+        .map(|&(s, _)| s);
     let skip_encoding_if = data.attr.skip_encoding_if(mode);
-    let default_attr = data
+    let default_attr = data.attr.is_default(mode).map(|(s, value)| (*s, value));
+
+    if packing != Packing::Tagged {
+        if let Some(span) = data.attr.one_sided_skip(mode) {
+            e.cx.error_span(
+                span,
+                format_args!(
+                    "Fields which are only skipped when encoding or decoding cannot be used in #[{ATTR}(packed)] or #[{ATTR}(transparent)] containers"
+                ),
+            );
+        }
+    }
+
+    let flatten = data.attr.flatten(mode).map(|&(s, ())| s);
+    let encoding = data
         .attr
-        .is_default(mode)
-        .map(|(s, path)| (*s, path.as_ref()));
+        .encoding(mode)
+        .map(|&(_, e)| e)
+        .unwrap_or_default();
+
+    if let Some(span) = flatten {
+        validate_flatten(e, data, mode, span, packing);
+    }
 
     let member = match data.ident {
         Some(ident) => syn::Member::Named(ident.clone()),
@@ -579,7 +1005,10 @@ fn setup_field<'a>(
             base: Box::new(syn::Expr::Path(syn::ExprPath {
                 attrs: Vec::new(),
                 qself: None,
-                path: <Token![self]>::default().into(),
+                path: match e.type_attr.remote(mode) {
+                    Some(..) => syn::Ident::new(REMOTE_VALUE, Span::call_site()).into(),
+                    None => <Token![self]>::default().into(),
+                },
             })),
             dot_token: <Token![.]>::default(),
             member: member.clone(),
@@ -607,12 +1036,15 @@ fn setup_field<'a>(
         decode_path,
         name,
         pattern,
+        aliases,
         skip,
         skip_encoding_if,
         default_attr,
+        flatten,
         self_access,
         member,
         packing,
+        encoding,
         var,
         ty: data.ty,
     }
@@ -644,6 +1076,41 @@ pub(crate) fn split_name(
     (all, ty.clone(), method)
 }
 
+/// Determine the name type of a collection of fields or variants from their
+/// explicit names, if every one is named using an integer literal.
+///
+/// This allows integer names like `#[musli(name = 0)]` to be used without
+/// specifying `#[musli(name(type = ..))]`, in which case formats which require
+/// string keys such as JSON will stringify them.
+fn determine_names_type<'b>(
+    names: impl IntoIterator<Item = Option<&'b (Span, syn::Expr)>>,
+) -> Option<syn::Type> {
+    let mut output = None::<syn::Type>;
+
+    for name in names {
+        let (_, name) = name?;
+        let ty = determine_type_inner(name, false)?;
+
+        if !matches!(&ty, syn::Type::Path(p) if p.path.is_ident("usize") || p.path.is_ident("isize"))
+        {
+            return None;
+        }
+
+        match &output {
+            // Mixing signed and unsigned names, so fall back to a signed type.
+            Some(existing) if *existing != ty => {
+                output = Some(syn::parse_quote!(isize));
+            }
+            Some(..) => {}
+            None => {
+                output = Some(ty);
+            }
+        }
+    }
+
+    output
+}
+
 pub(crate) fn determine_type(expr: &syn::Expr) -> Option<(Span, syn::Type)> {
     Some((expr.span(), determine_type_inner(expr, false)?))
 }
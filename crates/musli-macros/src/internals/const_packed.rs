@@ -0,0 +1,287 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+
+use super::attr::{FieldEncoding, Packing};
+use super::build::{Build, BuildData};
+use super::{Result, Tokens, ATTR};
+
+struct Ctxt<'a> {
+    tokens: &'a Tokens<'a>,
+    encode_t: TokenStream,
+    out_var: syn::Ident,
+    at_var: syn::Ident,
+}
+
+/// Expand the `PACKED_SIZE` constant and `encode_const` function generated
+/// through `#[musli(const_packed)]`.
+///
+/// The generated function produces the same bytes as the `packed` encoding
+/// does with its default options, which uses fixed-width numbers in the native
+/// byte order.
+///
+/// Values which are bitwise encodable are written as they are laid out in
+/// memory by that encoding, so arrays nested inside of them don't have a
+/// length prefix.
+pub(crate) fn const_packed(e: &Build<'_, '_>, span: Span) -> Result<TokenStream> {
+    let st = match &e.data {
+        BuildData::Struct(st) => st,
+        BuildData::Enum(..) => {
+            e.cx.error_span(
+                span,
+                format_args!("#[{ATTR}(const_packed)] is only supported on structs"),
+            );
+            return Err(());
+        }
+    };
+
+    if st.packing == Packing::Tagged {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "#[{ATTR}(const_packed)] requires a #[{ATTR}(packed)] or #[{ATTR}(transparent)] container"
+            ),
+        );
+        return Err(());
+    }
+
+    if let Some(param) = e.input.generics.params.first() {
+        e.cx.error_span(
+            param.span(),
+            format_args!("#[{ATTR}(const_packed)] is not supported on generic types"),
+        );
+        return Err(());
+    }
+
+    if e.remote.is_some() || e.into.is_some() {
+        e.cx.error_span(
+            span,
+            format_args!(
+                "#[{ATTR}(const_packed)] cannot be combined with #[{ATTR}(remote)] or #[{ATTR}(into)]"
+            ),
+        );
+        return Err(());
+    }
+
+    let cx = Ctxt {
+        tokens: e.tokens,
+        encode_t: e.mode.as_trait_t(&e.p.allocator_ident).to_token_stream(),
+        out_var: e.cx.ident("out"),
+        at_var: e.cx.ident("at"),
+    };
+
+    let encode_t = &cx.encode_t;
+    let bitwise = quote!(<Self as #encode_t>::IS_BITWISE_ENCODE);
+    let mut sizes = Vec::new();
+    let mut writes = Vec::new();
+
+    for f in &st.unskipped_fields {
+        let member = &f.member;
+
+        let unsupported = if !f.encode_path.1.is_default() {
+            Some("#[musli(with)] or #[musli(niche)]")
+        } else if f.skip_encoding_if.is_some() {
+            Some("#[musli(skip_encoding_if)]")
+        } else if f.flatten.is_some() {
+            Some("#[musli(flatten)]")
+        } else {
+            None
+        };
+
+        if let Some(unsupported) = unsupported {
+            e.cx.error_span(
+                f.span,
+                format_args!(
+                    "#[{ATTR}(const_packed)]: field `{}` uses {unsupported}, which cannot be encoded in a const context",
+                    member.to_token_stream()
+                ),
+            );
+            continue;
+        }
+
+        let access = quote!(self.#member);
+
+        match field(e, &cx, f.ty, access, f.encoding, bitwise.clone(), 0) {
+            Some((size, write)) => {
+                sizes.push(size);
+                writes.push(write);
+            }
+            None => {
+                e.cx.error_span(
+                    f.ty.span(),
+                    format_args!(
+                        "#[{ATTR}(const_packed)]: field `{}` cannot be encoded in a const context",
+                        member.to_token_stream()
+                    ),
+                );
+            }
+        }
+    }
+
+    if e.cx.has_errors() {
+        return Err(());
+    }
+
+    let Tokens { const_packed_t, .. } = e.tokens;
+    let Ctxt {
+        out_var, at_var, ..
+    } = &cx;
+
+    let type_ident = &e.input.ident;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #type_ident {
+            /// The number of bytes produced by `encode_const`.
+            pub const PACKED_SIZE: usize = 0 #(+ #sizes)*;
+
+            /// Encode the value in a const context, producing the same bytes
+            /// as the `packed` encoding does with its default options.
+            #[allow(unused_mut)]
+            pub const fn encode_const(&self) -> [u8; Self::PACKED_SIZE] {
+                let mut #out_var = [0u8; Self::PACKED_SIZE];
+                let mut #at_var = 0usize;
+                #(#writes)*
+                let _ = #at_var;
+                #out_var
+            }
+        }
+
+        #[automatically_derived]
+        impl #const_packed_t for #type_ident {
+            const PACKED_SIZE: usize = Self::PACKED_SIZE;
+        }
+    })
+}
+
+/// Construct the size expression and the statements writing a single value of
+/// type `ty` accessed through `access`.
+///
+/// The `bitwise` expression indicates if the value is part of a bitwise encoded
+/// value, in which case arrays are written without a length prefix.
+fn field(
+    e: &Build<'_, '_>,
+    cx: &Ctxt<'_>,
+    ty: &syn::Type,
+    access: TokenStream,
+    encoding: FieldEncoding,
+    bitwise: TokenStream,
+    depth: usize,
+) -> Option<(TokenStream, TokenStream)> {
+    let Tokens {
+        const_packed_t,
+        const_write,
+        f32_to_bits,
+        f64_to_bits,
+        size_of,
+        ..
+    } = cx.tokens;
+
+    let Ctxt {
+        encode_t,
+        out_var,
+        at_var,
+        ..
+    } = cx;
+
+    let write =
+        |bytes: TokenStream| quote!((#out_var, #at_var) = #const_write(#out_var, #at_var, #bytes););
+
+    match ty {
+        syn::Type::Paren(ty) => field(e, cx, &ty.elem, access, encoding, bitwise, depth),
+        syn::Type::Group(ty) => field(e, cx, &ty.elem, access, encoding, bitwise, depth),
+        syn::Type::Array(array) => {
+            let len = &array.len;
+
+            if let FieldEncoding::Bytes = encoding {
+                if !is_ident(&array.elem, "u8") {
+                    return None;
+                }
+
+                return Some((quote!((#len)), write(quote!(#access))));
+            }
+
+            let index = e.cx.ident(&format!("i{depth}"));
+            let elem = &array.elem;
+
+            let (size, inner) = field(
+                e,
+                cx,
+                elem,
+                quote!(#access[#index]),
+                FieldEncoding::Default,
+                quote!((#bitwise || <#elem as #encode_t>::IS_BITWISE_ENCODE)),
+                depth + 1,
+            )?;
+
+            let (prefix_size, prefix) = match encoding {
+                FieldEncoding::Packed => (quote!(0), TokenStream::new()),
+                _ => {
+                    let write = write(quote!(usize::to_ne_bytes(#len)));
+
+                    (
+                        quote!((if #bitwise { 0 } else { #size_of::<usize>() })),
+                        quote!(if !#bitwise { #write }),
+                    )
+                }
+            };
+
+            let size = quote!((#prefix_size + (#len) * (#size)));
+
+            let write = quote! {
+                #prefix
+
+                let mut #index = 0usize;
+
+                while #index < (#len) {
+                    #inner
+                    #index += 1;
+                }
+            };
+
+            Some((size, write))
+        }
+        syn::Type::Path(path) if path.qself.is_none() => {
+            if !matches!(encoding, FieldEncoding::Default | FieldEncoding::Trace) {
+                return None;
+            }
+
+            let last = path.path.segments.last()?;
+
+            if !last.arguments.is_none() {
+                return None;
+            }
+
+            if let Some(ident) = path.path.get_ident() {
+                let bytes = match ident.to_string().as_str() {
+                    "u8" | "u16" | "u32" | "u64" | "u128" | "usize" | "i8" | "i16" | "i32"
+                    | "i64" | "i128" | "isize" => quote!(#ident::to_ne_bytes(#access)),
+                    "bool" => quote!([#access as u8]),
+                    "char" => quote!(u32::to_ne_bytes(#access as u32)),
+                    "f32" => quote!(u32::to_ne_bytes(#f32_to_bits(#access))),
+                    "f64" => quote!(u64::to_ne_bytes(#f64_to_bits(#access))),
+                    _ => TokenStream::new(),
+                };
+
+                if !bytes.is_empty() {
+                    let size = quote!(#size_of::<#ident>());
+                    return Some((size, write(bytes)));
+                }
+            }
+
+            let size = quote_spanned!(ty.span() => <#ty as #const_packed_t>::PACKED_SIZE);
+            let write = write(quote!(<#ty>::encode_const(&#access)));
+            Some((size, write))
+        }
+        _ => None,
+    }
+}
+
+fn is_ident(ty: &syn::Type, ident: &str) -> bool {
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => path.path.is_ident(ident),
+        syn::Type::Paren(ty) => is_ident(&ty.elem, ident),
+        syn::Type::Group(ty) => is_ident(&ty.elem, ident),
+        _ => false,
+    }
+}
@@ -20,6 +20,8 @@ impl<'a> Expansion<'a> {
             decode_bytes_t: tokens.decode_bytes_t,
             trace_decode_t: tokens.trace_decode_t,
             decode_t: tokens.decode_t,
+            niche_encode: tokens.niche_encode,
+            niche_decode: tokens.niche_decode,
             only,
         }
     }
@@ -1,6 +1,7 @@
 pub(crate) mod apply;
 pub(crate) mod attr;
 pub(crate) mod build;
+mod const_packed;
 mod ctxt;
 mod expansion;
 mod mode;
@@ -12,6 +13,7 @@ pub(crate) const ATTR: &str = "musli";
 
 pub(crate) use self::attr::Only;
 pub(crate) use self::build::{Build, Parameters};
+pub(crate) use self::const_packed::const_packed;
 pub(crate) use self::ctxt::Ctxt;
 pub(crate) use self::expansion::Expansion;
 pub(crate) use self::mode::{ImportedMethod, Mode};
@@ -80,6 +80,8 @@ pub(crate) struct Mode<'a> {
     pub(crate) decode_bytes_t: Import<'a>,
     pub(crate) trace_decode_t: Import<'a>,
     pub(crate) decode_t: Import<'a>,
+    pub(crate) niche_encode: Import<'a>,
+    pub(crate) niche_decode: Import<'a>,
     pub(crate) only: Only,
 }
 
@@ -22,27 +22,42 @@ impl ToTokens for Import<'_> {
 pub(crate) struct Tokens<'a> {
     pub(crate) allocator_t: Import<'a>,
     pub(crate) as_decoder_t: Import<'a>,
+    pub(crate) capabilities_t: Import<'a>,
+    pub(crate) clone_t: Import<'a>,
     pub(crate) collect_string: Import<'a>,
+    pub(crate) const_packed_t: Import<'a>,
+    pub(crate) const_write: Import<'a>,
     pub(crate) context_t: Import<'a>,
     pub(crate) decode_bytes_t: Import<'a>,
+    pub(crate) decode_flatten_t: Import<'a>,
     pub(crate) decode_packed_t: Import<'a>,
+    pub(crate) decode_remote_t: Import<'a>,
     pub(crate) decode_t: Import<'a>,
     pub(crate) decoder_t: Import<'a>,
     pub(crate) default_function: Import<'a>,
     pub(crate) encode_bytes_t: Import<'a>,
+    pub(crate) encode_flatten_t: Import<'a>,
     pub(crate) encode_packed_t: Import<'a>,
+    pub(crate) encode_remote_t: Import<'a>,
     pub(crate) encode_t: Import<'a>,
     pub(crate) encoder_t: Import<'a>,
+    pub(crate) f32_to_bits: Import<'a>,
+    pub(crate) f64_to_bits: Import<'a>,
     pub(crate) fmt: Import<'a>,
+    pub(crate) from_t: Import<'a>,
+    pub(crate) into_t: Import<'a>,
     pub(crate) map_decoder_t: Import<'a>,
     pub(crate) map_encoder_t: Import<'a>,
     pub(crate) map_entry_encoder_t: Import<'a>,
     pub(crate) map_hint: Import<'a>,
     pub(crate) messages: Import<'a>,
     pub(crate) needs_drop: Import<'a>,
+    pub(crate) niche_decode: Import<'a>,
+    pub(crate) niche_encode: Import<'a>,
     pub(crate) offset_of: Import<'a>,
     pub(crate) option: Import<'a>,
     pub(crate) pack_decoder_t: Import<'a>,
+    pub(crate) requires: Import<'a>,
     pub(crate) result: Import<'a>,
     pub(crate) sequence_encoder_t: Import<'a>,
     pub(crate) size_of: Import<'a>,
@@ -51,6 +66,7 @@ pub(crate) struct Tokens<'a> {
     pub(crate) struct_field_decoder_t: Import<'a>,
     pub(crate) trace_decode_t: Import<'a>,
     pub(crate) trace_encode_t: Import<'a>,
+    pub(crate) try_from_t: Import<'a>,
     pub(crate) try_fast_decode: Import<'a>,
     pub(crate) try_fast_encode: Import<'a>,
     pub(crate) variant_decoder_t: Import<'a>,
@@ -63,27 +79,42 @@ impl<'a> Tokens<'a> {
         Self {
             allocator_t: Import(prefix, "Allocator"),
             as_decoder_t: Import(prefix, "AsDecoder"),
+            capabilities_t: Import(prefix, "Capabilities"),
+            clone_t: Import(prefix, "Clone"),
             collect_string: Import(prefix, "collect_string"),
+            const_packed_t: Import(prefix, "ConstPacked"),
+            const_write: Import(prefix, "const_write"),
             context_t: Import(prefix, "Context"),
             decode_bytes_t: Import(prefix, "DecodeBytes"),
+            decode_flatten_t: Import(prefix, "DecodeFlatten"),
             decode_packed_t: Import(prefix, "DecodePacked"),
+            decode_remote_t: Import(prefix, "DecodeRemote"),
             decode_t: Import(prefix, "Decode"),
             decoder_t: Import(prefix, "Decoder"),
             default_function: Import(prefix, "default"),
             encode_bytes_t: Import(prefix, "EncodeBytes"),
+            encode_flatten_t: Import(prefix, "EncodeFlatten"),
             encode_packed_t: Import(prefix, "EncodePacked"),
+            encode_remote_t: Import(prefix, "EncodeRemote"),
             encode_t: Import(prefix, "Encode"),
             encoder_t: Import(prefix, "Encoder"),
+            f32_to_bits: Import(prefix, "f32_to_bits"),
+            f64_to_bits: Import(prefix, "f64_to_bits"),
             fmt: Import(prefix, "fmt"),
+            from_t: Import(prefix, "From"),
+            into_t: Import(prefix, "Into"),
             map_decoder_t: Import(prefix, "MapDecoder"),
             map_encoder_t: Import(prefix, "MapEncoder"),
             map_entry_encoder_t: Import(prefix, "EntryEncoder"),
             map_hint: Import(prefix, "MapHint"),
             messages: Import(prefix, "m"),
             needs_drop: Import(prefix, "needs_drop"),
+            niche_decode: Import(prefix, "niche_decode"),
+            niche_encode: Import(prefix, "niche_encode"),
             offset_of: Import(prefix, "offset_of"),
             option: Import(prefix, "Option"),
             pack_decoder_t: Import(prefix, "SequenceDecoder"),
+            requires: Import(prefix, "requires"),
             result: Import(prefix, "Result"),
             sequence_encoder_t: Import(prefix, "SequenceEncoder"),
             size_of: Import(prefix, "size_of"),
@@ -92,6 +123,7 @@ impl<'a> Tokens<'a> {
             struct_field_decoder_t: Import(prefix, "EntryDecoder"),
             trace_decode_t: Import(prefix, "DecodeTrace"),
             trace_encode_t: Import(prefix, "EncodeTrace"),
+            try_from_t: Import(prefix, "TryFrom"),
             try_fast_decode: Import(prefix, "TryFastDecode"),
             try_fast_encode: Import(prefix, "TryFastEncode"),
             variant_decoder_t: Import(prefix, "VariantDecoder"),
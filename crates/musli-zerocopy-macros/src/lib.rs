@@ -15,6 +15,7 @@ use proc_macro::TokenStream;
 
 #[cfg(feature = "sneaky-fields")]
 mod sneaky_fields;
+mod validate_tree;
 mod visit;
 mod zero_copy;
 
@@ -40,6 +41,17 @@ pub fn visit(input: TokenStream) -> TokenStream {
     }
 }
 
+#[proc_macro_derive(ValidateTree, attributes(validate_tree))]
+pub fn validate_tree(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    let expander = validate_tree::Expander::new(&input);
+
+    match expander.expand() {
+        Ok(stream) => stream.into(),
+        Err(errors) => to_compile_errors(errors).into(),
+    }
+}
+
 // NB: Only used in UI tests.
 #[proc_macro_attribute]
 #[doc(hidden)]
@@ -0,0 +1,201 @@
+use std::cell::RefCell;
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::meta::ParseNestedMeta;
+use syn::{DeriveInput, Token};
+
+#[derive(Default)]
+struct Ctxt {
+    errors: RefCell<Vec<syn::Error>>,
+}
+
+impl Ctxt {
+    fn error(&self, error: syn::Error) {
+        self.errors.borrow_mut().push(error);
+    }
+}
+
+pub struct Expander<'a> {
+    input: &'a DeriveInput,
+}
+
+impl<'a> Expander<'a> {
+    pub fn new(input: &'a DeriveInput) -> Self {
+        Self { input }
+    }
+}
+
+impl Expander<'_> {
+    pub fn expand(&self) -> Result<TokenStream, Vec<syn::Error>> {
+        let cx = Ctxt::default();
+
+        let Ok(output) = expand(&cx, self.input) else {
+            return Err(cx.errors.into_inner());
+        };
+
+        let errors = cx.errors.into_inner();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        Ok(output)
+    }
+}
+
+fn expand(cx: &Ctxt, input: &DeriveInput) -> Result<TokenStream, ()> {
+    let mut krate: syn::Path = syn::parse_quote!(musli_zerocopy);
+
+    for attr in &input.attrs {
+        if attr.path().is_ident("validate_tree") {
+            let result = attr.parse_nested_meta(|meta: ParseNestedMeta| {
+                if meta.path.is_ident("crate") {
+                    if meta.input.parse::<Option<Token![=]>>()?.is_some() {
+                        krate = meta.input.parse()?;
+                    } else {
+                        krate = syn::parse_quote!(crate);
+                    }
+
+                    return Ok(());
+                }
+
+                Err(syn::Error::new(
+                    meta.input.span(),
+                    "ValidateTree: Unsupported attribute",
+                ))
+            });
+
+            if let Err(error) = result {
+                cx.error(error);
+            }
+        }
+    }
+
+    let error: syn::Path = syn::parse_quote!(#krate::Error);
+    let result: syn::Path = syn::parse_quote!(#krate::__private::result::Result);
+    let validate_tree: syn::Path = syn::parse_quote!(#krate::__private::ValidateTree);
+    let tree_validator: syn::Path = syn::parse_quote!(#krate::__private::TreeValidator);
+
+    let body = match &input.data {
+        syn::Data::Struct(st) => {
+            let (pattern, validate) = process_fields(cx, &st.fields, &validate_tree);
+
+            quote! {
+                let Self #pattern = self;
+                #(#validate)*
+            }
+        }
+        syn::Data::Enum(en) => {
+            let mut arms = Vec::new();
+
+            for v in &en.variants {
+                let (pattern, validate) = process_fields(cx, &v.fields, &validate_tree);
+                let ident = &v.ident;
+
+                arms.push(quote! {
+                    Self::#ident #pattern => {
+                        #(#validate)*
+                    }
+                });
+            }
+
+            quote! {
+                match self {
+                    #(#arms,)*
+                }
+            }
+        }
+        syn::Data::Union(..) => {
+            cx.error(syn::Error::new(
+                Span::call_site(),
+                "ValidateTree: Unions are not supported",
+            ));
+
+            return Err(());
+        }
+    };
+
+    let name = &input.ident;
+
+    let mut generics = input.generics.clone();
+
+    for param in generics.type_params_mut() {
+        param.bounds.push(syn::parse_quote!(#validate_tree));
+    }
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        unsafe impl #impl_generics #validate_tree for #name #ty_generics #where_clause {
+            #[inline]
+            #[allow(unused_variables)]
+            fn validate_tree(&self, cx: &mut #tree_validator<'_>) -> #result<(), #error> {
+                #body
+                #result::Ok(())
+            }
+        }
+    })
+}
+
+/// Process fields, returning a pattern which binds every field which should
+/// be validated and the statements used to validate them.
+fn process_fields(
+    cx: &Ctxt,
+    fields: &syn::Fields,
+    validate_tree: &syn::Path,
+) -> (TokenStream, Vec<TokenStream>) {
+    let mut bindings = Vec::new();
+    let mut validate = Vec::new();
+
+    for (index, field) in fields.iter().enumerate() {
+        let mut ignore = false;
+
+        for attr in &field.attrs {
+            if attr.path().is_ident("validate_tree") {
+                let result = attr.parse_nested_meta(|meta: ParseNestedMeta| {
+                    Err(syn::Error::new(
+                        meta.input.span(),
+                        "ValidateTree: Unsupported attribute",
+                    ))
+                });
+
+                if let Err(error) = result {
+                    cx.error(error);
+                }
+            }
+
+            // Errors in this attribute are reported by the ZeroCopy derive.
+            if attr.path().is_ident("zero_copy") {
+                let _ = attr.parse_nested_meta(|meta: ParseNestedMeta| {
+                    if meta.path.is_ident("ignore") {
+                        ignore = true;
+                    }
+
+                    Ok(())
+                });
+            }
+        }
+
+        if ignore {
+            continue;
+        }
+
+        let member = match &field.ident {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(syn::Index::from(index)),
+        };
+
+        let variable = format_ident!("__field{}", index);
+
+        validate.push(quote!(#validate_tree::validate_tree(#variable, cx)?;));
+        bindings.push(quote!(#member: #variable));
+    }
+
+    let pattern = match fields {
+        syn::Fields::Unit => TokenStream::new(),
+        _ => quote!({ #(#bindings,)* .. }),
+    };
+
+    (pattern, validate)
+}
@@ -57,6 +57,10 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
     let mut krate: syn::Path = syn::parse_quote!(musli_zerocopy);
     let mut swap_bytes_self = false;
     let mut swap_bytes = None;
+    let mut layout = None;
+    let mut compact = None;
+    let mut assert_no_padding = false;
+    let mut pointer = false;
 
     for attr in &attrs {
         if attr.path().is_ident("repr") {
@@ -97,6 +101,28 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
                     return Ok(());
                 }
 
+                if meta.path.is_ident("layout") {
+                    layout = Some(meta.path.span());
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("compact") {
+                    compact = Some(meta.path.span());
+                    return Ok(());
+                }
+
+                if meta.path.is_ident("assert_no_padding") {
+                    assert_no_padding = true;
+                    return Ok(());
+                }
+
+                // Internal attribute used by pointer types which provide a
+                // `POINTER_LAYOUT` associated constant.
+                if meta.path.is_ident("pointer") {
+                    pointer = true;
+                    return Ok(());
+                }
+
                 Err(syn::Error::new(
                     meta.input.span(),
                     "ZeroCopy: Unsupported attribute",
@@ -143,6 +169,7 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
     // error.
     let check_fields;
     let type_impls;
+    let mut compact_impl = None;
     let mut check_zero_sized = Vec::new();
 
     match &data {
@@ -156,6 +183,15 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
                 return Err(());
             }
 
+            if let Some(span) = compact {
+                cx.error(syn::Error::new(
+                    span,
+                    "ZeroCopy: zero_copy(compact) is only supported on enums",
+                ));
+
+                return Err(());
+            }
+
             // Field types.
             let mut output = process_fields(cx, &st.fields);
             check_zero_sized.append(&mut output.check_zero_sized);
@@ -272,9 +308,57 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
                 )
             };
 
-            type_impls = None;
+            type_impls = layout.is_some().then(|| {
+                let type_layout: syn::Path = syn::parse_quote!(#krate::layout::TypeLayout);
+                let field_layout: syn::Path = syn::parse_quote!(#krate::layout::FieldLayout);
+
+                let fields = output.members.iter().zip(types).map(|(member, ty)| {
+                    let field_name = match member {
+                        syn::Member::Named(ident) => ident.to_string(),
+                        syn::Member::Unnamed(index) => index.index.to_string(),
+                    };
+
+                    let ty_name = type_name(ty);
+
+                    quote! {
+                        #field_layout::new(
+                            #field_name,
+                            #ty_name,
+                            #mem::offset_of!(Self, #member),
+                            #mem::size_of::<#ty>(),
+                            #mem::align_of::<#ty>(),
+                            <#ty as #zero_copy>::__LAYOUT,
+                            <#ty as #zero_copy>::__POINTER,
+                        )
+                    }
+                });
+
+                let type_name = name.to_string();
+
+                quote! {
+                    #[automatically_derived]
+                    impl #impl_generics #name #ty_generics #where_clause {
+                        /// The layout of this type.
+                        pub const LAYOUT: #type_layout = #type_layout::new(
+                            #type_name,
+                            #mem::size_of::<Self>(),
+                            #mem::align_of::<Self>(),
+                            &[#(#fields),*],
+                        );
+                    }
+                }
+            });
         }
         syn::Data::Enum(en) => {
+            if let Some(span) = layout {
+                cx.error(syn::Error::new(
+                    span,
+                    "ZeroCopy: zero_copy(layout) is only supported on structs",
+                ));
+
+                return Err(());
+            }
+
             if let Some(span) = swap_bytes {
                 if en.variants.len() % 2 != 0 {
                     cx.error(syn::Error::new(
@@ -312,6 +396,7 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
             let mut padded_variants = Vec::new();
             let mut byte_ordered_variants = Vec::new();
             let mut variant_fields = Vec::new();
+            let mut compact_variants = Vec::new();
 
             let mut enumerator = Enumerator::new(num, ty.span());
 
@@ -389,6 +474,20 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
 
                 let ident = &variant.ident;
 
+                // Each variant is laid out like a `repr(C)` struct starting
+                // with the discriminant, so the data of the variant ends after
+                // its last field. Note that this includes ignored fields since
+                // they might still affect alignment.
+                let all_types = variant.fields.iter().map(|f| &f.ty);
+
+                compact_variants.push(quote! {
+                    Self::#ident { .. } => const {
+                        let end = #mem::size_of::<#ty>();
+                        #(let end = end.next_multiple_of(#mem::align_of::<#all_types>()) + #mem::size_of::<#all_types>();)*
+                        end
+                    }
+                });
+
                 let Fields { types, .. } = &output;
 
                 pad_variants.push(quote! {
@@ -583,6 +682,24 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
                 };
             );
 
+            if compact.is_some() {
+                let compact: syn::Path = syn::parse_quote!(#krate::compact::Compact);
+
+                compact_impl = Some(quote! {
+                    // SAFETY: The size of each variant is calculated from the
+                    // layout of its fields.
+                    #[automatically_derived]
+                    unsafe impl #impl_generics #compact for #name #ty_generics #where_clause {
+                        #[inline]
+                        fn compact_size(&self) -> usize {
+                            match self {
+                                #(#compact_variants,)*
+                            }
+                        }
+                    }
+                });
+            }
+
             type_impls = Some(quote! {
                 #[cfg(test)]
                 impl #impl_generics #name #ty_generics #where_clause {
@@ -615,6 +732,42 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
 
     let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
+    let layout_const = layout.is_some().then(|| {
+        quote! {
+            const __LAYOUT: #krate::__private::Option<&'static #krate::layout::TypeLayout> =
+                #krate::__private::Option::Some(&Self::LAYOUT);
+        }
+    });
+
+    let pointer_const = pointer.then(|| {
+        quote! {
+            const __POINTER: #krate::__private::Option<#krate::layout::PointerLayout> =
+                #krate::__private::Option::Some(Self::POINTER_LAYOUT);
+        }
+    });
+
+    // Padding is asserted when `PADDED` is evaluated, which for non-generic
+    // types is forced immediately.
+    let (padded, check_padding) = if assert_no_padding {
+        let message = format!("ZeroCopy: {name} contains padding");
+
+        let check_padding = generics.params.is_empty().then(|| {
+            quote! {
+                const _: bool = <#name as #zero_copy>::PADDED;
+            }
+        });
+
+        let padded = quote! {{
+            let padded = #padded;
+            ::core::assert!(!padded, #message);
+            padded
+        }};
+
+        (padded, check_padding)
+    } else {
+        (padded, None)
+    };
+
     let (swap_bytes_block, can_swap_bytes) = if swap_bytes_self {
         (quote!(this), quote!(true))
     } else {
@@ -630,11 +783,17 @@ fn expand(cx: &Ctxt, input: syn::DeriveInput) -> Result<TokenStream, ()> {
 
         #type_impls
 
+        #compact_impl
+
+        #check_padding
+
         #[automatically_derived]
         unsafe impl #impl_generics #zero_copy for #name #ty_generics #where_clause {
             const ANY_BITS: bool = #any_bits;
             const PADDED: bool = #padded;
             const CAN_SWAP_BYTES: bool = #can_swap_bytes;
+            #layout_const
+            #pointer_const
 
             #[inline]
             unsafe fn pad(padder: &mut #padder<'_, Self>) {
@@ -801,6 +960,31 @@ fn process_fields<'a>(cx: &Ctxt, fields: &'a syn::Fields) -> Fields<'a> {
     output
 }
 
+/// Render a type as a string with conventional spacing, since stringifying
+/// tokens puts spaces between all of them.
+fn type_name(ty: &syn::Type) -> String {
+    let mut out = quote!(#ty).to_string();
+
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ::", "::"),
+        (":: ", "::"),
+        (" ,", ","),
+        (" ;", ";"),
+        ("& ", "&"),
+        ("[ ", "["),
+        (" ]", "]"),
+        ("( ", "("),
+        (" )", ")"),
+    ] {
+        out = out.replace(from, to);
+    }
+
+    out
+}
+
 fn unpad_variant(ident: &syn::Ident) -> Option<syn::Ident> {
     let s = ident.to_string();
     let trimmed = s.trim_matches('_');
@@ -0,0 +1,158 @@
+use alloc::vec::Vec;
+
+use crate::error::{ErrorKind, IntoRepr};
+use crate::{ByteOrder, Error, OwnedBuf, Size};
+
+use super::{BitSetRef, WORD_BITS};
+
+/// Store a bitset with the given bits set.
+///
+/// The length of the stored bitset is one more than the largest bit which is
+/// set. Use [`Builder::with_len`] to store a bitset with a larger length.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{bitset, OwnedBuf};
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let set = bitset::store(&mut buf, [1, 5, 64])?;
+/// assert_eq!(set.len(), 65);
+/// assert_eq!(set.iter_ones(&buf)?.collect::<Vec<_>>(), [1, 5, 64]);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub fn store<E, O, I>(buf: &mut OwnedBuf<E, O>, it: I) -> Result<BitSetRef<E, O>, Error>
+where
+    I: IntoIterator<Item = usize>,
+    E: ByteOrder,
+    O: Size,
+{
+    let mut builder = Builder::new();
+
+    for index in it {
+        builder.insert(index);
+    }
+
+    builder.build(buf)
+}
+
+/// An in-memory bitset as it's being constructed.
+///
+/// This can be used over [`store()`] to provide more control.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{bitset, OwnedBuf};
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let mut set = bitset::Builder::with_len(1000);
+/// set.insert(10);
+/// set.insert(999);
+///
+/// let set = set.build(&mut buf)?;
+/// assert_eq!(set.len(), 1000);
+/// assert_eq!(set.count_ones(&buf)?, 2);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub struct Builder {
+    words: Vec<u64>,
+    bits: usize,
+}
+
+impl Builder {
+    /// Construct a new empty bitset builder.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            bits: 0,
+        }
+    }
+
+    /// Construct a new bitset builder with a length of `bits`, where no bits
+    /// are set.
+    #[inline]
+    pub fn with_len(bits: usize) -> Self {
+        Self {
+            words: alloc::vec![0; bits.div_ceil(WORD_BITS)],
+            bits,
+        }
+    }
+
+    /// Get the current length of the bitset in bits.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits
+    }
+
+    /// Test if the bitset has a length of zero bits.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0
+    }
+
+    /// Set the bit at `index`, extending the length of the bitset if needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::bitset;
+    ///
+    /// let mut set = bitset::Builder::new();
+    /// set.insert(100);
+    /// assert_eq!(set.len(), 101);
+    /// ```
+    pub fn insert(&mut self, index: usize) {
+        let word = index / WORD_BITS;
+
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        self.words[word] |= 1 << (index % WORD_BITS);
+        self.bits = self.bits.max(index + 1);
+    }
+
+    /// Test if the bit at `index` is set.
+    #[inline]
+    pub fn contains(&self, index: usize) -> bool {
+        self.words
+            .get(index / WORD_BITS)
+            .is_some_and(|w| w & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    /// Store the bitset in the given buffer.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the length of the bitset can't be represented by `O`.
+    pub fn build<E, O>(self, buf: &mut OwnedBuf<E, O>) -> Result<BitSetRef<E, O>, Error>
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        let Some(bits) = O::try_from_usize(self.bits) else {
+            return Err(Error::new(ErrorKind::InvalidMetadataRange {
+                metadata: self.bits.into_repr(),
+                max: O::MAX.into_repr(),
+            }));
+        };
+
+        let words = self.words.into_iter().map(E::swap_u64).collect::<Vec<_>>();
+
+        Ok(BitSetRef {
+            bits: O::swap_bytes::<E>(bits),
+            words: buf.store_slice(&words),
+        })
+    }
+}
+
+impl Default for Builder {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,411 @@
+//! A compact bitset stored in a buffer.
+//!
+//! The bitset is stored as a length in bits followed by a reference to an
+//! array of `u64` words, where each word is stored in the configured
+//! [`ByteOrder`]. Bit `n` is stored in word `n / 64` at position `n % 64`.
+//!
+//! # Examples
+//!
+//! ```
+//! use musli_zerocopy::{bitset, OwnedBuf};
+//!
+//! let mut buf = OwnedBuf::new();
+//!
+//! let a = bitset::store(&mut buf, [1, 5, 64, 130])?;
+//! let b = bitset::store(&mut buf, [5, 100, 130])?;
+//!
+//! assert!(a.contains(&buf, 64)?);
+//! assert!(!a.contains(&buf, 63)?);
+//! assert_eq!(a.count_ones(&buf)?, 4);
+//! assert_eq!(a.rank(&buf, 64)?, 2);
+//!
+//! assert!(a.intersects(&buf, &b)?);
+//! assert_eq!(a.intersection(&buf, &b)?.collect::<Vec<_>>(), [5, 130]);
+//! # Ok::<_, musli_zerocopy::Error>(())
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "alloc")]
+pub use self::builder::{store, Builder};
+#[cfg(feature = "alloc")]
+mod builder;
+
+use core::iter::Zip;
+use core::marker::PhantomData;
+use core::slice;
+
+use crate::buf::{TreeValidator, ValidateTree};
+use crate::endian::Native;
+use crate::error::ErrorKind;
+use crate::{Buf, ByteOrder, DefaultSize, Error, Ref, Size, ZeroCopy};
+
+/// The number of bits stored in a single word.
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A stored reference to a bitset.
+///
+/// See the [module level documentation][self] for more information.
+#[derive(ZeroCopy)]
+#[zero_copy(crate)]
+#[repr(C)]
+pub struct BitSetRef<E = Native, O = DefaultSize>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    bits: O,
+    words: Ref<[u64], E, O>,
+}
+
+impl<E, O> BitSetRef<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    /// Get the length of the bitset in bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [1, 5, 64])?;
+    /// assert_eq!(set.len(), 65);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.bits.as_usize::<E>()
+    }
+
+    /// Test if the bitset has a length of zero bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [])?;
+    /// assert!(set.is_empty());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_zero()
+    }
+
+    /// Test if the bit at `index` is set.
+    ///
+    /// Bits beyond the length of the bitset are never set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [1, 5, 64])?;
+    /// assert!(set.contains(&buf, 5)?);
+    /// assert!(!set.contains(&buf, 6)?);
+    /// assert!(!set.contains(&buf, 1000)?);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn contains(&self, buf: &Buf, index: usize) -> Result<bool, Error> {
+        if index >= self.len() {
+            return Ok(false);
+        }
+
+        let words = buf.load(self.words)?;
+
+        let Some(&word) = words.get(index / WORD_BITS) else {
+            return Ok(false);
+        };
+
+        Ok(E::swap_u64(word) & (1 << (index % WORD_BITS)) != 0)
+    }
+
+    /// Count the number of bits which are set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [1, 5, 64])?;
+    /// assert_eq!(set.count_ones(&buf)?, 3);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn count_ones(&self, buf: &Buf) -> Result<usize, Error> {
+        let words = buf.load(self.words)?;
+        Ok(words.iter().map(|w| w.count_ones() as usize).sum())
+    }
+
+    /// Count the number of bits which are set before `index`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [1, 5, 64])?;
+    /// assert_eq!(set.rank(&buf, 0)?, 0);
+    /// assert_eq!(set.rank(&buf, 5)?, 1);
+    /// assert_eq!(set.rank(&buf, 6)?, 2);
+    /// assert_eq!(set.rank(&buf, 1000)?, 3);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn rank(&self, buf: &Buf, index: usize) -> Result<usize, Error> {
+        let words = buf.load(self.words)?;
+        let index = index.min(self.len());
+
+        let (full, rest) = (index / WORD_BITS, index % WORD_BITS);
+
+        let mut count = words
+            .iter()
+            .take(full)
+            .map(|w| w.count_ones() as usize)
+            .sum::<usize>();
+
+        if rest != 0 {
+            if let Some(&word) = words.get(full) {
+                let mask = (1u64 << rest) - 1;
+                count += (E::swap_u64(word) & mask).count_ones() as usize;
+            }
+        }
+
+        Ok(count)
+    }
+
+    /// Iterate over the indexes of all bits which are set in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let set = bitset::store(&mut buf, [64, 1, 5])?;
+    /// assert_eq!(set.iter_ones(&buf)?.collect::<Vec<_>>(), [1, 5, 64]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter_ones<'buf>(&self, buf: &'buf Buf) -> Result<IterOnes<'buf, E>, Error> {
+        let words = buf.load(self.words)?;
+
+        Ok(IterOnes {
+            ones: Ones::empty(),
+            words: words.iter(),
+            _marker: PhantomData,
+        })
+    }
+
+    /// Test if any bit is set in both this and the `other` bitset.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let a = bitset::store(&mut buf, [1, 5, 64])?;
+    /// let b = bitset::store(&mut buf, [2, 64])?;
+    /// let c = bitset::store(&mut buf, [3])?;
+    ///
+    /// assert!(a.intersects(&buf, &b)?);
+    /// assert!(!a.intersects(&buf, &c)?);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn intersects(&self, buf: &Buf, other: &Self) -> Result<bool, Error> {
+        let a = buf.load(self.words)?;
+        let b = buf.load(other.words)?;
+        Ok(a.iter().zip(b).any(|(a, b)| a & b != 0))
+    }
+
+    /// Iterate over the indexes of all bits which are set in both this and the
+    /// `other` bitset in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{bitset, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let a = bitset::store(&mut buf, [1, 5, 64, 200])?;
+    /// let b = bitset::store(&mut buf, [5, 64, 100])?;
+    ///
+    /// assert_eq!(a.intersection(&buf, &b)?.collect::<Vec<_>>(), [5, 64]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn intersection<'buf>(
+        &self,
+        buf: &'buf Buf,
+        other: &Self,
+    ) -> Result<Intersection<'buf, E>, Error> {
+        let a = buf.load(self.words)?;
+        let b = buf.load(other.words)?;
+
+        Ok(Intersection {
+            ones: Ones::empty(),
+            words: a.iter().zip(b),
+            _marker: PhantomData,
+        })
+    }
+}
+
+unsafe impl<E, O> ValidateTree for BitSetRef<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    /// Validate that the length of the bitset matches the number of stored
+    /// words, and that any bits in the last word beyond the length are unset.
+    ///
+    /// This ensures that equal bitsets are stored identically.
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        let bits = self.len();
+        let words = cx.load(self.words)?;
+
+        if words.len() != bits.div_ceil(WORD_BITS) {
+            return Err(Error::new(ErrorKind::BitSetLengthMismatch {
+                bits,
+                words: words.len(),
+            }));
+        }
+
+        if let (Some(&last), rest @ 1..) = (words.last(), bits % WORD_BITS) {
+            if E::swap_u64(last) >> rest != 0 {
+                return Err(Error::new(ErrorKind::BitSetTrailingBits { bits }));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<E, O> Clone for BitSetRef<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<E, O> Copy for BitSetRef<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+}
+
+/// Iterator over the set bits in a single word.
+#[derive(Clone)]
+struct Ones {
+    /// The index of the first bit in the current word.
+    base: usize,
+    /// The index of the first bit in the next word.
+    next: usize,
+    word: u64,
+}
+
+impl Ones {
+    #[inline]
+    fn empty() -> Self {
+        Self {
+            base: 0,
+            next: 0,
+            word: 0,
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        if self.word == 0 {
+            return None;
+        }
+
+        let bit = self.word.trailing_zeros() as usize;
+        self.word &= self.word - 1;
+        Some(self.base + bit)
+    }
+
+    #[inline]
+    fn advance(&mut self, word: u64) {
+        self.base = self.next;
+        self.next += WORD_BITS;
+        self.word = word;
+    }
+}
+
+/// Iterator over the set bits in a bitset, as returned by
+/// [`BitSetRef::iter_ones`].
+pub struct IterOnes<'buf, E = Native> {
+    ones: Ones,
+    words: slice::Iter<'buf, u64>,
+    _marker: PhantomData<E>,
+}
+
+impl<E> Iterator for IterOnes<'_, E>
+where
+    E: ByteOrder,
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = self.ones.next() {
+                return Some(index);
+            }
+
+            self.ones.advance(E::swap_u64(*self.words.next()?));
+        }
+    }
+}
+
+/// Iterator over the bits set in two bitsets, as returned by
+/// [`BitSetRef::intersection`].
+pub struct Intersection<'buf, E = Native> {
+    ones: Ones,
+    words: Zip<slice::Iter<'buf, u64>, slice::Iter<'buf, u64>>,
+    _marker: PhantomData<E>,
+}
+
+impl<E> Iterator for Intersection<'_, E>
+where
+    E: ByteOrder,
+{
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(index) = self.ones.next() {
+                return Some(index);
+            }
+
+            let (a, b) = self.words.next()?;
+            self.ones.advance(E::swap_u64(a & b));
+        }
+    }
+}
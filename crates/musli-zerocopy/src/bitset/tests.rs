@@ -0,0 +1,134 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::endian::{Big, Little};
+use crate::{Error, OwnedBuf, Ref};
+
+use super::{store, BitSetRef, Builder};
+
+#[test]
+fn bitset_queries() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let bits = [0, 1, 63, 64, 65, 127, 128, 500];
+    let set = store(&mut buf, bits)?;
+
+    assert_eq!(set.len(), 501);
+    assert_eq!(set.count_ones(&buf)?, bits.len());
+    assert_eq!(set.iter_ones(&buf)?.collect::<Vec<_>>(), bits);
+
+    for index in 0..600 {
+        assert_eq!(set.contains(&buf, index)?, bits.contains(&index), "{index}");
+
+        let rank = bits.iter().filter(|&&b| b < index).count();
+        assert_eq!(set.rank(&buf, index)?, rank, "{index}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn bitset_empty() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let set = store(&mut buf, [])?;
+    assert!(set.is_empty());
+    assert!(!set.contains(&buf, 0)?);
+    assert_eq!(set.count_ones(&buf)?, 0);
+    assert_eq!(set.rank(&buf, 10)?, 0);
+    assert_eq!(set.iter_ones(&buf)?.count(), 0);
+    let set = buf.store(&set);
+    buf.validate_tree(set)?;
+
+    let set = Builder::with_len(128).build(&mut buf)?;
+    assert_eq!(set.len(), 128);
+    assert_eq!(set.iter_ones(&buf)?.count(), 0);
+    let set = buf.store(&set);
+    buf.validate_tree(set)?;
+    Ok(())
+}
+
+#[test]
+fn bitset_intersection() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let a = store(&mut buf, [1, 64, 100, 200, 1000])?;
+    let b = store(&mut buf, [2, 64, 200, 300])?;
+    let c = store(&mut buf, [3, 65])?;
+
+    assert!(a.intersects(&buf, &b)?);
+    assert!(!a.intersects(&buf, &c)?);
+
+    assert_eq!(a.intersection(&buf, &b)?.collect::<Vec<_>>(), [64, 200]);
+    assert_eq!(b.intersection(&buf, &a)?.collect::<Vec<_>>(), [64, 200]);
+    assert_eq!(a.intersection(&buf, &c)?.count(), 0);
+    Ok(())
+}
+
+fn byte_order<E>() -> Result<(), Error>
+where
+    E: crate::ByteOrder,
+{
+    let mut buf = OwnedBuf::new().with_byte_order::<E>();
+
+    let set = store(&mut buf, [3, 70, 129])?;
+    let other = store(&mut buf, [70, 128])?;
+
+    assert_eq!(set.len(), 130);
+    assert!(set.contains(&buf, 70)?);
+    assert_eq!(set.rank(&buf, 129)?, 2);
+    assert_eq!(set.iter_ones(&buf)?.collect::<Vec<_>>(), [3, 70, 129]);
+    assert_eq!(set.intersection(&buf, &other)?.collect::<Vec<_>>(), [70]);
+
+    let set = buf.store(&set);
+    buf.validate_tree(set)?;
+    Ok(())
+}
+
+#[test]
+fn bitset_byte_order() -> Result<(), Error> {
+    byte_order::<Little>()?;
+    byte_order::<Big>()?;
+    Ok(())
+}
+
+#[test]
+fn bitset_validate_length() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let words = buf.store_slice(&[1u64, 0]);
+    let set = buf.store(&BitSetRef { bits: 64u32, words });
+
+    assert_eq!(
+        buf.validate_tree(set).unwrap_err().to_string(),
+        "Bitset with a length of 64 bits cannot be stored in 2 words"
+    );
+
+    let words = buf.store_slice(&[1u64]);
+    let set = buf.store(&BitSetRef { bits: 65u32, words });
+
+    assert_eq!(
+        buf.validate_tree(set).unwrap_err().to_string(),
+        "Bitset with a length of 65 bits cannot be stored in 1 words"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn bitset_validate_trailing_bits() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let words: Ref<[u64]> = buf.store_slice(&[1u64 << 10]);
+    let set = buf.store(&BitSetRef { bits: 10u32, words });
+
+    assert_eq!(
+        buf.validate_tree(set).unwrap_err().to_string(),
+        "Bitset with a length of 10 bits has bits set beyond its length"
+    );
+
+    let words: Ref<[u64]> = buf.store_slice(&[1u64 << 9]);
+    let set = buf.store(&BitSetRef { bits: 10u32, words });
+    buf.validate_tree(set)?;
+    Ok(())
+}
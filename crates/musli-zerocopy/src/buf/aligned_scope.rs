@@ -0,0 +1,225 @@
+use core::cmp::Reverse;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of};
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use alloc::vec::Vec;
+
+use crate::buf::OwnedBuf;
+use crate::endian::{ByteOrder, Native};
+use crate::pointer::{DefaultSize, Ref, Size};
+use crate::traits::ZeroCopy;
+
+/// Identifier of the next scope, used to tell slots of different scopes
+/// apart.
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A scope which collects values and stores them ordered by descending
+/// alignment to minimize padding, constructed through
+/// [`OwnedBuf::aligned_scope`].
+///
+/// Since the location of a value isn't known until the scope has been
+/// flushed, storing a value returns a [`Slot`] which is resolved into a
+/// [`Ref`] through the [`Placement`] produced by the scope.
+pub struct AlignedScope<E = Native, O = DefaultSize>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    id: usize,
+    staging: OwnedBuf<E, O>,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    offset: usize,
+    size: usize,
+    align: usize,
+}
+
+impl<E, O> AlignedScope<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            staging: OwnedBuf::new().with_byte_order::<E>().with_size::<O>(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Store a value in the scope.
+    ///
+    /// The returned [`Slot`] is resolved into a [`Ref`] with
+    /// [`Placement::get`] once the scope has been flushed. Values in the scope
+    /// cannot reference each other, since their locations aren't known until
+    /// then.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let ((a, b), placement) = buf.aligned_scope(|scope| {
+    ///     (scope.store(&1u8), scope.store(&2u64))
+    /// });
+    ///
+    /// assert_eq!(buf.load(placement.get(a))?, &1u8);
+    /// assert_eq!(buf.load(placement.get(b))?, &2u64);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn store<T>(&mut self, value: &T) -> Slot<T>
+    where
+        T: ZeroCopy,
+    {
+        let offset = self.staging.store(value).offset();
+
+        let index = self.entries.len();
+
+        self.entries.push(Entry {
+            offset,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+        });
+
+        Slot {
+            scope: self.id,
+            index,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Write all collected values to `buf` ordered by descending alignment.
+    ///
+    /// Since the size of a type is always a multiple of its alignment, only
+    /// the first value written might need padding.
+    pub(crate) fn flush(self, buf: &mut OwnedBuf<E, O>) -> Placement<E, O> {
+        let mut order = (0..self.entries.len()).collect::<Vec<_>>();
+        order.sort_by_key(|&index| Reverse(self.entries[index].align));
+
+        let mut offsets = alloc::vec![0; self.entries.len()];
+        let staging = self.staging.as_slice();
+
+        for index in order {
+            let entry = &self.entries[index];
+            buf.next_offset_with_and_reserve(entry.align, entry.size);
+            offsets[index] = buf.len();
+            buf.extend_from_slice(&staging[entry.offset..entry.offset + entry.size]);
+        }
+
+        Placement {
+            scope: self.id,
+            offsets,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// A value stored in an [`AlignedScope`], which can be resolved into a [`Ref`]
+/// with [`Placement::get`].
+pub struct Slot<T> {
+    scope: usize,
+    index: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Clone for Slot<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Slot<T> {}
+
+impl<T> fmt::Debug for Slot<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slot")
+            .field("scope", &self.scope)
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+/// The locations that values in an [`AlignedScope`] were written to.
+pub struct Placement<E = Native, O = DefaultSize>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    scope: usize,
+    offsets: Vec<usize>,
+    _marker: PhantomData<(E, O)>,
+}
+
+impl<E, O> Placement<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    /// Resolve a [`Slot`] into a reference to where its value was written.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slot doesn't belong to the scope this placement was
+    /// produced by.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let (slot, placement) = buf.aligned_scope(|scope| scope.store(&42u32));
+    /// let number = placement.get(slot);
+    /// assert_eq!(buf.load(number)?, &42);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn get<T>(&self, slot: Slot<T>) -> Ref<T, E, O>
+    where
+        T: ZeroCopy,
+    {
+        assert_eq!(
+            slot.scope, self.scope,
+            "Slot {} belongs to a different scope than this placement",
+            slot.index
+        );
+
+        Ref::new(self.offsets[slot.index])
+    }
+
+    /// Get the number of values which were placed.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Test if no values were placed.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+}
+
+impl<E, O> fmt::Debug for Placement<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Placement")
+            .field("scope", &self.scope)
+            .field("offsets", &self.offsets)
+            .finish()
+    }
+}
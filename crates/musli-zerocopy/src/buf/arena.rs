@@ -0,0 +1,320 @@
+#![allow(clippy::type_complexity)]
+
+use core::mem::size_of;
+
+use alloc::vec::Vec;
+
+use crate::buf::OwnedBuf;
+use crate::endian::{ByteOrder, Native};
+use crate::mem::MaybeUninit;
+use crate::pointer::{DefaultSize, Ref, Size};
+use crate::traits::ZeroCopy;
+
+/// An arena of nodes of the same type stored in a contiguous region of an
+/// [`OwnedBuf`], constructed through [`OwnedBuf::store_arena`].
+///
+/// Capacity for every node is reserved up front, so the location of a node is
+/// known before it has been written. This makes it possible to link nodes to
+/// each other through [`Arena::next`] without any manual offset calculations.
+///
+/// Freed nodes are put on a free list and reused by later pushes.
+pub struct Arena<T, E = Native, O = DefaultSize>
+where
+    T: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    slots: Ref<[MaybeUninit<T>], E, O>,
+    occupied: Vec<bool>,
+    free: Vec<usize>,
+    len: usize,
+}
+
+impl<T, E, O> Arena<T, E, O>
+where
+    T: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    pub(crate) fn new(slots: Ref<[MaybeUninit<T>], E, O>) -> Self {
+        assert!(size_of::<T>() != 0, "Arena of zero-sized types");
+
+        Self {
+            slots,
+            occupied: Vec::new(),
+            free: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Get the number of live nodes in the arena.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<u32>(4);
+    /// assert_eq!(arena.len(), 0);
+    ///
+    /// let a = arena.push(&mut buf, &1).unwrap();
+    /// arena.push(&mut buf, &2).unwrap();
+    /// assert_eq!(arena.len(), 2);
+    ///
+    /// arena.free(a);
+    /// assert_eq!(arena.len(), 1);
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test if the arena has no live nodes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<u32>(4);
+    /// assert!(arena.is_empty());
+    ///
+    /// arena.push(&mut buf, &1).unwrap();
+    /// assert!(!arena.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the number of nodes the arena can hold.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let arena = buf.store_arena::<u32>(4);
+    /// assert_eq!(arena.capacity(), 4);
+    /// ```
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Get the reference that the next call to [`Arena::push`] will return,
+    /// or `None` if the arena is full.
+    ///
+    /// This is used to link a node to one which hasn't been pushed yet.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(C)]
+    /// struct Node { value: u32, next: Ref<Node> }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<Node>(3);
+    ///
+    /// // The first node pushed ends the list by referencing itself.
+    /// let mut head = arena.next().unwrap();
+    ///
+    /// for value in [3, 2, 1] {
+    ///     head = arena.push(&mut buf, &Node { value, next: head }).unwrap();
+    /// }
+    ///
+    /// assert!(arena.next().is_none());
+    ///
+    /// let mut values = Vec::new();
+    ///
+    /// loop {
+    ///     let node = buf.load(head)?;
+    ///     values.push(node.value);
+    ///
+    ///     if node.next == head {
+    ///         break;
+    ///     }
+    ///
+    ///     head = node.next;
+    /// }
+    ///
+    /// assert_eq!(values, [1, 2, 3]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn next(&self) -> Option<Ref<T, E, O>> {
+        let index = self.next_index()?;
+        Some(self.slots.assume_init().get_unchecked(index))
+    }
+
+    /// Push a node into the arena, returning a reference to it or `None` if
+    /// the arena is full.
+    ///
+    /// Slots of freed nodes are reused before any unused ones.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buf` is not the buffer the arena was stored in.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<u32>(2);
+    ///
+    /// let a = arena.push(&mut buf, &1).unwrap();
+    /// let b = arena.push(&mut buf, &2).unwrap();
+    /// assert!(arena.push(&mut buf, &3).is_none());
+    ///
+    /// arena.free(a);
+    /// let c = arena.push(&mut buf, &3).unwrap();
+    /// assert_eq!(a, c);
+    ///
+    /// assert_eq!(buf.load(b)?, &2);
+    /// assert_eq!(buf.load(c)?, &3);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn push(&mut self, buf: &mut OwnedBuf<E, O>, value: &T) -> Option<Ref<T, E, O>> {
+        let index = self.next_index()?;
+
+        buf.load_uninit_slice_mut(self.slots)[index].write(value);
+
+        if self.free.last() == Some(&index) {
+            self.free.pop();
+            self.occupied[index] = true;
+        } else {
+            self.occupied.push(true);
+        }
+
+        self.len += 1;
+        Some(self.slots.assume_init().get_unchecked(index))
+    }
+
+    /// Free a node, allowing its slot to be reused by a later push.
+    ///
+    /// The freed node keeps its last value until the slot is reused, so the
+    /// slice returned by [`Arena::finish`] can still be loaded in full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the node doesn't belong to this arena or has already been
+    /// freed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<u32>(2);
+    ///
+    /// let a = arena.push(&mut buf, &1).unwrap();
+    /// arena.push(&mut buf, &2).unwrap();
+    /// arena.free(a);
+    ///
+    /// let (_, nodes) = arena.finish();
+    /// assert_eq!(nodes.len(), 1);
+    /// assert_eq!(buf.load(nodes[0])?, &2);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn free(&mut self, node: Ref<T, E, O>) {
+        let index = self.index_of(node);
+
+        assert!(
+            self.occupied.get(index).copied().unwrap_or_default(),
+            "Node at offset {} has not been pushed or is already freed",
+            node.offset()
+        );
+
+        self.occupied[index] = false;
+        self.free.push(index);
+        self.len -= 1;
+    }
+
+    /// Finish the arena, returning a reference to the slice of every slot
+    /// which has been used and references to the live nodes in the order of
+    /// their slots.
+    ///
+    /// Unused capacity at the end of the arena is not included in the slice,
+    /// but remains in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(C)]
+    /// struct Node { value: u32, parent: Ref<Node> }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<Node>(8);
+    ///
+    /// let parent = arena.next().unwrap();
+    /// let root = arena.push(&mut buf, &Node { value: 1, parent }).unwrap();
+    /// arena.push(&mut buf, &Node { value: 2, parent: root }).unwrap();
+    /// arena.push(&mut buf, &Node { value: 3, parent: root }).unwrap();
+    ///
+    /// let (slice, nodes) = arena.finish();
+    /// assert_eq!(slice.len(), 3);
+    /// assert_eq!(nodes.len(), 3);
+    ///
+    /// for node in buf.load(slice)?.iter().skip(1) {
+    ///     assert_eq!(buf.load(node.parent)?.value, 1);
+    /// }
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn finish(self) -> (Ref<[T], E, O>, Vec<Ref<T, E, O>>) {
+        let slots = self.slots.assume_init();
+        let (slice, _) = slots.split_at(self.occupied.len());
+
+        let nodes = self
+            .occupied
+            .iter()
+            .enumerate()
+            .filter(|(_, occupied)| **occupied)
+            .map(|(index, _)| slots.get_unchecked(index))
+            .collect();
+
+        (slice, nodes)
+    }
+
+    #[inline]
+    fn next_index(&self) -> Option<usize> {
+        if let Some(&index) = self.free.last() {
+            return Some(index);
+        }
+
+        let index = self.occupied.len();
+        (index < self.slots.len()).then_some(index)
+    }
+
+    fn index_of(&self, node: Ref<T, E, O>) -> usize {
+        let start = self.slots.offset();
+
+        let index = node
+            .offset()
+            .checked_sub(start)
+            .filter(|offset| offset % size_of::<T>() == 0)
+            .map(|offset| offset / size_of::<T>())
+            .filter(|&index| index < self.slots.len());
+
+        let Some(index) = index else {
+            panic!(
+                "Node at offset {} does not belong to the arena",
+                node.offset()
+            );
+        };
+
+        index
+    }
+}
@@ -8,7 +8,7 @@ mod sealed {
 
     pub trait Sealed {}
 
-    impl<K, V, E, O> Sealed for crate::phf::map::MapRef<K, V, E, O>
+    impl<K, V, E, O, H> Sealed for crate::phf::map::MapRef<K, V, E, O, H>
     where
         K: ZeroCopy,
         V: ZeroCopy,
@@ -26,7 +26,7 @@ mod sealed {
     {
     }
 
-    impl<T, E, O> Sealed for crate::phf::set::SetRef<T, E, O>
+    impl<T, E, O, H> Sealed for crate::phf::set::SetRef<T, E, O, H>
     where
         T: ZeroCopy,
         E: ByteOrder,
@@ -8,11 +8,15 @@ use core::slice::SliceIndex;
 #[cfg(feature = "alloc")]
 use alloc::borrow::{Cow, ToOwned};
 
+use crate::buf::{
+    self, Bindable, Load, LoadMut, TreeValidator, TrustedBuf, ValidateTree, Validator,
+};
 #[cfg(feature = "alloc")]
-use crate::buf::OwnedBuf;
-use crate::buf::{self, Bindable, Load, LoadMut, Validator};
-use crate::endian::ByteOrder;
+use crate::buf::{Dump, OwnedBuf};
+use crate::endian::{ByteOrder, Native};
 use crate::error::{Error, ErrorKind};
+#[cfg(feature = "alloc")]
+use crate::pointer::Pointee;
 use crate::pointer::{Ref, Size};
 use crate::traits::{UnsizedZeroCopy, ZeroCopy};
 
@@ -419,6 +423,81 @@ impl Buf {
         self.load_sized_unaligned::<T>(offset)
     }
 
+    /// Read a value of type `T` at the given `offset`.
+    ///
+    /// This is the same as loading a [`Ref<T>`] constructed with
+    /// [`Ref::new(offset)`], and is intended for parsing where offsets are
+    /// computed rather than stored in the buffer.
+    ///
+    /// [`Ref::new(offset)`]: Ref::new
+    ///
+    /// # Errors
+    ///
+    /// This will error if the value at `offset` is out of bounds, is not
+    /// aligned for the type `T`, or is not a valid bit pattern for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Buf;
+    ///
+    /// let mut bytes = [0u8; 8];
+    /// bytes[..4].copy_from_slice(&4u32.to_ne_bytes());
+    /// bytes[4..].copy_from_slice(&42u32.to_ne_bytes());
+    ///
+    /// let buf = Buf::new(&bytes).to_aligned_with(4);
+    ///
+    /// let offset = *buf.read::<u32>(0)?;
+    /// assert_eq!(buf.read::<u32>(offset as usize)?, &42);
+    /// assert!(buf.read::<u32>(8).is_err());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn read<T>(&self, offset: usize) -> Result<&T, Error>
+    where
+        T: ZeroCopy,
+    {
+        self.load_sized::<T>(offset)
+    }
+
+    /// Read a slice of `len` values of type `T` starting at the given
+    /// `offset`.
+    ///
+    /// This is the same as loading a [`Ref<[T]>`] constructed with
+    /// [`Ref::with_metadata(offset, len)`].
+    ///
+    /// [`Ref<[T]>`]: Ref
+    /// [`Ref::with_metadata(offset, len)`]: Ref::with_metadata
+    ///
+    /// # Errors
+    ///
+    /// This will error if the slice is out of bounds, is not aligned for the
+    /// type `T`, or if any of its elements is not a valid bit pattern for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&2u32);
+    /// buf.store(&[10u16, 20, 30]);
+    ///
+    /// let len = *buf.read::<u32>(0)? as usize;
+    /// assert_eq!(buf.read_slice::<u16>(4, len)?, &[10, 20]);
+    /// assert_eq!(buf.read_slice::<u16>(6, len)?, &[20, 30]);
+    /// assert!(buf.read_slice::<u16>(6, 3).is_err());
+    /// assert!(buf.read_slice::<u16>(5, 1).is_err());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn read_slice<T>(&self, offset: usize, len: usize) -> Result<&[T], Error>
+    where
+        T: ZeroCopy,
+    {
+        self.load_unsized(Ref::<[T], Native, usize>::with_metadata(offset, len))
+    }
+
     /// Load the given value as a mutable reference.
     ///
     /// # Errors
@@ -498,6 +577,242 @@ impl Buf {
         ptr.bind(self)
     }
 
+    /// Eagerly validate the value behind `root` and every value reachable
+    /// from it, such as the values behind references, slices, strings and
+    /// containers like [`swiss::MapRef`] or [`trie::TrieRef`].
+    ///
+    /// Values which are reachable through several references are validated
+    /// once for each reference.
+    ///
+    /// [`swiss::MapRef`]: crate::swiss::MapRef
+    /// [`trie::TrieRef`]: crate::trie::TrieRef
+    ///
+    /// # Errors
+    ///
+    /// Errors if any reachable value fails to load, or if a chain of more than
+    /// 1024 references is encountered, which for example happens if the
+    /// references in the buffer form a cycle. Use [`Buf::validate_graph`] to
+    /// validate buffers which are allowed to contain cycles.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy, ValidateTree)]
+    /// #[repr(C)]
+    /// struct Person {
+    ///     name: Ref<str>,
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let name = buf.store_unsized("Aristotle");
+    /// let person = buf.store(&Person { name, age: 61 });
+    ///
+    /// buf.validate_tree(person)?;
+    ///
+    /// // Corrupt the name so that it's no longer valid UTF-8.
+    /// buf.load_mut(name.coerce::<[u8]>())?[0] = 0xff;
+    /// assert!(buf.validate_tree(person).is_err());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn validate_tree<T>(&self, root: T) -> Result<(), Error>
+    where
+        T: Load,
+        T::Target: ValidateTree,
+    {
+        TreeValidator::new(self).follow(root)
+    }
+
+    /// Eagerly validate the value behind `root` and every value reachable
+    /// from it like [`Buf::validate_tree`], while permitting the references in
+    /// the buffer to form cycles.
+    ///
+    /// Every distinct value is only validated once, so values which are
+    /// reachable through several references, such as an ancestor referenced
+    /// by one of its descendants, are not visited again. Success is only
+    /// reported if every reachable value is valid.
+    ///
+    /// At most 1048576 distinct values are visited, use
+    /// [`Buf::validate_graph_with_limit`] to configure this.
+    ///
+    /// # Errors
+    ///
+    /// Errors if any reachable value fails to load, if a chain of more than
+    /// 1024 references to distinct values is encountered, or if too many
+    /// distinct values are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy, ValidateTree)]
+    /// #[repr(C)]
+    /// struct Node {
+    ///     name: Ref<str>,
+    ///     parent: Ref<Node>,
+    /// }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let name = buf.store_unsized("root");
+    /// let root = buf.store_uninit::<Node>();
+    /// let parent = root.assume_init();
+    /// buf.load_uninit_mut(root).write(&Node { name, parent });
+    ///
+    /// // The root is its own parent, so only graph validation terminates.
+    /// assert!(buf.validate_tree(parent).is_err());
+    /// buf.validate_graph(parent)?;
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn validate_graph<T>(&self, root: T) -> Result<(), Error>
+    where
+        T: Load,
+        T::Target: ValidateTree,
+    {
+        self.validate_graph_with_limit(root, buf::DEFAULT_VISIT_LIMIT)
+    }
+
+    /// Eagerly validate the value behind `root` and every value reachable
+    /// from it like [`Buf::validate_graph`], visiting at most `limit` distinct
+    /// values.
+    ///
+    /// This bounds the memory used to keep track of visited values.
+    ///
+    /// # Errors
+    ///
+    /// Errors under the same conditions as [`Buf::validate_graph`], or if more
+    /// than `limit` distinct values are visited.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let a = buf.store_unsized("a");
+    /// let b = buf.store_unsized("b");
+    /// let names = buf.store_slice(&[a, b, a]);
+    ///
+    /// buf.validate_graph_with_limit(names, 3)?;
+    /// assert!(buf.validate_graph_with_limit(names, 2).is_err());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn validate_graph_with_limit<T>(&self, root: T, limit: usize) -> Result<(), Error>
+    where
+        T: Load,
+        T::Target: ValidateTree,
+    {
+        TreeValidator::with_visit_limit(self, limit).follow(root)
+    }
+
+    /// Validate the tree reachable from `root` through
+    /// [`Buf::validate_tree`], and return a [`TrustedBuf`] which permits
+    /// loading values reachable from it without further validation.
+    ///
+    /// # Errors
+    ///
+    /// Errors under the same conditions as [`Buf::validate_tree`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy, ValidateTree)]
+    /// #[repr(C)]
+    /// struct Names {
+    ///     names: Ref<[Ref<str>]>,
+    /// }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let first = buf.store_unsized("first");
+    /// let second = buf.store_unsized("second");
+    /// let names = buf.store_slice(&[first, second]);
+    /// let root = buf.store(&Names { names });
+    ///
+    /// let trusted = buf.trust(root)?;
+    ///
+    /// // SAFETY: Every reference is reachable from the validated root.
+    /// let names = unsafe { trusted.load(trusted.root().names) };
+    /// let names = names.iter().map(|name| unsafe { trusted.load(*name) });
+    /// assert!(names.eq(["first", "second"]));
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn trust<T>(&self, root: T) -> Result<TrustedBuf<'_, T::Target>, Error>
+    where
+        T: Load,
+        T::Target: ValidateTree,
+    {
+        let root = root.load(self)?;
+        TreeValidator::new(self).enter(root)?;
+        // SAFETY: The root was loaded from this buffer and its tree has just
+        // been validated.
+        Ok(unsafe { TrustedBuf::new(self, root) })
+    }
+
+    /// Produce a human-readable dump of the value behind `ptr`, including the
+    /// offsets of its fields and the values behind any references it
+    /// contains.
+    ///
+    /// Fields are only broken down for types deriving [`ZeroCopy`] with
+    /// `#[zero_copy(layout)]`, the bytes of other values are shown as is. This
+    /// doesn't validate the buffer, so it can be used to diagnose why loading
+    /// a value fails. Values which are reachable through several references
+    /// are only shown once.
+    ///
+    /// [`ZeroCopy`]: derive@crate::ZeroCopy
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(C)]
+    /// #[zero_copy(layout)]
+    /// struct Person {
+    ///     name: Ref<str>,
+    ///     age: u8,
+    /// }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let name = buf.store_unsized("Aristotle");
+    /// let person = buf.store(&Person { name, age: 61 });
+    ///
+    /// let expected = concat!(
+    ///     "    12  Person\n",
+    ///     "    12    name: Ref<str> -> 0 (len: 9)\n",
+    ///     "     0      \"Aristotle\"\n",
+    ///     "    20    age: u8 = 3d\n",
+    /// );
+    ///
+    /// assert_eq!(buf.dump(person).to_string(), expected);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn dump<T, E, O>(&self, ptr: Ref<T, E, O>) -> Dump<'_>
+    where
+        T: ?Sized + Pointee,
+        E: ByteOrder,
+        O: Size,
+    {
+        let len = T::__len::<E, O>(ptr.metadata());
+        Dump::new(self, Ref::<T, E, O>::POINTER_LAYOUT, ptr.offset(), len)
+    }
+
     /// Cast the current buffer into the given type.
     ///
     /// This is usually only used indirectly by deriving [`ZeroCopy`].
@@ -744,6 +1059,39 @@ impl Buf {
         }
     }
 
+    /// Load an unsized reference without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the range described by `start` and
+    /// `metadata` is in bounds, aligned and valid for `T`.
+    #[inline]
+    pub(crate) unsafe fn load_unsized_unchecked<T>(
+        &self,
+        start: usize,
+        metadata: T::Metadata,
+    ) -> *const T
+    where
+        T: ?Sized + UnsizedZeroCopy,
+    {
+        let ptr = NonNull::new_unchecked(self.data.as_ptr().add(start) as *mut u8);
+        T::with_metadata(ptr, metadata)
+    }
+
+    /// Load the given sized value as a reference without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that the value at `offset` is in bounds, aligned
+    /// and valid for `T`.
+    #[inline]
+    pub(crate) unsafe fn load_sized_unchecked<T>(&self, offset: usize) -> &T
+    where
+        T: ZeroCopy,
+    {
+        &*self.data.as_ptr().add(offset).cast::<T>()
+    }
+
     /// Load the given sized value as a reference.
     #[inline]
     pub(crate) fn load_sized<T>(&self, offset: usize) -> Result<&T, Error>
@@ -751,7 +1099,7 @@ impl Buf {
         T: ZeroCopy,
     {
         unsafe {
-            let end = offset + size_of::<T>();
+            let end = offset.saturating_add(size_of::<T>());
 
             // SAFETY: align_of::<T>() is always a power of two.
             let buf = self.inner_get(offset, end, align_of::<T>())?;
@@ -841,7 +1189,7 @@ impl Buf {
         }
 
         let start = a.max(b);
-        let end = start + size_of::<T>();
+        let end = start.saturating_add(size_of::<T>());
 
         if end > self.data.len() {
             return Err(Error::new(ErrorKind::OutOfRangeBounds {
@@ -875,7 +1223,7 @@ impl Buf {
     where
         T: ZeroCopy,
     {
-        let end = offset + size_of::<T>();
+        let end = offset.saturating_add(size_of::<T>());
 
         unsafe {
             // SAFETY: align_of::<T>() is always a power of two.
@@ -900,7 +1248,7 @@ impl Buf {
     where
         T: ZeroCopy,
     {
-        let end = start + size_of::<T>();
+        let end = start.saturating_add(size_of::<T>());
 
         unsafe {
             // SAFETY: align_of::<T>() is always a power of two.
@@ -0,0 +1,377 @@
+use core::fmt;
+use core::str;
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::buf::Buf;
+use crate::endian::ByteOrder;
+use crate::layout::{PointeeKind, PointerLayout, TypeLayout};
+use crate::pointer::{Pointee, Ref, Size};
+
+/// The default maximum number of differences which are reported by [`diff`].
+const DEFAULT_LIMIT: usize = 64;
+
+/// The maximum number of nested values which are compared, which bounds the
+/// stack used to follow long chains of references.
+const MAX_DEPTH: usize = 256;
+
+/// Structurally compare the values behind `root_a` in `a` and `root_b` in
+/// `b`, returning where they differ.
+///
+/// Both values are walked in lockstep, following references into each buffer
+/// separately. Where values are stored doesn't matter, only what is stored,
+/// so two archives constructed in different orders compare as equal.
+///
+/// Like [`Buf::dump`], fields are only broken down for types deriving
+/// [`ZeroCopy`] with `#[zero_copy(layout)]`, other values are compared
+/// byte-by-byte. This doesn't validate either buffer, so it can be used to
+/// find where a corrupted archive diverges from a good one.
+///
+/// At most 64 differences are reported, use [`diff_with_limit`] to configure
+/// this.
+///
+/// [`ZeroCopy`]: derive@crate::ZeroCopy
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+/// use musli_zerocopy::buf::DifferenceKind;
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(C)]
+/// #[zero_copy(layout)]
+/// struct Person {
+///     name: Ref<str>,
+///     age: u8,
+/// }
+///
+/// let mut a = OwnedBuf::new();
+/// let name = a.store_unsized("Aristotle");
+/// let root_a = a.store(&Person { name, age: 61 });
+///
+/// let mut b = OwnedBuf::new();
+/// let root_b = b.store_uninit::<Person>();
+/// let name = b.store_unsized("Plato");
+/// b.load_uninit_mut(root_b).write(&Person { name, age: 61 });
+/// let root_b = root_b.assume_init();
+///
+/// let differences = musli_zerocopy::diff(&a, root_a, &b, root_b);
+/// assert_eq!(differences.len(), 1);
+/// assert_eq!(differences[0].path(), "name");
+/// assert_eq!(differences[0].kind(), DifferenceKind::Value);
+/// assert_eq!(differences[0].offset_a(), 0);
+/// assert_eq!(differences[0].offset_b(), 12);
+/// ```
+pub fn diff<T, E, O>(
+    a: &Buf,
+    root_a: Ref<T, E, O>,
+    b: &Buf,
+    root_b: Ref<T, E, O>,
+) -> Vec<Difference>
+where
+    T: ?Sized + Pointee,
+    E: ByteOrder,
+    O: Size,
+{
+    diff_with_limit(a, root_a, b, root_b, DEFAULT_LIMIT)
+}
+
+/// Structurally compare two values like [`diff`], reporting at most `limit`
+/// differences.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::OwnedBuf;
+///
+/// let mut a = OwnedBuf::new();
+/// let root_a = a.store_slice(&[1u32, 2, 3, 4]);
+///
+/// let mut b = OwnedBuf::new();
+/// let root_b = b.store_slice(&[0u32, 0, 0, 0]);
+///
+/// assert_eq!(musli_zerocopy::diff(&a, root_a, &b, root_b).len(), 4);
+/// assert_eq!(musli_zerocopy::diff_with_limit(&a, root_a, &b, root_b, 2).len(), 2);
+/// ```
+pub fn diff_with_limit<T, E, O>(
+    a: &Buf,
+    root_a: Ref<T, E, O>,
+    b: &Buf,
+    root_b: Ref<T, E, O>,
+    limit: usize,
+) -> Vec<Difference>
+where
+    T: ?Sized + Pointee,
+    E: ByteOrder,
+    O: Size,
+{
+    let mut differ = Differ {
+        a,
+        b,
+        limit,
+        path: String::new(),
+        differences: Vec::new(),
+        visited: BTreeSet::new(),
+    };
+
+    let pointer = Ref::<T, E, O>::POINTER_LAYOUT;
+    let side_a = (root_a.offset(), T::__len::<E, O>(root_a.metadata()));
+    let side_b = (root_b.offset(), T::__len::<E, O>(root_b.metadata()));
+    differ.target(&pointer, side_a, side_b, 0);
+    differ.differences
+}
+
+/// A difference between two values, as returned by [`diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Difference {
+    path: String,
+    offset_a: usize,
+    offset_b: usize,
+    kind: DifferenceKind,
+}
+
+impl Difference {
+    /// The path to the value which differs, such as `points[1].x`.
+    ///
+    /// This is empty if the root values themselves differ.
+    #[inline]
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The offset of the value in the first buffer.
+    #[inline]
+    pub fn offset_a(&self) -> usize {
+        self.offset_a
+    }
+
+    /// The offset of the value in the second buffer.
+    #[inline]
+    pub fn offset_b(&self) -> usize {
+        self.offset_b
+    }
+
+    /// How the values differ.
+    #[inline]
+    pub fn kind(&self) -> DifferenceKind {
+        self.kind
+    }
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "<root>"
+        } else {
+            &self.path
+        };
+
+        write!(
+            f,
+            "{path}: {} (at {} and {})",
+            self.kind, self.offset_a, self.offset_b
+        )
+    }
+}
+
+/// How two values differ, as returned by [`Difference::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DifferenceKind {
+    /// The values have different contents.
+    Value,
+    /// The slices have a different number of elements.
+    Length {
+        /// The length of the slice in the first buffer.
+        a: usize,
+        /// The length of the slice in the second buffer.
+        b: usize,
+    },
+    /// The values can't be compared, because the value in one of the buffers
+    /// is out of bounds or contains a pointer which can't be read.
+    Structure,
+}
+
+impl fmt::Display for DifferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DifferenceKind::Value => write!(f, "value mismatch"),
+            DifferenceKind::Length { a, b } => write!(f, "length mismatch ({a} != {b})"),
+            DifferenceKind::Structure => write!(f, "structure mismatch"),
+        }
+    }
+}
+
+struct Differ<'a> {
+    a: &'a Buf,
+    b: &'a Buf,
+    limit: usize,
+    path: String,
+    differences: Vec<Difference>,
+    visited: BTreeSet<(usize, usize, usize)>,
+}
+
+impl Differ<'_> {
+    #[inline]
+    fn is_full(&self) -> bool {
+        self.differences.len() >= self.limit
+    }
+
+    fn report(&mut self, offset_a: usize, offset_b: usize, kind: DifferenceKind) {
+        if self.is_full() {
+            return;
+        }
+
+        self.differences.push(Difference {
+            path: self.path.clone(),
+            offset_a,
+            offset_b,
+            kind,
+        });
+    }
+
+    /// Run `f` with `segment` appended to the current path.
+    fn with_path(&mut self, segment: fmt::Arguments<'_>, f: impl FnOnce(&mut Self)) {
+        use core::fmt::Write;
+
+        let len = self.path.len();
+        _ = self.path.write_fmt(segment);
+        f(self);
+        self.path.truncate(len);
+    }
+
+    /// Compare the values which `pointer` points to in each buffer.
+    fn target(
+        &mut self,
+        pointer: &PointerLayout,
+        (offset_a, len_a): (usize, usize),
+        (offset_b, len_b): (usize, usize),
+        depth: usize,
+    ) {
+        match pointer.kind() {
+            PointeeKind::Sized => {
+                self.value(pointer, offset_a, offset_b, depth);
+            }
+            PointeeKind::Slice => {
+                if len_a != len_b {
+                    self.report(
+                        offset_a,
+                        offset_b,
+                        DifferenceKind::Length { a: len_a, b: len_b },
+                    );
+                }
+
+                for index in 0..len_a.min(len_b) {
+                    if self.is_full() {
+                        return;
+                    }
+
+                    let a = offset_a.wrapping_add(index.wrapping_mul(pointer.size()));
+                    let b = offset_b.wrapping_add(index.wrapping_mul(pointer.size()));
+
+                    self.with_path(format_args!("[{index}]"), |this| {
+                        this.value(pointer, a, b, depth);
+                    });
+                }
+            }
+            PointeeKind::Str => {
+                let a = self.a.get(offset_a..offset_a.saturating_add(len_a));
+                let b = self.b.get(offset_b..offset_b.saturating_add(len_b));
+
+                match (a.map(str::from_utf8), b.map(str::from_utf8)) {
+                    (Some(Ok(a)), Some(Ok(b))) => {
+                        if a != b {
+                            self.report(offset_a, offset_b, DifferenceKind::Value);
+                        }
+                    }
+                    _ => {
+                        self.report(offset_a, offset_b, DifferenceKind::Structure);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compare a single value described by `pointer` stored at the given
+    /// offsets, and anything it points to.
+    fn value(&mut self, pointer: &PointerLayout, offset_a: usize, offset_b: usize, depth: usize) {
+        self.compare(
+            offset_a,
+            offset_b,
+            pointer.size(),
+            pointer.layout(),
+            pointer.pointer(),
+            depth,
+        );
+    }
+
+    fn compare(
+        &mut self,
+        offset_a: usize,
+        offset_b: usize,
+        size: usize,
+        layout: Option<&'static TypeLayout>,
+        pointer: Option<PointerLayout>,
+        depth: usize,
+    ) {
+        if self.is_full() {
+            return;
+        }
+
+        let (Some(a), Some(b)) = (
+            self.a.get(offset_a..offset_a.saturating_add(size)),
+            self.b.get(offset_b..offset_b.saturating_add(size)),
+        ) else {
+            self.report(offset_a, offset_b, DifferenceKind::Structure);
+            return;
+        };
+
+        if let Some(layout) = layout {
+            for field in layout.fields() {
+                if self.is_full() {
+                    return;
+                }
+
+                let separator = if self.path.is_empty() { "" } else { "." };
+
+                self.with_path(format_args!("{separator}{}", field.name()), |this| {
+                    this.compare(
+                        offset_a + field.offset(),
+                        offset_b + field.offset(),
+                        field.size(),
+                        field.layout(),
+                        field.pointer(),
+                        depth,
+                    );
+                });
+            }
+
+            return;
+        }
+
+        if let Some(pointer) = pointer {
+            let (Some(target_a), Some(target_b)) = (pointer.read(a), pointer.read(b)) else {
+                self.report(offset_a, offset_b, DifferenceKind::Structure);
+                return;
+            };
+
+            if depth >= MAX_DEPTH
+                || !self
+                    .visited
+                    .insert((target_a.0, target_b.0, pointer.size()))
+            {
+                return;
+            }
+
+            self.target(&pointer, target_a, target_b, depth + 1);
+            return;
+        }
+
+        if a != b {
+            self.report(offset_a, offset_b, DifferenceKind::Value);
+        }
+    }
+}
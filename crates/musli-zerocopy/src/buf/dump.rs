@@ -0,0 +1,236 @@
+use core::fmt;
+use core::str;
+
+use alloc::collections::BTreeSet;
+
+use crate::buf::Buf;
+use crate::layout::{PointeeKind, PointerLayout, TypeLayout};
+
+/// The maximum number of elements of a slice which are included in a dump.
+const MAX_ELEMENTS: usize = 64;
+
+/// The maximum number of bytes of a value without a known layout which are
+/// included in a dump.
+const MAX_BYTES: usize = 32;
+
+/// The maximum number of nested values which are included in a dump, which
+/// bounds the stack used to follow long chains of references.
+const MAX_DEPTH: usize = 256;
+
+/// A human-readable dump of the values stored in a buffer, as returned by
+/// [`Buf::dump`].
+///
+/// This is produced by formatting it through its [`Display`] implementation.
+///
+/// [`Display`]: fmt::Display
+pub struct Dump<'a> {
+    buf: &'a Buf,
+    pointer: PointerLayout,
+    offset: usize,
+    len: usize,
+}
+
+impl<'a> Dump<'a> {
+    #[inline]
+    pub(crate) fn new(buf: &'a Buf, pointer: PointerLayout, offset: usize, len: usize) -> Self {
+        Self {
+            buf,
+            pointer,
+            offset,
+            len,
+        }
+    }
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut dumper = Dumper {
+            buf: self.buf,
+            f,
+            visited: BTreeSet::new(),
+        };
+
+        dumper
+            .visited
+            .insert((self.offset, self.len, self.pointer.size()));
+        dumper.target(&self.pointer, self.offset, self.len, 0)
+    }
+}
+
+impl fmt::Debug for Dump<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+struct Dumper<'a, 'f, 'b> {
+    buf: &'a Buf,
+    f: &'f mut fmt::Formatter<'b>,
+    visited: BTreeSet<(usize, usize, usize)>,
+}
+
+impl Dumper<'_, '_, '_> {
+    /// Write the start of a row describing the value at `offset`.
+    fn row(&mut self, offset: usize, depth: usize, label: fmt::Arguments<'_>) -> fmt::Result {
+        write!(
+            self.f,
+            "{offset:>6}  {:indent$}{label}",
+            "",
+            indent = depth * 2
+        )
+    }
+
+    /// Dump the value which `pointer` points to.
+    fn target(
+        &mut self,
+        pointer: &PointerLayout,
+        offset: usize,
+        len: usize,
+        depth: usize,
+    ) -> fmt::Result {
+        match pointer.kind() {
+            PointeeKind::Sized => {
+                let layout = pointer.layout();
+                let name = layout.map(TypeLayout::name).unwrap_or("value");
+
+                self.value(
+                    offset,
+                    depth,
+                    format_args!("{name}"),
+                    pointer.size(),
+                    pointer.align(),
+                    layout,
+                    pointer.pointer(),
+                )?;
+            }
+            PointeeKind::Slice => {
+                let layout = pointer.layout();
+                let pointee = pointer.pointer();
+
+                for index in 0..len.min(MAX_ELEMENTS) {
+                    let offset = offset.wrapping_add(index.wrapping_mul(pointer.size()));
+
+                    match layout {
+                        Some(layout) => self.value(
+                            offset,
+                            depth,
+                            format_args!("[{index}]: {}", layout.name()),
+                            pointer.size(),
+                            pointer.align(),
+                            Some(layout),
+                            pointee,
+                        )?,
+                        None => self.value(
+                            offset,
+                            depth,
+                            format_args!("[{index}]"),
+                            pointer.size(),
+                            pointer.align(),
+                            None,
+                            pointee,
+                        )?,
+                    }
+                }
+
+                if len > MAX_ELEMENTS {
+                    self.row(
+                        offset.wrapping_add(MAX_ELEMENTS.wrapping_mul(pointer.size())),
+                        depth,
+                        format_args!("... ({} more)", len - MAX_ELEMENTS),
+                    )?;
+                    writeln!(self.f)?;
+                }
+            }
+            PointeeKind::Str => {
+                self.row(offset, depth, format_args!(""))?;
+
+                match self.buf.get(offset..offset.saturating_add(len)) {
+                    Some(bytes) => match str::from_utf8(bytes) {
+                        Ok(string) => writeln!(self.f, "{string:?}")?,
+                        Err(..) => writeln!(self.f, "<invalid utf-8>")?,
+                    },
+                    None => writeln!(self.f, "<out of bounds>")?,
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Dump a single value, its fields, and anything it points to.
+    #[allow(clippy::too_many_arguments)]
+    fn value(
+        &mut self,
+        offset: usize,
+        depth: usize,
+        label: fmt::Arguments<'_>,
+        size: usize,
+        align: usize,
+        layout: Option<&'static TypeLayout>,
+        pointer: Option<PointerLayout>,
+    ) -> fmt::Result {
+        self.row(offset, depth, label)?;
+
+        if offset % align != 0 {
+            write!(self.f, " (misaligned)")?;
+        }
+
+        let Some(bytes) = self.buf.get(offset..offset.saturating_add(size)) else {
+            return writeln!(self.f, " <out of bounds>");
+        };
+
+        if let Some(layout) = layout {
+            writeln!(self.f)?;
+
+            for field in layout.fields() {
+                self.value(
+                    offset + field.offset(),
+                    depth + 1,
+                    format_args!("{}: {}", field.name(), field.ty()),
+                    field.size(),
+                    field.align(),
+                    field.layout(),
+                    field.pointer(),
+                )?;
+            }
+
+            return Ok(());
+        }
+
+        if let Some(pointer) = pointer {
+            let Some((target, len)) = pointer.read(bytes) else {
+                return writeln!(self.f, " <invalid pointer>");
+            };
+
+            write!(self.f, " -> {target}")?;
+
+            if pointer.kind() != PointeeKind::Sized {
+                write!(self.f, " (len: {len})")?;
+            }
+
+            if depth >= MAX_DEPTH {
+                return writeln!(self.f, " (too deep)");
+            }
+
+            if !self.visited.insert((target, len, pointer.size())) {
+                return writeln!(self.f, " (see above)");
+            }
+
+            writeln!(self.f)?;
+            return self.target(&pointer, target, len, depth + 1);
+        }
+
+        write!(self.f, " =")?;
+
+        for b in bytes.iter().take(MAX_BYTES) {
+            write!(self.f, " {b:02x}")?;
+        }
+
+        if bytes.len() > MAX_BYTES {
+            write!(self.f, " ...")?;
+        }
+
+        writeln!(self.f)
+    }
+}
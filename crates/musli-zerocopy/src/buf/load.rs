@@ -18,6 +18,24 @@ pub trait Load {
 
     /// Validate the value.
     fn load<'buf>(&self, buf: &'buf Buf) -> Result<&'buf Self::Target, Error>;
+
+    /// Load the value without validating it.
+    ///
+    /// This is used by [`TrustedBuf::load`], and the default implementation
+    /// only skips error handling. Implementations which can skip validation
+    /// entirely should override it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that [`Load::load`] would succeed for the same
+    /// pointer and buffer, such as when the pointer is reachable from a root
+    /// which has been validated through [`Buf::validate_tree`].
+    ///
+    /// [`TrustedBuf::load`]: crate::buf::TrustedBuf::load
+    #[inline]
+    unsafe fn load_unchecked<'buf>(&self, buf: &'buf Buf) -> &'buf Self::Target {
+        self.load(buf).unwrap_unchecked()
+    }
 }
 
 /// Trait used for loading any kind of reference through [`Buf::load_mut`].
@@ -45,6 +63,11 @@ where
     fn load<'buf>(&self, buf: &'buf Buf) -> Result<&'buf Self::Target, Error> {
         buf.load_sized::<T>(self.offset())
     }
+
+    #[inline]
+    unsafe fn load_unchecked<'buf>(&self, buf: &'buf Buf) -> &'buf Self::Target {
+        buf.load_sized_unchecked::<T>(self.offset())
+    }
 }
 
 impl<T, E, O> Load for Ref<[T], E, O>
@@ -59,6 +82,11 @@ where
     fn load<'buf>(&self, buf: &'buf Buf) -> Result<&'buf Self::Target, Error> {
         buf.load_unsized(*self)
     }
+
+    #[inline]
+    unsafe fn load_unchecked<'buf>(&self, buf: &'buf Buf) -> &'buf Self::Target {
+        &*buf.load_unsized_unchecked::<[T]>(self.offset(), self.len())
+    }
 }
 
 impl<E, O> Load for Ref<str, E, O>
@@ -72,6 +100,11 @@ where
     fn load<'buf>(&self, buf: &'buf Buf) -> Result<&'buf Self::Target, Error> {
         buf.load_unsized(*self)
     }
+
+    #[inline]
+    unsafe fn load_unchecked<'buf>(&self, buf: &'buf Buf) -> &'buf Self::Target {
+        &*buf.load_unsized_unchecked::<str>(self.offset(), self.len())
+    }
 }
 
 impl<T, E, O> LoadMut for Ref<T, E, O>
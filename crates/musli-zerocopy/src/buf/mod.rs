@@ -105,6 +105,14 @@ pub(crate) mod visit;
 pub use self::validator::Validator;
 mod validator;
 
+#[cfg(feature = "alloc")]
+pub(crate) use self::validate_tree::DEFAULT_VISIT_LIMIT;
+pub use self::validate_tree::{TreeValidator, ValidateTree};
+mod validate_tree;
+
+pub use self::trusted_buf::TrustedBuf;
+mod trusted_buf;
+
 pub use self::padder::Padder;
 mod padder;
 
@@ -116,9 +124,39 @@ pub use self::owned_buf::OwnedBuf;
 #[cfg(feature = "alloc")]
 mod owned_buf;
 
+#[cfg(feature = "alloc")]
+pub use self::transaction::Transaction;
+#[cfg(feature = "alloc")]
+mod transaction;
+
+#[cfg(feature = "alloc")]
+pub use self::padding_report::PaddingReport;
+#[cfg(feature = "alloc")]
+mod padding_report;
+
+#[cfg(feature = "alloc")]
+pub use self::aligned_scope::{AlignedScope, Placement, Slot};
+#[cfg(feature = "alloc")]
+mod aligned_scope;
+
+#[cfg(feature = "alloc")]
+pub use self::arena::Arena;
+#[cfg(feature = "alloc")]
+mod arena;
+
 pub use self::slice_mut::SliceMut;
 mod slice_mut;
 
+#[cfg(feature = "alloc")]
+pub use self::dump::Dump;
+#[cfg(feature = "alloc")]
+mod dump;
+
+#[cfg(feature = "alloc")]
+pub use self::diff::{diff, diff_with_limit, Difference, DifferenceKind};
+#[cfg(feature = "alloc")]
+mod diff;
+
 use core::mem::size_of;
 use core::ptr::NonNull;
 
@@ -11,7 +11,10 @@ use std::io;
 
 use alloc::alloc;
 
-use crate::buf::{self, Buf, DefaultAlignment, Padder, StoreBuf};
+use crate::buf::{
+    self, AlignedScope, Arena, Buf, DefaultAlignment, Padder, PaddingReport, Placement, StoreBuf,
+    Transaction,
+};
 use crate::endian::{ByteOrder, Native};
 use crate::error::Error;
 use crate::mem::MaybeUninit;
@@ -50,6 +53,10 @@ where
     requested: usize,
     /// The current alignment.
     align: usize,
+    /// Bytes of padding inserted to align stored values.
+    padding: usize,
+    /// The number of times padding has been inserted.
+    alignments: usize,
     /// Holding onto the current pointer size.
     _marker: PhantomData<(E, O)>,
 }
@@ -136,6 +143,8 @@ impl OwnedBuf {
             capacity: 0,
             requested: align,
             align,
+            padding: 0,
+            alignments: 0,
             _marker: PhantomData,
         }
     }
@@ -174,6 +183,74 @@ impl OwnedBuf {
         // SAFETY: Alignment of `T` is always a power of two.
         unsafe { Self::with_capacity_and_custom_alignment(capacity, align_of::<T>()) }
     }
+
+    /// Allocate a new buffer with exactly enough capacity to store a slice of
+    /// `count` elements of `T` through [`store_slice`], followed by the
+    /// [`Ref`] pointing to it through [`store`].
+    ///
+    /// The buffer is aligned for both `T` and the default alignment, so that
+    /// neither of these operations need to reallocate.
+    ///
+    /// [`store_slice`]: Self::store_slice
+    /// [`store`]: Self::store
+    ///
+    /// # Panics
+    ///
+    /// Panics if the needed capacity overflows `isize::MAX`.
+    ///
+    /// ```should_panic
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// OwnedBuf::with_capacity_for::<u64>(usize::MAX / 4);
+    /// ```
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let values = [1u64, 2, 3, 4];
+    ///
+    /// let mut buf = OwnedBuf::with_capacity_for::<u64>(values.len());
+    /// let capacity = buf.capacity();
+    ///
+    /// let slice = buf.store_slice(&values);
+    /// let root = buf.store(&slice);
+    ///
+    /// assert_eq!(buf.capacity(), capacity);
+    /// assert_eq!(buf.len(), capacity);
+    ///
+    /// let slice = buf.load(root)?;
+    /// assert_eq!(buf.load(*slice)?, &values[..]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn with_capacity_for<T>(count: usize) -> Self
+    where
+        T: ZeroCopy,
+    {
+        let Some(capacity) = capacity_for::<T, Ref<[T]>>(count) else {
+            panic!("Capacity for {count} elements overflows");
+        };
+
+        let align = align_of::<T>().max(align_of::<DefaultAlignment>());
+
+        // SAFETY: Alignments are always a power of two.
+        unsafe { Self::with_capacity_and_custom_alignment(capacity, align) }
+    }
+}
+
+/// Calculate the number of bytes needed to store `count` elements of `T`
+/// followed by a reference `R` to them, including padding.
+fn capacity_for<T, R>(count: usize) -> Option<usize> {
+    let slice = size_of::<T>().checked_mul(count)?;
+    let padded = slice.checked_next_multiple_of(align_of::<R>())?;
+    let capacity = padded.checked_add(size_of::<R>())?;
+
+    if capacity > isize::MAX as usize {
+        return None;
+    }
+
+    Some(capacity)
 }
 
 impl<E, O> OwnedBuf<E, O>
@@ -202,6 +279,8 @@ where
             capacity: this.capacity,
             requested: this.requested,
             align: this.align,
+            padding: this.padding,
+            alignments: this.alignments,
             _marker: PhantomData,
         }
     }
@@ -230,6 +309,8 @@ where
             capacity: this.capacity,
             requested: this.requested,
             align: this.align,
+            padding: this.padding,
+            alignments: this.alignments,
             _marker: PhantomData,
         }
     }
@@ -247,6 +328,8 @@ where
                 capacity: 0,
                 requested: align,
                 align,
+                padding: 0,
+                alignments: 0,
                 _marker: PhantomData,
             };
         }
@@ -266,6 +349,8 @@ where
                 capacity,
                 requested: align,
                 align,
+                padding: 0,
+                alignments: 0,
                 _marker: PhantomData,
             }
         }
@@ -307,6 +392,8 @@ where
     #[inline]
     pub fn clear(&mut self) {
         self.len = 0;
+        self.padding = 0;
+        self.alignments = 0;
     }
 
     /// Test if the buffer is empty.
@@ -356,6 +443,85 @@ where
         self.requested
     }
 
+    /// Get the number of bytes of padding which have been inserted between
+    /// stored values to align them.
+    ///
+    /// This doesn't include padding inside of stored values, and is reset
+    /// when the buffer is [cleared].
+    ///
+    /// [cleared]: Self::clear
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&1u8);
+    /// buf.store(&2u32);
+    /// buf.store(&3u8);
+    /// buf.store(&4u32);
+    ///
+    /// assert_eq!(buf.padding(), 6);
+    /// assert_eq!(buf.alignments(), 2);
+    /// ```
+    #[inline]
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+
+    /// Get the number of times padding has been inserted to align a stored
+    /// value.
+    ///
+    /// See [`padding()`] for the number of bytes this amounts to.
+    ///
+    /// [`padding()`]: Self::padding
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&1u32);
+    /// buf.store(&2u32);
+    /// assert_eq!(buf.alignments(), 0);
+    ///
+    /// buf.store(&3u8);
+    /// buf.store(&4u64);
+    /// assert_eq!(buf.alignments(), 1);
+    /// ```
+    #[inline]
+    pub fn alignments(&self) -> usize {
+        self.alignments
+    }
+
+    /// Get a report of the padding which has been inserted to align stored
+    /// values.
+    ///
+    /// The report is rendered through its [`Display`] implementation.
+    ///
+    /// [`Display`]: core::fmt::Display
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&1u8);
+    /// buf.store(&2u32);
+    ///
+    /// let report = buf.report();
+    /// assert_eq!(report.len(), 8);
+    /// assert_eq!(report.padding(), 3);
+    /// assert_eq!(report.to_string(), "8 bytes, 3 bytes of padding (37.5%) from 1 alignments");
+    /// ```
+    #[inline]
+    pub fn report(&self) -> PaddingReport {
+        PaddingReport::new(self.len, self.padding, self.alignments)
+    }
+
     /// Reserve capacity for at least `capacity` more bytes in this buffer.
     ///
     /// # Examples
@@ -375,6 +541,157 @@ where
         self.ensure_capacity(new_capacity);
     }
 
+    /// Perform a sequence of writes to the buffer which are rolled back unless
+    /// the closure `f` succeeds.
+    ///
+    /// The closure is provided a [`Transaction`] which dereferences to this
+    /// buffer. If it returns an error or panics, the buffer is truncated back to
+    /// the length it had before and its requested alignment is restored.
+    ///
+    /// Any [`Ref`] created inside of a transaction which is rolled back is
+    /// invalidated. See [`Transaction`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let first = buf.store_unsized("first");
+    /// let len = buf.len();
+    ///
+    /// let result = buf.transaction(|tx| {
+    ///     let mut strings = Vec::new();
+    ///
+    ///     for string in ["second", "a string which is too long", "third"] {
+    ///         if string.len() > 10 {
+    ///             return Err("string too long");
+    ///         }
+    ///
+    ///         strings.push(tx.store_unsized(string));
+    ///     }
+    ///
+    ///     Ok(tx.store_slice(&strings))
+    /// });
+    ///
+    /// assert!(result.is_err());
+    /// assert_eq!(buf.len(), len);
+    /// assert_eq!(buf.load(first)?, "first");
+    ///
+    /// let strings: Ref<[Ref<str>]> = buf.transaction(|tx| {
+    ///     let second = tx.store_unsized("second");
+    ///     Ok::<_, musli_zerocopy::Error>(tx.store_slice(&[second]))
+    /// })?;
+    ///
+    /// assert_eq!(buf.load(buf.load(strings)?[0])?, "second");
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn transaction<F, T, R>(&mut self, f: F) -> Result<T, R>
+    where
+        F: FnOnce(&mut Transaction<'_, E, O>) -> Result<T, R>,
+    {
+        let mut tx = Transaction::new(self);
+        let output = f(&mut tx)?;
+        tx.commit();
+        Ok(output)
+    }
+
+    /// Store a group of values ordered by descending alignment, which
+    /// minimizes the padding needed between them.
+    ///
+    /// Values stored through the [`AlignedScope`] are collected and written to
+    /// the buffer once the closure returns. Each store produces a [`Slot`]
+    /// which is resolved into a [`Ref`] through the returned [`Placement`].
+    ///
+    /// Values of the same alignment are written in the order they were
+    /// stored.
+    ///
+    /// [`Slot`]: crate::buf::Slot
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let ((a, b, c, d), placement) = buf.aligned_scope(|scope| {
+    ///     (
+    ///         scope.store(&1u8),
+    ///         scope.store(&2u64),
+    ///         scope.store(&3u16),
+    ///         scope.store(&4u32),
+    ///     )
+    /// });
+    ///
+    /// assert_eq!(buf.len(), 15);
+    /// assert_eq!(buf.padding(), 0);
+    ///
+    /// assert_eq!(buf.load(placement.get(a))?, &1);
+    /// assert_eq!(buf.load(placement.get(b))?, &2);
+    /// assert_eq!(buf.load(placement.get(c))?, &3);
+    /// assert_eq!(buf.load(placement.get(d))?, &4);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn aligned_scope<F, T>(&mut self, f: F) -> (T, Placement<E, O>)
+    where
+        F: FnOnce(&mut AlignedScope<E, O>) -> T,
+    {
+        let mut scope = AlignedScope::new();
+        let output = f(&mut scope);
+        let placement = scope.flush(self);
+        (output, placement)
+    }
+
+    /// Reserve an [`Arena`] with room for `capacity` nodes of type `T`.
+    ///
+    /// The slots of the arena are stored immediately and zero-initialized, so
+    /// anything stored after this call is placed after the arena.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `T` is a zero-sized type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let mut arena = buf.store_arena::<u32>(4);
+    ///
+    /// let a = arena.push(&mut buf, &1).unwrap();
+    /// let b = arena.push(&mut buf, &2).unwrap();
+    ///
+    /// let (slice, nodes) = arena.finish();
+    /// assert_eq!(nodes, [a, b]);
+    /// assert_eq!(buf.load(slice)?, &[1, 2]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn store_arena<T>(&mut self, capacity: usize) -> Arena<T, E, O>
+    where
+        T: ZeroCopy,
+    {
+        Arena::new(self.store_slice_uninit(capacity))
+    }
+
+    /// Roll back the buffer to the given length, requested alignment and
+    /// padding statistics.
+    #[inline]
+    pub(crate) fn rollback(
+        &mut self,
+        len: usize,
+        requested: usize,
+        padding: usize,
+        alignments: usize,
+    ) {
+        self.len = self.len.min(len);
+        self.requested = requested;
+        self.padding = padding;
+        self.alignments = alignments;
+    }
+
     /// Advance the length of the owned buffer by `size`.
     ///
     /// # Safety
@@ -573,6 +890,125 @@ where
         unsafe { &mut *(self.data.as_ptr().add(at) as *mut MaybeUninit<T>) }
     }
 
+    /// Store an uninitialized slice of `len` elements.
+    ///
+    /// This is the slice counterpart to [`store_uninit()`], which allows for
+    /// reserving space for a slice up front and then writing its elements one
+    /// by one through [`load_uninit_slice_mut()`]. This is useful when the
+    /// value of an element depends on data which is stored after the slice,
+    /// such as when elements reference each other.
+    ///
+    /// The memory for the slice will be zero-initialized at
+    /// [`next_offset<T>()`], and the slice is finalized with
+    /// [`Ref::assume_init`] once every element has been written.
+    ///
+    /// [`store_uninit()`]: Self::store_uninit
+    /// [`load_uninit_slice_mut()`]: Self::load_uninit_slice_mut
+    /// [`next_offset<T>()`]: Self::next_offset()
+    ///
+    /// # Panics
+    ///
+    /// Panics if the size of the slice overflows, or if its offset or length
+    /// can't be represented by `O`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(C)]
+    /// struct Node { value: u32, next: Ref<Node> }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let nodes = buf.store_slice_uninit::<Node>(3);
+    ///
+    /// // Each node references the one after it, and the last one itself.
+    /// let first = nodes.offset();
+    ///
+    /// for (index, node) in buf.load_uninit_slice_mut(nodes).iter_mut().enumerate() {
+    ///     let next = first + (index + 1).min(2) * std::mem::size_of::<Node>();
+    ///     node.write(&Node { value: index as u32, next: Ref::new(next) });
+    /// }
+    ///
+    /// let nodes = buf.load(nodes.assume_init())?;
+    /// let last = buf.load(nodes[0].next)?;
+    /// assert_eq!(last.value, 1);
+    /// assert_eq!(buf.load(last.next)?.value, 2);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn store_slice_uninit<T>(&mut self, len: usize) -> Ref<[MaybeUninit<T>], E, O>
+    where
+        T: ZeroCopy,
+    {
+        let Some(size) = size_of::<T>().checked_mul(len) else {
+            panic!("Size of slice with {len} elements overflows");
+        };
+
+        // SAFETY: We've just reserved capacity for this write.
+        unsafe {
+            self.next_offset_with_and_reserve(align_of::<T>(), size);
+            let offset = self.len;
+            self.data.as_ptr().add(self.len).write_bytes(0, size);
+            self.len += size;
+            Ref::with_metadata(offset, len)
+        }
+    }
+
+    /// Access a slice that might not have been initialized for writing.
+    ///
+    /// This does not prevent [`Ref`] from different instances of [`OwnedBuf`]
+    /// from being written. It would only result in garbled data, but wouldn't
+    /// be a safety concern.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slice referenced does not fit within the [`len()`] of the
+    /// current structure. This might happen if you try and use a reference
+    /// constructed from a different [`OwnedBuf`] instance.
+    ///
+    /// [`len()`]: Self::len()
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let slice = buf.store_slice_uninit::<u32>(4);
+    ///
+    /// for (n, value) in buf.load_uninit_slice_mut(slice).iter_mut().enumerate() {
+    ///     value.write(&(n as u32 * 10));
+    /// }
+    ///
+    /// assert_eq!(buf.load(slice.assume_init())?, &[0, 10, 20, 30]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn load_uninit_slice_mut<T, U, I>(
+        &mut self,
+        reference: Ref<[MaybeUninit<T>], U, I>,
+    ) -> &mut [MaybeUninit<T>]
+    where
+        T: ZeroCopy,
+        U: ByteOrder,
+        I: Size,
+    {
+        let at = reference.offset();
+        let len = reference.len();
+
+        let end = size_of::<T>()
+            .checked_mul(len)
+            .and_then(|size| at.checked_add(size));
+
+        assert!(end.is_some_and(|end| end <= self.len), "Length overflow");
+
+        // SAFETY: `MaybeUninit<T>` has no representation requirements and is
+        // unaligned, and the range has been checked to be in bounds.
+        unsafe { slice::from_raw_parts_mut(self.data.as_ptr().add(at).cast(), len) }
+    }
+
     /// Insert a value with the given size.
     ///
     /// The memory for `T` will be initialized at [`next_offset<T>()`] and the
@@ -709,7 +1145,16 @@ where
         Ref::new(offset)
     }
 
-    /// Write a value to the buffer.
+    /// Write an unsized value to the buffer.
+    ///
+    /// This supports both `str` and `[T]` where `T` implements [`ZeroCopy`],
+    /// and the returned reference carries the length of the value as its
+    /// metadata. Storing a slice through this method is identical to using
+    /// [`store_slice`], including how it is aligned and padded.
+    ///
+    /// [`store_slice`]: Self::store_slice
+    ///
+    /// # Examples
     ///
     /// ```
     /// use musli_zerocopy::OwnedBuf;
@@ -725,6 +1170,22 @@ where
     /// assert_eq!(buf.load(second)?, "second");
     /// # Ok::<_, musli_zerocopy::Error>(())
     /// ```
+    ///
+    /// Storing slices of non-byte types:
+    ///
+    /// ```
+    /// use musli_zerocopy::{OwnedBuf, Ref};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&1u8);
+    ///
+    /// let numbers: Ref<[u32]> = buf.store_unsized(&[1u32, 2, 3][..]);
+    /// assert_eq!(numbers.offset(), 4);
+    /// assert_eq!(numbers.len(), 3);
+    ///
+    /// assert_eq!(buf.load(numbers)?, &[1, 2, 3]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
     #[inline]
     pub fn store_unsized<T>(&mut self, value: &T) -> Ref<T, E, O>
     where
@@ -750,6 +1211,10 @@ where
 
     /// Insert a slice into the buffer.
     ///
+    /// This is a convenience alias for [`store_unsized`] when storing slices.
+    ///
+    /// [`store_unsized`]: Self::store_unsized
+    ///
     /// # Examples
     ///
     /// ```
@@ -995,6 +1460,11 @@ where
         let extra = buf::padding_to(self.len, align);
         self.reserve(extra + reserve);
 
+        if extra != 0 {
+            self.padding += extra;
+            self.alignments += 1;
+        }
+
         // SAFETY: The length is ensures to be within the address space.
         unsafe {
             self.data.as_ptr().add(self.len).write_bytes(0, extra);
@@ -1270,6 +1740,8 @@ where
             // Set requested to the same as original.
             new.requested = self.requested;
             new.len = self.len;
+            new.padding = self.padding;
+            new.alignments = self.alignments;
             ManuallyDrop::into_inner(new)
         }
     }
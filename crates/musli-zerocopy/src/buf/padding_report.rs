@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// A report over the padding inserted into a buffer, as returned by
+/// [`OwnedBuf::report`].
+///
+/// This is rendered through its [`Display`] implementation.
+///
+/// [`OwnedBuf::report`]: crate::buf::OwnedBuf::report
+/// [`Display`]: fmt::Display
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaddingReport {
+    len: usize,
+    padding: usize,
+    alignments: usize,
+}
+
+impl PaddingReport {
+    #[inline]
+    pub(crate) fn new(len: usize, padding: usize, alignments: usize) -> Self {
+        Self {
+            len,
+            padding,
+            alignments,
+        }
+    }
+
+    /// The length of the buffer in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Test if the buffer the report was produced for is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The number of bytes of padding inserted to align stored values.
+    #[inline]
+    pub fn padding(&self) -> usize {
+        self.padding
+    }
+
+    /// The number of times padding was inserted.
+    #[inline]
+    pub fn alignments(&self) -> usize {
+        self.alignments
+    }
+}
+
+impl fmt::Display for PaddingReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} bytes, {} bytes of padding", self.len, self.padding)?;
+
+        if self.len != 0 {
+            let percent = self.padding as f64 * 100.0 / self.len as f64;
+            write!(f, " ({percent:.1}%)")?;
+        }
+
+        write!(f, " from {} alignments", self.alignments)
+    }
+}
@@ -632,7 +632,13 @@ where
         self.to_aligned_with(self.requested)
     }
 
-    /// Write a value to the buffer.
+    /// Write an unsized value to the buffer.
+    ///
+    /// This supports both `str` and `[T]` where `T` implements [`ZeroCopy`].
+    /// Storing a slice through this method is identical to using
+    /// [`store_slice`].
+    ///
+    /// [`store_slice`]: Self::store_slice
     ///
     /// # Examples
     ///
@@ -644,11 +650,13 @@ where
     ///
     /// let first = buf.store_unsized("first");
     /// let second = buf.store_unsized("second");
+    /// let numbers = buf.store_unsized(&[1u32, 2, 3][..]);
     ///
     /// let buf = buf.to_requested();
     ///
     /// assert_eq!(buf.load(first)?, "first");
     /// assert_eq!(buf.load(second)?, "second");
+    /// assert_eq!(buf.load(numbers)?, &[1, 2, 3]);
     /// # Ok::<_, musli_zerocopy::Error>(())
     /// ```
     #[inline]
@@ -676,6 +684,10 @@ where
 
     /// Insert a slice into the buffer.
     ///
+    /// This is a convenience alias for [`store_unsized`] when storing slices.
+    ///
+    /// [`store_unsized`]: Self::store_unsized
+    ///
     /// # Examples
     ///
     /// ```
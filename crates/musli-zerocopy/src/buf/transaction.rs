@@ -0,0 +1,131 @@
+use core::ops::{Deref, DerefMut};
+
+use crate::buf::OwnedBuf;
+use crate::endian::{ByteOrder, Native};
+use crate::pointer::{DefaultSize, Size};
+
+/// A guard over an [`OwnedBuf`] which rolls back any writes made through it
+/// unless it is committed.
+///
+/// This is constructed through [`OwnedBuf::transaction`], see it for more
+/// information.
+///
+/// Rolling back truncates the buffer to the length it had when the
+/// transaction was started and restores its requested alignment and padding
+/// statistics. Data before
+/// that point which is modified in place, such as through [`load_mut`], is
+/// not restored.
+///
+/// [`load_mut`]: crate::buf::Buf::load_mut
+///
+/// # Invalidation
+///
+/// Any [`Ref`] created through a transaction which is rolled back points to
+/// data which is no longer part of the buffer. Loading it will either fail
+/// because it is out of bounds, or once the buffer has been written to again
+/// silently produce unrelated data. Such references must not be kept around,
+/// which is naturally the case if they are only ever returned from the
+/// transaction closure.
+///
+/// [`Ref`]: crate::Ref
+pub struct Transaction<'a, E = Native, O = DefaultSize>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    buf: &'a mut OwnedBuf<E, O>,
+    len: usize,
+    requested: usize,
+    padding: usize,
+    alignments: usize,
+    committed: bool,
+}
+
+impl<'a, E, O> Transaction<'a, E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    pub(crate) fn new(buf: &'a mut OwnedBuf<E, O>) -> Self {
+        let len = buf.len();
+        let requested = buf.requested();
+        let padding = buf.padding();
+        let alignments = buf.alignments();
+
+        Self {
+            buf,
+            len,
+            requested,
+            padding,
+            alignments,
+            committed: false,
+        }
+    }
+
+    /// Get the length of the buffer at the point the transaction was started.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// buf.store(&1u32);
+    ///
+    /// buf.transaction(|tx| {
+    ///     tx.store(&2u32);
+    ///     assert_eq!(tx.checkpoint(), 4);
+    ///     assert_eq!(tx.len(), 8);
+    ///     Ok::<_, ()>(())
+    /// })?;
+    /// # Ok::<_, ()>(())
+    /// ```
+    #[inline]
+    pub fn checkpoint(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub(crate) fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl<E, O> Deref for Transaction<'_, E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    type Target = OwnedBuf<E, O>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.buf
+    }
+}
+
+impl<E, O> DerefMut for Transaction<'_, E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.buf
+    }
+}
+
+impl<E, O> Drop for Transaction<'_, E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn drop(&mut self) {
+        if !self.committed {
+            self.buf
+                .rollback(self.len, self.requested, self.padding, self.alignments);
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::buf::{Buf, Load};
+
+/// A buffer whose tree of values reachable from a root has been validated
+/// through [`Buf::trust`].
+///
+/// Since the buffer is immutably borrowed for the lifetime of the
+/// `TrustedBuf`, the data it has validated can't change. This permits loading
+/// values reachable from the root without performing any per-load validation
+/// through [`TrustedBuf::load`].
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
+///
+/// #[derive(ZeroCopy, ValidateTree)]
+/// #[repr(C)]
+/// struct Person {
+///     name: Ref<str>,
+///     age: u32,
+/// }
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let name = buf.store_unsized("Aristotle");
+/// let person = buf.store(&Person { name, age: 61 });
+///
+/// let trusted = buf.trust(person)?;
+/// let person = trusted.root();
+///
+/// // SAFETY: `person.name` is reachable from the validated root.
+/// let name = unsafe { trusted.load(person.name) };
+/// assert_eq!(name, "Aristotle");
+/// assert_eq!(person.age, 61);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub struct TrustedBuf<'buf, T>
+where
+    T: ?Sized,
+{
+    buf: &'buf Buf,
+    root: &'buf T,
+}
+
+impl<'buf, T> TrustedBuf<'buf, T>
+where
+    T: ?Sized,
+{
+    /// Construct a new trusted buffer.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `root` has been loaded from `buf` and that
+    /// it and every value reachable from it has been validated through
+    /// [`ValidateTree`].
+    ///
+    /// [`ValidateTree`]: crate::buf::ValidateTree
+    #[inline]
+    pub(crate) unsafe fn new(buf: &'buf Buf, root: &'buf T) -> Self {
+        Self { buf, root }
+    }
+
+    /// Access the validated root value.
+    #[inline]
+    pub fn root(&self) -> &'buf T {
+        self.root
+    }
+
+    /// Access the underlying buffer.
+    ///
+    /// Loads performed through the returned buffer are validated as usual.
+    #[inline]
+    pub fn buf(&self) -> &'buf Buf {
+        self.buf
+    }
+
+    /// Load the given pointer without validating it.
+    ///
+    /// # Safety
+    ///
+    /// The caller must ensure that `ptr` is reachable from the [root] of this
+    /// buffer, such as by reading it out of the root or out of a value loaded
+    /// from a pointer which is itself reachable from the root. Such pointers
+    /// have already been validated when this buffer was constructed.
+    ///
+    /// This guarantee rests on the [`ValidateTree`] implementations of the
+    /// root and of every value reachable from it, which are required to visit
+    /// every reference they hold.
+    ///
+    /// [root]: Self::root
+    /// [`ValidateTree`]: crate::ValidateTree
+    #[inline]
+    pub unsafe fn load<P>(&self, ptr: P) -> &'buf P::Target
+    where
+        P: Load,
+    {
+        ptr.load_unchecked(self.buf)
+    }
+}
+
+impl<T> Clone for TrustedBuf<'_, T>
+where
+    T: ?Sized,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for TrustedBuf<'_, T> where T: ?Sized {}
@@ -0,0 +1,244 @@
+#[cfg(feature = "alloc")]
+use core::mem::size_of_val;
+
+#[cfg(feature = "alloc")]
+use alloc::collections::BTreeSet;
+
+use crate::buf::{Buf, Load};
+use crate::endian::ByteOrder;
+use crate::error::{Error, ErrorKind};
+use crate::pointer::{Pointee, Ref, Size};
+
+/// The maximum number of references which will be followed in a chain before
+/// tree validation gives up.
+///
+/// This protects against cyclic or maliciously deep structures in untrusted
+/// buffers.
+const MAX_DEPTH: usize = 1024;
+
+/// The default maximum number of distinct values which will be visited by
+/// [`Buf::validate_graph`].
+#[cfg(feature = "alloc")]
+pub(crate) const DEFAULT_VISIT_LIMIT: usize = 1 << 20;
+
+/// Trait used to eagerly validate a value and everything which is reachable
+/// from it through [`Buf::validate_tree`].
+///
+/// Implementations should call [`ValidateTree::validate_tree`] on every field
+/// of the value, which for references such as [`Ref`] loads and validates the
+/// value being pointed to.
+///
+/// This can be derived through the [`ValidateTree`][derive@crate::ValidateTree]
+/// derive.
+///
+/// # Safety
+///
+/// [`TrustedBuf::load`] loads pointers without validating them, relying on
+/// every pointer reachable from a validated root having been validated by this
+/// trait. Implementations must therefore visit every reference which can be
+/// reached from the value, either by following it through
+/// [`TreeValidator::follow`] or by calling [`ValidateTree::validate_tree`] on
+/// the field holding it. A reference may only be loaded with
+/// [`TreeValidator::load`] if the value it points to cannot contain any further
+/// references.
+///
+/// [`TrustedBuf::load`]: crate::buf::TrustedBuf::load
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
+///
+/// #[derive(ZeroCopy, ValidateTree)]
+/// #[repr(C)]
+/// struct Person {
+///     name: Ref<str>,
+///     age: u32,
+/// }
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let name = buf.store_unsized("Aristotle");
+/// let person = buf.store(&Person { name, age: 61 });
+///
+/// buf.validate_tree(person)?;
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub unsafe trait ValidateTree {
+    /// Validate the value and every reference which is reachable from it.
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error>;
+}
+
+/// The context used when performing tree validation through
+/// [`Buf::validate_tree`] or [`Buf::validate_graph`].
+pub struct TreeValidator<'buf> {
+    buf: &'buf Buf,
+    depth: usize,
+    #[cfg(feature = "alloc")]
+    visited: Option<Visited>,
+}
+
+impl<'buf> TreeValidator<'buf> {
+    #[inline]
+    pub(crate) fn new(buf: &'buf Buf) -> Self {
+        Self {
+            buf,
+            depth: 0,
+            #[cfg(feature = "alloc")]
+            visited: None,
+        }
+    }
+
+    /// Construct a validator which validates every distinct value only once,
+    /// visiting at most `limit` distinct values.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub(crate) fn with_visit_limit(buf: &'buf Buf, limit: usize) -> Self {
+        Self {
+            buf,
+            depth: 0,
+            visited: Some(Visited {
+                set: BTreeSet::new(),
+                limit,
+            }),
+        }
+    }
+
+    /// Access the buffer being validated.
+    #[inline]
+    pub fn buf(&self) -> &'buf Buf {
+        self.buf
+    }
+
+    /// Load the given pointer without validating what it points to.
+    ///
+    /// This is appropriate for data which cannot contain any further
+    /// references, such as the internal bookkeeping of a container.
+    #[inline]
+    pub fn load<P>(&self, ptr: P) -> Result<&'buf P::Target, Error>
+    where
+        P: Load,
+    {
+        ptr.load(self.buf)
+    }
+
+    /// Load the given pointer and validate the tree of the value it points to.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the value can't be loaded, if any value reachable from it
+    /// fails to validate, or if too many references have been followed in a
+    /// single chain.
+    #[inline]
+    pub fn follow<P>(&mut self, ptr: P) -> Result<(), Error>
+    where
+        P: Load,
+        P::Target: ValidateTree,
+    {
+        let buf: &Buf = self.buf;
+        self.enter(ptr.load(buf)?)
+    }
+
+    /// Validate the tree of a value which has been loaded from the buffer.
+    #[inline]
+    pub(crate) fn enter<T>(&mut self, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + ValidateTree,
+    {
+        #[cfg(feature = "alloc")]
+        if let Some(visited) = &mut self.visited {
+            if !visited.insert(value)? {
+                return Ok(());
+            }
+        }
+
+        if self.depth == MAX_DEPTH {
+            return Err(Error::new(ErrorKind::DepthLimitExceeded {
+                limit: MAX_DEPTH,
+            }));
+        }
+
+        self.depth += 1;
+        let result = value.validate_tree(self);
+        self.depth -= 1;
+        result
+    }
+}
+
+/// The set of values which have been visited during graph validation.
+#[cfg(feature = "alloc")]
+struct Visited {
+    set: BTreeSet<(usize, usize, usize)>,
+    limit: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl Visited {
+    /// Insert the given value, returning `false` if it has already been
+    /// visited.
+    ///
+    /// A value is identified by its address and size, and by the function
+    /// used to validate it. Two types can only share a validation function if
+    /// it does exactly the same thing, in which case validating a value as
+    /// either of them is equivalent.
+    fn insert<T>(&mut self, value: &T) -> Result<bool, Error>
+    where
+        T: ?Sized + ValidateTree,
+    {
+        let validate: fn(&T, &mut TreeValidator<'_>) -> Result<(), Error> = T::validate_tree;
+
+        let key = (
+            value as *const T as *const u8 as usize,
+            size_of_val(value),
+            validate as usize,
+        );
+
+        if self.set.contains(&key) {
+            return Ok(false);
+        }
+
+        if self.set.len() == self.limit {
+            return Err(Error::new(ErrorKind::VisitLimitExceeded {
+                limit: self.limit,
+            }));
+        }
+
+        self.set.insert(key);
+        Ok(true)
+    }
+}
+
+unsafe impl<T, E, O> ValidateTree for Ref<T, E, O>
+where
+    T: ?Sized + Pointee,
+    Self: Load,
+    <Self as Load>::Target: ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        cx.follow(*self)
+    }
+}
+
+unsafe impl ValidateTree for str {
+    #[inline]
+    fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+unsafe impl<T> ValidateTree for [T]
+where
+    T: ValidateTree,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        for value in self {
+            value.validate_tree(cx)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,178 @@
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+
+use crate::error::{ErrorKind, IntoRepr};
+use crate::{ByteOrder, Error, OwnedBuf, Size};
+
+use super::{Compact, CompactSliceRef};
+
+/// Store a compact slice with the given elements.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+///
+/// #[derive(Debug, PartialEq, ZeroCopy)]
+/// #[repr(u8)]
+/// #[zero_copy(compact)]
+/// enum Value {
+///     A,
+///     B([u64; 8]),
+/// }
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let values = compact::store(&mut buf, [Value::A, Value::A, Value::B([1; 8])])?;
+/// assert_eq!(values.len(), 3);
+/// assert_eq!(values.data_len(), 1 + 1 + 72);
+/// assert_eq!(values.get(&buf, 2)?, Some(Value::B([1; 8])));
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub fn store<T, E, O, I>(buf: &mut OwnedBuf<E, O>, it: I) -> Result<CompactSliceRef<T, E, O>, Error>
+where
+    T: Compact,
+    I: IntoIterator<Item = T>,
+    E: ByteOrder,
+    O: Size,
+{
+    let mut builder = Builder::new();
+
+    for value in it {
+        builder.push(&value);
+    }
+
+    builder.build(buf)
+}
+
+/// An in-memory compact slice as it's being constructed.
+///
+/// This can be used over [`store()`] to provide more control.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+///
+/// #[derive(Debug, PartialEq, ZeroCopy)]
+/// #[repr(u8)]
+/// #[zero_copy(compact)]
+/// enum Value {
+///     A,
+///     B(u32),
+/// }
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let mut values = compact::Builder::new();
+/// values.push(&Value::B(1));
+/// values.push(&Value::A);
+///
+/// let values = values.build(&mut buf)?;
+/// assert_eq!(values.get(&buf, 0)?, Some(Value::B(1)));
+/// assert_eq!(values.get(&buf, 1)?, Some(Value::A));
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub struct Builder<T> {
+    offsets: Vec<usize>,
+    data: Vec<u8>,
+    scratch: OwnedBuf,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Builder<T>
+where
+    T: Compact,
+{
+    /// Construct a new empty compact slice builder.
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            offsets: Vec::new(),
+            data: Vec::new(),
+            scratch: OwnedBuf::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Get the number of elements in the slice.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Test if the slice is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Push an element to the end of the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut values = compact::Builder::new();
+    /// values.push(&Value::A);
+    /// assert_eq!(values.len(), 1);
+    /// ```
+    pub fn push(&mut self, value: &T) {
+        // Storing the value in a buffer ensures that any padding is
+        // initialized before its bytes are copied.
+        self.scratch.clear();
+        self.scratch.store(value);
+
+        let size = value.compact_size();
+        self.offsets.push(self.data.len());
+        self.data
+            .extend_from_slice(&self.scratch.as_slice()[..size]);
+    }
+
+    /// Store the compact slice in the given buffer.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the offset of an element can't be represented by `O`.
+    pub fn build<E, O>(self, buf: &mut OwnedBuf<E, O>) -> Result<CompactSliceRef<T, E, O>, Error>
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        let mut offsets = Vec::with_capacity(self.offsets.len());
+
+        for offset in self.offsets {
+            let Some(value) = O::try_from_usize(offset) else {
+                return Err(Error::new(ErrorKind::InvalidMetadataRange {
+                    metadata: offset.into_repr(),
+                    max: O::MAX.into_repr(),
+                }));
+            };
+
+            offsets.push(O::swap_bytes::<E>(value));
+        }
+
+        Ok(CompactSliceRef {
+            offsets: buf.store_slice(&offsets),
+            data: buf.store_slice(&self.data),
+            _marker: PhantomData,
+        })
+    }
+}
+
+impl<T> Default for Builder<T>
+where
+    T: Compact,
+{
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,382 @@
+//! Compact storage for slices of enums.
+//!
+//! A regular slice of an enum stores every element with the size of its
+//! largest variant. If most elements are small variants this wastes a lot of
+//! space. A [`CompactSliceRef`] instead stores each element with only the
+//! bytes needed by its variant, next to an array of offsets used to find each
+//! element.
+//!
+//! Since elements are no longer stored with the size or alignment of the enum,
+//! they are copied out of the buffer and validated when they're accessed.
+//!
+//! Types stored this way must implement [`Compact`], which can be derived with
+//! `#[zero_copy(compact)]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+//!
+//! #[derive(Debug, PartialEq, ZeroCopy)]
+//! #[repr(u8)]
+//! #[zero_copy(compact)]
+//! enum Event {
+//!     Tick,
+//!     Key(u32),
+//!     Frame([u8; 256]),
+//! }
+//!
+//! let mut buf = OwnedBuf::new();
+//!
+//! let events = compact::store(&mut buf, [Event::Tick, Event::Key(42), Event::Tick])?;
+//!
+//! assert_eq!(events.len(), 3);
+//! assert_eq!(events.get(&buf, 1)?, Some(Event::Key(42)));
+//! assert_eq!(events.get(&buf, 3)?, None);
+//!
+//! // Much smaller than three elements of 257 bytes each.
+//! assert!(buf.len() < 64);
+//! # Ok::<_, musli_zerocopy::Error>(())
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "alloc")]
+pub use self::builder::{store, Builder};
+#[cfg(feature = "alloc")]
+mod builder;
+
+use core::marker::PhantomData;
+use core::mem::{size_of, MaybeUninit};
+use core::slice;
+
+use crate::buf::{TreeValidator, ValidateTree};
+use crate::endian::Native;
+use crate::error::ErrorKind;
+use crate::{Buf, ByteOrder, DefaultSize, Error, Ref, Size, ZeroCopy};
+
+/// A type which can be stored in a [`CompactSliceRef`].
+///
+/// This can be derived for enums with `#[zero_copy(compact)]`.
+///
+/// # Safety
+///
+/// The value returned by [`compact_size()`] must cover every byte of `self`
+/// which is read when it's validated, and must not exceed the size of `Self`.
+///
+/// [`compact_size()`]: Self::compact_size
+pub unsafe trait Compact: ZeroCopy {
+    /// The number of leading bytes of `self` which need to be stored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::ZeroCopy;
+    /// use musli_zerocopy::compact::Compact;
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(u16)]
+    /// #[zero_copy(compact)]
+    /// enum Value {
+    ///     Empty,
+    ///     Number(u64),
+    /// }
+    ///
+    /// assert_eq!(Value::Empty.compact_size(), 2);
+    /// assert_eq!(Value::Number(1).compact_size(), 16);
+    /// ```
+    fn compact_size(&self) -> usize;
+}
+
+/// A stored reference to a compact slice.
+///
+/// See the [module level documentation][self] for more information.
+#[derive(ZeroCopy)]
+#[zero_copy(crate)]
+#[repr(C)]
+pub struct CompactSliceRef<T, E = Native, O = DefaultSize>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    offsets: Ref<[O], E, O>,
+    data: Ref<[u8], E, O>,
+    _marker: PhantomData<T>,
+}
+
+impl<T, E, O> CompactSliceRef<T, E, O>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    /// Get the number of elements in the slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let values = compact::store(&mut buf, [Value::A, Value::B(1)])?;
+    /// assert_eq!(values.len(), 2);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    /// Test if the slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let values = compact::store::<Value, _, _, _>(&mut buf, [])?;
+    /// assert!(values.is_empty());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Get the number of bytes used to store the elements of the slice, not
+    /// including their offsets.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let values = compact::store(&mut buf, [Value::A, Value::B(1), Value::A])?;
+    /// assert_eq!(values.data_len(), 1 + 8 + 1);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Get the element at `index`, or `None` if it's out of bounds.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the slice is not stored in `buf`, or if the element is not
+    /// valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+    ///
+    /// #[derive(Debug, PartialEq, ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let values = compact::store(&mut buf, [Value::A, Value::B(1)])?;
+    /// assert_eq!(values.get(&buf, 0)?, Some(Value::A));
+    /// assert_eq!(values.get(&buf, 1)?, Some(Value::B(1)));
+    /// assert_eq!(values.get(&buf, 2)?, None);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn get(&self, buf: &Buf, index: usize) -> Result<Option<T>, Error> {
+        let offsets = buf.load(self.offsets)?;
+        let data = buf.load(self.data)?;
+
+        if index >= offsets.len() {
+            return Ok(None);
+        }
+
+        Ok(Some(element::<T, E, O>(offsets, data, index)?))
+    }
+
+    /// Iterate over all elements in the slice.
+    ///
+    /// # Errors
+    ///
+    /// Errors if the slice is not stored in `buf`. Each element is validated
+    /// as it's being iterated over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{compact, OwnedBuf, ZeroCopy};
+    ///
+    /// #[derive(Debug, PartialEq, ZeroCopy)]
+    /// #[repr(u8)]
+    /// #[zero_copy(compact)]
+    /// enum Value { A, B(u32) }
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let values = compact::store(&mut buf, [Value::B(1), Value::A])?;
+    /// let values = values.iter(&buf)?.collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(values, [Value::B(1), Value::A]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub fn iter<'buf>(&self, buf: &'buf Buf) -> Result<Iter<'buf, T, E, O>, Error> {
+        let offsets = buf.load(self.offsets)?;
+        let data = buf.load(self.data)?;
+
+        Ok(Iter {
+            offsets,
+            data,
+            index: 0,
+            _marker: PhantomData,
+        })
+    }
+}
+
+unsafe impl<T, E, O> ValidateTree for CompactSliceRef<T, E, O>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    /// Validate that every element of the slice is in bounds, that elements
+    /// are stored in order, and that each element is valid.
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        let offsets = cx.load(self.offsets)?;
+        let data = cx.load(self.data)?;
+
+        for index in 0..offsets.len() {
+            element::<T, E, O>(offsets, data, index)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, E, O> Clone for CompactSliceRef<T, E, O>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, O> Copy for CompactSliceRef<T, E, O>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+}
+
+/// Iterator over the elements of a compact slice, as returned by
+/// [`CompactSliceRef::iter`].
+pub struct Iter<'buf, T, E = Native, O = DefaultSize> {
+    offsets: &'buf [O],
+    data: &'buf [u8],
+    index: usize,
+    _marker: PhantomData<(T, E)>,
+}
+
+impl<T, E, O> Iterator for Iter<'_, T, E, O>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    type Item = Result<T, Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.offsets.len() {
+            return None;
+        }
+
+        let value = element::<T, E, O>(self.offsets, self.data, self.index);
+        self.index += 1;
+        Some(value)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.offsets.len() - self.index;
+        (len, Some(len))
+    }
+}
+
+/// Copy out and validate the element at `index`, which must be in bounds of
+/// `offsets`.
+fn element<T, E, O>(offsets: &[O], data: &[u8], index: usize) -> Result<T, Error>
+where
+    T: Compact,
+    E: ByteOrder,
+    O: Size,
+{
+    let start = offsets[index].as_usize::<E>();
+
+    let end = match offsets.get(index + 1) {
+        Some(end) => end.as_usize::<E>(),
+        None => data.len(),
+    };
+
+    let Some(bytes) = data.get(start..end) else {
+        return Err(Error::new(ErrorKind::OutOfRangeBounds {
+            range: start..end,
+            len: data.len(),
+        }));
+    };
+
+    if bytes.len() > size_of::<T>() {
+        return Err(Error::new(ErrorKind::CompactSizeMismatch {
+            len: bytes.len(),
+            expected: size_of::<T>(),
+        }));
+    }
+
+    let mut value = MaybeUninit::<T>::zeroed();
+
+    // SAFETY: The storage is zero-initialized and exactly the size of `T`.
+    let storage =
+        unsafe { slice::from_raw_parts_mut(value.as_mut_ptr().cast::<u8>(), size_of::<T>()) };
+
+    storage[..bytes.len()].copy_from_slice(bytes);
+
+    let value = Buf::new(storage).load_at_unaligned::<T>(0)?;
+    let expected = value.compact_size();
+
+    if bytes.len() != expected {
+        return Err(Error::new(ErrorKind::CompactSizeMismatch {
+            len: bytes.len(),
+            expected,
+        }));
+    }
+
+    Ok(value)
+}
@@ -0,0 +1,139 @@
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use crate::endian::Big;
+use crate::{Error, OwnedBuf, ZeroCopy};
+
+use super::{store, Builder, Compact};
+
+// Large variants are what compact storage is for.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, Copy, PartialEq, ZeroCopy)]
+#[repr(u8)]
+#[zero_copy(crate, compact)]
+enum Value {
+    Empty,
+    Small(u8),
+    Pair { a: u16, b: u32 },
+    Large([u64; 32]),
+}
+
+#[test]
+fn compact_sizes() {
+    assert_eq!(Value::Empty.compact_size(), 1);
+    assert_eq!(Value::Small(1).compact_size(), 2);
+    assert_eq!(Value::Pair { a: 1, b: 2 }.compact_size(), 8);
+    assert_eq!(Value::Large([0; 32]).compact_size(), 8 + 256);
+    assert_eq!(core::mem::size_of::<Value>(), 8 + 256);
+}
+
+#[test]
+fn compact_roundtrip() -> Result<(), Error> {
+    let values = [
+        Value::Small(1),
+        Value::Empty,
+        Value::Large([7; 32]),
+        Value::Pair { a: 2, b: 3 },
+        Value::Empty,
+    ];
+
+    let mut buf = OwnedBuf::new().with_byte_order::<Big>().with_size::<u32>();
+    let slice = store(&mut buf, values)?;
+
+    assert_eq!(slice.len(), values.len());
+    assert_eq!(slice.data_len(), 2 + 1 + 264 + 8 + 1);
+
+    for (index, value) in values.iter().enumerate() {
+        assert_eq!(slice.get(&buf, index)?.as_ref(), Some(value));
+    }
+
+    assert_eq!(slice.get(&buf, values.len())?, None);
+
+    let collected = slice.iter(&buf)?.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(collected, values);
+
+    let slice = buf.store(&slice);
+    buf.validate_tree(slice)?;
+    Ok(())
+}
+
+#[test]
+fn compact_mostly_small() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let values = (0..1000u32).map(|n| {
+        if n % 10 == 0 {
+            Value::Large([n as u64; 32])
+        } else {
+            Value::Small(n as u8)
+        }
+    });
+
+    let slice = store(&mut buf, values)?;
+    let compact = buf.len();
+
+    let mut plain = OwnedBuf::new();
+    let values = (0..1000u32)
+        .map(|n| Value::Small(n as u8))
+        .collect::<Vec<_>>();
+    plain.store_slice(&values);
+
+    // 900 elements of 2 bytes and 100 elements of 264 bytes, plus offsets.
+    assert_eq!(slice.data_len(), 900 * 2 + 100 * 264);
+    assert!(compact * 5 < plain.len(), "{compact} vs {}", plain.len());
+
+    for n in 0..1000u32 {
+        let expected = if n % 10 == 0 {
+            Value::Large([n as u64; 32])
+        } else {
+            Value::Small(n as u8)
+        };
+
+        assert_eq!(slice.get(&buf, n as usize)?, Some(expected));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn compact_builder_empty() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let slice = Builder::<Value>::new().build(&mut buf)?;
+    assert!(slice.is_empty());
+    assert_eq!(slice.get(&buf, 0)?, None);
+    assert_eq!(slice.iter(&buf)?.count(), 0);
+
+    let slice = buf.store(&slice);
+    buf.validate_tree(slice)?;
+    Ok(())
+}
+
+#[test]
+fn compact_corrupt() -> Result<(), Error> {
+    let mut buf = OwnedBuf::new();
+
+    let slice = store(&mut buf, [Value::Empty, Value::Small(1)])?;
+    let data = slice.data.offset();
+
+    // Turn the first element into a variant which needs more bytes than are
+    // stored for it.
+    buf.as_mut_slice()[data] = 1;
+
+    let error = slice.get(&buf, 0).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Compact element stored with 1 bytes but expected 2 bytes"
+    );
+
+    // The second element is unaffected.
+    assert_eq!(slice.get(&buf, 1)?, Some(Value::Small(1)));
+
+    let slice = buf.store(&slice);
+    assert!(buf.validate_tree(slice).is_err());
+
+    // Illegal discriminants are caught when validating the element.
+    buf.as_mut_slice()[data] = 42;
+    assert!(buf.load(slice).and_then(|s| s.get(&buf, 0)).is_err());
+    Ok(())
+}
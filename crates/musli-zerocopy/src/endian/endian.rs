@@ -2,8 +2,9 @@ use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
 use core::{any, fmt};
 
+use crate::buf::{TreeValidator, ValidateTree};
 use crate::endian::{Big, ByteOrder, Little, Native};
-use crate::ZeroCopy;
+use crate::{Error, ZeroCopy};
 
 /// Wrapper capable of enforcing a custom [`ByteOrder`].
 ///
@@ -184,6 +185,17 @@ where
     }
 }
 
+unsafe impl<T, E> ValidateTree for Endian<T, E>
+where
+    T: Copy + ZeroCopy + ValidateTree,
+    E: ByteOrder,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        T::swap_bytes::<E>(self.value).validate_tree(cx)
+    }
+}
+
 impl<T, E> fmt::Debug for Endian<T, E>
 where
     T: ZeroCopy + fmt::Debug,
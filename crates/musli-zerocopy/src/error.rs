@@ -216,6 +216,24 @@ pub(crate) enum ErrorKind {
     StackOverflow {
         capacity: usize,
     },
+    DepthLimitExceeded {
+        limit: usize,
+    },
+    BitSetLengthMismatch {
+        bits: usize,
+        words: usize,
+    },
+    BitSetTrailingBits {
+        bits: usize,
+    },
+    CompactSizeMismatch {
+        len: usize,
+        expected: usize,
+    },
+    #[cfg(feature = "alloc")]
+    VisitLimitExceeded {
+        limit: usize,
+    },
     #[cfg(feature = "alloc")]
     CapacityError,
     #[cfg(feature = "alloc")]
@@ -291,6 +309,31 @@ impl fmt::Display for ErrorKind {
             ErrorKind::StackOverflow { capacity } => {
                 write!(f, "Stack with capacity {capacity} overflowed")
             }
+            ErrorKind::DepthLimitExceeded { limit } => {
+                write!(f, "Reference depth limit of {limit} exceeded")
+            }
+            ErrorKind::BitSetLengthMismatch { bits, words } => {
+                write!(
+                    f,
+                    "Bitset with a length of {bits} bits cannot be stored in {words} words"
+                )
+            }
+            ErrorKind::BitSetTrailingBits { bits } => {
+                write!(
+                    f,
+                    "Bitset with a length of {bits} bits has bits set beyond its length"
+                )
+            }
+            ErrorKind::CompactSizeMismatch { len, expected } => {
+                write!(
+                    f,
+                    "Compact element stored with {len} bytes but expected {expected} bytes"
+                )
+            }
+            #[cfg(feature = "alloc")]
+            ErrorKind::VisitLimitExceeded { limit } => {
+                write!(f, "Limit of {limit} visited values exceeded")
+            }
             ErrorKind::Utf8Error { error } => error.fmt(f),
             #[cfg(feature = "alloc")]
             ErrorKind::CapacityError => {
@@ -0,0 +1,480 @@
+//! Introspection of the layout of [`ZeroCopy`] types.
+//!
+//! Deriving [`ZeroCopy`] with `#[zero_copy(layout)]` emits an associated
+//! constant `LAYOUT` containing a [`TypeLayout`], which describes the size,
+//! alignment and fields of the type. Nested field types which are derived the
+//! same way have their layouts linked, so the full layout of a type can be
+//! inspected recursively.
+//!
+//! This is compile-time data only and comes at no runtime cost unless it is
+//! used. It's also used by [`Buf::dump`] to show what is stored in a buffer.
+//!
+//! [`ZeroCopy`]: crate::ZeroCopy
+//! [`Buf::dump`]: crate::buf::Buf::dump
+//!
+//! # Examples
+//!
+//! Reordering fields to remove padding:
+//!
+//! ```
+//! use musli_zerocopy::ZeroCopy;
+//!
+//! #[derive(ZeroCopy)]
+//! #[repr(C)]
+//! #[zero_copy(layout)]
+//! struct Padded {
+//!     a: u8,
+//!     b: u64,
+//!     c: u16,
+//! }
+//!
+//! #[derive(ZeroCopy)]
+//! #[repr(C)]
+//! #[zero_copy(layout)]
+//! struct Reordered {
+//!     b: u64,
+//!     c: u16,
+//!     a: u8,
+//! }
+//!
+//! assert_eq!(Padded::LAYOUT.size(), 24);
+//! assert_eq!(Padded::LAYOUT.total_padding(), 13);
+//!
+//! let padding = Padded::LAYOUT.padding().collect::<Vec<_>>();
+//! assert_eq!(padding[0].offset(), 1);
+//! assert_eq!(padding[0].len(), 7);
+//!
+//! assert_eq!(Reordered::LAYOUT.size(), 16);
+//! assert_eq!(Reordered::LAYOUT.total_padding(), 5);
+//!
+//! println!("{}", Padded::LAYOUT);
+//! ```
+//!
+//! The displayed table for `Padded` looks like this:
+//!
+//! ```text
+//! Padded (size: 24, align: 8, padding: 13)
+//! offset  size  align  field
+//!      0     1      1  a: u8
+//!      1     7         (padding)
+//!      8     8      8  b: u64
+//!     16     2      2  c: u16
+//!     18     6         (padding)
+//! ```
+
+use core::fmt;
+use core::mem::{align_of, size_of};
+
+use crate::ZeroCopy;
+
+/// The layout of a type deriving [`ZeroCopy`] with `#[zero_copy(layout)]`.
+///
+/// See the [module level documentation][self] for more information.
+///
+/// [`ZeroCopy`]: crate::ZeroCopy
+#[derive(Debug, Clone, Copy)]
+pub struct TypeLayout {
+    name: &'static str,
+    size: usize,
+    align: usize,
+    fields: &'static [FieldLayout],
+}
+
+impl TypeLayout {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        size: usize,
+        align: usize,
+        fields: &'static [FieldLayout],
+    ) -> Self {
+        Self {
+            name,
+            size,
+            align,
+            fields,
+        }
+    }
+
+    /// The name of the type.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The size of the type in bytes.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the type in bytes.
+    #[inline]
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The fields of the type, in the order they are declared.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::ZeroCopy;
+    ///
+    /// #[derive(ZeroCopy)]
+    /// #[repr(C)]
+    /// #[zero_copy(layout)]
+    /// struct Point(u8, u32);
+    ///
+    /// let fields = Point::LAYOUT.fields();
+    /// assert_eq!(fields[0].name(), "0");
+    /// assert_eq!(fields[1].offset(), 4);
+    /// assert_eq!(fields[1].ty(), "u32");
+    /// ```
+    #[inline]
+    pub const fn fields(&self) -> &'static [FieldLayout] {
+        self.fields
+    }
+
+    /// Iterate over the runs of padding bytes directly inside of this type.
+    ///
+    /// This does not include padding inside of fields, see
+    /// [`TypeLayout::total_padding`] for that.
+    #[inline]
+    pub fn padding(&self) -> Padding {
+        Padding {
+            fields: self.fields,
+            size: self.size,
+            offset: 0,
+        }
+    }
+
+    /// The total number of padding bytes in this type, including padding
+    /// inside of fields which have a known layout.
+    pub const fn total_padding(&self) -> usize {
+        let mut used = 0;
+        let mut nested = 0;
+        let mut n = 0;
+
+        while n < self.fields.len() {
+            let field = &self.fields[n];
+            used += field.size;
+
+            if let Some(layout) = field.layout {
+                nested += layout.total_padding();
+            }
+
+            n += 1;
+        }
+
+        self.size.saturating_sub(used) + nested
+    }
+
+    fn fmt_fields(&self, f: &mut fmt::Formatter<'_>, base: usize, depth: usize) -> fmt::Result {
+        let mut padding = self.padding().peekable();
+
+        for field in self.fields {
+            while let Some(run) = padding.next_if(|run| run.offset < field.offset) {
+                run.fmt_row(f, base, depth)?;
+            }
+
+            writeln!(
+                f,
+                "{:>6}  {:>4}  {:>5}  {:indent$}{}: {}",
+                base + field.offset,
+                field.size,
+                field.align,
+                "",
+                field.name,
+                field.ty,
+                indent = depth * 2,
+            )?;
+
+            if let Some(layout) = field.layout {
+                layout.fmt_fields(f, base + field.offset, depth + 1)?;
+            }
+        }
+
+        for run in padding {
+            run.fmt_row(f, base, depth)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders the layout as a human-readable table.
+impl fmt::Display for TypeLayout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} (size: {}, align: {}, padding: {})",
+            self.name,
+            self.size,
+            self.align,
+            self.total_padding()
+        )?;
+        writeln!(f, "offset  size  align  field")?;
+        self.fmt_fields(f, 0, 0)
+    }
+}
+
+/// The layout of a single field in a [`TypeLayout`].
+#[derive(Debug, Clone, Copy)]
+pub struct FieldLayout {
+    name: &'static str,
+    ty: &'static str,
+    offset: usize,
+    size: usize,
+    align: usize,
+    layout: Option<&'static TypeLayout>,
+    pointer: Option<PointerLayout>,
+}
+
+impl FieldLayout {
+    #[doc(hidden)]
+    pub const fn new(
+        name: &'static str,
+        ty: &'static str,
+        offset: usize,
+        size: usize,
+        align: usize,
+        layout: Option<&'static TypeLayout>,
+        pointer: Option<PointerLayout>,
+    ) -> Self {
+        Self {
+            name,
+            ty,
+            offset,
+            size,
+            align,
+            layout,
+            pointer,
+        }
+    }
+
+    /// The name of the field, or its index for tuple structs.
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// The type of the field as written in the declaration.
+    #[inline]
+    pub const fn ty(&self) -> &'static str {
+        self.ty
+    }
+
+    /// The offset of the field in bytes from the start of the type.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The size of the field in bytes.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment of the field's type in bytes.
+    #[inline]
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The layout of the field's type, if it also derives [`ZeroCopy`] with
+    /// `#[zero_copy(layout)]`.
+    ///
+    /// [`ZeroCopy`]: crate::ZeroCopy
+    #[inline]
+    pub const fn layout(&self) -> Option<&'static TypeLayout> {
+        self.layout
+    }
+
+    /// What the field points to, if its type is a pointer such as [`Ref`].
+    ///
+    /// [`Ref`]: crate::pointer::Ref
+    #[inline]
+    pub const fn pointer(&self) -> Option<PointerLayout> {
+        self.pointer
+    }
+}
+
+/// The layout of what a pointer such as [`Ref`] points to.
+///
+/// [`Ref`]: crate::pointer::Ref
+#[derive(Debug, Clone, Copy)]
+pub struct PointerLayout {
+    kind: PointeeKind,
+    size: usize,
+    align: usize,
+    layout: fn() -> Option<&'static TypeLayout>,
+    pointer: fn() -> Option<PointerLayout>,
+    read: fn(&[u8]) -> Option<(usize, usize)>,
+}
+
+impl PointerLayout {
+    #[doc(hidden)]
+    pub const fn new<T>(kind: PointeeKind) -> Self
+    where
+        T: ZeroCopy,
+    {
+        Self {
+            kind,
+            size: size_of::<T>(),
+            align: align_of::<T>(),
+            layout: layout_of::<T>,
+            pointer: pointer_of::<T>,
+            read: |_| None,
+        }
+    }
+
+    #[doc(hidden)]
+    pub const fn with_read(self, read: fn(&[u8]) -> Option<(usize, usize)>) -> Self {
+        Self { read, ..self }
+    }
+
+    /// The kind of value being pointed to.
+    #[inline]
+    pub const fn kind(&self) -> PointeeKind {
+        self.kind
+    }
+
+    /// The size in bytes of the value being pointed to, or of each element if
+    /// it's a slice or a string.
+    #[inline]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+
+    /// The alignment in bytes of the value being pointed to, or of each
+    /// element if it's a slice or a string.
+    #[inline]
+    pub const fn align(&self) -> usize {
+        self.align
+    }
+
+    /// The layout of the value being pointed to, or of each element if it's a
+    /// slice, if its type derives [`ZeroCopy`] with `#[zero_copy(layout)]`.
+    #[inline]
+    pub fn layout(&self) -> Option<&'static TypeLayout> {
+        (self.layout)()
+    }
+
+    /// What the value being pointed to, or each element if it's a slice,
+    /// points to in turn if it's also a pointer.
+    #[inline]
+    pub fn pointer(&self) -> Option<PointerLayout> {
+        (self.pointer)()
+    }
+
+    /// Read the offset and the number of elements that a pointer with this
+    /// layout stored at the start of `bytes` points to.
+    ///
+    /// This returns `None` if `bytes` is too short to contain the pointer.
+    #[inline]
+    pub fn read(&self, bytes: &[u8]) -> Option<(usize, usize)> {
+        (self.read)(bytes)
+    }
+}
+
+/// The kind of value a pointer points to, as returned by
+/// [`PointerLayout::kind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PointeeKind {
+    /// A single sized value.
+    Sized,
+    /// A slice of elements.
+    Slice,
+    /// A string slice.
+    Str,
+}
+
+fn layout_of<T>() -> Option<&'static TypeLayout>
+where
+    T: ZeroCopy,
+{
+    T::__LAYOUT
+}
+
+fn pointer_of<T>() -> Option<PointerLayout>
+where
+    T: ZeroCopy,
+{
+    T::__POINTER
+}
+
+/// A run of padding bytes, as returned by [`TypeLayout::padding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaddingRun {
+    offset: usize,
+    len: usize,
+}
+
+impl PaddingRun {
+    /// The offset of the first padding byte.
+    #[inline]
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The number of padding bytes.
+    #[inline]
+    #[allow(clippy::len_without_is_empty)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    fn fmt_row(&self, f: &mut fmt::Formatter<'_>, base: usize, depth: usize) -> fmt::Result {
+        writeln!(
+            f,
+            "{:>6}  {:>4}  {:>5}  {:indent$}(padding)",
+            base + self.offset,
+            self.len,
+            "",
+            "",
+            indent = depth * 2,
+        )
+    }
+}
+
+/// Iterator over runs of padding bytes, as returned by
+/// [`TypeLayout::padding`].
+pub struct Padding {
+    fields: &'static [FieldLayout],
+    size: usize,
+    offset: usize,
+}
+
+impl Iterator for Padding {
+    type Item = PaddingRun;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let [field, rest @ ..] = self.fields {
+            let offset = self.offset;
+            self.fields = rest;
+            self.offset = self.offset.max(field.offset + field.size);
+
+            if field.offset > offset {
+                return Some(PaddingRun {
+                    offset,
+                    len: field.offset - offset,
+                });
+            }
+        }
+
+        if self.offset < self.size {
+            let offset = self.offset;
+            self.offset = self.size;
+
+            return Some(PaddingRun {
+                offset,
+                len: self.size - offset,
+            });
+        }
+
+        None
+    }
+}
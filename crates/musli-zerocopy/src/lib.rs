@@ -46,6 +46,10 @@
 //!   SwissTable implementation.
 //! * [`trie`] is an implementation of a prefix-trie, which supports efficient
 //!   multi-value byte-prefixed lookups.
+//! * [`bitset`] is a compact bitset supporting membership, rank and
+//!   intersection queries.
+//! * [`compact`] stores slices of enums where each element only occupies the
+//!   size of its variant.
 //!
 //! Finally if you're interested in the performance of `musli-zerocopy` you
 //! should go to [`benchmarks`]. I will be extending this suite with more
@@ -507,6 +511,7 @@
 //!
 //! [`aligned_buf(bytes, align)`]: https://docs.rs/musli-zerocopy/latest/musli_zerocopy/pointer/trait.Size.html
 //! [`benchmarks`]: https://udoprog.github.io/musli/benchmarks/
+//! [`bitset`]: https://docs.rs/musli-zerocopy/latest/musli_zerocopy/bitset/index.html
 //! [`ByteOrder`]: https://docs.rs/musli-zerocopy/latest/musli_zerocopy/trait.ByteOrder.html
 //! [`hashbrown` crate]: https://docs.rs/phf
 //! [`OwnedBuf::with_size`]: https://docs.rs/musli-zerocopy/latest/musli_zerocopy/buf/struct.OwnedBuf.html#method.with_size
@@ -542,12 +547,19 @@ extern crate std;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
 pub use self::buf::OwnedBuf;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::buf::{diff, diff_with_limit};
 #[doc(inline)]
-pub use self::buf::{Buf, DefaultAlignment, SliceMut, Visit};
+pub use self::buf::{Buf, DefaultAlignment, SliceMut, ValidateTree, Visit};
 pub mod buf;
 
 pub mod mem;
 
+pub mod bitset;
+
+pub mod compact;
+
 pub mod slice;
 
 pub mod trie;
@@ -576,12 +588,25 @@ pub mod pointer;
 pub use self::endian::{ByteOrder, Endian};
 pub mod endian;
 
+#[doc(inline)]
+pub use self::opt::Opt;
+mod opt;
+
+pub mod layout;
+
 mod lossy_str;
 mod stack;
 
 /// Macro to derive a simple [`Visit`] implementation.
 pub use musli_zerocopy_macros::Visit;
 
+/// Macro to derive a [`ValidateTree`] implementation which validates every
+/// field of a struct or the fields of the active variant of an enum.
+///
+/// Fields marked with `#[zero_copy(ignore)]` are skipped, and every generic
+/// type parameter is required to implement [`ValidateTree`].
+pub use musli_zerocopy_macros::ValidateTree;
+
 /// Derive macro to implement [`ZeroCopy`].
 ///
 /// Implementing this trait ensures that the type can safely be coerced to and
@@ -832,6 +857,86 @@ pub use musli_zerocopy_macros::Visit;
 ///
 /// <br>
 ///
+/// ### `#[zero_copy(layout)]`
+///
+/// Emits an associated `LAYOUT` constant of type [`TypeLayout`] describing the
+/// size, alignment, fields and padding of the struct. This is only supported
+/// on structs. The layout is also used to break down the struct into its
+/// fields when dumping a buffer with [`Buf::dump`].
+///
+/// [`TypeLayout`]: crate::layout::TypeLayout
+/// [`Buf::dump`]: crate::buf::Buf::dump
+///
+/// ```
+/// use musli_zerocopy::ZeroCopy;
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(C)]
+/// #[zero_copy(layout)]
+/// struct Custom { a: u8, b: u32 }
+///
+/// assert_eq!(Custom::LAYOUT.fields()[1].offset(), 4);
+/// assert_eq!(Custom::LAYOUT.total_padding(), 3);
+/// ```
+///
+/// <br>
+///
+/// ### `#[zero_copy(assert_no_padding)]`
+///
+/// Causes a compile error if the type contains padding, including padding
+/// inside of its fields. This is useful to catch poorly ordered fields which
+/// waste space in the buffer. For generic types the check happens when the
+/// type is used with specific parameters.
+///
+/// ```
+/// use musli_zerocopy::ZeroCopy;
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(C)]
+/// #[zero_copy(assert_no_padding)]
+/// struct Dense { a: u32, b: u16, c: u8, d: u8 }
+/// ```
+///
+/// ```compile_fail
+/// use musli_zerocopy::ZeroCopy;
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(C)]
+/// #[zero_copy(assert_no_padding)]
+/// struct Sparse { a: u8, b: u32, c: u8 }
+/// ```
+///
+/// <br>
+///
+/// ### `#[zero_copy(compact)]`
+///
+/// Implements [`Compact`] for an enum, which allows slices of it to be stored
+/// with [`compact::store`] where each element only occupies the size of its
+/// variant. This is only supported on enums.
+///
+/// [`Compact`]: crate::compact::Compact
+/// [`compact::store`]: crate::compact::store
+///
+/// ```
+/// use musli_zerocopy::ZeroCopy;
+/// use musli_zerocopy::compact::Compact;
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(u8)]
+/// #[zero_copy(compact)]
+/// enum Value {
+///     Empty,
+///     Small(u16),
+///     Large([u32; 16]),
+/// }
+///
+/// assert_eq!(Value::Empty.compact_size(), 1);
+/// assert_eq!(Value::Small(1).compact_size(), 4);
+/// assert_eq!(Value::Large([0; 16]).compact_size(), 68);
+/// ```
+///
+/// <br>
+///
 /// ### `#[zero_copy(swap_bytes)]`
 ///
 /// Allows enums to be byte-ordered swap with some extra work.
@@ -920,10 +1025,12 @@ pub mod __private {
     }
 
     pub mod mem {
-        pub use ::core::mem::{align_of, size_of};
+        pub use ::core::mem::{align_of, offset_of, size_of};
     }
 
-    pub use crate::buf::{Buf, Visit};
+    pub use ::core::option::Option;
+
+    pub use crate::buf::{Buf, TreeValidator, ValidateTree, Visit};
     pub use crate::endian::ByteOrder;
     pub use crate::traits::{ZeroCopy, ZeroSized};
 
@@ -4,6 +4,8 @@ use core::ptr::NonNull;
 use core::slice;
 
 use crate::buf;
+use crate::endian::ByteOrder;
+use crate::layout::PointerLayout;
 use crate::pointer::{Pointee, Size};
 use crate::traits::ZeroCopy;
 
@@ -126,4 +128,45 @@ where
     {
         T::try_from_metadata(metadata)
     }
+
+    const __POINTER: PointerLayout = T::__POINTER;
+
+    #[inline]
+    fn __len<E, O>(stored: Self::Stored<O>) -> usize
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        T::__len::<E, O>(stored)
+    }
+}
+
+impl<T> Pointee for [MaybeUninit<T>]
+where
+    T: ZeroCopy,
+{
+    type Metadata = usize;
+    type Stored<O>
+        = O
+    where
+        O: Size;
+
+    #[inline]
+    fn try_from_metadata<O>(metadata: usize) -> Option<O>
+    where
+        O: Size,
+    {
+        O::try_from_usize(metadata)
+    }
+
+    const __POINTER: PointerLayout = <[T]>::__POINTER;
+
+    #[inline]
+    fn __len<E, O>(stored: O) -> usize
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        stored.as_usize::<E>()
+    }
 }
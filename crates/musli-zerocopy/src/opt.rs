@@ -0,0 +1,195 @@
+use crate::{ValidateTree, Visit, ZeroCopy};
+
+/// An optional value which can be used in zero-copy layouts.
+///
+/// The layout of [`Option<T>`] is `#[repr(Rust)]` except for a few types with
+/// [specific representation guarantees], like `Option<NonZeroU32>`. This type
+/// instead has a well-defined layout, where the presence of a value is
+/// indicated by a leading flag byte. A flag byte which is neither `0` or `1` is
+/// rejected during validation.
+///
+/// It converts to and from [`Option<T>`] through [`From`].
+///
+/// [specific representation guarantees]: https://doc.rust-lang.org/std/option/index.html#representation
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{Opt, OwnedBuf, Ref, ZeroCopy};
+///
+/// #[derive(ZeroCopy)]
+/// #[repr(C)]
+/// struct Person {
+///     name: Ref<str>,
+///     nickname: Opt<Ref<str>>,
+///     age: Opt<u32>,
+/// }
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let name = buf.store_unsized("Aristotle");
+/// let nickname = buf.store_unsized("The Philosopher");
+///
+/// let person = buf.store(&Person {
+///     name,
+///     nickname: Opt::Some(nickname),
+///     age: Opt::None,
+/// });
+///
+/// let person = buf.load(person)?;
+/// assert_eq!(buf.load(person.name)?, "Aristotle");
+///
+/// let nickname = person.nickname.as_option().map(|s| buf.load(*s)).transpose()?;
+/// assert_eq!(nickname, Some("The Philosopher"));
+/// assert_eq!(person.age.as_option(), None);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+///
+/// Invalid flag bytes are rejected:
+///
+/// ```
+/// use musli_zerocopy::{buf, Opt, Ref};
+///
+/// let bytes = buf::aligned_buf::<Opt<u32>>(&[2, 0, 0, 0, 0, 0, 0, 0]);
+/// assert!(bytes.load(Ref::<Opt<u32>>::zero()).is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, ZeroCopy, ValidateTree)]
+#[zero_copy(crate, bounds = {T: ZeroCopy})]
+#[validate_tree(crate)]
+#[repr(u8)]
+pub enum Opt<T> {
+    /// No value.
+    None = 0,
+    /// Some value of type `T`.
+    Some(T) = 1,
+}
+
+impl<T> Opt<T> {
+    /// Test if the optional value is present.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Opt;
+    ///
+    /// assert!(Opt::Some(42u32).is_some());
+    /// assert!(!Opt::<u32>::None.is_some());
+    /// ```
+    #[inline]
+    pub const fn is_some(&self) -> bool {
+        matches!(self, Opt::Some(..))
+    }
+
+    /// Test if the optional value is absent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Opt;
+    ///
+    /// assert!(Opt::<u32>::None.is_none());
+    /// assert!(!Opt::Some(42u32).is_none());
+    /// ```
+    #[inline]
+    pub const fn is_none(&self) -> bool {
+        matches!(self, Opt::None)
+    }
+
+    /// Access the optional value as an [`Option<&T>`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Opt;
+    ///
+    /// assert_eq!(Opt::Some(42u32).as_option(), Some(&42));
+    /// assert_eq!(Opt::<u32>::None.as_option(), None);
+    /// ```
+    #[inline]
+    pub const fn as_option(&self) -> Option<&T> {
+        match self {
+            Opt::Some(value) => Some(value),
+            Opt::None => None,
+        }
+    }
+
+    /// Access the optional value as an [`Option<&mut T>`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Opt;
+    ///
+    /// let mut opt = Opt::Some(42u32);
+    ///
+    /// if let Some(value) = opt.as_option_mut() {
+    ///     *value += 1;
+    /// }
+    ///
+    /// assert_eq!(opt, Opt::Some(43));
+    /// ```
+    #[inline]
+    pub fn as_option_mut(&mut self) -> Option<&mut T> {
+        match self {
+            Opt::Some(value) => Some(value),
+            Opt::None => None,
+        }
+    }
+
+    /// Convert into an [`Option<T>`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::Opt;
+    ///
+    /// assert_eq!(Opt::Some(42u32).into_option(), Some(42));
+    /// assert_eq!(Opt::<u32>::None.into_option(), None);
+    /// ```
+    #[inline]
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Opt::Some(value) => Some(value),
+            Opt::None => None,
+        }
+    }
+}
+
+impl<T> Default for Opt<T> {
+    #[inline]
+    fn default() -> Self {
+        Opt::None
+    }
+}
+
+impl<T> From<Option<T>> for Opt<T> {
+    #[inline]
+    fn from(value: Option<T>) -> Self {
+        match value {
+            Some(value) => Opt::Some(value),
+            None => Opt::None,
+        }
+    }
+}
+
+impl<T> From<Opt<T>> for Option<T> {
+    #[inline]
+    fn from(value: Opt<T>) -> Self {
+        value.into_option()
+    }
+}
+
+impl<T> Visit for Opt<T>
+where
+    T: ZeroCopy,
+{
+    type Target = Opt<T>;
+
+    #[inline]
+    fn visit<V, O>(&self, _: &crate::Buf, visitor: V) -> Result<O, crate::Error>
+    where
+        V: FnOnce(&Self::Target) -> O,
+    {
+        Ok(visitor(self))
+    }
+}
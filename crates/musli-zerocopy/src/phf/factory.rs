@@ -5,7 +5,7 @@ use core::hash::Hash;
 use crate::buf::{StoreBuf, Visit};
 use crate::error::Error;
 use crate::phf::hashing::HashKey;
-use crate::phf::{Entry, MapRef, SetRef};
+use crate::phf::{Entry, MapRef, PhfHasher, SetRef, Sip13};
 use crate::Ref;
 use crate::ZeroCopy;
 
@@ -70,9 +70,54 @@ where
     S: ?Sized + StoreBuf,
     I: IntoIterator<Item = (K, V)>,
     I::IntoIter: ExactSizeIterator,
+{
+    store_map_with_hasher::<Sip13, _, _, _, _>(buf, entries)
+}
+
+/// Store a map based on a perfect hash function into a buffer, using the hash
+/// function `H`.
+///
+/// This is the same as [`store_map`], except that the hash function used is
+/// specified through the `H` parameter which is captured in the returned
+/// [`MapRef`]. See the [`hasher`] module for more information.
+///
+/// [`hasher`]: crate::phf::hasher
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::OwnedBuf;
+/// use musli_zerocopy::phf::{self, Fnv1a};
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let first = buf.store_unsized("first");
+/// let second = buf.store_unsized("second");
+///
+/// let map = phf::store_map_with_hasher::<Fnv1a, _, _, _, _>(&mut buf, [(first, 1u32), (second, 2u32)])?;
+/// let map = buf.bind(map)?;
+///
+/// assert_eq!(map.get("first")?, Some(&1));
+/// assert_eq!(map.get("second")?, Some(&2));
+/// assert_eq!(map.get("third")?, None);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub fn store_map_with_hasher<H, K, V, S, I>(
+    buf: &mut S,
+    entries: I,
+) -> Result<MapRef<K, V, S::ByteOrder, S::Size, H>, Error>
+where
+    H: PhfHasher,
+    K: Visit + ZeroCopy,
+    V: ZeroCopy,
+    K::Target: Hash,
+    S: ?Sized + StoreBuf,
+    I: IntoIterator<Item = (K, V)>,
+    I::IntoIter: ExactSizeIterator,
 {
     let entries = entries.into_iter().map(|(k, v)| Entry::new(k, v));
-    let (key, entries, displacements) = store_raw(buf, entries, |entry| &entry.key)?;
+    let (key, entries, displacements) =
+        store_raw::<H, _, _, _, _>(buf, entries, |entry| &entry.key)?;
     Ok(MapRef::new(key, entries, displacements))
 }
 
@@ -136,11 +181,48 @@ where
     S: ?Sized + StoreBuf,
     I: IntoIterator<Item: Visit<Target: Hash> + ZeroCopy, IntoIter: ExactSizeIterator>,
 {
-    let (key, entries, displacements) = store_raw(buf, entries, |entry| entry)?;
+    store_set_with_hasher::<Sip13, _, _>(buf, entries)
+}
+
+/// Store a set based on a perfect hash function into a buffer, using the hash
+/// function `H`.
+///
+/// This is the same as [`store_set`], except that the hash function used is
+/// specified through the `H` parameter which is captured in the returned
+/// [`SetRef`]. See the [`hasher`] module for more information.
+///
+/// [`hasher`]: crate::phf::hasher
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::OwnedBuf;
+/// use musli_zerocopy::phf::{self, Fnv1a};
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let set = phf::store_set_with_hasher::<Fnv1a, _, _>(&mut buf, [1, 2])?;
+/// let set = buf.bind(set)?;
+///
+/// assert!(set.contains(&1)?);
+/// assert!(set.contains(&2)?);
+/// assert!(!set.contains(&3)?);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub fn store_set_with_hasher<H, S, I>(
+    buf: &mut S,
+    entries: I,
+) -> Result<SetRef<I::Item, S::ByteOrder, S::Size, H>, Error>
+where
+    H: PhfHasher,
+    S: ?Sized + StoreBuf,
+    I: IntoIterator<Item: Visit<Target: Hash> + ZeroCopy, IntoIter: ExactSizeIterator>,
+{
+    let (key, entries, displacements) = store_raw::<H, _, _, _, _>(buf, entries, |entry| entry)?;
     Ok(SetRef::new(key, entries, displacements))
 }
 
-fn store_raw<K, I, S, F>(
+fn store_raw<H, K, I, S, F>(
     buf: &mut S,
     entries: I,
     access: F,
@@ -153,6 +235,7 @@ fn store_raw<K, I, S, F>(
     Error,
 >
 where
+    H: PhfHasher,
     K: Visit<Target: Hash> + ZeroCopy,
     I: IntoIterator<Item: ZeroCopy, IntoIter: ExactSizeIterator>,
     S: ?Sized + StoreBuf,
@@ -168,7 +251,7 @@ where
 
     let hash_state = {
         buf.align_in_place();
-        crate::phf::generator::generate_hash(
+        crate::phf::generator::generate_hash::<H, _, _, _, _, _>(
             buf.as_mut_buf(),
             &entries,
             &displacements,
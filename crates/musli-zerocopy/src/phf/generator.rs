@@ -6,7 +6,7 @@ use alloc::vec::Vec;
 use crate::buf::{Buf, Visit};
 use crate::error::{Error, ErrorKind};
 use crate::phf::hashing::{displace, hash, HashKey, Hashes};
-use crate::phf::Entry;
+use crate::phf::{Entry, PhfHasher};
 use crate::{ByteOrder, Ref, Size, ZeroCopy};
 
 use rand::distributions::Standard;
@@ -26,7 +26,7 @@ pub(crate) fn displacements_len(len: usize) -> usize {
     len.div_ceil(DEFAULT_LAMBDA)
 }
 
-pub(crate) fn generate_hash<K, T, F, E, O>(
+pub(crate) fn generate_hash<H, K, T, F, E, O>(
     buf: &mut Buf,
     entries: &Ref<[T], E, O>,
     displacements: &Ref<[Entry<u32, u32>], E, O>,
@@ -34,6 +34,7 @@ pub(crate) fn generate_hash<K, T, F, E, O>(
     access: F,
 ) -> Result<HashState, Error>
 where
+    H: PhfHasher,
     K: Visit,
     K::Target: Hash,
     F: Fn(&T) -> &K,
@@ -42,7 +43,9 @@ where
     O: Size,
 {
     for key in SmallRng::seed_from_u64(FIXED_SEED).sample_iter(Standard) {
-        if let Some(hash) = try_generate_hash(buf, entries, displacements, map, key, &access)? {
+        if let Some(hash) =
+            try_generate_hash::<H, _, _, _, _, _>(buf, entries, displacements, map, key, &access)?
+        {
             return Ok(hash);
         }
 
@@ -59,7 +62,7 @@ where
     Err(Error::new(ErrorKind::FailedPhf))
 }
 
-fn try_generate_hash<K, T, F, E, O>(
+fn try_generate_hash<H, K, T, F, E, O>(
     buf: &mut Buf,
     entries: &Ref<[T], E, O>,
     displacements: &Ref<[Entry<u32, u32>], E, O>,
@@ -68,6 +71,7 @@ fn try_generate_hash<K, T, F, E, O>(
     access: &F,
 ) -> Result<Option<HashState>, Error>
 where
+    H: PhfHasher,
     K: Visit,
     K::Target: Hash,
     F: ?Sized + Fn(&T) -> &K,
@@ -80,7 +84,7 @@ where
     for entry in entries.iter() {
         let entry = buf.load(entry)?;
         let entry_key = access(entry);
-        let h = hash(buf, entry_key, &key)?;
+        let h = hash::<H, _>(buf, entry_key, &key)?;
         hashes.push(h);
     }
 
@@ -91,7 +95,9 @@ where
         buckets[to].push(index);
     }
 
-    buckets.sort_by_key(|a| a.len());
+    // Place the largest buckets first, since they are the hardest to fit.
+    let mut order = (0..buckets.len()).collect::<Vec<_>>();
+    order.sort_by_key(|&i| core::cmp::Reverse(buckets[i].len()));
 
     let table_len = hashes.len();
     // let mut map = vec![usize::MAX; table_len];
@@ -104,6 +110,7 @@ where
     // are equal. (A u64 is far too large to overflow in a reasonable
     // time for current hardware.)
     let mut try_map = vec![0u64; table_len];
+    let mut taken = vec![false; table_len];
     let mut generation = 0u64;
 
     // the actual values corresponding to the markers above, as
@@ -111,7 +118,10 @@ where
     // chosen the right displacements.
     let mut values_to_add = vec![];
 
-    'outer: for (bucket, d_ref) in buckets.iter().zip(displacements.iter()) {
+    'outer: for i in order {
+        let bucket = &buckets[i];
+        let d_ref = displacements.at(i);
+
         for d1 in 0..(table_len as u32) {
             'inner: for d2 in 0..(table_len as u32) {
                 values_to_add.clear();
@@ -122,7 +132,7 @@ where
                     let index = displace(f1, f2, d1, d2) as usize;
                     let index = index % table_len;
 
-                    if *buf.load(map.at(index))? != usize::MAX || try_map[index] == generation {
+                    if taken[index] || try_map[index] == generation {
                         continue 'inner;
                     }
 
@@ -133,9 +143,10 @@ where
                 // We've picked a good set of displacements
                 *buf.load_mut(d_ref)? = Entry::new(d1, d2);
 
+                // The map records where each entry should be moved to.
                 for &(i, key) in &values_to_add {
-                    let m = buf.load_mut(map.at(i))?;
-                    *m = key;
+                    taken[i] = true;
+                    *buf.load_mut(map.at(key))? = i;
                 }
 
                 continue 'outer;
@@ -0,0 +1,164 @@
+//! Hash functions which can be used by perfect hash maps and sets.
+//!
+//! The hash function used is captured as a type parameter of [`MapRef`] and
+//! [`SetRef`], so that the code which builds a map and the code which reads it
+//! can't disagree on which one is in use. By default [`Sip13`] is used.
+//!
+//! Since the layout of a perfect hash map is verified when it is constructed,
+//! the resistance against collision attacks offered by SipHash is less
+//! important than for a regular hash map. So for short keys a faster hash
+//! function such as [`Fnv1a`] can be a better choice.
+//!
+//! [`MapRef`]: crate::phf::MapRef
+//! [`SetRef`]: crate::phf::SetRef
+
+use core::hash::Hasher;
+
+use crate::sip::{Hash128, Hasher128, SipHasher13};
+
+/// A hash function used by perfect hash maps and sets.
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::OwnedBuf;
+/// use musli_zerocopy::phf::{self, Fnv1a, MapRef};
+///
+/// let mut buf = OwnedBuf::new();
+///
+/// let map: MapRef<u64, u32, _, _, Fnv1a> = phf::store_map_with_hasher(&mut buf, [(10u64, 1), (20u64, 2)])?;
+/// let map = buf.bind(map)?;
+///
+/// assert_eq!(map.get(&10u64)?, Some(&1));
+/// assert_eq!(map.get(&20u64)?, Some(&2));
+/// assert_eq!(map.get(&30u64)?, None);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub trait PhfHasher: 'static + Hasher {
+    /// Construct a new hasher using the given seed.
+    ///
+    /// When a map is constructed, seeds are tried until one is found which
+    /// produces a perfect hash function for all keys. So different seeds should
+    /// produce well-distributed and independent hashes.
+    fn with_seed(seed: u64) -> Self;
+
+    /// Finish hashing, producing 128 bits of output as two halves.
+    fn finish128(&self) -> [u64; 2];
+}
+
+/// The SipHash 1-3 hash function.
+///
+/// This is the default hash function used by perfect hash maps and sets.
+#[derive(Debug, Clone, Copy)]
+pub struct Sip13 {
+    hasher: SipHasher13,
+}
+
+impl PhfHasher for Sip13 {
+    #[inline]
+    fn with_seed(seed: u64) -> Self {
+        Self {
+            hasher: SipHasher13::new_with_keys(0, seed),
+        }
+    }
+
+    #[inline]
+    fn finish128(&self) -> [u64; 2] {
+        let Hash128 { h1, h2 } = self.hasher.finish128();
+        [h1, h2]
+    }
+}
+
+impl Hasher for Sip13 {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hasher.finish()
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.hasher.write_usize(i);
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.hasher.write_u8(i);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.hasher.write_u16(i);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.hasher.write_u32(i);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.hasher.write_u64(i);
+    }
+}
+
+/// The 64-bit FNV-1a hash function.
+///
+/// This is very fast for short keys, but mixes its input poorly compared to
+/// [`Sip13`]. To compensate, the output is passed through a finalizer before
+/// it is used.
+#[derive(Debug, Clone, Copy)]
+pub struct Fnv1a {
+    state: u64,
+}
+
+impl Fnv1a {
+    const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+}
+
+impl PhfHasher for Fnv1a {
+    #[inline]
+    fn with_seed(seed: u64) -> Self {
+        Self {
+            state: Self::OFFSET_BASIS ^ fmix64(seed),
+        }
+    }
+
+    #[inline]
+    fn finish128(&self) -> [u64; 2] {
+        let h1 = fmix64(self.state);
+        let h2 = fmix64(h1 ^ self.state.rotate_left(32));
+        [h1, h2]
+    }
+}
+
+impl Hasher for Fnv1a {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.state ^= u64::from(b);
+            self.state = self.state.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        fmix64(self.state)
+    }
+}
+
+/// The 64-bit finalizer from MurmurHash3.
+#[inline]
+const fn fmix64(mut k: u64) -> u64 {
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xff51afd7ed558ccd);
+    k ^= k >> 33;
+    k = k.wrapping_mul(0xc4ceb9fe1a85ec53);
+    k ^= k >> 33;
+    k
+}
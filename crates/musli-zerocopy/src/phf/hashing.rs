@@ -2,8 +2,7 @@ use core::hash::Hash;
 
 use crate::buf::{Buf, Visit};
 use crate::error::{Error, ErrorKind};
-use crate::phf::Entry;
-use crate::sip::{Hash128, Hasher128, SipHasher13};
+use crate::phf::{Entry, PhfHasher};
 
 #[non_exhaustive]
 pub(crate) struct Hashes {
@@ -20,15 +19,16 @@ pub(crate) fn displace(f1: u32, f2: u32, d1: u32, d2: u32) -> u32 {
 }
 
 #[inline]
-pub(crate) fn hash<T>(buf: &Buf, value: &T, key: &HashKey) -> Result<Hashes, Error>
+pub(crate) fn hash<H, T>(buf: &Buf, value: &T, key: &HashKey) -> Result<Hashes, Error>
 where
+    H: PhfHasher,
     T: ?Sized + Visit,
     T::Target: Hash,
 {
-    let mut hasher = SipHasher13::new_with_keys(0, *key);
+    let mut hasher = H::with_seed(*key);
     value.visit(buf, |value| value.hash(&mut hasher))?;
 
-    let Hash128 { h1, h2 } = hasher.finish128();
+    let [h1, h2] = hasher.finish128();
 
     Ok(Hashes {
         g: (h1 >> 32) as usize,
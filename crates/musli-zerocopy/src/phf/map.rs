@@ -14,12 +14,13 @@
 
 use core::borrow::Borrow;
 use core::hash::Hash;
+use core::marker::PhantomData;
 
-use crate::buf::{Bindable, Buf, Visit};
+use crate::buf::{Bindable, Buf, TreeValidator, ValidateTree, Visit};
 use crate::endian::{ByteOrder, Native};
 use crate::error::Error;
 use crate::phf::hashing::HashKey;
-use crate::phf::Entry;
+use crate::phf::{Entry, PhfHasher, Sip13};
 use crate::pointer::{DefaultSize, Ref, Size};
 use crate::{Endian, ZeroCopy};
 
@@ -44,17 +45,19 @@ use crate::{Endian, ZeroCopy};
 /// assert!(!map.contains_key(&3)?);
 /// # Ok::<_, musli_zerocopy::Error>(())
 /// ```
-pub struct Map<'a, K, V> {
+pub struct Map<'a, K, V, H = Sip13> {
     key: HashKey,
     entries: &'a [Entry<K, V>],
     displacements: &'a [Entry<u32, u32>],
     buf: &'a Buf,
+    _hasher: PhantomData<H>,
 }
 
-impl<K, V> Map<'_, K, V>
+impl<K, V, H> Map<'_, K, V, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
+    H: PhfHasher,
 {
     /// Get a value from the map.
     ///
@@ -145,7 +148,7 @@ where
             return Ok(None);
         }
 
-        let hashes = crate::phf::hashing::hash(self.buf, key, &self.key)?;
+        let hashes = crate::phf::hashing::hash::<H, _>(self.buf, key, &self.key)?;
         let index =
             crate::phf::hashing::get_index(&hashes, self.displacements, self.entries.len())?;
 
@@ -162,15 +165,16 @@ where
 }
 
 /// Bind a [`MapRef`] into a [`Map`].
-impl<K, V, E, O> Bindable for MapRef<K, V, E, O>
+impl<K, V, E, O, H> Bindable for MapRef<K, V, E, O, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
     E: ByteOrder,
     O: Size,
+    H: PhfHasher,
 {
     type Bound<'a>
-        = Map<'a, K, V>
+        = Map<'a, K, V, H>
     where
         Self: 'a;
 
@@ -181,6 +185,7 @@ where
             entries: buf.load(self.entries)?,
             displacements: buf.load(self.displacements)?,
             buf,
+            _hasher: PhantomData,
         })
     }
 }
@@ -218,7 +223,7 @@ where
 #[derive(Debug, ZeroCopy)]
 #[repr(C)]
 #[zero_copy(crate)]
-pub struct MapRef<K, V, E = Native, O = DefaultSize>
+pub struct MapRef<K, V, E = Native, O = DefaultSize, H = Sip13>
 where
     K: ZeroCopy,
     V: ZeroCopy,
@@ -228,9 +233,11 @@ where
     key: Endian<HashKey, E>,
     entries: Ref<[Entry<K, V>], E, O>,
     displacements: Ref<[Entry<u32, u32>], E, O>,
+    #[zero_copy(ignore)]
+    _hasher: PhantomData<H>,
 }
 
-impl<K, V, E, O> MapRef<K, V, E, O>
+impl<K, V, E, O, H> MapRef<K, V, E, O, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
@@ -247,16 +254,18 @@ where
             key: Endian::new(key),
             entries,
             displacements,
+            _hasher: PhantomData,
         }
     }
 }
 
-impl<K, V, E, O> MapRef<K, V, E, O>
+impl<K, V, E, O, H> MapRef<K, V, E, O, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
     E: ByteOrder,
     O: Size,
+    H: PhfHasher,
 {
     /// Get a value from the map.
     ///
@@ -344,7 +353,7 @@ where
             return Ok(None);
         }
 
-        let hashes = crate::phf::hashing::hash(buf, key, &self.key.to_ne())?;
+        let hashes = crate::phf::hashing::hash::<H, _>(buf, key, &self.key.to_ne())?;
 
         let displacements = |index| match self.displacements.get(index) {
             Some(entry) => Ok(Some(buf.load(entry)?)),
@@ -372,7 +381,22 @@ where
     }
 }
 
-impl<K, V, E, O> Clone for MapRef<K, V, E, O>
+unsafe impl<K, V, E, O, H> ValidateTree for MapRef<K, V, E, O, H>
+where
+    K: ZeroCopy + ValidateTree,
+    V: ZeroCopy + ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        cx.follow(self.entries)?;
+        cx.load(self.displacements)?;
+        Ok(())
+    }
+}
+
+impl<K, V, E, O, H> Clone for MapRef<K, V, E, O, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
@@ -384,7 +408,7 @@ where
     }
 }
 
-impl<K, V, E, O> Copy for MapRef<K, V, E, O>
+impl<K, V, E, O, H> Copy for MapRef<K, V, E, O, H>
 where
     K: ZeroCopy,
     V: ZeroCopy,
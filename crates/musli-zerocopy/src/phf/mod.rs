@@ -17,6 +17,10 @@ pub(crate) mod generator;
 
 pub(crate) mod hashing;
 
+#[doc(inline)]
+pub use self::hasher::{Fnv1a, PhfHasher, Sip13};
+pub mod hasher;
+
 pub(crate) use self::entry::Entry;
 mod entry;
 
@@ -14,12 +14,13 @@
 
 use core::borrow::Borrow;
 use core::hash::Hash;
+use core::marker::PhantomData;
 
-use crate::buf::{Bindable, Buf, Visit};
+use crate::buf::{Bindable, Buf, TreeValidator, ValidateTree, Visit};
 use crate::endian::{ByteOrder, Native};
 use crate::error::Error;
 use crate::phf::hashing::HashKey;
-use crate::phf::Entry;
+use crate::phf::{Entry, PhfHasher, Sip13};
 use crate::pointer::{DefaultSize, Ref, Size};
 use crate::{Endian, ZeroCopy};
 
@@ -41,16 +42,18 @@ use crate::{Endian, ZeroCopy};
 /// assert!(!set.contains(&3)?);
 /// # Ok::<_, musli_zerocopy::Error>(())
 /// ```
-pub struct Set<'a, T> {
+pub struct Set<'a, T, H = Sip13> {
     key: HashKey,
     entries: &'a [T],
     displacements: &'a [Entry<u32, u32>],
     buf: &'a Buf,
+    _hasher: PhantomData<H>,
 }
 
-impl<T> Set<'_, T>
+impl<T, H> Set<'_, T, H>
 where
     T: ZeroCopy,
+    H: PhfHasher,
 {
     /// Get a value from the set.
     ///
@@ -81,7 +84,7 @@ where
             return Ok(false);
         }
 
-        let hashes = crate::phf::hashing::hash(self.buf, key, &self.key)?;
+        let hashes = crate::phf::hashing::hash::<H, _>(self.buf, key, &self.key)?;
         let index =
             crate::phf::hashing::get_index(&hashes, self.displacements, self.entries.len())?;
 
@@ -94,14 +97,15 @@ where
 }
 
 /// Bind a [`SetRef`] into a [`Set`].
-impl<T, E, O> Bindable for SetRef<T, E, O>
+impl<T, E, O, H> Bindable for SetRef<T, E, O, H>
 where
     T: ZeroCopy,
     E: ByteOrder,
     O: Size,
+    H: PhfHasher,
 {
     type Bound<'a>
-        = Set<'a, T>
+        = Set<'a, T, H>
     where
         Self: 'a;
 
@@ -112,6 +116,7 @@ where
             entries: buf.load(self.entries)?,
             displacements: buf.load(self.displacements)?,
             buf,
+            _hasher: PhantomData,
         })
     }
 }
@@ -146,7 +151,7 @@ where
 #[derive(Debug, ZeroCopy)]
 #[repr(C)]
 #[zero_copy(crate)]
-pub struct SetRef<T, E = Native, O = DefaultSize>
+pub struct SetRef<T, E = Native, O = DefaultSize, H = Sip13>
 where
     T: ZeroCopy,
     E: ByteOrder,
@@ -155,9 +160,25 @@ where
     key: Endian<HashKey, E>,
     entries: Ref<[T], E, O>,
     displacements: Ref<[Entry<u32, u32>], E, O>,
+    #[zero_copy(ignore)]
+    _hasher: PhantomData<H>,
 }
 
-impl<T, E, O> SetRef<T, E, O>
+unsafe impl<T, E, O, H> ValidateTree for SetRef<T, E, O, H>
+where
+    T: ZeroCopy + ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        cx.follow(self.entries)?;
+        cx.load(self.displacements)?;
+        Ok(())
+    }
+}
+
+impl<T, E, O, H> SetRef<T, E, O, H>
 where
     T: ZeroCopy,
     E: ByteOrder,
@@ -174,15 +195,17 @@ where
             key: Endian::new(key),
             entries,
             displacements,
+            _hasher: PhantomData,
         }
     }
 }
 
-impl<T, E, O> SetRef<T, E, O>
+impl<T, E, O, H> SetRef<T, E, O, H>
 where
     T: ZeroCopy,
     E: ByteOrder,
     O: Size,
+    H: PhfHasher,
 {
     /// Get a value from the set.
     ///
@@ -213,7 +236,7 @@ where
             return Ok(false);
         }
 
-        let hashes = crate::phf::hashing::hash(buf, key, &self.key.to_ne())?;
+        let hashes = crate::phf::hashing::hash::<H, _>(buf, key, &self.key.to_ne())?;
 
         let displacements = |index| match self.displacements.get(index) {
             Some(entry) => Ok(Some(buf.load(entry)?)),
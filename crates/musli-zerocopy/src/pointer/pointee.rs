@@ -1,6 +1,8 @@
 use core::fmt;
 
+use crate::endian::ByteOrder;
 use crate::error::IntoRepr;
+use crate::layout::{PointeeKind, PointerLayout};
 use crate::pointer::Size;
 use crate::traits::ZeroCopy;
 
@@ -12,6 +14,7 @@ mod sealed {
     pub trait Sealed {}
 
     impl<T> Sealed for MaybeUninit<T> where T: Pointee {}
+    impl<T> Sealed for [MaybeUninit<T>] where T: ZeroCopy {}
     impl<T> Sealed for T where T: ZeroCopy {}
     impl<T> Sealed for [T] where T: ZeroCopy {}
     impl Sealed for str {}
@@ -47,6 +50,17 @@ pub trait Pointee: self::sealed::Sealed {
     fn try_from_metadata<O>(metadata: Self::Metadata) -> Option<Self::Stored<O>>
     where
         O: Size;
+
+    /// The layout of the pointee.
+    #[doc(hidden)]
+    const __POINTER: PointerLayout;
+
+    /// The number of elements described by the stored metadata.
+    #[doc(hidden)]
+    fn __len<E, O>(stored: Self::Stored<O>) -> usize
+    where
+        E: ByteOrder,
+        O: Size;
 }
 
 impl<T> Pointee for T
@@ -66,6 +80,17 @@ where
     {
         Some(())
     }
+
+    const __POINTER: PointerLayout = PointerLayout::new::<T>(PointeeKind::Sized);
+
+    #[inline(always)]
+    fn __len<E, O>((): ()) -> usize
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        1
+    }
 }
 
 impl<T> Pointee for [T]
@@ -85,6 +110,17 @@ where
     {
         O::try_from_usize(metadata)
     }
+
+    const __POINTER: PointerLayout = PointerLayout::new::<T>(PointeeKind::Slice);
+
+    #[inline(always)]
+    fn __len<E, O>(stored: O) -> usize
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        stored.as_usize::<E>()
+    }
 }
 
 impl Pointee for str {
@@ -101,4 +137,15 @@ impl Pointee for str {
     {
         O::try_from_usize(metadata)
     }
+
+    const __POINTER: PointerLayout = PointerLayout::new::<u8>(PointeeKind::Str);
+
+    #[inline(always)]
+    fn __len<E, O>(stored: O) -> usize
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        stored.as_usize::<E>()
+    }
 }
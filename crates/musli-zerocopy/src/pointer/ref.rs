@@ -3,9 +3,12 @@ use core::fmt;
 use core::hash::Hash;
 use core::marker::PhantomData;
 use core::mem::size_of;
+use core::ptr;
 
+use crate::buf::Buf;
 use crate::endian::{Big, ByteOrder, Little, Native};
 use crate::error::{Error, ErrorKind, IntoRepr};
+use crate::layout::PointerLayout;
 use crate::mem::MaybeUninit;
 use crate::pointer::Coerce;
 use crate::pointer::{DefaultSize, Pointee, Size};
@@ -20,6 +23,10 @@ use crate::ZeroCopy;
 ///   reference is a wide pointer. This is used when encoding types such as
 ///   `[T]` or `str` to include additional data necessary to handle the type.
 ///
+/// References are compared, ordered and hashed by their logical offset and
+/// metadata rather than by their stored bytes, so references to the same
+/// location are equal regardless of their byte order `E`.
+///
 /// [`Buf`]: crate::buf::Buf
 /// [`offset()`]: Ref::offset
 /// [`metadata()`]: Ref::metadata
@@ -42,7 +49,7 @@ use crate::ZeroCopy;
 /// ```
 #[derive(ZeroCopy)]
 #[repr(C)]
-#[zero_copy(crate, swap_bytes_self)]
+#[zero_copy(crate, swap_bytes_self, pointer)]
 pub struct Ref<T, E = Native, O = DefaultSize>
 where
     T: ?Sized + Pointee,
@@ -461,6 +468,59 @@ where
             _marker: PhantomData,
         }
     }
+
+    /// Load the slice from the given buffer into a [`Vec`], converting every
+    /// element from the byte order `U` into [`Native`].
+    ///
+    /// The slice is validated as it's stored in the buffer before it is
+    /// copied. If `U` is [`Native`], this is a plain copy.
+    ///
+    /// Note that the byte order `E` only governs how the reference itself is
+    /// stored, while `U` is the byte order of the elements it points to.
+    ///
+    /// [`Vec`]: alloc::vec::Vec
+    ///
+    /// # Errors
+    ///
+    /// This will not compile through a constant assertion if `T` can't be byte
+    /// swapped as per [`ZeroCopy::CAN_SWAP_BYTES`].
+    ///
+    /// Errors if the slice is out of bounds, misaligned, or doesn't validate
+    /// as `[T]` in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{endian, OwnedBuf};
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let slice = buf.store_slice(&[0x10203040u32.to_be(), 0x50607080u32.to_be()]);
+    ///
+    /// let values = slice.to_vec_swapped::<endian::Big>(&buf)?;
+    /// assert_eq!(values, [0x10203040, 0x50607080]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn to_vec_swapped<U>(self, buf: &Buf) -> Result<alloc::vec::Vec<T>, Error>
+    where
+        U: ByteOrder,
+    {
+        const {
+            assert!(T::CAN_SWAP_BYTES, "Type does not support byte-swapping");
+        }
+
+        let slice = buf.load(self)?;
+        let mut values = alloc::vec::Vec::with_capacity(slice.len());
+
+        for value in slice {
+            // SAFETY: The slice has been validated, and `ZeroCopy` types are
+            // plain data which can be copied by value.
+            let value = unsafe { ptr::read(value) };
+            values.push(U::try_map(value, T::swap_bytes::<U>));
+        }
+
+        Ok(values)
+    }
 }
 
 impl<E, O> Ref<str, E, O>
@@ -568,6 +628,22 @@ where
     pub fn metadata(self) -> T::Stored<O> {
         self.metadata
     }
+
+    /// The layout of what this reference points to.
+    #[doc(hidden)]
+    pub const POINTER_LAYOUT: PointerLayout = T::__POINTER.with_read(Self::read_layout);
+
+    fn read_layout(bytes: &[u8]) -> Option<(usize, usize)> {
+        if bytes.len() < size_of::<Self>() {
+            return None;
+        }
+
+        // SAFETY: The offset and metadata of a reference are plain integers,
+        // so any bit pattern is valid, and we've checked that there is enough
+        // data to read.
+        let this = unsafe { bytes.as_ptr().cast::<Self>().read_unaligned() };
+        Some((this.offset(), T::__len::<E, O>(this.metadata)))
+    }
 }
 
 impl<T, E, O> Ref<T, E, O>
@@ -830,6 +906,139 @@ where
     }
 }
 
+impl<T, E, O> Ref<[MaybeUninit<T>], E, O>
+where
+    T: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    /// Assume that every element of the referenced slice is initialized.
+    ///
+    /// Like [`Ref::<MaybeUninit<T>>::assume_init`], this isn't unsafe since
+    /// the slice is validated when it's loaded. Elements which haven't been
+    /// written are zeroed, which might not be a valid bit pattern for `T`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let slice = buf.store_slice_uninit::<u32>(2);
+    ///
+    /// let uninit = buf.load_uninit_slice_mut(slice);
+    /// uninit[0].write(&1);
+    /// uninit[1].write(&2);
+    ///
+    /// let slice = slice.assume_init();
+    /// assert_eq!(buf.load(slice)?, &[1, 2]);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[inline]
+    pub const fn assume_init(self) -> Ref<[T], E, O> {
+        Ref {
+            offset: self.offset,
+            metadata: self.metadata,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Return the number of elements in the uninitialized slice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let slice = buf.store_slice_uninit::<u32>(2);
+    /// assert_eq!(slice.len(), 2);
+    /// ```
+    #[inline]
+    pub fn len(self) -> usize {
+        self.metadata.as_usize::<E>()
+    }
+
+    /// Test if the uninitialized slice is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    /// let slice = buf.store_slice_uninit::<u32>(0);
+    /// assert!(slice.is_empty());
+    /// ```
+    #[inline]
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, E, O> Ref<T, E, O>
+where
+    T: ?Sized + Pointee,
+    E: ByteOrder,
+    O: Size,
+{
+    /// The offset and metadata of the reference in native byte order.
+    #[inline]
+    fn logical(self) -> (O, T::Stored<O>) {
+        (
+            self.offset.swap_bytes::<E>(),
+            self.metadata.swap_bytes::<E>(),
+        )
+    }
+
+    /// Test if this reference points to the same location as a reference
+    /// with a possibly different byte order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::{endian, Ref};
+    ///
+    /// let a = Ref::<[u32], endian::Little>::with_metadata(8, 2);
+    /// let b = Ref::<[u32], endian::Big>::with_metadata(8, 2);
+    /// assert!(a.eq_ignoring_order(&b));
+    /// assert!(!a.eq_ignoring_order(&Ref::<[u32], endian::Big>::with_metadata(8, 3)));
+    /// ```
+    #[inline]
+    pub fn eq_ignoring_order<U>(&self, other: &Ref<T, U, O>) -> bool
+    where
+        T: Pointee<Stored<O>: PartialEq>,
+        U: ByteOrder,
+        O: PartialEq,
+    {
+        self.logical() == other.logical()
+    }
+
+    /// Order this reference against a reference with a possibly different
+    /// byte order, by their logical offset and metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    ///
+    /// use musli_zerocopy::{endian, Ref};
+    ///
+    /// let a = Ref::<u32, endian::Big>::new(8u32);
+    /// let b = Ref::<u32, endian::Little>::new(256u32);
+    /// assert_eq!(a.cmp_ignoring_order(&b), Ordering::Less);
+    /// ```
+    #[inline]
+    pub fn cmp_ignoring_order<U>(&self, other: &Ref<T, U, O>) -> Ordering
+    where
+        T: Pointee<Stored<O>: Ord>,
+        U: ByteOrder,
+        O: Ord,
+    {
+        self.logical().cmp(&other.logical())
+    }
+}
+
 impl<T, E, O> fmt::Debug for Ref<T, E, O>
 where
     T: ?Sized + Pointee<Stored<O>: fmt::Debug>,
@@ -868,6 +1077,8 @@ where
 {
 }
 
+/// References are compared by their logical offset and metadata. To compare
+/// references with different byte orders, use [`Ref::eq_ignoring_order`].
 impl<T, E, O> PartialEq for Ref<T, E, O>
 where
     T: ?Sized + Pointee<Stored<O>: PartialEq>,
@@ -876,7 +1087,7 @@ where
 {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        self.offset == other.offset && self.metadata == other.metadata
+        self.logical() == other.logical()
     }
 }
 
@@ -888,6 +1099,9 @@ where
 {
 }
 
+/// References are ordered by their logical offset and metadata rather than
+/// their raw bytes. To order references with different byte orders, use
+/// [`Ref::cmp_ignoring_order`].
 impl<T, E, O> PartialOrd for Ref<T, E, O>
 where
     T: ?Sized + Pointee<Stored<O>: PartialOrd>,
@@ -896,12 +1110,7 @@ where
 {
     #[inline]
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match self.offset.partial_cmp(&other.offset) {
-            Some(Ordering::Equal) => {}
-            ord => return ord,
-        }
-
-        self.metadata.partial_cmp(&other.metadata)
+        self.logical().partial_cmp(&other.logical())
     }
 }
 
@@ -913,15 +1122,12 @@ where
 {
     #[inline]
     fn cmp(&self, other: &Self) -> Ordering {
-        match self.offset.cmp(&other.offset) {
-            Ordering::Equal => {}
-            ord => return ord,
-        }
-
-        self.metadata.cmp(&other.metadata)
+        self.logical().cmp(&other.logical())
     }
 }
 
+/// References are hashed by their logical offset and metadata, so that the
+/// hash is consistent with [`Ref::eq_ignoring_order`].
 impl<T, E, O> Hash for Ref<T, E, O>
 where
     T: ?Sized + Pointee<Stored<O>: Hash>,
@@ -930,7 +1136,6 @@ where
 {
     #[inline]
     fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
-        self.offset.hash(state);
-        self.metadata.hash(state);
+        self.logical().hash(state);
     }
 }
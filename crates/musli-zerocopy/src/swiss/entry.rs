@@ -1,4 +1,4 @@
-use crate::ZeroCopy;
+use crate::{ValidateTree, ZeroCopy};
 
 /// An entry which is used when constructing a [`Map<K, V>`].
 ///
@@ -11,8 +11,9 @@ use crate::ZeroCopy;
 ///
 /// [`Map<K, V>`]: crate::swiss::Map
 /// [`OwnedBuf`]: crate::buf::OwnedBuf
-#[derive(Debug, ZeroCopy)]
+#[derive(Debug, ZeroCopy, ValidateTree)]
 #[zero_copy(crate, bounds = {K: ZeroCopy, V: ZeroCopy})]
+#[validate_tree(crate)]
 #[repr(C)]
 pub(crate) struct Entry<K, V> {
     /// The first element in the pair.
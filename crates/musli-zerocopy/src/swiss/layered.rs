@@ -0,0 +1,384 @@
+//! A map composed of several swiss maps layered on top of each other.
+//!
+//! This allows a large base map to be updated by storing small delta maps in
+//! separate buffers, rather than rebuilding the whole base map. Each layer is
+//! an ordinary [`MapRef`] stored with [`swiss::store_map`], optionally paired
+//! with a [`SetRef`] of keys which have been deleted by that layer.
+//!
+//! Lookups probe the layers from the newest to the oldest. The first layer
+//! whose map contains the key provides the value, and if a layer instead has
+//! the key among its tombstones the key is considered absent.
+//!
+//! Layers can be compacted into a single map with [`swiss::merge`].
+//!
+//! [`swiss::store_map`]: crate::swiss::store_map
+//! [`swiss::merge`]: crate::swiss::merge
+//!
+//! ## Examples
+//!
+//! ```
+//! use musli_zerocopy::OwnedBuf;
+//! use musli_zerocopy::swiss::{self, Layer, LayeredMap};
+//!
+//! let mut base = OwnedBuf::new();
+//! let base_map = swiss::store_map(&mut base, [(1u32, 10u32), (2, 20), (3, 30)])?;
+//!
+//! let mut delta = OwnedBuf::new();
+//! let delta_map = swiss::store_map(&mut delta, [(2u32, 21u32), (4, 40)])?;
+//! let deleted = swiss::store_set(&mut delta, [3u32])?;
+//!
+//! let layers = [
+//!     Layer::new(&delta, delta_map).with_tombstones(deleted),
+//!     Layer::new(&base, base_map),
+//! ];
+//!
+//! let map = LayeredMap::new(&layers);
+//!
+//! assert_eq!(map.get(&1u32)?, Some(&10));
+//! assert_eq!(map.get(&2u32)?, Some(&21));
+//! assert_eq!(map.get(&3u32)?, None);
+//! assert_eq!(map.get(&4u32)?, Some(&40));
+//!
+//! let mut merged = OwnedBuf::new();
+//! let merged_map = swiss::merge(&map, &mut merged)?;
+//!
+//! assert_eq!(merged_map.len(), 3);
+//! assert_eq!(merged_map.get(&merged, &2u32)?, Some(&21));
+//! assert_eq!(merged_map.get(&merged, &3u32)?, None);
+//! # Ok::<_, musli_zerocopy::Error>(())
+//! ```
+
+use core::borrow::Borrow;
+use core::hash::Hash;
+
+#[cfg(feature = "alloc")]
+use core::ops::ControlFlow;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::buf::{Buf, Visit};
+use crate::endian::{ByteOrder, Native};
+use crate::error::Error;
+use crate::pointer::{DefaultSize, Size};
+use crate::swiss::{MapRef, SetRef};
+use crate::ZeroCopy;
+
+#[cfg(feature = "alloc")]
+use crate::buf::OwnedBuf;
+
+/// A single layer in a [`LayeredMap`].
+///
+/// A layer is a map stored in a buffer, together with an optional set of keys
+/// which the layer deletes from older layers.
+pub struct Layer<'a, K, V, E = Native, O = DefaultSize>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    buf: &'a Buf,
+    map: MapRef<K, V, E, O>,
+    tombstones: Option<SetRef<K, E, O>>,
+}
+
+impl<'a, K, V, E, O> Layer<'a, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    /// Construct a new layer from a map stored in `buf`.
+    #[inline]
+    pub fn new(buf: &'a Buf, map: MapRef<K, V, E, O>) -> Self {
+        Self {
+            buf,
+            map,
+            tombstones: None,
+        }
+    }
+
+    /// Associate a set of deleted keys with the layer.
+    ///
+    /// The set must be stored in the same buffer as the map of the layer.
+    #[inline]
+    pub fn with_tombstones(self, tombstones: SetRef<K, E, O>) -> Self {
+        Self {
+            tombstones: Some(tombstones),
+            ..self
+        }
+    }
+
+    /// Get the buffer the layer is stored in.
+    #[inline]
+    pub fn buf(&self) -> &'a Buf {
+        self.buf
+    }
+
+    /// Get the map of the layer.
+    #[inline]
+    pub fn map(&self) -> &MapRef<K, V, E, O> {
+        &self.map
+    }
+
+    /// Get the set of keys deleted by the layer, if any.
+    #[inline]
+    pub fn tombstones(&self) -> Option<&SetRef<K, E, O>> {
+        self.tombstones.as_ref()
+    }
+
+    /// Test if the layer provides or deletes the given key, in which case
+    /// older layers should not be consulted.
+    #[cfg(feature = "alloc")]
+    fn shadows<Q>(&self, key: &Q) -> Result<bool, Error>
+    where
+        Q: ?Sized + Visit,
+        Q::Target: Eq + Hash,
+        K: Visit,
+        K::Target: Borrow<Q::Target>,
+    {
+        if self.map.contains_key(self.buf, key)? {
+            return Ok(true);
+        }
+
+        match &self.tombstones {
+            Some(tombstones) => tombstones.contains(self.buf, key),
+            None => Ok(false),
+        }
+    }
+}
+
+impl<K, V, E, O> Clone for Layer<'_, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, E, O> Copy for Layer<'_, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+}
+
+/// A map made up of several [`Layer`]s, ordered from newest to oldest.
+///
+/// Since every layer is stored in its own buffer, keys used for lookups must
+/// not be references into a buffer, like `&str` or `&u32` rather than
+/// [`Ref<str>`].
+///
+/// See the [module level documentation][self] for more information.
+///
+/// [`Ref<str>`]: crate::Ref
+pub struct LayeredMap<'a, K, V, E = Native, O = DefaultSize>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    layers: &'a [Layer<'a, K, V, E, O>],
+}
+
+impl<'a, K, V, E, O> LayeredMap<'a, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    /// Construct a new layered map, where `layers` are ordered from newest to
+    /// oldest.
+    #[inline]
+    pub fn new(layers: &'a [Layer<'a, K, V, E, O>]) -> Self {
+        Self { layers }
+    }
+
+    /// Get the layers of the map, ordered from newest to oldest.
+    #[inline]
+    pub fn layers(&self) -> &'a [Layer<'a, K, V, E, O>] {
+        self.layers
+    }
+
+    /// Get a value from the map.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss::{self, Layer, LayeredMap};
+    ///
+    /// let mut base = OwnedBuf::new();
+    /// let base_map = swiss::store_map(&mut base, [(1, 2), (2, 3)])?;
+    ///
+    /// let mut delta = OwnedBuf::new();
+    /// let delta_map = swiss::store_map(&mut delta, [(2, 4)])?;
+    ///
+    /// let layers = [Layer::new(&delta, delta_map), Layer::new(&base, base_map)];
+    /// let map = LayeredMap::new(&layers);
+    ///
+    /// assert_eq!(map.get(&1)?, Some(&2));
+    /// assert_eq!(map.get(&2)?, Some(&4));
+    /// assert_eq!(map.get(&3)?, None);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn get<Q>(&self, key: &Q) -> Result<Option<&'a V>, Error>
+    where
+        Q: ?Sized + Visit,
+        Q::Target: Eq + Hash,
+        K: 'a + Visit,
+        K::Target: Borrow<Q::Target>,
+    {
+        for layer in self.layers {
+            if let Some(value) = layer.map.get(layer.buf, key)? {
+                return Ok(Some(value));
+            }
+
+            if let Some(tombstones) = &layer.tombstones {
+                if tombstones.contains(layer.buf, key)? {
+                    return Ok(None);
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Test if the map contains the given `key`.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss::{self, Layer, LayeredMap};
+    ///
+    /// let mut base = OwnedBuf::new();
+    /// let base_map = swiss::store_map(&mut base, [(1, 2), (2, 3)])?;
+    ///
+    /// let mut delta = OwnedBuf::new();
+    /// let delta_map = swiss::store_map::<i32, i32, _, _, _>(&mut delta, [])?;
+    /// let deleted = swiss::store_set(&mut delta, [1])?;
+    ///
+    /// let layers = [
+    ///     Layer::new(&delta, delta_map).with_tombstones(deleted),
+    ///     Layer::new(&base, base_map),
+    /// ];
+    ///
+    /// let map = LayeredMap::new(&layers);
+    ///
+    /// assert!(!map.contains_key(&1)?);
+    /// assert!(map.contains_key(&2)?);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn contains_key<Q>(&self, key: &Q) -> Result<bool, Error>
+    where
+        Q: ?Sized + Visit,
+        Q::Target: Eq + Hash,
+        K: 'a + Visit,
+        K::Target: Borrow<Q::Target>,
+    {
+        Ok(self.get(key)?.is_some())
+    }
+}
+
+impl<K, V, E, O> Clone for LayeredMap<'_, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<K, V, E, O> Copy for LayeredMap<'_, K, V, E, O>
+where
+    K: ZeroCopy,
+    V: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+}
+
+/// Compact a [`LayeredMap`] into a single map stored in `buf`.
+///
+/// Keys and values are copied into the new map, so they must not be
+/// references into the buffers of the layers.
+///
+/// The output is deterministic. Entries are stored newest layer first and in
+/// storage order within each layer, so merging the same layers always produces
+/// the same map.
+///
+/// ## Examples
+///
+/// ```
+/// use musli_zerocopy::OwnedBuf;
+/// use musli_zerocopy::swiss::{self, Layer, LayeredMap};
+///
+/// let mut base = OwnedBuf::new();
+/// let base_map = swiss::store_map(&mut base, [(1, 2), (2, 3)])?;
+///
+/// let mut delta = OwnedBuf::new();
+/// let delta_map = swiss::store_map(&mut delta, [(3, 4)])?;
+/// let deleted = swiss::store_set(&mut delta, [1])?;
+///
+/// let layers = [
+///     Layer::new(&delta, delta_map).with_tombstones(deleted),
+///     Layer::new(&base, base_map),
+/// ];
+///
+/// let mut buf = OwnedBuf::new();
+/// let map = swiss::merge(&LayeredMap::new(&layers), &mut buf)?;
+/// let map = buf.bind(map)?;
+///
+/// assert_eq!(map.len(), 2);
+/// assert_eq!(map.get(&1)?, None);
+/// assert_eq!(map.get(&2)?, Some(&3));
+/// assert_eq!(map.get(&3)?, Some(&4));
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+pub fn merge<K, V, E, O>(
+    map: &LayeredMap<'_, K, V, E, O>,
+    buf: &mut OwnedBuf<E, O>,
+) -> Result<MapRef<K, V, E, O>, Error>
+where
+    K: Clone + ZeroCopy + Visit<Target = K> + Eq + Hash,
+    V: Clone + ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    let mut entries = Vec::new();
+
+    for (index, layer) in map.layers.iter().enumerate() {
+        let newer = &map.layers[..index];
+
+        // The walk never breaks, so it always runs to completion.
+        let _ = layer.map.for_each_entry(layer.buf, |key, value| {
+            for newer in newer {
+                if newer.shadows(key)? {
+                    return Ok(ControlFlow::Continue(()));
+                }
+            }
+
+            entries.push((key.clone(), value.clone()));
+            Ok::<_, Error>(ControlFlow::<()>::Continue(()))
+        })?;
+    }
+
+    crate::swiss::store_map(buf, entries)
+}
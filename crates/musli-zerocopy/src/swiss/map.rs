@@ -10,22 +10,42 @@
 //! * [`MapRef<K, V>`] which is the *pointer* of the map. This is what you store
 //!   in [`ZeroCopy`] types and is what is returned by [`swiss::store_map`].
 //!
+//! Values can also be looked up by the [`Bucket`] they are stored in, as
+//! returned by [`Map::retain_indices`].
+//!
 //! [`swiss::store_map`]: crate::swiss::store_map
 
 use core::borrow::Borrow;
 use core::convert::identity as likely;
 use core::hash::{Hash, Hasher};
 use core::mem::size_of;
+use core::ops::ControlFlow;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-use crate::buf::{Bindable, Buf, Visit};
+use crate::buf::{Bindable, Buf, TreeValidator, ValidateTree, Visit};
 use crate::endian::{ByteOrder, Native};
 use crate::error::{Error, ErrorKind};
 use crate::pointer::{DefaultSize, Ref, Size};
 use crate::sip::SipHasher13;
-use crate::swiss::raw::{h2, probe_seq, Group};
+use crate::swiss::raw::{h2, is_full, probe_seq, Group};
 use crate::swiss::Entry;
 use crate::{Endian, ZeroCopy};
 
+/// The bucket a value is stored in, as returned by [`Map::retain_indices`] and
+/// [`MapRef::retain_indices`].
+///
+/// A bucket can only be used to look up a value in the map it was returned
+/// from, and stays valid for as long as the buffer holding that map is left
+/// unchanged. Which bucket a value ends up in depends on the hashing and the
+/// layout of the table, so it is not guaranteed to be the same for maps storing
+/// the same entries, nor across versions of this crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Bucket {
+    index: usize,
+}
+
 /// A map bound to a [`Buf`] through [`Buf::bind`] for convenience.
 ///
 /// ## Examples
@@ -172,6 +192,118 @@ where
         Ok(entry.is_some())
     }
 
+    /// Visit every value in the map without hashing or comparing any keys.
+    ///
+    /// Buckets are walked linearly in storage order and empty buckets are
+    /// skipped, which is much faster than looking up each value by key when
+    /// every value has to be inspected. The walk stops early if `f` returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    ///
+    /// use musli_zerocopy::{Error, OwnedBuf};
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2), (2, 3), (3, 4)])?;
+    /// let map = buf.bind(map)?;
+    ///
+    /// let mut sum = 0;
+    ///
+    /// map.for_each_value(|value| {
+    ///     sum += *value;
+    ///     Ok::<_, Error>(ControlFlow::<()>::Continue(()))
+    /// })?;
+    ///
+    /// assert_eq!(sum, 9);
+    ///
+    /// let found = map.for_each_value(|value| {
+    ///     Ok::<_, Error>(if *value > 2 { ControlFlow::Break(*value) } else { ControlFlow::Continue(()) })
+    /// })?;
+    ///
+    /// assert!(found.is_break());
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn for_each_value<B, U>(
+        &self,
+        mut f: impl FnMut(&V) -> Result<ControlFlow<B>, U>,
+    ) -> Result<ControlFlow<B>, U>
+    where
+        U: From<Error>,
+    {
+        self.table.for_each_full(|_, entry| f(&entry.value))
+    }
+
+    /// Collect the [`Bucket`] of every value matching the given predicate,
+    /// without hashing or comparing any keys.
+    ///
+    /// The returned buckets are in storage order and can be used with
+    /// [`Map::get_value_at`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2), (2, 3), (3, 4)])?;
+    /// let map = buf.bind(map)?;
+    ///
+    /// let buckets = map.retain_indices(|value| *value % 2 == 0)?;
+    /// assert_eq!(buckets.len(), 2);
+    ///
+    /// for bucket in buckets {
+    ///     assert!(matches!(map.get_value_at(bucket)?, Some(2 | 4)));
+    /// }
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn retain_indices(&self, mut pred: impl FnMut(&V) -> bool) -> Result<Vec<Bucket>, Error> {
+        let mut buckets = Vec::new();
+
+        // The predicate never breaks, so the walk always runs to completion.
+        let _ = self.table.for_each_full(|index, entry| {
+            if pred(&entry.value) {
+                buckets.push(Bucket { index });
+            }
+
+            Ok::<_, Error>(ControlFlow::<()>::Continue(()))
+        })?;
+
+        Ok(buckets)
+    }
+
+    /// Get the value stored in the given [`Bucket`], as returned by
+    /// [`Map::retain_indices`].
+    ///
+    /// Returns `None` if the bucket is empty, which can only happen if it was
+    /// returned from a different map.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2)])?;
+    /// let map = buf.bind(map)?;
+    ///
+    /// let buckets = map.retain_indices(|_| true)?;
+    /// assert_eq!(map.get_value_at(buckets[0])?, Some(&2));
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn get_value_at(&self, bucket: Bucket) -> Result<Option<&V>, Error> {
+        Ok(self.table.get_full(bucket.index)?.map(|entry| &entry.value))
+    }
+
     fn hash<H>(&self, value: &H) -> u64
     where
         H: ?Sized + Hash,
@@ -264,6 +396,41 @@ where
         }
     }
 
+    /// Construct a map which doesn't refer to any storage.
+    ///
+    /// Looking up values in this map is an error, callers must check
+    /// [`MapRef::is_empty`] first.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn empty() -> Self {
+        Self {
+            key: Endian::new(0),
+            table: RawTableRef::new(Ref::with_metadata(0, 0), Ref::with_metadata(0, 0), 0, 0),
+        }
+    }
+
+    /// Convert the map to use a different byte order and size.
+    ///
+    /// This is only valid if the keys and values of the map don't depend on
+    /// the byte order.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn try_convert<U, P>(self) -> Result<MapRef<K, V, U, P>, Error>
+    where
+        U: ByteOrder,
+        P: Size,
+    {
+        let table = &self.table;
+
+        Ok(MapRef {
+            key: Endian::new(self.key.to_ne()),
+            table: RawTableRef::new(
+                Ref::try_with_metadata(table.ctrl.offset(), table.ctrl.len())?,
+                Ref::try_with_metadata(table.entries.offset(), table.entries.len())?,
+                table.bucket_mask.to_ne(),
+                table.len.to_ne(),
+            ),
+        })
+    }
+
     /// Get a value from the map.
     ///
     /// ## Examples
@@ -374,6 +541,142 @@ where
         Ok(entry.is_some())
     }
 
+    /// Visit every value in the map without hashing or comparing any keys.
+    ///
+    /// Buckets are walked linearly in storage order and empty buckets are
+    /// skipped. Each visited entry is validated as it is loaded, including its
+    /// key, so unlike [`bind()`] this doesn't require the whole map to be
+    /// validated up front. The walk stops early if `f` returns
+    /// [`ControlFlow::Break`].
+    ///
+    /// [`bind()`]: crate::buf::Buf::bind
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use core::ops::ControlFlow;
+    ///
+    /// use musli_zerocopy::{Error, OwnedBuf};
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2), (2, 3), (3, 4)])?;
+    ///
+    /// let mut sum = 0;
+    ///
+    /// map.for_each_value(&buf, |value| {
+    ///     sum += *value;
+    ///     Ok::<_, Error>(ControlFlow::<()>::Continue(()))
+    /// })?;
+    ///
+    /// assert_eq!(sum, 9);
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn for_each_value<'a, B, U>(
+        &self,
+        buf: &'a Buf,
+        mut f: impl FnMut(&'a V) -> Result<ControlFlow<B>, U>,
+    ) -> Result<ControlFlow<B>, U>
+    where
+        K: 'a,
+        V: 'a,
+        U: From<Error>,
+    {
+        self.table.for_each_full(buf, |_, entry| f(&entry.value))
+    }
+
+    /// Visit every key and value in the map in storage order.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn for_each_entry<'a, B, U>(
+        &self,
+        buf: &'a Buf,
+        mut f: impl FnMut(&'a K, &'a V) -> Result<ControlFlow<B>, U>,
+    ) -> Result<ControlFlow<B>, U>
+    where
+        K: 'a,
+        V: 'a,
+        U: From<Error>,
+    {
+        self.table
+            .for_each_full(buf, |_, entry| f(&entry.key, &entry.value))
+    }
+
+    /// Collect the [`Bucket`] of every value matching the given predicate,
+    /// without hashing or comparing any keys.
+    ///
+    /// The returned buckets are in storage order and can be used with
+    /// [`MapRef::get_value_at`].
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2), (2, 3), (3, 4)])?;
+    ///
+    /// let buckets = map.retain_indices(&buf, |value| *value % 2 == 0)?;
+    /// assert_eq!(buckets.len(), 2);
+    ///
+    /// for bucket in buckets {
+    ///     assert!(matches!(map.get_value_at(&buf, bucket)?, Some(2 | 4)));
+    /// }
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn retain_indices(
+        &self,
+        buf: &Buf,
+        mut pred: impl FnMut(&V) -> bool,
+    ) -> Result<Vec<Bucket>, Error> {
+        let mut buckets = Vec::new();
+
+        // The predicate never breaks, so the walk always runs to completion.
+        let _ = self.table.for_each_full(buf, |index, entry| {
+            if pred(&entry.value) {
+                buckets.push(Bucket { index });
+            }
+
+            Ok::<_, Error>(ControlFlow::<()>::Continue(()))
+        })?;
+
+        Ok(buckets)
+    }
+
+    /// Get the value stored in the given [`Bucket`], as returned by
+    /// [`MapRef::retain_indices`].
+    ///
+    /// Returns `None` if the bucket is empty, which can only happen if it was
+    /// returned from a different map.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli_zerocopy::OwnedBuf;
+    /// use musli_zerocopy::swiss;
+    ///
+    /// let mut buf = OwnedBuf::new();
+    ///
+    /// let map = swiss::store_map(&mut buf, [(1, 2)])?;
+    ///
+    /// let buckets = map.retain_indices(&buf, |_| true)?;
+    /// assert_eq!(map.get_value_at(&buf, buckets[0])?, Some(&2));
+    /// # Ok::<_, musli_zerocopy::Error>(())
+    /// ```
+    pub fn get_value_at<'a>(&self, buf: &'a Buf, bucket: Bucket) -> Result<Option<&'a V>, Error>
+    where
+        K: 'a,
+        V: 'a,
+    {
+        Ok(self
+            .table
+            .get_full(buf, bucket.index)?
+            .map(|entry| &entry.value))
+    }
+
     #[inline]
     fn hash<H>(&self, value: &H) -> u64
     where
@@ -385,6 +688,19 @@ where
     }
 }
 
+unsafe impl<K, V, E, O> ValidateTree for MapRef<K, V, E, O>
+where
+    K: ZeroCopy + ValidateTree,
+    V: ZeroCopy + ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        self.table.validate_tree(cx)
+    }
+}
+
 impl<K, V, E, O> Clone for MapRef<K, V, E, O>
 where
     K: ZeroCopy,
@@ -432,6 +748,35 @@ impl<'a, T> RawTable<'a, T> {
         })
     }
 
+    /// Visit every occupied bucket in the table in storage order.
+    pub(crate) fn for_each_full<B, U>(
+        &self,
+        mut f: impl FnMut(usize, &'a T) -> Result<ControlFlow<B>, U>,
+    ) -> Result<ControlFlow<B>, U>
+    where
+        U: From<Error>,
+    {
+        for (index, &ctrl) in self.ctrl.iter().take(self.entries.len()).enumerate() {
+            if !is_full(ctrl) {
+                continue;
+            }
+
+            if let ControlFlow::Break(b) = f(index, self.entry(index)?)? {
+                return Ok(ControlFlow::Break(b));
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Get the entry at the given bucket index, if it is occupied.
+    pub(crate) fn get_full(&self, index: usize) -> Result<Option<&'a T>, Error> {
+        match self.ctrl.get(index) {
+            Some(&ctrl) if is_full(ctrl) => Ok(Some(self.entry(index)?)),
+            _ => Ok(None),
+        }
+    }
+
     fn entry(&self, index: usize) -> Result<&'a T, Error> {
         let Some(entry) = self.entries.get(index) else {
             return Err(Error::new(ErrorKind::IndexOutOfBounds {
@@ -550,6 +895,43 @@ where
         })
     }
 
+    /// Visit every occupied bucket in the table in storage order.
+    pub(crate) fn for_each_full<'buf, B, U>(
+        &self,
+        buf: &'buf Buf,
+        mut f: impl FnMut(usize, &'buf T) -> Result<ControlFlow<B>, U>,
+    ) -> Result<ControlFlow<B>, U>
+    where
+        T: 'buf,
+        U: From<Error>,
+    {
+        let ctrl = buf.load(self.ctrl)?;
+
+        for (index, &ctrl) in ctrl.iter().take(self.entries.len()).enumerate() {
+            if !is_full(ctrl) {
+                continue;
+            }
+
+            if let ControlFlow::Break(b) = f(index, self.entry(index, buf)?)? {
+                return Ok(ControlFlow::Break(b));
+            }
+        }
+
+        Ok(ControlFlow::Continue(()))
+    }
+
+    /// Get the entry at the given bucket index, if it is occupied.
+    pub(crate) fn get_full<'buf>(
+        &self,
+        buf: &'buf Buf,
+        index: usize,
+    ) -> Result<Option<&'buf T>, Error> {
+        match buf.load(self.ctrl)?.get(index) {
+            Some(&ctrl) if is_full(ctrl) => Ok(Some(self.entry(index, buf)?)),
+            _ => Ok(None),
+        }
+    }
+
     fn entry<'buf>(&self, index: usize, buf: &'buf Buf) -> Result<&'buf T, Error> {
         let Some(entry) = self.entries.get(index) else {
             return Err(Error::new(ErrorKind::IndexOutOfBounds {
@@ -606,6 +988,27 @@ where
     }
 }
 
+unsafe impl<T, E, O> ValidateTree for RawTableRef<T, E, O>
+where
+    T: ZeroCopy + ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        let ctrl = cx.load(self.ctrl)?;
+        let entries = cx.load(self.entries)?;
+
+        // Only occupied buckets hold values which can be reached.
+        for (&ctrl, entry) in ctrl.iter().zip(entries) {
+            if is_full(ctrl) {
+                entry.validate_tree(cx)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 impl<T, E, O> Clone for RawTableRef<T, E, O>
 where
     T: ZeroCopy,
@@ -35,6 +35,13 @@ pub mod map;
 pub use self::set::{Set, SetRef};
 pub mod set;
 
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::layered::merge;
+#[doc(inline)]
+pub use self::layered::{Layer, LayeredMap};
+pub mod layered;
+
 #[cfg(feature = "alloc")]
 mod constructor;
 
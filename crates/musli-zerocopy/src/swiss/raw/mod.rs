@@ -116,7 +116,6 @@ pub(crate) const EMPTY: u8 = 0b1111_1111;
 
 /// Checks whether a control byte represents a full bucket (top bit is clear).
 #[inline]
-#[cfg(feature = "alloc")]
 pub(crate) fn is_full(ctrl: u8) -> bool {
     ctrl & 0x80 == 0
 }
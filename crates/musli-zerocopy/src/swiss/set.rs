@@ -15,7 +15,7 @@
 use core::borrow::Borrow;
 use core::hash::{Hash, Hasher};
 
-use crate::buf::{Bindable, Buf, Visit};
+use crate::buf::{Bindable, Buf, TreeValidator, ValidateTree, Visit};
 use crate::endian::{ByteOrder, Native};
 use crate::error::Error;
 use crate::pointer::{DefaultSize, Size};
@@ -156,6 +156,37 @@ where
     table: RawTableRef<T, E, O>,
 }
 
+unsafe impl<T, E, O> ValidateTree for SetRef<T, E, O>
+where
+    T: ZeroCopy + ValidateTree,
+    E: ByteOrder,
+    O: Size,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        self.table.validate_tree(cx)
+    }
+}
+
+impl<T, E, O> Clone for SetRef<T, E, O>
+where
+    T: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T, E, O> Copy for SetRef<T, E, O>
+where
+    T: ZeroCopy,
+    E: ByteOrder,
+    O: Size,
+{
+}
+
 impl<T, E, O> SetRef<T, E, O>
 where
     T: ZeroCopy,
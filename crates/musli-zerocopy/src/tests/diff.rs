@@ -0,0 +1,148 @@
+use std::format;
+use std::string::ToString;
+use std::vec::Vec;
+
+use crate::buf::{DifferenceKind, OwnedBuf};
+use crate::pointer::Ref;
+use crate::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Tag {
+    name: Ref<str>,
+    weight: u32,
+}
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Document {
+    title: Ref<str>,
+    tags: Ref<[Tag]>,
+    scores: Ref<[u32]>,
+}
+
+fn store(buf: &mut OwnedBuf, tags: &[(&str, u32)], scores: &[u32]) -> Ref<Document> {
+    let title = buf.store_unsized("report");
+
+    let tags = tags
+        .iter()
+        .map(|&(name, weight)| Tag {
+            name: buf.store_unsized(name),
+            weight,
+        })
+        .collect::<Vec<_>>();
+
+    let tags = buf.store_slice(&tags);
+    let scores = buf.store_slice(scores);
+    buf.store(&Document {
+        title,
+        tags,
+        scores,
+    })
+}
+
+#[test]
+fn test_diff_equal() {
+    let mut a = OwnedBuf::new();
+    let root_a = store(&mut a, &[("red", 1), ("blue", 2)], &[1, 2, 3]);
+
+    // Stored in a different order, but structurally the same.
+    let mut b = OwnedBuf::new();
+    let _ = b.store_unsized("padding");
+    let root_b = store(&mut b, &[("red", 1), ("blue", 2)], &[1, 2, 3]);
+
+    assert_ne!(a.as_slice(), b.as_slice());
+    assert!(crate::diff(&a, root_a, &b, root_b).is_empty());
+}
+
+#[test]
+fn test_diff_nested() {
+    let mut a = OwnedBuf::new();
+    let root_a = store(&mut a, &[("red", 1), ("blue", 2)], &[1, 2, 3]);
+
+    let mut b = OwnedBuf::new();
+    let root_b = store(&mut b, &[("red", 1), ("green", 2)], &[1, 2]);
+
+    let differences = crate::diff(&a, root_a, &b, root_b);
+
+    let paths = differences
+        .iter()
+        .map(|d| (d.path(), d.kind()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        paths,
+        [
+            ("tags[1].name", DifferenceKind::Value),
+            ("scores", DifferenceKind::Length { a: 3, b: 2 }),
+        ]
+    );
+
+    let a_name = a.load(a.load(a.load(root_a).unwrap().tags).unwrap()[1].name);
+    let b_name = b.load(b.load(b.load(root_b).unwrap().tags).unwrap()[1].name);
+    assert_eq!(a_name.unwrap(), "blue");
+    assert_eq!(b_name.unwrap(), "green");
+
+    let doc_a = a.load(root_a).unwrap();
+    let doc_b = b.load(root_b).unwrap();
+    assert_eq!(
+        differences[0].offset_a(),
+        a.load(doc_a.tags).unwrap()[1].name.offset()
+    );
+    assert_eq!(
+        differences[0].offset_b(),
+        b.load(doc_b.tags).unwrap()[1].name.offset()
+    );
+    assert_eq!(differences[1].offset_a(), doc_a.scores.offset());
+    assert_eq!(differences[1].offset_b(), doc_b.scores.offset());
+
+    assert_eq!(
+        differences[1].to_string(),
+        format!(
+            "scores: length mismatch (3 != 2) (at {} and {})",
+            doc_a.scores.offset(),
+            doc_b.scores.offset()
+        )
+    );
+}
+
+#[test]
+fn test_diff_limit() {
+    let mut a = OwnedBuf::new();
+    let root_a = store(&mut a, &[], &[1, 2, 3, 4]);
+
+    let mut b = OwnedBuf::new();
+    let root_b = store(&mut b, &[], &[5, 6, 7, 8]);
+
+    let differences = crate::diff_with_limit(&a, root_a, &b, root_b, 2);
+
+    let paths = differences.iter().map(|d| d.path()).collect::<Vec<_>>();
+    assert_eq!(paths, ["scores[0]", "scores[1]"]);
+}
+
+#[test]
+fn test_diff_corrupt() {
+    let mut a = OwnedBuf::new();
+    let root_a = store(&mut a, &[("red", 1)], &[1]);
+
+    let mut b = OwnedBuf::new();
+    let title = b.store_unsized("report");
+    let tags = Ref::<[Tag]>::with_metadata(1000, 1);
+    let scores = b.store_slice(&[1u32]);
+    let root_b = b.store(&Document {
+        title,
+        tags,
+        scores,
+    });
+
+    let differences = crate::diff(&a, root_a, &b, root_b);
+
+    let paths = differences
+        .iter()
+        .map(|d| (d.path(), d.kind(), d.offset_b()))
+        .collect::<Vec<_>>();
+
+    assert_eq!(paths, [("tags[0]", DifferenceKind::Structure, 1000)]);
+}
@@ -0,0 +1,109 @@
+use std::string::ToString;
+
+use crate::buf::OwnedBuf;
+use crate::pointer::Ref;
+use crate::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Point {
+    x: u16,
+    y: u16,
+}
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Shape {
+    name: Ref<str>,
+    points: Ref<[Point]>,
+    origin: Point,
+}
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Node {
+    value: u32,
+    parent: Ref<Node>,
+}
+
+#[test]
+fn test_dump_nested() {
+    let mut buf = OwnedBuf::new();
+
+    let name = buf.store_unsized("line");
+    let points = buf.store_slice(&[Point { x: 1, y: 2 }, Point { x: 3, y: 4 }]);
+    let shape = buf.store(&Shape {
+        name,
+        points,
+        origin: Point { x: 5, y: 6 },
+    });
+
+    let expected = concat!(
+        "    12  Shape\n",
+        "    12    name: Ref<str> -> 0 (len: 4)\n",
+        "     0      \"line\"\n",
+        "    20    points: Ref<[Point]> -> 4 (len: 2)\n",
+        "     4      [0]: Point\n",
+        "     4        x: u16 = 01 00\n",
+        "     6        y: u16 = 02 00\n",
+        "     8      [1]: Point\n",
+        "     8        x: u16 = 03 00\n",
+        "    10        y: u16 = 04 00\n",
+        "    28    origin: Point\n",
+        "    28      x: u16 = 05 00\n",
+        "    30      y: u16 = 06 00\n",
+    );
+
+    assert_eq!(buf.dump(shape).to_string(), expected);
+}
+
+#[test]
+fn test_dump_cycle() {
+    let mut buf = OwnedBuf::new();
+
+    let node = buf.store_uninit::<Node>();
+    let parent = node.assume_init();
+    buf.load_uninit_mut(node).write(&Node { value: 7, parent });
+
+    let expected = concat!(
+        "     0  Node\n",
+        "     0    value: u32 = 07 00 00 00\n",
+        "     4    parent: Ref<Node> -> 0 (see above)\n",
+    );
+
+    assert_eq!(buf.dump(parent).to_string(), expected);
+}
+
+#[test]
+fn test_dump_invalid() {
+    let mut buf = OwnedBuf::new();
+
+    let name = Ref::<str>::with_metadata(1000, 4);
+    let points = Ref::<[Point]>::with_metadata(1, 1);
+    let shape = buf.store(&Shape {
+        name,
+        points,
+        origin: Point { x: 0, y: 0 },
+    });
+
+    let expected = concat!(
+        "     0  Shape\n",
+        "     0    name: Ref<str> -> 1000 (len: 4)\n",
+        "  1000      <out of bounds>\n",
+        "     8    points: Ref<[Point]> -> 1 (len: 1)\n",
+        "     1      [0]: Point (misaligned)\n",
+        "     1        x: u16 (misaligned) = 03 00\n",
+        "     3        y: u16 (misaligned) = 00 04\n",
+        "    16    origin: Point\n",
+        "    16      x: u16 = 00 00\n",
+        "    18      y: u16 = 00 00\n",
+    );
+
+    assert_eq!(buf.dump(shape).to_string(), expected);
+
+    let expected = "   100  value <out of bounds>\n";
+    assert_eq!(buf.dump(Ref::<u32>::new(100u32)).to_string(), expected);
+}
@@ -0,0 +1,105 @@
+use std::string::ToString;
+use std::vec::Vec;
+
+use crate::pointer::Ref;
+use crate::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Inner {
+    a: u8,
+    b: u32,
+}
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout)]
+struct Outer {
+    first: u16,
+    inner: Inner,
+    name: Ref<str>,
+    pair: [u8; 4],
+}
+
+#[derive(ZeroCopy)]
+#[repr(C, packed)]
+#[zero_copy(crate, layout)]
+struct Packed(u8, u64);
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate, layout, bounds = {T: ZeroCopy})]
+struct Generic<T> {
+    flag: u8,
+    value: T,
+}
+
+#[test]
+fn test_layout_fields() {
+    let layout = Inner::LAYOUT;
+    assert_eq!(layout.name(), "Inner");
+    assert_eq!(layout.size(), 8);
+    assert_eq!(layout.align(), 4);
+
+    let [a, b] = layout.fields() else {
+        panic!("expected two fields");
+    };
+
+    assert_eq!((a.name(), a.ty(), a.offset(), a.size()), ("a", "u8", 0, 1));
+    assert_eq!((b.name(), b.ty(), b.offset(), b.size()), ("b", "u32", 4, 4));
+    assert!(a.layout().is_none());
+
+    let padding = layout
+        .padding()
+        .map(|run| (run.offset(), run.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(padding, [(1, 3)]);
+    assert_eq!(layout.total_padding(), 3);
+}
+
+#[test]
+fn test_layout_nested() {
+    let layout = Outer::LAYOUT;
+    let inner = layout.fields()[1];
+
+    assert_eq!(inner.offset(), 4);
+    assert!(inner.layout().is_some_and(|l| l.name() == "Inner"));
+    assert!(layout.fields()[2].layout().is_none());
+
+    let padding = layout
+        .padding()
+        .map(|run| (run.offset(), run.len()))
+        .collect::<Vec<_>>();
+    assert_eq!(padding, [(2, 2)]);
+    assert_eq!(layout.total_padding(), 5);
+}
+
+#[test]
+fn test_layout_packed_and_generic() {
+    assert_eq!(Packed::LAYOUT.fields()[1].offset(), 1);
+    assert_eq!(Packed::LAYOUT.total_padding(), 0);
+    assert_eq!(Packed::LAYOUT.padding().count(), 0);
+
+    assert_eq!(Generic::<u64>::LAYOUT.size(), 16);
+    assert_eq!(Generic::<u64>::LAYOUT.total_padding(), 7);
+    assert_eq!(Generic::<u8>::LAYOUT.total_padding(), 0);
+}
+
+#[test]
+fn test_layout_display() {
+    let expected = concat!(
+        "Outer (size: 24, align: 4, padding: 5)\n",
+        "offset  size  align  field\n",
+        "     0     2      2  first: u16\n",
+        "     2     2         (padding)\n",
+        "     4     8      4  inner: Inner\n",
+        "     4     1      1    a: u8\n",
+        "     5     3           (padding)\n",
+        "     8     4      4    b: u32\n",
+        "    12     8      4  name: Ref<str>\n",
+        "    20     4      1  pair: [u8; 4]\n",
+    );
+
+    assert_eq!(Outer::LAYOUT.to_string(), expected);
+}
@@ -1,2 +1,13 @@
+mod diff;
+mod dump;
 mod enum_byte_order;
+mod layout;
+mod opt;
+mod owned_buf;
+mod phf;
 mod primitives;
+mod read;
+mod ref_eq;
+mod swiss_layered;
+mod to_vec_swapped;
+mod validate_tree;
@@ -0,0 +1,60 @@
+use anyhow::Result;
+
+use crate::pointer::Ref;
+use crate::{Opt, OwnedBuf, ZeroCopy};
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate)]
+struct Fields {
+    name: Opt<Ref<str>>,
+    number: Opt<u32>,
+    flag: Opt<bool>,
+}
+
+#[test]
+fn test_opt_roundtrip() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+
+    let name = buf.store_unsized("hello");
+
+    let some = buf.store(&Fields {
+        name: Opt::Some(name),
+        number: Opt::Some(42),
+        flag: Opt::Some(true),
+    });
+
+    let none = buf.store(&Fields {
+        name: Opt::None,
+        number: Opt::None,
+        flag: Opt::None,
+    });
+
+    let some = buf.load(some)?;
+    assert_eq!(buf.load(*some.name.as_option().unwrap())?, "hello");
+    assert_eq!(some.number, Opt::Some(42));
+    assert_eq!(some.flag, Opt::Some(true));
+
+    let none = buf.load(none)?;
+    assert!(none.name.is_none());
+    assert!(none.number.is_none());
+    assert!(none.flag.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_opt_invalid() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+
+    let number = buf.store(&Opt::Some(42u32));
+    let flag = buf.store(&Opt::Some(true));
+
+    // Invalid flag byte.
+    buf.as_mut_slice()[number.offset()] = 2;
+    assert!(buf.load(number).is_err());
+
+    // Valid flag byte, but invalid inner value.
+    buf.as_mut_slice()[flag.offset() + 1] = 2;
+    assert!(buf.load(flag).is_err());
+    Ok(())
+}
@@ -0,0 +1,306 @@
+use std::string::ToString;
+
+use anyhow::Result;
+
+use crate::{OwnedBuf, Ref, ZeroCopy};
+
+#[derive(ZeroCopy, Clone, Copy)]
+#[repr(C, align(16))]
+#[zero_copy(crate)]
+struct Aligned {
+    value: u8,
+}
+
+#[test]
+fn test_with_capacity_for() -> Result<()> {
+    let values = [1u8, 2, 3];
+
+    let mut buf = OwnedBuf::with_capacity_for::<u8>(values.len());
+    let capacity = buf.capacity();
+    let slice = buf.store_slice(&values);
+    buf.store(&slice);
+    assert_eq!(buf.capacity(), capacity);
+    assert_eq!(buf.len(), capacity);
+
+    let values = [Aligned { value: 1 }, Aligned { value: 2 }];
+
+    let mut buf = OwnedBuf::with_capacity_for::<Aligned>(values.len());
+    let capacity = buf.capacity();
+    let slice = buf.store_slice(&values);
+    let root = buf.store(&slice);
+    assert_eq!(buf.capacity(), capacity);
+    assert_eq!(buf.len(), capacity);
+
+    let slice = buf.load(*buf.load(root)?)?;
+    assert_eq!(slice[1].value, 2);
+
+    let buf = OwnedBuf::with_capacity_for::<u32>(0);
+    assert_eq!(buf.capacity(), size_of::<crate::Ref<[u32]>>());
+    Ok(())
+}
+
+#[test]
+fn test_transaction_rollback() -> Result<()> {
+    let mut buf = OwnedBuf::with_alignment::<u8>();
+    let first = buf.store(&1u8);
+    let len = buf.len();
+    let requested = buf.requested();
+
+    let result = buf.transaction(|tx| {
+        tx.store(&2u64);
+        assert_eq!(tx.requested(), 8);
+        Err::<(), _>("failed")
+    });
+
+    assert_eq!(result, Err("failed"));
+    assert_eq!(buf.len(), len);
+    assert_eq!(buf.requested(), requested);
+    assert_eq!(*buf.load(first)?, 1);
+
+    let second = buf.transaction(|tx| Ok::<_, ()>(tx.store(&2u8))).unwrap();
+    assert_eq!(*buf.load(second)?, 2);
+    assert_eq!(buf.len(), len + 1);
+    Ok(())
+}
+
+#[test]
+fn test_transaction_panic() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    let first = buf.store_unsized("first");
+    let len = buf.len();
+
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        buf.transaction(|tx| {
+            tx.store_unsized("second");
+            panic!("failed while storing");
+            #[allow(unreachable_code)]
+            Ok::<_, ()>(())
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(buf.len(), len);
+    assert_eq!(buf.load(first)?, "first");
+
+    // Nested transactions roll back independently.
+    let outer = buf.transaction(|tx| {
+        let second = tx.store_unsized("second");
+        let inner = tx.transaction(|tx| {
+            tx.store_unsized("third");
+            Err::<(), _>(())
+        });
+        assert!(inner.is_err());
+        Ok::<_, ()>(second)
+    });
+
+    let second = outer.unwrap();
+    assert_eq!(buf.load(second)?, "second");
+    assert_eq!(buf.len(), len + "second".len());
+    Ok(())
+}
+
+#[test]
+fn test_padding_statistics() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    buf.store(&1u8);
+    buf.store(&Aligned { value: 2 });
+    assert_eq!(buf.padding(), 15);
+    assert_eq!(buf.alignments(), 1);
+
+    let result = buf.transaction(|tx| {
+        tx.store(&3u8);
+        tx.store(&4u64);
+        assert_eq!(tx.padding(), 22);
+        assert_eq!(tx.alignments(), 2);
+        Err::<(), _>("rollback")
+    });
+
+    assert!(result.is_err());
+    assert_eq!(buf.padding(), 15);
+    assert_eq!(buf.alignments(), 1);
+
+    buf.clear();
+    assert_eq!(buf.padding(), 0);
+    assert_eq!(buf.alignments(), 0);
+    assert_eq!(
+        buf.report().to_string(),
+        "0 bytes, 0 bytes of padding from 0 alignments"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_aligned_scope() -> Result<()> {
+    let mut unordered = OwnedBuf::new();
+    let a = unordered.store(&1u8);
+    let b = unordered.store(&Aligned { value: 2 });
+    let c = unordered.store(&3u16);
+    let d = unordered.store(&4u64);
+    let e = unordered.store(&5u8);
+
+    let mut buf = OwnedBuf::new();
+
+    let ((a2, b2, c2, d2, e2), placement) = buf.aligned_scope(|scope| {
+        (
+            scope.store(&1u8),
+            scope.store(&Aligned { value: 2 }),
+            scope.store(&3u16),
+            scope.store(&4u64),
+            scope.store(&5u8),
+        )
+    });
+
+    assert_eq!(placement.len(), 5);
+    assert!(buf.len() < unordered.len());
+    assert!(buf.padding() < unordered.padding());
+    assert_eq!(buf.padding(), 0);
+
+    assert_eq!(buf.load(placement.get(a2))?, unordered.load(a)?);
+    assert_eq!(buf.load(placement.get(b2))?.value, unordered.load(b)?.value);
+    assert_eq!(buf.load(placement.get(c2))?, unordered.load(c)?);
+    assert_eq!(buf.load(placement.get(d2))?, unordered.load(d)?);
+    assert_eq!(buf.load(placement.get(e2))?, unordered.load(e)?);
+
+    // Values of the same alignment keep the order they were stored in.
+    assert!(placement.get(a2).offset() < placement.get(e2).offset());
+    Ok(())
+}
+
+#[test]
+#[should_panic = "belongs to a different scope"]
+fn test_aligned_scope_foreign_slot() {
+    let mut buf = OwnedBuf::new();
+
+    let (slot, _) = buf.aligned_scope(|scope| scope.store(&1u32));
+    let (_, placement) = buf.aligned_scope(|scope| scope.store(&2u32));
+
+    placement.get(slot);
+}
+
+#[test]
+fn test_store_slice_uninit() -> Result<()> {
+    #[derive(ZeroCopy, Clone, Copy)]
+    #[repr(C)]
+    #[zero_copy(crate)]
+    struct Node {
+        value: u32,
+        next: Ref<Node>,
+    }
+
+    let mut buf = OwnedBuf::new();
+    buf.store(&1u8);
+
+    let nodes = buf.store_slice_uninit::<Node>(4);
+    assert_eq!(nodes.offset() % core::mem::align_of::<Node>(), 0);
+    assert_eq!(nodes.len(), 4);
+
+    let start = nodes.offset();
+
+    // Fill the nodes in reverse, each referencing the one after it and the
+    // last one referencing the first.
+    for index in (0..4).rev() {
+        let next = start + ((index + 1) % 4) * core::mem::size_of::<Node>();
+
+        buf.load_uninit_slice_mut(nodes)[index].write(&Node {
+            value: index as u32 * 10,
+            next: Ref::new(next),
+        });
+    }
+
+    let tail = buf.store(&42u32);
+
+    let nodes = nodes.assume_init();
+    let slice = buf.load(nodes)?;
+    assert_eq!(slice.len(), 4);
+
+    let mut node = &slice[0];
+
+    for expected in [10, 20, 30, 0, 10] {
+        node = buf.load(node.next)?;
+        assert_eq!(node.value, expected);
+    }
+
+    assert_eq!(buf.load(tail)?, &42);
+    Ok(())
+}
+
+#[test]
+fn test_store_slice_uninit_empty() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    let empty = buf.store_slice_uninit::<u64>(0);
+
+    assert!(empty.is_empty());
+    assert!(buf.load_uninit_slice_mut(empty).is_empty());
+    assert!(buf.load(empty.assume_init())?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_store_slice_uninit_unwritten() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    let values = buf.store_slice_uninit::<char>(2);
+    buf.load_uninit_slice_mut(values)[0].write(&'a');
+
+    // Unwritten elements are zeroed, which happens to be a valid `char`.
+    assert_eq!(buf.load(values.assume_init())?, &['a', '\0']);
+
+    let values = buf.store_slice_uninit::<core::num::NonZeroU32>(1);
+    assert!(buf.load(values.assume_init()).is_err());
+    Ok(())
+}
+
+#[test]
+#[should_panic = "Length overflow"]
+fn test_load_uninit_slice_mut_out_of_bounds() {
+    let mut other = OwnedBuf::new();
+    let values = other.store_slice_uninit::<u32>(4);
+
+    let mut buf = OwnedBuf::new();
+    buf.store(&1u32);
+    buf.load_uninit_slice_mut(values);
+}
+
+#[test]
+fn test_arena_reuse() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    buf.store(&0u8);
+
+    let mut arena = buf.store_arena::<u32>(4);
+    let after = buf.store(&5u32);
+
+    let a = arena.push(&mut buf, &1).unwrap();
+    let b = arena.push(&mut buf, &2).unwrap();
+    let c = arena.push(&mut buf, &3).unwrap();
+    arena.free(b);
+    arena.free(a);
+
+    assert_eq!(arena.next(), Some(a));
+    assert_eq!(arena.push(&mut buf, &4), Some(a));
+    assert_eq!(arena.push(&mut buf, &6), Some(b));
+    assert_eq!(arena.len(), 3);
+
+    let (slice, nodes) = arena.finish();
+    assert_eq!(nodes, [a, b, c]);
+    assert_eq!(buf.load(slice)?, &[4, 6, 3]);
+    assert_eq!(*buf.load(after)?, 5);
+    Ok(())
+}
+
+#[test]
+#[should_panic = "has not been pushed or is already freed"]
+fn test_arena_double_free() {
+    let mut buf = OwnedBuf::new();
+    let mut arena = buf.store_arena::<u32>(2);
+    let a = arena.push(&mut buf, &1).unwrap();
+    arena.free(a);
+    arena.free(a);
+}
+
+#[test]
+#[should_panic = "does not belong to the arena"]
+fn test_arena_foreign_node() {
+    let mut buf = OwnedBuf::new();
+    let mut arena = buf.store_arena::<u32>(2);
+    let other = buf.store(&1u32);
+    arena.free(other);
+}
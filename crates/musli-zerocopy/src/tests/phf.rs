@@ -0,0 +1,65 @@
+use alloc::format;
+use alloc::vec::Vec;
+
+use anyhow::Result;
+
+use crate::phf::{self, Fnv1a, PhfHasher, Sip13};
+use crate::OwnedBuf;
+
+fn test_map<H>() -> Result<()>
+where
+    H: PhfHasher,
+{
+    for len in [1u64, 10, 100, 1000] {
+        let mut buf = OwnedBuf::new();
+        let entries = (0..len).map(|n| (n, n * 2)).collect::<Vec<_>>();
+        let map = phf::store_map_with_hasher::<H, _, _, _, _>(&mut buf, entries)?;
+        let map = buf.bind(map)?;
+
+        for n in 0..len {
+            assert_eq!(map.get(&n)?, Some(&(n * 2)));
+        }
+
+        assert_eq!(map.get(&len)?, None);
+    }
+
+    Ok(())
+}
+
+fn test_set<H>() -> Result<()>
+where
+    H: PhfHasher,
+{
+    for len in [1usize, 10, 100, 1000] {
+        let mut buf = OwnedBuf::new();
+
+        let strings = (0..len)
+            .map(|n| buf.store_unsized(format!("key{n}").as_str()))
+            .collect::<Vec<_>>();
+
+        let set = phf::store_set_with_hasher::<H, _, _>(&mut buf, strings)?;
+        let set = buf.bind(set)?;
+
+        for n in 0..len {
+            assert!(set.contains(format!("key{n}").as_str())?);
+        }
+
+        assert!(!set.contains("missing")?);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_phf_sip13() -> Result<()> {
+    test_map::<Sip13>()?;
+    test_set::<Sip13>()?;
+    Ok(())
+}
+
+#[test]
+fn test_phf_fnv1a() -> Result<()> {
+    test_map::<Fnv1a>()?;
+    test_set::<Fnv1a>()?;
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use anyhow::Result;
+
+use crate::pointer::Ref;
+use crate::{OwnedBuf, ZeroCopy};
+
+#[test]
+fn read_matches_ref() -> Result<()> {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopy)]
+    #[repr(C)]
+    #[zero_copy(crate)]
+    struct Header {
+        count: u32,
+        offset: u32,
+    }
+
+    let mut buf = OwnedBuf::new();
+    let header = buf.store_uninit::<Header>();
+    let values = buf.store_slice(&[1u64, 2, 3]);
+
+    buf.load_uninit_mut(header).write(&Header {
+        count: 3,
+        offset: values.offset() as u32,
+    });
+
+    let header = *buf.read::<Header>(0)?;
+    assert_eq!(&header, buf.load(Ref::<Header>::zero())?);
+
+    let slice = buf.read_slice::<u64>(header.offset as usize, header.count as usize)?;
+    assert_eq!(slice, &[1, 2, 3]);
+    assert_eq!(slice, buf.load(values)?);
+
+    assert_eq!(buf.read_slice::<u64>(values.offset() + 8, 2)?, &[2, 3]);
+    assert!(buf.read_slice::<u64>(values.offset(), 0)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn read_errors() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    buf.store(&[1u32, 2]);
+
+    assert!(buf.read::<u32>(8).is_err());
+    assert!(buf.read::<u32>(2).is_err());
+    assert!(buf.read::<u32>(usize::MAX).is_err());
+
+    assert!(buf.read_slice::<u32>(4, 2).is_err());
+    assert!(buf.read_slice::<u32>(2, 1).is_err());
+    assert!(buf.read_slice::<u32>(0, usize::MAX).is_err());
+    assert!(buf.read_slice::<u32>(usize::MAX, 1).is_err());
+
+    assert!(buf.read::<bool>(0).is_ok());
+    assert!(buf.read::<bool>(4).is_err());
+    assert!(buf.read_slice::<char>(0, 2).is_ok());
+    assert!(buf.read_slice::<char>(0, 1).is_ok());
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use core::hash::{BuildHasher, Hash};
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+
+use crate::endian::{Big, Little};
+use crate::pointer::Ref;
+
+fn hash<T>(state: &RandomState, value: &T) -> u64
+where
+    T: Hash,
+{
+    state.hash_one(value)
+}
+
+#[test]
+fn ref_eq_across_byte_orders() {
+    let little = Ref::<u32, Little>::new(12u32);
+    let big = Ref::<u32, Big>::new(12u32);
+
+    assert!(little.eq_ignoring_order(&big));
+    assert!(big.eq_ignoring_order(&little));
+    assert!(!little.eq_ignoring_order(&Ref::<u32, Big>::new(16u32)));
+
+    let little = Ref::<[u32], Little>::with_metadata(8u32, 3);
+    let big = Ref::<[u32], Big>::with_metadata(8u32, 3);
+
+    assert!(little.eq_ignoring_order(&big));
+    assert!(!little.eq_ignoring_order(&Ref::<[u32], Big>::with_metadata(8u32, 4)));
+}
+
+#[test]
+fn ref_eq_infers_byte_order() {
+    let r = Ref::<u32>::new(0u32);
+    assert_eq!(r, Ref::new(0u32));
+    assert!(r <= Ref::new(0u32));
+}
+
+#[test]
+fn ref_hash_and_order_by_logical_offset() {
+    let state = RandomState::new();
+
+    let a = Ref::<[u32], Big>::with_metadata(8u32, 3);
+    let b = Ref::<[u32], Big>::with_metadata(256u32, 3);
+
+    assert_eq!(hash(&state, &a), hash(&state, &a.to_le()));
+    assert_eq!(hash(&state, &a), hash(&state, &a.to_ne()));
+
+    // In big-endian, the raw bytes of `256` would order before `8`.
+    assert!(a < b);
+    assert!(a.to_le() < b.to_le());
+    assert!(a.cmp_ignoring_order(&b.to_le()).is_lt());
+
+    let mut interned = HashMap::new();
+    interned.insert(a, "a");
+    interned.insert(b, "b");
+    interned.insert(a.to_le().to_be(), "c");
+
+    assert_eq!(interned.len(), 2);
+    assert_eq!(interned.get(&a), Some(&"c"));
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::vec::Vec;
+
+use anyhow::Result;
+
+use crate::swiss::{self, Layer, LayeredMap};
+use crate::OwnedBuf;
+
+const BASE: u64 = 100_000;
+
+/// A delta applied on top of the layers below it.
+struct Delta {
+    inserts: Vec<(u64, u64)>,
+    deletes: Vec<u64>,
+}
+
+/// Construct a delta which updates every `update`th key, deletes every
+/// `delete`th key and inserts `insert` keys which are not in the base.
+fn delta(round: u64, update: u64, delete: u64, insert: u64) -> Delta {
+    let mut inserts = Vec::new();
+    let mut deletes = Vec::new();
+
+    for key in 0..BASE {
+        if key % delete == round {
+            deletes.push(key);
+        } else if key % update == round {
+            inserts.push((key, key * 10 + round));
+        }
+    }
+
+    for n in 0..insert {
+        let key = BASE + round * 1000 + n;
+        inserts.push((key, key * 10 + round));
+    }
+
+    Delta { inserts, deletes }
+}
+
+fn check(
+    expected: &HashMap<u64, u64>,
+    mut get: impl FnMut(u64) -> Result<Option<u64>>,
+) -> Result<()> {
+    for key in 0..BASE + 4000 {
+        assert_eq!(get(key)?, expected.get(&key).copied(), "key {key}");
+    }
+
+    Ok(())
+}
+
+#[test]
+fn layered_map() -> Result<()> {
+    let mut expected = HashMap::new();
+
+    let mut base = OwnedBuf::new();
+    let entries = (0..BASE).map(|n| (n, n * 10)).collect::<Vec<_>>();
+    expected.extend(entries.iter().copied());
+    let base_map = swiss::store_map(&mut base, entries)?;
+
+    let mut deltas = [
+        delta(1, 97, 89, 100),
+        delta(2, 101, 83, 200),
+        delta(3, 103, 79, 300),
+    ];
+
+    // The second delta deletes keys from the base and the first delta, which
+    // the third delta inserts again.
+    deltas[1].deletes.extend([5, BASE + 1000]);
+    deltas[2].inserts.extend([(5, 42), (BASE + 1000, 43)]);
+
+    let mut bufs = Vec::new();
+
+    for delta in &deltas {
+        for &key in &delta.deletes {
+            expected.remove(&key);
+        }
+
+        expected.extend(delta.inserts.iter().copied());
+
+        let mut buf = OwnedBuf::new();
+        let map = swiss::store_map(&mut buf, delta.inserts.iter().copied())?;
+        let deleted = swiss::store_set(&mut buf, delta.deletes.iter().copied())?;
+        bufs.push((buf, map, deleted));
+    }
+
+    let mut layers = bufs
+        .iter()
+        .rev()
+        .map(|(buf, map, deleted)| Layer::new(buf, *map).with_tombstones(*deleted))
+        .collect::<Vec<_>>();
+
+    layers.push(Layer::new(&base, base_map));
+
+    let map = LayeredMap::new(&layers);
+    check(&expected, |key| Ok(map.get(&key)?.copied()))?;
+
+    let mut merged = OwnedBuf::new();
+    let merged_map = swiss::merge(&map, &mut merged)?;
+    assert_eq!(merged_map.len(), expected.len());
+
+    let merged_map = merged.bind(merged_map)?;
+    check(&expected, |key| Ok(merged_map.get(&key)?.copied()))?;
+
+    // Merging the same layers produces the same output.
+    let mut again = OwnedBuf::new();
+    swiss::merge(&map, &mut again)?;
+    assert_eq!(merged.as_slice(), again.as_slice());
+    Ok(())
+}
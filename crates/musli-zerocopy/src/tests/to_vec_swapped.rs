@@ -0,0 +1,72 @@
+use anyhow::Result;
+
+use crate::endian::{self, Big, Little, Native};
+use crate::{OwnedBuf, ZeroCopy};
+
+#[derive(Debug, Clone, Copy, PartialEq, ZeroCopy)]
+#[repr(C)]
+#[zero_copy(crate)]
+struct Point {
+    x: u32,
+    y: i16,
+    z: u8,
+}
+
+#[test]
+fn swap_primitives() -> Result<()> {
+    let values = [0x10203040u32, 0x50607080, 0, u32::MAX];
+
+    let mut buf = OwnedBuf::new();
+    let big = buf.store_slice(&values.map(u32::to_be));
+    let little = buf.store_slice(&values.map(u32::to_le));
+    let native = buf.store_slice(&values);
+
+    assert_eq!(big.to_vec_swapped::<Big>(&buf)?, values);
+    assert_eq!(little.to_vec_swapped::<Little>(&buf)?, values);
+    assert_eq!(native.to_vec_swapped::<Native>(&buf)?, values);
+    Ok(())
+}
+
+#[test]
+fn swap_bytes_is_noop() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+    let bytes = buf.store_slice(&[1u8, 2, 3, 4]);
+    let empty = buf.store_slice::<u64>(&[]);
+
+    assert_eq!(bytes.to_vec_swapped::<Big>(&buf)?, [1, 2, 3, 4]);
+    assert_eq!(bytes.to_vec_swapped::<Little>(&buf)?, [1, 2, 3, 4]);
+    assert!(empty.to_vec_swapped::<Big>(&buf)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn swap_derived() -> Result<()> {
+    let points = [
+        Point { x: 1, y: -2, z: 3 },
+        Point {
+            x: 0x10203040,
+            y: 0x0102,
+            z: 0xff,
+        },
+    ];
+
+    let mut buf = OwnedBuf::new();
+    let stored = points.map(endian::from_endian::<_, Big>);
+    let big = buf.store_slice(&stored);
+
+    assert_eq!(big.to_vec_swapped::<Big>(&buf)?, points);
+    assert_eq!(
+        big.to_vec_swapped::<Little>(&buf)?,
+        stored.map(endian::from_endian::<_, Little>)
+    );
+    Ok(())
+}
+
+#[test]
+fn validate_before_copy() {
+    let mut buf = OwnedBuf::new();
+    let slice = buf.store_slice(&[2u8, 2]);
+    let slice = crate::Ref::<[bool]>::with_metadata(slice.offset(), slice.len());
+
+    assert!(slice.to_vec_swapped::<Big>(&buf).is_err());
+}
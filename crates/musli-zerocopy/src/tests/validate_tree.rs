@@ -0,0 +1,144 @@
+use anyhow::Result;
+
+use crate::pointer::Ref;
+use crate::{swiss, trie, OwnedBuf, ValidateTree, ZeroCopy};
+
+#[derive(ZeroCopy, ValidateTree)]
+#[repr(u8)]
+#[zero_copy(crate)]
+#[validate_tree(crate)]
+enum Value {
+    Empty,
+    Number(u32),
+    String(Ref<str>),
+    List { values: Ref<[Value]> },
+}
+
+#[test]
+fn test_validate_enum_tree() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+
+    let hello = buf.store_unsized("hello");
+    let values = buf.store_slice(&[Value::Empty, Value::Number(42), Value::String(hello)]);
+    let root = buf.store(&Value::List { values });
+
+    buf.validate_tree(root)?;
+
+    let trusted = buf.trust(root)?;
+
+    let Value::List { values } = trusted.root() else {
+        panic!("expected list");
+    };
+
+    // SAFETY: The values are reachable from the validated root.
+    let values = unsafe { trusted.load(*values) };
+    assert!(matches!(values[1], Value::Number(42)));
+
+    let Value::String(string) = &values[2] else {
+        panic!("expected string");
+    };
+
+    // SAFETY: The string is reachable from the validated root.
+    assert_eq!(unsafe { trusted.load(*string) }, "hello");
+
+    buf.load_mut(hello.coerce::<[u8]>())?[0] = 0xff;
+    assert!(buf.validate_tree(root).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_validate_tree_cycle() -> Result<()> {
+    #[derive(ZeroCopy, ValidateTree)]
+    #[repr(C)]
+    #[zero_copy(crate)]
+    #[validate_tree(crate)]
+    struct Node {
+        next: Ref<Node>,
+    }
+
+    let mut buf = OwnedBuf::new();
+
+    let node = buf.store_uninit::<Node>();
+    let next = node.assume_init();
+    buf.load_uninit_mut(node).write(&Node { next });
+
+    // Shallow loads are fine, but following the references never terminates.
+    assert!(buf.load(next).is_ok());
+    assert!(buf.validate_tree(next).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_validate_graph_cycle() -> Result<()> {
+    #[derive(ZeroCopy, ValidateTree)]
+    #[repr(C)]
+    #[zero_copy(crate)]
+    #[validate_tree(crate)]
+    struct Node {
+        name: Ref<str>,
+        children: Ref<[Ref<Node>]>,
+        parent: Ref<Node>,
+    }
+
+    let mut buf = OwnedBuf::new();
+
+    let root_name = buf.store_unsized("root");
+    let child_name = buf.store_unsized("child");
+
+    let root = buf.store_uninit::<Node>();
+    let child = buf.store_uninit::<Node>();
+
+    let children = buf.store_slice(&[child.assume_init(), child.assume_init()]);
+    let no_children = buf.store_slice(&[]);
+
+    buf.load_uninit_mut(root).write(&Node {
+        name: root_name,
+        children,
+        parent: root.assume_init(),
+    });
+
+    buf.load_uninit_mut(child).write(&Node {
+        name: child_name,
+        children: no_children,
+        parent: root.assume_init(),
+    });
+
+    let root = root.assume_init();
+
+    assert!(buf.validate_tree(root).is_err());
+    buf.validate_graph(root)?;
+
+    // root, its name, the children slice, the child, its name and its empty
+    // children slice.
+    buf.validate_graph_with_limit(root, 6)?;
+    assert!(buf.validate_graph_with_limit(root, 5).is_err());
+
+    // A value which is only reachable through the cycle is still validated.
+    buf.load_mut(child_name.coerce::<[u8]>())?[0] = 0xff;
+    assert!(buf.validate_graph(root).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_validate_containers() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+
+    let a = buf.store_unsized("a");
+    let b = buf.store_unsized("b");
+
+    let map = swiss::store_map(&mut buf, [(1u32, a), (2u32, b)])?;
+    let map = buf.store(&map);
+    buf.validate_tree(map)?;
+
+    let mut builder = trie::Builder::new();
+    builder.insert(&buf, a, a)?;
+    builder.insert(&buf, b, b)?;
+    let trie = builder.build(&mut buf)?;
+    let trie = buf.store(&trie);
+    buf.validate_tree(trie)?;
+
+    buf.load_mut(b.coerce::<[u8]>())?[0] = 0xff;
+    assert!(buf.validate_tree(map).is_err());
+    assert!(buf.validate_tree(trie).is_err());
+    Ok(())
+}
@@ -24,9 +24,10 @@ use core::ptr::NonNull;
 use core::slice;
 use core::str;
 
-use crate::buf::{Buf, Padder, Validator, Visit};
+use crate::buf::{Buf, Padder, TreeValidator, ValidateTree, Validator, Visit};
 use crate::endian::ByteOrder;
 use crate::error::{Error, ErrorKind};
+use crate::layout::{PointerLayout, TypeLayout};
 use crate::pointer::{Pointee, Size};
 
 mod sealed {
@@ -237,6 +238,16 @@ where
     }
 }
 
+unsafe impl<T> ValidateTree for Wrapping<T>
+where
+    T: ValidateTree,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        self.0.validate_tree(cx)
+    }
+}
+
 /// `()` can be ignored as a zero-sized field.
 ///
 /// # Examples
@@ -320,7 +331,9 @@ unsafe impl<T: ?Sized> ZeroSized for PhantomData<T> {}
 /// * Non-zero sized tuples. Since tuples do not have a stable layout.
 /// * `Option<T>` since that is a `#[repr(Rust)]` type, except where [specific
 ///   representation guarantees] are made such as with `Option<NonZero*>` types.
+///   Use [`Opt<T>`] instead.
 ///
+/// [`Opt<T>`]: crate::Opt
 /// [specific representation guarantees]:
 ///     https://doc.rust-lang.org/std/option/index.html#representation
 ///
@@ -420,6 +433,17 @@ pub unsafe trait ZeroCopy: Sized {
     /// Most notably this is `false` for [`char`].
     const CAN_SWAP_BYTES: bool;
 
+    /// The layout of the type, if it derives [`ZeroCopy`] with
+    /// `#[zero_copy(layout)]`.
+    #[doc(hidden)]
+    const __LAYOUT: Option<&'static TypeLayout> = None;
+
+    /// What the type points to, if it's a pointer such as [`Ref`].
+    ///
+    /// [`Ref`]: crate::pointer::Ref
+    #[doc(hidden)]
+    const __POINTER: Option<PointerLayout> = None;
+
     /// Mark padding for the current type.
     ///
     /// The `this` receiver takes the current type as pointer instead of a
@@ -815,6 +839,13 @@ macro_rules! impl_number {
                 Ok(visitor(self))
             }
         }
+
+        unsafe impl ValidateTree for $ty {
+            #[inline]
+            fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
     };
 }
 
@@ -865,6 +896,13 @@ macro_rules! impl_float {
                 Ok(visitor(self))
             }
         }
+
+        unsafe impl ValidateTree for $ty {
+            #[inline]
+            fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
     };
 }
 
@@ -915,6 +953,13 @@ impl Visit for char {
     }
 }
 
+unsafe impl ValidateTree for char {
+    #[inline]
+    fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 unsafe impl ZeroCopy for bool {
     const ANY_BITS: bool = false;
     const PADDED: bool = false;
@@ -954,6 +999,13 @@ impl Visit for bool {
     }
 }
 
+unsafe impl ValidateTree for bool {
+    #[inline]
+    fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
 macro_rules! impl_nonzero_number {
     ($ty:ident, $inner:ty) => {
         #[doc = concat!(" [`ZeroCopy`] implementation for `", stringify!($ty), "`")]
@@ -1031,6 +1083,13 @@ macro_rules! impl_nonzero_number {
             }
         }
 
+        unsafe impl ValidateTree for ::core::num::$ty {
+            #[inline]
+            fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
+
         #[doc = concat!(" [`ZeroCopy`] implementation for `Option<", stringify!($ty), ">`")]
         ///
         /// # Examples
@@ -1099,6 +1158,13 @@ macro_rules! impl_nonzero_number {
                 Ok(visitor(self))
             }
         }
+
+        unsafe impl ValidateTree for Option<::core::num::$ty> {
+            #[inline]
+            fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
     };
 }
 
@@ -1175,6 +1241,13 @@ macro_rules! impl_zst {
                 Ok(visitor(self))
             }
         }
+
+        unsafe impl $(<$($bounds)*>)* ValidateTree for $ty {
+            #[inline]
+            fn validate_tree(&self, _: &mut TreeValidator<'_>) -> Result<(), Error> {
+                Ok(())
+            }
+        }
     };
 }
 
@@ -1241,6 +1314,20 @@ where
     }
 }
 
+unsafe impl<T, const N: usize> ValidateTree for [T; N]
+where
+    T: ValidateTree,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        for value in self {
+            value.validate_tree(cx)?;
+        }
+
+        Ok(())
+    }
+}
+
 impl<T> Visit for [T; 0] {
     type Target = [T; 0];
 
@@ -10,7 +10,7 @@ use crate::pointer::{Coerce, Pointee};
 use crate::slice::{BinarySearch, Slice};
 use crate::{Buf, ByteOrder, Error, OwnedBuf, Ref, Size, ZeroCopy};
 
-use super::{prefix, DefaultFlavor, Flavor, LinksRef, NodeRef, TrieRef};
+use super::{prefix, ChildIndex, DefaultFlavor, Flavor, LinksRef, NodeRef, TrieRef};
 
 /// Store the given collection in a trie.
 ///
@@ -293,6 +293,14 @@ impl<T> Links<T> {
     {
         let values = F::Values::try_from_ref(buf.store_slice(&self.values))?;
 
+        let mut first = Vec::with_capacity(self.children.len());
+
+        for node in &self.children {
+            first.push(buf.load(node.string)?.first().copied());
+        }
+
+        let index = F::Index::store(buf, &first)?;
+
         let mut children = Vec::with_capacity(self.children.len());
 
         for node in self.children {
@@ -300,7 +308,11 @@ impl<T> Links<T> {
         }
 
         let children = F::Children::try_from_ref(buf.store_slice(&children))?;
-        Ok(LinksRef { values, children })
+        Ok(LinksRef {
+            values,
+            children,
+            index,
+        })
     }
 }
 
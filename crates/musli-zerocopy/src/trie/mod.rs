@@ -17,10 +17,15 @@ use core::marker::PhantomData;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
-use crate::endian::Native;
+#[cfg(feature = "alloc")]
+use crate::buf::OwnedBuf;
+use crate::buf::{TreeValidator, ValidateTree};
+use crate::endian::{Endian, Native};
+use crate::error::ErrorKind;
 use crate::lossy_str::LossyStr;
 use crate::slice::{binary_search_by, BinarySearch, Slice};
 use crate::stack::ArrayStack;
+use crate::swiss;
 use crate::{Buf, ByteOrder, DefaultSize, Error, Ref, Size, ZeroCopy};
 
 type StackEntry<'buf, T, F> = (LinksRef<T, F>, usize, &'buf [u8]);
@@ -51,6 +56,9 @@ type StackEntry<'buf, T, F> = (LinksRef<T, F>, usize, &'buf [u8]);
 ///     type Children<T> = Packed<[T], u32, u8>
 ///     where
 ///         T: ZeroCopy;
+///
+///     // Children are looked up through a binary search.
+///     type Index = ();
 /// }
 ///
 /// fn populate<F>(buf: &mut OwnedBuf, mut trie: trie::Builder<u32, F>) -> Result<trie::TrieRef<u32, F>, Error>
@@ -96,8 +104,122 @@ pub trait Flavor {
     type Children<T>: Slice<Item = T>
     where
         T: ZeroCopy;
+
+    /// The index used to look up the children of a node.
+    ///
+    /// This is either `()` to look up children through a binary search, or a
+    /// [`swiss::MapRef`] to look up the children of wide nodes through a
+    /// [SwissTable] like [`HashedFlavor`] does.
+    ///
+    /// [SwissTable]: https://abseil.io/about/design/swisstables
+    type Index: ChildIndex;
+}
+
+mod sealed {
+    use crate::endian::{ByteOrder, Endian};
+    use crate::pointer::Size;
+    use crate::swiss::MapRef;
+
+    pub trait Sealed {}
+
+    impl Sealed for () {}
+
+    impl<E, O> Sealed for MapRef<u8, Endian<u16, E>, E, O>
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+    }
+}
+
+/// An index over the children of a trie node, which is used to look up a
+/// child by the first byte of its string.
+///
+/// See [`Flavor::Index`].
+pub trait ChildIndex: self::sealed::Sealed + Copy + ZeroCopy + ValidateTree {
+    /// Test if the node is indexed. Children of nodes which are not indexed
+    /// are looked up through a binary search.
+    fn is_indexed(&self) -> bool;
+
+    /// Get the position of the child whose string starts with `byte`.
+    fn get(&self, buf: &Buf, byte: u8) -> Result<Option<usize>, Error>;
+
+    /// Store an index over children, given the first byte of each child's
+    /// string.
+    #[cfg(feature = "alloc")]
+    #[doc(hidden)]
+    fn store<E, O>(buf: &mut OwnedBuf<E, O>, first: &[Option<u8>]) -> Result<Self, Error>
+    where
+        E: ByteOrder,
+        O: Size;
+}
+
+impl ChildIndex for () {
+    #[inline]
+    fn is_indexed(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn get(&self, _: &Buf, _: u8) -> Result<Option<usize>, Error> {
+        Ok(None)
+    }
+
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn store<E, O>(_: &mut OwnedBuf<E, O>, _: &[Option<u8>]) -> Result<Self, Error>
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        Ok(())
+    }
 }
 
+impl<U, P> ChildIndex for swiss::MapRef<u8, Endian<u16, U>, U, P>
+where
+    U: ByteOrder,
+    P: Size,
+{
+    #[inline]
+    fn is_indexed(&self) -> bool {
+        !self.is_empty()
+    }
+
+    #[inline]
+    fn get(&self, buf: &Buf, byte: u8) -> Result<Option<usize>, Error> {
+        let value = swiss::MapRef::get(self, buf, &byte)?;
+        Ok(value.map(|index| usize::from(index.to_ne())))
+    }
+
+    #[cfg(feature = "alloc")]
+    fn store<E, O>(buf: &mut OwnedBuf<E, O>, first: &[Option<u8>]) -> Result<Self, Error>
+    where
+        E: ByteOrder,
+        O: Size,
+    {
+        if first.len() < HASHED_MIN_CHILDREN {
+            return Ok(swiss::MapRef::empty());
+        }
+
+        // Children are sorted, so only the first child can have an empty
+        // string. Nodes have at most one child per distinct first byte, so
+        // positions fit in an `u16`.
+        let entries = first
+            .iter()
+            .enumerate()
+            .filter_map(|(index, byte)| Some(((*byte)?, Endian::new(index as u16))))
+            .collect::<Vec<_>>();
+
+        swiss::store_map(buf, entries)?.try_convert()
+    }
+}
+
+/// The minimum number of children a node needs to be indexed with
+/// [`HashedFlavor`].
+#[cfg(feature = "alloc")]
+const HASHED_MIN_CHILDREN: usize = 8;
+
 /// Marker type indicating the default trie [`Flavor`] to use for a given
 /// [`ByteOrder`] and [`Size`].
 pub struct DefaultFlavor<E = Native, O = DefaultSize>(PhantomData<(E, O)>)
@@ -119,6 +241,60 @@ where
         = Ref<[T], E, O>
     where
         T: ZeroCopy;
+    type Index = ();
+}
+
+/// A trie [`Flavor`] which stores the children of wide nodes in a
+/// [SwissTable].
+///
+/// By default children are looked up through a binary search over their
+/// strings. With this flavor, nodes with many children instead look them up by
+/// hashing the first byte of the string, which is faster for wide nodes such
+/// as the root of a trie over a large alphabet. This comes at the cost of
+/// storing an additional table for each wide node.
+///
+/// [SwissTable]: https://abseil.io/about/design/swisstables
+///
+/// # Examples
+///
+/// ```
+/// use musli_zerocopy::{trie, OwnedBuf};
+///
+/// let mut buf = OwnedBuf::new();
+/// let mut trie = trie::Builder::<u32, trie::HashedFlavor>::with_flavor();
+///
+/// for (n, c) in ('a'..='z').enumerate() {
+///     let key = buf.store_unsized(c.to_string().as_str());
+///     trie.insert(&buf, key, n as u32)?;
+/// }
+///
+/// let trie = trie.build(&mut buf)?;
+///
+/// assert_eq!(trie.get(&buf, "a")?, Some(&[0][..]));
+/// assert_eq!(trie.get(&buf, "z")?, Some(&[25][..]));
+/// assert_eq!(trie.get(&buf, "å")?, None);
+/// # Ok::<_, musli_zerocopy::Error>(())
+/// ```
+pub struct HashedFlavor<E = Native, O = DefaultSize>(PhantomData<(E, O)>)
+where
+    E: ByteOrder,
+    O: Size;
+
+impl<E, O> Flavor for HashedFlavor<E, O>
+where
+    E: ByteOrder,
+    O: Size,
+{
+    type String = Ref<[u8], E, O>;
+    type Values<T>
+        = Ref<[T], E, O>
+    where
+        T: ZeroCopy;
+    type Children<T>
+        = Ref<[T], E, O>
+    where
+        T: ZeroCopy;
+    type Index = swiss::MapRef<u8, Endian<u16, E>, E, O>;
 }
 
 /// A stored reference to a trie.
@@ -235,6 +411,34 @@ where
         let mut string = string.as_ref();
 
         loop {
+            if let (true, Some(&first)) = (this.index.is_indexed(), string.first()) {
+                let Some(n) = this.index.get(buf, first)? else {
+                    return Ok(None);
+                };
+
+                let Some(child) = this.children.get(n) else {
+                    return Err(Error::new(ErrorKind::IndexOutOfBounds {
+                        index: n,
+                        len: this.children.len(),
+                    }));
+                };
+
+                let child = buf.load(child)?;
+                let child_string = buf.load(child.string)?;
+
+                let Some(suffix) = string.strip_prefix(child_string) else {
+                    return Ok(None);
+                };
+
+                if suffix.is_empty() {
+                    return Ok(Some(buf.load(child.links.values)?));
+                }
+
+                string = suffix;
+                this = child.links;
+                continue;
+            }
+
             let search =
                 binary_search_by(buf, this.children, |c| Ok(buf.load(c.string)?.cmp(string)))?;
 
@@ -252,14 +456,13 @@ where
                     let child = this.children.get_unchecked(n - 1);
                     let child = buf.load(child)?;
 
-                    // Find common prefix and split nodes if necessary.
-                    let prefix = prefix(buf.load(child.string)?, string);
-
-                    if prefix == 0 {
+                    // The string can only be found in the child if the string
+                    // of the child is a prefix of it.
+                    let Some(suffix) = string.strip_prefix(buf.load(child.string)?) else {
                         return Ok(None);
-                    }
+                    };
 
-                    string = &string[prefix..];
+                    string = suffix;
                     this = child.links;
                 }
             };
@@ -1058,6 +1261,17 @@ where
     }
 }
 
+unsafe impl<T, F> ValidateTree for TrieRef<T, F>
+where
+    T: ZeroCopy + ValidateTree,
+    F: Flavor,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        self.links.validate_tree(cx)
+    }
+}
+
 impl<T, F> Clone for TrieRef<T, F>
 where
     T: ZeroCopy,
@@ -1090,6 +1304,21 @@ where
 {
     values: F::Values<T>,
     children: F::Children<NodeRef<T, F>>,
+    index: F::Index,
+}
+
+unsafe impl<T, F> ValidateTree for LinksRef<T, F>
+where
+    T: ZeroCopy + ValidateTree,
+    F: Flavor,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        cx.follow(self.values)?;
+        cx.follow(self.children)?;
+        self.index.validate_tree(cx)?;
+        Ok(())
+    }
 }
 
 impl<T, F> Clone for LinksRef<T, F>
@@ -1126,6 +1355,18 @@ where
     links: LinksRef<T, F>,
 }
 
+unsafe impl<T, F> ValidateTree for NodeRef<T, F>
+where
+    T: ZeroCopy + ValidateTree,
+    F: Flavor,
+{
+    #[inline]
+    fn validate_tree(&self, cx: &mut TreeValidator<'_>) -> Result<(), Error> {
+        cx.load(self.string)?;
+        self.links.validate_tree(cx)
+    }
+}
+
 /// Calculate the common prefix between two strings.
 fn prefix(a: &[u8], b: &[u8]) -> usize {
     a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count()
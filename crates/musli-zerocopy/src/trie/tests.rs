@@ -1,10 +1,13 @@
+use alloc::format;
+use alloc::string::String;
 use alloc::vec::Vec;
 
 use anyhow::Result;
 
-use crate::{Error, OwnedBuf};
+use crate::endian::Big;
+use crate::{ByteOrder, Error, OwnedBuf, Size};
 
-use super::{store, Builder};
+use super::{store, Builder, DefaultFlavor, Flavor, HashedFlavor};
 
 #[test]
 fn regular_trie() -> Result<(), Error> {
@@ -174,3 +177,80 @@ fn entries() -> Result<()> {
     assert_eq!(values, [("running", 8),]);
     Ok(())
 }
+
+/// Keys which produce wide nodes at several levels, including children with
+/// empty strings.
+fn wide_keys() -> Vec<String> {
+    let mut keys = Vec::new();
+
+    for a in 0..=255u8 {
+        keys.push(format!("{}", a as char));
+
+        for b in (b'a'..=b'z').step_by((a % 5 + 1) as usize) {
+            keys.push(format!("{}{}", a as char, b as char));
+            keys.push(format!("{}{}suffix", a as char, b as char));
+        }
+    }
+
+    keys.push(String::from("work"));
+    keys.push(String::from("working"));
+    keys.push(String::from("workingx"));
+    keys
+}
+
+fn test_wide<F, E, O>(mut buf: OwnedBuf<E, O>) -> Result<()>
+where
+    F: Flavor,
+    E: ByteOrder,
+    O: Size,
+{
+    let keys = wide_keys();
+    let mut trie = Builder::<u32, F>::with_flavor();
+
+    for (n, key) in keys.iter().enumerate() {
+        let string = buf.store_unsized(key.as_str());
+        trie.insert(&buf, string, n as u32)?;
+    }
+
+    let trie = trie.build(&mut buf)?;
+
+    for (n, key) in keys.iter().enumerate() {
+        assert_eq!(trie.get(&buf, key)?, Some(&[n as u32][..]), "{key:?}");
+    }
+
+    for missing in ["", "workx", "wor", "workingxx", "aa_", "\u{1}b", "zzz"] {
+        assert_eq!(trie.get(&buf, missing)?, None, "{missing:?}");
+    }
+
+    let mut values = trie.values(&buf).collect::<Result<Vec<_>, _>>()?;
+    values.sort();
+    assert!(values.into_iter().copied().eq(0..keys.len() as u32));
+
+    let trie = buf.store(&trie);
+    buf.validate_tree(trie)?;
+    Ok(())
+}
+
+#[test]
+fn wide_trie() -> Result<()> {
+    test_wide::<DefaultFlavor, _, _>(OwnedBuf::new())?;
+    test_wide::<HashedFlavor, _, _>(OwnedBuf::new())?;
+    test_wide::<HashedFlavor<Big, u32>, _, _>(
+        OwnedBuf::new().with_byte_order::<Big>().with_size::<u32>(),
+    )?;
+    Ok(())
+}
+
+#[test]
+fn partial_prefix() -> Result<()> {
+    let mut buf = OwnedBuf::new();
+
+    let values = [
+        (buf.store_unsized("working"), 1),
+        (buf.store_unsized("workingx"), 2),
+    ];
+
+    let trie = store(&mut buf, values)?;
+    assert_eq!(trie.get(&buf, "workx")?, None);
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use musli_zerocopy::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(assert_no_padding)]
+struct Sparse {
+    a: u8,
+    b: u32,
+    c: u8,
+}
+
+fn main() {
+}
@@ -0,0 +1,14 @@
+use musli_zerocopy::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(C)]
+#[zero_copy(assert_no_padding)]
+struct Dense {
+    b: u32,
+    a: u8,
+    c: u8,
+    d: [u8; 2],
+}
+
+fn main() {
+}
@@ -0,0 +1,12 @@
+use musli_zerocopy::ZeroCopy;
+
+#[derive(ZeroCopy)]
+#[repr(u8)]
+#[zero_copy(layout)]
+enum Enum {
+    A,
+    B(u32),
+}
+
+fn main() {
+}
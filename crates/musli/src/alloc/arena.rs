@@ -0,0 +1,781 @@
+#[cfg(test)]
+mod tests;
+
+use core::alloc::Layout;
+use core::cell::UnsafeCell;
+use core::cmp;
+use core::fmt;
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of, ManuallyDrop};
+use core::ops::Deref;
+use core::ptr::{self, NonNull};
+use core::slice;
+use core::str;
+
+use rust_alloc::alloc;
+
+use crate::de::{Decode, Decoder, SequenceDecoder, UnsizedVisitor};
+use crate::en::{Encode, Encoder};
+use crate::Context;
+
+use super::{Alloc, AllocError, Allocator, String, Vec};
+
+/// The smallest chunk of memory requested by an [`Arena`].
+const MIN_CHUNK_SIZE: usize = 1024;
+
+/// The minimum alignment of chunks requested by an [`Arena`].
+const CHUNK_ALIGN: usize = 16;
+
+/// A bump allocator which hands out references that live as long as the arena
+/// is borrowed.
+///
+/// When used as the allocator of a context, values such as [`ArenaStr`] and
+/// [`ArenaSlice`] can be decoded. These reference memory in the arena rather
+/// than the input being decoded, so they outlive the input while avoiding a
+/// separate heap allocation for each value.
+///
+/// Since these types can only be decoded when the allocator is an arena, a
+/// container which contains them needs a `decode_bound` which requires the
+/// allocator of its [`Decode`] implementation to be an [`ArenaAllocator`].
+///
+/// Memory is requested from the system allocator in chunks of increasing size
+/// and is only released when the arena is [`reset`] or dropped. Values stored
+/// in the arena are never dropped.
+///
+/// [`reset`]: Arena::reset
+///
+/// ## Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::alloc::{Arena, ArenaAllocator, ArenaStr};
+/// use musli::context;
+///
+/// #[derive(Encode)]
+/// struct Person {
+///     name: String,
+///     tags: Vec<String>,
+/// }
+///
+/// #[derive(Decode)]
+/// #[musli(decode_bound<'de, A> = {A: ArenaAllocator<'a>})]
+/// struct ArenaPerson<'a> {
+///     name: ArenaStr<'a>,
+///     tags: Vec<ArenaStr<'a>>,
+/// }
+///
+/// let arena = Arena::new();
+///
+/// let person = {
+///     // The input buffer can be discarded once decoding is done.
+///     let input = musli::storage::to_vec(&Person {
+///         name: String::from("Aristotle"),
+///         tags: vec![String::from("philosopher")],
+///     })?;
+///
+///     let cx = context::new_in(&arena);
+///     musli::storage::DEFAULT.from_slice_with::<_, ArenaPerson<'_>>(&cx, &input)?
+/// };
+///
+/// assert_eq!(person.name, "Aristotle");
+/// assert_eq!(person.tags, ["philosopher"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub struct Arena {
+    // This must be an unsafe cell, since it's mutably accessed through an
+    // immutable pointer. None of the methods accessing it call out to other
+    // code, so accesses never overlap.
+    internal: UnsafeCell<Internal>,
+}
+
+// SAFETY: The arena owns all of its chunks. Allocations and references into the
+// arena borrow it, so it can't be moved to another thread while any of them
+// are alive.
+unsafe impl Send for Arena {}
+
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Arena>();
+};
+
+impl Arena {
+    /// Construct a new empty arena.
+    ///
+    /// No memory is allocated until the arena is first used.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            internal: UnsafeCell::new(Internal {
+                chunks: rust_alloc::vec::Vec::new(),
+                start: ptr::null_mut(),
+                head: ptr::null_mut(),
+                end: ptr::null_mut(),
+            }),
+        }
+    }
+
+    /// Construct a new arena which can store at least `capacity` bytes before
+    /// it has to request more memory.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli::alloc::Arena;
+    ///
+    /// let arena = Arena::with_capacity(4096);
+    /// assert_eq!(arena.alloc_str("Hello")?, "Hello");
+    /// # Ok::<_, musli::alloc::AllocError>(())
+    /// ```
+    pub fn with_capacity(capacity: usize) -> Self {
+        let mut this = Self::new();
+        this.internal.get_mut().grow(capacity, 1);
+        this
+    }
+
+    /// Copy a string into the arena.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli::alloc::Arena;
+    ///
+    /// let arena = Arena::new();
+    ///
+    /// let a = arena.alloc_str("Hello")?;
+    /// let b = arena.alloc_str("World")?;
+    /// assert_eq!([a, b], ["Hello", "World"]);
+    /// # Ok::<_, musli::alloc::AllocError>(())
+    /// ```
+    pub fn alloc_str(&self, string: &str) -> Result<&str, AllocError> {
+        let bytes = self.alloc_slice_copy(string.as_bytes())?;
+        // SAFETY: The bytes were copied from a valid string.
+        Ok(unsafe { str::from_utf8_unchecked(bytes) })
+    }
+
+    /// Copy a slice of values into the arena.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli::alloc::Arena;
+    ///
+    /// let arena = Arena::new();
+    ///
+    /// let values = arena.alloc_slice_copy(&[1u32, 2, 3])?;
+    /// assert_eq!(values, [1, 2, 3]);
+    /// # Ok::<_, musli::alloc::AllocError>(())
+    /// ```
+    pub fn alloc_slice_copy<T>(&self, values: &[T]) -> Result<&[T], AllocError>
+    where
+        T: Copy,
+    {
+        let mut raw = self.alloc_empty::<T>();
+        raw.resize(0, values.len())?;
+
+        // SAFETY: The allocation has just been resized to fit the values, and
+        // the values are copied in their entirety.
+        unsafe {
+            ptr::copy_nonoverlapping(values.as_ptr(), raw.as_mut_ptr(), values.len());
+            Ok(raw.leak(values.len()))
+        }
+    }
+
+    /// Release all memory in the arena for reuse.
+    ///
+    /// The largest chunk of memory is retained, so an arena which is reset
+    /// between decoding similarly sized values eventually stops allocating.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use musli::alloc::Arena;
+    ///
+    /// let mut arena = Arena::new();
+    ///
+    /// for n in 0..4 {
+    ///     let value = arena.alloc_str(&n.to_string())?;
+    ///     assert_eq!(value, n.to_string());
+    ///     arena.reset();
+    /// }
+    /// # Ok::<_, musli::alloc::AllocError>(())
+    /// ```
+    pub fn reset(&mut self) {
+        self.internal.get_mut().reset();
+    }
+}
+
+impl Default for Arena {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Arena {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Arena").finish_non_exhaustive()
+    }
+}
+
+impl<'a> Allocator for &'a Arena {
+    type Alloc<T> = ArenaAlloc<'a, T>;
+
+    #[inline]
+    fn alloc<T>(self, value: T) -> Result<Self::Alloc<T>, AllocError> {
+        let mut raw = self.alloc_empty::<T>();
+        raw.resize(0, 1)?;
+
+        // SAFETY: The allocation has just been resized to fit one element.
+        unsafe {
+            raw.as_mut_ptr().write(value);
+        }
+
+        Ok(raw)
+    }
+
+    #[inline]
+    fn alloc_empty<T>(self) -> Self::Alloc<T> {
+        ArenaAlloc {
+            arena: self,
+            data: NonNull::dangling(),
+            cap: 0,
+        }
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for &super::Arena {}
+}
+
+/// An allocator which is backed by an [`Arena`].
+///
+/// This is only implemented for `&'a Arena`, and is used to bound the
+/// allocator of a [`Decode`] implementation for types which contain values
+/// stored in the arena, such as `#[musli(decode_bound<'de, A> = {A:
+/// ArenaAllocator<'a>})]`.
+pub trait ArenaAllocator<'a>: Allocator + self::sealed::Sealed {
+    /// Access the underlying arena.
+    #[doc(hidden)]
+    fn arena(self) -> &'a Arena;
+
+    /// Leak the allocation, returning a reference to its first `len` elements.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements of the allocation must be initialized.
+    #[doc(hidden)]
+    unsafe fn leak<T>(alloc: Self::Alloc<T>, len: usize) -> &'a mut [T];
+}
+
+impl<'a> ArenaAllocator<'a> for &'a Arena {
+    #[inline]
+    fn arena(self) -> &'a Arena {
+        self
+    }
+
+    #[inline]
+    unsafe fn leak<T>(alloc: Self::Alloc<T>, len: usize) -> &'a mut [T] {
+        alloc.leak(len)
+    }
+}
+
+/// A chunk of memory owned by an [`Arena`].
+struct Chunk {
+    data: NonNull<u8>,
+    layout: Layout,
+}
+
+struct Internal {
+    /// All chunks allocated by the arena, the last one being the current one.
+    chunks: rust_alloc::vec::Vec<Chunk>,
+    /// The start of the current chunk.
+    start: *mut u8,
+    /// The first free byte in the current chunk.
+    head: *mut u8,
+    /// The end of the current chunk.
+    end: *mut u8,
+}
+
+impl Internal {
+    /// Bump allocate `size` bytes with the given alignment.
+    fn bump(&mut self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        if let Some(ptr) = self.try_bump(size, align) {
+            return Some(ptr);
+        }
+
+        self.grow(size, align)?;
+        self.try_bump(size, align)
+    }
+
+    fn try_bump(&mut self, size: usize, align: usize) -> Option<NonNull<u8>> {
+        if self.head.is_null() {
+            return None;
+        }
+
+        let offset = (self.head as usize).wrapping_neg() & (align - 1);
+        let remaining = self.end as usize - self.head as usize;
+
+        if offset.checked_add(size)? > remaining {
+            return None;
+        }
+
+        // SAFETY: We've checked above that the allocation fits in the current
+        // chunk.
+        unsafe {
+            let ptr = self.head.add(offset);
+            self.head = ptr.add(size);
+            Some(NonNull::new_unchecked(ptr))
+        }
+    }
+
+    /// Allocate a new chunk which can fit `size` bytes with the given
+    /// alignment, and make it the current chunk.
+    fn grow(&mut self, size: usize, align: usize) -> Option<()> {
+        let last = self.chunks.last().map_or(0, |c| c.layout.size());
+
+        let size = cmp::max(last.saturating_mul(2), size.checked_add(align)?);
+        let size = cmp::max(size, MIN_CHUNK_SIZE);
+        let layout = Layout::from_size_align(size, cmp::max(align, CHUNK_ALIGN)).ok()?;
+
+        // SAFETY: The layout has a non-zero size.
+        let data = NonNull::new(unsafe { alloc::alloc(layout) })?;
+        self.chunks.push(Chunk { data, layout });
+
+        self.start = data.as_ptr();
+        self.head = data.as_ptr();
+        // SAFETY: The chunk is `size` bytes large.
+        self.end = unsafe { data.as_ptr().add(size) };
+        Some(())
+    }
+
+    /// Test if the region at `ptr` of `size` bytes is the last allocation in
+    /// the current chunk.
+    fn is_last(&self, ptr: *mut u8, size: usize) -> bool {
+        ptr as usize >= self.start as usize && ptr.wrapping_add(size) == self.head
+    }
+
+    /// Try to resize the last allocation in place.
+    fn try_resize_in_place(&mut self, ptr: *mut u8, old: usize, new: usize) -> bool {
+        if !self.is_last(ptr, old) || new > self.end as usize - ptr as usize {
+            return false;
+        }
+
+        // SAFETY: The region is in the current chunk and we've checked that the
+        // new size fits.
+        self.head = unsafe { ptr.add(new) };
+        true
+    }
+
+    fn reset(&mut self) {
+        let Some(largest) = self.chunks.iter().map(|c| c.layout.size()).max() else {
+            return;
+        };
+
+        let mut retained = None;
+
+        for chunk in self.chunks.drain(..) {
+            if retained.is_none() && chunk.layout.size() == largest {
+                retained = Some(chunk);
+                continue;
+            }
+
+            // SAFETY: The chunk was allocated with this layout.
+            unsafe { alloc::dealloc(chunk.data.as_ptr(), chunk.layout) };
+        }
+
+        if let Some(chunk) = retained {
+            self.start = chunk.data.as_ptr();
+            self.head = chunk.data.as_ptr();
+            // SAFETY: The chunk is this large.
+            self.end = unsafe { chunk.data.as_ptr().add(chunk.layout.size()) };
+            self.chunks.push(chunk);
+        }
+    }
+}
+
+impl Drop for Internal {
+    fn drop(&mut self) {
+        for chunk in self.chunks.drain(..) {
+            // SAFETY: The chunk was allocated with this layout.
+            unsafe { alloc::dealloc(chunk.data.as_ptr(), chunk.layout) };
+        }
+    }
+}
+
+/// A raw allocation in an [`Arena`].
+///
+/// Growing an allocation which was the last one made in the arena happens in
+/// place, otherwise it's copied into a new region. Dropping the last allocation
+/// makes its memory available again, while the memory of any other allocation
+/// is retained until the arena is reset.
+pub struct ArenaAlloc<'a, T> {
+    arena: &'a Arena,
+    data: NonNull<T>,
+    cap: usize,
+}
+
+impl<'a, T> ArenaAlloc<'a, T> {
+    /// Leak the allocation, returning a reference to its first `len` elements
+    /// which lives as long as the arena is borrowed.
+    ///
+    /// Any trailing capacity is released if this was the last allocation.
+    ///
+    /// # Safety
+    ///
+    /// The first `len` elements of the allocation must be initialized.
+    unsafe fn leak(self, len: usize) -> &'a mut [T] {
+        let this = ManuallyDrop::new(self);
+
+        if size_of::<T>() != 0 && this.cap > 0 {
+            let internal = &mut *this.arena.internal.get();
+            let ptr = this.data.as_ptr().cast::<u8>();
+            let size = size_of::<T>();
+            internal.try_resize_in_place(ptr, this.cap * size, len * size);
+        }
+
+        slice::from_raw_parts_mut(this.data.as_ptr(), len)
+    }
+}
+
+impl<T> Alloc<T> for ArenaAlloc<'_, T> {
+    #[inline]
+    fn as_ptr(&self) -> *const T {
+        self.data.as_ptr().cast_const()
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut T {
+        self.data.as_ptr()
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        if size_of::<T>() == 0 {
+            usize::MAX
+        } else {
+            self.cap
+        }
+    }
+
+    fn resize(&mut self, len: usize, additional: usize) -> Result<(), AllocError> {
+        if size_of::<T>() == 0 {
+            return Ok(());
+        }
+
+        let required = len.checked_add(additional).ok_or(AllocError)?;
+
+        if required <= self.cap {
+            return Ok(());
+        }
+
+        let cap = cmp::max(self.cap.saturating_mul(2), required);
+        let cap = cmp::max(cap, 4);
+        let layout = Layout::array::<T>(cap).map_err(|_| AllocError)?;
+
+        // SAFETY: None of the methods on the arena call out to other code, so
+        // this is the only live reference to its internal state.
+        let internal = unsafe { &mut *self.arena.internal.get() };
+        let old = self.data.as_ptr().cast::<u8>();
+
+        if self.cap > 0
+            && internal.try_resize_in_place(old, self.cap * size_of::<T>(), layout.size())
+        {
+            self.cap = cap;
+            return Ok(());
+        }
+
+        let data = internal
+            .bump(layout.size(), align_of::<T>())
+            .ok_or(AllocError)?
+            .cast::<T>();
+
+        // SAFETY: The new region is distinct from the old one and large enough
+        // to hold `len` elements.
+        unsafe {
+            ptr::copy_nonoverlapping(self.data.as_ptr(), data.as_ptr(), len);
+        }
+
+        self.data = data;
+        self.cap = cap;
+        Ok(())
+    }
+
+    #[inline]
+    fn try_merge<B>(&mut self, _: usize, other: B, _: usize) -> Result<(), B>
+    where
+        B: Alloc<T>,
+    {
+        if size_of::<T>() == 0 {
+            return Ok(());
+        }
+
+        Err(other)
+    }
+}
+
+impl<T> Drop for ArenaAlloc<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        if size_of::<T>() == 0 || self.cap == 0 {
+            return;
+        }
+
+        // SAFETY: None of the methods on the arena call out to other code, so
+        // this is the only live reference to its internal state.
+        let internal = unsafe { &mut *self.arena.internal.get() };
+        let ptr = self.data.as_ptr().cast::<u8>();
+        internal.try_resize_in_place(ptr, self.cap * size_of::<T>(), 0);
+    }
+}
+
+/// A string stored in an [`Arena`].
+///
+/// This decodes by copying the string into the arena used as the allocator of
+/// the context, so it can outlive the input it was decoded from. See [`Arena`]
+/// for an example.
+///
+/// A dedicated type is needed since `&'de str` always borrows from the input.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArenaStr<'a>(&'a str);
+
+impl<'a> ArenaStr<'a> {
+    /// Construct a new arena string wrapping the given string.
+    #[inline]
+    pub const fn new(string: &'a str) -> Self {
+        Self(string)
+    }
+
+    /// Get the underlying string.
+    #[inline]
+    pub const fn as_str(&self) -> &'a str {
+        self.0
+    }
+}
+
+impl Deref for ArenaStr<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl AsRef<str> for ArenaStr<'_> {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.0
+    }
+}
+
+impl fmt::Debug for ArenaStr<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl fmt::Display for ArenaStr<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq<str> for ArenaStr<'_> {
+    #[inline]
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for ArenaStr<'_> {
+    #[inline]
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<ArenaStr<'_>> for &str {
+    #[inline]
+    fn eq(&self, other: &ArenaStr<'_>) -> bool {
+        *self == other.0
+    }
+}
+
+impl<M> Encode<M> for ArenaStr<'_> {
+    type Encode = str;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_string(self.0)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self.0
+    }
+}
+
+impl<'de, 'a, M, A> Decode<'de, M, A> for ArenaStr<'a>
+where
+    A: ArenaAllocator<'a>,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct Visitor<'a, A>(PhantomData<(&'a (), A)>);
+
+        impl<'de, 'a, C, A> UnsizedVisitor<'de, C, str> for Visitor<'a, A>
+        where
+            C: Context<Allocator = A>,
+            A: ArenaAllocator<'a>,
+        {
+            type Ok = ArenaStr<'a>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "string")
+            }
+
+            #[inline]
+            fn visit_owned(self, _: C, value: String<A>) -> Result<Self::Ok, C::Error> {
+                let (raw, len) = value.into_bytes().into_raw_parts();
+                // SAFETY: The first `len` bytes of a string are initialized.
+                let bytes = unsafe { A::leak(raw, len) };
+                // SAFETY: The bytes came from a string.
+                Ok(ArenaStr(unsafe { str::from_utf8_unchecked(bytes) }))
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, string: &str) -> Result<Self::Ok, C::Error> {
+                let string = cx.alloc().arena().alloc_str(string).map_err(cx.map())?;
+                Ok(ArenaStr(string))
+            }
+        }
+
+        decoder.decode_string(Visitor(PhantomData))
+    }
+}
+
+/// A slice of values stored in an [`Arena`].
+///
+/// This decodes a sequence into the arena used as the allocator of the
+/// context, so it can outlive the input it was decoded from. Since values
+/// stored in the arena are never dropped, the elements must be [`Copy`].
+///
+/// ## Examples
+///
+/// ```
+/// use musli::alloc::{Arena, ArenaSlice, ArenaStr};
+/// use musli::context;
+///
+/// let input = musli::wire::to_vec(&vec![vec!["a", "b"], vec!["c"]])?;
+///
+/// let arena = Arena::new();
+/// let cx = context::new_in(&arena);
+///
+/// let value: ArenaSlice<'_, ArenaSlice<'_, ArenaStr<'_>>> =
+///     musli::wire::DEFAULT.from_slice_with(&cx, &input)?;
+///
+/// assert_eq!(value.len(), 2);
+/// assert_eq!(value[0].as_slice(), ["a", "b"]);
+/// assert_eq!(value[1].as_slice(), ["c"]);
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct ArenaSlice<'a, T>(&'a [T]);
+
+impl<'a, T> ArenaSlice<'a, T> {
+    /// Construct a new arena slice wrapping the given slice.
+    #[inline]
+    pub const fn new(slice: &'a [T]) -> Self {
+        Self(slice)
+    }
+
+    /// Get the underlying slice.
+    #[inline]
+    pub const fn as_slice(&self) -> &'a [T] {
+        self.0
+    }
+}
+
+impl<T> Deref for ArenaSlice<'_, T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<T> AsRef<[T]> for ArenaSlice<'_, T> {
+    #[inline]
+    fn as_ref(&self) -> &[T] {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for ArenaSlice<'_, T>
+where
+    T: fmt::Debug,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl<M, T> Encode<M> for ArenaSlice<'_, T>
+where
+    T: Encode<M>,
+{
+    type Encode = [T];
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        self.0.encode(encoder)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self.0
+    }
+}
+
+impl<'de, 'a, M, A, T> Decode<'de, M, A> for ArenaSlice<'a, T>
+where
+    A: ArenaAllocator<'a>,
+    T: Copy + Decode<'de, M, A>,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        decoder.decode_sequence(|seq| {
+            let mut values = Vec::new_in(cx.alloc());
+
+            while let Some(value) = seq.try_next()? {
+                values.push(value).map_err(cx.map())?;
+            }
+
+            let (raw, len) = values.into_raw_parts();
+            // SAFETY: The first `len` elements of a vector are initialized.
+            Ok(ArenaSlice(unsafe { A::leak(raw, len) }))
+        })
+    }
+}
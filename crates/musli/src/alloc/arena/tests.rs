@@ -0,0 +1,140 @@
+use std::format;
+use std::string::String as StdString;
+use std::vec;
+use std::vec::Vec as StdVec;
+
+use crate::alloc::{Alloc, Allocator, String, Vec};
+use crate::context;
+
+use super::{Arena, ArenaSlice, ArenaStr};
+
+#[test]
+fn grow_in_place() {
+    let arena = Arena::new();
+
+    let mut a = Vec::<u32, _>::new_in(&arena);
+
+    for n in 0..100 {
+        a.push(n).unwrap();
+    }
+
+    let ptr = a.as_slice().as_ptr();
+    a.reserve(1).unwrap();
+    assert_eq!(a.as_slice().as_ptr(), ptr);
+
+    assert_eq!(a.as_slice(), (0..100).collect::<StdVec<_>>());
+}
+
+#[test]
+fn interleaved() {
+    let arena = Arena::new();
+
+    let mut a = String::new_in(&arena);
+    let mut b = String::new_in(&arena);
+
+    for n in 0..200 {
+        a.push_str(if n % 2 == 0 { "even" } else { "odd" }).unwrap();
+        b.push(char::from(b'a' + (n % 26) as u8)).unwrap();
+    }
+
+    let expected_a = (0..200)
+        .map(|n| if n % 2 == 0 { "even" } else { "odd" })
+        .collect::<StdString>();
+
+    let expected_b = (0..200)
+        .map(|n| char::from(b'a' + (n % 26) as u8))
+        .collect::<StdString>();
+
+    assert_eq!(a.as_str(), expected_a);
+    assert_eq!(b.as_str(), expected_b);
+}
+
+#[test]
+fn release_last() {
+    let arena = Arena::new();
+
+    let first = arena.alloc_str("first").unwrap();
+
+    let ptr = {
+        let mut temp = String::new_in(&arena);
+        temp.push_str("temporary").unwrap();
+        temp.as_str().as_ptr()
+    };
+
+    let second = arena.alloc_str("second").unwrap();
+    assert_eq!(second.as_ptr(), ptr);
+    assert_eq!(first, "first");
+    assert_eq!(second, "second");
+}
+
+#[test]
+fn large_and_aligned() {
+    let arena = Arena::new();
+
+    let small = arena.alloc_slice_copy(&[1u8, 2, 3]).unwrap();
+    let large = arena.alloc_slice_copy(&[7u64; 4096]).unwrap();
+    let aligned = arena.alloc_slice_copy(&[1u128, 2]).unwrap();
+
+    assert_eq!(small, [1, 2, 3]);
+    assert!(large.iter().all(|&n| n == 7));
+    assert_eq!(aligned, [1, 2]);
+    assert_eq!(aligned.as_ptr() as usize % core::mem::align_of::<u128>(), 0);
+}
+
+#[test]
+fn zero_sized() {
+    let arena = Arena::new();
+
+    let mut values = Vec::new_in(&arena);
+
+    for _ in 0..1000 {
+        values.push(()).unwrap();
+    }
+
+    assert_eq!(values.len(), 1000);
+    assert_eq!(arena.alloc_slice_copy(&[(); 10]).unwrap().len(), 10);
+
+    let raw = (&arena).alloc(()).unwrap();
+    assert_eq!(raw.capacity(), usize::MAX);
+}
+
+#[test]
+fn reset() {
+    let mut arena = Arena::with_capacity(16);
+
+    for round in 0..4 {
+        let values = (0..1000)
+            .map(|n| arena.alloc_str(&format!("{round}-{n}")).unwrap())
+            .collect::<StdVec<_>>();
+
+        for (n, value) in values.iter().enumerate() {
+            assert_eq!(*value, format!("{round}-{n}"));
+        }
+
+        arena.reset();
+    }
+}
+
+#[test]
+fn decode_outlives_input() {
+    let arena = Arena::new();
+    let cx = context::new_in(&arena);
+
+    let (strings, nested) = {
+        let input = crate::storage::to_vec(&vec!["a", "bb", "ccc"]).unwrap();
+        let strings: ArenaSlice<'_, ArenaStr<'_>> = crate::storage::DEFAULT
+            .from_slice_with(&cx, &input)
+            .unwrap();
+
+        let input = crate::json::to_vec(&vec![vec!["\u{e4}\\n"], vec![]]).unwrap();
+        let nested: StdVec<ArenaSlice<'_, ArenaStr<'_>>> =
+            crate::json::DEFAULT.from_slice_with(&cx, &input).unwrap();
+
+        (strings, nested)
+    };
+
+    assert_eq!(strings.as_slice(), ["a", "bb", "ccc"]);
+    assert_eq!(nested.len(), 2);
+    assert_eq!(nested[0].as_slice(), ["\u{e4}\\n"]);
+    assert!(nested[1].is_empty());
+}
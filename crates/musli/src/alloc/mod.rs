@@ -5,11 +5,19 @@
 //!   Particularly [`std::alloc::System`].
 //! * The [`Slice`] allocator, which can allocate buffers from a fixed-size
 //!   slice.
+//! * The [`Arena`] allocator, which bump allocates and can be used to decode
+//!   values such as [`ArenaStr`] which outlive the input they were decoded
+//!   from.
 //!
 //! The following types are also provided for convenience:
 //! * [`Vec`] which can be used as a vector of allocations.
 //! * [`String`] which can be used as a safe string container.
 //!
+//! The [`System`] allocator is stateless, so it is [`Send`] and [`Sync`] and
+//! can be copied into as many threads as needed. The [`Slice`] allocator
+//! mutates its internal state as it allocates, so while it can be sent to
+//! another thread each thread should use its own buffer and allocator.
+//!
 //! <br>
 //!
 //! ## Examples
@@ -99,6 +107,12 @@ pub use self::stack::{Slice, SliceAlloc};
 mod array_buffer;
 pub use self::array_buffer::ArrayBuffer;
 
+#[cfg(feature = "alloc")]
+mod arena;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::arena::{Arena, ArenaAlloc, ArenaAllocator, ArenaSlice, ArenaStr};
+
 /// Call the given block `body` with an instance of the [`DefaultAllocator`].
 ///
 /// This is useful if you want to write application which are agnostic to
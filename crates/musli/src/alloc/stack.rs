@@ -31,6 +31,10 @@ const MAX_BYTES: usize = i32::MAX as usize;
 /// is fine for use with the `musli` crate, but might be a limitation for other
 /// use-cases.
 ///
+/// The allocator can be sent to another thread, but it can't be shared between
+/// threads since allocating mutates its internal state. To use it from multiple
+/// threads, give each thread its own buffer and allocator.
+///
 /// ## Examples
 ///
 /// ```
@@ -119,6 +123,16 @@ pub struct Slice<'a> {
     _marker: PhantomData<&'a mut [MaybeUninit<u8>]>,
 }
 
+// SAFETY: The allocator exclusively borrows its buffer and owns all of its
+// state. Allocations borrow the allocator, so it can't be moved to another
+// thread while any of them are alive.
+unsafe impl Send for Slice<'_> {}
+
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    assert_send::<Slice<'static>>();
+};
+
 impl<'a> Slice<'a> {
     /// Construct a new slice allocator.
     ///
@@ -645,7 +659,9 @@ impl Internal {
 
         let ptr = self.free.end.cast::<Header>().wrapping_sub(1);
 
-        if ptr < self.free.start.cast() || ptr >= self.free.end.cast() {
+        // The header must not overlap with the region it describes, which
+        // ends at `end`.
+        if ptr < end.cast() || ptr >= self.free.end.cast() {
             return None;
         }
 
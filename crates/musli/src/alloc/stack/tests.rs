@@ -3,7 +3,7 @@ use std::fmt::{self, Write};
 use std::string::String;
 use std::vec::Vec as StdVec;
 
-use crate::alloc::{Allocator, ArrayBuffer, Vec};
+use crate::alloc::{Alloc, Allocator, ArrayBuffer, Vec};
 
 use super::{Header, HeaderId, Range, Slice};
 
@@ -596,3 +596,119 @@ fn limits() {
 
     assert!(a.extend_from_slice(&[0]).is_err());
 }
+
+#[repr(align(16))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Aligned([u8; 16]);
+
+/// Test that allocations are padded to their alignment regardless of how many
+/// bytes have been allocated before them.
+#[test]
+fn align_padding() {
+    for offset in 0..32 {
+        let mut buf = ArrayBuffer::new();
+        let alloc = Slice::new(&mut buf);
+
+        let mut a = Vec::<u8, _>::new_in(&alloc);
+        assert!(a.extend_from_slice(&[1; 32][..offset]).is_ok());
+
+        let b = alloc.alloc(Aligned([2; 16])).unwrap();
+        assert_eq!(b.as_ptr().align_offset(16), 0, "offset {offset}");
+
+        let bytes = unsafe { (*alloc.internal.get()).bytes() };
+        assert!(
+            bytes >= offset + 16 && bytes < offset + 32,
+            "offset {offset}"
+        );
+
+        assert_eq!(unsafe { b.as_ptr().read() }, Aligned([2; 16]));
+        assert_eq!(a.as_slice(), &[1; 32][..offset]);
+
+        drop(b);
+        assert!(a.extend_from_slice(&[3; 8]).is_ok());
+        assert_eq!(&a.as_slice()[offset..], &[3; 8]);
+    }
+}
+
+/// Test alignment when the buffer itself starts at an unaligned address, in
+/// which case there's no prior region to absorb the padding.
+#[test]
+fn align_padding_unaligned_buffer() {
+    let mut buf = ArrayBuffer::new();
+
+    for start in 0..16 {
+        let alloc = Slice::new(&mut buf[start..]);
+
+        let a = alloc.alloc(Aligned([1; 16])).unwrap();
+        let b = alloc.alloc(7u8).unwrap();
+        let c = alloc.alloc(Aligned([2; 16])).unwrap();
+
+        assert_eq!(a.as_ptr().align_offset(16), 0, "start {start}");
+        assert_eq!(c.as_ptr().align_offset(16), 0, "start {start}");
+
+        unsafe {
+            assert_eq!(a.as_ptr().read(), Aligned([1; 16]));
+            assert_eq!(b.as_ptr().read(), 7);
+            assert_eq!(c.as_ptr().read(), Aligned([2; 16]));
+        }
+    }
+}
+
+/// Test that growing a vector of aligned values keeps them aligned as the
+/// allocation is moved around.
+#[test]
+fn align_grow() {
+    let mut buf = ArrayBuffer::new();
+    let alloc = Slice::new(&mut buf);
+
+    let mut a = Vec::<Aligned, _>::new_in(&alloc);
+    let mut b = Vec::<u8, _>::new_in(&alloc);
+
+    for n in 0..16u8 {
+        assert!(a.push(Aligned([n; 16])).is_ok());
+        assert!(b.push(n).is_ok());
+        assert_eq!(a.as_slice().as_ptr().align_offset(16), 0);
+    }
+
+    for (n, value) in a.as_slice().iter().enumerate() {
+        assert_eq!(*value, Aligned([n as u8; 16]));
+    }
+
+    assert_eq!(b.len(), 16);
+}
+
+/// Test that an allocator which is running out of space fails to allocate
+/// rather than overlapping an allocation with its own header.
+#[test]
+fn exhaustion() {
+    let mut buf = ArrayBuffer::<256>::with_size();
+
+    for len in (0..=256).step_by(8) {
+        let alloc = Slice::new(&mut buf[..len]);
+        let mut values = StdVec::new();
+
+        for n in 0u8.. {
+            let Ok(value) = alloc.alloc(Aligned([n; 16])) else {
+                break;
+            };
+
+            values.push((n, value));
+        }
+
+        for (n, value) in &values {
+            assert_eq!(unsafe { value.as_ptr().read() }, Aligned([*n; 16]));
+        }
+
+        let i = unsafe { &*alloc.internal.get() };
+        assert!(i.free.start <= i.free.end, "length {len}");
+
+        // Freeing the last allocation makes room for another one.
+        let last = values.pop();
+
+        if let Some((n, value)) = last {
+            drop(value);
+            let value = alloc.alloc(Aligned([n; 16])).unwrap();
+            assert_eq!(unsafe { value.as_ptr().read() }, Aligned([n; 16]));
+        }
+    }
+}
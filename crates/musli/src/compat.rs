@@ -3,12 +3,47 @@
 //! Note that most types in this module have an attribute equivalent:
 //! * [`Bytes`] corresponds to using `#[musli(bytes)]` on a field.
 //! * [`Packed`] corresponds to using `#[musli(packed)]` on a field.
+//!
+//! [`MapPairs`] and [`as_map`] allow encoding key-value pairs as a map without
+//! collecting them into a map type first, and [`Shared`] allows shared
+//! pointers to be deduplicated.
+//!
+//! The [`pairs`] module does the opposite, and can be used with
+//! `#[musli(with = musli::compat::pairs)]` to encode a map as a sequence of
+//! key-value pairs.
+//!
+//! The [`utf16`] module can be used with `#[musli(with =
+//! musli::compat::utf16)]` to encode a string as a sequence of UTF-16 code
+//! units.
+//!
+//! [`Fixed`] is a fixed-point decimal number which is encoded as a decimal
+//! string in human-readable formats and as an integer otherwise.
+
+pub mod pairs;
+pub mod utf16;
+
+pub use self::fixed::{Fixed, ParseFixedError};
+mod fixed;
 
+use core::iter::Map;
+use core::slice;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use rust_alloc::sync::Arc;
+
+#[cfg(feature = "alloc")]
+use crate::de::VariantDecoder;
 use crate::de::{Decode, DecodeBytes, DecodePacked, Decoder};
-use crate::en::{Encode, EncodeBytes, EncodePacked, Encoder};
-use crate::hint::SequenceHint;
+#[cfg(feature = "alloc")]
+use crate::en::VariantEncoder;
+use crate::en::{Encode, EncodeBytes, EncodePacked, Encoder, MapEncoder};
+use crate::hint::{MapHint, SequenceHint};
 use crate::mode::{Binary, Text};
 use crate::Allocator;
+#[cfg(feature = "alloc")]
+use crate::Context;
 
 /// Ensures that the given value `T` is encoded as a sequence.
 ///
@@ -62,6 +97,255 @@ where
     }
 }
 
+/// Encodes an iterator over key-value pairs as a map.
+///
+/// This produces the same output as encoding a map type such as [`BTreeMap`]
+/// containing the same entries in the same order, without having to collect
+/// them first. The iterator is cloned every time the value is encoded.
+///
+/// See [`as_map`] for a convenience constructor over a slice of pairs.
+///
+/// [`BTreeMap`]: alloc::collections::BTreeMap
+///
+/// # Examples
+///
+/// ```
+/// use musli::compat::MapPairs;
+///
+/// let keys = ["a", "b"];
+/// let values = [1u32, 2u32];
+///
+/// let pairs = MapPairs::new(keys.iter().zip(values.iter()));
+/// let json = musli::json::to_string(&pairs)?;
+/// assert_eq!(json, r#"{"a":1,"b":2}"#);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct MapPairs<I>(pub I);
+
+impl<I> MapPairs<I> {
+    /// Construct a new map pairs wrapper.
+    #[inline]
+    pub const fn new(iter: I) -> Self {
+        Self(iter)
+    }
+}
+
+impl<M, I, K, V> Encode<M> for MapPairs<I>
+where
+    I: Clone + ExactSizeIterator<Item = (K, V)>,
+    K: Encode<M>,
+    V: Encode<M>,
+{
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let hint = MapHint::with_size(self.0.len());
+
+        encoder.encode_map_fn(&hint, |map| {
+            for (k, v) in self.0.clone() {
+                map.insert_entry(k, v)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+/// The iterator used by [`as_map`].
+pub type MapSlice<'a, K, V> = Map<slice::Iter<'a, (K, V)>, fn(&'a (K, V)) -> (&'a K, &'a V)>;
+
+/// Encode a slice of key-value pairs as a map.
+///
+/// # Examples
+///
+/// ```
+/// use musli::compat;
+///
+/// let pairs = [("a", 1u32), ("b", 2u32)];
+/// let json = musli::json::to_string(&compat::as_map(&pairs))?;
+/// assert_eq!(json, r#"{"a":1,"b":2}"#);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[inline]
+pub fn as_map<K, V>(pairs: &[(K, V)]) -> MapPairs<MapSlice<'_, K, V>> {
+    MapPairs(pairs.iter().map(|(k, v)| (k, v)))
+}
+
+/// Deduplicate a shared pointer such as [`Arc<T>`] or [`Rc<T>`].
+///
+/// By default this is encoded exactly like the wrapped pointer, which encodes
+/// the value it points to. If the context has sharing enabled through
+/// [`DefaultContext::with_sharing`], each shared allocation is only encoded
+/// once, and later occurrences refer back to it. When decoding with sharing
+/// enabled, these are resolved into clones of the same pointer.
+///
+/// [`Arc<T>`]: rust_alloc::sync::Arc
+/// [`Rc<T>`]: rust_alloc::rc::Rc
+/// [`DefaultContext::with_sharing`]: crate::context::DefaultContext::with_sharing
+///
+/// # Examples
+///
+/// ```
+/// use std::rc::Rc;
+///
+/// use musli::{Decode, Encode};
+/// use musli::compat::Shared;
+/// use musli::context;
+/// use musli::wire::{Encoding, Error};
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Node {
+///     name: String,
+/// }
+///
+/// #[derive(Encode, Decode)]
+/// struct Graph {
+///     first: Shared<Rc<Node>>,
+///     second: Shared<Rc<Node>>,
+/// }
+///
+/// let node = Rc::new(Node { name: String::from("Aristotle") });
+///
+/// let graph = Graph {
+///     first: Shared(node.clone()),
+///     second: Shared(node),
+/// };
+///
+/// let cx = context::new().with_error().with_sharing();
+///
+/// let mut data = Vec::new();
+/// ENCODING.encode_with(&cx, &mut data, &graph)?;
+///
+/// let graph: Graph = ENCODING.from_slice_with(&cx, &data[..])?;
+/// assert!(Rc::ptr_eq(&graph.first.0, &graph.second.0));
+/// assert_eq!(graph.first.0.name, "Aristotle");
+/// Ok::<_, Error>(())
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Shared<P>(pub P);
+
+impl<P> Shared<P> {
+    /// Construct a new shared pointer wrapper.
+    #[inline]
+    pub const fn new(value: P) -> Self {
+        Self(value)
+    }
+}
+
+/// Tag used when a shared allocation is encoded for the first time.
+#[cfg(feature = "alloc")]
+const SHARED_VALUE: u8 = 0;
+/// Tag used when referring back to a shared allocation.
+#[cfg(feature = "alloc")]
+const SHARED_REFERENCE: u8 = 1;
+
+#[cfg(feature = "alloc")]
+macro_rules! shared {
+    ($($ty:ident),* $(,)?) => {
+        $(
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+            impl<M, T> Encode<M> for Shared<$ty<T>>
+            where
+                T: 'static + Encode<M>,
+            {
+                type Encode = Self;
+
+                #[inline]
+                fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+                where
+                    E: Encoder<Mode = M>,
+                {
+                    let cx = encoder.cx();
+
+                    if !cx.is_sharing() {
+                        return encoder.encode(&*self.0);
+                    }
+
+                    let ptr = $ty::as_ptr(&self.0).cast::<()>();
+
+                    encoder.encode_variant_fn(|variant| {
+                        if let Some(index) = cx.shared_index(ptr) {
+                            variant.encode_tag()?.encode(SHARED_REFERENCE)?;
+                            variant.encode_data()?.encode(index)?;
+                        } else {
+                            variant.encode_tag()?.encode(SHARED_VALUE)?;
+                            variant.encode_data()?.encode(&*self.0)?;
+                            cx.insert_shared_ptr(ptr);
+                        }
+
+                        Ok(())
+                    })
+                }
+
+                #[inline]
+                fn as_encode(&self) -> &Self::Encode {
+                    self
+                }
+            }
+
+            #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+            impl<'de, M, A, T> Decode<'de, M, A> for Shared<$ty<T>>
+            where
+                A: Allocator,
+                T: 'static + Decode<'de, M, A>,
+            {
+                #[inline]
+                fn decode<D>(decoder: D) -> Result<Self, D::Error>
+                where
+                    D: Decoder<'de, Mode = M, Allocator = A>,
+                {
+                    let cx = decoder.cx();
+
+                    if !cx.is_sharing() {
+                        return Ok(Self($ty::new(decoder.decode()?)));
+                    }
+
+                    decoder.decode_variant(|variant| {
+                        let tag = variant.decode_tag()?.decode::<u8>()?;
+
+                        match tag {
+                            SHARED_VALUE => {
+                                let value = $ty::new(variant.decode_value()?.decode::<T>()?);
+                                cx.insert_shared_value(value.clone());
+                                Ok(Self(value))
+                            }
+                            SHARED_REFERENCE => {
+                                let index = variant.decode_value()?.decode::<usize>()?;
+
+                                let Some(value) = cx.shared_value::<$ty<T>>(index) else {
+                                    return Err(cx.message(format_args!(
+                                        "No shared value of the expected type at index {index}"
+                                    )));
+                                };
+
+                                Ok(Self(value))
+                            }
+                            tag => Err(cx.message(format_args!("Unsupported shared tag {tag}"))),
+                        }
+                    })
+                }
+            }
+        )*
+    };
+}
+
+#[cfg(feature = "alloc")]
+shared!(Arc, Rc);
+
 /// Treat `T` as if its bytes.
 ///
 /// This corresponds to the "Bytes" type in the [data model of Müsli] and is the
@@ -0,0 +1,285 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::de::{Decode, Decoder};
+use crate::en::{Encode, Encoder};
+use crate::{Allocator, Context};
+
+/// The longest string a [`Fixed`] is formatted as, which is a sign, 19 digits
+/// and a decimal point.
+const MAX_LEN: usize = 21;
+
+/// A fixed-point decimal number with `SCALE` fractional digits, backed by an
+/// `i64` holding the number multiplied by `10^SCALE`.
+///
+/// In formats which are [human-readable] such as JSON, this is encoded as a
+/// decimal string with exactly `SCALE` fractional digits. Decoding such a
+/// string rejects numbers with more fractional digits than `SCALE` rather than
+/// rounding them. In every other format it is encoded as its raw integer.
+///
+/// `SCALE` can be at most 18, since `10^19` doesn't fit in an `i64`.
+///
+/// [human-readable]: crate::hint::Capabilities::is_human_readable
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::compat::Fixed;
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Price {
+///     amount: Fixed<2>,
+/// }
+///
+/// let price = Price { amount: Fixed(-1250) };
+///
+/// let json = musli::json::to_string(&price)?;
+/// assert_eq!(json, r#"{"amount":"-12.50"}"#);
+/// assert_eq!(musli::json::from_str::<Price>(&json)?, price);
+///
+/// assert!(musli::json::from_str::<Price>(r#"{"amount":"12.505"}"#).is_err());
+///
+/// let bytes = musli::storage::to_vec(&price)?;
+/// assert_eq!(musli::storage::from_slice::<Price>(&bytes)?, price);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Fixed<const SCALE: u32>(pub i64);
+
+impl<const SCALE: u32> Fixed<SCALE> {
+    /// The multiplier between the number and its raw integer.
+    const FACTOR: u64 = {
+        assert!(SCALE <= 18, "Fixed supports a scale of at most 18");
+        10u64.pow(SCALE)
+    };
+
+    /// Construct a fixed-point number from its raw integer, which is the
+    /// number multiplied by `10^SCALE`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::compat::Fixed;
+    ///
+    /// let value = Fixed::<3>::from_raw(1500);
+    /// assert_eq!(value.to_string(), "1.500");
+    /// ```
+    #[inline]
+    pub const fn from_raw(raw: i64) -> Self {
+        Self(raw)
+    }
+
+    /// Get the raw integer of the fixed-point number, which is the number
+    /// multiplied by `10^SCALE`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::compat::Fixed;
+    ///
+    /// let value: Fixed<3> = "1.5".parse()?;
+    /// assert_eq!(value.into_raw(), 1500);
+    /// # Ok::<_, musli::compat::ParseFixedError>(())
+    /// ```
+    #[inline]
+    pub const fn into_raw(self) -> i64 {
+        self.0
+    }
+
+    /// Format the number into the given buffer.
+    fn format(self, buf: &mut [u8; MAX_LEN]) -> &str {
+        let mut at = MAX_LEN;
+        let n = self.0.unsigned_abs();
+        let mut int = n / Self::FACTOR;
+        let mut frac = n % Self::FACTOR;
+
+        let mut push = |b: u8| {
+            at -= 1;
+            buf[at] = b;
+        };
+
+        if SCALE > 0 {
+            for _ in 0..SCALE {
+                push(b'0' + (frac % 10) as u8);
+                frac /= 10;
+            }
+
+            push(b'.');
+        }
+
+        loop {
+            push(b'0' + (int % 10) as u8);
+            int /= 10;
+
+            if int == 0 {
+                break;
+            }
+        }
+
+        if self.0 < 0 {
+            push(b'-');
+        }
+
+        // SAFETY: Only ASCII characters have been written.
+        unsafe { core::str::from_utf8_unchecked(&buf[at..]) }
+    }
+}
+
+impl<const SCALE: u32> fmt::Display for Fixed<SCALE> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.format(&mut [0; MAX_LEN]))
+    }
+}
+
+impl<const SCALE: u32> FromStr for Fixed<SCALE> {
+    type Err = ParseFixedError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let (int, frac) = match s.split_once('.') {
+            Some((int, frac)) => (int, Some(frac)),
+            None => (s, None),
+        };
+
+        let int = digits(int)?;
+
+        let frac = match frac {
+            Some(frac) => {
+                let Some(rest) = (SCALE as usize).checked_sub(frac.len()) else {
+                    let kind = if frac.bytes().all(|b| b.is_ascii_digit()) {
+                        Kind::Precision(SCALE)
+                    } else {
+                        Kind::Invalid
+                    };
+
+                    return Err(ParseFixedError::new(kind));
+                };
+
+                digits(frac)? * 10u64.pow(rest as u32)
+            }
+            None => 0,
+        };
+
+        let Some(n) = int
+            .checked_mul(Self::FACTOR)
+            .and_then(|n| n.checked_add(frac))
+        else {
+            return Err(ParseFixedError::new(Kind::Overflow));
+        };
+
+        let n = if negative {
+            0i64.checked_sub_unsigned(n)
+        } else {
+            i64::try_from(n).ok()
+        };
+
+        let Some(n) = n else {
+            return Err(ParseFixedError::new(Kind::Overflow));
+        };
+
+        Ok(Self(n))
+    }
+}
+
+/// Parse a non-empty sequence of decimal digits.
+fn digits(s: &str) -> Result<u64, ParseFixedError> {
+    if s.is_empty() {
+        return Err(ParseFixedError::new(Kind::Invalid));
+    }
+
+    let mut n = 0u64;
+
+    for b in s.bytes() {
+        if !b.is_ascii_digit() {
+            return Err(ParseFixedError::new(Kind::Invalid));
+        }
+
+        n = n
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(u64::from(b - b'0')))
+            .ok_or(ParseFixedError::new(Kind::Overflow))?;
+    }
+
+    Ok(n)
+}
+
+impl<M, const SCALE: u32> Encode<M> for Fixed<SCALE> {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        if encoder.capabilities().is_human_readable() {
+            encoder.encode_string(self.format(&mut [0; MAX_LEN]))
+        } else {
+            encoder.encode_i64(self.0)
+        }
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A, const SCALE: u32> Decode<'de, M, A> for Fixed<SCALE>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        if decoder.capabilities().is_human_readable() {
+            let cx = decoder.cx();
+            decoder.decode_unsized(|string: &str| string.parse().map_err(cx.map()))
+        } else {
+            Ok(Self(decoder.decode_i64()?))
+        }
+    }
+}
+
+/// Error raised when parsing a [`Fixed`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseFixedError {
+    kind: Kind,
+}
+
+impl ParseFixedError {
+    #[inline]
+    const fn new(kind: Kind) -> Self {
+        Self { kind }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Kind {
+    Invalid,
+    Precision(u32),
+    Overflow,
+}
+
+impl fmt::Display for ParseFixedError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            Kind::Invalid => write!(f, "Invalid decimal number"),
+            Kind::Precision(scale) => {
+                write!(f, "Decimal number has more than {scale} fractional digits")
+            }
+            Kind::Overflow => write!(f, "Decimal number is out of range"),
+        }
+    }
+}
+
+impl core::error::Error for ParseFixedError {}
@@ -0,0 +1,88 @@
+//! Encode a map as a sequence of key-value pairs.
+//!
+//! This is intended to be used with `#[musli(with = musli::compat::pairs)]` on
+//! a map field. Each entry is encoded as a two-element sequence of its key and
+//! value, so in JSON a map is written as `[[key, value], ...]` rather than as an
+//! object. This preserves key types which can't be represented as object keys,
+//! and the order in which the entries are iterated over.
+//!
+//! JSON already falls back to pairs for maps whose keys can't be object keys,
+//! so this is mainly useful to force the layout for maps with string or
+//! numeric keys.
+//!
+//! Decoding collects the pairs into the map using [`Extend`], so any map type
+//! which implements [`Default`] and [`Extend<(K, V)>`], and which iterates over
+//! `(K, V)` when consumed, can be used. If a key appears more than once the
+//! last entry wins, as with a regular map.
+//!
+//! To only change the representation in text formats such as JSON and keep
+//! binary formats unchanged, the attribute can be limited to the
+//! [`Text`][crate::mode::Text] mode using `#[musli(mode = Text, with =
+//! musli::compat::pairs)]`.
+//!
+//! # Examples
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! use musli::{Decode, Encode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Scores {
+//!     #[musli(mode = Text, with = musli::compat::pairs)]
+//!     by_id: BTreeMap<u32, String>,
+//! }
+//!
+//! let scores = Scores {
+//!     by_id: BTreeMap::from([(1, String::from("low")), (10, String::from("high"))]),
+//! };
+//!
+//! let json = musli::json::to_string(&scores)?;
+//! assert_eq!(json, r#"{"by_id":[[1,"low"],[10,"high"]]}"#);
+//! assert_eq!(musli::json::from_str::<Scores>(&json)?, scores);
+//! # Ok::<_, musli::json::Error>(())
+//! ```
+
+use crate::de::{Decode, Decoder, SequenceDecoder};
+use crate::en::{Encode, Encoder, SequenceEncoder};
+use crate::hint::SequenceHint;
+
+/// Encode the given map as a sequence of key-value pairs.
+pub fn encode<'a, E, T, K, V>(value: &'a T, encoder: E) -> Result<E::Ok, E::Error>
+where
+    E: Encoder,
+    &'a T: IntoIterator<Item = (&'a K, &'a V)>,
+    <&'a T as IntoIterator>::IntoIter: ExactSizeIterator,
+    K: 'a + Encode<E::Mode>,
+    V: 'a + Encode<E::Mode>,
+{
+    let iter = value.into_iter();
+    let hint = SequenceHint::with_size(iter.len());
+
+    encoder.encode_sequence_fn(&hint, |seq| {
+        for (k, v) in iter {
+            seq.push((k, v))?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Decode a map from a sequence of key-value pairs.
+pub fn decode<'de, D, T, K, V>(decoder: D) -> Result<T, D::Error>
+where
+    D: Decoder<'de>,
+    T: Default + IntoIterator<Item = (K, V)> + Extend<(K, V)>,
+    K: Decode<'de, D::Mode, D::Allocator>,
+    V: Decode<'de, D::Mode, D::Allocator>,
+{
+    decoder.decode_sequence(|seq| {
+        let mut out = T::default();
+
+        while let Some(pair) = seq.try_next::<(K, V)>()? {
+            out.extend([pair]);
+        }
+
+        Ok(out)
+    })
+}
@@ -0,0 +1,106 @@
+//! Encode a string as a sequence of UTF-16 code units.
+//!
+//! This is intended to be used with `#[musli(with = musli::compat::utf16)]` on
+//! a [`String`] field, to interoperate with systems which natively represent
+//! strings as UTF-16, such as Windows APIs, Java or JavaScript. The string is
+//! encoded as a length-prefixed sequence of `u16` code units rather than as
+//! UTF-8 bytes.
+//!
+//! Decoding validates that every surrogate is part of a pair, and errors if a
+//! lone surrogate is encountered since it can't be represented in a
+//! [`String`].
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Message {
+//!     #[musli(with = musli::compat::utf16)]
+//!     text: String,
+//! }
+//!
+//! let message = Message {
+//!     text: String::from("hi 🎉"),
+//! };
+//!
+//! let json = musli::json::to_string(&message)?;
+//! assert_eq!(json, r#"{"text":[104,105,32,55356,57225]}"#);
+//! assert_eq!(musli::json::from_str::<Message>(&json)?, message);
+//! # Ok::<_, musli::json::Error>(())
+//! ```
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+
+use rust_alloc::string::String;
+
+use crate::de::{Decoder, SequenceDecoder};
+use crate::en::{Encoder, SequenceEncoder};
+use crate::hint::SequenceHint;
+use crate::Context;
+
+/// Encode the given string as a sequence of UTF-16 code units.
+pub fn encode<E, T>(value: &T, encoder: E) -> Result<E::Ok, E::Error>
+where
+    E: Encoder,
+    T: ?Sized + AsRef<str>,
+{
+    let value = value.as_ref();
+    let hint = SequenceHint::with_size(value.encode_utf16().count());
+
+    encoder.encode_sequence_fn(&hint, |seq| {
+        for unit in value.encode_utf16() {
+            seq.push(unit)?;
+        }
+
+        Ok(())
+    })
+}
+
+/// Decode a string from a sequence of UTF-16 code units.
+pub fn decode<'de, D>(decoder: D) -> Result<String, D::Error>
+where
+    D: Decoder<'de>,
+{
+    decoder.decode_sequence(|seq| {
+        let cx = seq.cx();
+        let mut out = String::new();
+        let mut high = None::<u16>;
+
+        while let Some(unit) = seq.try_next::<u16>()? {
+            let c = match (high.take(), unit) {
+                (None, 0xd800..=0xdbff) => {
+                    high = Some(unit);
+                    continue;
+                }
+                (Some(high), 0xdc00..=0xdfff) => {
+                    let c =
+                        0x10000 + ((u32::from(high) - 0xd800) << 10) + (u32::from(unit) - 0xdc00);
+                    char::from_u32(c)
+                }
+                (Some(high), _) => {
+                    return Err(
+                        cx.message(format_args!("Lone surrogate 0x{high:04x} in UTF-16 string"))
+                    );
+                }
+                (None, _) => char::from_u32(u32::from(unit)),
+            };
+
+            let Some(c) = c else {
+                return Err(
+                    cx.message(format_args!("Lone surrogate 0x{unit:04x} in UTF-16 string"))
+                );
+            };
+
+            out.push(c);
+        }
+
+        if let Some(high) = high {
+            return Err(cx.message(format_args!("Lone surrogate 0x{high:04x} in UTF-16 string")));
+        }
+
+        Ok(out)
+    })
+}
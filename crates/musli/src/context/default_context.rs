@@ -1,13 +1,20 @@
 use core::error::Error;
 use core::fmt;
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
 use crate::{Allocator, Context};
 
+#[cfg(feature = "alloc")]
+use super::ErrorMarker;
+#[cfg(feature = "std")]
+use super::Intern;
+#[cfg(feature = "alloc")]
+use super::Share;
 use super::{
-    Capture, ContextError, Emit, ErrorMode, Errors, Ignore, NoTrace, Report, Trace, TraceImpl,
-    TraceMode,
+    Capture, ContextError, Emit, ErrorMode, Errors, Ignore, InternMode, NoIntern, NoShare, NoTrace,
+    Report, ShareMode, Trace, TraceImpl, TraceMode,
 };
 
 /// The default context which uses an allocator to track the location of errors.
@@ -19,9 +26,21 @@ use super::{
 /// The default constructor is only available when the `alloc` feature is
 /// enabled, and will use the [`System`] allocator.
 ///
+/// A context holds the state of one encoding or decoding operation at a time.
+/// It can be sent to other threads, but a context which collects errors
+/// through [`with_trace`] or [`with_capture`] can't be shared between them, and
+/// one which deduplicates through [`with_sharing`] or interns through
+/// [`with_interning`] can't leave the thread it was constructed on. See the
+/// [module-level documentation] for how to use contexts with multiple threads.
+///
 /// [`new`]: super::new
 /// [`new_in`]: super::new_in
-pub struct DefaultContext<A, T, C>
+/// [`with_trace`]: DefaultContext::with_trace
+/// [`with_capture`]: DefaultContext::with_capture
+/// [`with_sharing`]: DefaultContext::with_sharing
+/// [`with_interning`]: DefaultContext::with_interning
+/// [module-level documentation]: super#thread-safety
+pub struct DefaultContext<A, T, C, S = NoShare, I = NoIntern>
 where
     A: Allocator,
     T: TraceMode,
@@ -29,8 +48,23 @@ where
     alloc: A,
     trace: T::Impl<A>,
     capture: C,
+    share: S,
+    intern: I,
+    validating: bool,
+    suspended: AtomicUsize,
+    max_depth: usize,
+    depth: AtomicUsize,
 }
 
+#[cfg(feature = "alloc")]
+const _: () = {
+    const fn assert_send<T: Send>() {}
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<DefaultContext<System, NoTrace, Ignore>>();
+    assert_send::<DefaultContext<System, Trace, Capture<ErrorMarker>>>();
+    assert_send::<DefaultContext<System, Trace, Emit<ErrorMarker>>>();
+};
+
 #[cfg(feature = "alloc")]
 impl DefaultContext<System, NoTrace, Ignore> {
     /// Construct the default context which uses the [`System`] allocator for
@@ -54,6 +88,12 @@ where
             alloc,
             trace,
             capture: Ignore,
+            share: NoShare,
+            intern: NoIntern,
+            validating: false,
+            suspended: AtomicUsize::new(0),
+            max_depth: usize::MAX,
+            depth: AtomicUsize::new(0),
         }
     }
 }
@@ -66,11 +106,13 @@ impl Default for DefaultContext<System, NoTrace, Ignore> {
     }
 }
 
-impl<A, T, C> DefaultContext<A, T, C>
+impl<A, T, C, S, I> DefaultContext<A, T, C, S, I>
 where
     A: Allocator,
     T: TraceMode,
     C: ErrorMode<A>,
+    S: ShareMode,
+    I: InternMode,
 {
     /// Enable tracing through the current allocator `A`.
     ///
@@ -84,13 +126,19 @@ where
     /// [`errors`]: DefaultContext::errors
     /// [`Disabled`]: crate::alloc::Disabled
     #[inline]
-    pub fn with_trace(self) -> DefaultContext<A, Trace, C> {
+    pub fn with_trace(self) -> DefaultContext<A, Trace, C, S, I> {
         let trace = Trace::new_in(self.alloc);
 
         DefaultContext {
             alloc: self.alloc,
             trace,
             capture: self.capture,
+            share: self.share,
+            intern: self.intern,
+            validating: self.validating,
+            suspended: self.suspended,
+            max_depth: self.max_depth,
+            depth: self.depth,
         }
     }
 
@@ -134,7 +182,7 @@ where
     /// Ok::<_, musli::context::ErrorMarker>(())
     /// ```
     #[inline]
-    pub fn with_capture<E>(self) -> DefaultContext<A, T, Capture<E>>
+    pub fn with_capture<E>(self) -> DefaultContext<A, T, Capture<E>, S, I>
     where
         E: ContextError<A>,
     {
@@ -142,6 +190,12 @@ where
             alloc: self.alloc,
             trace: self.trace,
             capture: Capture::new(),
+            share: self.share,
+            intern: self.intern,
+            validating: self.validating,
+            suspended: self.suspended,
+            max_depth: self.max_depth,
+            depth: self.depth,
         }
     }
 
@@ -181,7 +235,7 @@ where
     /// Ok::<_, Error>(())
     /// ```
     #[inline]
-    pub fn with_error<E>(self) -> DefaultContext<A, T, Emit<E>>
+    pub fn with_error<E>(self) -> DefaultContext<A, T, Emit<E>, S, I>
     where
         E: ContextError<A>,
     {
@@ -189,11 +243,218 @@ where
             alloc: self.alloc,
             trace: self.trace,
             capture: Emit::new(),
+            share: self.share,
+            intern: self.intern,
+            validating: self.validating,
+            suspended: self.suspended,
+            max_depth: self.max_depth,
+            depth: self.depth,
+        }
+    }
+
+    /// Configure the context to only validate the input.
+    ///
+    /// The decoded value is expected to be discarded. This allows
+    /// implementations of [`Decode`] for containers such as `String` and `Vec`
+    /// to avoid retaining what they decode, so the values they produce will be
+    /// empty.
+    ///
+    /// The same inputs are accepted and rejected as without validation only as
+    /// long as nothing inspects the contents of those values while decoding.
+    /// Conversions through `#[musli(from = ..)]` and `#[musli(try_from = ..)]`
+    /// decode in full for this reason, but a [`Decode`] implementation or a
+    /// `#[musli(with = ..)]` module which checks what it decodes has to
+    /// suspend validation itself through [`Context::suspend_validating`].
+    ///
+    /// See [`Context::is_validating`].
+    ///
+    /// [`Decode`]: crate::Decode
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::context;
+    /// use musli::storage::{Encoding, Error};
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let data = ENCODING.to_vec(&Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// })?;
+    ///
+    /// let cx = context::new().with_error().with_validation();
+    ///
+    /// let person: Person = ENCODING.from_slice_with(&cx, &data[..])?;
+    /// assert_eq!(person.name, "");
+    /// assert_eq!(person.age, 61);
+    ///
+    /// assert!(ENCODING.from_slice_with::<_, Person>(&cx, &data[..data.len() - 1]).is_err());
+    /// Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn with_validation(mut self) -> Self {
+        self.validating = true;
+        self
+    }
+
+    /// Limit how deeply values which are decoded recursively can be nested.
+    ///
+    /// Recursive types such as `enum Expr { Num(i64), Add(Box<Expr>,
+    /// Box<Expr>) }` can otherwise be made to overflow the stack when decoding
+    /// untrusted input. With a limit in place, decoding a value which is nested
+    /// more than `max_depth` levels deep through pointers such as `Box<T>`
+    /// instead fails with an error.
+    ///
+    /// By default the depth is not limited.
+    ///
+    /// See [`Context::enter_depth`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::context;
+    /// use musli::storage::{Encoding, Error};
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// enum List {
+    ///     Nil,
+    ///     Cons(u32, Box<List>),
+    /// }
+    ///
+    /// let list = (0..16).fold(List::Nil, |list, n| List::Cons(n, Box::new(list)));
+    /// let data = ENCODING.to_vec(&list)?;
+    ///
+    /// let cx = context::new().with_error::<Error>().with_max_depth(16);
+    /// assert!(ENCODING.from_slice_with::<_, List>(&cx, &data[..]).is_ok());
+    ///
+    /// let cx = context::new().with_error::<Error>().with_max_depth(15);
+    /// assert!(ENCODING.from_slice_with::<_, List>(&cx, &data[..]).is_err());
+    /// Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Deduplicate shared allocations.
+    ///
+    /// This makes wrappers such as [`Shared`] encode each shared allocation
+    /// once, with later occurrences referring back to it. When decoding, the
+    /// references are resolved into clones of the same shared pointer.
+    ///
+    /// Sharing must be enabled both when encoding and decoding, since it
+    /// changes how the wrapped values are encoded.
+    ///
+    /// See [`Context::is_sharing`].
+    ///
+    /// [`Shared`]: crate::compat::Shared
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use musli::compat::Shared;
+    /// use musli::context;
+    /// use musli::storage::{Encoding, Error};
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let value = Arc::new(String::from("Hello World"));
+    /// let values = vec![Shared(value.clone()), Shared(value)];
+    ///
+    /// let cx = context::new().with_error().with_sharing();
+    ///
+    /// let mut data = Vec::new();
+    /// ENCODING.encode_with(&cx, &mut data, &values)?;
+    ///
+    /// let values: Vec<Shared<Arc<String>>> = ENCODING.from_slice_with(&cx, &data[..])?;
+    /// assert!(Arc::ptr_eq(&values[0].0, &values[1].0));
+    /// Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn with_sharing(self) -> DefaultContext<A, T, C, Share, I> {
+        DefaultContext {
+            alloc: self.alloc,
+            trace: self.trace,
+            capture: self.capture,
+            share: Share::new(),
+            intern: self.intern,
+            validating: self.validating,
+            suspended: self.suspended,
+            max_depth: self.max_depth,
+            depth: self.depth,
+        }
+    }
+
+    /// Intern decoded strings, keeping at most `max_entries` distinct strings.
+    ///
+    /// This makes shared string pointers such as `Arc<str>` and `Rc<str>`
+    /// which are decoded with equal contents share a single allocation, which
+    /// can greatly reduce memory use for data with many repeated strings.
+    /// Once `max_entries` distinct strings have been interned, further strings
+    /// are decoded into allocations of their own. Owned strings such as
+    /// `String` and `Box<str>` always have an allocation of their own.
+    ///
+    /// Interning doesn't affect the encoded data.
+    ///
+    /// See [`Context::is_interning`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use musli::context;
+    /// use musli::storage::{Encoding, Error};
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let values = vec!["info", "warning", "info"];
+    ///
+    /// let mut data = Vec::new();
+    /// ENCODING.encode(&mut data, &values)?;
+    ///
+    /// let cx = context::new().with_error().with_interning(1024);
+    ///
+    /// let values: Vec<Arc<str>> = ENCODING.from_slice_with(&cx, &data[..])?;
+    /// assert!(Arc::ptr_eq(&values[0], &values[2]));
+    /// assert!(!Arc::ptr_eq(&values[0], &values[1]));
+    /// Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "std")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+    #[inline]
+    pub fn with_interning(self, max_entries: usize) -> DefaultContext<A, T, C, S, Intern> {
+        DefaultContext {
+            alloc: self.alloc,
+            trace: self.trace,
+            capture: self.capture,
+            share: self.share,
+            intern: Intern::new(max_entries),
+            validating: self.validating,
+            suspended: self.suspended,
+            max_depth: self.max_depth,
+            depth: self.depth,
         }
     }
 }
 
-impl<A, C> DefaultContext<A, Trace, C>
+impl<A, C, S, I> DefaultContext<A, Trace, C, S, I>
 where
     A: Allocator,
 {
@@ -261,7 +522,7 @@ where
     }
 }
 
-impl<A, T, E> DefaultContext<A, T, Capture<E>>
+impl<A, T, E, S, I> DefaultContext<A, T, Capture<E>, S, I>
 where
     A: Allocator,
     T: TraceMode,
@@ -280,11 +541,13 @@ where
     }
 }
 
-impl<A, T, C> Context for &DefaultContext<A, T, C>
+impl<A, T, C, S, I> Context for &DefaultContext<A, T, C, S, I>
 where
     A: Allocator,
     T: TraceMode,
     C: ErrorMode<A>,
+    S: ShareMode,
+    I: InternMode,
 {
     type Error = C::Error;
     type Mark = <<T as TraceMode>::Impl<A> as TraceImpl<A>>::Mark;
@@ -294,6 +557,10 @@ where
     fn clear(self) {
         self.trace.clear();
         self.capture.clear();
+        self.share.clear();
+        self.intern.clear();
+        self.depth.store(0, Ordering::Relaxed);
+        self.suspended.store(0, Ordering::Relaxed);
     }
 
     #[inline]
@@ -301,6 +568,94 @@ where
         self.alloc
     }
 
+    #[inline]
+    fn is_validating(self) -> bool {
+        self.validating && self.suspended.load(Ordering::Relaxed) == 0
+    }
+
+    #[inline]
+    fn suspend_validating(self) {
+        let suspended = self.suspended.load(Ordering::Relaxed);
+        self.suspended.store(suspended + 1, Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn resume_validating(self) {
+        let suspended = self.suspended.load(Ordering::Relaxed);
+        self.suspended
+            .store(suspended.saturating_sub(1), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn enter_depth(self) -> Result<(), Self::Error> {
+        let depth = self.depth.load(Ordering::Relaxed);
+
+        if depth >= self.max_depth {
+            return Err(self.message(format_args!("Maximum depth of {} exceeded", self.max_depth)));
+        }
+
+        self.depth.store(depth + 1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    #[inline]
+    fn leave_depth(self) {
+        let depth = self.depth.load(Ordering::Relaxed);
+        self.depth.store(depth.saturating_sub(1), Ordering::Relaxed);
+    }
+
+    #[inline]
+    fn is_sharing(self) -> bool {
+        self.share.is_sharing()
+    }
+
+    #[inline]
+    fn shared_index(self, ptr: *const ()) -> Option<usize> {
+        self.share.shared_index(ptr)
+    }
+
+    #[inline]
+    fn insert_shared_ptr(self, ptr: *const ()) {
+        self.share.insert_shared_ptr(ptr);
+    }
+
+    #[inline]
+    fn shared_value<U>(self, index: usize) -> Option<U>
+    where
+        U: 'static + Clone,
+    {
+        self.share.shared_value(index)
+    }
+
+    #[inline]
+    fn insert_shared_value<U>(self, value: U)
+    where
+        U: 'static + Clone,
+    {
+        self.share.insert_shared_value(value);
+    }
+
+    #[inline]
+    fn is_interning(self) -> bool {
+        self.intern.is_interning()
+    }
+
+    #[inline]
+    fn interned<U>(self, string: &str) -> Option<U>
+    where
+        U: 'static + Clone,
+    {
+        self.intern.interned(string)
+    }
+
+    #[inline]
+    fn insert_interned<U>(self, string: &str, value: U)
+    where
+        U: 'static + Clone,
+    {
+        self.intern.insert_interned(string, value);
+    }
+
     #[inline]
     fn custom<E>(self, message: E) -> Self::Error
     where
@@ -423,4 +778,12 @@ where
     fn leave_map_key(self) {
         self.trace.leave_map_key();
     }
+
+    #[inline]
+    fn annotate<V>(self, key: &'static str, value: V)
+    where
+        V: fmt::Display,
+    {
+        self.trace.annotate(self.alloc, key, &value);
+    }
 }
@@ -0,0 +1,144 @@
+#[cfg(feature = "std")]
+use core::any::Any;
+#[cfg(feature = "std")]
+use core::cell::RefCell;
+
+#[cfg(feature = "std")]
+use rust_alloc::boxed::Box;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::NoIntern {}
+    #[cfg(feature = "std")]
+    impl Sealed for super::Intern {}
+}
+
+/// The trait governing how decoded strings are interned.
+///
+/// See [`DefaultContext::with_interning`] for more information.
+///
+/// [`DefaultContext::with_interning`]: super::DefaultContext::with_interning
+pub trait InternMode
+where
+    Self: self::sealed::Sealed,
+{
+    #[doc(hidden)]
+    fn clear(&self);
+
+    #[doc(hidden)]
+    fn is_interning(&self) -> bool;
+
+    #[doc(hidden)]
+    fn interned<T>(&self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone;
+
+    #[doc(hidden)]
+    fn insert_interned<T>(&self, string: &str, value: T)
+    where
+        T: 'static + Clone;
+}
+
+/// Disable interning of decoded strings.
+///
+/// This is the default behavior you get when calling [`new`] or [`new_in`].
+///
+/// [`new`]: super::new
+/// [`new_in`]: super::new_in
+#[non_exhaustive]
+pub struct NoIntern;
+
+impl InternMode for NoIntern {
+    #[inline]
+    fn clear(&self) {}
+
+    #[inline]
+    fn is_interning(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn interned<T>(&self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        _ = string;
+        None
+    }
+
+    #[inline]
+    fn insert_interned<T>(&self, string: &str, value: T)
+    where
+        T: 'static + Clone,
+    {
+        _ = string;
+        _ = value;
+    }
+}
+
+/// Intern decoded strings through a bounded table keyed by their contents.
+///
+/// Note that since the table stores shared pointers which are not necessarily
+/// thread safe, a context using this mode is neither [`Send`] nor [`Sync`].
+///
+/// See [`DefaultContext::with_interning`] for more information.
+///
+/// [`DefaultContext::with_interning`]: super::DefaultContext::with_interning
+#[cfg(feature = "std")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "std")))]
+pub struct Intern {
+    max_entries: usize,
+    values: RefCell<HashMap<Box<str>, Box<dyn Any>>>,
+}
+
+#[cfg(feature = "std")]
+impl Intern {
+    #[inline]
+    pub(super) fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            values: RefCell::new(HashMap::new()),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl InternMode for Intern {
+    #[inline]
+    fn clear(&self) {
+        self.values.borrow_mut().clear();
+    }
+
+    #[inline]
+    fn is_interning(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn interned<T>(&self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.values
+            .borrow()
+            .get(string)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    #[inline]
+    fn insert_interned<T>(&self, string: &str, value: T)
+    where
+        T: 'static + Clone,
+    {
+        let mut values = self.values.borrow_mut();
+
+        if values.len() < self.max_entries {
+            values
+                .entry(string.into())
+                .or_insert_with(|| Box::new(value));
+        }
+    }
+}
@@ -1,6 +1,52 @@
 //! [`Context`] implementations.
 //!
+//! <br>
+//!
+//! ## Thread safety
+//!
+//! Encodings such as [`storage::Encoding`] are only configuration, so they are
+//! always [`Send`] and [`Sync`] and a single one can be shared by any number of
+//! threads. A context on the other hand holds the state of one operation at a
+//! time, so while it can be sent to another thread it is not in general
+//! [`Sync`]. The [`System`] allocator is stateless and can be freely copied
+//! into other threads, while a [`Slice`] allocator can be sent but not shared.
+//!
+//! Contexts are cheap to construct, so when working with multiple threads the
+//! intended pattern is to construct one per thread or per operation:
+//!
+//! ```
+//! use std::thread;
+//!
+//! use musli::context;
+//! use musli::storage::Encoding;
+//!
+//! const ENCODING: Encoding = Encoding::new();
+//!
+//! thread::scope(|s| {
+//!     let threads = (0..4u32)
+//!         .map(|n| {
+//!             s.spawn(move || {
+//!                 let cx = context::new().with_trace();
+//!                 let data = ENCODING.to_vec_with(&cx, &n)?;
+//!                 let value: u32 = ENCODING.from_slice_with(&cx, &data)?;
+//!                 Ok::<_, context::ErrorMarker>(value)
+//!             })
+//!         })
+//!         .collect::<Vec<_>>();
+//!
+//!     for (n, thread) in threads.into_iter().enumerate() {
+//!         assert_eq!(thread.join().unwrap()?, n as u32);
+//!     }
+//!
+//!     Ok::<_, context::ErrorMarker>(())
+//! })?;
+//! # Ok::<_, context::ErrorMarker>(())
+//! ```
+//!
 //! [`Context`]: crate::Context
+//! [`storage::Encoding`]: crate::storage::Encoding
+//! [`System`]: crate::alloc::System
+//! [`Slice`]: crate::alloc::Slice
 
 mod access;
 use self::access::{Access, Shared};
@@ -13,6 +59,20 @@ mod capture;
 #[doc(inline)]
 pub use self::capture::{Capture, Emit, ErrorMode, Ignore};
 
+mod share;
+#[cfg(feature = "alloc")]
+#[doc(inline)]
+pub use self::share::Share;
+#[doc(inline)]
+pub use self::share::{NoShare, ShareMode};
+
+mod intern;
+#[cfg(feature = "std")]
+#[doc(inline)]
+pub use self::intern::Intern;
+#[doc(inline)]
+pub use self::intern::{InternMode, NoIntern};
+
 mod error_marker;
 #[doc(inline)]
 pub use self::error_marker::ErrorMarker;
@@ -25,6 +85,10 @@ mod context_error;
 #[doc(inline)]
 pub use self::context_error::ContextError;
 
+mod user_data;
+#[doc(inline)]
+pub use self::user_data::WithUserData;
+
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
 use crate::Allocator;
@@ -0,0 +1,169 @@
+#[cfg(feature = "alloc")]
+use core::any::Any;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use rust_alloc::collections::BTreeMap;
+#[cfg(feature = "alloc")]
+use rust_alloc::vec::Vec;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::NoShare {}
+    #[cfg(feature = "alloc")]
+    impl Sealed for super::Share {}
+}
+
+/// The trait governing how shared allocations are deduplicated.
+///
+/// See [`DefaultContext::with_sharing`] for more information.
+///
+/// [`DefaultContext::with_sharing`]: super::DefaultContext::with_sharing
+pub trait ShareMode
+where
+    Self: self::sealed::Sealed,
+{
+    #[doc(hidden)]
+    fn clear(&self);
+
+    #[doc(hidden)]
+    fn is_sharing(&self) -> bool;
+
+    #[doc(hidden)]
+    fn shared_index(&self, ptr: *const ()) -> Option<usize>;
+
+    #[doc(hidden)]
+    fn insert_shared_ptr(&self, ptr: *const ());
+
+    #[doc(hidden)]
+    fn shared_value<T>(&self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone;
+
+    #[doc(hidden)]
+    fn insert_shared_value<T>(&self, value: T)
+    where
+        T: 'static + Clone;
+}
+
+/// Disable deduplication of shared allocations.
+///
+/// This is the default behavior you get when calling [`new`] or [`new_in`].
+///
+/// [`new`]: super::new
+/// [`new_in`]: super::new_in
+#[non_exhaustive]
+pub struct NoShare;
+
+impl ShareMode for NoShare {
+    #[inline]
+    fn clear(&self) {}
+
+    #[inline]
+    fn is_sharing(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn shared_index(&self, ptr: *const ()) -> Option<usize> {
+        _ = ptr;
+        None
+    }
+
+    #[inline]
+    fn insert_shared_ptr(&self, ptr: *const ()) {
+        _ = ptr;
+    }
+
+    #[inline]
+    fn shared_value<T>(&self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        _ = index;
+        None
+    }
+
+    #[inline]
+    fn insert_shared_value<T>(&self, value: T)
+    where
+        T: 'static + Clone,
+    {
+        _ = value;
+    }
+}
+
+/// Deduplicate shared allocations through a table of pointers.
+///
+/// Note that since decoded values of any type can be stored in the table, a
+/// context using this mode is neither [`Send`] nor [`Sync`].
+///
+/// See [`DefaultContext::with_sharing`] for more information.
+///
+/// [`DefaultContext::with_sharing`]: super::DefaultContext::with_sharing
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub struct Share {
+    pointers: RefCell<BTreeMap<usize, usize>>,
+    values: RefCell<Vec<Box<dyn Any>>>,
+}
+
+#[cfg(feature = "alloc")]
+impl Share {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self {
+            pointers: RefCell::new(BTreeMap::new()),
+            values: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl ShareMode for Share {
+    #[inline]
+    fn clear(&self) {
+        self.pointers.borrow_mut().clear();
+        self.values.borrow_mut().clear();
+    }
+
+    #[inline]
+    fn is_sharing(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn shared_index(&self, ptr: *const ()) -> Option<usize> {
+        self.pointers.borrow().get(&(ptr as usize)).copied()
+    }
+
+    #[inline]
+    fn insert_shared_ptr(&self, ptr: *const ()) {
+        let mut pointers = self.pointers.borrow_mut();
+        let index = pointers.len();
+        pointers.entry(ptr as usize).or_insert(index);
+    }
+
+    #[inline]
+    fn shared_value<T>(&self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.values
+            .borrow()
+            .get(index)?
+            .downcast_ref::<T>()
+            .cloned()
+    }
+
+    #[inline]
+    fn insert_shared_value<T>(&self, value: T)
+    where
+        T: 'static + Clone,
+    {
+        self.values.borrow_mut().push(Box::new(value));
+    }
+}
@@ -121,6 +121,11 @@ where
 
     #[doc(hidden)]
     fn leave_map_key(&self);
+
+    #[doc(hidden)]
+    fn annotate<T>(&self, alloc: A, key: &'static str, value: &T)
+    where
+        T: fmt::Display;
 }
 
 /// Marker type indicating that tracing is enabled.
@@ -153,8 +158,9 @@ where
     A: Allocator,
 {
     mark: Cell<usize>,
-    errors: UnsafeCell<Vec<(Range<usize>, String<A>), A>>,
+    errors: UnsafeCell<Vec<Entry<A>, A>>,
     path: UnsafeCell<Vec<Step<A>, A>>,
+    annotations: UnsafeCell<Vec<(&'static str, String<A>), A>>,
     // How many elements of `path` we've gone over capacity.
     cap: Cell<usize>,
     include_type: bool,
@@ -170,11 +176,13 @@ where
     pub(super) fn new_in(alloc: A) -> Self {
         let errors = Vec::new_in(alloc);
         let path = Vec::new_in(alloc);
+        let annotations = Vec::new_in(alloc);
 
         Self {
             mark: Cell::new(0),
             errors: UnsafeCell::new(errors),
             path: UnsafeCell::new(path),
+            annotations: UnsafeCell::new(annotations),
             cap: Cell::new(0),
             include_type: false,
             access: Access::new(),
@@ -249,14 +257,28 @@ where
         Some(string)
     }
 
-    /// Push an error into the collection.
+    /// Push an error into the collection together with a copy of the
+    /// annotations which are currently active.
     #[inline]
-    fn push_error(&self, range: Range<usize>, error: String<A>) {
+    fn push_error(&self, alloc: A, range: Range<usize>, error: String<A>) {
         let _access = self.access.exclusive();
 
+        let mut annotations = Vec::new_in(alloc);
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        for (key, value) in unsafe { (*self.annotations.get()).as_slice() } {
+            if let Some(value) = self.format_string(alloc, value.as_str()) {
+                _ = annotations.push((*key, value));
+            }
+        }
+
         // SAFETY: We've checked that we have exclusive access just above.
         unsafe {
-            _ = (*self.errors.get()).push((range, error));
+            _ = (*self.errors.get()).push(Entry {
+                range,
+                error,
+                annotations,
+            });
         }
     }
 }
@@ -276,6 +298,7 @@ where
         unsafe {
             (*self.errors.get()).clear();
             (*self.path.get()).clear();
+            (*self.annotations.get()).clear();
         }
     }
 
@@ -295,7 +318,7 @@ where
         T: 'static + Send + Sync + fmt::Display + fmt::Debug,
     {
         if let Some(string) = self.format_string(alloc, message) {
-            self.push_error(self.mark.get()..self.mark.get(), string);
+            self.push_error(alloc, self.mark.get()..self.mark.get(), string);
         }
     }
 
@@ -305,7 +328,7 @@ where
         T: fmt::Display,
     {
         if let Some(string) = self.format_string(alloc, message) {
-            self.push_error(self.mark.get()..self.mark.get(), string);
+            self.push_error(alloc, self.mark.get()..self.mark.get(), string);
         }
     }
 
@@ -315,7 +338,7 @@ where
         T: fmt::Display,
     {
         if let Some(string) = self.format_string(alloc, message) {
-            self.push_error(*mark..self.mark.get(), string);
+            self.push_error(alloc, *mark..self.mark.get(), string);
         }
     }
 
@@ -325,7 +348,7 @@ where
         T: 'static + Send + Sync + fmt::Display + fmt::Debug,
     {
         if let Some(string) = self.format_string(alloc, message) {
-            self.push_error(*mark..self.mark.get(), string);
+            self.push_error(alloc, *mark..self.mark.get(), string);
         }
     }
 
@@ -412,6 +435,31 @@ where
     fn leave_map_key(&self) {
         self.pop_path();
     }
+
+    #[inline]
+    fn annotate<T>(&self, alloc: A, key: &'static str, value: &T)
+    where
+        T: fmt::Display,
+    {
+        let Some(value) = self.format_string(alloc, value) else {
+            return;
+        };
+
+        let _access = self.access.exclusive();
+
+        // SAFETY: We've checked that we have exclusive access just above.
+        let annotations = unsafe { &mut (*self.annotations.get()) };
+
+        if let Some((_, existing)) = annotations
+            .as_slice_mut()
+            .iter_mut()
+            .find(|(k, _)| *k == key)
+        {
+            *existing = value;
+        } else {
+            _ = annotations.push((key, value));
+        }
+    }
 }
 
 #[non_exhaustive]
@@ -564,6 +612,16 @@ where
 
     #[inline]
     fn leave_map_key(&self) {}
+
+    #[inline]
+    fn annotate<T>(&self, alloc: A, key: &'static str, value: &T)
+    where
+        T: fmt::Display,
+    {
+        _ = alloc;
+        _ = key;
+        _ = value;
+    }
 }
 
 /// A line-separated report of all errors.
@@ -603,7 +661,7 @@ where
 {
     path: &'a [Step<A>],
     cap: usize,
-    errors: slice::Iter<'a, (Range<usize>, String<A>)>,
+    errors: slice::Iter<'a, Entry<A>>,
     _access: Shared<'a>,
 }
 
@@ -615,8 +673,14 @@ where
 
     #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let (range, error) = self.errors.next()?;
-        Some(Error::new(self.path, self.cap, range.clone(), error))
+        let entry = self.errors.next()?;
+        Some(Error::new(
+            self.path,
+            entry.annotations.as_slice(),
+            self.cap,
+            entry.range.clone(),
+            &entry.error,
+        ))
     }
 }
 
@@ -641,6 +705,7 @@ where
     A: Allocator,
 {
     path: &'a [Step<A>],
+    annotations: &'a [(&'static str, String<A>)],
     cap: usize,
     range: Range<usize>,
     error: &'a str,
@@ -651,14 +716,51 @@ where
     A: Allocator,
 {
     #[inline]
-    fn new(path: &'a [Step<A>], cap: usize, range: Range<usize>, error: &'a str) -> Self {
+    fn new(
+        path: &'a [Step<A>],
+        annotations: &'a [(&'static str, String<A>)],
+        cap: usize,
+        range: Range<usize>,
+        error: &'a str,
+    ) -> Self {
         Self {
             path,
+            annotations,
             cap,
             range,
             error,
         }
     }
+
+    /// Get the message of the error.
+    #[inline]
+    pub fn message(&self) -> &'a str {
+        self.error
+    }
+
+    /// Get the value of the annotation with the given `key`, if it was
+    /// recorded through [`Context::annotate`] before this error was reported.
+    ///
+    /// [`Context::annotate`]: crate::Context::annotate
+    #[inline]
+    pub fn annotation(&self, key: &str) -> Option<&'a str> {
+        self.annotations
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Iterate over all annotations recorded through [`Context::annotate`]
+    /// which were active when this error was reported, in the order in which
+    /// they were first recorded.
+    ///
+    /// [`Context::annotate`]: crate::Context::annotate
+    #[inline]
+    pub fn annotations(&self) -> impl Iterator<Item = (&'static str, &'a str)> + '_ {
+        self.annotations
+            .iter()
+            .map(|(key, value)| (*key, value.as_str()))
+    }
 }
 
 impl<A> fmt::Display for Error<'_, A>
@@ -683,10 +785,31 @@ where
             write!(f, "{path}: {}", self.error)?;
         }
 
+        if let [(key, value), rest @ ..] = self.annotations {
+            write!(f, " [{key} = {value}")?;
+
+            for (key, value) in rest {
+                write!(f, ", {key} = {value}")?;
+            }
+
+            write!(f, "]")?;
+        }
+
         Ok(())
     }
 }
 
+/// A collected error together with the annotations active when it was
+/// reported.
+struct Entry<A>
+where
+    A: Allocator,
+{
+    range: Range<usize>,
+    error: String<A>,
+    annotations: Vec<(&'static str, String<A>), A>,
+}
+
 /// A single traced step.
 #[derive(Debug)]
 enum Step<A>
@@ -0,0 +1,306 @@
+use core::any::Any;
+use core::error::Error;
+use core::fmt;
+
+use crate::Context;
+
+/// A context which wraps another context and associates user data with it.
+///
+/// The user data can be accessed through [`Context::user_data`], which makes
+/// it possible to thread application configuration to functions used with
+/// `#[musli(default_with_cx = ..)]`. Everything else is delegated to the
+/// wrapped context.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{context, Context, Decode, Encode};
+/// use musli::json::Encoding;
+///
+/// const ENCODING: Encoding = Encoding::new();
+///
+/// #[derive(Clone)]
+/// struct Config {
+///     region: &'static str,
+/// }
+///
+/// fn region<C>(cx: C) -> String
+/// where
+///     C: Context,
+/// {
+///     match cx.user_data::<Config>() {
+///         Some(config) => String::from(config.region),
+///         None => String::from("global"),
+///     }
+/// }
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Tenant {
+///     name: String,
+///     #[musli(default_with_cx = region)]
+///     region: String,
+/// }
+///
+/// let cx = context::new();
+/// let cx = context::WithUserData::new(&cx, Config { region: "eu-west" });
+///
+/// let tenant: Tenant = ENCODING.from_slice_with(&cx, br#"{"name": "Acme"}"#)?;
+/// assert_eq!(tenant.region, "eu-west");
+///
+/// let tenant: Tenant = ENCODING.from_slice(br#"{"name": "Acme"}"#)?;
+/// assert_eq!(tenant.region, "global");
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+pub struct WithUserData<C, U> {
+    inner: C,
+    data: U,
+}
+
+impl<C, U> WithUserData<C, U> {
+    /// Wrap the context `inner`, associating `data` with it.
+    #[inline]
+    pub fn new(inner: C, data: U) -> Self {
+        Self { inner, data }
+    }
+
+    /// Get a reference to the user data.
+    #[inline]
+    pub fn data(&self) -> &U {
+        &self.data
+    }
+}
+
+impl<C, U> Context for &WithUserData<C, U>
+where
+    C: Context,
+    U: 'static,
+{
+    type Error = C::Error;
+    type Mark = C::Mark;
+    type Allocator = C::Allocator;
+
+    #[inline]
+    fn clear(self) {
+        self.inner.clear();
+    }
+
+    #[inline]
+    fn advance(self, n: usize) {
+        self.inner.advance(n);
+    }
+
+    #[inline]
+    fn mark(self) -> Self::Mark {
+        self.inner.mark()
+    }
+
+    #[inline]
+    fn alloc(self) -> Self::Allocator {
+        self.inner.alloc()
+    }
+
+    #[inline]
+    fn is_validating(self) -> bool {
+        self.inner.is_validating()
+    }
+
+    #[inline]
+    fn suspend_validating(self) {
+        self.inner.suspend_validating();
+    }
+
+    #[inline]
+    fn resume_validating(self) {
+        self.inner.resume_validating();
+    }
+
+    #[inline]
+    fn is_sharing(self) -> bool {
+        self.inner.is_sharing()
+    }
+
+    #[inline]
+    fn shared_index(self, ptr: *const ()) -> Option<usize> {
+        self.inner.shared_index(ptr)
+    }
+
+    #[inline]
+    fn insert_shared_ptr(self, ptr: *const ()) {
+        self.inner.insert_shared_ptr(ptr);
+    }
+
+    #[inline]
+    fn shared_value<T>(self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.inner.shared_value(index)
+    }
+
+    #[inline]
+    fn insert_shared_value<T>(self, value: T)
+    where
+        T: 'static + Clone,
+    {
+        self.inner.insert_shared_value(value);
+    }
+
+    #[inline]
+    fn is_interning(self) -> bool {
+        self.inner.is_interning()
+    }
+
+    #[inline]
+    fn interned<T>(self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.inner.interned(string)
+    }
+
+    #[inline]
+    fn insert_interned<T>(self, string: &str, value: T)
+    where
+        T: 'static + Clone,
+    {
+        self.inner.insert_interned(string, value);
+    }
+
+    #[inline]
+    fn user_data<T>(self) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        match (&self.data as &dyn Any).downcast_ref::<T>() {
+            Some(data) => Some(data.clone()),
+            None => self.inner.user_data(),
+        }
+    }
+
+    #[inline]
+    fn enter_depth(self) -> Result<(), Self::Error> {
+        self.inner.enter_depth()
+    }
+
+    #[inline]
+    fn leave_depth(self) {
+        self.inner.leave_depth();
+    }
+
+    #[inline]
+    fn custom<E>(self, error: E) -> Self::Error
+    where
+        E: 'static + Send + Sync + Error,
+    {
+        self.inner.custom(error)
+    }
+
+    #[inline]
+    fn message<M>(self, message: M) -> Self::Error
+    where
+        M: fmt::Display,
+    {
+        self.inner.message(message)
+    }
+
+    #[inline]
+    fn marked_message<M>(self, mark: &Self::Mark, message: M) -> Self::Error
+    where
+        M: fmt::Display,
+    {
+        self.inner.marked_message(mark, message)
+    }
+
+    #[inline]
+    fn marked_custom<E>(self, mark: &Self::Mark, message: E) -> Self::Error
+    where
+        E: 'static + Send + Sync + Error,
+    {
+        self.inner.marked_custom(mark, message)
+    }
+
+    #[inline]
+    fn enter_struct(self, type_name: &'static str) {
+        self.inner.enter_struct(type_name);
+    }
+
+    #[inline]
+    fn leave_struct(self) {
+        self.inner.leave_struct();
+    }
+
+    #[inline]
+    fn enter_enum(self, type_name: &'static str) {
+        self.inner.enter_enum(type_name);
+    }
+
+    #[inline]
+    fn leave_enum(self) {
+        self.inner.leave_enum();
+    }
+
+    #[inline]
+    fn enter_named_field<F>(self, type_name: &'static str, field: F)
+    where
+        F: fmt::Display,
+    {
+        self.inner.enter_named_field(type_name, field);
+    }
+
+    #[inline]
+    fn enter_unnamed_field<F>(self, index: u32, name: F)
+    where
+        F: fmt::Display,
+    {
+        self.inner.enter_unnamed_field(index, name);
+    }
+
+    #[inline]
+    fn leave_field(self) {
+        self.inner.leave_field();
+    }
+
+    #[inline]
+    fn enter_variant<V>(self, type_name: &'static str, tag: V)
+    where
+        V: fmt::Display,
+    {
+        self.inner.enter_variant(type_name, tag);
+    }
+
+    #[inline]
+    fn leave_variant(self) {
+        self.inner.leave_variant();
+    }
+
+    #[inline]
+    fn enter_map_key<K>(self, field: K)
+    where
+        K: fmt::Display,
+    {
+        self.inner.enter_map_key(field);
+    }
+
+    #[inline]
+    fn leave_map_key(self) {
+        self.inner.leave_map_key();
+    }
+
+    #[inline]
+    fn enter_sequence_index(self, index: usize) {
+        self.inner.enter_sequence_index(index);
+    }
+
+    #[inline]
+    fn leave_sequence_index(self) {
+        self.inner.leave_sequence_index();
+    }
+
+    #[inline]
+    fn annotate<V>(self, key: &'static str, value: V)
+    where
+        V: fmt::Display,
+    {
+        self.inner.annotate(key, value);
+    }
+}
@@ -18,9 +18,10 @@
 
 #[doc(inline)]
 pub use musli_core::de::{
-    AsDecoder, Decode, DecodeBytes, DecodeOwned, DecodePacked, DecodeSliceBuilder, DecodeTrace,
-    DecodeUnsized, DecodeUnsizedBytes, Decoder, EntriesDecoder, EntryDecoder, MapDecoder,
-    SequenceDecoder, SizeHint, Skip, TryFastDecode, UnsizedVisitor, VariantDecoder, Visitor,
+    AsDecoder, Decode, DecodeBytes, DecodeFlatten, DecodeOwned, DecodePacked, DecodeSliceBuilder,
+    DecodeTrace, DecodeUnsized, DecodeUnsizedBytes, Decoder, EntriesDecoder, EntryDecoder,
+    MapDecoder, SequenceDecoder, SizeHint, Skip, TryFastDecode, UnsizedVisitor, VariantDecoder,
+    Visitor,
 };
 
 #[cfg(any(
@@ -7,6 +7,7 @@ use crate::de::{
     Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, Skip,
     UnsizedVisitor, VariantDecoder, Visitor,
 };
+use crate::hint::Capabilities;
 use crate::int::continuation as c;
 use crate::options;
 use crate::reader::Limit;
@@ -14,7 +15,7 @@ use crate::storage::de::StorageDecoder;
 use crate::Context;
 use crate::{Options, Reader};
 
-use super::integer_encoding::{decode_typed_signed, decode_typed_unsigned};
+use super::integer_encoding::{decode_typed_float, decode_typed_signed, decode_typed_unsigned};
 use super::tag::{Kind, Mark, Tag, F32, F64, I128, I16, I32, I64, I8, U128, U16, U32, U64, U8};
 
 const BUFFER_OPTIONS: Options = options::new().build();
@@ -23,16 +24,18 @@ const BUFFER_OPTIONS: Options = options::new().build();
 pub struct SelfDecoder<const OPT: Options, R, C, M> {
     cx: C,
     reader: R,
+    strict_types: bool,
     _marker: PhantomData<M>,
 }
 
 impl<const OPT: Options, R, C, M> SelfDecoder<OPT, R, C, M> {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, reader: R) -> Self {
+    pub(crate) fn new(cx: C, reader: R, strict_types: bool) -> Self {
         Self {
             cx,
             reader,
+            strict_types,
             _marker: PhantomData,
         }
     }
@@ -69,7 +72,7 @@ where
 
             match tag.kind() {
                 Kind::Number => {
-                    _ = c::decode::<_, _, u128>(self.cx, self.reader.borrow_mut())?;
+                    c::skip::<_, _, u128>(self.cx, self.reader.borrow_mut())?;
                 }
                 Kind::Mark => match tag.mark() {
                     Mark::Variant => {
@@ -78,8 +81,11 @@ where
                     Mark::Some => {
                         remaining += 1;
                     }
+                    Mark::Tagged => {
+                        remaining += 2;
+                    }
                     Mark::Char => {
-                        _ = c::decode::<_, _, u32>(self.cx, self.reader.borrow_mut())?;
+                        c::skip::<_, _, u32>(self.cx, self.reader.borrow_mut())?;
                     }
                     _ => {}
                 },
@@ -118,7 +124,12 @@ where
     fn shared_decode_map(mut self) -> Result<RemainingSelfDecoder<OPT, R, C, M>, C::Error> {
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::Map, &pos)?;
-        Ok(RemainingSelfDecoder::new(self.cx, self.reader, len))
+        Ok(RemainingSelfDecoder::new(
+            self.cx,
+            self.reader,
+            len,
+            self.strict_types,
+        ))
     }
 
     // Standard function for decoding a pair sequence.
@@ -126,7 +137,12 @@ where
     fn shared_decode_sequence(mut self) -> Result<RemainingSelfDecoder<OPT, R, C, M>, C::Error> {
         let pos = self.cx.mark();
         let len = self.decode_prefix(Kind::Sequence, &pos)?;
-        Ok(RemainingSelfDecoder::new(self.cx, self.reader, len))
+        Ok(RemainingSelfDecoder::new(
+            self.cx,
+            self.reader,
+            len,
+            self.strict_types,
+        ))
     }
 
     /// Decode the length of a prefix.
@@ -176,6 +192,7 @@ pub struct RemainingSelfDecoder<const OPT: Options, R, C, M> {
     cx: C,
     reader: R,
     remaining: usize,
+    strict_types: bool,
     _marker: PhantomData<M>,
 }
 
@@ -186,11 +203,12 @@ where
     M: 'static,
 {
     #[inline]
-    fn new(cx: C, reader: R, remaining: usize) -> Self {
+    fn new(cx: C, reader: R, remaining: usize, strict_types: bool) -> Self {
         Self {
             cx,
             reader,
             remaining,
+            strict_types,
             _marker: PhantomData,
         }
     }
@@ -252,7 +270,7 @@ where
     where
         U: Context<Allocator = Self::Allocator>,
     {
-        Ok(SelfDecoder::new(cx, self.reader))
+        Ok(SelfDecoder::new(cx, self.reader, self.strict_types))
     }
 
     #[inline]
@@ -260,6 +278,17 @@ where
         write!(f, "type supported by the descriptive decoder")
     }
 
+    const CAPABILITIES: Capabilities = options::capabilities::<OPT>(
+        Capabilities::SELF_DESCRIBING
+            .with(Capabilities::CAN_BORROW)
+            .with(Capabilities::CAN_SKIP),
+    );
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Self::CAPABILITIES
+    }
+
     #[inline]
     fn skip(self) -> Result<(), C::Error> {
         self.skip_any()
@@ -290,7 +319,7 @@ where
     {
         let pos = self.cx.mark();
         let len = self.decode_pack_length(&pos)?;
-        let mut decoder = SelfDecoder::new(self.cx, self.reader.limit(len));
+        let mut decoder = SelfDecoder::new(self.cx, self.reader.limit(len), self.strict_types);
         let output = f(&mut decoder)?;
         decoder.end()?;
         Ok(output)
@@ -477,59 +506,59 @@ where
 
     #[inline]
     fn decode_u8(self) -> Result<u8, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader)
+        decode_typed_unsigned(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_u16(self) -> Result<u16, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader)
+        decode_typed_unsigned(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_u32(self) -> Result<u32, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader)
+        decode_typed_unsigned(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_u64(self) -> Result<u64, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader)
+        decode_typed_unsigned(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_u128(self) -> Result<u128, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader)
+        decode_typed_unsigned(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_i8(self) -> Result<i8, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_i16(self) -> Result<i16, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_i32(self) -> Result<i32, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_i64(self) -> Result<i64, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
     fn decode_i128(self) -> Result<i128, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     /// Decode a 32-bit floating point value by reading the 32-bit in-memory
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f32(self) -> Result<f32, C::Error> {
-        let bits = self.decode_u32()?;
+        let bits = decode_typed_float(self.cx, self.reader, self.strict_types)?;
         Ok(f32::from_bits(bits))
     }
 
@@ -537,18 +566,18 @@ where
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f64(self) -> Result<f64, C::Error> {
-        let bits = self.decode_u64()?;
+        let bits = decode_typed_float(self.cx, self.reader, self.strict_types)?;
         Ok(f64::from_bits(bits))
     }
 
     #[inline]
     fn decode_usize(mut self) -> Result<usize, C::Error> {
-        decode_typed_unsigned(self.cx, self.reader.borrow_mut())
+        decode_typed_unsigned(self.cx, self.reader.borrow_mut(), self.strict_types)
     }
 
     #[inline]
     fn decode_isize(self) -> Result<isize, C::Error> {
-        decode_typed_signed(self.cx, self.reader)
+        decode_typed_signed(self.cx, self.reader, self.strict_types)
     }
 
     #[inline]
@@ -624,6 +653,29 @@ where
         f(&mut self)
     }
 
+    #[inline]
+    fn decode_tagged(mut self) -> Result<(u32, Self), C::Error> {
+        const TAGGED: Tag = Tag::from_mark(Mark::Tagged);
+
+        let pos = self.cx.mark();
+        let tag = Tag::from_byte(self.reader.read_byte(self.cx)?);
+
+        if tag != TAGGED {
+            return Err(self.cx.marked_message(
+                &pos,
+                format_args! {
+                    "Expected tagged value, was {tag:?}"
+                },
+            ));
+        }
+
+        let tag =
+            SelfDecoder::<OPT, _, _, M>::new(self.cx, self.reader.borrow_mut(), self.strict_types)
+                .decode_u32()?;
+
+        Ok((tag, self))
+    }
+
     #[inline]
     fn decode_any<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
     where
@@ -739,7 +791,10 @@ where
                     self.decode_empty()?;
                     visitor.visit_empty(cx)
                 }
-                mark => Err(cx.message(format_args!("Unsupported mark {mark:?}"))),
+                Mark::Tagged => {
+                    let (tag, decoder) = self.decode_tagged()?;
+                    visitor.visit_tagged(cx, tag, decoder)
+                }
             },
             kind => Err(cx.message(format_args!("Unsupported kind {kind:?}"))),
         }
@@ -805,7 +860,11 @@ where
         }
 
         self.remaining -= 1;
-        Ok(Some(SelfDecoder::new(self.cx, self.reader.borrow_mut())))
+        Ok(Some(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        )))
     }
 
     #[inline]
@@ -854,7 +913,11 @@ where
         }
 
         self.remaining -= 1;
-        Ok(Some(SelfDecoder::new(self.cx, self.reader.borrow_mut())))
+        Ok(Some(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        )))
     }
 
     #[inline]
@@ -863,6 +926,7 @@ where
             self.cx,
             self.reader.borrow_mut(),
             take(&mut self.remaining),
+            self.strict_types,
         ))
     }
 }
@@ -896,12 +960,20 @@ where
         }
 
         self.remaining -= 1;
-        Ok(Some(SelfDecoder::new(self.cx, self.reader.borrow_mut())))
+        Ok(Some(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        )))
     }
 
     #[inline]
     fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
-        Ok(SelfDecoder::new(self.cx, self.reader.borrow_mut()))
+        Ok(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        ))
     }
 
     #[inline]
@@ -932,7 +1004,11 @@ where
 
     #[inline]
     fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
-        Ok(SelfDecoder::new(self.cx, self.reader.borrow_mut()))
+        Ok(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        ))
     }
 
     #[inline]
@@ -965,12 +1041,20 @@ where
 
     #[inline]
     fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
-        Ok(SelfDecoder::new(self.cx, self.reader.borrow_mut()))
+        Ok(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        ))
     }
 
     #[inline]
     fn decode_value(&mut self) -> Result<Self::DecodeValue<'_>, C::Error> {
-        Ok(SelfDecoder::new(self.cx, self.reader.borrow_mut()))
+        Ok(SelfDecoder::new(
+            self.cx,
+            self.reader.borrow_mut(),
+            self.strict_types,
+        ))
     }
 }
 
@@ -4,8 +4,9 @@ use core::marker::PhantomData;
 use crate::en::{
     Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
 };
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::int::continuation as c;
+use crate::options;
 use crate::storage::en::StorageEncoder;
 use crate::writer::BufWriter;
 use crate::{Context, Options, Writer};
@@ -104,6 +105,11 @@ where
         write!(f, "type supported by the descriptive encoder")
     }
 
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::SELF_DESCRIBING.with(Capabilities::CAN_SKIP)
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
     where
@@ -230,12 +236,22 @@ where
 
     #[inline]
     fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
-        encode_typed_unsigned(self.cx, self.writer.borrow_mut(), F32, value.to_bits())
+        encode_typed_unsigned(
+            self.cx,
+            self.writer.borrow_mut(),
+            F32,
+            options::canonical_f32::<OPT>(value).to_bits(),
+        )
     }
 
     #[inline]
     fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
-        encode_typed_unsigned(self.cx, self.writer.borrow_mut(), F64, value.to_bits())
+        encode_typed_unsigned(
+            self.cx,
+            self.writer.borrow_mut(),
+            F64,
+            options::canonical_f64::<OPT>(value).to_bits(),
+        )
     }
 
     #[inline]
@@ -262,6 +278,17 @@ where
         Ok(())
     }
 
+    #[inline]
+    fn encode_tagged<T>(mut self, tag: u32, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        const TAGGED: Tag = Tag::from_mark(Mark::Tagged);
+        self.writer.write_byte(self.cx, TAGGED.byte())?;
+        SelfEncoder::<OPT, _, _, M>::new(self.cx, self.writer.borrow_mut()).encode_u32(tag)?;
+        value.encode(self)
+    }
+
     #[inline]
     fn encode_sequence(mut self, hint: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
         encode_prefix::<OPT, _, _>(self.cx, self.writer.borrow_mut(), Kind::Sequence, hint.size)?;
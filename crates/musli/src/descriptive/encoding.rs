@@ -6,13 +6,25 @@ use core::marker;
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
 use crate::mode::Binary;
+use crate::negotiate::{FormatId, FormatKind};
 use crate::options;
-use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options};
+#[cfg(feature = "alloc")]
+use crate::value::Value;
+use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options, Reader};
 
 use super::de::SelfDecoder;
 use super::en::SelfEncoder;
 #[cfg(feature = "alloc")]
 use super::error::Error;
+#[cfg(feature = "alloc")]
+use super::lossy::{Issue, Lossy};
+
+/// The version of the layout of the descriptive format.
+///
+/// This is bumped whenever the layout changes in a way which isn't compatible
+/// with older versions, and is part of the [`FormatId`] returned by
+/// [`Encoding::format_id`].
+pub const FORMAT_VERSION: u16 = 1;
 
 /// The default flavor used by the [`DEFAULT`] configuration.
 pub const OPTIONS: options::Options = options::new().build();
@@ -30,12 +42,46 @@ pub const DEFAULT: Encoding = Encoding::new();
 
 crate::macros::bare_encoding!(Binary, DEFAULT, descriptive, IntoReader, IntoWriter);
 
+/// Decode the given slice into a [`Value`] using the [`DEFAULT`] [`Encoding`],
+/// recovering as much as possible from corrupt input.
+///
+/// See [`Encoding::to_value_lossy`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use musli::value::Value;
+///
+/// let mut bytes = musli::descriptive::to_vec(&["Plato", "Aristotle"])?;
+/// // Corrupt the first character of "Aristotle".
+/// bytes[8] = 0xff;
+///
+/// let (value, issues) = musli::descriptive::to_value_lossy(&bytes);
+///
+/// assert_eq!(issues.len(), 1);
+/// assert_eq!(issues[0].offset(), 7);
+///
+/// let Value::Sequence(values) = value else {
+///     panic!("expected sequence");
+/// };
+///
+/// assert!(matches!(&values[0], Value::String(s) if s.as_str() == "Plato"));
+/// assert_eq!(values[1], Value::Invalid(7));
+/// # Ok::<_, musli::descriptive::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[inline]
+pub fn to_value_lossy(bytes: &[u8]) -> (Value<System>, rust_alloc::vec::Vec<Issue>) {
+    DEFAULT.to_value_lossy(bytes)
+}
+
 /// Setting up encoding with parameters.
 pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
 where
     M: 'static,
 {
-    _marker: marker::PhantomData<M>,
+    strict_types: bool,
+    _marker: marker::PhantomData<fn() -> M>,
 }
 
 impl Default for Encoding<OPTIONS, Binary> {
@@ -76,6 +122,7 @@ impl Encoding<OPTIONS, Binary> {
     /// ```
     pub const fn new() -> Self {
         Encoding {
+            strict_types: false,
             _marker: marker::PhantomData,
         }
     }
@@ -101,6 +148,7 @@ where
         T: 'static,
     {
         Encoding {
+            strict_types: self.strict_types,
             _marker: marker::PhantomData,
         }
     }
@@ -118,10 +166,119 @@ where
     /// ```
     pub const fn with_options<const U: Options>(self) -> Encoding<U, M> {
         Encoding {
+            strict_types: self.strict_types,
             _marker: marker::PhantomData,
         }
     }
 
+    /// Get the identifier of the format and options used by this encoding,
+    /// which can be used to [`negotiate`] a configuration with a peer.
+    ///
+    /// [`negotiate`]: crate::negotiate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options, Integer};
+    /// use musli::descriptive::{Encoding, DEFAULT};
+    ///
+    /// const OPTIONS: Options = options::new().integer(Integer::Fixed).build();
+    /// const CONFIG: Encoding<OPTIONS> = Encoding::new().with_options();
+    ///
+    /// assert_ne!(CONFIG.format_id(), DEFAULT.format_id());
+    /// ```
+    #[inline]
+    pub const fn format_id(&self) -> FormatId {
+        FormatId::new(FormatKind::Descriptive, FORMAT_VERSION, OPT)
+    }
+
+    /// Disable type coercions of numbers when decoding.
+    ///
+    /// By default a number is decoded into any numerical type which can
+    /// represent it, so an unsigned number can be decoded into a signed type
+    /// and vice versa, and an integer can be decoded into a floating point
+    /// type. With strict types enabled the kind of number stored must match
+    /// the type being decoded, while its width may still differ.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::descriptive::Encoding;
+    /// # use musli::descriptive::Error;
+    ///
+    /// const STRICT: Encoding = Encoding::new().with_strict_types();
+    ///
+    /// let bytes = musli::descriptive::to_vec(&42u32)?;
+    ///
+    /// let value: i64 = musli::descriptive::from_slice(&bytes)?;
+    /// assert_eq!(value, 42);
+    ///
+    /// let value: u8 = STRICT.from_slice(&bytes)?;
+    /// assert_eq!(value, 42);
+    ///
+    /// assert!(STRICT.from_slice::<i64>(&bytes).is_err());
+    /// assert!(STRICT.from_slice::<f32>(&bytes).is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_strict_types(self) -> Self {
+        Encoding {
+            strict_types: true,
+            ..self
+        }
+    }
+
+    /// Construct a decoder using the current configuration.
+    #[inline]
+    fn new_decoder<'de, C, R>(self, cx: C, reader: R) -> SelfDecoder<OPT, R, C, M>
+    where
+        C: Context,
+        R: Reader<'de>,
+    {
+        SelfDecoder::new(cx, reader, self.strict_types)
+    }
+
+    /// Decode the given slice into a [`Value`], recovering as much as possible
+    /// from corrupt input.
+    ///
+    /// Instead of failing at the first error, a [`Value::Invalid`] placeholder
+    /// is substituted for each value that could not be decoded and an [`Issue`]
+    /// describing it is recorded.
+    ///
+    /// If the extent of the invalid value could be determined, such as for a
+    /// string which is not valid UTF-8, decoding resumes after it. Otherwise
+    /// decoding stops and the containers decoded so far are returned.
+    ///
+    /// Decoding also stops at values which are nested more than 256 levels
+    /// deep, so that hostile input can't exhaust the stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::value::Value;
+    ///
+    /// let mut bytes = musli::descriptive::to_vec(&["Plato", "Aristotle"])?;
+    /// // Corrupt the first character of "Aristotle".
+    /// bytes[8] = 0xff;
+    ///
+    /// let (value, issues) = musli::descriptive::Encoding::new().to_value_lossy(&bytes);
+    ///
+    /// assert_eq!(issues.len(), 1);
+    /// assert_eq!(issues[0].offset(), 7);
+    ///
+    /// let Value::Sequence(values) = value else {
+    ///     panic!("expected sequence");
+    /// };
+    ///
+    /// assert!(matches!(&values[0], Value::String(s) if s.as_str() == "Plato"));
+    /// assert_eq!(values[1], Value::Invalid(7));
+    /// # Ok::<_, musli::descriptive::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn to_value_lossy(self, bytes: &[u8]) -> (Value<System>, rust_alloc::vec::Vec<Issue>) {
+        Lossy::<OPT, M>::new(bytes).decode()
+    }
+
     crate::macros::encoding_impls!(
         M,
         descriptive,
@@ -129,6 +286,7 @@ where
         SelfDecoder::<OPT, _, _, M>::new,
         IntoReader::into_reader,
         IntoWriter::into_writer,
+        @decode new_decoder,
     );
 }
 
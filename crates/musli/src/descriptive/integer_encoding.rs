@@ -21,7 +21,11 @@ where
 }
 
 #[inline]
-pub(crate) fn decode_typed_unsigned<'de, C, R, T>(cx: C, reader: R) -> Result<T, C::Error>
+pub(crate) fn decode_typed_unsigned<'de, C, R, T>(
+    cx: C,
+    reader: R,
+    strict: bool,
+) -> Result<T, C::Error>
 where
     C: Context,
     R: Reader<'de>,
@@ -30,6 +34,8 @@ where
     let (value, kind): (T, NumberKind) = decode_typed(cx, reader)?;
 
     match kind {
+        NumberKind::Unsigned => Ok(value),
+        kind if strict => Err(cx.message(format_args!("Expected unsigned number, got {:?}", kind))),
         NumberKind::Signed => {
             let value = zig::decode(value);
 
@@ -39,7 +45,7 @@ where
 
             Ok(value)
         }
-        NumberKind::Unsigned | NumberKind::Float => Ok(value),
+        NumberKind::Float => Ok(value),
         kind => Err(cx.message(format_args!(
             "Expected signed or unsigned number, got {:?}",
             kind
@@ -47,6 +53,33 @@ where
     }
 }
 
+/// Decode the bits of a floating point number.
+///
+/// Unless `strict` is set, any number which is decodeable as an unsigned value
+/// is accepted and its bits reinterpreted.
+#[inline]
+pub(crate) fn decode_typed_float<'de, C, R, T>(
+    cx: C,
+    reader: R,
+    strict: bool,
+) -> Result<T, C::Error>
+where
+    C: Context,
+    R: Reader<'de>,
+    T: Unsigned + TryFrom<T::Signed>,
+{
+    if !strict {
+        return decode_typed_unsigned(cx, reader, false);
+    }
+
+    let (value, kind): (T, NumberKind) = decode_typed(cx, reader)?;
+
+    match kind {
+        NumberKind::Float => Ok(value),
+        kind => Err(cx.message(format_args!("Expected float, got {:?}", kind))),
+    }
+}
+
 #[inline]
 fn encode_typed<C, W, T>(cx: C, mut writer: W, bits: u8, value: T) -> Result<(), C::Error>
 where
@@ -91,7 +124,11 @@ where
 }
 
 #[inline]
-pub(crate) fn decode_typed_signed<'de, C, R, T>(cx: C, reader: R) -> Result<T, C::Error>
+pub(crate) fn decode_typed_signed<'de, C, R, T>(
+    cx: C,
+    reader: R,
+    strict: bool,
+) -> Result<T, C::Error>
 where
     C: Context,
     R: Reader<'de>,
@@ -101,6 +138,7 @@ where
 
     match kind {
         NumberKind::Signed => Ok(zig::decode(value)),
+        kind if strict => Err(cx.message(format_args!("Expected signed number, got {:?}", kind))),
         NumberKind::Unsigned => {
             let Ok(value) = T::try_from(value) else {
                 return Err(cx.message(format_args!("Unsigned value outside of signed range")));
@@ -0,0 +1,251 @@
+//! Lossy decoding of descriptive payloads into a [`Value`].
+
+use core::fmt;
+use core::marker::PhantomData;
+
+use rust_alloc::string::{String, ToString};
+use rust_alloc::vec::Vec;
+
+use crate::alloc::{self, System};
+use crate::context;
+use crate::value::Value;
+use crate::{Decode, Options};
+
+use super::de::SelfDecoder;
+use super::error::Error;
+use super::tag::{Kind, Mark, Tag};
+
+/// An issue encountered while decoding a corrupt payload with
+/// [`to_value_lossy`].
+///
+/// [`to_value_lossy`]: super::to_value_lossy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    offset: usize,
+    message: String,
+}
+
+impl Issue {
+    /// The offset in the input at which the issue was encountered.
+    ///
+    /// This is the same offset as is stored in the [`Value::Invalid`]
+    /// placeholder which was substituted for the value.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// A description of the issue.
+    #[inline]
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for Issue {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (at offset {})", self.message, self.offset)
+    }
+}
+
+/// The maximum depth of nested values which are decoded.
+const MAX_DEPTH: usize = 256;
+
+/// Walks a descriptive payload, decoding everything that can be decoded.
+pub(crate) struct Lossy<'de, const OPT: Options, M> {
+    bytes: &'de [u8],
+    pos: usize,
+    depth: usize,
+    issues: Vec<Issue>,
+    _marker: PhantomData<M>,
+}
+
+impl<'de, const OPT: Options, M> Lossy<'de, OPT, M>
+where
+    M: 'static,
+{
+    pub(crate) fn new(bytes: &'de [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            depth: 0,
+            issues: Vec::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Decode the payload, returning the value and the issues encountered.
+    pub(crate) fn decode(mut self) -> (Value<System>, Vec<Issue>) {
+        let (value, _) = self.value();
+        (value, self.issues)
+    }
+
+    /// Decode a single value.
+    ///
+    /// The returned boolean indicates if the walk can continue. Containers
+    /// only carry the number of elements they contain, so if the extent of a
+    /// value can't be determined nothing that follows it can be recovered
+    /// either.
+    fn value(&mut self) -> (Value<System>, bool) {
+        let start = self.pos;
+
+        // The payload is walked recursively, so deeply nested input would
+        // otherwise exhaust the stack.
+        if self.depth == MAX_DEPTH {
+            return self.fatal(
+                start,
+                format_args!("Values nested more than {MAX_DEPTH} levels deep"),
+            );
+        }
+
+        self.depth += 1;
+        let output = self.value_at(start);
+        self.depth -= 1;
+        output
+    }
+
+    /// Decode a single value starting at `start`.
+    fn value_at(&mut self, start: usize) -> (Value<System>, bool) {
+        let Some(&byte) = self.bytes.get(start) else {
+            return self.fatal(start, "Unexpected end of input");
+        };
+
+        let tag = Tag::from_byte(byte);
+
+        match tag.kind() {
+            Kind::Sequence => {
+                let len = match self.len(tag) {
+                    Ok(len) => len,
+                    Err(error) => return self.fatal(start, error),
+                };
+
+                let mut values = alloc::Vec::new_in(System::new());
+
+                for _ in 0..len {
+                    let (value, ok) = self.value();
+
+                    if values.push(value).is_err() {
+                        return self.fatal(start, "Allocation failed");
+                    }
+
+                    if !ok {
+                        return (Value::Sequence(values), false);
+                    }
+                }
+
+                (Value::Sequence(values), true)
+            }
+            Kind::Map => {
+                let len = match self.len(tag) {
+                    Ok(len) => len,
+                    Err(error) => return self.fatal(start, error),
+                };
+
+                let mut entries = alloc::Vec::new_in(System::new());
+
+                for _ in 0..len {
+                    let (entry, ok) = match self.value() {
+                        (key, true) => {
+                            let (value, ok) = self.value();
+                            ((key, value), ok)
+                        }
+                        (key, false) => ((key, Value::Invalid(self.pos)), false),
+                    };
+
+                    if entries.push(entry).is_err() {
+                        return self.fatal(start, "Allocation failed");
+                    }
+
+                    if !ok {
+                        return (Value::Map(entries), false);
+                    }
+                }
+
+                (Value::Map(entries), true)
+            }
+            Kind::Mark if matches!(tag.mark(), Mark::Variant) => {
+                self.pos += 1;
+
+                let (tag, ok) = self.value();
+
+                let (value, ok) = if ok {
+                    self.value()
+                } else {
+                    (Value::Invalid(self.pos), false)
+                };
+
+                match alloc::Box::new_in((tag, value), System::new()) {
+                    Ok(value) => (Value::Variant(value), ok),
+                    Err(..) => self.fatal(start, "Allocation failed"),
+                }
+            }
+            Kind::Mark if matches!(tag.mark(), Mark::Some) => {
+                self.pos += 1;
+
+                let (value, ok) = self.value();
+
+                match alloc::Box::new_in(value, System::new()) {
+                    Ok(value) => (Value::Option(Some(value)), ok),
+                    Err(..) => self.fatal(start, "Allocation failed"),
+                }
+            }
+            _ => self.leaf(start),
+        }
+    }
+
+    /// Decode a value which is not a container.
+    ///
+    /// Its extent is determined up front, so that if the value itself is
+    /// invalid it can be skipped over.
+    fn leaf(&mut self, start: usize) -> (Value<System>, bool) {
+        let cx = context::new().with_error::<Error>();
+
+        let mut reader = &self.bytes[start..];
+
+        if let Err(error) = SelfDecoder::<OPT, _, _, M>::new(&cx, &mut reader, false).skip_any() {
+            return self.fatal(start, error);
+        }
+
+        let end = self.bytes.len() - reader.len();
+        self.pos = end;
+
+        let decoder = SelfDecoder::<OPT, _, _, M>::new(&cx, &self.bytes[start..end], false);
+
+        match Value::decode(decoder) {
+            Ok(value) => (value, true),
+            Err(error) => {
+                self.issue(start, error);
+                (Value::Invalid(start), true)
+            }
+        }
+    }
+
+    /// Read the tag and length prefix of a container.
+    fn len(&mut self, tag: Tag) -> Result<usize, Error> {
+        let mut reader = &self.bytes[self.pos + 1..];
+
+        let len = match tag.data() {
+            Some(len) => len as usize,
+            None => {
+                let cx = context::new().with_error::<Error>();
+                crate::int::decode_usize::<_, _, OPT>(&cx, &mut reader)?
+            }
+        };
+
+        self.pos = self.bytes.len() - reader.len();
+        Ok(len)
+    }
+
+    fn fatal(&mut self, offset: usize, error: impl ToString) -> (Value<System>, bool) {
+        self.issue(offset, error);
+        (Value::Invalid(offset), false)
+    }
+
+    fn issue(&mut self, offset: usize, error: impl ToString) {
+        self.issues.push(Issue {
+            offset,
+            message: error.to_string(),
+        });
+    }
+}
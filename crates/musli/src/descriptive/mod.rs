@@ -81,6 +81,38 @@
 //!
 //! <br>
 //!
+//! ## Dynamic values
+//!
+//! Since every value carries its type, decoding into a [`Value`] retains the
+//! exact type which was encoded. A `u8` is decoded as [`Number::U8`] and an
+//! `f32` as [`Number::F32`], which is useful for tooling that needs to report
+//! exactly what is on the wire.
+//!
+//! Note that `usize` and `isize` don't have tags of their own, and are encoded
+//! as the fixed-width integer of the same size on the platform which encoded
+//! them.
+//!
+//! ```
+//! use musli::alloc::System;
+//! use musli::value::{Number, Value};
+//!
+//! let bytes = musli::descriptive::to_vec(&(1u8, 2.0f32))?;
+//! let value: Value<System> = musli::descriptive::from_slice(&bytes)?;
+//!
+//! let Value::Sequence(values) = &value else {
+//!     panic!("expected sequence");
+//! };
+//!
+//! assert!(matches!(values[0], Value::Number(Number::U8(1))));
+//! assert!(matches!(values[1], Value::Number(Number::F32(..))));
+//! # Ok::<_, musli::descriptive::Error>(())
+//! ```
+//!
+//! [`Number::U8`]: crate::value::Number::U8
+//! [`Number::F32`]: crate::value::Number::F32
+//!
+//! <br>
+//!
 //! ## Implementation details
 //!
 //! Each field is prefix *typed* with a single byte tag that describes exactly
@@ -97,6 +129,10 @@ mod en;
 mod encoding;
 mod error;
 mod integer_encoding;
+#[cfg(feature = "alloc")]
+mod lossy;
+#[cfg(feature = "alloc")]
+mod split;
 mod tag;
 
 #[cfg(feature = "test")]
@@ -109,6 +145,10 @@ pub mod test;
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::encoding::to_value_lossy;
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[doc(inline)]
@@ -119,11 +159,22 @@ pub use self::encoding::to_vec;
 pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_slice_partial, to_fixed_bytes, to_slice,
+    validate,
+};
 #[doc(inline)]
-pub use self::encoding::{Encoding, DEFAULT, OPTIONS};
+pub use self::encoding::{Encoding, DEFAULT, FORMAT_VERSION, OPTIONS};
 #[doc(inline)]
 pub use self::error::Error;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::lossy::Issue;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::split::{length_of, split, Split};
 
 /// The maximum length that can be inlined in the tag without adding additional
 /// data to the wire format.
@@ -0,0 +1,107 @@
+use core::fmt;
+use core::iter::FusedIterator;
+
+use crate::de::Decoder;
+use crate::mode::Binary;
+use crate::reader::SliceReader;
+
+use super::de::SelfDecoder;
+use super::encoding::OPTIONS;
+use super::error::Error;
+
+/// Get the number of bytes occupied by the first value in `bytes`.
+///
+/// Since each value in the descriptive format is self-delimiting, this only
+/// needs to skip over the value, it doesn't decode it into a type.
+///
+/// # Examples
+///
+/// ```
+/// use musli::descriptive;
+/// # use musli::descriptive::Error;
+///
+/// let mut data = descriptive::to_vec(&"Aristotle")?;
+/// let len = data.len();
+/// data.extend(descriptive::to_vec(&61u32)?);
+///
+/// assert_eq!(descriptive::length_of(&data)?, len);
+/// assert!(descriptive::length_of(&data[..len - 1]).is_err());
+/// # Ok::<_, Error>(())
+/// ```
+#[inline]
+pub fn length_of(bytes: &[u8]) -> Result<usize, Error> {
+    let cx = crate::context::new().with_error();
+    let mut reader = SliceReader::new(bytes);
+    SelfDecoder::<OPTIONS, _, _, Binary>::new(&cx, &mut reader, false).skip()?;
+    Ok(bytes.len() - reader.remaining())
+}
+
+/// Split `bytes` into the byte ranges of each successive value stored in it.
+///
+/// This is useful for splitting apart batches of concatenated messages without
+/// decoding them. See [`length_of`] for how each value is delimited.
+///
+/// If a value can't be skipped over, such as when the data is truncated, the
+/// iterator produces an error and then ends.
+///
+/// # Examples
+///
+/// ```
+/// use musli::descriptive;
+/// # use musli::descriptive::Error;
+///
+/// let mut data = Vec::new();
+/// descriptive::encode(&mut data, &"Aristotle")?;
+/// descriptive::encode(&mut data, &61u32)?;
+///
+/// let values = descriptive::split(&data).collect::<Result<Vec<_>, _>>()?;
+/// assert_eq!(values.len(), 2);
+/// assert_eq!(descriptive::from_slice::<String>(values[0])?, "Aristotle");
+/// assert_eq!(descriptive::from_slice::<u32>(values[1])?, 61);
+/// # Ok::<_, Error>(())
+/// ```
+#[inline]
+pub fn split(bytes: &[u8]) -> Split<'_> {
+    Split { bytes }
+}
+
+/// Iterator over the values in a slice of bytes, constructed through
+/// [`split`].
+#[derive(Clone)]
+pub struct Split<'de> {
+    bytes: &'de [u8],
+}
+
+impl<'de> Iterator for Split<'de> {
+    type Item = Result<&'de [u8], Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() {
+            return None;
+        }
+
+        match length_of(self.bytes) {
+            Ok(len) => {
+                let (head, tail) = self.bytes.split_at(len);
+                self.bytes = tail;
+                Some(Ok(head))
+            }
+            Err(error) => {
+                self.bytes = &[];
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl FusedIterator for Split<'_> {}
+
+impl fmt::Debug for Split<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Split")
+            .field("remaining", &self.bytes.len())
+            .finish()
+    }
+}
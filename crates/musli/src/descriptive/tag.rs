@@ -25,9 +25,9 @@ pub(crate) enum Mark {
     Char = 0b101,
     /// A unit type.
     Unit = 0b110,
-    /// A reserved mark.
-    #[allow(unused)]
-    Reserved0 = 0b111,
+    /// The marker indicating a value with a semantic tag, which is followed by
+    /// the tag as a number and the value.
+    Tagged = 0b111,
 }
 
 /// The kind of a number.
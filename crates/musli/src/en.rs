@@ -18,8 +18,8 @@
 
 #[doc(inline)]
 pub use musli_core::en::{
-    Encode, EncodeBytes, EncodePacked, EncodeTrace, Encoder, EntriesEncoder, EntryEncoder,
-    MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
+    Encode, EncodeBytes, EncodeFlatten, EncodePacked, EncodeTrace, Encoder, EntriesEncoder,
+    EntryEncoder, MapEncoder, SequenceEncoder, TryFastEncode, VariantEncoder,
 };
 
 #[cfg(any(
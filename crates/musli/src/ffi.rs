@@ -0,0 +1,335 @@
+//! A minimal C ABI for decoding [`musli::wire`] payloads from non-Rust callers.
+//!
+//! The [`export_decode!`] macro generates `extern "C"` functions which take a
+//! pointer and length to a wire-encoded buffer, decode it using the default
+//! [`wire::Encoding`] and hand the result back to the caller in one of two
+//! ways:
+//!
+//! * As an **opaque handle**, which is a boxed value that the caller must free
+//!   through the generated free function. Any accessors for the value are
+//!   written by hand as regular `extern "C"` functions.
+//! * By filling a **caller-provided mirror**. This requires the type to be
+//!   [`Copy`] and marked with `#[repr(C)]`, so that the caller can declare an
+//!   identical struct on its side and own its memory.
+//!
+//! Every generated function returns a [`Status`]. If it is anything but
+//! [`Status::Ok`], a human-readable message describing the error can be
+//! retrieved on the same thread through [`musli_last_error`].
+//!
+//! None of the types involved use generics or Rust-specific layouts, so a
+//! header for the exported functions can be generated with [`cbindgen`].
+//!
+//! [`musli::wire`]: crate::wire
+//! [`wire::Encoding`]: crate::wire::Encoding
+//! [`export_decode!`]: crate::ffi::export_decode
+//! [`cbindgen`]: https://docs.rs/cbindgen
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::ffi::Status;
+//!
+//! #[derive(Encode, Decode)]
+//! pub struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! musli::ffi::export_decode!(Person, decode = example_decode_person, free = example_free_person);
+//!
+//! /// Get the age of a person.
+//! ///
+//! /// # Safety
+//! ///
+//! /// The person must have been returned by `example_decode_person`.
+//! #[no_mangle]
+//! pub unsafe extern "C" fn example_person_age(person: *const Person) -> u32 {
+//!     (*person).age
+//! }
+//!
+//! let bytes = musli::wire::to_vec(&Person { name: String::from("Aristotle"), age: 61 })?;
+//!
+//! let mut person = std::ptr::null_mut();
+//!
+//! unsafe {
+//!     assert_eq!(example_decode_person(bytes.as_ptr(), bytes.len(), &mut person), Status::Ok);
+//!     assert_eq!(example_person_age(person), 61);
+//!     example_free_person(person);
+//! }
+//! # Ok::<_, musli::wire::Error>(())
+//! ```
+
+#![cfg(feature = "ffi")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "ffi")))]
+
+use core::cell::RefCell;
+use core::ffi::c_char;
+use core::fmt;
+use core::ptr;
+use core::slice;
+
+use std::ffi::CString;
+use std::panic::{self, AssertUnwindSafe};
+
+use rust_alloc::boxed::Box;
+use rust_alloc::string::ToString;
+use rust_alloc::vec::Vec;
+
+use crate::alloc::System;
+use crate::mode::Binary;
+use crate::Decode;
+
+#[doc(inline)]
+pub use crate::__export_decode as export_decode;
+
+/// The status returned by exported functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Status {
+    /// The operation succeeded.
+    Ok = 0,
+    /// A pointer argument which must not be null was null.
+    NullPointer = 1,
+    /// The payload could not be decoded.
+    Decode = 2,
+    /// Decoding panicked.
+    Panic = 3,
+}
+
+std::thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Get the message of the last error which occurred on the current thread, or
+/// a null pointer if there is none.
+///
+/// The returned string is nul-terminated and remains valid until the next call
+/// to an exported function on the same thread.
+#[no_mangle]
+pub extern "C" fn musli_last_error() -> *const c_char {
+    LAST_ERROR.with(|error| match &*error.borrow() {
+        Some(message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
+
+/// Clear the last error which occurred on the current thread.
+#[no_mangle]
+pub extern "C" fn musli_clear_last_error() {
+    set_last_error(None);
+}
+
+/// Decode a value of type `T` from a wire-encoded buffer and store it in a
+/// newly allocated box, writing its pointer to `out`.
+///
+/// The value must be freed with [`free`]. This is what the `decode` function
+/// generated by [`export_decode!`] calls.
+///
+/// # Safety
+///
+/// If `len` is non-zero, `ptr` must point to `len` bytes which are valid for
+/// reads. `out` must either be null or valid for writes.
+pub unsafe fn decode_boxed<T>(ptr: *const u8, len: usize, out: *mut *mut T) -> Status
+where
+    T: for<'de> Decode<'de, Binary, System>,
+{
+    if out.is_null() {
+        return null_pointer("out");
+    }
+
+    match decode::<T>(ptr, len) {
+        Ok(value) => {
+            out.write(Box::into_raw(Box::new(value)));
+            Status::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Decode a value of type `T` from a wire-encoded buffer into the
+/// caller-provided `out`.
+///
+/// This is what the `decode_into` function generated by [`export_decode!`]
+/// calls. Nothing is written to `out` unless decoding succeeds.
+///
+/// # Safety
+///
+/// If `len` is non-zero, `ptr` must point to `len` bytes which are valid for
+/// reads. `out` must either be null or valid for writes, and `T` must have a
+/// layout which matches the one declared by the caller, which in practice means
+/// that it should be `#[repr(C)]`.
+pub unsafe fn decode_into<T>(ptr: *const u8, len: usize, out: *mut T) -> Status
+where
+    T: Copy + for<'de> Decode<'de, Binary, System>,
+{
+    if out.is_null() {
+        return null_pointer("out");
+    }
+
+    match decode::<T>(ptr, len) {
+        Ok(value) => {
+            out.write(value);
+            Status::Ok
+        }
+        Err(status) => status,
+    }
+}
+
+/// Free a value previously returned by [`decode_boxed`].
+///
+/// Freeing a null pointer does nothing.
+///
+/// # Safety
+///
+/// The value must have been allocated by [`decode_boxed`] with the same `T`,
+/// and must not be used after it has been freed.
+pub unsafe fn free<T>(value: *mut T) {
+    if !value.is_null() {
+        drop(Box::from_raw(value));
+    }
+}
+
+unsafe fn decode<T>(ptr: *const u8, len: usize) -> Result<T, Status>
+where
+    T: for<'de> Decode<'de, Binary, System>,
+{
+    set_last_error(None);
+
+    let bytes = if len == 0 {
+        &[][..]
+    } else if ptr.is_null() {
+        return Err(null_pointer("ptr"));
+    } else {
+        slice::from_raw_parts(ptr, len)
+    };
+
+    match panic::catch_unwind(AssertUnwindSafe(|| crate::wire::from_slice::<T>(bytes))) {
+        Ok(Ok(value)) => Ok(value),
+        Ok(Err(error)) => {
+            set_last_error(Some(&error));
+            Err(Status::Decode)
+        }
+        Err(..) => {
+            set_last_error(Some(&"Decoding panicked"));
+            Err(Status::Panic)
+        }
+    }
+}
+
+fn null_pointer(name: &str) -> Status {
+    set_last_error(Some(&format_args!("Argument `{name}` is a null pointer")));
+    Status::NullPointer
+}
+
+fn set_last_error(message: Option<&dyn fmt::Display>) {
+    let message = message.map(|message| {
+        let mut bytes = Vec::from(message.to_string());
+        bytes.retain(|&b| b != 0);
+        // SAFETY: All nul bytes were just removed.
+        unsafe { CString::from_vec_unchecked(bytes) }
+    });
+
+    LAST_ERROR.with(|error| *error.borrow_mut() = message);
+}
+
+/// Export `extern "C"` functions for decoding a wire-encoded type.
+///
+/// The names of the exported functions are specified explicitly, since they
+/// share one namespace with every other exported symbol in the final binary.
+/// By convention they should be prefixed like `musli_wire_decode_<type>`.
+///
+/// Using `decode` and `free` generates functions which hand out the decoded
+/// value as an opaque handle:
+///
+/// ```c
+/// Status decode(const uint8_t *ptr, size_t len, Type **out);
+/// void free(Type *value);
+/// ```
+///
+/// Using `decode_into` generates a function which fills a caller-provided
+/// mirror of the type. This requires the type to be [`Copy`], and it should be
+/// marked with `#[repr(C)]`:
+///
+/// ```c
+/// Status decode_into(const uint8_t *ptr, size_t len, Type *out);
+/// ```
+///
+/// See the [`ffi` module][crate::ffi] for more information.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::ffi::Status;
+///
+/// #[derive(Clone, Copy, Encode, Decode)]
+/// #[repr(C)]
+/// pub struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// musli::ffi::export_decode!(Point, decode_into = example_decode_point);
+///
+/// let bytes = musli::wire::to_vec(&Point { x: 1.0, y: 2.0 })?;
+///
+/// let mut point = Point { x: 0.0, y: 0.0 };
+///
+/// unsafe {
+///     assert_eq!(example_decode_point(bytes.as_ptr(), bytes.len(), &mut point), Status::Ok);
+/// }
+///
+/// assert_eq!(point.x, 1.0);
+/// assert_eq!(point.y, 2.0);
+/// # Ok::<_, musli::wire::Error>(())
+/// ```
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __export_decode {
+    ($ty:ty, decode = $decode:ident, free = $free:ident $(,)?) => {
+        /// Decode a wire-encoded value into an opaque handle.
+        ///
+        /// # Safety
+        ///
+        /// If `len` is non-zero, `ptr` must point to `len` readable bytes. The
+        /// value written to `out` must be freed with the corresponding free
+        /// function.
+        #[no_mangle]
+        pub unsafe extern "C" fn $decode(
+            ptr: *const u8,
+            len: usize,
+            out: *mut *mut $ty,
+        ) -> $crate::ffi::Status {
+            $crate::ffi::decode_boxed::<$ty>(ptr, len, out)
+        }
+
+        /// Free a value returned by the corresponding decode function.
+        ///
+        /// # Safety
+        ///
+        /// The value must not be used after it has been freed.
+        #[no_mangle]
+        pub unsafe extern "C" fn $free(value: *mut $ty) {
+            $crate::ffi::free::<$ty>(value)
+        }
+    };
+
+    ($ty:ty, decode_into = $decode:ident $(,)?) => {
+        /// Decode a wire-encoded value into a caller-provided value.
+        ///
+        /// # Safety
+        ///
+        /// If `len` is non-zero, `ptr` must point to `len` readable bytes.
+        /// `out` must be valid for writes.
+        #[no_mangle]
+        pub unsafe extern "C" fn $decode(
+            ptr: *const u8,
+            len: usize,
+            out: *mut $ty,
+        ) -> $crate::ffi::Status {
+            $crate::ffi::decode_into::<$ty>(ptr, len, out)
+        }
+    };
+}
@@ -14,6 +14,28 @@ use crate::Context;
 
 /// A fixed-size bytes storage which keeps track of how much has been
 /// initialized.
+///
+/// Only the bytes which have been written are ever exposed, so reading through
+/// [`as_slice()`], [`AsRef<[u8]>`] or by dereferencing never observes the
+/// uninitialized tail of the storage. The number of bytes written is available
+/// through [`len()`].
+///
+/// [`as_slice()`]: Self::as_slice
+/// [`len()`]: Self::len
+///
+/// # Examples
+///
+/// ```
+/// use musli::FixedBytes;
+///
+/// let mut bytes = FixedBytes::<16>::new();
+/// assert!(bytes.extend_from_slice(b"hello"));
+///
+/// assert_eq!(bytes.len(), 5);
+/// assert_eq!(bytes.as_slice(), b"hello");
+/// assert_eq!(bytes.as_ref(), b"hello");
+/// assert_eq!(bytes.into_bytes(), None);
+/// ```
 pub struct FixedBytes<const N: usize> {
     /// Data storage.
     data: [MaybeUninit<u8>; N],
@@ -26,7 +48,8 @@ impl<const N: usize> FixedBytes<N> {
     #[inline]
     pub const fn new() -> Self {
         Self {
-            // SAFETY: MaybeUnint::uninit_array is not stable.
+            // SAFETY: An uninitialized `[MaybeUninit<_>; N]` is valid.
+            // MaybeUninit::uninit_array is not stable.
             data: unsafe { MaybeUninit::<[MaybeUninit<u8>; N]>::uninit().assume_init() },
             init: 0,
         }
@@ -36,13 +59,13 @@ impl<const N: usize> FixedBytes<N> {
     #[inline]
     pub fn with_capacity(capacity: usize) -> Self {
         assert!(
-            capacity < N,
+            capacity <= N,
             "Requested capacity {capacity} is larger than {N}"
         );
         Self::new()
     }
 
-    /// Get the length of the collection.
+    /// Get the number of bytes which have been written.
     #[inline]
     pub const fn len(&self) -> usize {
         self.init
@@ -81,7 +104,17 @@ impl<const N: usize> FixedBytes<N> {
         }
     }
 
-    /// Coerce into the slice of initialized memory which is present.
+    /// Coerce into the slice of bytes which have been written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::FixedBytes;
+    ///
+    /// let bytes: FixedBytes<128> = musli::storage::to_fixed_bytes(&42u32)?;
+    /// assert_eq!(bytes.as_slice(), &[42]);
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
     #[inline]
     pub fn as_slice(&self) -> &[u8] {
         if self.init == 0 {
@@ -105,6 +138,26 @@ impl<const N: usize> FixedBytes<N> {
         unsafe { core::slice::from_raw_parts_mut(self.data.as_mut_ptr().cast(), self.init) }
     }
 
+    /// Copy the bytes which have been written into a [`Vec`].
+    ///
+    /// [`Vec`]: rust_alloc::vec::Vec
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::FixedBytes;
+    ///
+    /// let bytes: FixedBytes<128> = musli::storage::to_fixed_bytes(&42u32)?;
+    /// assert_eq!(bytes.into_vec(), vec![42]);
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn into_vec(self) -> rust_alloc::vec::Vec<u8> {
+        self.as_slice().to_vec()
+    }
+
     /// Try and push a single byte.
     #[inline]
     pub fn push(&mut self, value: u8) -> bool {
@@ -174,6 +227,27 @@ impl<const N: usize> DerefMut for FixedBytes<N> {
     }
 }
 
+impl<const N: usize> AsRef<[u8]> for FixedBytes<N> {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl<const N: usize> AsMut<[u8]> for FixedBytes<N> {
+    #[inline]
+    fn as_mut(&mut self) -> &mut [u8] {
+        self.as_mut_slice()
+    }
+}
+
+impl<const N: usize> fmt::Debug for FixedBytes<N> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.as_slice().fmt(f)
+    }
+}
+
 impl<const N: usize> Default for FixedBytes<N> {
     #[inline]
     fn default() -> Self {
@@ -40,6 +40,29 @@ where
     Ok(value)
 }
 
+/// Skip over a variable-length integer which would decode into `T`, without
+/// accumulating its value.
+#[cfg(any(feature = "wire", feature = "descriptive"))]
+#[inline]
+pub fn skip<'de, C, R, T>(cx: C, mut r: R) -> Result<(), C::Error>
+where
+    C: Context,
+    R: Reader<'de>,
+    T: int::Unsigned,
+{
+    let mut shift = 0u32;
+
+    while r.read_byte(cx)? & CONT_BYTE == CONT_BYTE {
+        shift += 7;
+
+        if shift >= T::BITS {
+            return Err(cx.message("Bits overflow"));
+        }
+    }
+
+    Ok(())
+}
+
 /// Encode the given length using variable length encoding.
 #[inline]
 pub fn encode<C, W, T>(cx: C, mut w: W, mut value: T) -> Result<(), C::Error>
@@ -0,0 +1,560 @@
+//! Rewriting of JSON into the canonical form described by [RFC 8785].
+//!
+//! See [`Encoding::with_canonical`] for which parts of the specification are
+//! implemented.
+//!
+//! [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+//! [`Encoding::with_canonical`]: super::Encoding::with_canonical
+
+use core::cmp::Ordering;
+use core::str;
+
+use crate::alloc::Vec;
+use crate::{Allocator, Context, Writer};
+
+/// The largest number of digits which can appear in a number produced by
+/// `ryu` for an `f64`.
+const MAX_DIGITS: usize = 32;
+
+/// Rewrite the JSON in `input` into its canonical form and write it to `out`.
+pub(crate) fn canonicalize<C, W>(cx: C, input: &[u8], mut out: W) -> Result<(), C::Error>
+where
+    C: Context,
+    W: Writer,
+{
+    let mut p = Canonicalizer { cx, input, pos: 0 };
+    p.value(Some(&mut out))?;
+    p.whitespace();
+
+    if p.pos != input.len() {
+        return Err(cx.message("Trailing data after JSON value"));
+    }
+
+    Ok(())
+}
+
+struct Canonicalizer<'a, C> {
+    cx: C,
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl<C> Canonicalizer<'_, C>
+where
+    C: Context,
+{
+    #[inline]
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    #[inline]
+    fn whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), C::Error> {
+        if self.peek() != Some(expected) {
+            return Err(self
+                .cx
+                .message(format_args!("Expected `{}`", expected as char)));
+        }
+
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Process a single value, writing its canonical form to `out` if
+    /// present.
+    fn value<W>(&mut self, out: Option<&mut W>) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer,
+    {
+        self.whitespace();
+
+        match self.peek() {
+            Some(b'{') => self.object(out),
+            Some(b'[') => self.array(out),
+            Some(b'"') => {
+                let mut buf = Vec::new_in(self.cx.alloc());
+                self.string(&mut buf)?;
+
+                if let Some(out) = out {
+                    write_string(self.cx, out, buf.as_slice())?;
+                }
+
+                Ok(())
+            }
+            Some(b't') => self.literal(b"true", out),
+            Some(b'f') => self.literal(b"false", out),
+            Some(b'n') => self.literal(b"null", out),
+            Some(b'-' | b'0'..=b'9') => self.number(out),
+            _ => Err(self.cx.message("Expected JSON value")),
+        }
+    }
+
+    fn literal<W>(&mut self, literal: &'static [u8], out: Option<&mut W>) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer,
+    {
+        if !self.input[self.pos..].starts_with(literal) {
+            return Err(self.cx.message("Expected JSON literal"));
+        }
+
+        self.pos += literal.len();
+
+        if let Some(out) = out {
+            out.write_bytes(self.cx, literal)?;
+        }
+
+        Ok(())
+    }
+
+    fn object<W>(&mut self, out: Option<&mut W>) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer,
+    {
+        self.expect(b'{')?;
+
+        // Unescaped keys are stored back-to-back in a single buffer, and each
+        // entry records the range of its key and the span of its value.
+        let mut keys = Vec::new_in(self.cx.alloc());
+        let mut entries = Vec::<[usize; 4], _>::new_in(self.cx.alloc());
+
+        self.whitespace();
+
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.whitespace();
+                let key_start = keys.len();
+                self.string(&mut keys)?;
+                let key_end = keys.len();
+                self.whitespace();
+                self.expect(b':')?;
+                self.whitespace();
+
+                let value_start = self.pos;
+                self.value::<W>(None)?;
+
+                entries
+                    .push([key_start, key_end, value_start, self.pos])
+                    .map_err(self.cx.map())?;
+
+                self.whitespace();
+
+                match self.peek() {
+                    Some(b',') => {
+                        self.pos += 1;
+                    }
+                    Some(b'}') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.cx.message("Expected `,` or `}`")),
+                }
+            }
+        }
+
+        let Some(out) = out else {
+            return Ok(());
+        };
+
+        let keys = keys.as_slice();
+        let key = |&[start, end, ..]: &[usize; 4]| &keys[start..end];
+
+        entries
+            .as_slice_mut()
+            .sort_unstable_by(|a, b| utf16_cmp(key(a), key(b)));
+
+        if let Some([first, ..]) = entries
+            .as_slice()
+            .windows(2)
+            .find(|w| key(&w[0]) == key(&w[1]))
+        {
+            let name = str::from_utf8(key(first)).unwrap_or_default();
+            return Err(self
+                .cx
+                .message(format_args!("Duplicate object key `{name}`")));
+        }
+
+        out.write_byte(self.cx, b'{')?;
+
+        for (index, entry) in entries.as_slice().iter().enumerate() {
+            if index > 0 {
+                out.write_byte(self.cx, b',')?;
+            }
+
+            write_string(self.cx, out, key(entry))?;
+            out.write_byte(self.cx, b':')?;
+
+            let mut value = Canonicalizer {
+                cx: self.cx,
+                input: &self.input[..entry[3]],
+                pos: entry[2],
+            };
+
+            value.value(Some(&mut *out))?;
+        }
+
+        out.write_byte(self.cx, b'}')?;
+        Ok(())
+    }
+
+    fn array<W>(&mut self, mut out: Option<&mut W>) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer,
+    {
+        self.expect(b'[')?;
+
+        if let Some(out) = out.as_deref_mut() {
+            out.write_byte(self.cx, b'[')?;
+        }
+
+        self.whitespace();
+
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+        } else {
+            loop {
+                self.value(out.as_deref_mut())?;
+                self.whitespace();
+
+                match self.peek() {
+                    Some(b',') => {
+                        self.pos += 1;
+
+                        if let Some(out) = out.as_deref_mut() {
+                            out.write_byte(self.cx, b',')?;
+                        }
+                    }
+                    Some(b']') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    _ => return Err(self.cx.message("Expected `,` or `]`")),
+                }
+            }
+        }
+
+        if let Some(out) = out {
+            out.write_byte(self.cx, b']')?;
+        }
+
+        Ok(())
+    }
+
+    /// Decode a string, appending its unescaped contents to `buf`.
+    fn string<A>(&mut self, buf: &mut Vec<u8, A>) -> Result<(), C::Error>
+    where
+        A: Allocator,
+    {
+        self.expect(b'"')?;
+
+        loop {
+            let start = self.pos;
+
+            while let Some(b) = self.peek() {
+                if matches!(b, b'"' | b'\\' | 0..0x20) {
+                    break;
+                }
+
+                self.pos += 1;
+            }
+
+            let run = &self.input[start..self.pos];
+
+            if str::from_utf8(run).is_err() {
+                return Err(self.cx.message("Invalid UTF-8 in string"));
+            }
+
+            buf.extend_from_slice(run).map_err(self.cx.map())?;
+
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(());
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    self.escape(buf)?;
+                }
+                Some(..) => return Err(self.cx.message("Unescaped control character in string")),
+                None => return Err(self.cx.message("Unterminated string")),
+            }
+        }
+    }
+
+    fn escape<A>(&mut self, buf: &mut Vec<u8, A>) -> Result<(), C::Error>
+    where
+        A: Allocator,
+    {
+        let Some(b) = self.peek() else {
+            return Err(self.cx.message("Unterminated escape"));
+        };
+
+        self.pos += 1;
+
+        let c = match b {
+            b'"' => '"',
+            b'\\' => '\\',
+            b'/' => '/',
+            b'b' => '\x08',
+            b'f' => '\x0c',
+            b'n' => '\n',
+            b'r' => '\r',
+            b't' => '\t',
+            b'u' => {
+                let mut c = self.hex4()?;
+
+                if (0xd800..0xdc00).contains(&c) {
+                    self.expect(b'\\')?;
+                    self.expect(b'u')?;
+                    let low = self.hex4()?;
+
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return Err(self.cx.message("Invalid low surrogate in escape"));
+                    }
+
+                    c = 0x10000 + ((c - 0xd800) << 10) + (low - 0xdc00);
+                }
+
+                let Some(c) = char::from_u32(c) else {
+                    return Err(self.cx.message("Invalid unicode escape"));
+                };
+
+                c
+            }
+            _ => return Err(self.cx.message("Invalid escape")),
+        };
+
+        let mut bytes = [0; 4];
+        buf.extend_from_slice(c.encode_utf8(&mut bytes).as_bytes())
+            .map_err(self.cx.map())?;
+        Ok(())
+    }
+
+    fn hex4(&mut self) -> Result<u32, C::Error> {
+        let mut value = 0;
+
+        for _ in 0..4 {
+            let digit = match self.peek() {
+                Some(b @ b'0'..=b'9') => b - b'0',
+                Some(b @ b'a'..=b'f') => b - b'a' + 10,
+                Some(b @ b'A'..=b'F') => b - b'A' + 10,
+                _ => return Err(self.cx.message("Invalid unicode escape")),
+            };
+
+            self.pos += 1;
+            value = (value << 4) | u32::from(digit);
+        }
+
+        Ok(value)
+    }
+
+    fn number<W>(&mut self, out: Option<&mut W>) -> Result<(), C::Error>
+    where
+        W: ?Sized + Writer,
+    {
+        let start = self.pos;
+
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+
+        let digits = self.pos;
+        let mut integer = true;
+
+        while let Some(b) = self.peek() {
+            match b {
+                b'0'..=b'9' => {}
+                b'.' | b'e' | b'E' | b'+' | b'-' => {
+                    integer = false;
+                }
+                _ => break,
+            }
+
+            self.pos += 1;
+        }
+
+        if self.pos == digits {
+            return Err(self.cx.message("Expected digits in number"));
+        }
+
+        let Some(out) = out else {
+            return Ok(());
+        };
+
+        let text = &self.input[start..self.pos];
+
+        if integer {
+            // Integers are written as-is to avoid losing precision for values
+            // which can't be represented by an `f64`.
+            if text == b"-0" {
+                return out.write_byte(self.cx, b'0');
+            }
+
+            return out.write_bytes(self.cx, text);
+        }
+
+        let Ok(value) = str::from_utf8(text).unwrap_or_default().parse::<f64>() else {
+            return Err(self.cx.message("Invalid number"));
+        };
+
+        if !value.is_finite() {
+            return Err(self.cx.message("Number is out of range"));
+        }
+
+        write_f64(self.cx, out, value)
+    }
+}
+
+/// Compare two UTF-8 strings by their UTF-16 code units.
+fn utf16_cmp(a: &[u8], b: &[u8]) -> Ordering {
+    match (str::from_utf8(a), str::from_utf8(b)) {
+        (Ok(a), Ok(b)) => a.encode_utf16().cmp(b.encode_utf16()),
+        _ => a.cmp(b),
+    }
+}
+
+/// Write a string, only escaping what is strictly necessary.
+fn write_string<C, W>(cx: C, out: &mut W, bytes: &[u8]) -> Result<(), C::Error>
+where
+    C: Context,
+    W: ?Sized + Writer,
+{
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    out.write_byte(cx, b'"')?;
+
+    let mut start = 0;
+
+    for (index, &b) in bytes.iter().enumerate() {
+        let unicode;
+
+        let escape: &[u8] = match b {
+            b'"' => b"\\\"",
+            b'\\' => b"\\\\",
+            0x08 => b"\\b",
+            0x0c => b"\\f",
+            b'\n' => b"\\n",
+            b'\r' => b"\\r",
+            b'\t' => b"\\t",
+            0..0x20 => {
+                unicode = [
+                    b'\\',
+                    b'u',
+                    b'0',
+                    b'0',
+                    HEX[usize::from(b >> 4)],
+                    HEX[usize::from(b & 0xf)],
+                ];
+
+                &unicode
+            }
+            _ => continue,
+        };
+
+        out.write_bytes(cx, &bytes[start..index])?;
+        out.write_bytes(cx, escape)?;
+        start = index + 1;
+    }
+
+    out.write_bytes(cx, &bytes[start..])?;
+    out.write_byte(cx, b'"')?;
+    Ok(())
+}
+
+/// Write a number the way ECMAScript serializes a `Number`.
+fn write_f64<C, W>(cx: C, out: &mut W, value: f64) -> Result<(), C::Error>
+where
+    C: Context,
+    W: ?Sized + Writer,
+{
+    if value == 0.0 {
+        return out.write_byte(cx, b'0');
+    }
+
+    if value.is_sign_negative() {
+        out.write_byte(cx, b'-')?;
+    }
+
+    let mut buffer = ryu::Buffer::new();
+    let formatted = buffer.format_finite(value.abs());
+
+    let (mantissa, exponent) = match formatted.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i32>().unwrap_or_default()),
+        None => (formatted, 0),
+    };
+
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+
+    // Collect the significant digits, and the position `n` of the decimal
+    // point relative to the first one.
+    let mut digits = [0u8; MAX_DIGITS];
+    let mut len = 0;
+
+    for &b in integer.as_bytes().iter().chain(fraction.as_bytes()) {
+        if len == MAX_DIGITS {
+            return Err(cx.message("Number has too many digits"));
+        }
+
+        digits[len] = b;
+        len += 1;
+    }
+
+    let mut n = integer.len() as i32 + exponent;
+    let mut digits = &digits[..len];
+
+    while let [b'0', rest @ ..] = digits {
+        digits = rest;
+        n -= 1;
+    }
+
+    while let [rest @ .., b'0'] = digits {
+        digits = rest;
+    }
+
+    let k = digits.len() as i32;
+
+    if k <= n && n <= 21 {
+        out.write_bytes(cx, digits)?;
+
+        for _ in k..n {
+            out.write_byte(cx, b'0')?;
+        }
+    } else if 0 < n && n <= 21 {
+        let (integer, fraction) = digits.split_at(n as usize);
+        out.write_bytes(cx, integer)?;
+        out.write_byte(cx, b'.')?;
+        out.write_bytes(cx, fraction)?;
+    } else if -6 < n && n <= 0 {
+        out.write_bytes(cx, b"0.")?;
+
+        for _ in n..0 {
+            out.write_byte(cx, b'0')?;
+        }
+
+        out.write_bytes(cx, digits)?;
+    } else {
+        let (first, rest) = digits.split_at(1);
+        out.write_bytes(cx, first)?;
+
+        if !rest.is_empty() {
+            out.write_byte(cx, b'.')?;
+            out.write_bytes(cx, rest)?;
+        }
+
+        let exponent = n - 1;
+        out.write_byte(cx, b'e')?;
+        out.write_byte(cx, if exponent < 0 { b'-' } else { b'+' })?;
+
+        let mut buffer = itoa::Buffer::new();
+        out.write_bytes(cx, buffer.format(exponent.unsigned_abs()).as_bytes())?;
+    }
+
+    Ok(())
+}
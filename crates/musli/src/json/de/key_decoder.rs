@@ -3,15 +3,23 @@ use core::marker::PhantomData;
 
 use crate::alloc::Vec;
 use crate::de::{Decoder, SizeHint, Skip, UnsizedVisitor, Visitor};
+use crate::hint::Capabilities;
 use crate::Context;
 
 use super::super::parser::{Parser, Token};
-use super::{JsonDecoder, KeySignedVisitor, KeyUnsignedVisitor, StringReference};
+use super::{
+    JsonDecoder, JsonObjectDecoder, JsonSequenceDecoder, JsonVariantDecoder, KeySignedVisitor,
+    KeyUnsignedVisitor, StringReference,
+};
 
 /// A JSON object key decoder for Müsli.
+///
+/// Keys which aren't strings are decoded as plain values, since maps with
+/// such keys are encoded as an array of `[key, value]` pairs.
 pub(crate) struct JsonKeyDecoder<P, C, M> {
     cx: C,
     parser: P,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
@@ -23,14 +31,20 @@ where
 {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, parser: P) -> Self {
+    pub(crate) fn new(cx: C, parser: P, reject_duplicate_keys: bool) -> Self {
         Self {
             cx,
             parser,
+            reject_duplicate_keys,
             _marker: PhantomData,
         }
     }
 
+    #[inline]
+    fn into_value(self) -> JsonDecoder<P, C, M> {
+        JsonDecoder::new(self.cx, self.parser, self.reject_duplicate_keys)
+    }
+
     #[inline]
     fn decode_escaped_bytes<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
     where
@@ -45,6 +59,16 @@ where
     }
 }
 
+macro_rules! decode_integer {
+    ($slf:ident, $method:ident, $visitor:ident) => {{
+        if matches!($slf.parser.lex($slf.cx), Token::Number) {
+            return $slf.into_value().$method();
+        }
+
+        $slf.decode_escaped_bytes($visitor::new())
+    }};
+}
+
 #[crate::decoder(crate)]
 impl<'de, P, C, M> Decoder<'de> for JsonKeyDecoder<P, C, M>
 where
@@ -60,6 +84,12 @@ where
         = JsonKeyDecoder<P, U, M>
     where
         U: Context<Allocator = Self::Allocator>;
+    type DecodePack = JsonSequenceDecoder<P, C, M>;
+    type DecodeSequence = JsonSequenceDecoder<P, C, M>;
+    type DecodeMap = JsonObjectDecoder<P, C, M>;
+    type DecodeMapEntries = JsonObjectDecoder<P, C, M>;
+    type DecodeSome = JsonDecoder<P, C, M>;
+    type DecodeVariant = JsonVariantDecoder<P, C, M>;
 
     #[inline]
     fn cx(&self) -> Self::Cx {
@@ -71,7 +101,11 @@ where
     where
         U: Context<Allocator = Self::Allocator>,
     {
-        Ok(JsonKeyDecoder::new(cx, self.parser))
+        Ok(JsonKeyDecoder::new(
+            cx,
+            self.parser,
+            self.reject_duplicate_keys,
+        ))
     }
 
     #[inline]
@@ -79,9 +113,16 @@ where
         write!(f, "value that can be decoded from a object key")
     }
 
+    const CAPABILITIES: Capabilities = super::JSON_CAPABILITIES;
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Self::CAPABILITIES
+    }
+
     #[inline]
     fn skip(self) -> Result<(), C::Error> {
-        JsonDecoder::<_, _, M>::new(self.cx, self.parser).skip()
+        JsonDecoder::<_, _, M>::new(self.cx, self.parser, self.reject_duplicate_keys).skip()
     }
 
     #[inline]
@@ -91,63 +132,83 @@ where
     }
 
     #[inline]
-    fn decode_u8(self) -> Result<u8, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_empty(self) -> Result<(), C::Error> {
+        self.into_value().decode_empty()
+    }
+
+    #[inline]
+    fn decode_bool(self) -> Result<bool, C::Error> {
+        self.into_value().decode_bool()
     }
 
     #[inline]
-    fn decode_u16(self) -> Result<u16, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_u8(mut self) -> Result<u8, C::Error> {
+        decode_integer!(self, decode_u8, KeyUnsignedVisitor)
     }
 
     #[inline]
-    fn decode_u32(self) -> Result<u32, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_u16(mut self) -> Result<u16, C::Error> {
+        decode_integer!(self, decode_u16, KeyUnsignedVisitor)
     }
 
     #[inline]
-    fn decode_u64(self) -> Result<u64, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_u32(mut self) -> Result<u32, C::Error> {
+        decode_integer!(self, decode_u32, KeyUnsignedVisitor)
     }
 
     #[inline]
-    fn decode_u128(self) -> Result<u128, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_u64(mut self) -> Result<u64, C::Error> {
+        decode_integer!(self, decode_u64, KeyUnsignedVisitor)
     }
 
     #[inline]
-    fn decode_i8(self) -> Result<i8, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_u128(mut self) -> Result<u128, C::Error> {
+        decode_integer!(self, decode_u128, KeyUnsignedVisitor)
     }
 
     #[inline]
-    fn decode_i16(self) -> Result<i16, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_i8(mut self) -> Result<i8, C::Error> {
+        decode_integer!(self, decode_i8, KeySignedVisitor)
     }
 
     #[inline]
-    fn decode_i32(self) -> Result<i32, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_i16(mut self) -> Result<i16, C::Error> {
+        decode_integer!(self, decode_i16, KeySignedVisitor)
     }
 
     #[inline]
-    fn decode_i64(self) -> Result<i64, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_i32(mut self) -> Result<i32, C::Error> {
+        decode_integer!(self, decode_i32, KeySignedVisitor)
     }
 
     #[inline]
-    fn decode_i128(self) -> Result<i128, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_i64(mut self) -> Result<i64, C::Error> {
+        decode_integer!(self, decode_i64, KeySignedVisitor)
     }
 
     #[inline]
-    fn decode_usize(self) -> Result<usize, C::Error> {
-        self.decode_escaped_bytes(KeyUnsignedVisitor::new())
+    fn decode_i128(mut self) -> Result<i128, C::Error> {
+        decode_integer!(self, decode_i128, KeySignedVisitor)
     }
 
     #[inline]
-    fn decode_isize(self) -> Result<isize, C::Error> {
-        self.decode_escaped_bytes(KeySignedVisitor::new())
+    fn decode_usize(mut self) -> Result<usize, C::Error> {
+        decode_integer!(self, decode_usize, KeyUnsignedVisitor)
+    }
+
+    #[inline]
+    fn decode_isize(mut self) -> Result<isize, C::Error> {
+        decode_integer!(self, decode_isize, KeySignedVisitor)
+    }
+
+    #[inline]
+    fn decode_f32(self) -> Result<f32, C::Error> {
+        self.into_value().decode_f32()
+    }
+
+    #[inline]
+    fn decode_f64(self) -> Result<f64, C::Error> {
+        self.into_value().decode_f64()
     }
 
     #[inline]
@@ -155,7 +216,53 @@ where
     where
         V: UnsizedVisitor<'de, C, str>,
     {
-        JsonDecoder::<_, _, M>::new(self.cx, self.parser).decode_string(visitor)
+        JsonDecoder::<_, _, M>::new(self.cx, self.parser, self.reject_duplicate_keys)
+            .decode_string(visitor)
+    }
+
+    #[inline]
+    fn decode_option(self) -> Result<Option<Self::DecodeSome>, C::Error> {
+        self.into_value().decode_option()
+    }
+
+    #[inline]
+    fn decode_pack<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
+    {
+        self.into_value().decode_pack(f)
+    }
+
+    #[inline]
+    fn decode_sequence<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
+    {
+        self.into_value().decode_sequence(f)
+    }
+
+    #[inline]
+    fn decode_map<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
+    {
+        self.into_value().decode_map(f)
+    }
+
+    #[inline]
+    fn decode_map_entries<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeMapEntries) -> Result<O, C::Error>,
+    {
+        self.into_value().decode_map_entries(f)
+    }
+
+    #[inline]
+    fn decode_variant<F, O>(self, f: F) -> Result<O, C::Error>
+    where
+        F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
+    {
+        self.into_value().decode_variant(f)
     }
 
     #[inline]
@@ -168,10 +275,7 @@ where
                 let visitor = visitor.visit_string(self.cx, SizeHint::any())?;
                 self.decode_string(visitor)
             }
-            Token::Number => self.decode_number(visitor),
-            token => Err(self
-                .cx
-                .message(format_args!("Unsupported key type {token:?}"))),
+            _ => self.into_value().decode_any(visitor),
         }
     }
 }
@@ -25,7 +25,7 @@ use core::str;
 
 use crate::alloc::Vec;
 use crate::de::{Decoder, SequenceDecoder, SizeHint, Skip, UnsizedVisitor, Visitor};
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::options;
 use crate::Context;
 use crate::Options;
@@ -40,12 +40,21 @@ use super::parser::integer::{
 };
 use super::parser::{integer, Parser, StringReference, Token};
 
-const BUFFER_OPTIONS: Options = options::new().map_keys_as_numbers().build();
+const BUFFER_OPTIONS: Options = options::new()
+    .map_keys_as_numbers()
+    .human_readable()
+    .build();
+
+const JSON_CAPABILITIES: Capabilities = Capabilities::SELF_DESCRIBING
+    .with(Capabilities::CAN_BORROW)
+    .with(Capabilities::CAN_SKIP)
+    .with(Capabilities::HUMAN_READABLE);
 
 /// A JSON decoder for Müsli.
 pub(crate) struct JsonDecoder<P, C, M> {
     cx: C,
     parser: P,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
@@ -57,10 +66,11 @@ where
 {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, parser: P) -> Self {
+    pub(crate) fn new(cx: C, parser: P, reject_duplicate_keys: bool) -> Self {
         Self {
             cx,
             parser,
+            reject_duplicate_keys,
             _marker: PhantomData,
         }
     }
@@ -137,7 +147,11 @@ where
     where
         U: Context<Allocator = Self::Allocator>,
     {
-        Ok(JsonDecoder::new(cx, self.parser))
+        Ok(JsonDecoder::new(
+            cx,
+            self.parser,
+            self.reject_duplicate_keys,
+        ))
     }
 
     #[inline]
@@ -145,6 +159,17 @@ where
         write!(f, "value that can be decoded from JSON")
     }
 
+    const CAPABILITIES: Capabilities = JSON_CAPABILITIES;
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        if self.reject_duplicate_keys {
+            JSON_CAPABILITIES.with(Capabilities::REJECT_DUPLICATE_KEYS)
+        } else {
+            JSON_CAPABILITIES
+        }
+    }
+
     #[inline]
     fn skip(self) -> Result<(), C::Error> {
         self.skip_any()
@@ -156,6 +181,22 @@ where
         Ok(Skip::Skipped)
     }
 
+    #[inline]
+    fn decode_raw<V>(mut self, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        self.parser.skip_whitespace(self.cx);
+        let start = self.parser.remaining();
+
+        // Duplicate keys are part of the raw value, so they are always
+        // permitted.
+        JsonDecoder::<_, _, M>::new(self.cx, self.parser.borrow_mut(), false).skip_any()?;
+
+        let len = start.len() - self.parser.remaining().len();
+        visitor.visit_borrowed(self.cx, &start[..len])
+    }
+
     #[inline]
     fn decode_buffer(self) -> Result<Self::DecodeBuffer, C::Error> {
         let cx = self.cx;
@@ -356,7 +397,8 @@ where
     where
         F: FnOnce(&mut Self::DecodePack) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder =
+            JsonSequenceDecoder::new(self.cx, None, self.parser, self.reject_duplicate_keys)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -367,7 +409,8 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder =
+            JsonSequenceDecoder::new(self.cx, None, self.parser, self.reject_duplicate_keys)?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -378,7 +421,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeSequence) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonSequenceDecoder::new(self.cx, Some(hint.size), self.parser)?;
+        let mut decoder = JsonSequenceDecoder::new(
+            self.cx,
+            Some(hint.size),
+            self.parser,
+            self.reject_duplicate_keys,
+        )?;
         let output = f(&mut decoder)?;
         decoder.skip_sequence_remaining()?;
         Ok(output)
@@ -389,7 +437,8 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, None, self.parser)?;
+        let mut decoder =
+            JsonObjectDecoder::new(self.cx, None, self.parser, self.reject_duplicate_keys)?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
         Ok(output)
@@ -400,7 +449,12 @@ where
     where
         F: FnOnce(&mut Self::DecodeMap) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonObjectDecoder::new(self.cx, Some(hint.size), self.parser)?;
+        let mut decoder = JsonObjectDecoder::new(
+            self.cx,
+            Some(hint.size),
+            self.parser,
+            self.reject_duplicate_keys,
+        )?;
         let output = f(&mut decoder)?;
         decoder.skip_object_remaining()?;
         Ok(output)
@@ -419,7 +473,8 @@ where
     where
         F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
     {
-        let mut decoder = JsonVariantDecoder::new(self.cx, self.parser)?;
+        let mut decoder =
+            JsonVariantDecoder::new(self.cx, self.parser, self.reject_duplicate_keys)?;
         let output = f(&mut decoder)?;
         decoder.end()?;
         Ok(output)
@@ -7,13 +7,20 @@ use crate::Context;
 
 use super::{JsonDecoder, JsonKeyDecoder, JsonObjectPairDecoder};
 
+/// A JSON object decoder, which also accepts an array of `[key, value]` pairs
+/// for maps whose keys can't be used as object keys.
 #[must_use = "Must call skip_object_remaining to complete decoding"]
 pub(crate) struct JsonObjectDecoder<P, C, M> {
     cx: C,
     first: bool,
+    /// Entries are stored as an array of pairs.
+    pairs: bool,
+    /// A pair has been opened and has to be closed before the next entry.
+    open: bool,
     len: Option<usize>,
     parser: P,
     finalized: bool,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
@@ -26,35 +33,51 @@ where
     pub(super) fn new_in(
         cx: C,
         first: bool,
+        pairs: bool,
+        open: bool,
         len: Option<usize>,
         parser: P,
+        reject_duplicate_keys: bool,
     ) -> Result<Self, C::Error> {
         Ok(Self {
             cx,
             first,
+            pairs,
+            open,
             len,
             parser,
             finalized: false,
+            reject_duplicate_keys,
             _marker: PhantomData,
         })
     }
 
     #[inline]
-    pub(super) fn new(cx: C, len: Option<usize>, mut parser: P) -> Result<Self, C::Error> {
-        let actual = parser.lex(cx);
-
-        if !matches!(actual, Token::OpenBrace) {
-            return Err(cx.message(format_args!("Expected opening brace, was {actual}")));
-        }
+    pub(super) fn new(
+        cx: C,
+        len: Option<usize>,
+        mut parser: P,
+        reject_duplicate_keys: bool,
+    ) -> Result<Self, C::Error> {
+        let pairs = match parser.lex(cx) {
+            Token::OpenBrace => false,
+            Token::OpenBracket => true,
+            actual => {
+                return Err(cx.message(format_args!("Expected opening brace, was {actual}")));
+            }
+        };
 
         parser.skip(cx, 1)?;
 
         Ok(Self {
             cx,
             first: true,
+            pairs,
+            open: false,
             len,
             parser,
             finalized: false,
+            reject_duplicate_keys,
             _marker: PhantomData,
         })
     }
@@ -66,6 +89,10 @@ where
 
         let first = take(&mut self.first);
 
+        if self.pairs {
+            return self.parse_pair(first);
+        }
+
         loop {
             let token = self.parser.lex(self.cx);
 
@@ -88,6 +115,49 @@ where
         }
     }
 
+    fn parse_pair(&mut self, first: bool) -> Result<bool, C::Error> {
+        if take(&mut self.open) {
+            let actual = self.parser.lex(self.cx);
+
+            if !matches!(actual, Token::CloseBracket) {
+                return Err(self
+                    .cx
+                    .message(format_args!("Expected closing bracket `]`, was {actual}")));
+            }
+
+            self.parser.skip(self.cx, 1)?;
+        }
+
+        loop {
+            let token = self.parser.lex(self.cx);
+
+            match token {
+                Token::OpenBracket => {
+                    self.parser.skip(self.cx, 1)?;
+                    self.open = true;
+                    return Ok(true);
+                }
+                Token::Comma if !first => {
+                    self.parser.skip(self.cx, 1)?;
+                }
+                Token::CloseBracket => {
+                    return Ok(false);
+                }
+                token => {
+                    return Err(self.cx.message(format_args!(
+                        "Expected pair, or closing bracket `]` but found {token:?}"
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Parse the separator between the key and the value of an entry.
+    #[inline]
+    fn parse_separator(&mut self) -> Result<(), C::Error> {
+        parse_separator(self.cx, self.parser.borrow_mut(), self.pairs)
+    }
+
     /// Parse end of object.
     #[inline]
     pub(super) fn skip_object_remaining(mut self) -> Result<(), C::Error> {
@@ -103,7 +173,13 @@ where
 
         let actual = self.parser.lex(self.cx);
 
-        if !matches!(actual, Token::CloseBrace) {
+        if self.pairs {
+            if !matches!(actual, Token::CloseBracket) {
+                return Err(self
+                    .cx
+                    .message(format_args!("Expected closing bracket `]`, was {actual}")));
+            }
+        } else if !matches!(actual, Token::CloseBrace) {
             return Err(self
                 .cx
                 .message(format_args!("Expected closing brace `}}`, was {actual}")));
@@ -150,7 +226,9 @@ where
 
         Ok(Some(JsonObjectPairDecoder::new(
             self.cx,
+            self.pairs,
             self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
         )))
     }
 
@@ -164,7 +242,15 @@ where
                 .message("Cannot decode remaining entries after finalizing"));
         }
 
-        JsonObjectDecoder::new_in(self.cx, self.first, self.len, self.parser.borrow_mut())
+        JsonObjectDecoder::new_in(
+            self.cx,
+            self.first,
+            self.pairs,
+            take(&mut self.open),
+            self.len,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        )
     }
 }
 
@@ -196,21 +282,22 @@ where
             return Ok(None);
         }
 
-        Ok(Some(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut())))
+        Ok(Some(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        )))
     }
 
     #[inline]
     fn decode_entry_value(&mut self) -> Result<Self::DecodeEntryValue<'_>, C::Error> {
-        let actual = self.parser.lex(self.cx);
-
-        if !matches!(actual, Token::Colon) {
-            return Err(self
-                .cx
-                .message(format_args!("Expected colon `:`, was {actual}")));
-        }
+        self.parse_separator()?;
 
-        self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        ))
     }
 
     #[inline]
@@ -218,3 +305,23 @@ where
         self.skip_object_remaining()
     }
 }
+
+/// Parse the separator between the key and the value of an entry, which is a
+/// comma for pairs and a colon for object members.
+pub(super) fn parse_separator<'de, P, C>(cx: C, mut parser: P, pairs: bool) -> Result<(), C::Error>
+where
+    P: Parser<'de>,
+    C: Context,
+{
+    let actual = parser.lex(cx);
+
+    if pairs {
+        if !matches!(actual, Token::Comma) {
+            return Err(cx.message(format_args!("Expected comma `,`, was {actual}")));
+        }
+    } else if !matches!(actual, Token::Colon) {
+        return Err(cx.message(format_args!("Expected colon `:`, was {actual}")));
+    }
+
+    parser.skip(cx, 1)
+}
@@ -1,23 +1,28 @@
 use core::marker::PhantomData;
 
 use crate::de::EntryDecoder;
-use crate::json::parser::{Parser, Token};
+use crate::json::parser::Parser;
 use crate::Context;
 
+use super::object_decoder::parse_separator;
 use super::{JsonDecoder, JsonKeyDecoder};
 
 pub(crate) struct JsonObjectPairDecoder<P, C, M> {
     cx: C,
+    pairs: bool,
     parser: P,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
 impl<P, C, M> JsonObjectPairDecoder<P, C, M> {
     #[inline]
-    pub(super) fn new(cx: C, parser: P) -> Self {
+    pub(super) fn new(cx: C, pairs: bool, parser: P, reject_duplicate_keys: bool) -> Self {
         Self {
             cx,
+            pairs,
             parser,
+            reject_duplicate_keys,
             _marker: PhantomData,
         }
     }
@@ -44,20 +49,21 @@ where
 
     #[inline]
     fn decode_key(&mut self) -> Result<Self::DecodeKey<'_>, C::Error> {
-        Ok(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        ))
     }
 
     #[inline]
     fn decode_value(mut self) -> Result<Self::DecodeValue, C::Error> {
-        let actual = self.parser.lex(self.cx);
+        parse_separator(self.cx, self.parser.borrow_mut(), self.pairs)?;
 
-        if !matches!(actual, Token::Colon) {
-            return Err(self
-                .cx
-                .message(format_args!("Expected colon `:`, was {actual}")));
-        }
-
-        self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser,
+            self.reject_duplicate_keys,
+        ))
     }
 }
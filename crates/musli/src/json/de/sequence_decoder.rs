@@ -14,6 +14,7 @@ pub(crate) struct JsonSequenceDecoder<P, C, M> {
     first: bool,
     parser: P,
     finalized: bool,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
@@ -24,7 +25,12 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, len: Option<usize>, mut parser: P) -> Result<Self, C::Error> {
+    pub(super) fn new(
+        cx: C,
+        len: Option<usize>,
+        mut parser: P,
+        reject_duplicate_keys: bool,
+    ) -> Result<Self, C::Error> {
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBracket) {
@@ -39,6 +45,7 @@ where
             first: true,
             parser,
             finalized: false,
+            reject_duplicate_keys,
             _marker: PhantomData,
         })
     }
@@ -122,7 +129,11 @@ where
             return Ok(None);
         }
 
-        Ok(Some(JsonDecoder::new(self.cx, self.parser.borrow_mut())))
+        Ok(Some(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        )))
     }
 
     #[inline]
@@ -131,6 +142,10 @@ where
             return Err(self.cx.message(format_args!("Encountered short array")));
         }
 
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        ))
     }
 }
@@ -9,6 +9,7 @@ use super::{JsonDecoder, JsonKeyDecoder};
 pub(crate) struct JsonVariantDecoder<P, C, M> {
     cx: C,
     parser: P,
+    reject_duplicate_keys: bool,
     _marker: PhantomData<M>,
 }
 
@@ -19,7 +20,7 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, mut parser: P) -> Result<Self, C::Error> {
+    pub(super) fn new(cx: C, mut parser: P, reject_duplicate_keys: bool) -> Result<Self, C::Error> {
         let actual = parser.lex(cx);
 
         if !matches!(actual, Token::OpenBrace) {
@@ -30,6 +31,7 @@ where
         Ok(Self {
             cx,
             parser,
+            reject_duplicate_keys,
             _marker: PhantomData,
         })
     }
@@ -73,7 +75,11 @@ where
 
     #[inline]
     fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
-        Ok(JsonKeyDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonKeyDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        ))
     }
 
     #[inline]
@@ -87,6 +93,10 @@ where
         }
 
         self.parser.skip(self.cx, 1)?;
-        Ok(JsonDecoder::new(self.cx, self.parser.borrow_mut()))
+        Ok(JsonDecoder::new(
+            self.cx,
+            self.parser.borrow_mut(),
+            self.reject_duplicate_keys,
+        ))
     }
 }
@@ -4,11 +4,12 @@ use core::mem::take;
 use crate::en::SequenceEncoder;
 use crate::{Context, Writer};
 
-use super::JsonEncoder;
+use super::{FloatFormatter, JsonEncoder};
 
 /// Encoder for a JSON array.
 pub(crate) struct JsonArrayEncoder<W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
     first: bool,
     end: &'static [u8],
     writer: W,
@@ -22,16 +23,22 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, writer: W) -> Result<Self, C::Error> {
-        Self::with_end(cx, writer, b"]")
+    pub(super) fn new(cx: C, float_format: FloatFormatter, writer: W) -> Result<Self, C::Error> {
+        Self::with_end(cx, float_format, writer, b"]")
     }
 
     #[inline]
-    pub(super) fn with_end(cx: C, mut writer: W, end: &'static [u8]) -> Result<Self, C::Error> {
+    pub(super) fn with_end(
+        cx: C,
+        float_format: FloatFormatter,
+        mut writer: W,
+        end: &'static [u8],
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'[')?;
 
         Ok(Self {
             cx,
+            float_format,
             first: true,
             end,
             writer,
@@ -65,7 +72,11 @@ where
             self.writer.write_byte(self.cx, b',')?;
         }
 
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        Ok(JsonEncoder::new(
+            self.cx,
+            self.float_format,
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
@@ -5,7 +5,7 @@ mod object_encoder;
 use self::object_encoder::JsonObjectEncoder;
 
 mod object_key_encoder;
-use self::object_key_encoder::JsonObjectKeyEncoder;
+use self::object_key_encoder::{JsonObjectKeyEncoder, Layout};
 
 mod object_pair_encoder;
 use self::object_pair_encoder::JsonObjectPairEncoder;
@@ -16,13 +16,63 @@ use self::variant_encoder::JsonVariantEncoder;
 use core::fmt;
 use core::marker::PhantomData;
 
-use crate::en::{Encode, Encoder, SequenceEncoder};
-use crate::hint::{MapHint, SequenceHint};
+use crate::en::{Encode, Encoder, MapEncoder, SequenceEncoder};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
+use crate::json::FloatFormat;
+#[cfg(debug_assertions)]
+use crate::json::{
+    de::JsonDecoder,
+    parser::{Parser, SliceParser},
+};
+use crate::semantic;
 use crate::{Context, Writer};
 
+const JSON_CAPABILITIES: Capabilities = Capabilities::SELF_DESCRIBING
+    .with(Capabilities::CAN_SKIP)
+    .with(Capabilities::HUMAN_READABLE);
+
+/// How an encoder writes floating point numbers.
+///
+/// Fixed formatting goes through a function pointer which is only created by
+/// [`FloatFormatter::new`], so that the formatting machinery it relies on is
+/// only included in binaries which configure it.
+#[derive(Clone, Copy)]
+pub(crate) enum FloatFormatter {
+    Shortest,
+    Fixed(usize, FixedFn),
+}
+
+type FixedFn = fn(f64, usize, &mut fmt::Formatter<'_>) -> fmt::Result;
+
+impl FloatFormatter {
+    pub(crate) const fn new(format: FloatFormat) -> Self {
+        match format {
+            FloatFormat::Shortest => Self::Shortest,
+            FloatFormat::Fixed(places) => Self::Fixed(places, fmt_fixed),
+        }
+    }
+}
+
+/// Formatting a number with a fixed number of places is exact, so widening an
+/// `f32` into an `f64` first produces the same output.
+fn fmt_fixed(value: f64, places: usize, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{value:.places$}")
+}
+
+struct Fixed(f64, usize, FixedFn);
+
+impl fmt::Display for Fixed {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Fixed(value, places, fixed) = *self;
+        fixed(value, places, f)
+    }
+}
+
 /// A JSON encoder for Müsli.
 pub(crate) struct JsonEncoder<W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
     writer: W,
     _marker: PhantomData<M>,
 }
@@ -30,9 +80,10 @@ pub(crate) struct JsonEncoder<W, C, M> {
 impl<W, C, M> JsonEncoder<W, C, M> {
     /// Construct a new fixed width message encoder.
     #[inline]
-    pub(crate) fn new(cx: C, writer: W) -> Self {
+    pub(crate) fn new(cx: C, float_format: FloatFormatter, writer: W) -> Self {
         Self {
             cx,
+            float_format,
             writer,
             _marker: PhantomData,
         }
@@ -73,7 +124,7 @@ where
     where
         U: Context<Allocator = <Self::Cx as Context>::Allocator>,
     {
-        Ok(JsonEncoder::new(cx, self.writer))
+        Ok(JsonEncoder::new(cx, self.float_format, self.writer))
     }
 
     #[inline]
@@ -81,6 +132,11 @@ where
         write!(f, "value that can be encoded to JSON")
     }
 
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        JSON_CAPABILITIES
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
     where
@@ -195,16 +251,34 @@ where
 
     #[inline]
     fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
-        let mut buffer = ryu::Buffer::new();
-        self.writer
-            .write_bytes(self.cx, buffer.format(value).as_bytes())
+        match self.float_format {
+            FloatFormatter::Shortest => {
+                let mut buffer = ryu::Buffer::new();
+                self.writer
+                    .write_bytes(self.cx, buffer.format(value).as_bytes())
+            }
+            FloatFormatter::Fixed(places, fixed) => encode_fmt(
+                self.cx,
+                self.writer,
+                format_args!("{}", Fixed(f64::from(value), places, fixed)),
+            ),
+        }
     }
 
     #[inline]
     fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
-        let mut buffer = ryu::Buffer::new();
-        self.writer
-            .write_bytes(self.cx, buffer.format(value).as_bytes())
+        match self.float_format {
+            FloatFormatter::Shortest => {
+                let mut buffer = ryu::Buffer::new();
+                self.writer
+                    .write_bytes(self.cx, buffer.format(value).as_bytes())
+            }
+            FloatFormatter::Fixed(places, fixed) => encode_fmt(
+                self.cx,
+                self.writer,
+                format_args!("{}", Fixed(value, places, fixed)),
+            ),
+        }
     }
 
     #[inline]
@@ -240,7 +314,7 @@ where
     where
         I: IntoIterator<Item: AsRef<[u8]>>,
     {
-        let mut seq = JsonArrayEncoder::<_, _, M>::new(self.cx, self.writer)?;
+        let mut seq = JsonArrayEncoder::<_, _, M>::new(self.cx, self.float_format, self.writer)?;
 
         for bb in vectors {
             for &b in bb.as_ref() {
@@ -256,6 +330,13 @@ where
         encode_string(self.cx, self.writer.borrow_mut(), string.as_bytes())
     }
 
+    #[inline]
+    fn encode_raw(mut self, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+        #[cfg(debug_assertions)]
+        check_raw(self.cx, bytes)?;
+        self.writer.write_bytes(self.cx, bytes)
+    }
+
     #[inline]
     fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
         Ok(self)
@@ -266,29 +347,47 @@ where
         self.encode_empty()
     }
 
+    #[inline]
+    fn encode_tagged<T>(self, tag: u32, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        // Values with well-known tags are rendered as they are, since the tag
+        // is implied by the type. Other tags are preserved in an object.
+        if semantic::name(tag).is_some() {
+            return value.encode(self);
+        }
+
+        self.encode_map_fn(&MapHint::with_size(2), |map| {
+            map.insert_entry("$tag", tag)?;
+            map.insert_entry("$value", value)?;
+            Ok(())
+        })
+    }
+
     #[inline]
     fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
-        JsonArrayEncoder::new(self.cx, self.writer)
+        JsonArrayEncoder::new(self.cx, self.float_format, self.writer)
     }
 
     #[inline]
     fn encode_sequence(self, _: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
-        JsonArrayEncoder::new(self.cx, self.writer)
+        JsonArrayEncoder::new(self.cx, self.float_format, self.writer)
     }
 
     #[inline]
     fn encode_map(self, _: &MapHint) -> Result<Self::EncodeMap, C::Error> {
-        JsonObjectEncoder::new(self.cx, self.writer)
+        JsonObjectEncoder::new(self.cx, self.float_format, self.writer)
     }
 
     #[inline]
     fn encode_map_entries(self, _: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
-        JsonObjectEncoder::new(self.cx, self.writer)
+        JsonObjectEncoder::new(self.cx, self.float_format, self.writer)
     }
 
     #[inline]
     fn encode_variant(self) -> Result<Self::EncodeVariant, C::Error> {
-        JsonVariantEncoder::new(self.cx, self.writer)
+        JsonVariantEncoder::new(self.cx, self.float_format, self.writer)
     }
 
     #[inline]
@@ -301,9 +400,10 @@ where
         T: ?Sized + Encode<Self::Mode>,
     {
         self.writer.write_byte(self.cx, b'{')?;
-        JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        JsonObjectKeyEncoder::new(self.cx, self.float_format, self.writer.borrow_mut())
+            .encode(tag)?;
         self.writer.write_byte(self.cx, b':')?;
-        JsonArrayEncoder::with_end(self.cx, self.writer, b"]}")
+        JsonArrayEncoder::with_end(self.cx, self.float_format, self.writer, b"]}")
     }
 
     #[inline]
@@ -316,12 +416,77 @@ where
         T: ?Sized + Encode<Self::Mode>,
     {
         self.writer.write_byte(self.cx, b'{')?;
-        JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()).encode(tag)?;
+        JsonObjectKeyEncoder::new(self.cx, self.float_format, self.writer.borrow_mut())
+            .encode(tag)?;
         self.writer.write_byte(self.cx, b':')?;
-        JsonObjectEncoder::with_end(self.cx, self.writer, b"}}")
+        JsonObjectEncoder::with_end(self.cx, self.float_format, self.writer, b"}}")
     }
 }
 
+/// Encode formatted output.
+fn encode_fmt<W, C>(cx: C, writer: W, args: fmt::Arguments<'_>) -> Result<(), C::Error>
+where
+    W: Writer,
+    C: Context,
+{
+    struct Adapter<W, C>
+    where
+        C: Context,
+    {
+        cx: C,
+        writer: W,
+        error: Option<C::Error>,
+    }
+
+    impl<W, C> fmt::Write for Adapter<W, C>
+    where
+        W: Writer,
+        C: Context,
+    {
+        #[inline]
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            if let Err(error) = self.writer.write_bytes(self.cx, s.as_bytes()) {
+                self.error = Some(error);
+                return Err(fmt::Error);
+            }
+
+            Ok(())
+        }
+    }
+
+    let mut adapter = Adapter {
+        cx,
+        writer,
+        error: None,
+    };
+
+    if fmt::write(&mut adapter, args).is_err() {
+        return Err(match adapter.error {
+            Some(error) => error,
+            None => cx.message("Failed to format value"),
+        });
+    }
+
+    Ok(())
+}
+
+/// Check that raw bytes being encoded are a single well-formed value.
+#[cfg(debug_assertions)]
+fn check_raw<C>(cx: C, bytes: &[u8]) -> Result<(), C::Error>
+where
+    C: Context,
+{
+    let mut parser = SliceParser::new(bytes);
+    JsonDecoder::<_, _, ()>::new(cx, parser.borrow_mut(), false).skip_any()?;
+    parser.skip_whitespace(cx);
+
+    if !parser.remaining().is_empty() {
+        return Err(cx.message("Raw value is not a single well-formed JSON value"));
+    }
+
+    Ok(())
+}
+
 /// Encode a sequence of chars as a string.
 #[inline]
 fn encode_string<W, C>(cx: C, mut w: W, bytes: &[u8]) -> Result<(), C::Error>
@@ -3,12 +3,17 @@ use core::marker::PhantomData;
 use crate::en::{EntriesEncoder, MapEncoder};
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder, JsonObjectPairEncoder};
+use super::{FloatFormatter, JsonEncoder, JsonObjectKeyEncoder, JsonObjectPairEncoder, Layout};
 
 /// An object encoder for JSON.
+///
+/// Maps whose keys can't be used as object keys are written as an array of
+/// `[key, value]` pairs instead, which is decided by the first key encoded.
 pub(crate) struct JsonObjectEncoder<W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
     len: usize,
+    layout: Layout,
     end: &'static [u8],
     writer: W,
     _marker: PhantomData<M>,
@@ -21,17 +26,32 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, writer: W) -> Result<Self, C::Error> {
-        Self::with_end(cx, writer, b"}")
+    pub(super) fn new(cx: C, float_format: FloatFormatter, writer: W) -> Result<Self, C::Error> {
+        Ok(Self {
+            cx,
+            float_format,
+            len: 0,
+            layout: Layout::Pending,
+            end: b"}",
+            writer,
+            _marker: PhantomData,
+        })
     }
 
     #[inline]
-    pub(super) fn with_end(cx: C, mut writer: W, end: &'static [u8]) -> Result<Self, C::Error> {
+    pub(super) fn with_end(
+        cx: C,
+        float_format: FloatFormatter,
+        mut writer: W,
+        end: &'static [u8],
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'{')?;
 
         Ok(Self {
             cx,
+            float_format,
             len: 0,
+            layout: Layout::Object,
             end,
             writer,
             _marker: PhantomData,
@@ -49,7 +69,7 @@ where
     type Ok = ();
     type Mode = M;
     type EncodeEntry<'this>
-        = JsonObjectPairEncoder<W::Mut<'this>, C, M>
+        = JsonObjectPairEncoder<'this, W::Mut<'this>, C, M>
     where
         Self: 'this;
 
@@ -64,14 +84,23 @@ where
 
         Ok(JsonObjectPairEncoder::new(
             self.cx,
+            self.float_format,
             self.len == 1,
+            &mut self.layout,
             self.writer.borrow_mut(),
         ))
     }
 
     #[inline]
     fn finish_map(mut self) -> Result<Self::Ok, C::Error> {
-        self.writer.write_bytes(self.cx, self.end)
+        match self.layout {
+            Layout::Pending => {
+                self.writer.write_byte(self.cx, b'{')?;
+                self.writer.write_bytes(self.cx, self.end)
+            }
+            Layout::Object => self.writer.write_bytes(self.cx, self.end),
+            Layout::Pairs => self.writer.write_byte(self.cx, b']'),
+        }
     }
 }
 
@@ -85,7 +114,7 @@ where
     type Ok = ();
     type Mode = M;
     type EncodeEntryKey<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<'this, W::Mut<'this>, C, M>
     where
         Self: 'this;
     type EncodeEntryValue<'this>
@@ -101,21 +130,47 @@ where
     #[inline]
     fn encode_entry_key(&mut self) -> Result<Self::EncodeEntryKey<'_>, C::Error> {
         if self.len > 0 {
+            if self.layout == Layout::Pairs {
+                self.writer.write_byte(self.cx, b']')?;
+            }
+
             self.writer.write_byte(self.cx, b',')?;
         }
 
         self.len += 1;
-        Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
+
+        Ok(JsonObjectKeyEncoder::with_layout(
+            self.cx,
+            self.float_format,
+            Some(&mut self.layout),
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
     fn encode_entry_value(&mut self) -> Result<Self::EncodeEntryValue<'_>, C::Error> {
-        self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        let separator = if self.layout == Layout::Pairs {
+            b','
+        } else {
+            b':'
+        };
+
+        self.writer.write_byte(self.cx, separator)?;
+        Ok(JsonEncoder::new(
+            self.cx,
+            self.float_format,
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
     fn finish_entries(mut self) -> Result<Self::Ok, C::Error> {
-        self.writer.write_byte(self.cx, b'}')
+        let end: &[u8] = match self.layout {
+            Layout::Pending => b"{}",
+            Layout::Object => b"}",
+            Layout::Pairs => b"]]",
+        };
+
+        self.writer.write_bytes(self.cx, end)
     }
 }
@@ -2,27 +2,113 @@ use core::fmt;
 use core::marker::PhantomData;
 
 use crate::en::{Encode, Encoder};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::{Context, Writer};
 
-pub(crate) struct JsonObjectKeyEncoder<W, C, M> {
+use super::{FloatFormatter, JsonArrayEncoder, JsonEncoder, JsonObjectEncoder, JsonVariantEncoder};
+
+/// How the entries of a map are written.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Layout {
+    /// Nothing has been written yet, the layout is decided by the first key.
+    Pending,
+    /// Entries are written as the members of an object.
+    Object,
+    /// Entries are written as an array of `[key, value]` pairs, since the keys
+    /// can't be used as object keys.
+    Pairs,
+}
+
+pub(crate) struct JsonObjectKeyEncoder<'a, W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
+    layout: Option<&'a mut Layout>,
     writer: W,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonObjectKeyEncoder<W, C, M> {
+impl<'a, W, C, M> JsonObjectKeyEncoder<'a, W, C, M>
+where
+    W: Writer,
+    C: Context,
+{
+    /// Construct an encoder for a key which must be an object key.
+    #[inline]
+    pub(super) fn new(cx: C, float_format: FloatFormatter, writer: W) -> Self {
+        Self::with_layout(cx, float_format, None, writer)
+    }
+
+    /// Construct an encoder for the key of a map entry, where the first key
+    /// decides the layout of the map if it's still pending.
     #[inline]
-    pub(super) fn new(cx: C, writer: W) -> Self {
+    pub(super) fn with_layout(
+        cx: C,
+        float_format: FloatFormatter,
+        layout: Option<&'a mut Layout>,
+        writer: W,
+    ) -> Self {
         Self {
             cx,
+            float_format,
+            layout,
             writer,
             _marker: PhantomData,
         }
     }
+
+    /// Begin writing a key which can be used as an object key, returning
+    /// `true` if it should instead be written as a plain value since the map
+    /// is being written as pairs.
+    #[inline]
+    fn begin_object_key(&mut self) -> Result<bool, C::Error> {
+        match self.layout.as_deref_mut() {
+            Some(layout @ Layout::Pending) => {
+                self.writer.write_byte(self.cx, b'{')?;
+                *layout = Layout::Object;
+                Ok(false)
+            }
+            Some(Layout::Pairs) => {
+                self.writer.write_byte(self.cx, b'[')?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Begin writing a key which can't be used as an object key, switching the
+    /// map to be written as pairs if its layout is still pending.
+    #[inline]
+    fn into_pair_key(mut self, what: &str) -> Result<JsonEncoder<W, C, M>, C::Error> {
+        match self.layout.as_deref_mut() {
+            Some(layout @ Layout::Pending) => {
+                self.writer.write_bytes(self.cx, b"[[")?;
+                *layout = Layout::Pairs;
+            }
+            Some(Layout::Pairs) => {
+                self.writer.write_byte(self.cx, b'[')?;
+            }
+            _ => {
+                return Err(self.cx.message(format_args!(
+                    "Expected any type that can be used as an object key, but got {what}"
+                )));
+            }
+        }
+
+        Ok(self.into_value())
+    }
+
+    #[inline]
+    fn into_value(self) -> JsonEncoder<W, C, M> {
+        JsonEncoder::new(self.cx, self.float_format, self.writer)
+    }
 }
 
 macro_rules! format_integer {
-    ($slf:ident, $value:ident) => {{
+    ($slf:ident, $method:ident, $value:ident) => {{
+        if $slf.begin_object_key()? {
+            return $slf.into_value().$method($value);
+        }
+
         $slf.writer.write_byte($slf.cx, b'"')?;
         let mut buffer = itoa::Buffer::new();
         $slf.writer
@@ -33,7 +119,7 @@ macro_rules! format_integer {
 }
 
 #[crate::encoder(crate)]
-impl<W, C, M> Encoder for JsonObjectKeyEncoder<W, C, M>
+impl<'a, W, C, M> Encoder for JsonObjectKeyEncoder<'a, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -44,9 +130,15 @@ where
     type Ok = ();
     type Mode = M;
     type WithContext<U>
-        = JsonObjectKeyEncoder<W, U, M>
+        = JsonObjectKeyEncoder<'a, W, U, M>
     where
         U: Context<Allocator = <Self::Cx as Context>::Allocator>;
+    type EncodePack = JsonArrayEncoder<W, C, M>;
+    type EncodeSome = JsonEncoder<W, C, M>;
+    type EncodeSequence = JsonArrayEncoder<W, C, M>;
+    type EncodeMap = JsonObjectEncoder<W, C, M>;
+    type EncodeMapEntries = JsonObjectEncoder<W, C, M>;
+    type EncodeVariant = JsonVariantEncoder<W, C, M>;
 
     #[inline]
     fn cx(&self) -> Self::Cx {
@@ -58,7 +150,12 @@ where
     where
         U: Context<Allocator = <Self::Cx as Context>::Allocator>,
     {
-        Ok(JsonObjectKeyEncoder::new(cx, self.writer))
+        Ok(JsonObjectKeyEncoder::with_layout(
+            cx,
+            self.float_format,
+            self.layout,
+            self.writer,
+        ))
     }
 
     #[inline]
@@ -66,6 +163,11 @@ where
         write!(f, "any type that can be used as an object key")
     }
 
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        super::JSON_CAPABILITIES
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, Self::Error>
     where
@@ -74,68 +176,124 @@ where
         value.as_encode().encode(self)
     }
 
+    #[inline]
+    fn encode_empty(self) -> Result<Self::Ok, C::Error> {
+        self.into_pair_key("an empty value")?.encode_empty()
+    }
+
+    #[inline]
+    fn encode_bool(self, value: bool) -> Result<Self::Ok, C::Error> {
+        self.into_pair_key("a boolean")?.encode_bool(value)
+    }
+
     #[inline]
     fn encode_u8(mut self, value: u8) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_u8, value)
     }
 
     #[inline]
     fn encode_u16(mut self, value: u16) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_u16, value)
     }
 
     #[inline]
     fn encode_u32(mut self, value: u32) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_u32, value)
     }
 
     #[inline]
     fn encode_u64(mut self, value: u64) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_u64, value)
     }
 
     #[inline]
     fn encode_u128(mut self, value: u128) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_u128, value)
     }
 
     #[inline]
     fn encode_i8(mut self, value: i8) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_i8, value)
     }
 
     #[inline]
     fn encode_i16(mut self, value: i16) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_i16, value)
     }
 
     #[inline]
     fn encode_i32(mut self, value: i32) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_i32, value)
     }
 
     #[inline]
     fn encode_i64(mut self, value: i64) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_i64, value)
     }
 
     #[inline]
     fn encode_i128(mut self, value: i128) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_i128, value)
     }
 
     #[inline]
     fn encode_usize(mut self, value: usize) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_usize, value)
     }
 
     #[inline]
     fn encode_isize(mut self, value: isize) -> Result<Self::Ok, C::Error> {
-        format_integer!(self, value)
+        format_integer!(self, encode_isize, value)
+    }
+
+    #[inline]
+    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
+        self.into_pair_key("a float")?.encode_f32(value)
     }
 
     #[inline]
-    fn encode_string(self, string: &str) -> Result<Self::Ok, C::Error> {
+    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
+        self.into_pair_key("a float")?.encode_f64(value)
+    }
+
+    #[inline]
+    fn encode_string(mut self, string: &str) -> Result<Self::Ok, C::Error> {
+        self.begin_object_key()?;
         super::encode_string(self.cx, self.writer, string.as_bytes())
     }
+
+    #[inline]
+    fn encode_some(self) -> Result<Self::EncodeSome, C::Error> {
+        self.into_pair_key("an optional value")?.encode_some()
+    }
+
+    #[inline]
+    fn encode_none(self) -> Result<Self::Ok, C::Error> {
+        self.into_pair_key("an optional value")?.encode_none()
+    }
+
+    #[inline]
+    fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
+        self.into_pair_key("a pack")?.encode_pack()
+    }
+
+    #[inline]
+    fn encode_sequence(self, hint: &SequenceHint) -> Result<Self::EncodeSequence, C::Error> {
+        self.into_pair_key("a sequence")?.encode_sequence(hint)
+    }
+
+    #[inline]
+    fn encode_map(self, hint: &MapHint) -> Result<Self::EncodeMap, C::Error> {
+        self.into_pair_key("a map")?.encode_map(hint)
+    }
+
+    #[inline]
+    fn encode_map_entries(self, hint: &MapHint) -> Result<Self::EncodeMapEntries, C::Error> {
+        self.into_pair_key("a map")?.encode_map_entries(hint)
+    }
+
+    #[inline]
+    fn encode_variant(self) -> Result<Self::EncodeVariant, C::Error> {
+        self.into_pair_key("a variant")?.encode_variant()
+    }
 }
@@ -3,29 +3,39 @@ use core::marker::PhantomData;
 use crate::en::EntryEncoder;
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder};
+use super::{FloatFormatter, JsonEncoder, JsonObjectKeyEncoder, Layout};
 
 /// Encoder for a JSON object pair.
-pub(crate) struct JsonObjectPairEncoder<W, C, M> {
+pub(crate) struct JsonObjectPairEncoder<'a, W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
     empty: bool,
+    layout: &'a mut Layout,
     writer: W,
     _marker: PhantomData<M>,
 }
 
-impl<W, C, M> JsonObjectPairEncoder<W, C, M> {
+impl<'a, W, C, M> JsonObjectPairEncoder<'a, W, C, M> {
     #[inline]
-    pub(super) const fn new(cx: C, empty: bool, writer: W) -> Self {
+    pub(super) fn new(
+        cx: C,
+        float_format: FloatFormatter,
+        empty: bool,
+        layout: &'a mut Layout,
+        writer: W,
+    ) -> Self {
         Self {
             cx,
+            float_format,
             empty,
+            layout,
             writer,
             _marker: PhantomData,
         }
     }
 }
 
-impl<W, C, M> EntryEncoder for JsonObjectPairEncoder<W, C, M>
+impl<W, C, M> EntryEncoder for JsonObjectPairEncoder<'_, W, C, M>
 where
     W: Writer,
     C: Context,
@@ -35,7 +45,7 @@ where
     type Ok = ();
     type Mode = M;
     type EncodeKey<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<'this, W::Mut<'this>, C, M>
     where
         Self: 'this;
     type EncodeValue<'this>
@@ -54,17 +64,36 @@ where
             self.writer.write_byte(self.cx, b',')?;
         }
 
-        Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
+        Ok(JsonObjectKeyEncoder::with_layout(
+            self.cx,
+            self.float_format,
+            Some(&mut *self.layout),
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
     fn encode_value(&mut self) -> Result<Self::EncodeValue<'_>, C::Error> {
-        self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        let separator = if *self.layout == Layout::Pairs {
+            b','
+        } else {
+            b':'
+        };
+
+        self.writer.write_byte(self.cx, separator)?;
+        Ok(JsonEncoder::new(
+            self.cx,
+            self.float_format,
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
-    fn finish_entry(self) -> Result<Self::Ok, C::Error> {
+    fn finish_entry(mut self) -> Result<Self::Ok, C::Error> {
+        if *self.layout == Layout::Pairs {
+            self.writer.write_byte(self.cx, b']')?;
+        }
+
         Ok(())
     }
 }
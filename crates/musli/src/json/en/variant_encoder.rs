@@ -3,11 +3,12 @@ use core::marker::PhantomData;
 use crate::en::VariantEncoder;
 use crate::{Context, Writer};
 
-use super::{JsonEncoder, JsonObjectKeyEncoder};
+use super::{FloatFormatter, JsonEncoder, JsonObjectKeyEncoder};
 
 /// A JSON variant encoder.
 pub(crate) struct JsonVariantEncoder<W, C, M> {
     cx: C,
+    float_format: FloatFormatter,
     writer: W,
     _marker: PhantomData<M>,
 }
@@ -19,10 +20,15 @@ where
     M: 'static,
 {
     #[inline]
-    pub(super) fn new(cx: C, mut writer: W) -> Result<Self, C::Error> {
+    pub(super) fn new(
+        cx: C,
+        float_format: FloatFormatter,
+        mut writer: W,
+    ) -> Result<Self, C::Error> {
         writer.write_byte(cx, b'{')?;
         Ok(Self {
             cx,
+            float_format,
             writer,
             _marker: PhantomData,
         })
@@ -39,7 +45,7 @@ where
     type Ok = ();
     type Mode = M;
     type EncodeTag<'this>
-        = JsonObjectKeyEncoder<W::Mut<'this>, C, M>
+        = JsonObjectKeyEncoder<'this, W::Mut<'this>, C, M>
     where
         Self: 'this;
     type EncodeData<'this>
@@ -54,13 +60,21 @@ where
 
     #[inline]
     fn encode_tag(&mut self) -> Result<Self::EncodeTag<'_>, C::Error> {
-        Ok(JsonObjectKeyEncoder::new(self.cx, self.writer.borrow_mut()))
+        Ok(JsonObjectKeyEncoder::new(
+            self.cx,
+            self.float_format,
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
     fn encode_data(&mut self) -> Result<Self::EncodeData<'_>, C::Error> {
         self.writer.write_byte(self.cx, b':')?;
-        Ok(JsonEncoder::new(self.cx, self.writer.borrow_mut()))
+        Ok(JsonEncoder::new(
+            self.cx,
+            self.float_format,
+            self.writer.borrow_mut(),
+        ))
     }
 
     #[inline]
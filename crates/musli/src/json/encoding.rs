@@ -5,19 +5,19 @@ use core::marker;
 
 #[cfg(feature = "alloc")]
 use rust_alloc::string::String;
-#[cfg(feature = "alloc")]
-use rust_alloc::vec::Vec;
 
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
 use crate::mode::Text;
-use crate::{Context, Decode, Encode, IntoWriter};
+use crate::writer::BufWriter;
+use crate::{Context, Decode, Encode, IntoWriter, Writer};
 
+use super::canonical;
 use super::de::JsonDecoder;
-use super::en::JsonEncoder;
+use super::en::{FloatFormatter, JsonEncoder};
 #[cfg(feature = "alloc")]
 use super::error::Error;
-use super::parser::IntoParser;
+use super::parser::{IntoParser, Parser};
 
 /// The default configuration.
 pub const DEFAULT: Encoding = Encoding::new();
@@ -95,12 +95,34 @@ where
     DEFAULT.from_str(string)
 }
 
+/// How floating point numbers are written by an [`Encoding`].
+///
+/// See [`Encoding::with_float_format`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FloatFormat {
+    /// Write the shortest representation which parses back into exactly the
+    /// same value.
+    ///
+    /// This is the default.
+    Shortest,
+    /// Write the number with the given number of digits after the decimal
+    /// point, rounding the remaining digits.
+    ///
+    /// Numbers written this way are not guaranteed to parse back into the same
+    /// value.
+    Fixed(usize),
+}
+
 /// Setting up encoding with parameters.
 pub struct Encoding<M = Text>
 where
     M: 'static,
 {
-    _marker: marker::PhantomData<M>,
+    canonical: bool,
+    reject_duplicate_keys: bool,
+    float_format: FloatFormatter,
+    _marker: marker::PhantomData<fn() -> M>,
 }
 
 impl Default for Encoding<Text> {
@@ -147,6 +169,9 @@ impl Encoding<Text> {
     #[inline]
     pub const fn new() -> Self {
         Encoding {
+            canonical: false,
+            reject_duplicate_keys: false,
+            float_format: FloatFormatter::Shortest,
             _marker: marker::PhantomData,
         }
     }
@@ -172,10 +197,190 @@ where
         T: 'static,
     {
         Encoding {
+            canonical: self.canonical,
+            reject_duplicate_keys: self.reject_duplicate_keys,
+            float_format: self.float_format,
             _marker: marker::PhantomData,
         }
     }
 
+    /// Produce canonical output following the JSON Canonicalization Scheme
+    /// described in [RFC 8785].
+    ///
+    /// This makes the output deterministic, which is useful for example when
+    /// computing cryptographic signatures over it. Decoding is not affected.
+    ///
+    /// The following parts of the specification are implemented:
+    /// * Object members are sorted by the UTF-16 code units of their keys, and
+    ///   duplicate keys are rejected.
+    /// * No insignificant whitespace is emitted.
+    /// * Strings only escape `"`, `\` and control characters, using the short
+    ///   forms `\b`, `\t`, `\n`, `\f` and `\r` where available and
+    ///   `\u00xx` with lowercase hexadecimal digits otherwise.
+    /// * Floating point numbers are written in the shortest form which
+    ///   round-trips, using the serialization of `Number` in ECMAScript. `-0`
+    ///   is written as `0`, and non-finite numbers are rejected.
+    ///
+    /// The following parts are not fully implemented:
+    /// * Integers are written as-is rather than being converted to an IEEE 754
+    ///   double first. This preserves the precision of large 64-bit and 128-bit
+    ///   integers, but means that integers of magnitude `10^21` or above and
+    ///   integers which can't be represented exactly as a double are written
+    ///   differently than the specification requires.
+    /// * The output is produced by re-processing what the encoder wrote, so the
+    ///   whole value is buffered using the allocator of the context before
+    ///   anything is written to the output.
+    ///
+    /// [RFC 8785]: https://www.rfc-editor.org/rfc/rfc8785
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use musli::Encode;
+    /// use musli::json::Encoding;
+    /// # use musli::json::Error;
+    ///
+    /// const CANONICAL: Encoding = Encoding::new().with_canonical();
+    ///
+    /// #[derive(Encode)]
+    /// struct Document {
+    ///     title: String,
+    ///     score: f64,
+    ///     attributes: HashMap<String, u32>,
+    /// }
+    ///
+    /// let document = Document {
+    ///     title: String::from("€\n"),
+    ///     score: 1e21,
+    ///     attributes: HashMap::from([(String::from("b"), 2), (String::from("a"), 1)]),
+    /// };
+    ///
+    /// let out = CANONICAL.to_string(&document)?;
+    /// assert_eq!(out, r#"{"attributes":{"a":1,"b":2},"score":1e+21,"title":"€\n"}"#);
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_canonical(self) -> Self {
+        Encoding {
+            canonical: true,
+            ..self
+        }
+    }
+
+    /// Reject objects which contain the same key more than once when decoding
+    /// them into a map, such as a `HashMap` or a `BTreeMap`.
+    ///
+    /// By default the last entry for a key wins. Since duplicate keys can be
+    /// used to smuggle values past a validator which picks a different entry
+    /// than the final consumer, protocols which treat them as a sign of
+    /// corruption or tampering should enable this.
+    ///
+    /// This is reported by the decoder through
+    /// [`Capabilities::REJECT_DUPLICATE_KEYS`].
+    ///
+    /// [`Capabilities::REJECT_DUPLICATE_KEYS`]: crate::hint::Capabilities::REJECT_DUPLICATE_KEYS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    ///
+    /// use musli::json::{self, Encoding};
+    /// # use musli::json::Error;
+    ///
+    /// const STRICT: Encoding = Encoding::new().with_reject_duplicate_keys();
+    ///
+    /// let input = r#"{"role":"user","role":"admin"}"#;
+    ///
+    /// let map: HashMap<String, String> = json::from_str(input)?;
+    /// assert_eq!(map["role"], "admin");
+    ///
+    /// let result = STRICT.from_str::<HashMap<String, String>>(input);
+    /// assert!(result.is_err());
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_reject_duplicate_keys(self) -> Self {
+        Encoding {
+            reject_duplicate_keys: true,
+            ..self
+        }
+    }
+
+    /// Configure how floating point numbers are written.
+    ///
+    /// By default [`FloatFormat::Shortest`] is used, which writes the shortest
+    /// representation which parses back into exactly the same value. This
+    /// doesn't depend on the platform or the standard library in use, so the
+    /// output is stable across them. Non-finite numbers are written as `NaN`,
+    /// `inf` and `-inf` regardless of the format.
+    ///
+    /// This has no effect when [canonical output] is enabled, since it
+    /// prescribes its own formatting of numbers.
+    ///
+    /// [canonical output]: Self::with_canonical
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::{self, Encoding, FloatFormat};
+    /// # use musli::json::Error;
+    ///
+    /// const FIXED: Encoding = Encoding::new().with_float_format(FloatFormat::Fixed(2));
+    ///
+    /// let values = [0.1f64, 1.0 / 3.0, 1e21];
+    ///
+    /// assert_eq!(json::to_string(&values)?, "[0.1,0.3333333333333333,1e21]");
+    /// assert_eq!(FIXED.to_string(&values)?, "[0.10,0.33,1000000000000000000000.00]");
+    /// # Ok::<_, Error>(())
+    /// ```
+    pub const fn with_float_format(self, format: FloatFormat) -> Self {
+        Encoding {
+            float_format: FloatFormatter::new(format),
+            ..self
+        }
+    }
+
+    /// Construct a decoder using the current configuration.
+    #[inline]
+    fn new_decoder<'de, C, P>(self, cx: C, parser: P) -> JsonDecoder<P, C, M>
+    where
+        C: Context,
+        P: Parser<'de>,
+    {
+        JsonDecoder::new(cx, parser, self.reject_duplicate_keys)
+    }
+
+    /// Count the bytes remaining after a value, ignoring trailing whitespace.
+    #[inline]
+    pub(crate) fn trailing(self, rest: &[u8]) -> usize {
+        let ws = rest
+            .iter()
+            .take_while(|b| matches!(b, b' ' | b'\n' | b'\t' | b'\r'))
+            .count();
+
+        rest.len() - ws
+    }
+
+    /// Encode a value to a writer, canonicalizing the output if configured.
+    ///
+    /// This is always inlined so that when the encoding is a constant, the
+    /// canonicalization it doesn't use can be removed from the final binary.
+    #[inline(always)]
+    fn encode_to<C, W, T>(self, cx: C, writer: W, value: &T) -> Result<(), C::Error>
+    where
+        C: Context,
+        W: Writer,
+        T: ?Sized + Encode<M>,
+    {
+        if self.canonical {
+            return encode_canonical(cx, writer, value);
+        }
+
+        let encoder = JsonEncoder::<_, _, M>::new(cx, self.float_format, writer);
+        T::encode(value, encoder)
+    }
+
     crate::macros::encoding_impls!(
         M,
         json,
@@ -183,6 +388,9 @@ where
         JsonDecoder::<_, _, M>::new,
         IntoParser::into_parser,
         IntoWriter::into_writer,
+        @encode encode_to,
+        @decode new_decoder,
+        @trailing trailing,
     );
 
     /// Encode the given value to the given value to a [`String`] using the
@@ -262,15 +470,117 @@ where
     #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
     #[inline]
     pub fn to_string_with<T, C>(self, cx: C, value: &T) -> Result<String, C::Error>
+    where
+        C: Context,
+        T: ?Sized + Encode<M>,
+    {
+        let mut string = String::with_capacity(128);
+        self.append_to_string_with(cx, &mut string, value)?;
+        Ok(string)
+    }
+
+    /// Encode the given value using the current [`Encoding`], appending it to
+    /// the given [`String`].
+    ///
+    /// The value is encoded directly into the string without any intermediate
+    /// buffer or UTF-8 validation. If encoding fails, the string is left
+    /// unmodified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::json;
+    /// # use musli::json::Error;
+    ///
+    /// const ENCODING: json::Encoding = json::Encoding::new();
+    ///
+    /// #[derive(Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// let mut lines = String::new();
+    ///
+    /// for (name, age) in [("Aristotle", 61), ("Plato", 80)] {
+    ///     ENCODING.append_to_string(&mut lines, &Person {
+    ///         name: name.to_string(),
+    ///         age,
+    ///     })?;
+    ///
+    ///     lines.push('\n');
+    /// }
+    ///
+    /// assert_eq!(lines, "{\"name\":\"Aristotle\",\"age\":61}\n{\"name\":\"Plato\",\"age\":80}\n");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn append_to_string<T>(self, string: &mut String, value: &T) -> Result<(), Error>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        let cx = crate::context::new().with_error();
+        self.append_to_string_with(&cx, string, value)
+    }
+
+    /// Encode the given value using the current [`Encoding`], appending it to
+    /// the given [`String`].
+    ///
+    /// This is the same as [`Encoding::append_to_string`] but allows for using
+    /// a configurable [`Context`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json;
+    /// # use musli::json::Error;
+    ///
+    /// const ENCODING: json::Encoding = json::Encoding::new();
+    ///
+    /// let cx = musli::context::new().with_error();
+    ///
+    /// let mut out = String::from("values: ");
+    /// ENCODING.append_to_string_with(&cx, &mut out, &[1, 2, 3])?;
+    /// assert_eq!(out, "values: [1,2,3]");
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    pub fn append_to_string_with<T, C>(
+        self,
+        cx: C,
+        string: &mut String,
+        value: &T,
+    ) -> Result<(), C::Error>
     where
         C: Context,
         T: ?Sized + Encode<M>,
     {
         cx.clear();
-        let mut data = Vec::with_capacity(128);
-        T::encode(value, JsonEncoder::<_, _, M>::new(cx, &mut data))?;
-        // SAFETY: Encoder is guaranteed to produce valid UTF-8.
-        Ok(unsafe { String::from_utf8_unchecked(data) })
+
+        // The string is taken while it is being written to, so that it's left
+        // empty rather than holding invalid UTF-8 should encoding panic.
+        let mut data = core::mem::take(string).into_bytes();
+        let start = data.len();
+
+        let result = self.encode_to(cx, &mut data, value);
+
+        if result.is_err() {
+            data.truncate(start);
+        }
+
+        debug_assert!(
+            core::str::from_utf8(&data[start..]).is_ok(),
+            "JSON encoder produced invalid UTF-8"
+        );
+
+        // SAFETY: The JSON encoder is guaranteed to produce valid UTF-8, and
+        // on errors anything that was partially written has been truncated.
+        *string = unsafe { String::from_utf8_unchecked(data) };
+        result
     }
 }
 
@@ -282,3 +592,18 @@ impl<M> Clone for Encoding<M> {
 }
 
 impl<M> Copy for Encoding<M> {}
+
+/// Encode a value and rewrite it into its canonical form.
+#[inline(never)]
+fn encode_canonical<M, C, W, T>(cx: C, writer: W, value: &T) -> Result<(), C::Error>
+where
+    M: 'static,
+    C: Context,
+    W: Writer,
+    T: ?Sized + Encode<M>,
+{
+    let mut buf = BufWriter::new(cx.alloc());
+    let encoder = JsonEncoder::<_, _, M>::new(cx, FloatFormatter::Shortest, &mut buf);
+    T::encode(value, encoder)?;
+    canonical::canonicalize(cx, buf.into_inner().as_slice(), writer)
+}
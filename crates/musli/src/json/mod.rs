@@ -36,15 +36,40 @@
 //! });
 //! # Ok::<_, musli::json::Error>(())
 //! ```
+//!
+//! Maps are encoded as objects as long as their keys are strings or numbers.
+//! Maps with any other kind of key, like tuples or structs, are encoded as an
+//! array of `[key, value]` pairs instead, and either layout is accepted when
+//! decoding a map. Empty maps are always encoded as `{}`.
+//!
+//! ```
+//! use std::collections::BTreeMap;
+//!
+//! let map = BTreeMap::from([((0u32, 1u32), "a"), ((2, 3), "b")]);
+//!
+//! let json = musli::json::to_string(&map)?;
+//! assert_eq!(json, r#"[[[0,1],"a"],[[2,3],"b"]]"#);
+//!
+//! let decoded: BTreeMap<(u32, u32), String> = musli::json::from_str(&json)?;
+//! assert_eq!(decoded.len(), 2);
+//! # Ok::<_, musli::json::Error>(())
+//! ```
+//!
+//! To always encode a map as pairs, use `#[musli(with = musli::compat::pairs)]`.
+//!
+//! Parts of a document can be kept as the exact text they were decoded from
+//! with [`RawValue`] or [`RawValueBuf`], which are encoded back out verbatim.
 
 #![cfg(feature = "json")]
 #![cfg_attr(doc_cfg, doc(cfg(feature = "json")))]
 
+mod canonical;
 mod de;
 mod en;
 mod encoding;
 mod error;
 mod parser;
+mod raw;
 
 #[cfg(feature = "test")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
@@ -62,13 +87,19 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, from_str, to_fixed_bytes, to_slice};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_slice_partial, from_str, to_fixed_bytes,
+    to_slice, validate,
+};
 #[cfg(feature = "alloc")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
 #[doc(inline)]
 pub use self::encoding::{to_string, to_vec};
 #[doc(inline)]
-pub use self::encoding::{Encoding, DEFAULT};
+pub use self::encoding::{Encoding, FloatFormat, DEFAULT};
 #[doc(inline)]
 pub use self::error::Error;
 pub use self::parser::Parser;
+pub use self::raw::RawValue;
+#[cfg(feature = "alloc")]
+pub use self::raw::RawValueBuf;
@@ -927,7 +927,7 @@ mod traits {
                         base = base * base;
 
                         if exp & 1 == 1 {
-                            acc = acc * base;
+                            acc *= base;
                         }
                     }
 
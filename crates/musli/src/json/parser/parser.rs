@@ -1,6 +1,7 @@
 use crate::alloc::Vec;
 use crate::de::Visitor;
-use crate::json::parser::integer::decode_signed_full;
+use crate::dec2flt::dec2flt;
+use crate::json::parser::integer::{decode_signed_full, SignedPartsFull};
 use crate::json::parser::{StringReference, Token};
 use crate::Context;
 
@@ -96,6 +97,10 @@ pub trait Parser<'de>: private::Sealed {
     #[doc(hidden)]
     fn peek(&mut self) -> Option<u8>;
 
+    /// The remaining input which has not been parsed.
+    #[doc(hidden)]
+    fn remaining(&self) -> &'de [u8];
+
     #[doc(hidden)]
     fn lex<C>(&mut self, cx: C) -> Token
     where
@@ -149,13 +154,22 @@ pub trait Parser<'de>: private::Sealed {
         C: Context,
         V: Visitor<'de, C>,
     {
+        self.skip_whitespace(cx);
+        let input = self.remaining();
         let signed = decode_signed_full::<i128, _, _>(cx, self)?;
 
+        // Numbers which are not integers are parsed again from the input, since
+        // computing them from their parts is not precise.
+        let compute_float = |signed: SignedPartsFull<i128>| match dec2flt::<f64>(input) {
+            Some((value, _)) => value,
+            None => signed.compute_float(),
+        };
+
         if signed.is_negative {
             let value = match signed.compute() {
                 Ok(value) => value,
                 Err(..) => {
-                    let value = signed.compute_float();
+                    let value = compute_float(signed);
                     return visitor.visit_f64(cx, value);
                 }
             };
@@ -185,7 +199,7 @@ pub trait Parser<'de>: private::Sealed {
             let value = match signed.unsigned.compute() {
                 Ok(value) => value,
                 Err(..) => {
-                    let value = signed.unsigned.compute_float();
+                    let value = compute_float(signed);
                     return visitor.visit_f64(cx, value);
                 }
             };
@@ -263,6 +277,11 @@ where
         (**self).peek()
     }
 
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        (**self).remaining()
+    }
+
     #[inline]
     fn lex<C>(&mut self, cx: C) -> Token
     where
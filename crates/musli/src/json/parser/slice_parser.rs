@@ -123,6 +123,11 @@ impl<'de> Parser<'de> for SliceParser<'de> {
         self.slice.get(self.index).copied()
     }
 
+    #[inline]
+    fn remaining(&self) -> &'de [u8] {
+        self.slice.get(self.index..).unwrap_or_default()
+    }
+
     fn parse_f32<C>(&mut self, cx: C) -> Result<f32, C::Error>
     where
         C: Context,
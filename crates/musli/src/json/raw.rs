@@ -0,0 +1,256 @@
+use core::fmt;
+use core::str;
+
+#[cfg(feature = "alloc")]
+use rust_alloc::borrow::ToOwned;
+#[cfg(feature = "alloc")]
+use rust_alloc::string::String;
+
+use crate::de::UnsizedVisitor;
+use crate::{Allocator, Context, Decode, Decoder, Encode, Encoder};
+
+/// A JSON value which is kept as the exact text it was decoded from.
+///
+/// Decoding a [`RawValue`] only checks that the value is well-formed, which
+/// means that brackets and braces are matched and that strings are terminated.
+/// Strings are not unescaped and numbers are not parsed. When it's encoded,
+/// the text is written out verbatim.
+///
+/// This can be used to defer decoding parts of a document, or to pass them
+/// along without having to decode and encode them again. Since the value
+/// borrows from the input, this only works when decoding from a slice. See
+/// [`RawValueBuf`] for an owned variant.
+///
+/// Raw values are only supported by the JSON format.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json::RawValue;
+///
+/// #[derive(Encode, Decode)]
+/// struct Envelope<'a> {
+///     route: &'a str,
+///     payload: RawValue<'a>,
+/// }
+///
+/// let input = r#"{"route":"users","payload":{"id": 1e400, "id": [ ]}}"#;
+/// let envelope: Envelope<'_> = musli::json::from_str(input)?;
+///
+/// assert_eq!(envelope.route, "users");
+/// assert_eq!(envelope.payload.get(), r#"{"id": 1e400, "id": [ ]}"#);
+///
+/// let output = musli::json::to_string(&envelope)?;
+/// assert_eq!(output, input);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RawValue<'de> {
+    json: &'de str,
+}
+
+impl<'de> RawValue<'de> {
+    /// Get the text of the raw value.
+    #[inline]
+    pub fn get(&self) -> &'de str {
+        self.json
+    }
+
+    /// Convert the raw value into an owned [`RawValueBuf`].
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn to_raw_value_buf(&self) -> RawValueBuf {
+        RawValueBuf {
+            json: self.json.to_owned(),
+        }
+    }
+}
+
+impl fmt::Debug for RawValue<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawValue").field(&self.json).finish()
+    }
+}
+
+impl fmt::Display for RawValue<'_> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.json.fmt(f)
+    }
+}
+
+impl<M> Encode<M> for RawValue<'_> {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_raw(self.json.as_bytes())
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for RawValue<'de>
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct Visitor;
+
+        impl<'de, C> UnsizedVisitor<'de, C, [u8]> for Visitor
+        where
+            C: Context,
+        {
+            type Ok = RawValue<'de>;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a raw value borrowed from the input")
+            }
+
+            #[inline]
+            fn visit_borrowed(self, cx: C, bytes: &'de [u8]) -> Result<Self::Ok, C::Error> {
+                let json = str::from_utf8(bytes).map_err(|error| cx.custom(error))?;
+                Ok(RawValue { json })
+            }
+        }
+
+        decoder.decode_raw(Visitor)
+    }
+}
+
+/// An owned JSON value which is kept as the exact text it was decoded from.
+///
+/// This is the owned variant of [`RawValue`], which can be decoded from any
+/// input since it doesn't borrow from it.
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::json::RawValueBuf;
+///
+/// #[derive(Encode, Decode)]
+/// struct Envelope {
+///     route: String,
+///     payload: RawValueBuf,
+/// }
+///
+/// let input = r#"{"route":"users","payload":[1, "two", {"three": 3}]}"#;
+/// let envelope: Envelope = musli::json::from_str(input)?;
+///
+/// assert_eq!(envelope.payload.get(), r#"[1, "two", {"three": 3}]"#);
+/// assert_eq!(musli::json::to_string(&envelope)?, input);
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RawValueBuf {
+    json: String,
+}
+
+#[cfg(feature = "alloc")]
+impl RawValueBuf {
+    /// Get the text of the raw value.
+    #[inline]
+    pub fn get(&self) -> &str {
+        &self.json
+    }
+
+    /// Borrow the raw value as a [`RawValue`].
+    #[inline]
+    pub fn as_raw_value(&self) -> RawValue<'_> {
+        RawValue { json: &self.json }
+    }
+
+    /// Convert the raw value into the text it was decoded from.
+    #[inline]
+    pub fn into_string(self) -> String {
+        self.json
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Debug for RawValueBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RawValueBuf").field(&self.json).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for RawValueBuf {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.json.fmt(f)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<M> Encode<M> for RawValueBuf {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_raw(self.json.as_bytes())
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'de, M, A> Decode<'de, M, A> for RawValueBuf
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, [u8]> for Visitor
+        where
+            C: Context,
+        {
+            type Ok = RawValueBuf;
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a raw value")
+            }
+
+            #[inline]
+            fn visit_ref(self, cx: C, bytes: &[u8]) -> Result<Self::Ok, C::Error> {
+                let json = str::from_utf8(bytes).map_err(|error| cx.custom(error))?;
+
+                Ok(RawValueBuf {
+                    json: json.to_owned(),
+                })
+            }
+        }
+
+        decoder.decode_raw(Visitor)
+    }
+}
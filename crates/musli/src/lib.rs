@@ -430,6 +430,31 @@
 //!
 //! <br>
 //!
+//! ## Small binaries
+//!
+//! When code size matters, such as when targeting `wasm32-unknown-unknown`, the
+//! following keeps down the amount of code Müsli adds to a binary:
+//!
+//! * Disable default features and only enable the formats which are used, adding
+//!   `alloc` if allocations are needed. So for JSON, use `default-features =
+//!   false` with `features = ["alloc", "json"]`.
+//! * Leave `parse-full` disabled, since it replaces the integer parser used by
+//!   JSON with one which is more complete but larger.
+//! * Use a constant encoding like `json::DEFAULT` or a `const` item of your own.
+//!   Options such as canonical JSON or a fixed float format are then only
+//!   included in the binary when they are enabled.
+//! * Build with `opt-level = "z"`, `lto = true`, `codegen-units = 1` and
+//!   `panic = "abort"`.
+//!
+//! The size of a small model using these settings is tracked for
+//! `wasm32-unknown-unknown` and `x86_64-unknown-linux-gnu` by the `tests-wasm`
+//! crate in the repository, which guards against it growing. So far only the
+//! code used when encoding has been reduced in size. Reducing the code used when
+//! decoding, such as the construction of errors and the parsing of integers, is
+//! not covered.
+//!
+//! <br>
+//!
 //! ## Unsafety
 //!
 //! This is a non-exhaustive list of unsafe use in this crate, and why they are
@@ -453,6 +478,8 @@
 //! * Some `unsafe` is used for owned `String` decoding in all binary formats to
 //!   support faster string processing through [`simdutf8`]. Disabling the
 //!   `simdutf8` feature (enabled by default) removes the use of this unsafe.
+//!   Borrowed `&str` decoding uses the same validation, but borrows directly
+//!   from the input without copying so it doesn't need any unsafe.
 //!
 //! To ensure this library is correctly implemented with regards to memory
 //! safety, extensive testing and fuzzing is performed using `miri`. See
@@ -533,6 +560,8 @@ pub mod en;
 pub use musli_core::hint;
 #[doc(inline)]
 pub use musli_core::mode;
+#[doc(inline)]
+pub use musli_core::semantic;
 
 /// This is an attribute macro that must be used when implementing a
 /// [`Encoder`].
@@ -694,10 +723,15 @@ pub mod alloc;
 pub use self::alloc::Allocator;
 
 pub mod descriptive;
+pub mod ffi;
 pub mod json;
+pub mod negotiate;
 pub mod packed;
+pub mod registry;
 pub mod serde;
+pub mod session;
 pub mod storage;
+pub mod transcode;
 pub mod value;
 pub mod wire;
 
@@ -125,6 +125,14 @@ macro_rules! bare_encoding {
         /// Encode the given value to a fixed-size bytes using the [`DEFAULT`]
         /// [`Encoding`].
         ///
+        /// The returned [`FixedBytes`] only exposes the bytes which were written,
+        /// so [`FixedBytes::len`] is the length of the encoded value and
+        /// [`FixedBytes::as_slice`] never includes any uninitialized bytes.
+        ///
+        /// [`FixedBytes`]: crate::FixedBytes
+        /// [`FixedBytes::len`]: crate::FixedBytes::len
+        /// [`FixedBytes::as_slice`]: crate::FixedBytes::as_slice
+        ///
         /// ```
         /// use musli::{Decode, Encode, FixedBytes};
         #[doc = concat!("use musli::", stringify!($what), ";")]
@@ -278,6 +286,118 @@ macro_rules! bare_encoding {
         {
             $default.from_slice(bytes)
         }
+
+        /// Decode the given type `T` from the given slice using the [`DEFAULT`]
+        /// [`Encoding`], erroring if any bytes remain after the value.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), ";")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        #[doc = concat!("let mut data = ", stringify!($what), "::to_vec(&Person {")]
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        #[doc = concat!("let person: Person = ", stringify!($what), "::from_slice_exact(&data[..])?;")]
+        /// assert_eq!(person.name, "Aristotle");
+        ///
+        /// data.extend_from_slice(b"garbage");
+        #[doc = concat!("assert!(", stringify!($what), "::from_slice_exact::<Person>(&data[..]).is_err());")]
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn from_slice_exact<'de, T>(bytes: &'de [u8]) -> Result<T, Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            $default.from_slice_exact(bytes)
+        }
+
+        /// Decode the given type `T` from the start of the given slice using
+        /// the [`DEFAULT`] [`Encoding`], returning the value and the number of
+        /// bytes it was decoded from.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), ";")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        #[doc = concat!("let mut data = ", stringify!($what), "::to_vec(&[1u32, 2])?;")]
+        #[doc = concat!("data.extend(", stringify!($what), "::to_vec(&[3u32])?);")]
+        ///
+        #[doc = concat!("let (first, n) = ", stringify!($what), "::from_slice_partial::<Vec<u32>>(&data[..])?;")]
+        #[doc = concat!("let (second, m) = ", stringify!($what), "::from_slice_partial::<Vec<u32>>(&data[n..])?;")]
+        /// assert_eq!(first, [1, 2]);
+        /// assert_eq!(second, [3]);
+        /// assert_eq!(n + m, data.len());
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn from_slice_partial<'de, T>(bytes: &'de [u8]) -> Result<(T, usize), Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            $default.from_slice_partial(bytes)
+        }
+
+        /// Validate that the given slice can be decoded as the type `T` using
+        /// the [`DEFAULT`] [`Encoding`].
+        ///
+        /// This accepts and rejects the same inputs as [`from_slice`] for types
+        /// which don't inspect the contents of what they decode, but avoids
+        /// retaining what is being decoded where possible. See
+        /// [`DefaultContext::with_validation`] for details.
+        ///
+        /// [`from_slice`]: self::from_slice
+        /// [`DefaultContext::with_validation`]: crate::context::DefaultContext::with_validation
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), ";")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        #[doc = concat!("let data = ", stringify!($what), "::to_vec(&Person {")]
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        #[doc = concat!(stringify!($what), "::validate::<Person>(&data[..])?;")]
+        #[doc = concat!("assert!(", stringify!($what), "::validate::<Person>(&data[..data.len() - 1]).is_err());")]
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn validate<'de, T>(bytes: &'de [u8]) -> Result<(), Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            $default.validate::<T>(bytes)
+        }
     };
 }
 
@@ -285,13 +405,41 @@ pub(crate) use bare_encoding;
 
 /// Generate all public encoding helpers.
 macro_rules! encoding_impls {
+    (@encode $self:ident, $cx:ident, $writer:ident, $value:ident, $encoder_new:path) => {{
+        let encoder = $encoder_new($cx, $crate::writer::Writer::borrow_mut(&mut $writer));
+        T::encode($value, encoder)?;
+    }};
+
+    (@encode $self:ident, $cx:ident, $writer:ident, $value:ident, $encoder_new:path, $encode:ident) => {{
+        $self.$encode($cx, $crate::writer::Writer::borrow_mut(&mut $writer), $value)?;
+    }};
+
+    (@decode $self:ident, $cx:ident, $reader:ident, $decoder_new:path) => {
+        $decoder_new($cx, $reader)
+    };
+
+    (@decode $self:ident, $cx:ident, $reader:ident, $decoder_new:path, $decoder:ident) => {
+        $self.$decoder($cx, $reader)
+    };
+
+    (@trailing $self:ident, $rest:ident) => {
+        $rest.len()
+    };
+
+    (@trailing $self:ident, $rest:ident, $trailing:ident) => {
+        $self.$trailing($rest)
+    };
+
     (
         $mode:ident,
         $what:ident,
         $encoder_new:path,
         $decoder_new:path,
         $reader_trait:ident :: $into_reader:ident,
-        $writer_trait:ident :: $into_writer:ident $(,)?
+        $writer_trait:ident :: $into_writer:ident
+        $(, @encode $encode:ident)?
+        $(, @decode $decoder:ident)?
+        $(, @trailing $trailing:ident)? $(,)?
     ) => {
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
@@ -427,6 +575,14 @@ macro_rules! encoding_impls {
         /// Encode the given value to a fixed-size bytes using the current
         /// [`Encoding`].
         ///
+        /// The returned [`FixedBytes`] only exposes the bytes which were written,
+        /// so [`FixedBytes::len`] is the length of the encoded value and
+        /// [`FixedBytes::as_slice`] never includes any uninitialized bytes.
+        ///
+        /// [`FixedBytes`]: crate::FixedBytes
+        /// [`FixedBytes::len`]: crate::FixedBytes::len
+        /// [`FixedBytes::as_slice`]: crate::FixedBytes::as_slice
+        ///
         /// # Examples
         ///
         /// ```
@@ -598,6 +754,102 @@ macro_rules! encoding_impls {
             self.from_slice_with(&cx, bytes)
         }
 
+        /// Decode the given type `T` from the given slice using the current
+        /// [`Encoding`], erroring if any bytes remain after the value.
+        ///
+        /// Unlike [`Encoding::from_slice`], this catches framing errors where
+        /// more data than expected is provided.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Debug, Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// let mut data = ENCODING.to_vec(&Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        /// let person: Person = ENCODING.from_slice_exact(&data[..])?;
+        /// assert_eq!(person.name, "Aristotle");
+        /// assert_eq!(person.age, 61);
+        ///
+        /// data.extend_from_slice(b"garbage");
+        /// let error = ENCODING.from_slice_exact::<Person>(&data[..]).unwrap_err();
+        /// assert_eq!(error.to_string(), "7 trailing bytes after value");
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn from_slice_exact<'de, T>(self, bytes: &'de [u8]) -> Result<T, Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            let cx = $crate::context::new().with_error();
+            self.from_slice_exact_with(&cx, bytes)
+        }
+
+        /// Decode the given type `T` from the start of the given slice using
+        /// the current [`Encoding`], returning the value and the number of
+        /// bytes it was decoded from.
+        ///
+        /// This can be used to decode values which are stored one after
+        /// another.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// let mut data = ENCODING.to_vec(&Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        /// ENCODING.encode(&mut data, &Person {
+        ///     name: "Plato".to_string(),
+        ///     age: 80,
+        /// })?;
+        ///
+        /// let (first, n) = ENCODING.from_slice_partial::<Person>(&data[..])?;
+        /// let (second, m) = ENCODING.from_slice_partial::<Person>(&data[n..])?;
+        /// assert_eq!(first.name, "Aristotle");
+        /// assert_eq!(second.name, "Plato");
+        /// assert_eq!(n + m, data.len());
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn from_slice_partial<'de, T>(self, bytes: &'de [u8]) -> Result<(T, usize), Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            let cx = $crate::context::new().with_error();
+            self.from_slice_partial_with(&cx, bytes)
+        }
+
         /// Decode the given type `T` from the given string using the current
         /// [`Encoding`].
         ///
@@ -613,6 +865,52 @@ macro_rules! encoding_impls {
             self.from_slice(string.as_bytes())
         }
 
+        /// Validate that the given slice can be decoded as the type `T` using
+        /// the current [`Encoding`].
+        ///
+        /// This accepts and rejects the same inputs as [`Encoding::from_slice`]
+        /// for types which don't inspect the contents of what they decode, but
+        /// avoids retaining what is being decoded where possible. See
+        /// [`DefaultContext::with_validation`] for details.
+        ///
+        /// [`DefaultContext::with_validation`]: crate::context::DefaultContext::with_validation
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// let data = ENCODING.to_vec(&Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        /// ENCODING.validate::<Person>(&data[..])?;
+        /// assert!(ENCODING.validate::<Person>(&data[..data.len() - 1]).is_err());
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn validate<'de, T>(self, bytes: &'de [u8]) -> Result<(), Error>
+        where
+            T: Decode<'de, $mode, System>,
+        {
+            let cx = $crate::context::new().with_error().with_validation();
+            let _: T = self.from_slice_with(&cx, bytes)?;
+            Ok(())
+        }
+
         /// Encode the given value to the given [`Writer`] using the current
         /// [`Encoding`].
         ///
@@ -662,8 +960,7 @@ macro_rules! encoding_impls {
         {
             cx.clear();
             let mut writer = $writer_trait::$into_writer(writer);
-            let encoder = $encoder_new(cx, $crate::writer::Writer::borrow_mut(&mut writer));
-            T::encode(value, encoder)?;
+            $crate::macros::encoding_impls!(@encode self, cx, writer, value, $encoder_new $(, $encode)?);
             $crate::writer::Writer::finish(&mut writer, cx)
         }
 
@@ -922,7 +1219,7 @@ macro_rules! encoding_impls {
         {
             cx.clear();
             let reader = $reader_trait::$into_reader(reader);
-            T::decode($decoder_new(cx, reader))
+            T::decode($crate::macros::encoding_impls!(@decode self, cx, reader, $decoder_new $(, $decoder)?))
         }
 
         /// Decode the given type `T` from the given slice using the current
@@ -971,6 +1268,53 @@ macro_rules! encoding_impls {
             self.decode_with(cx, bytes)
         }
 
+        /// Decode the given type `T` from the given slice using the current
+        /// [`Encoding`], erroring if any bytes remain after the value.
+        ///
+        /// This is the same as [`Encoding::from_slice_exact`], but allows for
+        /// using a configurable [`Context`].
+        ///
+        /// [`Context`]: crate::Context
+        #[inline]
+        pub fn from_slice_exact_with<'de, C, T>(self, cx: C, bytes: &'de [u8]) -> Result<T, C::Error>
+        where
+            C: Context,
+            T: Decode<'de, $mode, C::Allocator>,
+        {
+            let (value, read) = self.from_slice_partial_with(cx, bytes)?;
+            let rest = &bytes[read..];
+            let trailing = $crate::macros::encoding_impls!(@trailing self, rest $(, $trailing)?);
+
+            if trailing > 0 {
+                return Err(cx.message(format_args!("{trailing} trailing bytes after value")));
+            }
+
+            Ok(value)
+        }
+
+        /// Decode the given type `T` from the start of the given slice using
+        /// the current [`Encoding`], returning the value and the number of
+        /// bytes it was decoded from.
+        ///
+        /// This is the same as [`Encoding::from_slice_partial`], but allows for
+        /// using a configurable [`Context`].
+        ///
+        /// [`Context`]: crate::Context
+        #[inline]
+        pub fn from_slice_partial_with<'de, C, T>(
+            self,
+            cx: C,
+            bytes: &'de [u8],
+        ) -> Result<(T, usize), C::Error>
+        where
+            C: Context,
+            T: Decode<'de, $mode, C::Allocator>,
+        {
+            let mut rest = bytes;
+            let value = self.decode_with(cx, &mut rest)?;
+            Ok((value, bytes.len() - rest.len()))
+        }
+
         /// Decode the given type `T` from the given string using the current
         /// [`Encoding`].
         ///
@@ -989,6 +1333,185 @@ macro_rules! encoding_impls {
         {
             self.from_slice_with(cx, string.as_bytes())
         }
+
+        /// Construct a session which encodes values one after another into
+        /// the given [`Writer`] using the current [`Encoding`].
+        ///
+        /// See the [`session`] module for more information.
+        ///
+        /// [`Writer`]: crate::Writer
+        /// [`session`]: crate::session
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::{Decode, Encode};
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// #[derive(Decode, Encode)]
+        /// struct Person {
+        ///     name: String,
+        ///     age: u32,
+        /// }
+        ///
+        /// let mut encoder = ENCODING.encoder(Vec::new());
+        ///
+        /// encoder.encode(&Person {
+        ///     name: "Aristotle".to_string(),
+        ///     age: 61,
+        /// })?;
+        ///
+        /// encoder.encode(&Person {
+        ///     name: "Plato".to_string(),
+        ///     age: 80,
+        /// })?;
+        ///
+        /// let data = encoder.into_writer();
+        ///
+        /// let mut decoder = ENCODING.decoder(&data);
+        /// let aristotle: Person = decoder.decode()?;
+        /// let plato: Person = decoder.decode()?;
+        /// assert_eq!(aristotle.name, "Aristotle");
+        /// assert_eq!(plato.name, "Plato");
+        /// assert!(decoder.is_empty());
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn encoder<W>(
+            self,
+            writer: W,
+        ) -> $crate::session::EncodeSession<
+            Self,
+            $crate::context::DefaultContext<System, $crate::context::NoTrace, $crate::context::Emit<Error>>,
+            W,
+        >
+        where
+            W: $crate::Writer,
+        {
+            self.encoder_with($crate::context::new().with_error(), writer)
+        }
+
+        /// Construct a session which decodes values one after another from
+        /// the given slice using the current [`Encoding`].
+        ///
+        /// See the [`session`] module for more information.
+        ///
+        /// [`session`]: crate::session
+        ///
+        /// # Examples
+        ///
+        /// ```
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        #[doc = concat!("# use musli::", stringify!($what), "::Error;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// let mut encoder = ENCODING.encoder(Vec::new());
+        ///
+        /// for n in 0..10u32 {
+        ///     encoder.encode(&n)?;
+        /// }
+        ///
+        /// let data = encoder.into_writer();
+        ///
+        /// let mut decoder = ENCODING.decoder(&data);
+        /// let mut sum = 0;
+        ///
+        /// while !decoder.is_empty() {
+        ///     sum += decoder.decode::<u32>()?;
+        /// }
+        ///
+        /// assert_eq!(sum, 45);
+        /// # Ok::<_, Error>(())
+        /// ```
+        #[cfg(feature = "alloc")]
+        #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+        #[inline]
+        pub fn decoder<'de>(
+            self,
+            bytes: &'de [u8],
+        ) -> $crate::session::DecodeSession<
+            'de,
+            Self,
+            $crate::context::DefaultContext<System, $crate::context::NoTrace, $crate::context::Emit<Error>>,
+        > {
+            self.decoder_with($crate::context::new().with_error(), bytes)
+        }
+
+        /// Construct a session which encodes values one after another into
+        /// the given [`Writer`] using the current [`Encoding`].
+        ///
+        /// This is the same as [`Encoding::encoder`], but allows for using a
+        /// configurable [`Context`] which is shared by every value encoded in
+        /// the session. The context is either a reference to a context, or
+        /// an owned [`DefaultContext`].
+        ///
+        /// [`Writer`]: crate::Writer
+        /// [`Context`]: crate::Context
+        /// [`DefaultContext`]: crate::context::DefaultContext
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use musli::alloc::{ArrayBuffer, Slice};
+        /// use musli::context;
+        #[doc = concat!("use musli::", stringify!($what), "::Encoding;")]
+        ///
+        /// const ENCODING: Encoding = Encoding::new();
+        ///
+        /// let mut buf = ArrayBuffer::new();
+        /// let alloc = Slice::new(&mut buf);
+        /// let cx = context::new_in(&alloc);
+        ///
+        /// let mut encoder = ENCODING.encoder_with(&cx, Vec::new());
+        ///
+        /// for n in 0..10u32 {
+        ///     encoder.encode(&n)?;
+        /// }
+        ///
+        /// let data = encoder.into_writer();
+        ///
+        /// let mut decoder = ENCODING.decoder_with(&cx, &data);
+        /// let mut sum = 0;
+        ///
+        /// while !decoder.is_empty() {
+        ///     sum += decoder.decode::<u32>()?;
+        /// }
+        ///
+        /// assert_eq!(sum, 45);
+        /// # Ok::<_, musli::context::ErrorMarker>(())
+        /// ```
+        #[inline]
+        pub fn encoder_with<C, W>(self, cx: C, writer: W) -> $crate::session::EncodeSession<Self, C, W>
+        where
+            C: $crate::session::SessionContext,
+            W: $crate::Writer,
+        {
+            $crate::session::EncodeSession::new(self, cx, writer)
+        }
+
+        /// Construct a session which decodes values one after another from
+        /// the given slice using the current [`Encoding`].
+        ///
+        /// This is the same as [`Encoding::decoder`], but allows for using a
+        /// configurable [`Context`] which is shared by every value decoded in
+        /// the session. The context is either a reference to a context, or
+        /// an owned [`DefaultContext`].
+        ///
+        /// [`Context`]: crate::Context
+        /// [`DefaultContext`]: crate::context::DefaultContext
+        #[inline]
+        pub fn decoder_with<'de, C>(self, cx: C, bytes: &'de [u8]) -> $crate::session::DecodeSession<'de, Self, C>
+        where
+            C: $crate::session::SessionContext,
+        {
+            $crate::session::DecodeSession::new(self, cx, bytes)
+        }
     };
 }
 
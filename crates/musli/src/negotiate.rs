@@ -0,0 +1,221 @@
+//! Negotiating which format configuration to use between two peers.
+//!
+//! A [`FormatId`] identifies a self-describing format, the [`Options`] it was
+//! configured with and the version of its layout. It can be retrieved from an
+//! encoding through `Encoding::format_id`, such as
+//! [`wire::Encoding::format_id`], and is encoded as [`FormatId::LEN`] bytes.
+//!
+//! Peers exchange the identifiers they support once, like in the hello message
+//! when a connection is set up, after which [`negotiate`] picks which one to
+//! use.
+//!
+//! The format versions, like [`wire::FORMAT_VERSION`], are bumped whenever the
+//! layout of a format changes in a way which isn't compatible with older
+//! versions of this crate.
+//!
+//! [`wire::Encoding::format_id`]: crate::wire::Encoding::format_id
+//! [`wire::FORMAT_VERSION`]: crate::wire::FORMAT_VERSION
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::negotiate::{self, FormatId};
+//! use musli::options::{self, Integer, Options};
+//! use musli::wire::Encoding;
+//!
+//! const FIXED: Options = options::new().integer(Integer::Fixed).build();
+//!
+//! const NEW: Encoding<FIXED> = Encoding::new().with_options();
+//! const OLD: Encoding = Encoding::new();
+//!
+//! // An upgraded server prefers the new configuration but still supports the
+//! // old one.
+//! let server = [NEW.format_id(), OLD.format_id()];
+//! let client = [OLD.format_id()];
+//!
+//! // The identifiers are exchanged as bytes.
+//! let bytes = client.map(|id| id.to_bytes());
+//! let client = bytes.map(|bytes| FormatId::from_bytes(bytes).unwrap());
+//!
+//! assert_eq!(negotiate::negotiate(&server, &client), Some(OLD.format_id()));
+//! ```
+
+#![cfg(any(feature = "wire", feature = "descriptive"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "wire", feature = "descriptive"))))]
+
+use core::fmt;
+
+use crate::Options;
+
+/// The kind of format a [`FormatId`] identifies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FormatKind {
+    /// The [`wire`] format.
+    ///
+    /// [`wire`]: crate::wire
+    Wire,
+    /// The [`descriptive`] format.
+    ///
+    /// [`descriptive`]: crate::descriptive
+    Descriptive,
+}
+
+impl FormatKind {
+    #[inline]
+    const fn to_byte(self) -> u8 {
+        match self {
+            FormatKind::Wire => 1,
+            FormatKind::Descriptive => 2,
+        }
+    }
+
+    #[inline]
+    const fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            1 => Some(FormatKind::Wire),
+            2 => Some(FormatKind::Descriptive),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for FormatKind {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatKind::Wire => write!(f, "wire"),
+            FormatKind::Descriptive => write!(f, "descriptive"),
+        }
+    }
+}
+
+/// Identifies a format, the options it's configured with and the version of
+/// its layout.
+///
+/// Two peers can exchange messages if they use the same [`FormatId`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormatId {
+    kind: FormatKind,
+    version: u16,
+    options: Options,
+}
+
+impl FormatId {
+    /// The number of bytes a [`FormatId`] is encoded as.
+    pub const LEN: usize = 7;
+
+    /// Construct a new format identifier.
+    ///
+    /// Prefer using `Encoding::format_id`, such as
+    /// [`wire::Encoding::format_id`], which always uses the current version of
+    /// the format.
+    ///
+    /// [`wire::Encoding::format_id`]: crate::wire::Encoding::format_id
+    #[inline]
+    pub const fn new(kind: FormatKind, version: u16, options: Options) -> Self {
+        Self {
+            kind,
+            version,
+            options,
+        }
+    }
+
+    /// The kind of format.
+    #[inline]
+    pub const fn kind(&self) -> FormatKind {
+        self.kind
+    }
+
+    /// The version of the layout of the format.
+    #[inline]
+    pub const fn version(&self) -> u16 {
+        self.version
+    }
+
+    /// The options the format is configured with.
+    #[inline]
+    pub const fn options(&self) -> Options {
+        self.options
+    }
+
+    /// Encode the identifier as bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::negotiate::FormatId;
+    ///
+    /// let id = musli::wire::DEFAULT.format_id();
+    /// assert_eq!(FormatId::from_bytes(id.to_bytes()), Some(id));
+    /// ```
+    #[inline]
+    pub const fn to_bytes(&self) -> [u8; Self::LEN] {
+        let [v0, v1] = self.version.to_le_bytes();
+        let [o0, o1, o2, o3] = self.options.to_le_bytes();
+        [self.kind.to_byte(), v0, v1, o0, o1, o2, o3]
+    }
+
+    /// Decode an identifier from bytes, returning `None` if the kind of format
+    /// isn't known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::negotiate::FormatId;
+    ///
+    /// assert_eq!(FormatId::from_bytes([0xff, 1, 0, 0, 0, 0, 0]), None);
+    /// ```
+    #[inline]
+    pub const fn from_bytes(bytes: [u8; Self::LEN]) -> Option<Self> {
+        let [kind, v0, v1, o0, o1, o2, o3] = bytes;
+
+        let Some(kind) = FormatKind::from_byte(kind) else {
+            return None;
+        };
+
+        Some(Self {
+            kind,
+            version: u16::from_le_bytes([v0, v1]),
+            options: Options::from_le_bytes([o0, o1, o2, o3]),
+        })
+    }
+}
+
+impl fmt::Display for FormatId {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/v{} (options {:#010x})",
+            self.kind, self.version, self.options
+        )
+    }
+}
+
+/// Pick the format to use out of the ones supported by both peers.
+///
+/// `ours` is ordered by preference, so the first identifier in it which is also
+/// in `theirs` is picked. Returns `None` if there is no identifier in common.
+///
+/// Since the result depends on which side is `ours`, one peer has to be
+/// responsible for negotiating, like the server, and inform the other of the
+/// outcome. Alternatively, both peers can agree on the outcome if they order
+/// the identifiers they support the same way.
+///
+/// # Examples
+///
+/// ```
+/// use musli::negotiate::{self, FormatId, FormatKind};
+///
+/// let a = FormatId::new(FormatKind::Wire, 1, 0);
+/// let b = FormatId::new(FormatKind::Wire, 2, 0);
+/// let c = FormatId::new(FormatKind::Descriptive, 2, 0);
+///
+/// assert_eq!(negotiate::negotiate(&[b, a], &[a, b]), Some(b));
+/// assert_eq!(negotiate::negotiate(&[a, b], &[a, b]), Some(a));
+/// assert_eq!(negotiate::negotiate(&[c], &[a, b]), None);
+/// ```
+pub fn negotiate(ours: &[FormatId], theirs: &[FormatId]) -> Option<FormatId> {
+    ours.iter().find(|id| theirs.contains(id)).copied()
+}
@@ -2,6 +2,14 @@
 
 use core::fmt;
 
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+use crate::hint::Capabilities;
+
 /// [`Options`] builder.
 pub struct Builder(Options);
 
@@ -40,6 +48,10 @@ const INTEGER_BIT: Options = 4;
 const FLOAT_BIT: Options = 8;
 const LENGTH_BIT: Options = 12;
 const MAP_KEYS_AS_NUMBERS_BIT: Options = 16;
+const REJECT_DUPLICATE_KEYS_BIT: Options = 17;
+const CANONICAL_NAN_BIT: Options = 18;
+const CANONICAL_ZERO_BIT: Options = 19;
+const HUMAN_READABLE_BIT: Options = 20;
 
 impl Builder {
     /// Indicates if an integer serialization should be variable.
@@ -136,6 +148,94 @@ impl Builder {
         Self((self.0 & !MASK) | (1 << MAP_KEYS_AS_NUMBERS_BIT))
     }
 
+    /// Configure a format to reject maps which contain the same key more than
+    /// once.
+    ///
+    /// By default the last entry for a key wins when decoding a map. Since
+    /// some protocols treat duplicate keys as a sign of corruption or
+    /// tampering, this makes decoding a map such as a `HashMap` or a
+    /// `BTreeMap` error instead.
+    ///
+    /// This is reported by decoders through
+    /// [`Capabilities::REJECT_DUPLICATE_KEYS`].
+    ///
+    /// [`Capabilities::REJECT_DUPLICATE_KEYS`]: crate::hint::Capabilities::REJECT_DUPLICATE_KEYS
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options};
+    ///
+    /// const OPTIONS: Options = options::new().reject_duplicate_keys().build();
+    /// ```
+    #[inline]
+    pub const fn reject_duplicate_keys(self) -> Self {
+        Self(self.0 | (1 << REJECT_DUPLICATE_KEYS_BIT))
+    }
+
+    /// Configure a format to encode every NaN using the same bits.
+    ///
+    /// A NaN can be represented by many different bit patterns, so two values
+    /// which both are NaN might otherwise encode differently. With this option
+    /// every NaN is encoded as a positive quiet NaN without a payload, which
+    /// is also what is produced when it's decoded. This is useful when the
+    /// encoded output needs to be stable, such as when it's hashed.
+    ///
+    /// Since floats can no longer be copied as-is, this disables bitwise
+    /// encoding and decoding of types which contain them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options};
+    /// use musli::storage::Encoding;
+    /// # use musli::storage::Error;
+    ///
+    /// const OPTIONS: Options = options::new().canonical_nan().build();
+    /// const ENCODING: Encoding<OPTIONS> = Encoding::new().with_options();
+    ///
+    /// let a = ENCODING.to_vec(&f64::NAN)?;
+    /// let b = ENCODING.to_vec(&-f64::from_bits(0x7ff8_0000_0000_0001))?;
+    /// assert_eq!(a, b);
+    ///
+    /// let value: f64 = ENCODING.from_slice(&b)?;
+    /// assert_eq!(value.to_bits(), 0x7ff8_0000_0000_0000);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub const fn canonical_nan(self) -> Self {
+        Self(self.0 | (1 << CANONICAL_NAN_BIT))
+    }
+
+    /// Configure a format to encode negative zero as positive zero.
+    ///
+    /// Negative and positive zero compare equal but have different bits, so
+    /// this permits them to encode identically. Note that this means that
+    /// negative zero decodes as positive zero.
+    ///
+    /// Like [`canonical_nan()`], this disables bitwise encoding and decoding
+    /// of types which contain floats.
+    ///
+    /// [`canonical_nan()`]: Builder::canonical_nan
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options};
+    /// use musli::storage::Encoding;
+    /// # use musli::storage::Error;
+    ///
+    /// const OPTIONS: Options = options::new().canonical_nan().canonical_zero().build();
+    /// const ENCODING: Encoding<OPTIONS> = Encoding::new().with_options();
+    ///
+    /// assert_eq!(ENCODING.to_vec(&-0.0f32)?, ENCODING.to_vec(&0.0f32)?);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[inline]
+    pub const fn canonical_zero(self) -> Self {
+        Self(self.0 | (1 << CANONICAL_ZERO_BIT))
+    }
+
     /// Configure the options to use fixed serialization.
     ///
     /// This causes numerical types to use the default fixed-length
@@ -192,6 +292,17 @@ impl Builder {
             .pointer(Width::Variable)
     }
 
+    /// Mark values decoded with these options as human-readable.
+    ///
+    /// This is used by human-readable formats such as JSON which buffer
+    /// values, so that the buffered value is decoded the same way as the
+    /// format it was read from.
+    #[cfg(feature = "json")]
+    #[inline]
+    pub(crate) const fn human_readable(self) -> Self {
+        Self(self.0 | (1 << HUMAN_READABLE_BIT))
+    }
+
     /// Built an options builder into a constant.
     ///
     /// # Examples
@@ -218,6 +329,13 @@ impl fmt::Debug for Builder {
                 "is_map_keys_as_numbers",
                 &is_map_keys_as_numbers_value(self.0),
             )
+            .field(
+                "is_reject_duplicate_keys",
+                &is_reject_duplicate_keys_value(self.0),
+            )
+            .field("is_canonical_nan", &is_canonical_nan_value(self.0))
+            .field("is_canonical_zero", &is_canonical_zero_value(self.0))
+            .field("is_human_readable", &is_human_readable_value(self.0))
             .finish()
     }
 }
@@ -320,6 +438,92 @@ const fn is_map_keys_as_numbers_value(opt: Options) -> bool {
     ((opt >> MAP_KEYS_AS_NUMBERS_BIT) & 0b1) == 1
 }
 
+/// Add the capabilities implied by the given options to `capabilities`.
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline]
+pub(crate) const fn capabilities<const OPT: Options>(
+    mut capabilities: Capabilities,
+) -> Capabilities {
+    if is_reject_duplicate_keys_value(OPT) {
+        capabilities = capabilities.with(Capabilities::REJECT_DUPLICATE_KEYS);
+    }
+
+    if is_human_readable_value(OPT) {
+        capabilities = capabilities.with(Capabilities::HUMAN_READABLE);
+    }
+
+    capabilities
+}
+
+const fn is_human_readable_value(opt: Options) -> bool {
+    ((opt >> HUMAN_READABLE_BIT) & 0b1) == 1
+}
+
+const fn is_reject_duplicate_keys_value(opt: Options) -> bool {
+    ((opt >> REJECT_DUPLICATE_KEYS_BIT) & 0b1) == 1
+}
+
+const fn is_canonical_nan_value(opt: Options) -> bool {
+    ((opt >> CANONICAL_NAN_BIT) & 0b1) == 1
+}
+
+const fn is_canonical_zero_value(opt: Options) -> bool {
+    ((opt >> CANONICAL_ZERO_BIT) & 0b1) == 1
+}
+
+/// Canonicalize a 32-bit float before it's encoded, according to the options.
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline]
+pub(crate) fn canonical_f32<const OPT: Options>(value: f32) -> f32 {
+    /// The bits of the canonical 32-bit NaN, which is a positive quiet NaN
+    /// without a payload.
+    const CANONICAL_NAN_F32: u32 = 0x7fc0_0000;
+
+    if is_canonical_nan_value(OPT) && value.is_nan() {
+        return f32::from_bits(CANONICAL_NAN_F32);
+    }
+
+    if is_canonical_zero_value(OPT) && value == 0.0 {
+        return 0.0;
+    }
+
+    value
+}
+
+/// Canonicalize a 64-bit float before it's encoded, according to the options.
+#[cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "value"
+))]
+#[inline]
+pub(crate) fn canonical_f64<const OPT: Options>(value: f64) -> f64 {
+    /// The bits of the canonical 64-bit NaN, which is a positive quiet NaN
+    /// without a payload.
+    const CANONICAL_NAN_F64: u64 = 0x7ff8_0000_0000_0000;
+
+    if is_canonical_nan_value(OPT) && value.is_nan() {
+        return f64::from_bits(CANONICAL_NAN_F64);
+    }
+
+    if is_canonical_zero_value(OPT) && value == 0.0 {
+        return 0.0;
+    }
+
+    value
+}
+
 #[cfg(any(
     feature = "storage",
     feature = "wire",
@@ -330,7 +534,8 @@ pub(crate) const fn is_native_fixed<const OPT: Options>() -> bool {
     matches!(
         (integer::<OPT>(), float::<OPT>(), length::<OPT>(),),
         (Integer::Fixed, Float::Fixed, Width::NATIVE)
-    )
+    ) && !is_canonical_nan_value(OPT)
+        && !is_canonical_zero_value(OPT)
 }
 
 /// Integer serialization mode.
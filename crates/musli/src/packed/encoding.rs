@@ -7,7 +7,7 @@ use core::marker;
 use crate::alloc::System;
 use crate::mode::Binary;
 use crate::options;
-use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options};
+use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options, Reader};
 
 #[cfg(feature = "alloc")]
 use super::error::Error;
@@ -27,7 +27,7 @@ pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
 where
     M: 'static,
 {
-    _marker: marker::PhantomData<M>,
+    _marker: marker::PhantomData<fn() -> M>,
 }
 
 impl Default for Encoding<OPTIONS, Binary> {
@@ -123,6 +123,101 @@ where
         IntoReader::into_reader,
         IntoWriter::into_writer,
     );
+
+    /// Decode the given type `T` from the start of the given reader using the
+    /// current [`Encoding`], returning the value together with the reader
+    /// positioned after it.
+    ///
+    /// Since fields of `#[musli(packed)]` types are stored back to back, this
+    /// can be used to decode a header made up of the leading fields of a
+    /// message, and to decide based on it how to decode the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::packed::Encoding;
+    /// # use musli::packed::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode)]
+    /// #[musli(packed)]
+    /// struct Header {
+    ///     kind: u8,
+    /// }
+    ///
+    /// #[derive(Encode)]
+    /// #[musli(packed)]
+    /// struct TextMessage {
+    ///     kind: u8,
+    ///     body: Text,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(packed)]
+    /// struct Ping {
+    ///     sequence: u32,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(packed)]
+    /// struct Text {
+    ///     text: String,
+    /// }
+    ///
+    /// let data = ENCODING.to_vec(&TextMessage {
+    ///     kind: 2,
+    ///     body: Text { text: String::from("Hello") },
+    /// })?;
+    ///
+    /// let (header, rest) = ENCODING.decode_reader_prefix::<_, Header>(&data[..])?;
+    ///
+    /// match header.kind {
+    ///     1 => {
+    ///         let ping: Ping = ENCODING.decode(rest)?;
+    ///         assert_eq!(ping.sequence, 42);
+    ///     }
+    ///     2 => {
+    ///         let text: Text = ENCODING.decode(rest)?;
+    ///         assert_eq!(text.text, "Hello");
+    ///     }
+    ///     kind => panic!("unsupported message kind {kind}"),
+    /// }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_reader_prefix<'de, R, T>(self, reader: R) -> Result<(T, R), Error>
+    where
+        R: Reader<'de>,
+        T: Decode<'de, M, System>,
+    {
+        let cx = crate::context::new().with_error();
+        self.decode_reader_prefix_with(&cx, reader)
+    }
+
+    /// Decode the given type `T` from the start of the given reader using the
+    /// current [`Encoding`], returning the value together with the reader
+    /// positioned after it.
+    ///
+    /// This is the same as [`Encoding::decode_reader_prefix`], but allows for
+    /// using a configurable [`Context`].
+    #[inline]
+    pub fn decode_reader_prefix_with<'de, C, R, T>(
+        self,
+        cx: C,
+        mut reader: R,
+    ) -> Result<(T, R), C::Error>
+    where
+        C: Context,
+        R: Reader<'de>,
+        T: Decode<'de, M, C::Allocator>,
+    {
+        let value = self.decode_with(cx, &mut reader)?;
+        Ok((value, reader))
+    }
 }
 
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
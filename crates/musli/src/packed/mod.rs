@@ -94,7 +94,10 @@ pub use self::encoding::to_vec;
 pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_slice_partial, to_fixed_bytes, to_slice,
+    validate,
+};
 #[doc(inline)]
 pub use self::encoding::{Encoding, DEFAULT, OPTIONS};
 #[doc(inline)]
@@ -17,8 +17,11 @@ mod sealed {
 
     impl Sealed for &[u8] {}
     impl Sealed for super::SliceReader<'_> {}
+    impl Sealed for super::IncrementalReader<'_> {}
     impl<'de, R> Sealed for Limit<R> where R: Reader<'de> {}
     impl<'de, R> Sealed for &mut R where R: ?Sized + Reader<'de> {}
+    #[cfg(feature = "encryption")]
+    impl Sealed for crate::wrap::DecryptingReader {}
 }
 
 /// Coerce a type into a [`Reader`].
@@ -61,9 +64,40 @@ where
     fn is_eof(&mut self) -> bool;
 
     /// Skip over the given number of bytes.
+    ///
+    /// This is used by formats to skip over values whose length in bytes is
+    /// known up front, such as bytes and strings, without inspecting them.
+    /// Readers over slices implement this by advancing their position, which
+    /// doesn't touch the skipped data at all.
+    ///
+    /// By default this reads the bytes through [`Reader::read_bytes`] and
+    /// discards them, which does not allocate.
+    #[inline]
     fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
     where
-        C: Context;
+        C: Context,
+    {
+        struct Visitor;
+
+        impl<C> UnsizedVisitor<'_, C, [u8]> for Visitor
+        where
+            C: Context,
+        {
+            type Ok = ();
+
+            #[inline]
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "bytes to skip")
+            }
+
+            #[inline]
+            fn visit_ref(self, _: C, _: &[u8]) -> Result<Self::Ok, C::Error> {
+                Ok(())
+            }
+        }
+
+        self.read_bytes(cx, n, Visitor)
+    }
 
     /// Peek the next value.
     fn peek(&mut self) -> Option<u8>;
@@ -490,6 +524,173 @@ where
     }
 }
 
+/// The outcome of decoding from input which might not be complete yet.
+///
+/// This is returned by incremental decoding functions, such as
+/// [`storage::Encoding::decode_incremental`].
+///
+/// [`storage::Encoding::decode_incremental`]: crate::storage::Encoding::decode_incremental
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Incremental<T> {
+    /// The value was decoded, along with the number of bytes it occupied.
+    Complete(T, usize),
+    /// The input ended before the value was decoded, and at least the given
+    /// number of bytes have to be appended to it before decoding it again.
+    NeedMore(usize),
+}
+
+/// A [`Reader`] over a slice which records how many bytes were missing when it
+/// runs out of input, rather than only failing.
+///
+/// This is used to decode from input which arrives in pieces, where running
+/// out of bytes means that the caller should append more bytes and try again.
+///
+/// # Examples
+///
+/// ```
+/// use musli::Context;
+/// use musli::reader::{IncrementalReader, Reader};
+///
+/// fn process<C>(cx: C) -> Result<(), C::Error>
+/// where
+///     C: Context
+/// {
+///     let mut reader = IncrementalReader::new(&[1, 2, 3]);
+///     reader.skip(cx, 2)?;
+///     assert_eq!(reader.consumed(), 2);
+///
+///     assert!(reader.read_array::<_, 4>(cx).is_err());
+///     assert_eq!(reader.needed(), Some(3));
+///     Ok(())
+/// }
+/// ```
+pub struct IncrementalReader<'de> {
+    slice: &'de [u8],
+    len: usize,
+    needed: Option<usize>,
+}
+
+impl<'de> IncrementalReader<'de> {
+    /// Construct a new incremental reader around the specified slice.
+    #[inline]
+    pub fn new(slice: &'de [u8]) -> Self {
+        Self {
+            slice,
+            len: slice.len(),
+            needed: None,
+        }
+    }
+
+    /// Get the remaining contents of the reader as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &'de [u8] {
+        self.slice
+    }
+
+    /// The number of bytes which have been read so far.
+    #[inline]
+    pub fn consumed(&self) -> usize {
+        self.len - self.slice.len()
+    }
+
+    /// The number of bytes which were missing the first time the reader ran
+    /// out of input, if it has.
+    #[inline]
+    pub fn needed(&self) -> Option<usize> {
+        self.needed
+    }
+
+    #[inline]
+    fn bounds_check<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        if self.slice.len() < n {
+            self.needed.get_or_insert(n - self.slice.len());
+            return Err(cx.message(SliceUnderflow::new(n, self.slice.len())));
+        }
+
+        Ok(())
+    }
+}
+
+impl<'de> Reader<'de> for IncrementalReader<'de> {
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn is_eof(&mut self) -> bool {
+        self.slice.is_empty()
+    }
+
+    #[inline]
+    fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, n)?;
+        self.slice.skip(cx, n)
+    }
+
+    #[inline]
+    fn read_bytes<C, V>(&mut self, cx: C, n: usize, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context,
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        self.bounds_check(cx, n)?;
+        self.slice.read_bytes(cx, n, visitor)
+    }
+
+    #[inline]
+    unsafe fn read_bytes_uninit<C>(&mut self, cx: C, ptr: *mut u8, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, n)?;
+        self.slice.read_bytes_uninit(cx, ptr, n)
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.slice.peek()
+    }
+
+    #[inline]
+    fn read<C>(&mut self, cx: C, buf: &mut [u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, buf.len())?;
+        self.slice.read(cx, buf)
+    }
+
+    #[inline]
+    fn read_byte<C>(&mut self, cx: C) -> Result<u8, C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, 1)?;
+        self.slice.read_byte(cx)
+    }
+
+    #[inline]
+    fn read_array<C, const N: usize>(&mut self, cx: C) -> Result<[u8; N], C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, N)?;
+        self.slice.read_array(cx)
+    }
+}
+
 /// Limit the number of bytes that can be read out of a reader to the specified limit.
 ///
 /// Constructed through [Reader::limit].
@@ -0,0 +1,341 @@
+//! Encoding and decoding trait objects through a registry of the types which
+//! implement them.
+//!
+//! The [`registry!`] macro associates a stable numerical id with each type
+//! which can be stored behind a trait object. Encoding `dyn Trait` stores the
+//! id of the concrete type followed by its value as a variant, and decoding a
+//! `Box<dyn Trait>` looks up the id to decode the right type.
+//!
+//! The trait must have [`AsAny`] as a supertrait, which is implemented for
+//! every sized type with a `'static` lifetime.
+//!
+//! Since ids are written instead of type names, they must stay the same for as
+//! long as encoded values need to be decoded, and must never be reused for a
+//! different type. Types can be added to a registry at any time.
+//!
+//! Values of types which aren't registered fail to encode, and unknown ids fail
+//! to decode. An optional `_ => Fallback` entry makes unknown ids decode as the
+//! [`Default`] value of `Fallback` instead, which lets older versions of a
+//! program skip over values of types added later.
+//!
+//! `Arc<dyn Trait>` and `Rc<dyn Trait>` encode through their contents, and can
+//! be decoded by using the [`arc`] or [`rc`] modules with
+//! `#[musli(with = ..)]`.
+//!
+//! [`registry!`]: crate::registry!
+//!
+//! # Examples
+//!
+//! ```
+//! use std::sync::Arc;
+//!
+//! use musli::{Decode, Encode};
+//! use musli::registry::AsAny;
+//!
+//! trait Shape: AsAny {
+//!     fn area(&self) -> f64;
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Circle {
+//!     radius: f64,
+//! }
+//!
+//! impl Shape for Circle {
+//!     fn area(&self) -> f64 {
+//!         std::f64::consts::PI * self.radius * self.radius
+//!     }
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Square {
+//!     side: f64,
+//! }
+//!
+//! impl Shape for Square {
+//!     fn area(&self) -> f64 {
+//!         self.side * self.side
+//!     }
+//! }
+//!
+//! musli::registry! {
+//!     dyn Shape {
+//!         1 => Circle,
+//!         2 => Square,
+//!     }
+//! }
+//!
+//! #[derive(Encode, Decode)]
+//! struct Drawing {
+//!     shapes: Vec<Box<dyn Shape>>,
+//!     #[musli(with = musli::registry::arc)]
+//!     background: Arc<dyn Shape>,
+//! }
+//!
+//! let drawing = Drawing {
+//!     shapes: vec![Box::new(Circle { radius: 1.0 }), Box::new(Square { side: 2.0 })],
+//!     background: Arc::new(Square { side: 10.0 }),
+//! };
+//!
+//! let bytes = musli::wire::to_vec(&drawing)?;
+//! let drawing: Drawing = musli::wire::from_slice(&bytes)?;
+//!
+//! let areas = drawing.shapes.iter().map(|shape| shape.area()).collect::<Vec<_>>();
+//! assert_eq!(areas, [std::f64::consts::PI, 4.0]);
+//! assert_eq!(drawing.background.area(), 100.0);
+//! # Ok::<_, musli::wire::Error>(())
+//! ```
+
+#![cfg(feature = "alloc")]
+#![cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+
+use core::any::Any;
+
+#[doc(hidden)]
+pub use rust_alloc::boxed::Box;
+
+/// Access a value as [`Any`], so that the concrete type behind a trait object
+/// can be recovered.
+///
+/// This has to be a supertrait of traits used with [`registry!`].
+///
+/// [`registry!`]: crate::registry!
+pub trait AsAny {
+    /// Access the value as [`Any`].
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<T> AsAny for T
+where
+    T: Any,
+{
+    #[inline]
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+macro_rules! shared {
+    ($module:ident, $ty:ident, $($path:ident)::*) => {
+        #[doc = concat!("Encode and decode an `", stringify!($ty), "` of a registered trait object.")]
+        ///
+        /// This is intended to be used with
+        #[doc = concat!("`#[musli(with = musli::registry::", stringify!($module), ")]`.")]
+        /// Decoding goes through a `Box` of the value, so it works for any type
+        /// which can be decoded as a `Box`.
+        pub mod $module {
+            use rust_alloc::boxed::Box;
+            use rust_alloc::$($path)::*;
+
+            use crate::{Decode, Decoder, Encode, Encoder};
+
+            #[doc = concat!("Encode the contents of an `", stringify!($ty), "`.")]
+            #[inline]
+            pub fn encode<E, T>(value: &$ty<T>, encoder: E) -> Result<E::Ok, E::Error>
+            where
+                E: Encoder,
+                T: ?Sized + Encode<E::Mode>,
+            {
+                encoder.encode(&**value)
+            }
+
+            #[doc = concat!("Decode an `", stringify!($ty), "` through a `Box`.")]
+            #[inline]
+            pub fn decode<'de, D, T>(decoder: D) -> Result<$ty<T>, D::Error>
+            where
+                D: Decoder<'de>,
+                T: ?Sized,
+                Box<T>: Decode<'de, D::Mode, D::Allocator>,
+            {
+                Ok($ty::from(decoder.decode::<Box<T>>()?))
+            }
+        }
+    };
+}
+
+shared!(arc, Arc, sync::Arc);
+shared!(rc, Rc, rc::Rc);
+
+/// Implement encoding for `dyn Trait` and decoding for `Box<dyn Trait>` by
+/// associating a stable numerical id with each type implementing the trait.
+///
+/// The trait must have [`AsAny`] as a supertrait. An optional trailing
+/// `_ => Fallback` entry is decoded as `Fallback::default()` when an unknown id
+/// is encountered, instead of failing.
+///
+/// See the [`registry`] module for details.
+///
+/// [`AsAny`]: crate::registry::AsAny
+/// [`registry`]: crate::registry
+///
+/// # Examples
+///
+/// ```
+/// use musli::registry::AsAny;
+/// use musli::{Decode, Encode};
+///
+/// trait Plugin: AsAny {
+///     fn name(&self) -> &str;
+/// }
+///
+/// #[derive(Encode, Decode)]
+/// struct Logger {
+///     level: u8,
+/// }
+///
+/// impl Plugin for Logger {
+///     fn name(&self) -> &str {
+///         "logger"
+///     }
+/// }
+///
+/// #[derive(Default)]
+/// struct Unsupported;
+///
+/// impl Plugin for Unsupported {
+///     fn name(&self) -> &str {
+///         "unsupported"
+///     }
+/// }
+///
+/// musli::registry! {
+///     dyn Plugin {
+///         1 => Logger,
+///         _ => Unsupported,
+///     }
+/// }
+///
+/// let plugin: Box<dyn Plugin> = Box::new(Logger { level: 3 });
+/// let bytes = musli::wire::to_vec(&plugin)?;
+/// let plugin: Box<dyn Plugin> = musli::wire::from_slice(&bytes)?;
+/// assert_eq!(plugin.name(), "logger");
+///
+/// // A newer version which has registered another plugin.
+/// trait NewPlugin: AsAny {}
+///
+/// #[derive(Encode, Decode)]
+/// struct Metrics {
+///     interval: u32,
+/// }
+///
+/// impl NewPlugin for Logger {}
+/// impl NewPlugin for Metrics {}
+///
+/// musli::registry! {
+///     dyn NewPlugin {
+///         1 => Logger,
+///         2 => Metrics,
+///     }
+/// }
+///
+/// let plugin: Box<dyn NewPlugin> = Box::new(Metrics { interval: 10 });
+/// let bytes = musli::wire::to_vec(&plugin)?;
+/// let plugin: Box<dyn Plugin> = musli::wire::from_slice(&bytes)?;
+/// assert_eq!(plugin.name(), "unsupported");
+/// # Ok::<_, musli::wire::Error>(())
+/// ```
+#[macro_export]
+macro_rules! registry {
+    (dyn $trait:path { $($id:literal => $ty:ty),* $(,)? }) => {
+        $crate::__registry!(dyn $trait { $($id => $ty),* } {});
+    };
+
+    (dyn $trait:path { $($id:literal => $ty:ty,)* _ => $fallback:ty $(,)? }) => {
+        $crate::__registry!(dyn $trait { $($id => $ty),* } { $fallback });
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __registry {
+    (dyn $trait:path { $($id:literal => $ty:ty),* } { $($fallback:ty)? }) => {
+        impl<M> $crate::Encode<M> for dyn $trait
+        where
+            $($ty: $crate::Encode<M>,)*
+        {
+            type Encode = Self;
+
+            #[inline]
+            fn encode<E>(&self, encoder: E) -> ::core::result::Result<E::Ok, E::Error>
+            where
+                E: $crate::Encoder<Mode = M>,
+            {
+                let any = $crate::registry::AsAny::as_any(self);
+
+                $(
+                    if let ::core::option::Option::Some(value) = any.downcast_ref::<$ty>() {
+                        const ID: u32 = $id;
+                        let variant = $crate::Encoder::encode_variant(encoder)?;
+                        return $crate::en::VariantEncoder::insert_variant(variant, ID, value);
+                    }
+                )*
+
+                let cx = $crate::Encoder::cx(&encoder);
+
+                ::core::result::Result::Err($crate::Context::message(
+                    cx,
+                    ::core::concat!(
+                        "Value is not of a type registered for dyn ",
+                        ::core::stringify!($trait)
+                    ),
+                ))
+            }
+
+            #[inline]
+            fn as_encode(&self) -> &Self::Encode {
+                self
+            }
+        }
+
+        impl<'de, M, A> $crate::Decode<'de, M, A> for $crate::registry::Box<dyn $trait>
+        where
+            A: $crate::Allocator,
+            u32: $crate::Decode<'de, M, A>,
+            $($ty: $crate::Decode<'de, M, A>,)*
+        {
+            #[inline]
+            fn decode<D>(decoder: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: $crate::Decoder<'de, Mode = M, Allocator = A>,
+            {
+                $crate::Decoder::decode_variant(decoder, |variant| {
+                    let tag = $crate::de::VariantDecoder::decode_tag(variant)?;
+                    let id: u32 = $crate::Decoder::decode(tag)?;
+
+                    $(
+                        if id == $id {
+                            let value = $crate::de::VariantDecoder::decode_value(variant)?;
+                            let value: $ty = $crate::Decoder::decode(value)?;
+                            let value: $crate::registry::Box<dyn $trait> = $crate::registry::Box::new(value);
+                            return ::core::result::Result::Ok(value);
+                        }
+                    )*
+
+                    $crate::__registry!(@unknown $trait, variant, id $(, $fallback)?)
+                })
+            }
+        }
+    };
+
+    (@unknown $trait:path, $variant:ident, $id:ident) => {{
+        let cx = $crate::de::VariantDecoder::cx($variant);
+
+        ::core::result::Result::Err($crate::Context::message(
+            cx,
+            ::core::format_args!(
+                "Unknown id {} for dyn {}",
+                $id,
+                ::core::stringify!($trait)
+            ),
+        ))
+    }};
+
+    (@unknown $trait:path, $variant:ident, $id:ident, $fallback:ty) => {{
+        let _ = $id;
+        let value = $crate::de::VariantDecoder::decode_value($variant)?;
+        $crate::Decoder::skip(value)?;
+        let value: $fallback = ::core::default::Default::default();
+        let value: $crate::registry::Box<dyn $trait> = $crate::registry::Box::new(value);
+        ::core::result::Result::Ok(value)
+    }};
+}
@@ -142,6 +142,96 @@ where
         self.inner.alloc()
     }
 
+    #[inline]
+    fn is_validating(self) -> bool {
+        self.inner.is_validating()
+    }
+
+    #[inline]
+    fn suspend_validating(self) {
+        self.inner.suspend_validating();
+    }
+
+    #[inline]
+    fn resume_validating(self) {
+        self.inner.resume_validating();
+    }
+
+    #[inline]
+    fn is_sharing(self) -> bool {
+        self.inner.is_sharing()
+    }
+
+    #[inline]
+    fn shared_index(self, ptr: *const ()) -> Option<usize> {
+        self.inner.shared_index(ptr)
+    }
+
+    #[inline]
+    fn insert_shared_ptr(self, ptr: *const ()) {
+        self.inner.insert_shared_ptr(ptr);
+    }
+
+    #[inline]
+    fn shared_value<T>(self, index: usize) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.inner.shared_value(index)
+    }
+
+    #[inline]
+    fn insert_shared_value<T>(self, value: T)
+    where
+        T: 'static + Clone,
+    {
+        self.inner.insert_shared_value(value);
+    }
+
+    #[inline]
+    fn is_interning(self) -> bool {
+        self.inner.is_interning()
+    }
+
+    #[inline]
+    fn interned<T>(self, string: &str) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.inner.interned(string)
+    }
+
+    #[inline]
+    fn insert_interned<T>(self, string: &str, value: T)
+    where
+        T: 'static + Clone,
+    {
+        self.inner.insert_interned(string, value);
+    }
+
+    #[inline]
+    fn user_data<T>(self) -> Option<T>
+    where
+        T: 'static + Clone,
+    {
+        self.inner.user_data()
+    }
+
+    #[inline]
+    fn enter_depth(self) -> Result<(), Self::Error> {
+        if let Err(error) = self.inner.enter_depth() {
+            *self.error.borrow_mut() = Some(error);
+            return Err(error::SerdeError::Captured);
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn leave_depth(self) {
+        self.inner.leave_depth();
+    }
+
     #[inline]
     fn custom<E>(self, error: E) -> Self::Error
     where
@@ -236,6 +326,14 @@ where
     fn leave_sequence_index(self) {
         self.inner.leave_sequence_index();
     }
+
+    #[inline]
+    fn annotate<V>(self, key: &'static str, value: V)
+    where
+        V: fmt::Display,
+    {
+        self.inner.annotate(key, value);
+    }
 }
 
 /// Encode the given serde value `T` to the given [Encoder] using the serde
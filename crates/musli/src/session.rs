@@ -0,0 +1,440 @@
+//! Sessions for encoding several values into one writer, or decoding several
+//! values from one slice.
+//!
+//! A session holds on to an encoding, a [`Context`] and the writer or slice
+//! it operates over, so that values can be processed one after another
+//! without setting each of them up again. In particular a context carrying an
+//! allocator is shared by every value processed in the session.
+//!
+//! Sessions are constructed through the `encoder` and `decoder` methods of an
+//! encoding, such as [`wire::Encoding::encoder`], or through their `_with`
+//! counterparts like [`wire::Encoding::encoder_with`] which use a
+//! configurable [`Context`].
+//!
+//! [`wire::Encoding::encoder`]: crate::wire::Encoding::encoder
+//! [`wire::Encoding::encoder_with`]: crate::wire::Encoding::encoder_with
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::wire::Encoding;
+//!
+//! const ENCODING: Encoding = Encoding::new();
+//!
+//! #[derive(Debug, PartialEq, Decode, Encode)]
+//! struct Point {
+//!     x: u32,
+//!     y: u32,
+//! }
+//!
+//! let mut encoder = ENCODING.encoder(Vec::new());
+//!
+//! for n in 0..4 {
+//!     encoder.encode(&Point { x: n, y: n * 2 })?;
+//! }
+//!
+//! let bytes = encoder.into_writer();
+//!
+//! let mut decoder = ENCODING.decoder(&bytes);
+//! let mut points = Vec::new();
+//!
+//! while !decoder.is_empty() {
+//!     points.push(decoder.decode::<Point>()?);
+//! }
+//!
+//! assert_eq!(points.len(), 4);
+//! assert_eq!(points[3], Point { x: 3, y: 6 });
+//! # Ok::<_, musli::wire::Error>(())
+//! ```
+
+#![cfg(any(
+    feature = "storage",
+    feature = "wire",
+    feature = "descriptive",
+    feature = "json",
+    feature = "value"
+))]
+#![cfg_attr(
+    doc_cfg,
+    doc(cfg(any(
+        feature = "storage",
+        feature = "wire",
+        feature = "descriptive",
+        feature = "json"
+    )))
+)]
+
+use crate::context::{DefaultContext, ErrorMode, InternMode, ShareMode, TraceMode};
+use crate::{Allocator, Context, Decode, Encode, Writer};
+
+mod sealed {
+    use crate::context::{DefaultContext, TraceMode};
+    use crate::{Allocator, Context};
+
+    pub trait Sealed {}
+
+    pub trait SealedContext {}
+
+    impl<C> SealedContext for C where C: Context {}
+
+    impl<A, T, C, S, I> SealedContext for DefaultContext<A, T, C, S, I>
+    where
+        A: Allocator,
+        T: TraceMode,
+    {
+    }
+}
+
+/// An encoding which can be used in a session.
+///
+/// This is implemented by the `Encoding` type of every binary and text
+/// format.
+pub trait Format: Copy + self::sealed::Sealed {
+    /// The mode used by the encoding.
+    type Mode: 'static;
+
+    #[doc(hidden)]
+    fn __encode_with<C, W, T>(self, cx: C, writer: &mut W, value: &T) -> Result<W::Ok, C::Error>
+    where
+        C: Context,
+        W: Writer,
+        T: ?Sized + Encode<Self::Mode>;
+
+    #[doc(hidden)]
+    fn __from_slice_partial_with<'de, C, T>(
+        self,
+        cx: C,
+        bytes: &'de [u8],
+    ) -> Result<(T, usize), C::Error>
+    where
+        C: Context,
+        T: Decode<'de, Self::Mode, C::Allocator>;
+
+    /// Bytes written between values which are not self-delimiting.
+    #[doc(hidden)]
+    const __SEPARATOR: &'static [u8] = b"";
+
+    /// Count the bytes in `rest` which are not insignificant trailing data.
+    #[doc(hidden)]
+    #[inline]
+    fn __trailing(self, rest: &[u8]) -> usize {
+        rest.len()
+    }
+}
+
+/// A context which can be held by a session.
+///
+/// This is implemented for every [`Context`], which is typically a reference
+/// to a context which outlives the session, and for an owned
+/// [`DefaultContext`].
+pub trait SessionContext: self::sealed::SealedContext {
+    /// The error produced by the context.
+    type Error;
+
+    /// The allocator used by the context.
+    type Allocator: Allocator;
+
+    #[doc(hidden)]
+    type Context<'this>: Context<Error = Self::Error, Allocator = Self::Allocator>
+    where
+        Self: 'this;
+
+    #[doc(hidden)]
+    fn __context(&self) -> Self::Context<'_>;
+}
+
+impl<C> SessionContext for C
+where
+    C: Context,
+{
+    type Error = C::Error;
+    type Allocator = C::Allocator;
+    type Context<'this>
+        = C
+    where
+        Self: 'this;
+
+    #[inline]
+    fn __context(&self) -> Self::Context<'_> {
+        *self
+    }
+}
+
+impl<A, T, C, S, I> SessionContext for DefaultContext<A, T, C, S, I>
+where
+    A: Allocator,
+    T: TraceMode,
+    C: ErrorMode<A>,
+    S: ShareMode,
+    I: InternMode,
+{
+    type Error = C::Error;
+    type Allocator = A;
+    type Context<'this>
+        = &'this Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn __context(&self) -> Self::Context<'_> {
+        self
+    }
+}
+
+/// A session encoding values one after another into a single writer.
+///
+/// Formats in which values are not self-delimiting separate them. JSON for
+/// example writes a newline between values, so that two numbers don't run
+/// together.
+///
+/// See the [module-level documentation][self] for more information.
+pub struct EncodeSession<F, C, W> {
+    format: F,
+    cx: C,
+    writer: W,
+    separate: bool,
+}
+
+impl<F, C, W> EncodeSession<F, C, W>
+where
+    F: Format,
+    C: SessionContext,
+    W: Writer,
+{
+    #[inline]
+    pub(crate) fn new(format: F, cx: C, writer: W) -> Self {
+        Self {
+            format,
+            cx,
+            writer,
+            separate: false,
+        }
+    }
+
+    /// Encode a value, appending it to the writer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::storage::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let mut encoder = ENCODING.encoder(Vec::new());
+    /// encoder.encode(&1u32)?;
+    /// encoder.encode("hello")?;
+    ///
+    /// let bytes = encoder.into_writer();
+    /// let mut decoder = ENCODING.decoder(&bytes);
+    /// assert_eq!(decoder.decode::<u32>()?, 1);
+    /// assert_eq!(decoder.decode::<String>()?, "hello");
+    /// assert!(decoder.is_empty());
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
+    #[inline]
+    pub fn encode<T>(&mut self, value: &T) -> Result<W::Ok, C::Error>
+    where
+        T: ?Sized + Encode<F::Mode>,
+    {
+        let cx = self.cx.__context();
+
+        if self.separate && !F::__SEPARATOR.is_empty() {
+            self.writer.write_bytes(cx, F::__SEPARATOR)?;
+        }
+
+        self.separate = true;
+        self.format.__encode_with(cx, &mut self.writer, value)
+    }
+
+    /// Access the context used by the session.
+    #[inline]
+    pub fn context(&self) -> &C {
+        &self.cx
+    }
+
+    /// Access the writer being encoded into.
+    #[inline]
+    pub fn writer(&self) -> &W {
+        &self.writer
+    }
+
+    /// Finish the session, returning the writer.
+    #[inline]
+    pub fn into_writer(self) -> W {
+        self.writer
+    }
+}
+
+/// A session decoding values one after another from a single slice.
+///
+/// Decoded values can borrow from the slice.
+///
+/// See the [module-level documentation][self] for more information.
+pub struct DecodeSession<'de, F, C> {
+    format: F,
+    cx: C,
+    bytes: &'de [u8],
+}
+
+impl<'de, F, C> DecodeSession<'de, F, C>
+where
+    F: Format,
+    C: SessionContext,
+{
+    #[inline]
+    pub(crate) fn new(format: F, cx: C, bytes: &'de [u8]) -> Self {
+        Self { format, cx, bytes }
+    }
+
+    /// Decode the next value from the slice.
+    ///
+    /// If decoding fails, the session is left at the start of the value
+    /// which couldn't be decoded.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::json::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let mut decoder = ENCODING.decoder(b"\"first\" \"second\"");
+    /// assert_eq!(decoder.decode::<&str>()?, "first");
+    /// assert_eq!(decoder.decode::<&str>()?, "second");
+    /// # Ok::<_, musli::json::Error>(())
+    /// ```
+    #[inline]
+    pub fn decode<T>(&mut self) -> Result<T, C::Error>
+    where
+        T: Decode<'de, F::Mode, C::Allocator>,
+    {
+        let (value, read) = self
+            .format
+            .__from_slice_partial_with(self.cx.__context(), self.bytes)?;
+        self.bytes = &self.bytes[read..];
+        Ok(value)
+    }
+
+    /// Test if there are no more values to decode.
+    ///
+    /// Trailing data which is insignificant to the format, such as
+    /// whitespace in JSON, is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::wire::Encoding;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let bytes = ENCODING.to_vec(&42u32)?;
+    ///
+    /// let mut decoder = ENCODING.decoder(&bytes);
+    /// assert!(!decoder.is_empty());
+    /// assert_eq!(decoder.decode::<u32>()?, 42);
+    /// assert!(decoder.is_empty());
+    /// # Ok::<_, musli::wire::Error>(())
+    /// ```
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.format.__trailing(self.bytes) == 0
+    }
+
+    /// The number of bytes remaining to be decoded.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Access the bytes remaining to be decoded.
+    #[inline]
+    pub fn as_slice(&self) -> &'de [u8] {
+        self.bytes
+    }
+
+    /// Access the context used by the session.
+    #[inline]
+    pub fn context(&self) -> &C {
+        &self.cx
+    }
+}
+
+macro_rules! format {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty $({ $($items:tt)* })?) => {
+        $(#[$meta])*
+        impl<$($generics)*> self::sealed::Sealed for $ty {}
+
+        $(#[$meta])*
+        impl<$($generics)*> Format for $ty {
+            type Mode = M;
+
+            #[inline]
+            fn __encode_with<C, W, T>(self, cx: C, writer: &mut W, value: &T) -> Result<W::Ok, C::Error>
+            where
+                C: Context,
+                W: Writer,
+                T: ?Sized + Encode<Self::Mode>,
+            {
+                self.encode_with(cx, writer, value)
+            }
+
+            #[inline]
+            fn __from_slice_partial_with<'de, C, T>(
+                self,
+                cx: C,
+                bytes: &'de [u8],
+            ) -> Result<(T, usize), C::Error>
+            where
+                C: Context,
+                T: Decode<'de, Self::Mode, C::Allocator>,
+            {
+                self.from_slice_partial_with(cx, bytes)
+            }
+
+            $($($items)*)*
+        }
+    };
+}
+
+format! {
+    #[cfg(feature = "descriptive")]
+    [const OPT: crate::Options, M: 'static] crate::descriptive::Encoding<OPT, M>
+}
+
+format! {
+    #[cfg(feature = "json")]
+    [M: 'static] crate::json::Encoding<M> {
+        const __SEPARATOR: &'static [u8] = b"\n";
+
+        #[inline]
+        fn __trailing(self, rest: &[u8]) -> usize {
+            self.trailing(rest)
+        }
+    }
+}
+
+format! {
+    #[cfg(any(
+        feature = "storage",
+        feature = "wire",
+        feature = "descriptive",
+        feature = "value"
+    ))]
+    [const OPT: crate::Options, M: 'static] crate::packed::Encoding<OPT, M>
+}
+
+format! {
+    #[cfg(any(
+        feature = "storage",
+        feature = "wire",
+        feature = "descriptive",
+        feature = "value"
+    ))]
+    [const OPT: crate::Options, M: 'static] crate::storage::Encoding<OPT, M>
+}
+
+format! {
+    #[cfg(feature = "wire")]
+    [const OPT: crate::Options, M: 'static] crate::wire::Encoding<OPT, M>
+}
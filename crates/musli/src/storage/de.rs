@@ -7,7 +7,8 @@ use crate::de::{
     utils, DecodeSliceBuilder, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder,
     SizeHint, TryFastDecode, UnsizedVisitor, VariantDecoder,
 };
-use crate::options::is_native_fixed;
+use crate::hint::Capabilities;
+use crate::options::{self, is_native_fixed};
 use crate::{Context, Decode, Options, Reader};
 
 /// Test if the current options and `$t` is suitable for bitwise slice decoding.
@@ -99,6 +100,13 @@ where
         write!(f, "type supported by the storage decoder")
     }
 
+    const CAPABILITIES: Capabilities = options::capabilities::<OPT>(Capabilities::CAN_BORROW);
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Self::CAPABILITIES
+    }
+
     #[inline]
     fn try_fast_decode<T>(mut self) -> Result<TryFastDecode<T, Self>, Self::Error>
     where
@@ -8,7 +8,7 @@ use crate::en::{
     TryFastEncode, VariantEncoder,
 };
 use crate::hint::{MapHint, SequenceHint};
-use crate::options::is_native_fixed;
+use crate::options::{self, is_native_fixed};
 use crate::{Context, Options, Writer};
 
 /// Test if the current options and `$t` is suitable for bitwise slice encoding.
@@ -220,12 +220,12 @@ where
 
     #[inline]
     fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
-        self.encode_u32(value.to_bits())
+        self.encode_u32(options::canonical_f32::<OPT>(value).to_bits())
     }
 
     #[inline]
     fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
-        self.encode_u64(value.to_bits())
+        self.encode_u64(options::canonical_f64::<OPT>(value).to_bits())
     }
 
     #[inline]
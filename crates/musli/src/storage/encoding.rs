@@ -7,12 +7,15 @@ use core::marker;
 use crate::alloc::System;
 use crate::mode::Binary;
 use crate::options;
-use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options};
+use crate::reader::{Incremental, IncrementalReader};
+use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options, Reader};
 
 use super::de::StorageDecoder;
 use super::en::StorageEncoder;
 #[cfg(feature = "alloc")]
 use super::error::Error;
+#[cfg(feature = "alloc")]
+use super::map_iter::DecodeMapIter;
 
 /// Default options to use with [`Encoding`].
 pub const OPTIONS: Options = options::new().build();
@@ -35,7 +38,7 @@ pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
 where
     M: 'static,
 {
-    _marker: marker::PhantomData<M>,
+    _marker: marker::PhantomData<fn() -> M>,
 }
 
 impl Default for Encoding<OPTIONS, Binary> {
@@ -131,6 +134,238 @@ where
         IntoReader::into_reader,
         IntoWriter::into_writer,
     );
+
+    /// Decode the given type `T` from the start of the given reader using the
+    /// current [`Encoding`], returning the value together with the reader
+    /// positioned after it.
+    ///
+    /// Since fields of `#[musli(packed)]` types are stored back to back, this
+    /// can be used to decode a header made up of the leading fields of a
+    /// message, and to decide based on it how to decode the rest.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::storage::Encoding;
+    /// # use musli::storage::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Decode)]
+    /// #[musli(packed)]
+    /// struct Header {
+    ///     kind: u8,
+    /// }
+    ///
+    /// #[derive(Encode)]
+    /// #[musli(packed)]
+    /// struct TextMessage {
+    ///     kind: u8,
+    ///     body: Text,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(packed)]
+    /// struct Ping {
+    ///     sequence: u32,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// #[musli(packed)]
+    /// struct Text {
+    ///     text: String,
+    /// }
+    ///
+    /// let data = ENCODING.to_vec(&TextMessage {
+    ///     kind: 2,
+    ///     body: Text { text: String::from("Hello") },
+    /// })?;
+    ///
+    /// let (header, rest) = ENCODING.decode_reader_prefix::<_, Header>(&data[..])?;
+    ///
+    /// match header.kind {
+    ///     1 => {
+    ///         let ping: Ping = ENCODING.decode(rest)?;
+    ///         assert_eq!(ping.sequence, 42);
+    ///     }
+    ///     2 => {
+    ///         let text: Text = ENCODING.decode(rest)?;
+    ///         assert_eq!(text.text, "Hello");
+    ///     }
+    ///     kind => panic!("unsupported message kind {kind}"),
+    /// }
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_reader_prefix<'de, R, T>(self, reader: R) -> Result<(T, R), Error>
+    where
+        R: Reader<'de>,
+        T: Decode<'de, M, System>,
+    {
+        let cx = crate::context::new().with_error();
+        self.decode_reader_prefix_with(&cx, reader)
+    }
+
+    /// Decode the given type `T` from the start of the given reader using the
+    /// current [`Encoding`], returning the value together with the reader
+    /// positioned after it.
+    ///
+    /// This is the same as [`Encoding::decode_reader_prefix`], but allows for
+    /// using a configurable [`Context`].
+    #[inline]
+    pub fn decode_reader_prefix_with<'de, C, R, T>(
+        self,
+        cx: C,
+        mut reader: R,
+    ) -> Result<(T, R), C::Error>
+    where
+        C: Context,
+        R: Reader<'de>,
+        T: Decode<'de, M, C::Allocator>,
+    {
+        let value = self.decode_with(cx, &mut reader)?;
+        Ok((value, reader))
+    }
+
+    /// Decode the given type `T` from the start of a slice which might not
+    /// contain all of the value yet, such as when bytes arrive in pieces from
+    /// a socket.
+    ///
+    /// If the slice ends before the value is decoded, this returns
+    /// [`Incremental::NeedMore`] with the least number of bytes which are missing,
+    /// instead of an error. The caller should append more bytes and call this
+    /// again with the whole input, since decoding starts over from the
+    /// beginning every time. Otherwise [`Incremental::Complete`] holds the value
+    /// and the number of bytes it occupied.
+    ///
+    /// Errors for input which is invalid are reported as usual. Unlike
+    /// [`Encoding::from_slice_partial`], which also decodes from the start of a
+    /// slice, running out of input isn't an error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::reader::Incremental;
+    /// use musli::storage::Encoding;
+    /// # use musli::storage::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Encode, Decode)]
+    /// struct Message {
+    ///     id: u32,
+    ///     body: String,
+    /// }
+    ///
+    /// let expected = Message { id: 42, body: String::from("Hello World") };
+    /// let data = ENCODING.to_vec(&expected)?;
+    ///
+    /// let mut buf = Vec::new();
+    /// let mut chunks = data.chunks(3);
+    ///
+    /// let (message, len) = loop {
+    ///     match ENCODING.decode_incremental::<Message>(&buf)? {
+    ///         Incremental::Complete(message, len) => break (message, len),
+    ///         Incremental::NeedMore(n) => {
+    ///             assert!(n > 0);
+    ///             buf.extend_from_slice(chunks.next().unwrap());
+    ///         }
+    ///     }
+    /// };
+    ///
+    /// assert_eq!(message, expected);
+    /// assert_eq!(len, data.len());
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_incremental<'de, T>(self, bytes: &'de [u8]) -> Result<Incremental<T>, Error>
+    where
+        T: Decode<'de, M, System>,
+    {
+        let cx = crate::context::new().with_error();
+        self.decode_incremental_with(&cx, bytes)
+    }
+
+    /// Decode the given type `T` from the start of a slice which might not
+    /// contain all of the value yet.
+    ///
+    /// This is the same as [`Encoding::decode_incremental`], but allows for using
+    /// a configurable [`Context`].
+    #[inline]
+    pub fn decode_incremental_with<'de, C, T>(
+        self,
+        cx: C,
+        bytes: &'de [u8],
+    ) -> Result<Incremental<T>, C::Error>
+    where
+        C: Context,
+        T: Decode<'de, M, C::Allocator>,
+    {
+        let mut reader = IncrementalReader::new(bytes);
+
+        match self.decode_with(cx, &mut reader) {
+            Ok(value) => Ok(Incremental::Complete(value, reader.consumed())),
+            Err(error) => match reader.needed() {
+                Some(n) => Ok(Incremental::NeedMore(n)),
+                None => Err(error),
+            },
+        }
+    }
+
+    /// Decode the entries of a map such as a [`BTreeMap`] or a [`HashMap`] one
+    /// at a time from the given reader, without collecting them.
+    ///
+    /// Only the length of the map is read up front. Each entry is decoded as
+    /// it is requested from the returned [`DecodeMapIter`], so entries are
+    /// produced in the order in which they were encoded. For a [`BTreeMap`]
+    /// this is in order of its keys.
+    ///
+    /// If an entry fails to decode, the error is produced and iteration ends.
+    ///
+    /// [`BTreeMap`]: std::collections::BTreeMap
+    /// [`HashMap`]: std::collections::HashMap
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use musli::storage::Encoding;
+    /// # use musli::storage::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let map = BTreeMap::from([(3u32, "three"), (1, "one"), (2, "two")]);
+    /// let data = ENCODING.to_vec(&map)?;
+    ///
+    /// let mut entries = ENCODING.decode_map_iter::<_, u32, String>(&data[..])?;
+    /// assert_eq!(entries.remaining(), 3);
+    /// assert_eq!(entries.next().transpose()?, Some((1, String::from("one"))));
+    ///
+    /// let rest = entries.collect::<Result<Vec<_>, _>>()?;
+    /// assert_eq!(rest, [(2, String::from("two")), (3, String::from("three"))]);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_map_iter<'de, R, K, V>(
+        self,
+        reader: R,
+    ) -> Result<DecodeMapIter<OPT, R::Reader, K, V, M>, Error>
+    where
+        R: IntoReader<'de>,
+        K: Decode<'de, M, System>,
+        V: Decode<'de, M, System>,
+    {
+        DecodeMapIter::new(IntoReader::into_reader(reader))
+    }
 }
 
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
@@ -0,0 +1,511 @@
+//! An append-only log of storage encoded records, which can be recovered after
+//! a crash.
+//!
+//! Each record in the log is framed like this:
+//!
+//! ```text
+//! record:   tag: u8 | length: u32 (little endian) | [crc: u32 (little endian)] | payload: [u8; length]
+//! ```
+//!
+//! The `tag` is [`PLAIN`] for records without a checksum and [`CHECKED`] for
+//! records with one. The checksum is a CRC-32 of the length followed by the
+//! payload.
+//!
+//! If a process crashes while appending, the end of the log might contain a
+//! partially written record. A [`LogReader`] stops cleanly at such a record and
+//! reports how the log ended through [`LogReader::end`], together with the
+//! length of the log up until the last good record through
+//! [`LogReader::valid_len`]. The log can then be truncated to that length
+//! before appending to it again, which is what [`recover`] does.
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::storage::log::{LogEnd, LogReader, LogWriter};
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Entry {
+//!     key: String,
+//!     value: u32,
+//! }
+//!
+//! let mut writer = LogWriter::new(Vec::new());
+//! writer.append(&Entry { key: String::from("a"), value: 1 })?;
+//! writer.append(&Entry { key: String::from("b"), value: 2 })?;
+//!
+//! let mut log = writer.into_inner();
+//! let valid = log.len() as u64;
+//!
+//! // Simulate a crash while appending the third record.
+//! let mut writer = LogWriter::new(Vec::new());
+//! writer.append(&Entry { key: String::from("c"), value: 3 })?;
+//! log.extend_from_slice(&writer.into_inner()[..7]);
+//!
+//! let mut reader = LogReader::new(&log[..]);
+//! assert_eq!(reader.read::<Entry>()?, Some(Entry { key: String::from("a"), value: 1 }));
+//! assert_eq!(reader.read::<Entry>()?, Some(Entry { key: String::from("b"), value: 2 }));
+//! assert_eq!(reader.read::<Entry>()?, None);
+//!
+//! assert_eq!(reader.end(), Some(LogEnd::TornPrefix));
+//! assert_eq!(reader.valid_len(), valid);
+//! # Ok::<_, musli::storage::Error>(())
+//! ```
+
+use core::fmt;
+
+use std::fs::OpenOptions;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::vec::Vec;
+
+use crate::alloc::System;
+use crate::context::ContextError;
+use crate::de::DecodeOwned;
+use crate::mode::Binary;
+use crate::{Encode, Options};
+
+use super::encoding::{Encoding, OPTIONS};
+use super::error::Error;
+use super::Result;
+
+/// Tag of a record which doesn't have a checksum.
+pub const PLAIN: u8 = 0x01;
+
+/// Tag of a record which has a CRC-32 checksum.
+pub const CHECKED: u8 = 0x02;
+
+/// How a log read by a [`LogReader`] ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LogEnd {
+    /// The log ended cleanly after the last record.
+    Clean,
+    /// The log ended in the middle of the tag, length or checksum of a record.
+    TornPrefix,
+    /// The log ended in the middle of the payload of a record.
+    TornPayload,
+    /// The checksum of a record didn't match its content.
+    ChecksumMismatch,
+    /// A record started with an unknown tag.
+    InvalidTag,
+}
+
+impl LogEnd {
+    /// Test if the log ended cleanly.
+    ///
+    /// Every other way for a log to end means that it has a corrupt or
+    /// partially written record at [`LogReader::valid_len`].
+    #[inline]
+    pub fn is_clean(self) -> bool {
+        matches!(self, LogEnd::Clean)
+    }
+}
+
+impl fmt::Display for LogEnd {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LogEnd::Clean => write!(f, "Log ended cleanly"),
+            LogEnd::TornPrefix => write!(f, "Log ended in the prefix of a record"),
+            LogEnd::TornPayload => write!(f, "Log ended in the payload of a record"),
+            LogEnd::ChecksumMismatch => write!(f, "Checksum mismatch in record"),
+            LogEnd::InvalidTag => write!(f, "Invalid record tag"),
+        }
+    }
+}
+
+/// Writes storage encoded records to an append-only log.
+///
+/// Each record is written to the underlying writer with a single call to
+/// [`Write::write_all`], so opening a file in append mode is enough for
+/// records to be written at the end of it.
+///
+/// See the [module level documentation](self) for the format of the log.
+pub struct LogWriter<const OPT: Options, W, M = Binary>
+where
+    M: 'static,
+{
+    encoding: Encoding<OPT, M>,
+    writer: W,
+    checksum: bool,
+    buf: Vec<u8>,
+}
+
+impl<W> LogWriter<OPTIONS, W, Binary>
+where
+    W: Write,
+{
+    /// Construct a new log writer using the default storage [`Encoding`].
+    ///
+    /// Records are written with a checksum by default, see
+    /// [`LogWriter::with_checksum`].
+    #[inline]
+    pub fn new(writer: W) -> Self {
+        Self::with_encoding(Encoding::new(), writer)
+    }
+}
+
+impl<const OPT: Options, W, M> LogWriter<OPT, W, M>
+where
+    W: Write,
+    M: 'static,
+{
+    /// Construct a new log writer using the given [`Encoding`].
+    #[inline]
+    pub fn with_encoding(encoding: Encoding<OPT, M>, writer: W) -> Self {
+        Self {
+            encoding,
+            writer,
+            checksum: true,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Configure whether records are written with a CRC-32 checksum.
+    ///
+    /// Without a checksum, torn records can still be detected but corrupted
+    /// content can't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::storage::log::{LogReader, LogWriter};
+    ///
+    /// let mut writer = LogWriter::new(Vec::new()).with_checksum(false);
+    /// writer.append(&42u32)?;
+    ///
+    /// let log = writer.into_inner();
+    /// assert_eq!(log.len(), 6);
+    ///
+    /// let mut reader = LogReader::new(&log[..]);
+    /// assert_eq!(reader.read::<u32>()?, Some(42));
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
+    #[inline]
+    pub fn with_checksum(self, checksum: bool) -> Self {
+        Self { checksum, ..self }
+    }
+
+    /// Encode and append a record to the log.
+    ///
+    /// Returns the number of bytes written, including the framing of the
+    /// record.
+    pub fn append<T>(&mut self, value: &T) -> Result<usize>
+    where
+        T: ?Sized + Encode<M>,
+    {
+        let header = if self.checksum { 9 } else { 5 };
+
+        self.buf.clear();
+        self.buf.resize(header, 0);
+        self.encoding.encode(&mut self.buf, value)?;
+
+        let len = self.buf.len() - header;
+
+        let Ok(len) = u32::try_from(len) else {
+            return Err(Error::message(
+                System::new(),
+                format_args!("Record of {len} bytes is too large for the log"),
+            ));
+        };
+
+        let len = len.to_le_bytes();
+        self.buf[1..5].copy_from_slice(&len);
+
+        if self.checksum {
+            let crc = crc32(crc32_update(!0, &len), &self.buf[header..]);
+            self.buf[0] = CHECKED;
+            self.buf[5..9].copy_from_slice(&crc.to_le_bytes());
+        } else {
+            self.buf[0] = PLAIN;
+        }
+
+        self.writer.write_all(&self.buf).map_err(io_error)?;
+        Ok(self.buf.len())
+    }
+
+    /// Flush the underlying writer.
+    #[inline]
+    pub fn flush(&mut self) -> Result<()> {
+        self.writer.flush().map_err(io_error)
+    }
+
+    /// Access the underlying writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Coerce the log writer into the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Reads storage encoded records from an append-only log.
+///
+/// Reading stops at the first record which is partially written or corrupt,
+/// after which [`LogReader::end`] reports why and [`LogReader::valid_len`] the
+/// length of the log up until that record.
+///
+/// See the [module level documentation](self) for the format of the log.
+pub struct LogReader<const OPT: Options, R, M = Binary>
+where
+    M: 'static,
+{
+    encoding: Encoding<OPT, M>,
+    reader: R,
+    valid_len: u64,
+    end: Option<LogEnd>,
+    buf: Vec<u8>,
+}
+
+impl<R> LogReader<OPTIONS, R, Binary>
+where
+    R: Read,
+{
+    /// Construct a new log reader using the default storage [`Encoding`].
+    #[inline]
+    pub fn new(reader: R) -> Self {
+        Self::with_encoding(Encoding::new(), reader)
+    }
+}
+
+impl<const OPT: Options, R, M> LogReader<OPT, R, M>
+where
+    R: Read,
+    M: 'static,
+{
+    /// Construct a new log reader using the given [`Encoding`].
+    #[inline]
+    pub fn with_encoding(encoding: Encoding<OPT, M>, reader: R) -> Self {
+        Self {
+            encoding,
+            reader,
+            valid_len: 0,
+            end: None,
+            buf: Vec::new(),
+        }
+    }
+
+    /// Get how the log ended, or `None` if it hasn't been read to the end
+    /// yet.
+    #[inline]
+    pub fn end(&self) -> Option<LogEnd> {
+        self.end
+    }
+
+    /// Get the length of the log up until the end of the last good record
+    /// that has been read.
+    ///
+    /// Once the log has ended, this is the length to truncate it to in order
+    /// to remove any partially written or corrupt record at its end.
+    #[inline]
+    pub fn valid_len(&self) -> u64 {
+        self.valid_len
+    }
+
+    /// Read the payload of the next record, or `None` if the log has ended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::storage::log::{LogEnd, LogReader, LogWriter};
+    ///
+    /// let mut writer = LogWriter::new(Vec::new());
+    /// writer.append("hello")?;
+    ///
+    /// let log = writer.into_inner();
+    /// let mut reader = LogReader::new(&log[..]);
+    ///
+    /// assert_eq!(reader.read_record()?, Some(&b"\x05hello"[..]));
+    /// assert_eq!(reader.read_record()?, None);
+    /// assert_eq!(reader.end(), Some(LogEnd::Clean));
+    /// # Ok::<_, musli::storage::Error>(())
+    /// ```
+    pub fn read_record(&mut self) -> Result<Option<&[u8]>> {
+        if self.end.is_some() {
+            return Ok(None);
+        }
+
+        let mut tag = [0];
+
+        if read_full(&mut self.reader, &mut tag)? == 0 {
+            return Ok(self.finish(LogEnd::Clean));
+        }
+
+        let checksum = match tag[0] {
+            PLAIN => false,
+            CHECKED => true,
+            _ => return Ok(self.finish(LogEnd::InvalidTag)),
+        };
+
+        let mut header = [0; 8];
+        let header = if checksum {
+            &mut header[..]
+        } else {
+            &mut header[..4]
+        };
+
+        if read_full(&mut self.reader, header)? != header.len() {
+            return Ok(self.finish(LogEnd::TornPrefix));
+        }
+
+        let len = [header[0], header[1], header[2], header[3]];
+
+        self.buf.clear();
+
+        // Reading through `take` grows the buffer as data arrives, rather than
+        // trusting a length which might be corrupt.
+        let payload = u32::from_le_bytes(len) as u64;
+        let n = (&mut self.reader)
+            .take(payload)
+            .read_to_end(&mut self.buf)
+            .map_err(io_error)?;
+
+        if n as u64 != payload {
+            return Ok(self.finish(LogEnd::TornPayload));
+        }
+
+        if checksum {
+            let expected = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+
+            if crc32(crc32_update(!0, &len), &self.buf) != expected {
+                return Ok(self.finish(LogEnd::ChecksumMismatch));
+            }
+        }
+
+        self.valid_len += 1 + header.len() as u64 + payload;
+        Ok(Some(&self.buf))
+    }
+
+    /// Read and decode the next record, or `None` if the log has ended.
+    ///
+    /// Decoding fails if the payload of the record isn't exactly one value of
+    /// type `T`.
+    pub fn read<T>(&mut self) -> Result<Option<T>>
+    where
+        T: DecodeOwned<M, System>,
+    {
+        let encoding = self.encoding;
+
+        let Some(payload) = self.read_record()? else {
+            return Ok(None);
+        };
+
+        Ok(Some(encoding.from_slice_exact(payload)?))
+    }
+
+    #[inline]
+    fn finish<T>(&mut self, end: LogEnd) -> Option<T> {
+        self.end = Some(end);
+        None
+    }
+}
+
+/// Recover the log at the given path by truncating any partially written or
+/// corrupt record at its end, returning the length of the recovered log.
+///
+/// Records are not decoded, so this works for logs of any type.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::OpenOptions;
+///
+/// use musli::storage::log::{self, LogWriter};
+///
+/// let path = "records.log";
+/// log::recover(path)?;
+///
+/// let file = OpenOptions::new().append(true).create(true).open(path)?;
+/// let mut writer = LogWriter::new(file);
+/// writer.append(&42u32)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
+pub fn recover<P>(path: P) -> Result<u64>
+where
+    P: AsRef<Path>,
+{
+    let file = OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(path)
+        .map_err(io_error)?;
+
+    let mut reader = LogReader::new(io::BufReader::new(&file));
+    while reader.read_record()?.is_some() {}
+
+    let valid_len = reader.valid_len();
+
+    if reader.end() != Some(LogEnd::Clean) {
+        file.set_len(valid_len).map_err(io_error)?;
+        file.sync_all().map_err(io_error)?;
+    }
+
+    Ok(valid_len)
+}
+
+/// Read until `buf` is full or the reader is at its end, returning the number
+/// of bytes read.
+fn read_full<R>(reader: &mut R, buf: &mut [u8]) -> Result<usize>
+where
+    R: Read,
+{
+    let mut n = 0;
+
+    while n < buf.len() {
+        match reader.read(&mut buf[n..]) {
+            Ok(0) => break,
+            Ok(len) => n += len,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(io_error(error)),
+        }
+    }
+
+    Ok(n)
+}
+
+#[inline]
+fn io_error(error: io::Error) -> Error {
+    Error::custom(System::new(), error)
+}
+
+/// Calculate the CRC-32 (IEEE) checksum of `bytes` continuing from `crc`.
+#[inline]
+fn crc32(crc: u32, bytes: &[u8]) -> u32 {
+    !crc32_update(crc, bytes)
+}
+
+fn crc32_update(mut crc: u32, bytes: &[u8]) -> u32 {
+    for &b in bytes {
+        crc = CRC32_TABLE[((crc ^ b as u32) & 0xff) as usize] ^ (crc >> 8);
+    }
+
+    crc
+}
+
+static CRC32_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb88320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+
+            k += 1;
+        }
+
+        table[n] = c;
+        n += 1;
+    }
+
+    table
+};
@@ -0,0 +1,138 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::marker::PhantomData;
+
+use crate::alloc::System;
+use crate::context::{DefaultContext, Emit, NoTrace};
+use crate::{Context, Decode, Options, Reader};
+
+use super::de::StorageDecoder;
+use super::error::Error;
+
+/// Iterator over the entries of an encoded map, constructed through
+/// [`Encoding::decode_map_iter`].
+///
+/// Each call to [`next`] decodes exactly one key and value from the underlying
+/// reader. Once an entry fails to decode the position of the reader inside of
+/// the map is unknown, so the error is produced and the iterator then ends
+/// rather than decoding garbage.
+///
+/// [`Encoding::decode_map_iter`]: super::Encoding::decode_map_iter
+/// [`next`]: Iterator::next
+pub struct DecodeMapIter<const OPT: Options, R, K, V, M>
+where
+    M: 'static,
+{
+    cx: DefaultContext<System, NoTrace, Emit<Error>>,
+    reader: R,
+    remaining: usize,
+    _entry: PhantomData<(K, V)>,
+    _marker: PhantomData<fn() -> M>,
+}
+
+impl<'de, const OPT: Options, R, K, V, M> DecodeMapIter<OPT, R, K, V, M>
+where
+    R: Reader<'de>,
+    M: 'static,
+{
+    #[inline]
+    pub(super) fn new(mut reader: R) -> Result<Self, Error> {
+        let cx = crate::context::new().with_error();
+        let remaining = crate::int::decode_usize::<_, _, OPT>(&cx, reader.borrow_mut())?;
+
+        Ok(Self {
+            cx,
+            reader,
+            remaining,
+            _entry: PhantomData,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Get the number of entries which are left to decode.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Coerce the iterator into the underlying reader.
+    ///
+    /// Note that if not all entries have been decoded, the reader will be
+    /// positioned somewhere inside of the map.
+    #[inline]
+    pub fn into_reader(self) -> R {
+        self.reader
+    }
+
+    #[inline]
+    fn decode_entry(&mut self) -> Result<(K, V), Error>
+    where
+        K: Decode<'de, M, System>,
+        V: Decode<'de, M, System>,
+    {
+        let cx = &self.cx;
+        cx.clear();
+        let key = K::decode(StorageDecoder::<OPT, false, _, _, M>::new(
+            cx,
+            self.reader.borrow_mut(),
+        ))?;
+        let value = V::decode(StorageDecoder::<OPT, false, _, _, M>::new(
+            cx,
+            self.reader.borrow_mut(),
+        ))?;
+        Ok((key, value))
+    }
+}
+
+impl<'de, const OPT: Options, R, K, V, M> Iterator for DecodeMapIter<OPT, R, K, V, M>
+where
+    R: Reader<'de>,
+    K: Decode<'de, M, System>,
+    V: Decode<'de, M, System>,
+    M: 'static,
+{
+    type Item = Result<(K, V), Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        match self.decode_entry() {
+            Ok(entry) => Some(Ok(entry)),
+            Err(error) => {
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+impl<'de, const OPT: Options, R, K, V, M> FusedIterator for DecodeMapIter<OPT, R, K, V, M>
+where
+    R: Reader<'de>,
+    K: Decode<'de, M, System>,
+    V: Decode<'de, M, System>,
+    M: 'static,
+{
+}
+
+impl<const OPT: Options, R, K, V, M> fmt::Debug for DecodeMapIter<OPT, R, K, V, M>
+where
+    M: 'static,
+{
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DecodeMapIter")
+            .field("remaining", &self.remaining)
+            .finish()
+    }
+}
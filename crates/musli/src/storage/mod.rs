@@ -98,6 +98,11 @@ pub(crate) mod de;
 pub(crate) mod en;
 mod encoding;
 mod error;
+#[cfg(all(feature = "std", feature = "alloc"))]
+#[cfg_attr(doc_cfg, doc(cfg(all(feature = "std", feature = "alloc"))))]
+pub mod log;
+#[cfg(feature = "alloc")]
+mod map_iter;
 
 #[cfg(feature = "test")]
 #[cfg_attr(doc_cfg, doc(cfg(feature = "test")))]
@@ -119,8 +124,14 @@ pub use self::encoding::to_vec;
 pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_slice_partial, to_fixed_bytes, to_slice,
+    validate,
+};
 #[doc(inline)]
 pub use self::encoding::{Encoding, DEFAULT, OPTIONS};
 #[doc(inline)]
 pub use self::error::Error;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+pub use self::map_iter::DecodeMapIter;
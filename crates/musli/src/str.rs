@@ -70,6 +70,9 @@ where
 /// Checks if the passed byte sequence is valid UTF-8 and returns an
 /// [`std::str`] reference to the passed byte slice wrapped in `Ok()` if it is.
 ///
+/// This is used when decoding borrowed strings, so that they benefit from the
+/// same fast validation as owned strings.
+///
 /// # Errors
 ///
 /// Will return the zero-sized Err([`Utf8Error`]) on if the input contains
@@ -0,0 +1,249 @@
+//! Converting data between two formats without a concrete model.
+//!
+//! Transcoding decodes the input as a [`Value`] through
+//! [`Decoder::decode_any`] and then encodes that value using the destination
+//! format. This makes it possible to bridge between, say, a compact internal
+//! format and a human-readable one without defining the types involved.
+//!
+//! The source format must be self-describing, since [`Decoder::decode_any`]
+//! relies on the input describing its own structure. This is the case for
+//! [`descriptive`] and [`json`], which are the only formats implementing
+//! [`Source`]. Every format can be used as a [`Destination`].
+//!
+//! Note that transcoding is only as precise as the source format. Formats
+//! such as [`json`] for example do not distinguish between bytes and
+//! sequences of numbers, so the transcoded output will not either.
+//!
+//! [`Decoder::decode_any`]: crate::Decoder::decode_any
+//! [`descriptive`]: crate::descriptive
+//! [`json`]: crate::json
+//!
+//! # Examples
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//!
+//! #[derive(Debug, PartialEq, Decode, Encode)]
+//! #[musli(name_all = "name")]
+//! struct Person {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let person = Person {
+//!     name: String::from("Aristotle"),
+//!     age: 61,
+//! };
+//!
+//! let bytes = musli::descriptive::to_vec(&person)?;
+//!
+//! let mut json = Vec::new();
+//!
+//! musli::transcode::transcode(
+//!     musli::descriptive::Encoding::new(),
+//!     musli::json::Encoding::new(),
+//!     &bytes,
+//!     &mut json,
+//! )?;
+//!
+//! assert_eq!(json, br#"{"name":"Aristotle","age":61}"#);
+//! # Ok::<_, Box<dyn core::error::Error>>(())
+//! ```
+
+#![cfg(any(feature = "json", feature = "descriptive"))]
+#![cfg_attr(doc_cfg, doc(cfg(any(feature = "json", feature = "descriptive"))))]
+
+use crate::value::Value;
+use crate::{Context, Decode, Encode, IntoWriter, Options};
+
+crate::macros::implement_error! {
+    /// Error raised during transcoding.
+    pub struct Error;
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A self-describing format which can be transcoded from.
+///
+/// See the [module-level documentation][self] for more information.
+pub trait Source: Copy + self::sealed::Sealed {
+    /// The mode used when decoding.
+    type Mode: 'static;
+
+    #[doc(hidden)]
+    fn __from_slice_with<'de, C, T>(self, cx: C, bytes: &'de [u8]) -> Result<T, C::Error>
+    where
+        C: Context,
+        T: Decode<'de, Self::Mode, C::Allocator>;
+}
+
+/// A format which can be transcoded to.
+///
+/// See the [module-level documentation][self] for more information.
+pub trait Destination: Copy + self::sealed::Sealed {
+    /// The mode used when encoding.
+    type Mode: 'static;
+
+    #[doc(hidden)]
+    fn __encode_with<C, W, T>(self, cx: C, writer: W, value: &T) -> Result<W::Ok, C::Error>
+    where
+        C: Context,
+        W: IntoWriter,
+        T: ?Sized + Encode<Self::Mode>;
+}
+
+/// Transcode `input` from the format `from` to the format `to`, writing the
+/// output to `writer`.
+///
+/// See the [module-level documentation][self] for more information.
+///
+/// # Examples
+///
+/// ```
+/// let json = br#"{"name":"Aristotle","tags":["philosopher",true,null]}"#;
+///
+/// let mut bytes = Vec::new();
+///
+/// musli::transcode::transcode(
+///     musli::json::Encoding::new(),
+///     musli::descriptive::Encoding::new(),
+///     &json[..],
+///     &mut bytes,
+/// )?;
+///
+/// let value: musli::value::Value<_> = musli::descriptive::from_slice(&bytes)?;
+/// let output = musli::json::to_vec(&value)?;
+/// assert_eq!(output, json);
+/// # Ok::<_, Box<dyn core::error::Error>>(())
+/// ```
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[inline]
+pub fn transcode<S, D, W>(from: S, to: D, input: &[u8], writer: W) -> Result<W::Ok, Error>
+where
+    S: Source,
+    D: Destination,
+    W: IntoWriter,
+{
+    let cx = crate::context::new().with_error();
+    transcode_with(&cx, from, to, input, writer)
+}
+
+/// Transcode `input` from the format `from` to the format `to`, writing the
+/// output to `writer`.
+///
+/// This is the same as [`transcode`] but allows for using a configurable
+/// [`Context`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::context;
+/// use musli::transcode::Error;
+///
+/// let cx = context::new().with_error();
+/// let mut output = Vec::new();
+///
+/// let result = musli::transcode::transcode_with(
+///     &cx,
+///     musli::json::Encoding::new(),
+///     musli::descriptive::Encoding::new(),
+///     br#"{"name":"#,
+///     &mut output,
+/// );
+///
+/// let error: Error = result.unwrap_err();
+/// assert!(!error.to_string().is_empty());
+/// ```
+#[inline]
+pub fn transcode_with<C, S, D, W>(
+    cx: C,
+    from: S,
+    to: D,
+    input: &[u8],
+    writer: W,
+) -> Result<W::Ok, C::Error>
+where
+    C: Context,
+    S: Source,
+    D: Destination,
+    W: IntoWriter,
+{
+    let value: Value<C::Allocator> = from.__from_slice_with(cx, input)?;
+    to.__encode_with(cx, writer, &value)
+}
+
+macro_rules! destination {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty) => {
+        $(#[$meta])*
+        impl<$($generics)*> self::sealed::Sealed for $ty {}
+
+        $(#[$meta])*
+        impl<$($generics)*> Destination for $ty {
+            type Mode = M;
+
+            #[inline]
+            fn __encode_with<C, W, T>(self, cx: C, writer: W, value: &T) -> Result<W::Ok, C::Error>
+            where
+                C: Context,
+                W: IntoWriter,
+                T: ?Sized + Encode<Self::Mode>,
+            {
+                self.encode_with(cx, writer, value)
+            }
+        }
+    };
+}
+
+macro_rules! source {
+    ($(#[$meta:meta])* [$($generics:tt)*] $ty:ty) => {
+        $(#[$meta])*
+        impl<$($generics)*> Source for $ty {
+            type Mode = M;
+
+            #[inline]
+            fn __from_slice_with<'de, C, T>(self, cx: C, bytes: &'de [u8]) -> Result<T, C::Error>
+            where
+                C: Context,
+                T: Decode<'de, Self::Mode, C::Allocator>,
+            {
+                self.from_slice_with(cx, bytes)
+            }
+        }
+    };
+}
+
+destination! {
+    #[cfg(feature = "descriptive")]
+    [const OPT: Options, M: 'static] crate::descriptive::Encoding<OPT, M>
+}
+
+destination! {
+    #[cfg(feature = "json")]
+    [M: 'static] crate::json::Encoding<M>
+}
+
+destination! {
+    [const OPT: Options, M: 'static] crate::packed::Encoding<OPT, M>
+}
+
+destination! {
+    [const OPT: Options, M: 'static] crate::storage::Encoding<OPT, M>
+}
+
+destination! {
+    #[cfg(feature = "wire")]
+    [const OPT: Options, M: 'static] crate::wire::Encoding<OPT, M>
+}
+
+source! {
+    #[cfg(feature = "descriptive")]
+    [const OPT: Options, M: 'static] crate::descriptive::Encoding<OPT, M>
+}
+
+source! {
+    #[cfg(feature = "json")]
+    [M: 'static] crate::json::Encoding<M>
+}
@@ -4,16 +4,16 @@ use core::fmt;
 use core::marker::PhantomData;
 use core::slice;
 
-use crate::alloc::Allocator;
+use crate::alloc::{Allocator, Vec};
 use crate::de::UnsizedVisitor;
 use crate::de::{
     AsDecoder, Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, Skip,
     VariantDecoder, Visitor,
 };
-use crate::hint::SequenceHint;
+use crate::hint::{Capabilities, SequenceHint};
 use crate::reader::SliceReader;
 use crate::storage::de::StorageDecoder;
-use crate::{Context, Options};
+use crate::{options, Context, Options};
 
 use super::error::ErrorMessage;
 use super::type_hint::{NumberHint, TypeHint};
@@ -131,6 +131,17 @@ where
         write!(f, "cannot be decoded from value")
     }
 
+    const CAPABILITIES: Capabilities = options::capabilities::<OPT>(
+        Capabilities::SELF_DESCRIBING
+            .with(Capabilities::CAN_BORROW)
+            .with(Capabilities::CAN_SKIP),
+    );
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Self::CAPABILITIES
+    }
+
     #[inline]
     fn skip(self) -> Result<(), C::Error> {
         Ok(())
@@ -158,7 +169,24 @@ where
 
     #[inline]
     fn decode_char(self) -> Result<char, C::Error> {
-        ensure!(self, hint, ExpectedChar(hint), Value::Char(c) => Ok(*c))
+        match self.value {
+            Value::Char(c) => Ok(*c),
+            // Formats such as JSON represent characters as strings.
+            Value::String(string) => {
+                let mut chars = string.chars();
+
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(c),
+                    _ => Err(self
+                        .cx
+                        .message(ErrorMessage::ExpectedChar(self.value.type_hint()))),
+                }
+            }
+            value => {
+                let hint = value.type_hint();
+                Err(self.cx.message(ErrorMessage::ExpectedChar(hint)))
+            }
+        }
     }
 
     #[inline]
@@ -213,12 +241,12 @@ where
 
     #[inline]
     fn decode_f32(self) -> Result<f32, C::Error> {
-        ensure!(self, hint, ExpectedNumber(NumberHint::F32, hint), Value::Number(Number::F32(n)) => Ok(*n))
+        ensure_number!(self, OPT, hint, ExpectedNumber(NumberHint::F32, hint), Value::Number(n) => f32)
     }
 
     #[inline]
     fn decode_f64(self) -> Result<f64, C::Error> {
-        ensure!(self, hint, ExpectedNumber(NumberHint::F64, hint), Value::Number(Number::F64(n)) => Ok(*n))
+        ensure_number!(self, OPT, hint, ExpectedNumber(NumberHint::F64, hint), Value::Number(n) => f64)
     }
 
     #[inline]
@@ -233,9 +261,28 @@ where
 
     #[inline]
     fn decode_array<const N: usize>(self) -> Result<[u8; N], C::Error> {
-        ensure!(self, hint, ExpectedBytes(hint), Value::Bytes(bytes) => {
-            <[u8; N]>::try_from(bytes.as_slice()).map_err(|_| self.cx.message(ErrorMessage::ArrayOutOfBounds))
-        })
+        match self.value {
+            Value::Bytes(bytes) => <[u8; N]>::try_from(bytes.as_slice())
+                .map_err(|_| self.cx.message(ErrorMessage::ArrayOutOfBounds)),
+            // Formats such as JSON represent bytes as a sequence of numbers.
+            Value::Sequence(values) => {
+                if values.len() != N {
+                    return Err(self.cx.message(ErrorMessage::ArrayOutOfBounds));
+                }
+
+                let mut array = [0u8; N];
+
+                for (b, value) in array.iter_mut().zip(values.iter()) {
+                    *b = ValueDecoder::<OPT, _, _, M>::new(self.cx, value).decode_u8()?;
+                }
+
+                Ok(array)
+            }
+            value => {
+                let hint = value.type_hint();
+                Err(self.cx.message(ErrorMessage::ExpectedBytes(hint)))
+            }
+        }
     }
 
     #[inline]
@@ -243,9 +290,25 @@ where
     where
         V: UnsizedVisitor<'de, C, [u8]>,
     {
-        ensure!(self, hint, ExpectedBytes(hint), Value::Bytes(bytes) => {
-            visitor.visit_borrowed(self.cx, bytes)
-        })
+        match self.value {
+            Value::Bytes(bytes) => visitor.visit_borrowed(self.cx, bytes),
+            // Formats such as JSON represent bytes as a sequence of numbers.
+            Value::Sequence(values) => {
+                let mut bytes =
+                    Vec::with_capacity_in(values.len(), self.cx.alloc()).map_err(self.cx.map())?;
+
+                for value in values.iter() {
+                    let b = ValueDecoder::<OPT, _, _, M>::new(self.cx, value).decode_u8()?;
+                    bytes.push(b).map_err(self.cx.map())?;
+                }
+
+                visitor.visit_owned(self.cx, bytes)
+            }
+            value => {
+                let hint = value.type_hint();
+                Err(self.cx.message(ErrorMessage::ExpectedBytes(hint)))
+            }
+        }
     }
 
     #[inline]
@@ -265,6 +328,13 @@ where
         })
     }
 
+    #[inline]
+    fn decode_tagged(self) -> Result<(u32, Self), C::Error> {
+        ensure!(self, hint, ExpectedTagged(hint), Value::Tagged(tag, value) => {
+            Ok((*tag, ValueDecoder::new(self.cx, value)))
+        })
+    }
+
     #[inline]
     fn decode_pack<F, O>(self, f: F) -> Result<O, C::Error>
     where
@@ -318,9 +388,18 @@ where
     where
         F: FnOnce(&mut Self::DecodeVariant) -> Result<O, C::Error>,
     {
-        ensure!(self, hint, ExpectedVariant(hint), Value::Variant(st) => {
-            f(&mut IterValueVariantDecoder::new(self.cx, st))
-        })
+        match self.value {
+            Value::Variant(st) => f(&mut IterValueVariantDecoder::new(self.cx, st)),
+            // Formats such as JSON represent variants as a map with a single
+            // entry.
+            Value::Map(entries) if entries.len() == 1 => {
+                f(&mut IterValueVariantDecoder::new(self.cx, &entries[0]))
+            }
+            value => {
+                let hint = value.type_hint();
+                Err(self.cx.message(ErrorMessage::ExpectedVariant(hint)))
+            }
+        }
     }
 
     #[inline]
@@ -373,6 +452,12 @@ where
                     .as_ref()
                     .map(|value| ValueDecoder::<OPT, _, _, M>::new(self.cx, value)),
             ),
+            Value::Tagged(tag, value) => visitor.visit_tagged(
+                self.cx,
+                *tag,
+                ValueDecoder::<OPT, _, _, M>::new(self.cx, value),
+            ),
+            Value::Invalid(offset) => Err(self.cx.message(ErrorMessage::Invalid(*offset))),
         }
     }
 }
@@ -694,7 +779,8 @@ where
 
     #[inline]
     fn decode_tag(&mut self) -> Result<Self::DecodeTag<'_>, C::Error> {
-        Ok(ValueDecoder::new(self.cx, &self.pair.0))
+        // Tags might have been stored as map keys.
+        Ok(ValueDecoder::with_map_key(self.cx, &self.pair.0))
     }
 
     #[inline]
@@ -5,7 +5,8 @@ use core::marker::PhantomData;
 use crate::alloc::{Box, String, Vec};
 use crate::en::{Encode, Encoder};
 use crate::en::{EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder};
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
+use crate::options;
 use crate::storage::en::StorageEncoder;
 use crate::writer::BufWriter;
 use crate::{Allocator, Context, Options};
@@ -131,6 +132,11 @@ where
         write!(f, "value that can be encoded")
     }
 
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::SELF_DESCRIBING.with(Capabilities::CAN_SKIP)
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
     where
@@ -218,13 +224,19 @@ where
 
     #[inline]
     fn encode_f32(self, n: f32) -> Result<Self::Ok, C::Error> {
-        self.output.write(self.cx, Value::Number(Number::F32(n)))?;
+        self.output.write(
+            self.cx,
+            Value::Number(Number::F32(options::canonical_f32::<OPT>(n))),
+        )?;
         Ok(())
     }
 
     #[inline]
     fn encode_f64(self, n: f64) -> Result<Self::Ok, C::Error> {
-        self.output.write(self.cx, Value::Number(Number::F64(n)))?;
+        self.output.write(
+            self.cx,
+            Value::Number(Number::F64(options::canonical_f64::<OPT>(n))),
+        )?;
         Ok(())
     }
 
@@ -298,6 +310,20 @@ where
         Ok(())
     }
 
+    #[inline]
+    fn encode_tagged<T>(self, tag: u32, value: T) -> Result<Self::Ok, C::Error>
+    where
+        T: Encode<Self::Mode>,
+    {
+        // The value is encoded separately so that encoding nested tags
+        // doesn't grow the type of the encoder.
+        let mut inner = Value::Unit;
+        value.encode(ValueEncoder::<OPT, _, _, M>::new(self.cx, &mut inner))?;
+        let inner = Box::new_in(inner, self.cx.alloc()).map_err(self.cx.map())?;
+        self.output.write(self.cx, Value::Tagged(tag, inner))?;
+        Ok(())
+    }
+
     #[inline]
     fn encode_pack(self) -> Result<Self::EncodePack, C::Error> {
         PackValueEncoder::new(self.cx, self.output)
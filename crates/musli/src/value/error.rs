@@ -27,6 +27,8 @@ pub(crate) enum ErrorMessage {
     ExpectedPack(TypeHint),
     ExpectedMap(TypeHint),
     ExpectedVariant(TypeHint),
+    ExpectedTagged(TypeHint),
+    Invalid(usize),
 }
 
 impl fmt::Display for ErrorMessage {
@@ -76,6 +78,15 @@ impl fmt::Display for ErrorMessage {
             ErrorMessage::ExpectedVariant(hint) => {
                 write!(f, "Value buffer expected struct, but found {hint}")
             }
+            ErrorMessage::ExpectedTagged(hint) => {
+                write!(f, "Value buffer expected tagged value, but found {hint}")
+            }
+            ErrorMessage::Invalid(offset) => {
+                write!(
+                    f,
+                    "Value buffer contains a value which failed to decode at offset {offset}"
+                )
+            }
         }
     }
 }
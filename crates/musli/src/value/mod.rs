@@ -20,7 +20,7 @@ mod value;
 pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 #[doc(inline)]
-pub use self::value::{AsValueDecoder, IntoValueDecoder, Value};
+pub use self::value::{AsValueDecoder, IntoValueDecoder, Number, Value};
 #[doc(inline)]
 pub use error::Error;
 
@@ -35,7 +35,9 @@ use crate::value::en::ValueEncoder;
 use crate::Encode;
 use crate::{Decode, Options};
 
-const OPTIONS: Options = crate::options::new().build();
+// Map keys might have been stored as strings by a format like JSON, so they are
+// permitted to be decoded as numbers.
+const OPTIONS: Options = crate::options::new().map_keys_as_numbers().build();
 
 /// Encode something that implements [Encode] into a [Value] in the [`Binary`]
 /// mode.
@@ -1,6 +1,7 @@
 use core::fmt;
 
 use crate::de::SizeHint;
+use crate::semantic;
 
 /// A type hint.
 #[derive(Debug, Clone, Copy)]
@@ -26,6 +27,10 @@ pub(crate) enum TypeHint {
     Variant,
     /// An optional value.
     Option,
+    /// A value with a semantic tag.
+    Tagged(u32),
+    /// A value which failed to decode.
+    Invalid,
 }
 
 impl fmt::Display for TypeHint {
@@ -41,6 +46,11 @@ impl fmt::Display for TypeHint {
             TypeHint::Map(size) => write!(f, "map with {size}"),
             TypeHint::Variant => write!(f, "variant"),
             TypeHint::Option => write!(f, "option"),
+            TypeHint::Tagged(tag) => match semantic::name(*tag) {
+                Some(name) => write!(f, "{name} tagged value"),
+                None => write!(f, "value tagged with {tag}"),
+            },
+            TypeHint::Invalid => write!(f, "invalid value"),
         }
     }
 }
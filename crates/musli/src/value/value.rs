@@ -11,6 +11,7 @@ use crate::de::{
 };
 use crate::en::{Encode, Encoder};
 use crate::en::{MapEncoder, SequenceEncoder, VariantEncoder};
+use crate::semantic;
 use crate::{Allocator, Context, Options};
 
 use super::de::ValueDecoder;
@@ -46,6 +47,20 @@ where
     Variant(Box<(Value<A>, Value<A>), A>),
     /// An optional value.
     Option(Option<Box<Value<A>, A>>),
+    /// A value with a semantic tag attached to it, see the [`semantic`] module
+    /// for well-known tags.
+    ///
+    /// [`semantic`]: crate::semantic
+    Tagged(u32, Box<Value<A>, A>),
+    /// A placeholder for a value which could not be decoded, containing the
+    /// offset in the input at which decoding failed.
+    ///
+    /// This is only produced by lossy decoding, such as
+    /// [`descriptive::to_value_lossy`]. It is encoded as a unit value and
+    /// cannot be decoded into anything.
+    ///
+    /// [`descriptive::to_value_lossy`]: crate::descriptive::to_value_lossy
+    Invalid(usize),
 }
 
 impl<A> Value<A>
@@ -92,6 +107,8 @@ where
             Value::Map(map) => TypeHint::Map(SizeHint::exact(map.len())),
             Value::Variant(..) => TypeHint::Variant,
             Value::Option(..) => TypeHint::Option,
+            Value::Tagged(tag, _) => TypeHint::Tagged(*tag),
+            Value::Invalid(..) => TypeHint::Invalid,
         }
     }
 }
@@ -113,8 +130,47 @@ where
             Self::Map(value) => f.debug_tuple("Map").field(value).finish(),
             Self::Variant(value) => f.debug_tuple("Variant").field(value).finish(),
             Self::Option(value) => f.debug_tuple("Option").field(value).finish(),
+            Self::Tagged(tag, value) => {
+                let mut f = f.debug_tuple("Tagged");
+
+                match semantic::name(*tag) {
+                    Some(name) => f.field(&format_args!("{name}")),
+                    None => f.field(tag),
+                };
+
+                match (*tag, &**value) {
+                    (semantic::UUID, Value::Bytes(bytes)) if bytes.len() == 16 => {
+                        f.field(&format_args!("{}", format_uuid(bytes, &mut [0; 36])))
+                    }
+                    (_, value) => f.field(value),
+                };
+
+                f.finish()
+            }
+            Self::Invalid(offset) => f.debug_tuple("Invalid").field(offset).finish(),
+        }
+    }
+}
+
+/// Format up to 16 bytes as a hyphenated UUID into the given buffer.
+pub(crate) fn format_uuid<'buf>(bytes: &[u8], buf: &'buf mut [u8; 36]) -> &'buf str {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+
+    let mut at = 0;
+
+    for (n, &b) in bytes.iter().take(16).enumerate() {
+        if matches!(n, 4 | 6 | 8 | 10) {
+            buf[at] = b'-';
+            at += 1;
         }
+
+        buf[at] = HEX[usize::from(b >> 4)];
+        buf[at + 1] = HEX[usize::from(b & 0xf)];
+        at += 2;
     }
+
+    // SAFETY: Only ASCII characters have been written.
+    unsafe { core::str::from_utf8_unchecked(&buf[..at]) }
 }
 
 impl<A> PartialEq for Value<A>
@@ -133,6 +189,10 @@ where
             (Self::Map(lhs), Self::Map(rhs)) => lhs == rhs,
             (Self::Variant(lhs), Self::Variant(rhs)) => lhs == rhs,
             (Self::Option(lhs), Self::Option(rhs)) => lhs == rhs,
+            (Self::Tagged(lhs_tag, lhs), Self::Tagged(rhs_tag, rhs)) => {
+                lhs_tag == rhs_tag && lhs == rhs
+            }
+            (Self::Invalid(lhs), Self::Invalid(rhs)) => lhs == rhs,
             _ => core::mem::discriminant(self) == core::mem::discriminant(other),
         }
     }
@@ -155,11 +215,24 @@ where
             (Self::Map(lhs), Self::Map(rhs)) => lhs.partial_cmp(rhs),
             (Self::Variant(lhs), Self::Variant(rhs)) => lhs.partial_cmp(rhs),
             (Self::Option(lhs), Self::Option(rhs)) => lhs.partial_cmp(rhs),
+            (Self::Tagged(lhs_tag, lhs), Self::Tagged(rhs_tag, rhs)) => {
+                match lhs_tag.cmp(rhs_tag) {
+                    Ordering::Equal => lhs.partial_cmp(rhs),
+                    ordering => Some(ordering),
+                }
+            }
+            (Self::Invalid(lhs), Self::Invalid(rhs)) => lhs.partial_cmp(rhs),
             _ => None,
         }
     }
 }
 
+/// A number stored in a [`Value`], retaining the exact type it was encoded
+/// as.
+///
+/// When decoding from a self-descriptive format such as
+/// [`descriptive`][crate::descriptive], the variant corresponds to the type
+/// recorded on the wire.
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 #[non_exhaustive]
 pub enum Number {
@@ -173,15 +246,15 @@ pub enum Number {
     U64(u64),
     /// `u128`
     U128(u128),
-    /// `u8`
+    /// `i8`
     I8(i8),
-    /// `u16`
+    /// `i16`
     I16(i16),
-    /// `u32`
+    /// `i32`
     I32(i32),
-    /// `u64`
+    /// `i64`
     I64(i64),
-    /// `u128`
+    /// `i128`
     I128(i128),
     /// `usize`
     Usize(usize),
@@ -389,6 +462,16 @@ where
         }
     }
 
+    #[inline]
+    fn visit_tagged<D>(self, cx: C, tag: u32, decoder: D) -> Result<Self::Ok, C::Error>
+    where
+        D: Decoder<'de, Cx = C, Error = C::Error, Allocator = C::Allocator>,
+    {
+        let value = decoder.decode::<Value<C::Allocator>>()?;
+        let value = Box::new_in(value, cx.alloc()).map_err(cx.map())?;
+        Ok(Value::Tagged(tag, value))
+    }
+
     #[inline]
     fn visit_sequence<D>(self, seq: &mut D) -> Result<Self::Ok, C::Error>
     where
@@ -557,6 +640,17 @@ where
                 Some(value) => encoder.encode_some()?.encode(&**value),
                 None => encoder.encode_none(),
             },
+            Value::Tagged(tag, value) => match (*tag, &**value) {
+                // Formats such as JSON can't tell bytes apart from other
+                // sequences, so UUIDs are rendered as strings.
+                (semantic::UUID, Value::Bytes(bytes))
+                    if bytes.len() == 16 && encoder.capabilities().is_human_readable() =>
+                {
+                    encoder.encode_string(format_uuid(bytes, &mut [0; 36]))
+                }
+                (tag, value) => encoder.encode_tagged(tag, value),
+            },
+            Value::Invalid(..) => encoder.encode_empty(),
         }
     }
 
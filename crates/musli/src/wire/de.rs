@@ -7,11 +7,11 @@ use crate::de::{
     Decoder, EntriesDecoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, Skip,
     UnsizedVisitor, VariantDecoder,
 };
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
 use crate::int::continuation as c;
 use crate::reader::Limit;
 use crate::storage::de::StorageDecoder;
-use crate::{Context, Options, Reader};
+use crate::{options, Context, Options, Reader};
 
 use super::tag::{Kind, Tag};
 
@@ -91,7 +91,7 @@ where
                 }
                 Kind::Continuation => {
                     if tag.data().is_none() {
-                        let _ = c::decode::<_, _, u128>(self.cx, self.reader.borrow_mut())?;
+                        c::skip::<_, _, u128>(self.cx, self.reader.borrow_mut())?;
                     }
                 }
                 kind => {
@@ -251,6 +251,14 @@ where
         write!(f, "type supported by the wire decoder")
     }
 
+    const CAPABILITIES: Capabilities =
+        options::capabilities::<OPT>(Capabilities::CAN_BORROW.with(Capabilities::CAN_SKIP));
+
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Self::CAPABILITIES
+    }
+
     #[inline]
     fn skip(self) -> Result<(), C::Error> {
         self.skip_any()
@@ -432,7 +440,7 @@ where
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f32(self) -> Result<f32, C::Error> {
-        let bits = self.decode_u32()?;
+        let bits = crate::wire::int::decode_float::<_, _, u32, OPT>(self.cx, self.reader)?;
         Ok(f32::from_bits(bits))
     }
 
@@ -440,7 +448,7 @@ where
     /// IEEE 754 encoding byte-by-byte.
     #[inline]
     fn decode_f64(self) -> Result<f64, C::Error> {
-        let bits = self.decode_u64()?;
+        let bits = crate::wire::int::decode_float::<_, _, u64, OPT>(self.cx, self.reader)?;
         Ok(f64::from_bits(bits))
     }
 
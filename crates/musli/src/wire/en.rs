@@ -4,7 +4,8 @@ use core::marker::PhantomData;
 use crate::en::{
     Encode, Encoder, EntriesEncoder, EntryEncoder, MapEncoder, SequenceEncoder, VariantEncoder,
 };
-use crate::hint::{MapHint, SequenceHint};
+use crate::hint::{Capabilities, MapHint, SequenceHint};
+use crate::options;
 use crate::storage::en::StorageEncoder;
 use crate::writer::BufWriter;
 use crate::{Context, Options, Writer};
@@ -135,6 +136,11 @@ where
         write!(f, "type supported by the wire encoder")
     }
 
+    #[inline]
+    fn capabilities(&self) -> Capabilities {
+        Capabilities::CAN_SKIP
+    }
+
     #[inline]
     fn encode<T>(self, value: T) -> Result<Self::Ok, C::Error>
     where
@@ -250,13 +256,15 @@ where
     }
 
     #[inline]
-    fn encode_f32(self, value: f32) -> Result<Self::Ok, C::Error> {
-        self.encode_u32(value.to_bits())
+    fn encode_f32(mut self, value: f32) -> Result<Self::Ok, C::Error> {
+        let bits = options::canonical_f32::<OPT>(value).to_bits();
+        crate::wire::int::encode_float::<_, _, _, OPT>(self.cx, self.writer.borrow_mut(), bits)
     }
 
     #[inline]
-    fn encode_f64(self, value: f64) -> Result<Self::Ok, C::Error> {
-        self.encode_u64(value.to_bits())
+    fn encode_f64(mut self, value: f64) -> Result<Self::Ok, C::Error> {
+        let bits = options::canonical_f64::<OPT>(value).to_bits();
+        crate::wire::int::encode_float::<_, _, _, OPT>(self.cx, self.writer.borrow_mut(), bits)
     }
 
     #[inline]
@@ -6,14 +6,41 @@ use core::marker;
 #[cfg(feature = "alloc")]
 use crate::alloc::System;
 use crate::mode::Binary;
+use crate::negotiate::{FormatId, FormatKind};
 use crate::options;
 use crate::{Context, Decode, Encode, IntoReader, IntoWriter, Options};
+#[cfg(feature = "alloc")]
+use crate::{Reader, Writer};
 
 use super::de::WireDecoder;
 use super::en::WireEncoder;
 #[cfg(feature = "alloc")]
 use super::error::Error;
 
+/// The version of the layout of the wire format.
+///
+/// This is bumped whenever the layout changes in a way which isn't compatible
+/// with older versions, and is part of the [`FormatId`] returned by
+/// [`Encoding::format_id`].
+///
+/// Configurations which store floats with [`Float::Fixed`] and integers with
+/// [`Integer::Variable`], like [`MSGPACK_LIKE`], used to store floats like
+/// integers. Since that changed, they use [`FIXED_FLOAT_FORMAT_VERSION`]
+/// instead.
+///
+/// [`Float::Fixed`]: crate::options::Float::Fixed
+/// [`Integer::Variable`]: crate::options::Integer::Variable
+pub const FORMAT_VERSION: u16 = 1;
+
+/// The version of the layout of the wire format for configurations which store
+/// floats with [`Float::Fixed`] and integers with [`Integer::Variable`].
+///
+/// See [`FORMAT_VERSION`].
+///
+/// [`Float::Fixed`]: crate::options::Float::Fixed
+/// [`Integer::Variable`]: crate::options::Integer::Variable
+pub const FIXED_FLOAT_FORMAT_VERSION: u16 = 2;
+
 /// The default flavor used by the [`DEFAULT`] configuration.
 pub const OPTIONS: options::Options = options::new().build();
 
@@ -30,12 +57,78 @@ pub const DEFAULT: Encoding = Encoding::new();
 
 crate::macros::bare_encoding!(Binary, DEFAULT, wire, IntoReader, IntoWriter);
 
+/// The options used by [`msgpack_like()`].
+///
+/// Integers and lengths use variable-length encoding, while floats are stored
+/// with a fixed width.
+pub const MSGPACK_LIKE: Options = options::new()
+    .integer(options::Integer::Variable)
+    .float(options::Float::Fixed)
+    .pointer(options::Width::Variable)
+    .build();
+
+/// Construct an encoding which makes size tradeoffs similar to those of
+/// [MessagePack].
+///
+/// This is not an implementation of MessagePack, but a configuration of the
+/// wire format using [`MSGPACK_LIKE`] options. Compared to [`rmp-serde`]:
+///
+/// * Integers use variable-length encoding. Values up to 62 are stored in a
+///   single byte, where MessagePack stores up to 127. Large 64-bit values take
+///   up to 11 bytes, where MessagePack uses at most 9.
+/// * Floats are stored as 5 or 9 bytes, the same as MessagePack. With the
+///   [`DEFAULT`] options, the bits of a float are stored as a variable-length
+///   integer, which typically makes an `f64` take 10 or 11 bytes.
+/// * Strings, bytes and sequences are prefixed with their length, which is
+///   a single byte for lengths up to 62.
+/// * Fields are identified by their numerical index, which is usually one
+///   byte. By default `rmp-serde` encodes structs as arrays without field
+///   identifiers which saves this byte, but can't tolerate fields being added
+///   or removed. With named fields, `rmp-serde` is usually larger since each
+///   field name is stored in full.
+///
+/// Prefer this over MessagePack when you need to evolve your types over time,
+/// since unknown fields can be skipped and missing fields can be defaulted. If
+/// both ends always agree on the exact types used, [`storage`] or [`packed`]
+/// are more compact.
+///
+/// [MessagePack]: https://msgpack.org
+/// [`rmp-serde`]: https://docs.rs/rmp-serde
+/// [`storage`]: crate::storage
+/// [`packed`]: crate::packed
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::wire::{self, Encoding, MSGPACK_LIKE};
+///
+/// const ENCODING: Encoding<MSGPACK_LIKE> = wire::msgpack_like();
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// let point = Point { x: 1.5, y: -2.0 };
+///
+/// let bytes = ENCODING.to_vec(&point)?;
+/// assert!(bytes.len() < wire::to_vec(&point)?.len());
+/// assert_eq!(ENCODING.from_slice::<Point>(&bytes)?, point);
+/// # Ok::<_, wire::Error>(())
+/// ```
+#[inline]
+pub const fn msgpack_like() -> Encoding<MSGPACK_LIKE> {
+    Encoding::new().with_options()
+}
+
 /// Setting up encoding with parameters.
 pub struct Encoding<const OPT: Options = OPTIONS, M = Binary>
 where
     M: 'static,
 {
-    _marker: marker::PhantomData<M>,
+    _marker: marker::PhantomData<fn() -> M>,
 }
 
 impl Default for Encoding<OPTIONS, Binary> {
@@ -124,6 +217,32 @@ where
         }
     }
 
+    /// Get the identifier of the format and options used by this encoding,
+    /// which can be used to [`negotiate`] a configuration with a peer.
+    ///
+    /// [`negotiate`]: crate::negotiate
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::options::{self, Options, Integer};
+    /// use musli::wire::{Encoding, DEFAULT};
+    ///
+    /// const OPTIONS: Options = options::new().integer(Integer::Fixed).build();
+    /// const CONFIG: Encoding<OPTIONS> = Encoding::new().with_options();
+    ///
+    /// assert_ne!(CONFIG.format_id(), DEFAULT.format_id());
+    /// ```
+    #[inline]
+    pub const fn format_id(&self) -> FormatId {
+        let version = match (options::float::<OPT>(), options::integer::<OPT>()) {
+            (options::Float::Fixed, options::Integer::Variable) => FIXED_FLOAT_FORMAT_VERSION,
+            _ => FORMAT_VERSION,
+        };
+
+        FormatId::new(FormatKind::Wire, version, OPT)
+    }
+
     crate::macros::encoding_impls!(
         M,
         wire,
@@ -132,6 +251,124 @@ where
         IntoReader::into_reader,
         IntoWriter::into_writer,
     );
+
+    /// Encode the given value followed by a trailer to the given [`Writer`]
+    /// using the current [`Encoding`].
+    ///
+    /// The trailer is encoded as a single wire value directly after `value`.
+    /// Since every wire value is self-delimiting, decoders which are not aware
+    /// of trailers can decode `value` and ignore whatever follows it. See the
+    /// [module-level documentation] for the exact layout.
+    ///
+    /// [`Writer`]: crate::Writer
+    /// [module-level documentation]: crate::wire#trailers
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::{Decode, Encode};
+    /// use musli::wire::Encoding;
+    /// # use musli::wire::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// #[derive(Debug, PartialEq, Decode, Encode)]
+    /// struct Person {
+    ///     name: String,
+    ///     age: u32,
+    /// }
+    ///
+    /// #[derive(Debug, PartialEq, Decode, Encode)]
+    /// struct Trace {
+    ///     id: u64,
+    /// }
+    ///
+    /// let person = Person {
+    ///     name: "Aristotle".to_string(),
+    ///     age: 61,
+    /// };
+    ///
+    /// let mut data = Vec::new();
+    /// ENCODING.encode_with_trailer(&mut data, &person, &Trace { id: 42 })?;
+    ///
+    /// let (decoded, trace) = ENCODING.decode_with_trailer::<_, Person, Trace>(&data[..])?;
+    /// assert_eq!(decoded, person);
+    /// assert_eq!(trace, Some(Trace { id: 42 }));
+    ///
+    /// // Decoders which don't know about the trailer ignore it.
+    /// let decoded: Person = ENCODING.from_slice(&data[..])?;
+    /// assert_eq!(decoded, person);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn encode_with_trailer<W, T, Tr>(
+        self,
+        writer: W,
+        value: &T,
+        trailer: &Tr,
+    ) -> Result<W::Ok, Error>
+    where
+        W: IntoWriter,
+        T: ?Sized + Encode<M>,
+        Tr: ?Sized + Encode<M>,
+    {
+        let cx = crate::context::new().with_error();
+        let mut writer = IntoWriter::into_writer(writer);
+        value.encode(WireEncoder::<OPT, _, _, M>::new(&cx, writer.borrow_mut()))?;
+        trailer.encode(WireEncoder::<OPT, _, _, M>::new(&cx, writer.borrow_mut()))?;
+        writer.finish(&cx)
+    }
+
+    /// Decode a value of type `T` and an optional trailer of type `Tr` from
+    /// the given [`Reader`] using the current [`Encoding`].
+    ///
+    /// The trailer is `None` if there is no data following the value, which
+    /// is the case for payloads produced by [`Encoding::encode`]. Any data
+    /// which follows the trailer is left in the reader.
+    ///
+    /// [`Reader`]: crate::Reader
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use musli::wire::Encoding;
+    /// # use musli::wire::Error;
+    ///
+    /// const ENCODING: Encoding = Encoding::new();
+    ///
+    /// let data = ENCODING.to_vec(&"Aristotle")?;
+    ///
+    /// let (name, checksum) = ENCODING.decode_with_trailer::<_, String, u32>(&data[..])?;
+    /// assert_eq!(name, "Aristotle");
+    /// assert_eq!(checksum, None);
+    /// # Ok::<_, Error>(())
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+    #[inline]
+    pub fn decode_with_trailer<'de, R, T, Tr>(self, reader: R) -> Result<(T, Option<Tr>), Error>
+    where
+        R: IntoReader<'de>,
+        T: Decode<'de, M, System>,
+        Tr: Decode<'de, M, System>,
+    {
+        let cx = crate::context::new().with_error();
+        let mut reader = IntoReader::into_reader(reader);
+        let value = T::decode(WireDecoder::<OPT, _, _, M>::new(&cx, reader.borrow_mut()))?;
+
+        let trailer = if reader.is_eof() {
+            None
+        } else {
+            Some(Tr::decode(WireDecoder::<OPT, _, _, M>::new(
+                &cx,
+                reader.borrow_mut(),
+            ))?)
+        };
+
+        Ok((value, trailer))
+    }
 }
 
 impl<const OPT: Options, M> Clone for Encoding<OPT, M> {
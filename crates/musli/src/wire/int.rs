@@ -119,11 +119,7 @@ where
                 c::encode(cx, writer, value)
             }
         }
-        crate::options::Integer::Fixed => {
-            let bo = crate::options::byteorder::<OPT>();
-            writer.write_byte(cx, Tag::new(Kind::Prefix, T::BYTES).byte())?;
-            value.write_bytes(cx, writer, bo)
-        }
+        crate::options::Integer::Fixed => encode_fixed::<C, W, T, OPT>(cx, writer, value),
     }
 }
 
@@ -152,15 +148,78 @@ where
                 c::decode(cx, reader)
             }
         }
-        crate::options::Integer::Fixed => {
-            let bo = crate::options::byteorder::<OPT>();
+        crate::options::Integer::Fixed => decode_fixed::<C, R, T, OPT>(cx, reader),
+    }
+}
 
-            if Tag::from_byte(reader.read_byte(cx)?) != Tag::new(Kind::Prefix, T::BYTES) {
-                return Err(cx.message("Expected fixed integer"));
-            }
+/// Encode an unsigned integer with a fixed width prefix.
+#[inline]
+fn encode_fixed<C, W, T, const OPT: Options>(cx: C, mut writer: W, value: T) -> Result<(), C::Error>
+where
+    C: Context,
+    W: Writer,
+    T: UnsignedOps,
+{
+    let bo = crate::options::byteorder::<OPT>();
+    writer.write_byte(cx, Tag::new(Kind::Prefix, T::BYTES).byte())?;
+    value.write_bytes(cx, writer, bo)
+}
 
-            T::read_bytes(cx, reader, bo)
-        }
+/// Decode an unsigned integer with a fixed width prefix.
+#[inline]
+fn decode_fixed<'de, C, R, T, const OPT: Options>(cx: C, mut reader: R) -> Result<T, C::Error>
+where
+    C: Context,
+    R: Reader<'de>,
+    T: UnsignedOps,
+{
+    let bo = crate::options::byteorder::<OPT>();
+
+    if Tag::from_byte(reader.read_byte(cx)?) != Tag::new(Kind::Prefix, T::BYTES) {
+        return Err(cx.message("Expected fixed integer"));
+    }
+
+    T::read_bytes(cx, reader, bo)
+}
+
+/// Governs how the bits of floats are encoded into a [`Writer`].
+///
+/// With [`Float::Fixed`] they are always stored with a fixed width, since the
+/// bits of most floats are too large to benefit from variable-length encoding.
+/// Otherwise they are encoded like unsigned integers.
+///
+/// [`Float::Fixed`]: crate::options::Float::Fixed
+#[inline]
+pub(crate) fn encode_float<C, W, T, const OPT: Options>(
+    cx: C,
+    writer: W,
+    bits: T,
+) -> Result<(), C::Error>
+where
+    C: Context,
+    W: Writer,
+    T: UnsignedOps,
+{
+    match crate::options::float::<OPT>() {
+        crate::options::Float::Fixed => encode_fixed::<C, W, T, OPT>(cx, writer, bits),
+        _ => encode_unsigned::<C, W, T, OPT>(cx, writer, bits),
+    }
+}
+
+/// Governs how the bits of floats are decoded from a [`Reader`].
+#[inline]
+pub(crate) fn decode_float<'de, C, R, T, const OPT: Options>(
+    cx: C,
+    reader: R,
+) -> Result<T, C::Error>
+where
+    C: Context,
+    R: Reader<'de>,
+    T: UnsignedOps,
+{
+    match crate::options::float::<OPT>() {
+        crate::options::Float::Fixed => decode_fixed::<C, R, T, OPT>(cx, reader),
+        _ => decode_unsigned::<C, R, T, OPT>(cx, reader),
     }
 }
 
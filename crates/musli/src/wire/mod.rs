@@ -77,12 +77,63 @@
 //! # Ok::<_, musli::wire::Error>(())
 //! ```
 //!
+//! Users coming from MessagePack can use [`msgpack_like()`], which configures
+//! the wire format to make similar size tradeoffs.
+//!
 //! <br>
 //!
 //! ## Implementation details
 //!
 //! Each field is prefix *typed* with a single byte tag that allows a receiver
 //! to figure out exactly how much should be skipped over.
+//!
+//! <br>
+//!
+//! ## Trailers
+//!
+//! A message can carry transport metadata such as trace identifiers or
+//! checksums in a trailer which is encoded with
+//! [`Encoding::encode_with_trailer`] and decoded with
+//! [`Encoding::decode_with_trailer`]. The layout of such a message is:
+//!
+//! ```text
+//! <value> [<trailer>]
+//! ```
+//!
+//! Both `<value>` and `<trailer>` are regular wire values, each starting with
+//! its own type tag. No additional framing is used, so the trailer is only
+//! present if there are bytes remaining after `<value>` has been read.
+//!
+//! Decoding the top-level value only consumes the bytes which make up the
+//! value and leaves anything following it unread. This is guaranteed, which
+//! means that decoders which are not aware of trailers can decode messages
+//! which have one, and decoders which are can decode messages which don't.
+//!
+//! ```
+//! use musli::{Decode, Encode};
+//! use musli::wire::Encoding;
+//!
+//! const ENCODING: Encoding = Encoding::new();
+//!
+//! #[derive(Debug, PartialEq, Encode, Decode)]
+//! struct Trace {
+//!     id: u64,
+//! }
+//!
+//! let mut with_trailer = Vec::new();
+//! ENCODING.encode_with_trailer(&mut with_trailer, &"Aristotle", &Trace { id: 42 })?;
+//!
+//! // A decoder which doesn't know about trailers.
+//! let name: String = ENCODING.from_slice(&with_trailer)?;
+//! assert_eq!(name, "Aristotle");
+//!
+//! // A decoder which knows about trailers reading an old message.
+//! let without_trailer = ENCODING.to_vec(&"Aristotle")?;
+//! let (name, trace) = ENCODING.decode_with_trailer::<_, String, Trace>(&without_trailer[..])?;
+//! assert_eq!(name, "Aristotle");
+//! assert_eq!(trace, None);
+//! # Ok::<_, musli::wire::Error>(())
+//! ```
 
 #![cfg(feature = "wire")]
 #![cfg_attr(doc_cfg, doc(cfg(feature = "wire")))]
@@ -95,6 +146,8 @@ mod en;
 mod encoding;
 mod error;
 mod int;
+#[cfg(feature = "alloc")]
+mod split;
 mod tag;
 
 #[cfg(feature = "test")]
@@ -117,11 +170,21 @@ pub use self::encoding::to_vec;
 pub use self::encoding::to_writer;
 #[cfg(feature = "alloc")]
 #[doc(inline)]
-pub use self::encoding::{decode, encode, from_slice, to_fixed_bytes, to_slice};
+pub use self::encoding::{
+    decode, encode, from_slice, from_slice_exact, from_slice_partial, to_fixed_bytes, to_slice,
+    validate,
+};
 #[doc(inline)]
-pub use self::encoding::{Encoding, DEFAULT, OPTIONS};
+pub use self::encoding::{
+    msgpack_like, Encoding, DEFAULT, FIXED_FLOAT_FORMAT_VERSION, FORMAT_VERSION, MSGPACK_LIKE,
+    OPTIONS,
+};
 #[doc(inline)]
 pub use self::error::Error;
+#[cfg(feature = "alloc")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "alloc")))]
+#[doc(inline)]
+pub use self::split::{length_of, split, Split};
 
 /// The maximum length that can be inlined in the tag without adding additional
 /// data to the wire format.
@@ -2,6 +2,18 @@
 //!
 //! The main methods in this module is the [`wrap`] function which constructs an
 //! adapter around an I/O type to work with musli.
+//!
+//! With the `encryption` feature enabled, this module also provides
+//! [`EncryptingWriter`] and [`DecryptingReader`] which transparently encrypt
+//! and authenticate encoded data.
+
+#[cfg(feature = "encryption")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "encryption")))]
+pub use self::encryption::{
+    AuthenticationError, DecryptingReader, EncryptingWriter, CHUNK_SIZE, NONCE_PREFIX_LEN,
+};
+#[cfg(feature = "encryption")]
+mod encryption;
 
 #[cfg(feature = "std")]
 use crate::alloc::Vec;
@@ -0,0 +1,439 @@
+//! Transparent authenticated encryption of encoded data.
+//!
+//! Data is encrypted with ChaCha20-Poly1305 in chunks of at most
+//! [`CHUNK_SIZE`] bytes. The encrypted stream consists of a nonce prefix
+//! followed by a sequence of frames:
+//!
+//! ```text
+//! nonce prefix: [u8; 7]
+//! frame:        flag: u8 | length: u32 (little endian) | ciphertext: [u8; length] | tag: [u8; 16]
+//! ```
+//!
+//! The nonce of each frame is constructed from the nonce prefix, a big endian
+//! counter of the frame and the `flag`, which is `1` for the last frame and
+//! `0` otherwise. Reordering, dropping or truncating frames therefore causes
+//! authentication to fail.
+
+use core::fmt;
+use core::slice;
+
+use chacha20poly1305::{AeadInPlace, ChaCha20Poly1305, Key, KeyInit, Nonce, Tag};
+use rust_alloc::vec::Vec;
+
+use crate::alloc::Vec as AllocVec;
+use crate::de::UnsizedVisitor;
+use crate::reader::SliceUnderflow;
+use crate::{Context, IntoReader, Reader, Writer};
+
+/// The maximum number of plaintext bytes stored in a single encrypted frame.
+pub const CHUNK_SIZE: usize = 64 * 1024;
+
+/// The length of the nonce prefix which is used to construct the nonce of each
+/// frame.
+pub const NONCE_PREFIX_LEN: usize = 7;
+
+const TAG_LEN: usize = 16;
+const FLAG_MORE: u8 = 0;
+const FLAG_LAST: u8 = 1;
+
+/// Error raised when encrypted data fails to authenticate.
+///
+/// This is reported through [`Context::custom`] by [`DecryptingReader::new`]
+/// if any frame has been tampered with, truncated, reordered or was encrypted
+/// with a different key, or if there is trailing data after the last frame.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct AuthenticationError;
+
+impl fmt::Display for AuthenticationError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Encrypted data failed to authenticate")
+    }
+}
+
+impl core::error::Error for AuthenticationError {}
+
+/// A [`Writer`] which encrypts and authenticates everything written to it
+/// before passing it on to an inner [`Writer`].
+///
+/// Plaintext is buffered until a frame of [`CHUNK_SIZE`] bytes is full, and
+/// the last frame is written when the writer is finished. Finishing happens
+/// automatically when encoding through an [`Encoding`], which means that a
+/// single encrypting writer holds a single encoded value.
+///
+/// [`Encoding`]: crate::descriptive::Encoding
+///
+/// # Examples
+///
+/// ```
+/// use musli::{Decode, Encode};
+/// use musli::wrap::{DecryptingReader, EncryptingWriter};
+///
+/// #[derive(Debug, PartialEq, Encode, Decode)]
+/// struct Person {
+///     name: String,
+///     age: u32,
+/// }
+///
+/// let key = [42; 32];
+/// // The nonce prefix must never be reused with the same key.
+/// let nonce = [1, 2, 3, 4, 5, 6, 7];
+///
+/// let person = Person {
+///     name: String::from("Aristotle"),
+///     age: 61,
+/// };
+///
+/// let mut writer = EncryptingWriter::new(&key, nonce, Vec::new());
+/// musli::storage::encode(&mut writer, &person)?;
+/// let encrypted = writer.into_inner();
+///
+/// let cx = musli::context::new().with_error();
+/// let reader = DecryptingReader::new(&cx, &key, &encrypted[..])?;
+/// let decoded: Person = musli::storage::decode(reader)?;
+/// assert_eq!(decoded, person);
+/// # Ok::<_, musli::storage::Error>(())
+/// ```
+pub struct EncryptingWriter<W> {
+    cipher: ChaCha20Poly1305,
+    prefix: [u8; NONCE_PREFIX_LEN],
+    counter: u32,
+    buf: Vec<u8>,
+    inner: W,
+    header: bool,
+    finished: bool,
+}
+
+impl<W> EncryptingWriter<W>
+where
+    W: Writer,
+{
+    /// Construct a new encrypting writer using the given 256-bit `key`.
+    ///
+    /// The `nonce` prefix is written in the clear at the start of the output,
+    /// and must never be reused with the same key.
+    pub fn new(key: &[u8; 32], nonce: [u8; NONCE_PREFIX_LEN], inner: W) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(key)),
+            prefix: nonce,
+            counter: 0,
+            buf: Vec::with_capacity(CHUNK_SIZE),
+            inner,
+            header: false,
+            finished: false,
+        }
+    }
+
+    /// Access the inner writer.
+    #[inline]
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Coerce into the inner writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+
+    /// Encrypt and write the buffered plaintext as a single frame.
+    fn write_frame<C>(&mut self, cx: C, last: bool) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        if !self.header {
+            self.inner.write_bytes(cx, &self.prefix)?;
+            self.header = true;
+        }
+
+        let flag = if last { FLAG_LAST } else { FLAG_MORE };
+        let nonce = nonce(&self.prefix, self.counter, flag);
+
+        let Ok(tag) =
+            self.cipher
+                .encrypt_in_place_detached(Nonce::from_slice(&nonce), &[], &mut self.buf)
+        else {
+            return Err(cx.message("Failed to encrypt frame"));
+        };
+
+        let Some(counter) = self.counter.checked_add(1) else {
+            return Err(cx.message("Too many encrypted frames"));
+        };
+
+        self.counter = counter;
+
+        self.inner.write_byte(cx, flag)?;
+        self.inner
+            .write_bytes(cx, &(self.buf.len() as u32).to_le_bytes())?;
+        self.inner.write_bytes(cx, &self.buf)?;
+        self.inner.write_bytes(cx, tag.as_slice())?;
+        self.buf.clear();
+        Ok(())
+    }
+}
+
+impl<W> crate::writer::IntoWriter for EncryptingWriter<W>
+where
+    W: Writer,
+{
+    type Ok = W::Ok;
+    type Writer = Self;
+
+    #[inline]
+    fn into_writer(self) -> Self::Writer {
+        self
+    }
+}
+
+impl<W> Writer for EncryptingWriter<W>
+where
+    W: Writer,
+{
+    type Ok = W::Ok;
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn finish<C>(&mut self, cx: C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context,
+    {
+        if self.finished {
+            return Err(cx.message("Encrypting writer has already been finished"));
+        }
+
+        self.write_frame(cx, true)?;
+        self.finished = true;
+        self.inner.finish(cx)
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn extend<C>(&mut self, cx: C, buffer: AllocVec<u8, C::Allocator>) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.write_bytes(cx, buffer.as_slice())
+    }
+
+    fn write_bytes<C>(&mut self, cx: C, mut bytes: &[u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        if self.finished {
+            return Err(cx.message("Encrypting writer has already been finished"));
+        }
+
+        cx.advance(bytes.len());
+
+        while !bytes.is_empty() {
+            // A full frame is only written once more data arrives, so that the
+            // last frame is always written by `finish`.
+            if self.buf.len() == CHUNK_SIZE {
+                self.write_frame(cx, false)?;
+            }
+
+            let n = bytes.len().min(CHUNK_SIZE - self.buf.len());
+            let (head, tail) = bytes.split_at(n);
+            self.buf.extend_from_slice(head);
+            bytes = tail;
+        }
+
+        Ok(())
+    }
+}
+
+/// A [`Reader`] over data which was written by an [`EncryptingWriter`].
+///
+/// Every frame is authenticated and decrypted when the reader is constructed,
+/// so tampering is detected before any plaintext is decoded. Since the
+/// plaintext is owned by the reader, decoded values cannot borrow from it.
+///
+/// See [`EncryptingWriter`] for an example.
+pub struct DecryptingReader {
+    data: Vec<u8>,
+    position: usize,
+}
+
+impl DecryptingReader {
+    /// Authenticate and decrypt everything in `reader` using the given 256-bit
+    /// `key`.
+    ///
+    /// # Errors
+    ///
+    /// If any frame fails to authenticate, the data is truncated or there is
+    /// trailing data after the last frame, an [`AuthenticationError`] is
+    /// reported through [`Context::custom`].
+    pub fn new<'de, C, R>(cx: C, key: &[u8; 32], reader: R) -> Result<Self, C::Error>
+    where
+        C: Context,
+        R: IntoReader<'de>,
+    {
+        // Running out of data while parsing frames means that the data has
+        // been truncated, which is indistinguishable from tampering.
+        macro_rules! frame {
+            ($expr:expr) => {
+                $expr.map_err(|_| cx.custom(AuthenticationError))?
+            };
+        }
+
+        let mut reader = reader.into_reader();
+        let cipher = ChaCha20Poly1305::new(Key::from_slice(key));
+        let prefix = frame!(reader.read_array::<_, NONCE_PREFIX_LEN>(cx));
+
+        let mut data = Vec::new();
+        let mut counter = 0u32;
+
+        loop {
+            let flag = frame!(reader.read_byte(cx));
+
+            if !matches!(flag, FLAG_MORE | FLAG_LAST) {
+                return Err(cx.custom(AuthenticationError));
+            }
+
+            let len = u32::from_le_bytes(frame!(reader.read_array(cx))) as usize;
+
+            if len > CHUNK_SIZE {
+                return Err(cx.custom(AuthenticationError));
+            }
+
+            let start = data.len();
+            data.resize(start + len, 0);
+            frame!(reader.read(cx, &mut data[start..]));
+            let tag = frame!(reader.read_array::<_, TAG_LEN>(cx));
+
+            let nonce = nonce(&prefix, counter, flag);
+
+            let result = cipher.decrypt_in_place_detached(
+                Nonce::from_slice(&nonce),
+                &[],
+                &mut data[start..],
+                Tag::from_slice(&tag),
+            );
+
+            if result.is_err() {
+                return Err(cx.custom(AuthenticationError));
+            }
+
+            if flag == FLAG_LAST {
+                if !reader.is_eof() {
+                    return Err(cx.custom(AuthenticationError));
+                }
+
+                break;
+            }
+
+            let Some(next) = counter.checked_add(1) else {
+                return Err(cx.custom(AuthenticationError));
+            };
+
+            counter = next;
+        }
+
+        Ok(Self { data, position: 0 })
+    }
+
+    /// Get the remaining decrypted bytes.
+    #[inline]
+    pub fn as_slice(&self) -> &[u8] {
+        &self.data[self.position..]
+    }
+
+    #[inline]
+    fn bounds_check<C>(&self, cx: C, n: usize) -> Result<usize, C::Error>
+    where
+        C: Context,
+    {
+        let remaining = self.data.len() - self.position;
+
+        if n > remaining {
+            return Err(cx.custom(SliceUnderflow::new(n, remaining)));
+        }
+
+        Ok(self.position + n)
+    }
+}
+
+impl<'de> IntoReader<'de> for DecryptingReader {
+    type Reader = Self;
+
+    #[inline]
+    fn into_reader(self) -> Self::Reader {
+        self
+    }
+}
+
+impl<'de> Reader<'de> for DecryptingReader {
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn is_eof(&mut self) -> bool {
+        self.position == self.data.len()
+    }
+
+    #[inline]
+    fn skip<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.position = self.bounds_check(cx, n)?;
+        cx.advance(n);
+        Ok(())
+    }
+
+    #[inline]
+    fn peek(&mut self) -> Option<u8> {
+        self.data.get(self.position).copied()
+    }
+
+    #[inline]
+    fn read_bytes<C, V>(&mut self, cx: C, n: usize, visitor: V) -> Result<V::Ok, C::Error>
+    where
+        C: Context,
+        V: UnsizedVisitor<'de, C, [u8]>,
+    {
+        let end = self.bounds_check(cx, n)?;
+        let bytes = &self.data[self.position..end];
+        self.position = end;
+        let ok = visitor.visit_ref(cx, bytes)?;
+        cx.advance(n);
+        Ok(ok)
+    }
+
+    #[inline]
+    unsafe fn read_bytes_uninit<C>(&mut self, cx: C, ptr: *mut u8, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        let end = self.bounds_check(cx, n)?;
+        // SAFETY: The caller ensures that `ptr` is valid for `n` bytes.
+        slice::from_raw_parts_mut(ptr, n).copy_from_slice(&self.data[self.position..end]);
+        self.position = end;
+        cx.advance(n);
+        Ok(())
+    }
+}
+
+#[inline]
+fn nonce(prefix: &[u8; NONCE_PREFIX_LEN], counter: u32, flag: u8) -> [u8; 12] {
+    let mut nonce = [0; 12];
+    nonce[..NONCE_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[NONCE_PREFIX_LEN..NONCE_PREFIX_LEN + 4].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = flag;
+    nonce
+}
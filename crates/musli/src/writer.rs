@@ -4,6 +4,9 @@
 //!
 //! [`wrap`]: crate::wrap::wrap
 
+mod limit_writer;
+pub use self::limit_writer::{LimitExceeded, LimitWriter};
+
 mod slice_mut_writer;
 pub use self::slice_mut_writer::SliceMutWriter;
 
@@ -13,12 +16,15 @@ use crate::alloc::Vec;
 use crate::{Allocator, Context};
 
 mod sealed {
-    use super::Writer;
+    use super::{LimitWriter, Writer};
 
     pub trait Sealed {}
+    impl<W> Sealed for LimitWriter<W> where W: Writer {}
     impl<W> Sealed for &mut W where W: ?Sized + Writer {}
     #[cfg(feature = "std")]
     impl<W> Sealed for crate::wrap::Wrap<W> where W: std::io::Write {}
+    #[cfg(feature = "encryption")]
+    impl<W> Sealed for crate::wrap::EncryptingWriter<W> where W: Writer {}
     impl Sealed for &mut [u8] {}
 }
 
@@ -82,6 +88,17 @@ pub trait Writer {
     {
         self.write_bytes(cx, &[b])
     }
+
+    /// Limit the number of bytes that can be written to the writer.
+    ///
+    /// See [`LimitWriter`] for details.
+    #[inline]
+    fn limit(self, limit: usize) -> LimitWriter<Self>
+    where
+        Self: Sized,
+    {
+        LimitWriter::new(self, limit)
+    }
 }
 
 impl<'a, W> IntoWriter for &'a mut W
@@ -196,6 +213,81 @@ impl Writer for rust_alloc::vec::Vec<u8> {
     }
 }
 
+/// Writing into a [`String`] checks that everything written is valid UTF-8.
+///
+/// This permits encoders which produce text, like [`json`], to write directly
+/// into a string. Each call to [`Writer::write_bytes`] must contain complete
+/// UTF-8 sequences and [`Writer::write_byte`] only accepts ASCII, anything else
+/// is reported as a [`Utf8Error`] through [`Context::custom`].
+///
+/// [`json`]: crate::json
+/// [`Utf8Error`]: core::str::Utf8Error
+///
+/// # Examples
+///
+/// ```
+/// let mut string = String::from("values: ");
+/// musli::json::encode(&mut string, &[1, 2, 3])?;
+/// assert_eq!(string, "values: [1,2,3]");
+///
+/// let mut string = String::new();
+/// assert!(musli::storage::encode(&mut string, &[0xffu8]).is_err());
+/// # Ok::<_, musli::json::Error>(())
+/// ```
+#[cfg(feature = "alloc")]
+impl Writer for rust_alloc::string::String {
+    type Ok = ();
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn finish<C>(&mut self, _: C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context,
+    {
+        Ok(())
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn extend<C>(&mut self, cx: C, buffer: Vec<u8, C::Allocator>) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.write_bytes(cx, buffer.as_slice())
+    }
+
+    #[inline]
+    fn write_bytes<C>(&mut self, cx: C, bytes: &[u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.push_str(core::str::from_utf8(bytes).map_err(cx.map())?);
+        cx.advance(bytes.len());
+        Ok(())
+    }
+
+    #[inline]
+    fn write_byte<C>(&mut self, cx: C, b: u8) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        if !b.is_ascii() {
+            return Err(cx.custom(core::str::from_utf8(&[b]).unwrap_err()));
+        }
+
+        self.push(b as char);
+        cx.advance(1);
+        Ok(())
+    }
+}
+
 impl<'a> IntoWriter for &'a mut [u8] {
     type Ok = usize;
     type Writer = SliceMutWriter<'a>;
@@ -280,15 +372,22 @@ where
 struct SliceOverflow {
     n: usize,
     capacity: usize,
+    written: usize,
 }
 
 impl fmt::Display for SliceOverflow {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let SliceOverflow { n, capacity } = self;
+        let SliceOverflow {
+            n,
+            capacity,
+            written,
+        } = self;
+
+        let required = written.saturating_add(*n);
 
         write!(
             f,
-            "Tried to write {n} bytes to slice, with a remaining capacity of {capacity}"
+            "Tried to write {n} bytes to slice, with a remaining capacity of {capacity} (at least {required} bytes are required)"
         )
     }
 }
@@ -0,0 +1,196 @@
+use core::fmt;
+
+use crate::alloc::Vec;
+use crate::Context;
+
+use super::{IntoWriter, Writer};
+
+/// Limit the number of bytes that can be written to a writer to the specified
+/// limit.
+///
+/// Writing past the limit results in a [`LimitExceeded`] error reported
+/// through [`Context::custom`], without anything being written to the
+/// underlying writer by the offending call.
+///
+/// Constructed through [`Writer::limit`] or [`LimitWriter::new`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::writer::LimitWriter;
+///
+/// let mut out = Vec::new();
+/// musli::storage::encode(LimitWriter::new(&mut out, 8), &1u32)?;
+///
+/// let mut out = Vec::new();
+/// assert!(musli::storage::encode(LimitWriter::new(&mut out, 8), "Hello World").is_err());
+/// # Ok::<_, musli::storage::Error>(())
+/// ```
+pub struct LimitWriter<W> {
+    remaining: usize,
+    writer: W,
+}
+
+impl<W> LimitWriter<W> {
+    /// Construct a new writer which allows at most `limit` bytes to be written
+    /// to `writer`.
+    #[inline]
+    pub fn new(writer: W, limit: usize) -> Self {
+        Self {
+            remaining: limit,
+            writer,
+        }
+    }
+
+    /// Get the number of bytes that can still be written.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+
+    /// Coerce into the underlying writer.
+    #[inline]
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    #[inline]
+    fn bounds_check<C>(&mut self, cx: C, n: usize) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        match self.remaining.checked_sub(n) {
+            Some(remaining) => {
+                self.remaining = remaining;
+                Ok(())
+            }
+            None => Err(cx.custom(LimitExceeded {
+                n,
+                remaining: self.remaining,
+            })),
+        }
+    }
+}
+
+impl<W> IntoWriter for LimitWriter<W>
+where
+    W: Writer,
+{
+    type Ok = W::Ok;
+    type Writer = Self;
+
+    #[inline]
+    fn into_writer(self) -> Self::Writer {
+        self
+    }
+}
+
+impl<W> Writer for LimitWriter<W>
+where
+    W: Writer,
+{
+    type Ok = W::Ok;
+    type Mut<'this>
+        = &'this mut Self
+    where
+        Self: 'this;
+
+    #[inline]
+    fn finish<C>(&mut self, cx: C) -> Result<Self::Ok, C::Error>
+    where
+        C: Context,
+    {
+        self.writer.finish(cx)
+    }
+
+    #[inline]
+    fn borrow_mut(&mut self) -> Self::Mut<'_> {
+        self
+    }
+
+    #[inline]
+    fn extend<C>(&mut self, cx: C, buffer: Vec<u8, C::Allocator>) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, buffer.len())?;
+        self.writer.extend(cx, buffer)
+    }
+
+    #[inline]
+    fn write_bytes<C>(&mut self, cx: C, bytes: &[u8]) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, bytes.len())?;
+        self.writer.write_bytes(cx, bytes)
+    }
+
+    #[inline]
+    fn write_byte<C>(&mut self, cx: C, b: u8) -> Result<(), C::Error>
+    where
+        C: Context,
+    {
+        self.bounds_check(cx, 1)?;
+        self.writer.write_byte(cx, b)
+    }
+}
+
+/// Error raised when writing past the limit of a [`LimitWriter`].
+///
+/// This is reported through [`Context::custom`], so it can be told apart from
+/// other errors by error types which retain it, such as [`std::io::Error`].
+///
+/// # Examples
+///
+/// ```
+/// use musli::context;
+/// use musli::writer::{LimitExceeded, LimitWriter};
+///
+/// let cx = context::new().with_error::<std::io::Error>();
+///
+/// let mut out = Vec::new();
+/// let error = musli::storage::Encoding::new()
+///     .encode_with(&cx, LimitWriter::new(&mut out, 8), "Hello World")
+///     .unwrap_err();
+///
+/// let limit = error
+///     .get_ref()
+///     .and_then(|e| e.downcast_ref::<LimitExceeded>())
+///     .unwrap();
+///
+/// assert_eq!(limit.remaining(), 7);
+/// ```
+#[derive(Debug)]
+pub struct LimitExceeded {
+    n: usize,
+    remaining: usize,
+}
+
+impl LimitExceeded {
+    /// The number of bytes which the offending call tried to write.
+    #[inline]
+    pub fn attempted(&self) -> usize {
+        self.n
+    }
+
+    /// The number of bytes which could still be written to the writer.
+    #[inline]
+    pub fn remaining(&self) -> usize {
+        self.remaining
+    }
+}
+
+impl fmt::Display for LimitExceeded {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let LimitExceeded { n, remaining } = self;
+
+        write!(
+            f,
+            "Size limit exceeded, tried to write {n} bytes with {remaining} bytes remaining"
+        )
+    }
+}
+
+impl core::error::Error for LimitExceeded {}
@@ -7,7 +7,61 @@ use crate::Context;
 use super::{SliceOverflow, Writer};
 
 /// A writer into a slice.
+///
+/// This is what a `&mut [u8]` is converted into when it's used as a writer,
+/// such as through [`storage::to_slice`]. Encoding fails with an error once
+/// the slice is full, and on success the number of bytes written is returned.
+///
+/// [`storage::to_slice`]: crate::storage::to_slice
+///
+/// # Encoding into a memory-mapped file
+///
+/// Since the slice can be any memory, this can be used to encode directly into
+/// a memory-mapped file without going through an intermediate buffer. If an
+/// upper bound for the size of the encoded value is known, the file can be
+/// sized for it, mapped, encoded into and then truncated to the number of
+/// bytes which were written.
+///
+/// ```no_run
+/// use std::fs::OpenOptions;
+///
+/// use memmap2::MmapMut;
+/// use musli::{Decode, Encode};
+///
+/// #[derive(Encode, Decode)]
+/// struct Record {
+///     id: u64,
+///     values: Vec<u32>,
+/// }
+///
+/// let records = (0..1000)
+///     .map(|id| Record { id, values: vec![1, 2, 3] })
+///     .collect::<Vec<_>>();
+///
+/// // An upper bound of the encoded size of each record, plus some room for
+/// // the length prefix of the sequence.
+/// let capacity = 16 + records.len() * 64;
+///
+/// let file = OpenOptions::new()
+///     .read(true)
+///     .write(true)
+///     .create(true)
+///     .truncate(true)
+///     .open("records.bin")?;
+///
+/// file.set_len(capacity as u64)?;
+///
+/// // SAFETY: The file is not modified by anything else while it's mapped.
+/// let mut map = unsafe { MmapMut::map_mut(&file)? };
+/// let written = musli::storage::to_slice(&mut map[..], &records)?;
+/// map.flush()?;
+/// drop(map);
+///
+/// file.set_len(written as u64)?;
+/// # Ok::<_, Box<dyn std::error::Error>>(())
+/// ```
 pub struct SliceMutWriter<'a> {
+    origin: NonNull<u8>,
     start: NonNull<u8>,
     end: NonNull<u8>,
     _marker: PhantomData<&'a mut [u8]>,
@@ -21,6 +75,7 @@ impl<'a> SliceMutWriter<'a> {
             let start = NonNull::new_unchecked(range.start);
             let end = NonNull::new_unchecked(range.end);
             Self {
+                origin: start,
                 start,
                 end,
                 _marker: PhantomData,
@@ -32,6 +87,12 @@ impl<'a> SliceMutWriter<'a> {
     pub(crate) fn remaining(&self) -> usize {
         self.end.as_ptr() as usize - self.start.as_ptr() as usize
     }
+
+    /// Get the number of bytes which have been written.
+    #[inline]
+    pub(crate) fn written(&self) -> usize {
+        self.start.as_ptr() as usize - self.origin.as_ptr() as usize
+    }
 }
 
 impl<'a> Writer for SliceMutWriter<'a> {
@@ -75,6 +136,7 @@ impl<'a> Writer for SliceMutWriter<'a> {
             return Err(cx.message(SliceOverflow {
                 n: bytes.len(),
                 capacity: self.remaining(),
+                written: self.written(),
             }));
         }
 
@@ -95,9 +157,11 @@ impl<'a> Writer for SliceMutWriter<'a> {
         C: Context,
     {
         if self.start == self.end {
-            return Err(cx.message(format_args!(
-                "Buffer overflow, remaining is 0 while tried to write 1"
-            )));
+            return Err(cx.message(SliceOverflow {
+                n: 1,
+                capacity: 0,
+                written: self.written(),
+            }));
         }
 
         // SAFETY: Construction of the writer ensures the range is valid.
@@ -0,0 +1,129 @@
+//! Test that fields and variants can be decoded from their legacy names
+//! through `#[musli(alias = ..)]`.
+
+use musli::{Decode, Encode};
+
+/// How the struct used to look, before fields were renamed and renumbered.
+#[derive(Encode)]
+struct PersonV1 {
+    #[musli(mode = Text, name = "full_name")]
+    #[musli(mode = Binary, name = 3)]
+    name: String,
+    #[musli(mode = Text, name = "years")]
+    #[musli(mode = Binary, name = 4)]
+    age: u32,
+    #[musli(mode = Text, name = "status")]
+    #[musli(mode = Binary, name = 5)]
+    status: StatusV1,
+}
+
+#[derive(Encode)]
+enum StatusV1 {
+    #[musli(mode = Text, name = "Enabled")]
+    #[musli(mode = Binary, name = 7)]
+    On,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    #[musli(mode = Text, name = "name", alias = "full_name", alias = "fullName")]
+    #[musli(mode = Binary, alias = 3)]
+    name: String,
+    #[musli(mode = Text, name = "age", alias = "years")]
+    #[musli(mode = Binary, alias = 4)]
+    age: u32,
+    #[musli(mode = Text, name = "status")]
+    #[musli(mode = Binary, alias = 5)]
+    status: Status,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Status {
+    #[musli(mode = Text, name = "Active", alias = "Enabled")]
+    #[musli(mode = Binary, alias = 7)]
+    Active,
+    #[musli(mode = Text, name = "Inactive")]
+    Inactive,
+}
+
+fn expected() -> Person {
+    Person {
+        name: String::from("Aristotle"),
+        age: 61,
+        status: Status::Active,
+    }
+}
+
+fn legacy() -> PersonV1 {
+    PersonV1 {
+        name: String::from("Aristotle"),
+        age: 61,
+        status: StatusV1::On,
+    }
+}
+
+#[test]
+fn json_aliases() -> Result<(), Box<dyn std::error::Error>> {
+    let json = r#"{"full_name":"Aristotle","years":61,"status":"Enabled"}"#;
+    assert_eq!(musli::json::from_str::<Person>(json)?, expected());
+
+    let json = r#"{"fullName":"Aristotle","age":61,"status":"Active"}"#;
+    assert_eq!(musli::json::from_str::<Person>(json)?, expected());
+
+    let json = musli::json::to_string(&legacy())?;
+    assert_eq!(musli::json::from_str::<Person>(&json)?, expected());
+    Ok(())
+}
+
+#[test]
+fn encode_uses_primary_name() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        musli::json::to_string(&expected())?,
+        r#"{"name":"Aristotle","age":61,"status":"Active"}"#
+    );
+
+    let bytes = musli::wire::to_vec(&expected())?;
+    assert_eq!(musli::wire::from_slice::<Person>(&bytes)?, expected());
+    Ok(())
+}
+
+#[test]
+fn wire_aliases() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::wire::to_vec(&legacy())?;
+    assert_eq!(musli::wire::from_slice::<Person>(&bytes)?, expected());
+
+    let bytes = musli::storage::to_vec(&legacy())?;
+    assert_eq!(musli::storage::from_slice::<Person>(&bytes)?, expected());
+    Ok(())
+}
+
+#[test]
+fn unsized_names() -> Result<(), Box<dyn std::error::Error>> {
+    #[derive(Debug, PartialEq, Decode)]
+    #[musli(mode = Binary, name(type = str))]
+    struct Unsized {
+        #[musli(mode = Binary, name = "value", alias = "old_value")]
+        value: u32,
+    }
+
+    #[derive(Encode)]
+    #[musli(mode = Binary, name_all = "name")]
+    struct Old {
+        old_value: u32,
+    }
+
+    let bytes = musli::storage::to_vec(&Old { old_value: 42 })?;
+    assert_eq!(
+        musli::storage::from_slice::<Unsized>(&bytes)?,
+        Unsized { value: 42 }
+    );
+    Ok(())
+}
+
+#[test]
+fn unknown_names_are_still_skipped() -> Result<(), Box<dyn std::error::Error>> {
+    let json = r#"{"name":"Aristotle","nickname":"Ari","age":61,"status":"Active"}"#;
+    assert_eq!(musli::json::from_str::<Person>(json)?, expected());
+
+    Ok(())
+}
@@ -0,0 +1,149 @@
+//! Test that floats can be canonicalized when encoded, so that values which
+//! compare the same encode identically.
+
+use musli::options::{self, Options};
+use musli::{Decode, Encode};
+
+const DEFAULT: Options = options::new().build();
+const CANONICAL_NAN: Options = options::new().canonical_nan().build();
+const CANONICAL: Options = options::new().canonical_nan().canonical_zero().build();
+const CANONICAL_FIXED: Options = options::new()
+    .fixed()
+    .native_byte_order()
+    .canonical_nan()
+    .canonical_zero()
+    .build();
+
+const NANS_F32: [u32; 5] = [
+    0x7fc0_0000,
+    0xffc0_0000,
+    0x7f80_0001,
+    0x7fc0_1234,
+    0xffff_ffff,
+];
+
+const NANS_F64: [u64; 5] = [
+    0x7ff8_0000_0000_0000,
+    0xfff8_0000_0000_0000,
+    0x7ff0_0000_0000_0001,
+    0x7ff8_0000_dead_beef,
+    0xffff_ffff_ffff_ffff,
+];
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Sample {
+    a: f32,
+    b: f64,
+}
+
+macro_rules! test_canonical {
+    ($what:ident) => {
+        mod $what {
+            use musli::$what::{Encoding, Error};
+
+            use super::*;
+
+            #[test]
+            fn nan() -> Result<(), Error> {
+                let default = Encoding::new().with_options::<DEFAULT>();
+                let canonical = Encoding::new().with_options::<CANONICAL_NAN>();
+
+                let expected = canonical.to_vec(&f32::NAN)?;
+
+                for bits in NANS_F32 {
+                    let value = f32::from_bits(bits);
+                    assert_eq!(canonical.to_vec(&value)?, expected, "{bits:08x}");
+
+                    let decoded: f32 = canonical.from_slice(&canonical.to_vec(&value)?)?;
+                    assert_eq!(decoded.to_bits(), 0x7fc0_0000);
+
+                    // The default options preserve the bits.
+                    let decoded: f32 = default.from_slice(&default.to_vec(&value)?)?;
+                    assert_eq!(decoded.to_bits(), bits);
+                }
+
+                let expected = canonical.to_vec(&f64::NAN)?;
+
+                for bits in NANS_F64 {
+                    let value = f64::from_bits(bits);
+                    assert_eq!(canonical.to_vec(&value)?, expected, "{bits:016x}");
+
+                    let decoded: f64 = canonical.from_slice(&canonical.to_vec(&value)?)?;
+                    assert_eq!(decoded.to_bits(), 0x7ff8_0000_0000_0000);
+
+                    let decoded: f64 = default.from_slice(&default.to_vec(&value)?)?;
+                    assert_eq!(decoded.to_bits(), bits);
+                }
+
+                // Negative zero is only normalized when requested.
+                assert_ne!(canonical.to_vec(&-0.0f64)?, canonical.to_vec(&0.0f64)?);
+                Ok(())
+            }
+
+            #[test]
+            fn zero() -> Result<(), Error> {
+                let canonical = Encoding::new().with_options::<CANONICAL>();
+
+                assert_eq!(canonical.to_vec(&-0.0f32)?, canonical.to_vec(&0.0f32)?);
+                assert_eq!(canonical.to_vec(&-0.0f64)?, canonical.to_vec(&0.0f64)?);
+
+                let decoded: f64 = canonical.from_slice(&canonical.to_vec(&-0.0f64)?)?;
+                assert_eq!(decoded.to_bits(), 0.0f64.to_bits());
+
+                // Other values are left alone.
+                for value in [
+                    1.5f64,
+                    -1.5,
+                    f64::INFINITY,
+                    f64::NEG_INFINITY,
+                    f64::MIN_POSITIVE,
+                ] {
+                    let decoded: f64 = canonical.from_slice(&canonical.to_vec(&value)?)?;
+                    assert_eq!(decoded.to_bits(), value.to_bits());
+                }
+
+                Ok(())
+            }
+
+            #[test]
+            fn bitwise() -> Result<(), Error> {
+                let canonical = Encoding::new().with_options::<CANONICAL_FIXED>();
+
+                let a = vec![
+                    Sample {
+                        a: f32::from_bits(NANS_F32[1]),
+                        b: -0.0,
+                    };
+                    4
+                ];
+
+                let b = vec![
+                    Sample {
+                        a: f32::NAN,
+                        b: 0.0,
+                    };
+                    4
+                ];
+
+                assert_eq!(canonical.to_vec(&a)?, canonical.to_vec(&b)?);
+
+                let floats = NANS_F64.map(f64::from_bits);
+                let expected = canonical.to_vec(&[f64::NAN; 5])?;
+                assert_eq!(canonical.to_vec(&floats)?, expected);
+                assert_eq!(
+                    canonical.to_vec(&floats.to_vec())?,
+                    canonical.to_vec(&vec![f64::NAN; 5])?
+                );
+
+                let decoded: Vec<f64> = canonical.from_slice(&canonical.to_vec(&floats[..])?)?;
+                assert!(decoded.iter().all(|v| v.to_bits() == 0x7ff8_0000_0000_0000));
+                Ok(())
+            }
+        }
+    };
+}
+
+test_canonical!(storage);
+test_canonical!(wire);
+test_canonical!(descriptive);
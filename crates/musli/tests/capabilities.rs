@@ -0,0 +1,125 @@
+//! Test that each format reports its capabilities to encoders and decoders.
+
+use musli::hint::Capabilities;
+use musli::{Allocator, Decode, Decoder, Encode, Encoder};
+
+#[derive(Debug, PartialEq)]
+struct Caps(Capabilities, u32);
+
+impl<'de, M, A> Decode<'de, M, A> for Caps
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de>,
+    {
+        let capabilities = decoder.capabilities();
+        Ok(Caps(capabilities, decoder.decode_u32()?))
+    }
+}
+
+/// Encodes whether the encoder is self-describing and human-readable.
+struct EncoderCaps;
+
+impl<M> Encode<M> for EncoderCaps {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let caps = encoder.capabilities();
+        assert!(!caps.can_borrow());
+        encoder.encode((caps.is_self_describing(), caps.is_human_readable()))
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[test]
+fn encoder_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let data = musli::storage::to_vec(&EncoderCaps)?;
+    assert_eq!(
+        musli::storage::from_slice::<(bool, bool)>(&data)?,
+        (false, false)
+    );
+
+    let data = musli::packed::to_vec(&EncoderCaps)?;
+    assert_eq!(
+        musli::packed::from_slice::<(bool, bool)>(&data)?,
+        (false, false)
+    );
+
+    let data = musli::wire::to_vec(&EncoderCaps)?;
+    assert_eq!(
+        musli::wire::from_slice::<(bool, bool)>(&data)?,
+        (false, false)
+    );
+
+    let data = musli::descriptive::to_vec(&EncoderCaps)?;
+    assert_eq!(
+        musli::descriptive::from_slice::<(bool, bool)>(&data)?,
+        (true, false)
+    );
+
+    let data = musli::json::to_string(&EncoderCaps)?;
+    assert_eq!(musli::json::from_str::<(bool, bool)>(&data)?, (true, true));
+
+    let value = musli::value::encode(EncoderCaps)?;
+    assert_eq!(musli::value::decode::<(bool, bool)>(&value)?, (true, false));
+    Ok(())
+}
+
+#[test]
+fn format_capabilities() -> Result<(), Box<dyn std::error::Error>> {
+    let Caps(caps, 42) = musli::storage::from_slice(&musli::storage::to_vec(&42u32)?)? else {
+        panic!("unexpected value");
+    };
+
+    assert_eq!(caps, Capabilities::CAN_BORROW);
+
+    let Caps(caps, 42) = musli::packed::from_slice(&musli::packed::to_vec(&42u32)?)? else {
+        panic!("unexpected value");
+    };
+
+    assert_eq!(caps, Capabilities::CAN_BORROW);
+
+    let Caps(caps, 42) = musli::wire::from_slice(&musli::wire::to_vec(&42u32)?)? else {
+        panic!("unexpected value");
+    };
+
+    assert_eq!(caps, Capabilities::CAN_BORROW | Capabilities::CAN_SKIP);
+
+    let Caps(caps, 42) = musli::descriptive::from_slice(&musli::descriptive::to_vec(&42u32)?)?
+    else {
+        panic!("unexpected value");
+    };
+
+    assert!(caps.is_self_describing());
+    assert!(caps.can_skip());
+    assert!(!caps.is_human_readable());
+
+    let Caps(caps, 42) = musli::json::from_str("42")? else {
+        panic!("unexpected value");
+    };
+
+    assert!(caps.is_self_describing());
+    assert!(caps.can_skip());
+    assert!(caps.is_human_readable());
+
+    let value = musli::value::encode(42u32)?;
+
+    let Caps(caps, 42) = musli::value::decode(&value)? else {
+        panic!("unexpected value");
+    };
+
+    assert!(caps.is_self_describing());
+    assert!(!caps.is_human_readable());
+    Ok(())
+}
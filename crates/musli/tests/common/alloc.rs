@@ -0,0 +1,95 @@
+//! A global allocator which counts the allocations made by each thread.
+//!
+//! Including this module installs the allocator for the test binary:
+//!
+//! ```ignore
+//! #[path = "common/alloc.rs"]
+//! mod alloc;
+//! ```
+
+#![allow(dead_code)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+/// Allocations made by the current thread.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Allocations {
+    /// The number of allocations and reallocations.
+    pub count: usize,
+    /// The number of bytes allocated.
+    pub bytes: usize,
+    /// The size of the largest allocation.
+    pub largest: usize,
+}
+
+impl Allocations {
+    #[inline]
+    fn record(&mut self, size: usize) {
+        self.count += 1;
+        self.bytes += size;
+        self.largest = self.largest.max(size);
+    }
+}
+
+thread_local! {
+    static ALLOCATIONS: Cell<Allocations> = const {
+        Cell::new(Allocations {
+            count: 0,
+            bytes: 0,
+            largest: 0,
+        })
+    };
+}
+
+/// Record an allocation of the given size.
+///
+/// This is ignored if the thread local has already been torn down, since the
+/// allocator is still called while the thread exits.
+#[inline]
+fn record(size: usize) {
+    _ = ALLOCATIONS.try_with(|cell| {
+        let mut allocations = cell.get();
+        allocations.record(size);
+        cell.set(allocations);
+    });
+}
+
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    #[inline]
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record(layout.size());
+        System.alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    #[inline]
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Call `f` and return the allocations it made on the current thread.
+pub fn allocations<T>(f: impl FnOnce() -> T) -> (T, Allocations) {
+    let outer = ALLOCATIONS.take();
+    let value = f();
+    let inner = ALLOCATIONS.take();
+
+    ALLOCATIONS.set(Allocations {
+        count: outer.count + inner.count,
+        bytes: outer.bytes + inner.bytes,
+        largest: outer.largest.max(inner.largest),
+    });
+
+    (value, inner)
+}
@@ -0,0 +1,151 @@
+//! Test that `#[musli(const_packed)]` produces the same bytes in a const
+//! context as the packed encoding does at runtime.
+
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Empty;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Unsigned {
+    a: u8,
+    b: u16,
+    c: u32,
+    d: u64,
+    e: u128,
+    f: usize,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Signed(i8, i16, i32, i64, i128, isize);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Mixed {
+    flag: bool,
+    letter: char,
+    single: f32,
+    double: f64,
+    #[musli(skip)]
+    skipped: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+#[repr(C)]
+pub struct Bitwise {
+    a: u32,
+    b: [u32; 2],
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Arrays {
+    flag: bool,
+    values: [u16; 3],
+    nested: [[u8; 2]; 2],
+    #[musli(packed)]
+    unprefixed: [u32; 2],
+    #[musli(bytes)]
+    bytes: [u8; 4],
+    points: [Bitwise; 2],
+    mixed: [Mixed; 1],
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(transparent, const_packed)]
+pub struct Transparent([u8; 3]);
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed, const_packed)]
+pub struct Nested {
+    unsigned: Unsigned,
+    signed: Signed,
+    mixed: Mixed,
+    arrays: Arrays,
+    transparent: Transparent,
+    empty: Empty,
+}
+
+const UNSIGNED: Unsigned = Unsigned {
+    a: 1,
+    b: 0x0203,
+    c: 0x04050607,
+    d: 0x08090a0b0c0d0e0f,
+    e: u128::MAX - 1,
+    f: 42,
+};
+
+const SIGNED: Signed = Signed(-1, -2, i32::MIN, -4, i128::MAX, -6);
+
+const MIXED: Mixed = Mixed {
+    flag: true,
+    letter: '🎉',
+    single: 1.5,
+    double: -0.25,
+    skipped: 0,
+};
+
+const ARRAYS: Arrays = Arrays {
+    flag: false,
+    values: [1, 2, 3],
+    nested: [[4, 5], [6, 7]],
+    unprefixed: [8, 9],
+    bytes: *b"abcd",
+    points: [
+        Bitwise { a: 10, b: [11, 12] },
+        Bitwise { a: 13, b: [14, 15] },
+    ],
+    mixed: [MIXED],
+};
+
+const NESTED: Nested = Nested {
+    unsigned: UNSIGNED,
+    signed: SIGNED,
+    mixed: MIXED,
+    arrays: ARRAYS,
+    transparent: Transparent([16, 17, 18]),
+    empty: Empty,
+};
+
+static CONFIG: [u8; Nested::PACKED_SIZE] = NESTED.encode_const();
+
+macro_rules! assert_const {
+    ($value:expr) => {{
+        const BYTES: &[u8] = &$value.encode_const();
+        let expected = musli::packed::to_vec(&$value).unwrap();
+        assert_eq!(BYTES, &expected[..], "{}", stringify!($value));
+    }};
+}
+
+#[test]
+fn matches_runtime() {
+    assert_const!(Empty);
+    assert_const!(UNSIGNED);
+    assert_const!(SIGNED);
+    assert_const!(MIXED);
+    assert_const!(Bitwise { a: 1, b: [2, 3] });
+    assert_const!(ARRAYS);
+    assert_const!(Transparent([1, 2, 3]));
+    assert_const!(NESTED);
+}
+
+#[test]
+fn bitwise_arrays_are_unprefixed() {
+    const { assert!(musli::is_bitwise_encode::<Bitwise>()) };
+    assert_eq!(Bitwise::PACKED_SIZE, 12);
+}
+
+#[test]
+fn static_config() {
+    assert_eq!(CONFIG.len(), Nested::PACKED_SIZE);
+    assert_eq!(
+        musli::packed::from_slice::<Nested>(&CONFIG).unwrap(),
+        NESTED
+    );
+}
@@ -0,0 +1,109 @@
+//! Test that C strings are encoded with exactly one trailing nul, and that
+//! decoding a byte sequence with an interior nul or a missing trailing nul
+//! results in an error.
+
+#![cfg(feature = "test")]
+
+use std::borrow::Cow;
+use std::ffi::{CStr, CString};
+use std::rc::Rc;
+use std::sync::Arc;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct CStrings {
+    owned: CString,
+    boxed: Box<CStr>,
+    rc: Rc<CStr>,
+    arc: Arc<CStr>,
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        CStrings {
+            owned: CString::new("hello").unwrap(),
+            boxed: CString::new("world").unwrap().into(),
+            rc: CString::new("").unwrap().into(),
+            arc: CString::new("arc").unwrap().into(),
+        }
+    );
+}
+
+#[test]
+fn trailing_nul_encoded_once() {
+    let value = CString::new("ab").unwrap();
+    let bytes = musli::storage::to_vec(&value).unwrap();
+    assert_eq!(
+        bytes,
+        musli::storage::to_vec(&musli::compat::Bytes(&b"ab\0"[..])).unwrap()
+    );
+
+    let json = musli::json::to_string(&value).unwrap();
+    assert_eq!(json, "[97,98,0]");
+    assert_eq!(musli::json::from_str::<CString>(&json).unwrap(), value);
+}
+
+macro_rules! assert_decode_err {
+    ($bytes:expr, $message:expr) => {{
+        let bytes = musli::compat::Bytes::<&[u8]>($bytes);
+
+        let storage = musli::storage::to_vec(&bytes).unwrap();
+        let wire = musli::wire::to_vec(&bytes).unwrap();
+        let descriptive = musli::descriptive::to_vec(&bytes).unwrap();
+        let json = musli::json::to_vec(&bytes).unwrap();
+
+        macro_rules! check {
+            ($format:ident, $data:expr) => {{
+                let error = musli::$format::from_slice::<CString>(&$data).unwrap_err();
+                assert!(
+                    error.to_string().contains($message),
+                    "{}: {error}",
+                    stringify!($format)
+                );
+
+                let error = musli::$format::from_slice::<Box<CStr>>(&$data).unwrap_err();
+                assert!(
+                    error.to_string().contains($message),
+                    "{}: {error}",
+                    stringify!($format)
+                );
+
+                let error = musli::$format::from_slice::<Cow<'_, CStr>>(&$data).unwrap_err();
+                assert!(
+                    error.to_string().contains($message),
+                    "{}: {error}",
+                    stringify!($format)
+                );
+            }};
+        }
+
+        check!(storage, storage);
+        check!(wire, wire);
+        check!(descriptive, descriptive);
+        check!(json, json);
+
+        let error = musli::storage::from_slice::<&CStr>(&storage).unwrap_err();
+        assert!(error.to_string().contains($message), "{error}");
+    }};
+}
+
+#[test]
+fn interior_nul() {
+    assert_decode_err!(
+        b"a\0b",
+        "C string contains an interior nul byte at position 1"
+    );
+    assert_decode_err!(
+        b"a\0b\0",
+        "C string contains an interior nul byte at position 1"
+    );
+}
+
+#[test]
+fn missing_trailing_nul() {
+    assert_decode_err!(b"ab", "C string is not nul terminated");
+    assert_decode_err!(&[], "C string is not nul terminated");
+}
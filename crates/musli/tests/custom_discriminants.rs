@@ -0,0 +1,122 @@
+//! Test that integer variant names control the discriminant in binary formats,
+//! including when they aren't contiguous.
+
+use musli::{Decode, Encode};
+
+/// A message in a foreign protocol, where discriminants are neither
+/// contiguous nor in declaration order.
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Message {
+    #[musli(name = 0x10)]
+    Ping,
+    #[musli(name = 1000)]
+    Data(u32),
+    #[musli(name = 3)]
+    Close { code: u8 },
+}
+
+/// The same protocol with variants declared in a different order.
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Reordered {
+    #[musli(name = 3)]
+    Close { code: u8 },
+    #[musli(name = 0x10)]
+    Ping,
+    #[musli(name = 1000)]
+    Data(u32),
+}
+
+/// An older version of the protocol which doesn't know of the data message.
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Partial {
+    #[musli(name = 0x10)]
+    Ping,
+    #[musli(name = 3)]
+    Close { code: u8 },
+}
+
+fn messages() -> [Message; 3] {
+    [Message::Ping, Message::Data(42), Message::Close { code: 7 }]
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(full, Message::Ping);
+    musli::macros::assert_roundtrip_eq!(full, Message::Data(42));
+    musli::macros::assert_roundtrip_eq!(full, Message::Close { code: 7 });
+}
+
+#[test]
+fn wire_discriminants() -> Result<(), Box<dyn std::error::Error>> {
+    for (message, name) in messages().into_iter().zip([0x10usize, 1000, 3]) {
+        let bytes = musli::wire::to_vec(&message)?;
+        assert_eq!(musli::wire::from_slice::<Message>(&bytes)?, message);
+
+        let name_bytes = musli::wire::to_vec(&name)?;
+
+        assert!(
+            bytes.windows(name_bytes.len()).any(|w| w == name_bytes),
+            "{message:?}: {bytes:?} does not contain discriminant {name}"
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn binary_discriminants() -> Result<(), Box<dyn std::error::Error>> {
+    // The discriminant is encoded first, like any other `usize`.
+    let bytes = musli::storage::to_vec(&Message::Data(42))?;
+    assert!(bytes.starts_with(&musli::storage::to_vec(&1000usize)?));
+
+    let bytes = musli::packed::to_vec(&Message::Close { code: 7 })?;
+    assert!(bytes.starts_with(&musli::packed::to_vec(&3usize)?));
+    Ok(())
+}
+
+#[test]
+fn decode_by_discriminant() -> Result<(), Box<dyn std::error::Error>> {
+    let expected = [
+        Reordered::Ping,
+        Reordered::Data(42),
+        Reordered::Close { code: 7 },
+    ];
+
+    for (message, expected) in messages().into_iter().zip(expected) {
+        let bytes = musli::wire::to_vec(&message)?;
+        assert_eq!(musli::wire::from_slice::<Reordered>(&bytes)?, expected);
+
+        let bytes = musli::storage::to_vec(&message)?;
+        assert_eq!(musli::storage::from_slice::<Reordered>(&bytes)?, expected);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn unknown_discriminant() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::wire::to_vec(&Message::Close { code: 7 })?;
+    assert_eq!(
+        musli::wire::from_slice::<Partial>(&bytes)?,
+        Partial::Close { code: 7 }
+    );
+
+    let bytes = musli::wire::to_vec(&Message::Data(42))?;
+    assert!(musli::wire::from_slice::<Partial>(&bytes).is_err());
+
+    let bytes = musli::storage::to_vec(&Message::Data(42))?;
+    assert!(musli::storage::from_slice::<Partial>(&bytes).is_err());
+    Ok(())
+}
+
+#[test]
+fn json_names() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        musli::json::to_string(&Message::Data(42))?,
+        r#"{"1000":{"0":42}}"#
+    );
+
+    let message = musli::json::from_str::<Message>(r#"{"3":{"code":7}}"#)?;
+    assert_eq!(message, Message::Close { code: 7 });
+    Ok(())
+}
@@ -0,0 +1,163 @@
+//! Test that `#[musli(default_with_cx = <path>)]` constructs absent fields
+//! using user data associated with the context.
+
+#![cfg(feature = "test")]
+
+use musli::context::{self, WithUserData};
+use musli::{Context, Decode, Encode};
+
+#[derive(Clone)]
+struct Config {
+    region: &'static str,
+    retries: u32,
+}
+
+const CONFIG: Config = Config {
+    region: "eu-west",
+    retries: 5,
+};
+
+fn default_region<C>(cx: C) -> String
+where
+    C: Context,
+{
+    match cx.user_data::<Config>() {
+        Some(config) => String::from(config.region),
+        None => String::from("global"),
+    }
+}
+
+fn default_retries<C>(cx: C) -> u32
+where
+    C: Context,
+{
+    cx.user_data::<Config>().map_or(3, |config| config.retries)
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Tenant {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct TenantWithDefaults {
+    name: String,
+    #[musli(default_with_cx = default_region)]
+    region: String,
+    #[musli(skip, default_with_cx = default_retries)]
+    retries: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct PackedWithDefaults {
+    name: String,
+    #[musli(skip, default_with_cx = default_retries)]
+    retries: u32,
+}
+
+macro_rules! test_format {
+    ($format:ident) => {{
+        use musli::$format::{self, Encoding};
+
+        const ENCODING: Encoding = Encoding::new();
+
+        let tenant = Tenant {
+            name: String::from("Acme"),
+        };
+
+        let bytes = $format::to_vec(&tenant).unwrap();
+
+        let cx = context::new();
+        let cx = WithUserData::new(&cx, CONFIG);
+
+        let decoded: TenantWithDefaults = ENCODING.from_slice_with(&cx, &bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            TenantWithDefaults {
+                name: String::from("Acme"),
+                region: String::from("eu-west"),
+                retries: 5,
+            }
+        );
+
+        // Without user data the default functions fall back.
+        let decoded: TenantWithDefaults = $format::from_slice(&bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            TenantWithDefaults {
+                name: String::from("Acme"),
+                region: String::from("global"),
+                retries: 3,
+            }
+        );
+
+        // Present fields are left untouched.
+        let present = TenantWithDefaults {
+            name: String::from("Acme"),
+            region: String::from("us-east"),
+            retries: 1,
+        };
+
+        let bytes = $format::to_vec(&present).unwrap();
+        let decoded: TenantWithDefaults = ENCODING.from_slice_with(&cx, &bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            TenantWithDefaults {
+                name: String::from("Acme"),
+                region: String::from("us-east"),
+                retries: 5,
+            }
+        );
+
+        let packed = PackedWithDefaults {
+            name: String::from("Acme"),
+            retries: 1,
+        };
+
+        let bytes = $format::to_vec(&packed).unwrap();
+        let decoded: PackedWithDefaults = ENCODING.from_slice_with(&cx, &bytes).unwrap();
+
+        assert_eq!(
+            decoded,
+            PackedWithDefaults {
+                name: String::from("Acme"),
+                retries: 5,
+            }
+        );
+    }};
+}
+
+#[test]
+fn storage() {
+    test_format!(storage);
+}
+
+#[test]
+fn wire() {
+    test_format!(wire);
+}
+
+#[test]
+fn descriptive() {
+    test_format!(descriptive);
+}
+
+#[test]
+fn json() {
+    test_format!(json);
+}
+
+#[test]
+fn nested_user_data() {
+    let cx = context::new();
+    let cx = WithUserData::new(&cx, CONFIG);
+    let cx = WithUserData::new(&cx, 42u64);
+
+    assert_eq!((&cx).user_data::<u64>(), Some(42));
+    assert_eq!((&cx).user_data::<Config>().map(|c| c.retries), Some(5));
+    assert_eq!((&cx).user_data::<u32>(), None);
+}
@@ -0,0 +1,114 @@
+//! Test that `#[musli(deny_unknown_fields)]` rejects fields which aren't part
+//! of a struct instead of skipping them.
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode)]
+struct Extended {
+    name: String,
+    age: u32,
+    nickname: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Lenient {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(deny_unknown_fields)]
+struct Strict {
+    name: String,
+    age: u32,
+}
+
+#[derive(Debug, PartialEq, Encode)]
+#[musli(name_all = "name")]
+struct ExtendedNamed {
+    name: String,
+    age: u32,
+    nickname: String,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(deny_unknown_fields, name_all = "name")]
+struct StrictNamed {
+    name: String,
+    age: u32,
+}
+
+fn extended() -> Extended {
+    Extended {
+        name: String::from("Aristotle"),
+        age: 61,
+        nickname: String::from("Ari"),
+    }
+}
+
+macro_rules! test_format {
+    ($format:ident) => {
+        mod $format {
+            use super::*;
+
+            #[test]
+            fn deny_unknown_fields() {
+                let bytes = musli::$format::to_vec(&extended()).unwrap();
+
+                let lenient: Lenient = musli::$format::from_slice(&bytes).unwrap();
+                assert_eq!(lenient.name, "Aristotle");
+
+                let error = musli::$format::from_slice::<Strict>(&bytes).unwrap_err();
+                assert!(error.to_string().contains("has no field 2"), "{error}");
+
+                let bytes = musli::$format::to_vec(&Lenient {
+                    name: String::from("Plato"),
+                    age: 80,
+                })
+                .unwrap();
+
+                let strict: Strict = musli::$format::from_slice(&bytes).unwrap();
+                assert_eq!(strict.name, "Plato");
+            }
+
+            #[test]
+            fn deny_unknown_named_fields() {
+                let value = ExtendedNamed {
+                    name: String::from("Aristotle"),
+                    age: 61,
+                    nickname: String::from("Ari"),
+                };
+
+                let bytes = musli::$format::to_vec(&value).unwrap();
+                let error = musli::$format::from_slice::<StrictNamed>(&bytes).unwrap_err();
+                assert!(
+                    error.to_string().contains("has no field \"nickname\""),
+                    "{error}"
+                );
+            }
+        }
+    };
+}
+
+#[cfg(feature = "wire")]
+test_format!(wire);
+#[cfg(feature = "descriptive")]
+test_format!(descriptive);
+
+#[test]
+#[cfg(feature = "json")]
+fn json() {
+    let json = musli::json::to_string(&extended()).unwrap();
+    assert_eq!(json, r#"{"name":"Aristotle","age":61,"nickname":"Ari"}"#);
+
+    let lenient: Lenient = musli::json::from_str(&json).unwrap();
+    assert_eq!(lenient.age, 61);
+
+    let error = musli::json::from_str::<Strict>(&json).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Type Strict has no field \"nickname\""),
+        "{error}"
+    );
+}
@@ -0,0 +1,99 @@
+//! Test that decoding a descriptive payload into a [`Value`] preserves the
+//! exact types recorded in its tags.
+
+#![cfg(feature = "test")]
+
+use musli::alloc::System;
+use musli::value::{Number, Value};
+use musli::Encode;
+
+#[derive(Encode)]
+struct Frame {
+    a: u8,
+    b: u16,
+    c: u32,
+    d: u64,
+    e: u128,
+    f: i8,
+    g: i16,
+    h: i32,
+    i: i64,
+    j: i128,
+    k: f32,
+    l: f64,
+    m: char,
+    n: bool,
+    o: usize,
+    p: isize,
+}
+
+#[test]
+fn exact_numbers() {
+    let bytes = musli::descriptive::to_vec(&Frame {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+        e: 5,
+        f: -1,
+        g: -2,
+        h: -3,
+        i: -4,
+        j: -5,
+        k: 1.5,
+        l: 2.5,
+        m: 'x',
+        n: true,
+        o: 6,
+        p: -6,
+    })
+    .unwrap();
+
+    let value: Value<System> = musli::descriptive::from_slice(&bytes).unwrap();
+
+    let Value::Map(entries) = &value else {
+        panic!("expected map, got {value:?}");
+    };
+
+    let values = entries.iter().map(|(_, v)| v).collect::<Vec<_>>();
+
+    macro_rules! number {
+        ($index:expr, $pat:pat) => {
+            assert!(
+                matches!(values[$index], Value::Number($pat)),
+                "{}: {:?}",
+                $index,
+                values[$index]
+            );
+        };
+    }
+
+    assert_eq!(values.len(), 16);
+    number!(0, Number::U8(1));
+    number!(1, Number::U16(2));
+    number!(2, Number::U32(3));
+    number!(3, Number::U64(4));
+    number!(4, Number::U128(5));
+    number!(5, Number::I8(-1));
+    number!(6, Number::I16(-2));
+    number!(7, Number::I32(-3));
+    number!(8, Number::I64(-4));
+    number!(9, Number::I128(-5));
+    number!(10, Number::F32(1.5));
+    number!(11, Number::F64(2.5));
+    assert!(matches!(values[12], Value::Char('x')));
+    assert!(matches!(values[13], Value::Bool(true)));
+
+    // usize and isize are tagged as the fixed-width integer of the same size.
+    #[cfg(target_pointer_width = "64")]
+    {
+        number!(14, Number::U64(6));
+        number!(15, Number::I64(-6));
+    }
+
+    #[cfg(target_pointer_width = "32")]
+    {
+        number!(14, Number::U32(6));
+        number!(15, Number::I32(-6));
+    }
+}
@@ -0,0 +1,184 @@
+//! Test recovering partial values from corrupt descriptive payloads.
+
+use musli::alloc::System;
+use musli::value::Value;
+use musli::{Decode, Encode};
+
+#[derive(Debug, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Person {
+    name: String,
+    tags: Vec<String>,
+}
+
+fn people() -> Vec<Person> {
+    vec![
+        Person {
+            name: String::from("Plato"),
+            tags: vec![String::from("philosopher"), String::from("teacher")],
+        },
+        Person {
+            name: String::from("Aristotle"),
+            tags: vec![String::from("student")],
+        },
+    ]
+}
+
+fn find(bytes: &[u8], needle: &[u8]) -> usize {
+    bytes
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .expect("needle not found")
+}
+
+fn seq(value: &Value<System>) -> &[Value<System>] {
+    let Value::Sequence(values) = value else {
+        panic!("expected sequence, got {value:?}");
+    };
+
+    values
+}
+
+fn field<'a>(value: &'a Value<System>, name: &str) -> &'a Value<System> {
+    let Value::Map(entries) = value else {
+        panic!("expected map, got {value:?}");
+    };
+
+    for (key, value) in entries.iter() {
+        if let Value::String(key) = key {
+            if key.as_str() == name {
+                return value;
+            }
+        }
+    }
+
+    panic!("missing field {name}");
+}
+
+#[test]
+fn valid() {
+    let bytes = musli::descriptive::to_vec(&people()).unwrap();
+    let (value, issues) = musli::descriptive::to_value_lossy(&bytes);
+    assert!(issues.is_empty(), "{issues:?}");
+    assert_eq!(
+        value,
+        musli::descriptive::from_slice::<Value<System>>(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn recover_invalid_leaf() {
+    let mut bytes = musli::descriptive::to_vec(&people()).unwrap();
+
+    // Corrupt the first character of "philosopher", which is a string nested
+    // two containers deep.
+    let offset = find(&bytes, b"philosopher") - 1;
+    bytes[offset + 1] = 0xff;
+
+    let (value, issues) = musli::descriptive::to_value_lossy(&bytes);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].offset(), offset);
+
+    let people = seq(&value);
+    assert_eq!(people.len(), 2);
+
+    let tags = seq(field(&people[0], "tags"));
+    assert_eq!(tags[0], Value::Invalid(offset));
+    assert!(matches!(&tags[1], Value::String(s) if s.as_str() == "teacher"));
+
+    // Everything after the corrupt value is recovered.
+    assert!(matches!(field(&people[1], "name"), Value::String(s) if s.as_str() == "Aristotle"));
+}
+
+#[test]
+fn recover_multiple_invalid_leaves() {
+    let mut bytes = musli::descriptive::to_vec(&people()).unwrap();
+
+    let first = find(&bytes, b"Plato") - 1;
+    bytes[first + 1] = 0xff;
+    let second = find(&bytes, b"student") - 1;
+    bytes[second + 1] = 0xff;
+
+    let (value, issues) = musli::descriptive::to_value_lossy(&bytes);
+
+    let offsets = issues.iter().map(|i| i.offset()).collect::<Vec<_>>();
+    assert_eq!(offsets, [first, second]);
+
+    let people = seq(&value);
+    assert_eq!(*field(&people[0], "name"), Value::Invalid(first));
+    assert_eq!(seq(field(&people[1], "tags"))[0], Value::Invalid(second));
+}
+
+#[test]
+fn stop_at_invalid_tag() {
+    let mut bytes = musli::descriptive::to_vec(&people()).unwrap();
+
+    // Replace the tag of "teacher" with a reserved kind, which makes the
+    // extent of everything that follows unknown.
+    let offset = find(&bytes, b"teacher") - 1;
+    bytes[offset] = 0;
+
+    let (value, issues) = musli::descriptive::to_value_lossy(&bytes);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].offset(), offset);
+
+    // The partially decoded containers are retained.
+    let people = seq(&value);
+    assert_eq!(people.len(), 1);
+
+    let tags = seq(field(&people[0], "tags"));
+    assert!(matches!(&tags[0], Value::String(s) if s.as_str() == "philosopher"));
+    assert_eq!(tags[1], Value::Invalid(offset));
+}
+
+#[test]
+fn stop_at_truncated_input() {
+    let bytes = musli::descriptive::to_vec(&people()).unwrap();
+    let offset = find(&bytes, b"Aristotle") - 1;
+
+    let (value, issues) = musli::descriptive::to_value_lossy(&bytes[..offset + 3]);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].offset(), offset);
+
+    let people = seq(&value);
+    assert_eq!(people.len(), 2);
+    assert_eq!(seq(field(&people[0], "tags")).len(), 2);
+    assert_eq!(*field(&people[1], "name"), Value::Invalid(offset));
+}
+
+#[test]
+fn invalid_value_cannot_be_decoded() {
+    let value = Value::<System>::Invalid(4);
+    assert!(musli::value::decode::<String>(&value).is_err());
+}
+
+#[test]
+fn stop_at_deeply_nested_input() {
+    // A sequence containing a single empty sequence, where the first byte is
+    // the tag of a sequence with one element.
+    let bytes = musli::descriptive::to_vec(&vec![Vec::<u8>::new()]).unwrap();
+    assert_eq!(bytes.len(), 2);
+
+    let mut nested = vec![bytes[0]; 5000];
+    nested.push(bytes[1]);
+
+    let (value, issues) = musli::descriptive::to_value_lossy(&nested);
+
+    assert_eq!(issues.len(), 1, "{issues:?}");
+    assert_eq!(issues[0].offset(), 256);
+
+    let mut current = &value;
+    let mut depth = 0;
+
+    while let Value::Sequence(values) = current {
+        assert_eq!(values.len(), 1);
+        current = &values[0];
+        depth += 1;
+    }
+
+    assert_eq!(depth, 256);
+    assert_eq!(*current, Value::Invalid(256));
+}
@@ -0,0 +1,63 @@
+//! Test that skipping over large unknown fields in the descriptive format
+//! neither allocates nor depends on the size of the field.
+
+use musli::{Decode, Encode};
+
+#[path = "common/alloc.rs"]
+mod alloc;
+
+use self::alloc::allocations;
+
+#[derive(Encode)]
+#[musli(name_all = "name")]
+struct Newer {
+    id: u32,
+    #[musli(bytes)]
+    blob: Vec<u8>,
+    text: String,
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(name_all = "name")]
+struct Older {
+    id: u32,
+    name: String,
+}
+
+/// Decode an `Older` from a payload with unknown fields of the given size,
+/// returning the number of bytes allocated while decoding.
+fn decode_allocated(size: usize) -> usize {
+    let newer = Newer {
+        id: 42,
+        blob: vec![0xaa; size],
+        text: "x".repeat(size),
+        name: String::from("Aristotle"),
+    };
+
+    let bytes = musli::descriptive::to_vec(&newer).unwrap();
+    assert!(bytes.len() > size * 2);
+    drop(newer);
+
+    let (older, allocated) =
+        allocations(|| musli::descriptive::from_slice::<Older>(&bytes).unwrap());
+
+    assert_eq!(
+        older,
+        Older {
+            id: 42,
+            name: String::from("Aristotle"),
+        }
+    );
+
+    allocated.bytes
+}
+
+#[test]
+fn skip_large_unknown_fields() {
+    let small = decode_allocated(16);
+    let large = decode_allocated(16 * 1024 * 1024);
+
+    assert!(large < 1024, "allocated {large} bytes");
+    assert_eq!(small, large);
+}
@@ -0,0 +1,96 @@
+//! Test that numerical coercions in the descriptive format can be disabled
+//! with strict types.
+
+use musli::descriptive::{self, Encoding};
+use musli::{Decode, Encode};
+
+const STRICT: Encoding = Encoding::new().with_strict_types();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Unsigned {
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Signed {
+    value: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Float {
+    value: f64,
+}
+
+#[test]
+fn lenient_by_default() {
+    let bytes = descriptive::to_vec(&Unsigned { value: 42 }).unwrap();
+    let signed: Signed = descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(signed, Signed { value: 42 });
+
+    let bytes = descriptive::to_vec(&Signed { value: 42 }).unwrap();
+    let unsigned: Unsigned = descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(unsigned, Unsigned { value: 42 });
+
+    let bytes = descriptive::to_vec(&Unsigned { value: 42 }).unwrap();
+    assert!(descriptive::from_slice::<Float>(&bytes).is_ok());
+}
+
+#[test]
+fn strict_same_kind() {
+    let bytes = STRICT.to_vec(&Unsigned { value: 42 }).unwrap();
+    assert_eq!(
+        STRICT.from_slice::<Unsigned>(&bytes).unwrap(),
+        Unsigned { value: 42 }
+    );
+
+    let bytes = STRICT.to_vec(&Signed { value: -42 }).unwrap();
+    assert_eq!(
+        STRICT.from_slice::<Signed>(&bytes).unwrap(),
+        Signed { value: -42 }
+    );
+
+    let bytes = STRICT.to_vec(&Float { value: 4.2 }).unwrap();
+    assert_eq!(
+        STRICT.from_slice::<Float>(&bytes).unwrap(),
+        Float { value: 4.2 }
+    );
+
+    // Widths may differ.
+    let bytes = STRICT.to_vec(&42u64).unwrap();
+    assert_eq!(STRICT.from_slice::<u8>(&bytes).unwrap(), 42);
+
+    let bytes = STRICT.to_vec(&-42i8).unwrap();
+    assert_eq!(STRICT.from_slice::<i128>(&bytes).unwrap(), -42);
+}
+
+#[test]
+fn strict_rejects_coercions() {
+    let bytes = STRICT.to_vec(&Unsigned { value: 42 }).unwrap();
+    assert!(STRICT.from_slice::<Signed>(&bytes).is_err());
+    assert!(STRICT.from_slice::<Float>(&bytes).is_err());
+
+    let bytes = STRICT.to_vec(&Signed { value: 42 }).unwrap();
+    assert!(STRICT.from_slice::<Unsigned>(&bytes).is_err());
+    assert!(STRICT.from_slice::<Float>(&bytes).is_err());
+
+    let bytes = STRICT.to_vec(&Float { value: 4.2 }).unwrap();
+    assert!(STRICT.from_slice::<Unsigned>(&bytes).is_err());
+    assert!(STRICT.from_slice::<Signed>(&bytes).is_err());
+}
+
+#[test]
+fn strict_nested() {
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct Outer {
+        values: Vec<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Encode, Decode)]
+    struct OuterSigned {
+        values: Vec<i32>,
+    }
+
+    let bytes = STRICT.to_vec(&Outer { values: vec![1, 2] }).unwrap();
+    assert!(descriptive::from_slice::<OuterSigned>(&bytes).is_ok());
+    assert!(STRICT.from_slice::<OuterSigned>(&bytes).is_err());
+}
@@ -0,0 +1,167 @@
+//! Test that maps with duplicate keys are rejected when the format is
+//! configured to do so, and that the last entry wins otherwise.
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::en::MapEncoder;
+use musli::hint::MapHint;
+use musli::options::{self, Options};
+use musli::{Decode, Encode, Encoder};
+
+const OPTIONS: Options = options::new().reject_duplicate_keys().build();
+
+/// A list of pairs which is encoded as a map, permitting duplicate keys.
+struct Pairs(&'static [(u32, u32)]);
+
+impl<M> Encode<M> for Pairs {
+    const IS_BITWISE_ENCODE: bool = false;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let hint = MapHint::with_size(self.0.len());
+
+        encoder.encode_map_fn(&hint, |map| {
+            for (key, value) in self.0 {
+                map.insert_entry(key, value)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+const DUPLICATES: Pairs = Pairs(&[(1, 10), (2, 20), (1, 30)]);
+const UNIQUE: Pairs = Pairs(&[(1, 10), (2, 20)]);
+
+macro_rules! test_format {
+    ($name:ident, $format:ident) => {
+        #[test]
+        fn $name() -> Result<(), Box<dyn std::error::Error>> {
+            use musli::$format::Encoding;
+
+            const STRICT: Encoding<OPTIONS> = Encoding::new().with_options();
+
+            let bytes = musli::$format::to_vec(&DUPLICATES)?;
+            let map: HashMap<u32, u32> = musli::$format::from_slice(&bytes)?;
+            assert_eq!(map, HashMap::from([(1, 30), (2, 20)]));
+
+            let bytes = STRICT.to_vec(&DUPLICATES)?;
+            let error = STRICT.from_slice::<HashMap<u32, u32>>(&bytes).unwrap_err();
+            assert!(error.to_string().contains("Duplicate key"), "{error}");
+            let error = STRICT.from_slice::<BTreeMap<u32, u32>>(&bytes).unwrap_err();
+            assert!(error.to_string().contains("Duplicate key"), "{error}");
+
+            let bytes = STRICT.to_vec(&UNIQUE)?;
+            let map: BTreeMap<u32, u32> = STRICT.from_slice(&bytes)?;
+            assert_eq!(map, BTreeMap::from([(1, 10), (2, 20)]));
+            Ok(())
+        }
+    };
+}
+
+test_format!(storage, storage);
+test_format!(wire, wire);
+test_format!(descriptive, descriptive);
+
+#[test]
+fn json() -> Result<(), Box<dyn std::error::Error>> {
+    const STRICT: musli::json::Encoding = musli::json::Encoding::new().with_reject_duplicate_keys();
+
+    let input = r#"{"a":{"x":1,"x":2},"b":{"y":3}}"#;
+
+    let map: HashMap<String, HashMap<String, u32>> = musli::json::from_str(input)?;
+    assert_eq!(map["a"]["x"], 2);
+
+    let error = STRICT
+        .from_str::<HashMap<String, HashMap<String, u32>>>(input)
+        .unwrap_err();
+    assert!(error.to_string().contains("Duplicate key"), "{error}");
+
+    let input = r#"[{"x":1},{"y":2,"y":3}]"#;
+    let error = STRICT
+        .from_str::<Vec<BTreeMap<String, u32>>>(input)
+        .unwrap_err();
+    assert!(error.to_string().contains("Duplicate key"), "{error}");
+
+    let map: BTreeMap<String, u32> = STRICT.from_str(r#"{"x":1,"y":2}"#)?;
+    assert_eq!(map.len(), 2);
+    Ok(())
+}
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(name_all = "name")]
+struct Flattened {
+    name: String,
+    #[musli(flatten)]
+    extra: BTreeMap<String, u32>,
+}
+
+#[test]
+fn validate() -> Result<(), Box<dyn std::error::Error>> {
+    const STORAGE: musli::storage::Encoding<OPTIONS> =
+        musli::storage::Encoding::new().with_options();
+
+    for pairs in [&DUPLICATES, &UNIQUE] {
+        let bytes = STORAGE.to_vec(pairs)?;
+
+        assert_eq!(
+            STORAGE.validate::<HashMap<u32, u32>>(&bytes).is_ok(),
+            STORAGE.from_slice::<HashMap<u32, u32>>(&bytes).is_ok(),
+        );
+
+        assert_eq!(
+            STORAGE.validate::<BTreeMap<u32, u32>>(&bytes).is_ok(),
+            STORAGE.from_slice::<BTreeMap<u32, u32>>(&bytes).is_ok(),
+        );
+    }
+
+    assert!(STORAGE
+        .validate::<HashMap<u32, u32>>(&STORAGE.to_vec(&DUPLICATES)?)
+        .is_err());
+
+    const JSON: musli::json::Encoding = musli::json::Encoding::new().with_reject_duplicate_keys();
+
+    for (input, ok) in [
+        (r#"{"x":1,"y":2}"#, true),
+        (r#"{"x":1,"x":2}"#, false),
+        (r#"{"xy":1,"x":2}"#, true),
+    ] {
+        assert_eq!(JSON.from_str::<HashMap<String, u32>>(input).is_ok(), ok);
+        assert_eq!(
+            JSON.validate::<HashMap<String, u32>>(input.as_bytes())
+                .is_ok(),
+            ok,
+            "{input}"
+        );
+        assert_eq!(
+            JSON.validate::<BTreeMap<String, u32>>(input.as_bytes())
+                .is_ok(),
+            ok,
+            "{input}"
+        );
+    }
+
+    for (input, ok) in [
+        (r#"{"name":"a","x":1,"y":2}"#, true),
+        (r#"{"name":"a","x":1,"x":2}"#, false),
+    ] {
+        assert_eq!(JSON.from_str::<Flattened>(input).is_ok(), ok);
+        assert_eq!(
+            JSON.validate::<Flattened>(input.as_bytes()).is_ok(),
+            ok,
+            "{input}"
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,259 @@
+//! Test that a custom dynamic value can be decoded through a visitor, without
+//! relying on `musli::value`.
+
+use std::fmt;
+
+use musli::de::{
+    Decoder, EntryDecoder, MapDecoder, SequenceDecoder, SizeHint, UnsizedVisitor, Visitor,
+};
+use musli::{Allocator, Context, Decode};
+
+trait DynValue: fmt::Debug {
+    fn describe(&self) -> String;
+}
+
+#[derive(Debug)]
+struct Null;
+
+impl DynValue for Null {
+    fn describe(&self) -> String {
+        "null".to_string()
+    }
+}
+
+impl DynValue for bool {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DynValue for i64 {
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl DynValue for f64 {
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl DynValue for String {
+    fn describe(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+#[derive(Debug)]
+struct List(Vec<Dyn>);
+
+impl DynValue for List {
+    fn describe(&self) -> String {
+        let items = self.0.iter().map(|v| v.0.describe()).collect::<Vec<_>>();
+        format!("[{}]", items.join(", "))
+    }
+}
+
+#[derive(Debug)]
+struct Object(Vec<(String, Dyn)>);
+
+impl DynValue for Object {
+    fn describe(&self) -> String {
+        let entries = self
+            .0
+            .iter()
+            .map(|(k, v)| format!("{k:?}: {}", v.0.describe()))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(", "))
+    }
+}
+
+#[derive(Debug)]
+struct Dyn(Box<dyn DynValue>);
+
+impl Dyn {
+    fn new<T>(value: T) -> Self
+    where
+        T: 'static + DynValue,
+    {
+        Self(Box::new(value))
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Dyn
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        decoder.decode_any(DynVisitor)
+    }
+}
+
+struct DynVisitor;
+
+#[musli::visitor]
+impl<'de, C> Visitor<'de, C> for DynVisitor
+where
+    C: Context,
+{
+    type Ok = Dyn;
+    type String = StringVisitor;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a dynamic value")
+    }
+
+    #[inline]
+    fn visit_empty(self, _: C) -> Result<Self::Ok, C::Error> {
+        Ok(Dyn::new(Null))
+    }
+
+    #[inline]
+    fn visit_bool(self, _: C, value: bool) -> Result<Self::Ok, C::Error> {
+        Ok(Dyn::new(value))
+    }
+
+    #[inline]
+    fn visit_u8(self, cx: C, value: u8) -> Result<Self::Ok, C::Error> {
+        self.visit_u64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_u16(self, cx: C, value: u16) -> Result<Self::Ok, C::Error> {
+        self.visit_u64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_u32(self, cx: C, value: u32) -> Result<Self::Ok, C::Error> {
+        self.visit_u64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_u64(self, cx: C, value: u64) -> Result<Self::Ok, C::Error> {
+        let value = i64::try_from(value).map_err(cx.map())?;
+        Ok(Dyn::new(value))
+    }
+
+    #[inline]
+    fn visit_i8(self, cx: C, value: i8) -> Result<Self::Ok, C::Error> {
+        self.visit_i64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_i16(self, cx: C, value: i16) -> Result<Self::Ok, C::Error> {
+        self.visit_i64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_i32(self, cx: C, value: i32) -> Result<Self::Ok, C::Error> {
+        self.visit_i64(cx, value.into())
+    }
+
+    #[inline]
+    fn visit_i64(self, _: C, value: i64) -> Result<Self::Ok, C::Error> {
+        Ok(Dyn::new(value))
+    }
+
+    #[inline]
+    fn visit_f64(self, _: C, value: f64) -> Result<Self::Ok, C::Error> {
+        Ok(Dyn::new(value))
+    }
+
+    #[inline]
+    fn visit_option<D>(self, _: C, decoder: Option<D>) -> Result<Self::Ok, C::Error>
+    where
+        D: Decoder<'de, Cx = C, Error = C::Error, Allocator = C::Allocator>,
+    {
+        match decoder {
+            Some(decoder) => decoder.decode_any(self),
+            None => Ok(Dyn::new(Null)),
+        }
+    }
+
+    #[inline]
+    fn visit_sequence<D>(self, seq: &mut D) -> Result<Self::Ok, C::Error>
+    where
+        D: ?Sized + SequenceDecoder<'de, Cx = C>,
+    {
+        let mut items = Vec::with_capacity(seq.size_hint().or_default());
+
+        while let Some(item) = seq.try_decode_next()? {
+            items.push(item.decode_any(DynVisitor)?);
+        }
+
+        Ok(Dyn::new(List(items)))
+    }
+
+    #[inline]
+    fn visit_map<D>(self, map: &mut D) -> Result<Self::Ok, C::Error>
+    where
+        D: ?Sized + MapDecoder<'de, Cx = C>,
+    {
+        let mut entries = Vec::with_capacity(map.size_hint().or_default());
+
+        while let Some(mut entry) = map.decode_entry()? {
+            let key = entry.decode_key()?.decode::<String>()?;
+            let value = entry.decode_value()?.decode_any(DynVisitor)?;
+            entries.push((key, value));
+        }
+
+        Ok(Dyn::new(Object(entries)))
+    }
+
+    #[inline]
+    fn visit_string(self, _: C, _: SizeHint) -> Result<Self::String, C::Error> {
+        Ok(StringVisitor)
+    }
+}
+
+struct StringVisitor;
+
+impl<C> UnsizedVisitor<'_, C, str> for StringVisitor
+where
+    C: Context,
+{
+    type Ok = Dyn;
+
+    #[inline]
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a string")
+    }
+
+    #[inline]
+    fn visit_ref(self, _: C, string: &str) -> Result<Self::Ok, C::Error> {
+        Ok(Dyn::new(string.to_owned()))
+    }
+}
+
+#[test]
+fn json_dyn_value() {
+    let value: Dyn = musli::json::from_str(
+        r#"{"name": "plugin", "version": 3, "ratio": 0.5, "tags": ["a", true, null]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        value.0.describe(),
+        r#"{"name": "plugin", "version": 3, "ratio": 0.5, "tags": ["a", true, null]}"#
+    );
+}
+
+#[test]
+fn descriptive_dyn_value() {
+    let bytes = musli::descriptive::to_vec(&(String::from("plugin"), vec![1i64, -2, 3])).unwrap();
+    let value: Dyn = musli::descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(value.0.describe(), r#"["plugin", [1, -2, 3]]"#);
+}
+
+#[test]
+fn dyn_value_unsupported() {
+    let bytes = musli::descriptive::to_vec(&'a').unwrap();
+    let error = musli::descriptive::from_slice::<Dyn>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("a dynamic value"), "{error}");
+}
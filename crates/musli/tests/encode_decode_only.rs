@@ -0,0 +1,190 @@
+//! Test fields which only participate in one of encoding or decoding through a
+//! bare `#[musli(encode_only)]` or `#[musli(decode_only)]`.
+
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode, Encoder};
+
+/// A type which can only be encoded.
+#[derive(Debug, Default, PartialEq)]
+struct Host(&'static str);
+
+impl<M> Encode<M> for Host {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_string(self.0)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Audit {
+    action: String,
+    #[musli(encode_only)]
+    host: Host,
+    #[musli(decode_only)]
+    #[musli(default = legacy_default)]
+    legacy: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct AuditRecord {
+    action: String,
+    host: String,
+    #[musli(default)]
+    legacy: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+enum Event {
+    Audit {
+        action: String,
+        #[musli(encode_only)]
+        host: Host,
+    },
+}
+
+/// Only encoded, so decode-only attributes are accepted without warnings.
+#[derive(Encode)]
+struct WriteOnly {
+    #[musli(default = legacy_default)]
+    value: u32,
+}
+
+/// Only decoded, so encode-only attributes are accepted without warnings.
+#[derive(Debug, PartialEq, Decode)]
+struct ReadOnly {
+    #[musli(skip_encoding_if = is_zero)]
+    value: u32,
+}
+
+fn legacy_default() -> u32 {
+    42
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+#[test]
+fn encode_only() -> Result<(), musli::json::Error> {
+    let audit = Audit {
+        action: String::from("login"),
+        host: Host("example.com"),
+        legacy: 1,
+    };
+
+    let json = musli::json::to_string(&audit)?;
+    assert_eq!(json, r#"{"action":"login","host":"example.com"}"#);
+
+    let record: AuditRecord = musli::json::from_str(&json)?;
+
+    assert_eq!(
+        record,
+        AuditRecord {
+            action: String::from("login"),
+            host: String::from("example.com"),
+            legacy: None,
+        }
+    );
+
+    let decoded: Audit = musli::json::from_str(&json)?;
+
+    assert_eq!(
+        decoded,
+        Audit {
+            action: String::from("login"),
+            host: Host::default(),
+            legacy: 42,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn decode_only() -> Result<(), musli::json::Error> {
+    let record = AuditRecord {
+        action: String::from("login"),
+        host: String::from("example.com"),
+        legacy: Some(7),
+    };
+
+    let json = musli::json::to_string(&record)?;
+    let audit: Audit = musli::json::from_str(&json)?;
+
+    assert_eq!(
+        audit,
+        Audit {
+            action: String::from("login"),
+            host: Host::default(),
+            legacy: 7,
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn self_describing_formats() {
+    let audit = Audit {
+        action: String::from("login"),
+        host: Host("example.com"),
+        legacy: 1,
+    };
+
+    let expected = Audit {
+        action: String::from("login"),
+        host: Host::default(),
+        legacy: 42,
+    };
+
+    let bytes = musli::wire::to_vec(&audit).unwrap();
+    let actual: Audit = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(actual, expected);
+
+    let bytes = musli::descriptive::to_vec(&audit).unwrap();
+    let actual: Audit = musli::descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn enum_variant() -> Result<(), musli::json::Error> {
+    let event = Event::Audit {
+        action: String::from("login"),
+        host: Host("example.com"),
+    };
+
+    let json = musli::json::to_string(&event)?;
+    let decoded: Event = musli::json::from_str(&json)?;
+
+    assert_eq!(
+        decoded,
+        Event::Audit {
+            action: String::from("login"),
+            host: Host::default(),
+        }
+    );
+
+    Ok(())
+}
+
+#[test]
+fn single_derive() -> Result<(), musli::json::Error> {
+    let json = musli::json::to_string(&WriteOnly { value: 1 })?;
+    let decoded: ReadOnly = musli::json::from_str(&json)?;
+    assert_eq!(decoded, ReadOnly { value: 1 });
+    Ok(())
+}
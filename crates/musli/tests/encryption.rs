@@ -0,0 +1,100 @@
+//! Test transparently encrypting and decrypting encoded data.
+
+use musli::context;
+use musli::wrap::{AuthenticationError, DecryptingReader, EncryptingWriter, CHUNK_SIZE};
+use musli::{Decode, Encode};
+
+const KEY: [u8; 32] = [42; 32];
+const NONCE: [u8; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Archive {
+    name: String,
+    #[musli(bytes)]
+    data: Vec<u8>,
+}
+
+fn archive(len: usize) -> Archive {
+    Archive {
+        name: String::from("archive"),
+        data: (0..len).map(|n| n as u8).collect(),
+    }
+}
+
+fn encrypt(value: &Archive) -> Vec<u8> {
+    let mut writer = EncryptingWriter::new(&KEY, NONCE, Vec::new());
+    musli::descriptive::encode(&mut writer, value).unwrap();
+    writer.into_inner()
+}
+
+fn decrypt(key: &[u8; 32], bytes: &[u8]) -> Result<Archive, std::io::Error> {
+    let cx = context::new().with_error::<std::io::Error>();
+    let reader = DecryptingReader::new(&cx, key, bytes)?;
+    musli::descriptive::Encoding::new().decode_with(&cx, reader)
+}
+
+fn is_authentication_error(error: &std::io::Error) -> bool {
+    error
+        .get_ref()
+        .is_some_and(|e| e.downcast_ref::<AuthenticationError>().is_some())
+}
+
+#[test]
+fn roundtrip() {
+    for len in [0, 16, CHUNK_SIZE - 64, CHUNK_SIZE, CHUNK_SIZE * 2 + 10] {
+        let expected = archive(len);
+        let bytes = encrypt(&expected);
+        assert!(!bytes.windows(7).any(|w| w == b"archive"));
+        assert_eq!(decrypt(&KEY, &bytes).unwrap(), expected);
+    }
+}
+
+#[test]
+fn tampered() {
+    let bytes = encrypt(&archive(CHUNK_SIZE * 2));
+
+    for index in [0, 7, 8, 12, 40, CHUNK_SIZE, bytes.len() - 1] {
+        let mut tampered = bytes.clone();
+        tampered[index] ^= 0x01;
+        let error = decrypt(&KEY, &tampered).unwrap_err();
+        assert!(is_authentication_error(&error), "{index}: {error}");
+    }
+}
+
+#[test]
+fn wrong_key() {
+    let bytes = encrypt(&archive(16));
+    let error = decrypt(&[0; 32], &bytes).unwrap_err();
+    assert!(is_authentication_error(&error), "{error}");
+}
+
+#[test]
+fn truncated() {
+    let bytes = encrypt(&archive(CHUNK_SIZE * 2));
+
+    // Drop the last frame, leaving two complete frames after the nonce prefix.
+    let truncated = &bytes[..7 + 2 * (CHUNK_SIZE + 21)];
+    let error = decrypt(&KEY, truncated).unwrap_err();
+    assert!(is_authentication_error(&error), "{error}");
+
+    for len in [0, 3, 7, 8, 10, 100, bytes.len() - 1] {
+        let error = decrypt(&KEY, &bytes[..len]).unwrap_err();
+        assert!(is_authentication_error(&error), "{len}: {error}");
+    }
+}
+
+#[test]
+fn trailing() {
+    let mut bytes = encrypt(&archive(16));
+    bytes.push(0);
+
+    let error = decrypt(&KEY, &bytes).unwrap_err();
+    assert!(is_authentication_error(&error), "{error}");
+}
+
+#[test]
+fn finish_once() {
+    let mut writer = EncryptingWriter::new(&KEY, NONCE, Vec::new());
+    musli::descriptive::encode(&mut writer, &archive(16)).unwrap();
+    assert!(musli::descriptive::encode(&mut writer, &archive(16)).is_err());
+}
@@ -0,0 +1,89 @@
+//! Test that encoding into [`FixedBytes`] only exposes the bytes which were
+//! written.
+//!
+//! These are run under miri to ensure that no uninitialized bytes are read.
+
+#![cfg(feature = "test")]
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use musli::{Decode, Encode, FixedBytes};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Person {
+    name: String,
+    age: u32,
+}
+
+fn hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
+
+macro_rules! check {
+    ($format:ident) => {{
+        let person = Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        };
+
+        let bytes: FixedBytes<128> = musli::$format::to_fixed_bytes(&person).unwrap();
+        let vec = musli::$format::to_vec(&person).unwrap();
+
+        assert_eq!(bytes.len(), vec.len());
+        assert_eq!(bytes.as_slice(), &vec[..]);
+        assert_eq!(bytes.as_ref(), &vec[..]);
+        assert_eq!(hash(bytes.as_slice()), hash(&vec));
+
+        let decoded: Person = musli::$format::from_slice(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, person);
+
+        assert_eq!(bytes.into_vec(), vec);
+    }};
+}
+
+#[test]
+fn only_written_bytes() {
+    check!(storage);
+    check!(packed);
+    check!(wire);
+    check!(descriptive);
+    check!(json);
+}
+
+#[test]
+fn partially_written() {
+    let bytes = FixedBytes::<16>::with_capacity(16);
+    assert!(bytes.is_empty());
+    assert_eq!(hash(bytes.as_slice()), hash(&[]));
+    assert_eq!(bytes.into_bytes(), None);
+
+    let mut bytes = FixedBytes::<16>::new();
+    assert!(bytes.extend_from_slice(b"abc"));
+    assert!(bytes.push(b'd'));
+    assert_eq!(bytes.len(), 4);
+    assert_eq!(bytes.remaining(), 12);
+    assert_eq!(hash(bytes.as_slice()), hash(b"abcd"));
+    assert_eq!(format!("{bytes:?}"), "[97, 98, 99, 100]");
+
+    assert!(!bytes.extend_from_slice(&[0; 13]));
+    assert_eq!(bytes.as_slice(), b"abcd");
+
+    let mut full = FixedBytes::<4>::new();
+    assert!(full.extend_from_slice(b"wxyz"));
+    assert!(!full.push(0));
+    assert_eq!(full.into_bytes(), Some(*b"wxyz"));
+}
+
+#[test]
+fn overflow() {
+    let person = Person {
+        name: String::from("Aristotle"),
+        age: 61,
+    };
+
+    let error = musli::storage::to_fixed_bytes::<4, _>(&person).unwrap_err();
+    assert!(error.to_string().contains("capacity 4"), "{error}");
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "test")]
+
+use musli::compat::Fixed;
+use musli::{Decode, Encode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+struct Measurement {
+    whole: Fixed<0>,
+    cents: Fixed<2>,
+    fine: Fixed<18>,
+}
+
+#[test]
+fn format() {
+    assert_eq!(Fixed::<0>(-42).to_string(), "-42");
+    assert_eq!(Fixed::<2>(5).to_string(), "0.05");
+    assert_eq!(Fixed::<2>(-5).to_string(), "-0.05");
+    assert_eq!(Fixed::<3>(120_000).to_string(), "120.000");
+    assert_eq!(Fixed::<18>(i64::MIN).to_string(), "-9.223372036854775808");
+    assert_eq!(Fixed::<0>(i64::MIN).to_string(), "-9223372036854775808");
+    assert_eq!(format!("{:>8}", Fixed::<1>(15)), "     1.5");
+}
+
+#[test]
+fn parse() {
+    assert_eq!("12".parse::<Fixed<2>>(), Ok(Fixed(1200)));
+    assert_eq!("12.5".parse::<Fixed<2>>(), Ok(Fixed(1250)));
+    assert_eq!("-0.07".parse::<Fixed<2>>(), Ok(Fixed(-7)));
+    assert_eq!(
+        "-9.223372036854775808".parse::<Fixed<18>>(),
+        Ok(Fixed(i64::MIN))
+    );
+    assert_eq!(
+        "9.223372036854775807".parse::<Fixed<18>>(),
+        Ok(Fixed(i64::MAX))
+    );
+
+    for invalid in ["", "-", ".5", "1.", "1.2.3", "+1", "1e3", " 1", "0x10"] {
+        let error = invalid.parse::<Fixed<2>>().unwrap_err();
+        assert_eq!(error.to_string(), "Invalid decimal number", "{invalid:?}");
+    }
+
+    for precise in ["1.234", "0.000", "-1.001", "0.1234567890123456789012345"] {
+        let error = precise.parse::<Fixed<2>>().unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Decimal number has more than 2 fractional digits"
+        );
+    }
+
+    assert!("1.5".parse::<Fixed<0>>().is_err());
+
+    for overflow in ["9.223372036854775808", "-9.223372036854775809", "10"] {
+        let error = overflow.parse::<Fixed<18>>().unwrap_err();
+        assert_eq!(error.to_string(), "Decimal number is out of range");
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let values = [
+        Measurement {
+            whole: Fixed(0),
+            cents: Fixed(0),
+            fine: Fixed(0),
+        },
+        Measurement {
+            whole: Fixed(i64::MAX),
+            cents: Fixed(i64::MIN),
+            fine: Fixed(-1),
+        },
+        Measurement {
+            whole: Fixed(-3),
+            cents: Fixed(1999),
+            fine: Fixed(1_000_000_000_000_000_001),
+        },
+    ];
+
+    for value in values {
+        let json = musli::json::to_string(&value).unwrap();
+        assert_eq!(musli::json::from_str::<Measurement>(&json).unwrap(), value);
+
+        musli::macros::assert_roundtrip_eq!(full, value);
+    }
+}
+
+#[test]
+fn json() {
+    let value = Measurement {
+        whole: Fixed(7),
+        cents: Fixed(-250),
+        fine: Fixed(1),
+    };
+
+    let json = musli::json::to_string(&value).unwrap();
+    assert_eq!(
+        json,
+        r#"{"whole":"7","cents":"-2.50","fine":"0.000000000000000001"}"#
+    );
+
+    let error =
+        musli::json::from_str::<Measurement>(r#"{"whole":"7","cents":"-2.505","fine":"0"}"#)
+            .unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("Decimal number has more than 2 fractional digits"));
+}
+
+#[test]
+fn binary_is_integer() {
+    // The binary form is the same as the raw integer.
+    assert_eq!(
+        musli::storage::to_vec(&Fixed::<4>(-12_345)).unwrap(),
+        musli::storage::to_vec(&-12_345i64).unwrap()
+    );
+
+    assert_eq!(
+        musli::wire::to_vec(&Fixed::<4>(300)).unwrap(),
+        musli::wire::to_vec(&300i64).unwrap()
+    );
+}
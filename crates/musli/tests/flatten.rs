@@ -0,0 +1,105 @@
+//! Test capturing unknown fields through `#[musli(flatten)]`.
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::alloc::{Allocator, System};
+use musli::value::Value;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Full {
+    name: String,
+    age: u32,
+    title: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Partial<A>
+where
+    A: Allocator,
+{
+    name: String,
+    #[musli(flatten)]
+    extra: HashMap<String, Value<A>>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Strings {
+    name: String,
+    #[musli(alias = "title")]
+    role: String,
+    #[musli(flatten)]
+    extra: BTreeMap<String, String>,
+}
+
+fn full() -> Full {
+    Full {
+        name: String::from("Aristotle"),
+        age: 61,
+        title: String::from("Philosopher"),
+        tags: vec![String::from("student"), String::from("teacher")],
+    }
+}
+
+macro_rules! test_format {
+    ($name:ident, $what:ident) => {
+        #[test]
+        fn $name() {
+            let bytes = musli::$what::to_vec(&full()).unwrap();
+
+            let partial: Partial<System> = musli::$what::from_slice(&bytes).unwrap();
+            assert_eq!(partial.name, "Aristotle");
+
+            let mut keys = partial.extra.keys().map(String::as_str).collect::<Vec<_>>();
+            keys.sort();
+            assert_eq!(keys, ["age", "tags", "title"]);
+
+            // Round-tripping through the partial model retains every field.
+            let bytes = musli::$what::to_vec(&partial).unwrap();
+            let decoded: Full = musli::$what::from_slice(&bytes).unwrap();
+            assert_eq!(decoded, full());
+        }
+    };
+}
+
+test_format!(descriptive, descriptive);
+test_format!(json, json);
+
+#[test]
+fn json_output() {
+    let value = Strings {
+        name: String::from("Aristotle"),
+        role: String::from("Philosopher"),
+        extra: BTreeMap::from([
+            (String::from("born"), String::from("Stagira")),
+            (String::from("school"), String::from("Lyceum")),
+        ]),
+    };
+
+    let json = musli::json::to_string(&value).unwrap();
+    assert_eq!(
+        json,
+        r#"{"name":"Aristotle","role":"Philosopher","born":"Stagira","school":"Lyceum"}"#
+    );
+    assert_eq!(musli::json::from_str::<Strings>(&json).unwrap(), value);
+
+    // Aliases of known fields are not captured.
+    let legacy = r#"{"title":"Philosopher","name":"Aristotle","born":"Stagira","school":"Lyceum"}"#;
+    assert_eq!(musli::json::from_str::<Strings>(legacy).unwrap(), value);
+}
+
+#[test]
+fn missing_fields_are_still_required() {
+    let result = musli::json::from_str::<Strings>(r#"{"name":"Aristotle","born":"Stagira"}"#);
+    assert!(result.is_err());
+}
+
+#[test]
+fn no_unknown_fields() {
+    let value = musli::json::from_str::<Strings>(r#"{"name":"Plato","role":"Teacher"}"#).unwrap();
+    assert!(value.extra.is_empty());
+}
@@ -17,3 +17,41 @@ fn generic_with_bound() {
         }
     );
 }
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(mode = Binary, decode_bound<A> = {T: for<'de> Decode<'de, Binary, A>})]
+#[musli(mode = Text, decode_bound<A> = {T: for<'de> Decode<'de, Text, A>})]
+pub struct GenericWithDecodeBound<T> {
+    value: T,
+}
+
+#[test]
+fn generic_with_decode_bound() {
+    let bytes = musli::storage::to_vec(&GenericWithBound {
+        value: String::from("Hello"),
+    })
+    .unwrap();
+
+    let value: GenericWithDecodeBound<String> = musli::storage::from_slice(&bytes).unwrap();
+
+    assert_eq!(
+        value,
+        GenericWithDecodeBound {
+            value: String::from("Hello"),
+        }
+    );
+
+    let json = musli::json::to_string(&GenericWithBound {
+        value: String::from("Hello"),
+    })
+    .unwrap();
+
+    let value: GenericWithDecodeBound<String> = musli::json::from_str(&json).unwrap();
+
+    assert_eq!(
+        value,
+        GenericWithDecodeBound {
+            value: String::from("Hello"),
+        }
+    );
+}
@@ -0,0 +1,151 @@
+//! Test that hash maps and sets are allocated up front from the size hint,
+//! bounded so that untrusted lengths can't cause large allocations, and that
+//! they can be decoded with custom hashers.
+
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+use musli::en::MapEncoder;
+use musli::hint::MapHint;
+use musli::{Encode, Encoder};
+
+#[path = "common/alloc.rs"]
+mod alloc;
+
+use self::alloc::allocations;
+
+/// A simple FNV-1a hasher, standing in for custom hashers like fxhash.
+#[derive(Default)]
+struct Fnv(u64);
+
+impl Hasher for Fnv {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+type FnvBuildHasher = BuildHasherDefault<Fnv>;
+
+/// A map which claims to have `len` entries, but only encodes `entries`.
+struct Lying {
+    len: usize,
+    entries: &'static [(u32, u32)],
+}
+
+impl<M> Encode<M> for Lying {
+    const IS_BITWISE_ENCODE: bool = false;
+
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        let hint = MapHint::with_size(self.len);
+
+        encoder.encode_map_fn(&hint, |map| {
+            for (key, value) in self.entries {
+                map.insert_entry(key, value)?;
+            }
+
+            Ok(())
+        })
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[test]
+fn custom_hasher() {
+    let mut map = HashMap::<String, u32, FnvBuildHasher>::default();
+    let mut set = HashSet::<String, FnvBuildHasher>::default();
+
+    for n in 0..100 {
+        map.insert(format!("key{n}"), n);
+        set.insert(format!("key{n}"));
+    }
+
+    macro_rules! test_format {
+        ($format:ident) => {{
+            let bytes = musli::$format::to_vec(&map).unwrap();
+            let decoded: HashMap<String, u32, FnvBuildHasher> =
+                musli::$format::from_slice(&bytes).unwrap();
+            assert_eq!(decoded, map);
+
+            let bytes = musli::$format::to_vec(&set).unwrap();
+            let decoded: HashSet<String, FnvBuildHasher> =
+                musli::$format::from_slice(&bytes).unwrap();
+            assert_eq!(decoded, set);
+        }};
+    }
+
+    test_format!(storage);
+    test_format!(wire);
+    test_format!(descriptive);
+    test_format!(json);
+}
+
+#[test]
+fn presized_from_hint() {
+    let map = (0..10_000u32)
+        .map(|n| (n, n * 2))
+        .collect::<HashMap<_, _>>();
+    let bytes = musli::storage::to_vec(&map).unwrap();
+
+    // The table is allocated once up front, instead of growing as entries are
+    // inserted.
+    let (decoded, n) =
+        allocations(|| musli::storage::from_slice::<HashMap<u32, u32>>(&bytes).unwrap());
+    assert_eq!(decoded, map);
+    assert_eq!(n.count, 1);
+
+    let set = (0..10_000u32).collect::<HashSet<_>>();
+    let bytes = musli::storage::to_vec(&set).unwrap();
+
+    let (decoded, n) = allocations(|| musli::storage::from_slice::<HashSet<u32>>(&bytes).unwrap());
+    assert_eq!(decoded, set);
+    assert_eq!(n.count, 1);
+}
+
+#[test]
+fn capacity_is_bounded() {
+    let lying = Lying {
+        len: usize::MAX >> 1,
+        entries: &[(1, 10), (2, 20)],
+    };
+
+    let bytes = musli::storage::to_vec(&lying).unwrap();
+
+    // The claimed length is not trusted for the up-front allocation, so
+    // decoding fails on the missing entries without allocating for them.
+    let (result, n) = allocations(|| musli::storage::from_slice::<HashMap<u32, u32>>(&bytes));
+    assert!(result.is_err());
+    assert!(
+        n.largest <= 4 << 20,
+        "largest allocation was {} bytes",
+        n.largest
+    );
+
+    // Formats without a length prefix decode every entry, and the last value
+    // for a duplicate key wins.
+    let lying = Lying {
+        len: 1,
+        entries: &[(1, 10), (2, 20), (1, 30)],
+    };
+
+    let bytes = musli::json::to_vec(&lying).unwrap();
+    let decoded: HashMap<u32, u32> = musli::json::from_slice(&bytes).unwrap();
+    assert_eq!(decoded, HashMap::from([(1, 30), (2, 20)]));
+}
@@ -0,0 +1,148 @@
+//! Test that variants with `#[musli(human_name_all)]` are named by string in
+//! human-readable formats and by index everywhere else.
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(human_name_all = "snake_case")]
+enum Color {
+    Red,
+    DarkGreen,
+    Blue,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(human_name_all = "name")]
+enum Shape {
+    Circle { radius: u32 },
+    Square { side: u32 },
+    Point,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(human_name_all = "SCREAMING_SNAKE_CASE")]
+enum Event {
+    KeyDown(u32),
+    KeyUp(u32),
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(human_name_all = "kebab-case")]
+enum Level {
+    Low,
+    #[musli(default)]
+    Unknown,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", tag = "type")]
+enum Paint {
+    Solid { color: Color },
+    Layered { colors: Vec<Color>, level: Level },
+}
+
+#[test]
+fn json_uses_names() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        musli::json::to_string(&Color::DarkGreen)?,
+        r#""dark_green""#
+    );
+    assert_eq!(
+        musli::json::from_str::<Color>(r#""dark_green""#)?,
+        Color::DarkGreen
+    );
+
+    assert_eq!(
+        musli::json::to_string(&Shape::Square { side: 2 })?,
+        r#"{"Square":{"side":2}}"#
+    );
+    assert_eq!(
+        musli::json::from_str::<Shape>(r#"{"Square":{"side":2}}"#)?,
+        Shape::Square { side: 2 }
+    );
+    assert_eq!(musli::json::to_string(&Shape::Point)?, r#"{"Point":{}}"#);
+
+    assert_eq!(
+        musli::json::to_string(&Event::KeyUp(7))?,
+        r#"{"KEY_UP":{"0":7}}"#
+    );
+    assert_eq!(
+        musli::json::from_str::<Event>(r#"{"KEY_DOWN":{"0":7}}"#)?,
+        Event::KeyDown(7)
+    );
+
+    assert_eq!(
+        musli::json::from_str::<Level>(r#""something-else""#)?,
+        Level::Unknown
+    );
+    Ok(())
+}
+
+#[test]
+fn json_nested_in_tagged() -> Result<(), Box<dyn std::error::Error>> {
+    let paint = Paint::Solid {
+        color: Color::DarkGreen,
+    };
+
+    let json = musli::json::to_string(&paint)?;
+    assert_eq!(json, r#"{"type":"Solid","color":"dark_green"}"#);
+    assert_eq!(musli::json::from_str::<Paint>(&json)?, paint);
+
+    let paint = Paint::Layered {
+        colors: vec![Color::Red, Color::Blue],
+        level: Level::Low,
+    };
+
+    let json = musli::json::to_string(&paint)?;
+    assert_eq!(
+        json,
+        r#"{"type":"Layered","colors":["red","blue"],"level":"low"}"#
+    );
+    assert_eq!(musli::json::from_str::<Paint>(&json)?, paint);
+    Ok(())
+}
+
+#[test]
+fn binary_uses_indexes() -> Result<(), Box<dyn std::error::Error>> {
+    assert_eq!(
+        musli::storage::to_vec(&Color::DarkGreen)?,
+        musli::storage::to_vec(&1usize)?
+    );
+    assert_eq!(
+        musli::descriptive::to_vec(&Color::DarkGreen)?,
+        musli::descriptive::to_vec(&1usize)?
+    );
+
+    for color in [Color::Red, Color::DarkGreen, Color::Blue] {
+        let bytes = musli::wire::to_vec(&color)?;
+        assert_eq!(musli::wire::from_slice::<Color>(&bytes)?, color);
+    }
+
+    for event in [Event::KeyDown(1), Event::KeyUp(2)] {
+        let bytes = musli::wire::to_vec(&event)?;
+        assert_eq!(musli::wire::from_slice::<Event>(&bytes)?, event);
+    }
+
+    for shape in [
+        Shape::Circle { radius: 1 },
+        Shape::Square { side: 2 },
+        Shape::Point,
+    ] {
+        let bytes = musli::descriptive::to_vec(&shape)?;
+        assert_eq!(musli::descriptive::from_slice::<Shape>(&bytes)?, shape);
+
+        let bytes = musli::storage::to_vec(&shape)?;
+        assert_eq!(musli::storage::from_slice::<Shape>(&bytes)?, shape);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn json_unknown_name() {
+    let error = musli::json::from_str::<Color>(r#""green""#).unwrap_err();
+    assert!(error.to_string().contains("\"green\""), "{error}");
+
+    let error = musli::json::from_str::<Shape>(r#"{"Triangle":{}}"#).unwrap_err();
+    assert!(error.to_string().contains("\"Triangle\""), "{error}");
+}
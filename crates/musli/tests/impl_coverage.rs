@@ -0,0 +1,183 @@
+//! Snapshot of which standard library types implement [`Encode`] and
+//! [`Decode`], and round-trip tests for the less common ones.
+//!
+//! Raw pointers and `MaybeUninit<T>` are intentionally not supported, since
+//! there is no meaningful way to encode what they point to or whether they are
+//! initialized.
+
+#![cfg(feature = "test")]
+
+use std::cell::{Cell, RefCell};
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::ffi::{CString, OsString};
+use std::marker::{PhantomData, PhantomPinned};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{NonZeroU32, Saturating, Wrapping};
+use std::ops::{Bound, Range, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::atomic::AtomicU32;
+use std::sync::Arc;
+
+use musli::alloc::System;
+use musli::mode::{Binary, Text};
+use musli::{Decode, Encode};
+
+fn assert_encode<T>()
+where
+    T: ?Sized + Encode<Binary> + Encode<Text>,
+{
+}
+
+fn assert_decode<T>()
+where
+    T: for<'de> Decode<'de, Binary, System> + for<'de> Decode<'de, Text, System>,
+{
+}
+
+macro_rules! assert_impls {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            assert_encode::<$ty>();
+            assert_decode::<$ty>();
+        )*
+    };
+}
+
+#[test]
+fn coverage() {
+    assert_impls! {
+        (),
+        bool,
+        char,
+        u8, u16, u32, u64, u128, usize,
+        i8, i16, i32, i64, i128, isize,
+        f32, f64,
+        NonZeroU32,
+        AtomicU32,
+        Wrapping<u32>,
+        Saturating<u32>,
+        Reverse<u32>,
+        PhantomData<u32>,
+        PhantomPinned,
+        Cell<u32>,
+        RefCell<String>,
+        Option<u32>,
+        Result<u32, String>,
+        Bound<u32>,
+        Range<u32>,
+        RangeFrom<u32>,
+        RangeFull,
+        RangeInclusive<u32>,
+        RangeTo<u32>,
+        RangeToInclusive<u32>,
+        [u32; 4],
+        (u32, String),
+        String,
+        CString,
+        OsString,
+        PathBuf,
+        Box<u32>,
+        Box<str>,
+        Box<[u32]>,
+        Rc<u32>,
+        Arc<u32>,
+        Vec<u32>,
+        VecDeque<u32>,
+        BinaryHeap<u32>,
+        BTreeSet<u32>,
+        BTreeMap<u32, String>,
+        HashSet<u32>,
+        HashMap<u32, String>,
+        IpAddr,
+        Ipv4Addr,
+        Ipv6Addr,
+        SocketAddr,
+        SocketAddrV4,
+        SocketAddrV6,
+    };
+
+    assert_encode::<str>();
+    assert_encode::<[u32]>();
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Wrappers {
+    saturating: Saturating<u32>,
+    reverse: Reverse<String>,
+    pinned: PhantomPinned,
+    cell: Cell<u32>,
+    ref_cell: RefCell<Vec<String>>,
+}
+
+#[test]
+fn wrappers() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Wrappers {
+            saturating: Saturating(u32::MAX),
+            reverse: Reverse(String::from("hello")),
+            pinned: PhantomPinned,
+            cell: Cell::new(42),
+            ref_cell: RefCell::new(vec![String::from("a"), String::from("b")]),
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Bounds {
+    included: Bound<u32>,
+    excluded: Bound<String>,
+    unbounded: Bound<u64>,
+}
+
+#[test]
+fn bounds() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Bounds {
+            included: Bound::Included(10),
+            excluded: Bound::Excluded(String::from("end")),
+            unbounded: Bound::Unbounded,
+        }
+    );
+}
+
+#[test]
+fn binary_heap() {
+    let heap = BinaryHeap::from([5u32, 1, 8, 3]);
+    let bytes = musli::storage::to_vec(&heap).unwrap();
+    let decoded: BinaryHeap<u32> = musli::storage::from_slice(&bytes).unwrap();
+    assert_eq!(decoded.into_sorted_vec(), [1, 3, 5, 8]);
+
+    // A heap is encoded like any other sequence.
+    let mut values: Vec<u32> = musli::storage::from_slice(&bytes).unwrap();
+    values.sort();
+    assert_eq!(values, [1, 3, 5, 8]);
+}
+
+#[test]
+fn binary_heap_any_order() {
+    // Decoding doesn't rely on the elements being encoded in heap order.
+    for values in [[1u32, 3, 5, 8, 8], [8, 8, 5, 3, 1], [5, 1, 8, 3, 8]] {
+        let bytes = musli::storage::to_vec(&values).unwrap();
+        let mut heap: BinaryHeap<u32> = musli::storage::from_slice(&bytes).unwrap();
+        assert_eq!(heap.peek(), Some(&8));
+
+        let mut popped = Vec::new();
+
+        while let Some(value) = heap.pop() {
+            popped.push(value);
+        }
+
+        assert_eq!(popped, [8, 8, 5, 3, 1]);
+    }
+}
+
+#[test]
+fn ref_cell_mutably_borrowed() {
+    let cell = RefCell::new(42u32);
+    let _guard = cell.borrow_mut();
+    assert!(musli::storage::to_vec(&cell).is_err());
+}
@@ -0,0 +1,112 @@
+//! Test that `IndexMap` and `IndexSet` are encoded in iteration order, and that
+//! decoding them preserves the encoded order.
+
+#![cfg(all(feature = "test", feature = "indexmap"))]
+
+use indexmap::{IndexMap, IndexSet};
+use musli::alloc::System;
+use musli::value::Value;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Config {
+    settings: IndexMap<String, u32>,
+    tags: IndexSet<String>,
+}
+
+fn config() -> Config {
+    Config {
+        settings: IndexMap::from([
+            (String::from("zeta"), 1),
+            (String::from("alpha"), 2),
+            (String::from("mid"), 3),
+        ]),
+        tags: IndexSet::from([
+            String::from("second"),
+            String::from("first"),
+            String::from("third"),
+        ]),
+    }
+}
+
+fn assert_order(config: &Config) {
+    assert!(config.settings.keys().eq(["zeta", "alpha", "mid"]));
+    assert!(config.tags.iter().eq(["second", "first", "third"]));
+}
+
+#[test]
+fn roundtrip() {
+    let config = musli::macros::assert_roundtrip_eq!(full, config());
+    assert_order(&config);
+}
+
+#[test]
+fn decode_preserves_order() -> Result<(), Box<dyn std::error::Error>> {
+    macro_rules! test_format {
+        ($format:ident) => {{
+            let bytes = musli::$format::to_vec(&config())?;
+            assert_order(&musli::$format::from_slice::<Config>(&bytes)?);
+        }};
+    }
+
+    test_format!(storage);
+    test_format!(wire);
+    test_format!(descriptive);
+    test_format!(json);
+    Ok(())
+}
+
+#[test]
+fn json_iteration_order() -> Result<(), Box<dyn std::error::Error>> {
+    let json = musli::json::to_string(&config())?;
+
+    assert_eq!(
+        json,
+        r#"{"settings":{"zeta":1,"alpha":2,"mid":3},"tags":["second","first","third"]}"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn descriptive_iteration_order() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&config().settings)?;
+    let value: Value<System> = musli::descriptive::from_slice(&bytes)?;
+
+    let Value::Map(entries) = value else {
+        panic!("expected a map, got {value:?}");
+    };
+
+    let keys = entries
+        .iter()
+        .map(|(key, _)| match key {
+            Value::String(key) => key.as_str(),
+            key => panic!("expected a string key, got {key:?}"),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(keys, ["zeta", "alpha", "mid"]);
+    Ok(())
+}
+
+#[test]
+fn duplicate_keys() -> Result<(), Box<dyn std::error::Error>> {
+    const STRICT: musli::json::Encoding = musli::json::Encoding::new().with_reject_duplicate_keys();
+
+    let input = r#"{"b":1,"a":2,"b":3}"#;
+
+    // The last value wins, but the key keeps the position where it was first
+    // seen, which is the same as inserting into an `IndexMap`.
+    let map: IndexMap<String, u32> = musli::json::from_str(input)?;
+    assert_eq!(map.len(), 2);
+    assert!(map
+        .iter()
+        .eq([(&String::from("b"), &3), (&String::from("a"), &2)]));
+
+    let error = STRICT.from_str::<IndexMap<String, u32>>(input).unwrap_err();
+    assert!(error.to_string().contains("Duplicate key"), "{error}");
+
+    let set: IndexSet<u32> = musli::json::from_str("[3,1,3,2]")?;
+    assert!(set.iter().eq(&[3, 1, 2]));
+    Ok(())
+}
@@ -0,0 +1,116 @@
+//! Test that decoded strings are interned when the context is configured to do
+//! so, and that interning is inert otherwise.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use musli::context;
+use musli::{Decode, Encode};
+
+#[path = "common/alloc.rs"]
+mod alloc;
+
+use self::alloc::allocations;
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Line {
+    level: Arc<str>,
+    target: Arc<str>,
+    message: String,
+}
+
+fn line(level: &str, message: &str) -> Line {
+    Line {
+        level: Arc::from(level),
+        target: Arc::from("server"),
+        message: String::from(message),
+    }
+}
+
+#[test]
+fn interned_fields() {
+    let lines = vec![
+        line("info", "started"),
+        line("warning", "slow"),
+        line("info", "stopped"),
+    ];
+
+    let bytes = musli::wire::to_vec(&lines).unwrap();
+
+    let cx = context::new().with_interning(1024);
+    let decoded: Vec<Line> = musli::wire::Encoding::new()
+        .from_slice_with(&cx, &bytes)
+        .unwrap();
+
+    assert_eq!(decoded, lines);
+    assert!(Arc::ptr_eq(&decoded[0].level, &decoded[2].level));
+    assert!(!Arc::ptr_eq(&decoded[0].level, &decoded[1].level));
+    assert!(Arc::ptr_eq(&decoded[0].target, &decoded[1].target));
+    assert!(Arc::ptr_eq(&decoded[1].target, &decoded[2].target));
+}
+
+#[test]
+fn inert_by_default() {
+    let bytes = musli::wire::to_vec(&["info", "info"]).unwrap();
+
+    let decoded: Vec<Arc<str>> = musli::wire::from_slice(&bytes).unwrap();
+    assert!(!Arc::ptr_eq(&decoded[0], &decoded[1]));
+}
+
+#[test]
+fn rc_and_arc() {
+    let bytes = musli::wire::to_vec(&("info", "info", "info", "info")).unwrap();
+
+    let cx = context::new().with_interning(1024);
+    let (a, b, c, d): (Arc<str>, Rc<str>, Arc<str>, Rc<str>) = musli::wire::Encoding::new()
+        .from_slice_with(&cx, &bytes)
+        .unwrap();
+
+    assert!(Arc::ptr_eq(&a, &c));
+    // The string is interned by the first type it's decoded as.
+    assert!(!Rc::ptr_eq(&b, &d));
+    assert_eq!(&*b, "info");
+}
+
+#[test]
+fn max_entries() {
+    let bytes = musli::wire::to_vec(&["a", "b", "a", "b"]).unwrap();
+
+    let cx = context::new().with_interning(1);
+    let decoded: Vec<Arc<str>> = musli::wire::Encoding::new()
+        .from_slice_with(&cx, &bytes)
+        .unwrap();
+
+    assert!(Arc::ptr_eq(&decoded[0], &decoded[2]));
+    assert!(!Arc::ptr_eq(&decoded[1], &decoded[3]));
+}
+
+#[test]
+fn bounded_memory() {
+    const REPEATS: usize = 100_000;
+
+    let labels = (0..REPEATS)
+        .map(|n| ["debug", "info", "warning", "error"][n % 4])
+        .collect::<Vec<_>>();
+
+    let bytes = musli::wire::to_vec(&labels).unwrap();
+
+    let (plain, plain_allocations) =
+        allocations(|| musli::wire::from_slice::<Vec<Arc<str>>>(&bytes).unwrap());
+
+    let cx = context::new().with_interning(1024);
+
+    let (interned, interned_allocations) = allocations(|| {
+        musli::wire::Encoding::new()
+            .from_slice_with::<_, Vec<Arc<str>>>(&cx, &bytes)
+            .unwrap()
+    });
+
+    assert_eq!(plain, interned);
+    assert!(plain_allocations.count >= REPEATS, "{plain_allocations:?}");
+    assert!(interned_allocations.count < 64, "{interned_allocations:?}");
+
+    for (index, value) in interned.iter().enumerate().skip(4) {
+        assert!(Arc::ptr_eq(value, &interned[index % 4]));
+    }
+}
@@ -0,0 +1,165 @@
+//! Test that `#[musli(into = ..)]`, `#[musli(from = ..)]` and
+//! `#[musli(try_from = ..)]` encode and decode a type through another
+//! representation.
+
+#![cfg(feature = "test")]
+
+use std::fmt;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(into = String, try_from = String)]
+pub struct Email {
+    user: String,
+    domain: String,
+}
+
+impl From<Email> for String {
+    fn from(email: Email) -> Self {
+        format!("{}@{}", email.user, email.domain)
+    }
+}
+
+#[derive(Debug)]
+pub struct InvalidEmail;
+
+impl fmt::Display for InvalidEmail {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing `@`")
+    }
+}
+
+impl TryFrom<String> for Email {
+    type Error = InvalidEmail;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let Some((user, domain)) = value.split_once('@') else {
+            return Err(InvalidEmail);
+        };
+
+        Ok(Self {
+            user: user.to_owned(),
+            domain: domain.to_owned(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(into = Vec<T>, from = Vec<T>)]
+pub struct Stack<T> {
+    items: Vec<T>,
+}
+
+impl<T> From<Stack<T>> for Vec<T> {
+    fn from(stack: Stack<T>) -> Self {
+        stack.items
+    }
+}
+
+impl<T> From<Vec<T>> for Stack<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self { items }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct Contact {
+    email: Email,
+    history: Stack<u32>,
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Contact {
+            email: Email {
+                user: String::from("aristotle"),
+                domain: String::from("lyceum.gr"),
+            },
+            history: Stack {
+                items: vec![1, 2, 3],
+            },
+        }
+    );
+}
+
+#[test]
+fn encoded_as_repr() {
+    let email = Email {
+        user: String::from("plato"),
+        domain: String::from("academy.gr"),
+    };
+
+    let json = musli::json::to_string(&email).unwrap();
+    assert_eq!(json, r#""plato@academy.gr""#);
+
+    let stack = Stack {
+        items: vec![1u32, 2, 3],
+    };
+
+    let json = musli::json::to_string(&stack).unwrap();
+    assert_eq!(json, "[1,2,3]");
+}
+
+#[test]
+fn try_from_failure() {
+    let bytes = musli::storage::to_vec(&String::from("not an email")).unwrap();
+    let error = musli::storage::from_slice::<Email>(&bytes).unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Type Email could not be converted from String: missing `@`"
+    );
+
+    let error =
+        musli::json::from_str::<Contact>(r#"{"email":"socrates","history":[]}"#).unwrap_err();
+
+    assert!(
+        error
+            .to_string()
+            .contains("Type Email could not be converted from String: missing `@`"),
+        "{error}"
+    );
+}
+
+/// Only the text mode is encoded through a conversion.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(mode = Text, into = String, try_from = String)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl From<Version> for String {
+    fn from(version: Version) -> Self {
+        format!("{}.{}", version.major, version.minor)
+    }
+}
+
+impl TryFrom<String> for Version {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (major, minor) = value.split_once('.').ok_or("missing `.`")?;
+
+        Ok(Self {
+            major: major.parse().map_err(|_| "bad major version")?,
+            minor: minor.parse().map_err(|_| "bad minor version")?,
+        })
+    }
+}
+
+#[test]
+fn modes() {
+    let version = Version { major: 1, minor: 2 };
+
+    let json = musli::json::to_string(&version).unwrap();
+    assert_eq!(json, r#""1.2""#);
+    assert_eq!(musli::json::from_str::<Version>(&json).unwrap(), version);
+
+    let bytes = musli::wire::to_vec(&version).unwrap();
+    assert_ne!(bytes, musli::wire::to_vec(&String::from("1.2")).unwrap());
+    assert_eq!(musli::wire::from_slice::<Version>(&bytes).unwrap(), version);
+}
@@ -0,0 +1,121 @@
+//! Test appending encoded JSON directly onto an existing `String`.
+
+use musli::{Context, Decode, Encode, Encoder};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+/// A value which fails to encode.
+struct Fails;
+
+impl<M> Encode<M> for Fails {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        Err(encoder.cx().message("Cannot encode"))
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[test]
+fn append_documents() {
+    let people = [
+        Person {
+            name: String::from("Aristotle"),
+            age: 61,
+        },
+        Person {
+            name: String::from("Sokrates \"the ☕ drinker\"\n"),
+            age: 70,
+        },
+    ];
+
+    let mut lines = String::new();
+
+    for person in &people {
+        musli::json::Encoding::new()
+            .append_to_string(&mut lines, person)
+            .unwrap();
+        lines.push('\n');
+    }
+
+    let decoded = lines
+        .lines()
+        .map(|line| musli::json::from_str::<Person>(line).unwrap())
+        .collect::<Vec<_>>();
+
+    assert_eq!(decoded, people);
+}
+
+#[test]
+fn to_string_matches_append() {
+    let person = Person {
+        name: String::from("Plato"),
+        age: 80,
+    };
+
+    let mut string = String::from("prefix ");
+
+    musli::json::Encoding::new()
+        .append_to_string(&mut string, &person)
+        .unwrap();
+
+    assert_eq!(
+        string.strip_prefix("prefix ").unwrap(),
+        musli::json::to_string(&person).unwrap()
+    );
+}
+
+#[test]
+fn error_leaves_string_unmodified() {
+    let mut string = String::from("[\"ok\"]");
+
+    let result = musli::json::Encoding::new().append_to_string(&mut string, &(1u32, 2u32, Fails));
+
+    assert!(result.is_err());
+    assert_eq!(string, "[\"ok\"]");
+}
+
+#[test]
+fn string_writer() {
+    let person = Person {
+        name: String::from("Sokrates \"the ☕ drinker\""),
+        age: 70,
+    };
+
+    let mut string = String::from("prefix ");
+    musli::json::encode(&mut string, &person).unwrap();
+
+    assert_eq!(
+        string.strip_prefix("prefix ").unwrap(),
+        musli::json::to_string(&person).unwrap()
+    );
+}
+
+#[test]
+fn string_writer_rejects_invalid_utf8() {
+    let cx = musli::context::new().with_error::<std::io::Error>();
+
+    for value in [&[0xffu8][..], &[b'a', 0xff, b'b'][..]] {
+        let mut string = String::new();
+
+        let error = musli::storage::Encoding::new()
+            .encode_with(&cx, &mut string, value)
+            .unwrap_err();
+
+        assert!(error
+            .get_ref()
+            .is_some_and(|e| e.downcast_ref::<std::str::Utf8Error>().is_some()));
+    }
+}
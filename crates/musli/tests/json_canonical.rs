@@ -0,0 +1,156 @@
+//! Test canonical JSON output following RFC 8785.
+#![cfg(feature = "json")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::compat::MapPairs;
+use musli::json::Encoding;
+use musli::{Decode, Encode};
+
+const CANONICAL: Encoding = Encoding::new().with_canonical();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Document {
+    numbers: Vec<f64>,
+    string: String,
+    literals: (bool, bool, Option<u32>),
+    integers: Vec<i128>,
+    map: HashMap<String, u32>,
+}
+
+// Some of the values are test vectors from RFC 8785, which are intentionally
+// written with more precision than an `f64` holds.
+#[test]
+#[allow(clippy::excessive_precision)]
+fn numbers() {
+    let cases: &[(f64, &str)] = &[
+        (0.0, "0"),
+        (-0.0, "0"),
+        (1.0, "1"),
+        (-1.5, "-1.5"),
+        (4.50, "4.5"),
+        (2e-3, "0.002"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (1.5e-7, "1.5e-7"),
+        (333333333.33333329, "333333333.3333333"),
+        (1e20, "100000000000000000000"),
+        (1e21, "1e+21"),
+        (1.23e22, "1.23e+22"),
+        (1e30, "1e+30"),
+        (0.000000000000000000000000001, "1e-27"),
+        (9007199254740992.0, "9007199254740992"),
+        (f64::MAX, "1.7976931348623157e+308"),
+        (f64::MIN_POSITIVE, "2.2250738585072014e-308"),
+        (5e-324, "5e-324"),
+    ];
+
+    for &(value, expected) in cases {
+        let out = CANONICAL.to_string(&value).unwrap();
+        assert_eq!(out, expected, "{value:?}");
+    }
+
+    let out = CANONICAL.to_string(&0.1f32).unwrap();
+    assert_eq!(out, "0.1");
+
+    assert!(CANONICAL.to_string(&f64::NAN).is_err());
+    assert!(CANONICAL.to_string(&f64::INFINITY).is_err());
+}
+
+#[test]
+fn integers() {
+    let out = CANONICAL
+        .to_string(&[0i128, -1, u64::MAX as i128, i128::MIN])
+        .unwrap();
+    assert_eq!(
+        out,
+        "[0,-1,18446744073709551615,-170141183460469231731687303715884105728]"
+    );
+}
+
+#[test]
+fn strings() {
+    let out = CANONICAL
+        .to_string("\u{1}\u{8}\t\n\u{c}\r\u{1f}\"\\/\u{7f}\u{80}€😀")
+        .unwrap();
+    assert_eq!(
+        out,
+        "\"\\u0001\\b\\t\\n\\f\\r\\u001f\\\"\\\\/\u{7f}\u{80}€😀\""
+    );
+}
+
+#[test]
+fn sorted_keys() {
+    // Example from section 3.2.3 of RFC 8785.
+    let map = BTreeMap::from([
+        (String::from("\u{20ac}"), "Euro Sign"),
+        (String::from("\r"), "Carriage Return"),
+        (String::from("\u{fb33}"), "Hebrew Letter Dalet With Dagesh"),
+        (String::from("1"), "One"),
+        (String::from("\u{1f600}"), "Emoji: Grinning Face"),
+        (String::from("\u{80}"), "Control"),
+        (
+            String::from("\u{f6}"),
+            "Latin Small Letter O With Diaeresis",
+        ),
+    ]);
+
+    let out = CANONICAL.to_string(&map).unwrap();
+
+    assert_eq!(
+        out,
+        concat!(
+            "{\"\\r\":\"Carriage Return\",",
+            "\"1\":\"One\",",
+            "\"\u{80}\":\"Control\",",
+            "\"\u{f6}\":\"Latin Small Letter O With Diaeresis\",",
+            "\"\u{20ac}\":\"Euro Sign\",",
+            "\"\u{1f600}\":\"Emoji: Grinning Face\",",
+            "\"\u{fb33}\":\"Hebrew Letter Dalet With Dagesh\"}",
+        )
+    );
+}
+
+#[test]
+fn deterministic() {
+    let document = Document {
+        numbers: vec![1e21, 0.5, -0.0],
+        string: String::from("Hello\nWorld"),
+        literals: (true, false, None),
+        integers: vec![i128::MAX],
+        map: (0..32).map(|n| (n.to_string(), n)).collect(),
+    };
+
+    let out = CANONICAL.to_string(&document).unwrap();
+
+    for _ in 0..8 {
+        let mut map = HashMap::new();
+        map.extend(document.map.iter().map(|(k, v)| (k.clone(), *v)));
+
+        let other = Document {
+            map,
+            numbers: document.numbers.clone(),
+            string: document.string.clone(),
+            literals: document.literals,
+            integers: document.integers.clone(),
+        };
+
+        assert_eq!(CANONICAL.to_string(&other).unwrap(), out);
+    }
+
+    assert!(out.starts_with(r#"{"integers":[170141183460469231731687303715884105727],"literals":[true,false,null],"map":{"0":0,"1":1,"10":10,"#));
+    assert!(out.ends_with(r#""numbers":[1e+21,0.5,0],"string":"Hello\nWorld"}"#));
+
+    let decoded: Document = CANONICAL.from_str(&out).unwrap();
+    assert_eq!(decoded, document);
+
+    assert_eq!(CANONICAL.to_vec(&document).unwrap(), out.as_bytes());
+}
+
+#[test]
+fn duplicate_keys() {
+    let pairs = [("a", 1), ("a", 2)];
+    let pairs = MapPairs::new(pairs.iter().map(|(k, v)| (k, v)));
+    assert!(CANONICAL.to_string(&pairs).is_err());
+    assert_eq!(musli::json::to_string(&pairs).unwrap(), r#"{"a":1,"a":2}"#);
+}
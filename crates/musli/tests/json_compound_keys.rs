@@ -0,0 +1,118 @@
+//! Test that JSON encodes maps whose keys can't be used as object keys as an
+//! array of `[key, value]` pairs.
+
+#![cfg(feature = "test")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode)]
+#[musli(packed)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Grid {
+    cells: BTreeMap<Point, String>,
+    weights: BTreeMap<u32, f32>,
+}
+
+#[test]
+fn tuple_keys() {
+    let map = BTreeMap::from([((1u32, 2u32), true), ((3, 4), false)]);
+
+    let json = musli::json::to_string(&map).unwrap();
+    assert_eq!(json, "[[[1,2],true],[[3,4],false]]");
+    assert_eq!(
+        musli::json::from_str::<BTreeMap<(u32, u32), bool>>(&json).unwrap(),
+        map
+    );
+
+    let map = HashMap::from([((1u32, 2u32), String::from("a"))]);
+
+    let json = musli::json::to_string(&map).unwrap();
+    assert_eq!(json, r#"[[[1,2],"a"]]"#);
+    assert_eq!(
+        musli::json::from_str::<HashMap<(u32, u32), String>>(&json).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn scalar_keys() {
+    let map = BTreeMap::from([(false, 1u32), (true, 2)]);
+    let json = musli::json::to_string(&map).unwrap();
+    assert_eq!(json, "[[false,1],[true,2]]");
+    assert_eq!(
+        musli::json::from_str::<BTreeMap<bool, u32>>(&json).unwrap(),
+        map
+    );
+
+    let map = BTreeMap::from([(Some(1u32), 1u32), (None, 2)]);
+    let json = musli::json::to_string(&map).unwrap();
+    assert_eq!(json, "[[null,2],[1,1]]");
+    assert_eq!(
+        musli::json::from_str::<BTreeMap<Option<u32>, u32>>(&json).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn nested() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Grid {
+            cells: BTreeMap::from([
+                (Point { x: 0, y: 1 }, String::from("a")),
+                (Point { x: 2, y: 3 }, String::from("b")),
+            ]),
+            weights: BTreeMap::from([(1, 0.5)]),
+        },
+        json = r#"{"cells":[[[0,1],"a"],[[2,3],"b"]],"weights":{"1":0.5}}"#
+    );
+}
+
+#[test]
+fn object_keys_unchanged() {
+    let map = BTreeMap::from([(String::from("a"), 1u32), (String::from("b"), 2)]);
+    assert_eq!(musli::json::to_string(&map).unwrap(), r#"{"a":1,"b":2}"#);
+
+    let map = BTreeMap::from([(1u32, 1u32), (2, 2)]);
+    assert_eq!(musli::json::to_string(&map).unwrap(), r#"{"1":1,"2":2}"#);
+
+    let map = BTreeMap::<(u32, u32), u32>::new();
+    let json = musli::json::to_string(&map).unwrap();
+    assert_eq!(json, "{}");
+    assert_eq!(
+        musli::json::from_str::<BTreeMap<(u32, u32), u32>>(&json).unwrap(),
+        map
+    );
+}
+
+#[test]
+fn decode_either_layout() {
+    let expected = BTreeMap::from([(1u32, 10u32), (2, 20)]);
+
+    let object: BTreeMap<u32, u32> = musli::json::from_str(r#"{"1":10,"2":20}"#).unwrap();
+    assert_eq!(object, expected);
+
+    let pairs: BTreeMap<u32, u32> = musli::json::from_str("[[1,10],[2,20]]").unwrap();
+    assert_eq!(pairs, expected);
+
+    let empty: BTreeMap<(u32, u32), u32> = musli::json::from_str("[]").unwrap();
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn malformed_pairs() {
+    type Map = BTreeMap<(u32, u32), u32>;
+
+    assert!(musli::json::from_str::<Map>("[[[1,2]]]").is_err());
+    assert!(musli::json::from_str::<Map>("[[[1,2],3,4]]").is_err());
+    assert!(musli::json::from_str::<Map>("[[[1,2]:3]]").is_err());
+    assert!(musli::json::from_str::<Map>("[[[1,2],3]").is_err());
+    assert!(musli::json::from_str::<Map>(r#"{[1,2]:3}"#).is_err());
+}
@@ -0,0 +1,148 @@
+//! Test the formatting of floating point numbers in JSON.
+#![cfg(feature = "json")]
+
+use musli::json::{self, Encoding, FloatFormat};
+
+/// A simple xorshift generator so that the sampled values are the same on
+/// every run.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+#[test]
+fn shortest_roundtrips_f64() {
+    let mut values = vec![
+        0.0,
+        -0.0,
+        1.0,
+        0.1,
+        1.0 / 3.0,
+        f64::MAX,
+        f64::MIN,
+        f64::MIN_POSITIVE,
+        f64::EPSILON,
+        f64::from_bits(1),
+        f64::from_bits(0x000f_ffff_ffff_ffff),
+        9007199254740993.0,
+    ];
+
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+    values.extend((0..10000).map(|_| f64::from_bits(rng.next())));
+
+    for value in values.into_iter().filter(|v| v.is_finite()) {
+        let out = json::to_string(&value).unwrap();
+        let actual: f64 = json::from_str(&out).unwrap();
+        assert_eq!(actual.to_bits(), value.to_bits(), "{value:?} as {out}");
+    }
+}
+
+#[test]
+fn shortest_roundtrips_f32() {
+    let mut values = vec![
+        0.0,
+        -0.0,
+        1.0,
+        0.1,
+        1.0 / 3.0,
+        f32::MAX,
+        f32::MIN,
+        f32::MIN_POSITIVE,
+        f32::EPSILON,
+        f32::from_bits(1),
+        f32::from_bits(0x007f_ffff),
+    ];
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+    values.extend((0..10000).map(|_| f32::from_bits(rng.next() as u32)));
+
+    for value in values.into_iter().filter(|v| v.is_finite()) {
+        let out = json::to_string(&value).unwrap();
+        let actual: f32 = json::from_str(&out).unwrap();
+        assert_eq!(actual.to_bits(), value.to_bits(), "{value:?} as {out}");
+    }
+}
+
+#[test]
+fn shortest_output() {
+    let cases: &[(f64, &str)] = &[
+        (0.0, "0.0"),
+        (-0.0, "-0.0"),
+        (0.1, "0.1"),
+        (100.0, "100.0"),
+        (1e21, "1e21"),
+        (1e-7, "1e-7"),
+        (f64::MAX, "1.7976931348623157e308"),
+        (f64::from_bits(1), "5e-324"),
+    ];
+
+    for &(value, expected) in cases {
+        assert_eq!(json::to_string(&value).unwrap(), expected, "{value:?}");
+    }
+}
+
+#[test]
+fn fixed_output() {
+    const FIXED: Encoding = Encoding::new().with_float_format(FloatFormat::Fixed(3));
+    const INTEGER: Encoding = Encoding::new().with_float_format(FloatFormat::Fixed(0));
+
+    let cases: &[(f64, &str, &str)] = &[
+        (0.0, "0.000", "0"),
+        (-0.0, "-0.000", "-0"),
+        (1.0 / 3.0, "0.333", "0"),
+        (2.0 / 3.0, "0.667", "1"),
+        (-12.5, "-12.500", "-12"),
+        (1e6, "1000000.000", "1000000"),
+    ];
+
+    for &(value, fixed, integer) in cases {
+        assert_eq!(FIXED.to_string(&value).unwrap(), fixed, "{value:?}");
+        assert_eq!(INTEGER.to_string(&value).unwrap(), integer, "{value:?}");
+        assert_eq!(
+            FIXED.to_string(&(value as f32)).unwrap(),
+            fixed,
+            "{value:?}"
+        );
+    }
+
+    assert_eq!(
+        FIXED.to_string(&vec![0.5f64, 0.25]).unwrap(),
+        "[0.500,0.250]"
+    );
+
+    let value: f64 = FIXED
+        .from_str(&FIXED.to_string(&0.125f64).unwrap())
+        .unwrap();
+    assert_eq!(value, 0.125);
+}
+
+#[test]
+fn fixed_f32_matches_std() {
+    const FIXED: Encoding = Encoding::new().with_float_format(FloatFormat::Fixed(4));
+
+    let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+
+    let values = (0..10000)
+        .map(|_| f32::from_bits(rng.next() as u32))
+        .filter(|v| v.is_finite())
+        .chain([0.125, 0.00005, 2.5e-5, f32::MAX, f32::MIN_POSITIVE]);
+
+    for value in values {
+        assert_eq!(FIXED.to_string(&value).unwrap(), format!("{value:.4}"));
+    }
+}
+
+#[test]
+fn canonical_overrides_fixed() {
+    const CANONICAL: Encoding = Encoding::new()
+        .with_float_format(FloatFormat::Fixed(3))
+        .with_canonical();
+
+    assert_eq!(CANONICAL.to_string(&[0.5f64, 1.0]).unwrap(), "[0.5,1]");
+}
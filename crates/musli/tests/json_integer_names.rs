@@ -0,0 +1,54 @@
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct IntegerNames {
+    #[musli(name = 0)]
+    string: String,
+    #[musli(name = 1)]
+    number: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "index")]
+struct IndexNames {
+    string: String,
+    number: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct SignedNames {
+    #[musli(name = -1)]
+    string: String,
+    #[musli(name = 1)]
+    number: u32,
+}
+
+#[test]
+fn integer_names() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        IntegerNames {
+            string: String::from("hello"),
+            number: 42
+        },
+        json = r#"{"0":"hello","1":42}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        IndexNames {
+            string: String::from("hello"),
+            number: 42
+        },
+        json = r#"{"0":"hello","1":42}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        SignedNames {
+            string: String::from("hello"),
+            number: 42
+        },
+        json = r#"{"-1":"hello","1":42}"#
+    );
+}
@@ -0,0 +1,194 @@
+//! Test that [`RawValue`] and [`RawValueBuf`] keep the exact text of a JSON
+//! value, without decoding it.
+
+#![cfg(feature = "test")]
+
+use std::fmt::Write;
+
+use musli::json::{RawValue, RawValueBuf};
+use musli::{Decode, Encode, Encoder};
+
+#[path = "common/alloc.rs"]
+mod alloc;
+
+use self::alloc::allocations;
+
+#[derive(Debug, Encode, Decode)]
+struct Envelope<'a> {
+    route: &'a str,
+    version: u32,
+    payload: RawValue<'a>,
+}
+
+#[derive(Debug, Encode, Decode)]
+struct OwnedEnvelope {
+    route: String,
+    version: u32,
+    payload: RawValueBuf,
+}
+
+#[derive(Debug, Decode)]
+struct ParsedEnvelope<'a> {
+    #[allow(unused)]
+    route: &'a str,
+    #[allow(unused)]
+    payload: Vec<Item>,
+}
+
+#[derive(Debug, Decode)]
+struct Item {
+    #[allow(unused)]
+    id: u32,
+    #[allow(unused)]
+    name: String,
+}
+
+/// A payload of about 5MB.
+fn large_payload() -> String {
+    let mut payload = String::from("[");
+
+    for n in 0..60_000 {
+        if n > 0 {
+            payload.push(',');
+        }
+
+        write!(
+            payload,
+            r#"{{"id":{n},"name":"item \"{n}\"\n","score":{n}.5e-3,"tags":["a","b",null,true],"nested":{{"x":[[],{{}}]}}}}"#
+        )
+        .unwrap();
+    }
+
+    payload.push(']');
+    payload
+}
+
+#[test]
+fn route_large_payload() {
+    let payload = large_payload();
+    assert!(payload.len() >= 5 * 1024 * 1024, "{}", payload.len());
+
+    let input = format!(r#"{{"route":"users","version":2,"payload":{payload}}}"#);
+
+    let (envelope, raw) = allocations(|| musli::json::from_str::<Envelope<'_>>(&input).unwrap());
+
+    assert_eq!(envelope.route, "users");
+    assert_eq!(envelope.version, 2);
+    assert_eq!(envelope.payload.get(), payload);
+
+    let (_, parsed) = allocations(|| musli::json::from_str::<ParsedEnvelope<'_>>(&input).unwrap());
+
+    // Capturing the payload doesn't decode it, so it doesn't allocate for any
+    // of its contents.
+    assert!(raw.count <= 2, "{} allocations", raw.count);
+    assert!(parsed.count > 60_000, "{} allocations", parsed.count);
+
+    let output = musli::json::to_string(&envelope).unwrap();
+    assert!(output == input);
+}
+
+#[test]
+fn byte_identity() {
+    let cases = [
+        r#"{"a":1,"a":2,"a":{"a":[]}}"#,
+        "123456789012345678901234567890123456789012345678901234567890",
+        "-1.0e999999",
+        "-0.000E-0",
+        r#""é😀 \/ \\ \" \b\f\n\r\t""#,
+        r#"[ 1 ,	2 ,
+        { "key" : "value" } ]"#,
+        r#"{"":{"":{"":[[[[[]]]]]}}}"#,
+        "null",
+        "true",
+        "false",
+        r#""""#,
+        "[]",
+        "{}",
+    ];
+
+    for case in cases {
+        let json = format!(r#"{{"route":"r","version":1,"payload":{case}}}"#);
+
+        let envelope = musli::json::from_str::<Envelope<'_>>(&json).unwrap();
+        assert_eq!(envelope.payload.get(), case);
+        assert_eq!(musli::json::to_string(&envelope).unwrap(), json);
+
+        let envelope = musli::json::from_slice::<OwnedEnvelope>(json.as_bytes()).unwrap();
+        assert_eq!(envelope.payload.get(), case);
+        assert_eq!(musli::json::to_string(&envelope).unwrap(), json);
+    }
+}
+
+#[test]
+fn surrounding_whitespace() {
+    let value =
+        musli::json::from_str::<Vec<RawValue<'_>>>(" [ 1 , {\"a\" : 2}\n,\t[ ] ] ").unwrap();
+    let values = value.iter().map(RawValue::get).collect::<Vec<_>>();
+    assert_eq!(values, ["1", "{\"a\" : 2}", "[ ]"]);
+    assert_eq!(
+        musli::json::to_string(&value).unwrap(),
+        "[1,{\"a\" : 2},[ ]]"
+    );
+}
+
+#[test]
+fn malformed() {
+    let cases = [
+        "[1,2}",
+        "{\"a\":1]",
+        "[1,2",
+        "{\"a\"",
+        "\"unterminated",
+        "tru",
+        "{\"a\" 1}",
+        "}",
+    ];
+
+    for case in cases {
+        let json = format!(r#"{{"route":"r","version":1,"payload":{case}}}"#);
+        assert!(
+            musli::json::from_str::<Envelope<'_>>(&json).is_err(),
+            "{case}"
+        );
+    }
+}
+
+#[test]
+fn unsupported_formats() {
+    let value = musli::json::from_str::<RawValue<'_>>("[1,2,3]").unwrap();
+    assert!(musli::storage::to_vec(&value).is_err());
+
+    let bytes = musli::storage::to_vec(&[1u32, 2, 3]).unwrap();
+    assert!(musli::storage::from_slice::<RawValue<'_>>(&bytes).is_err());
+}
+
+/// Bypass [`RawValue`] to encode bytes which aren't well-formed.
+struct Unchecked(&'static str);
+
+impl<M> Encode<M> for Unchecked {
+    type Encode = Self;
+
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_raw(self.0.as_bytes())
+    }
+
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+#[test]
+#[cfg(debug_assertions)]
+fn encode_malformed() {
+    assert_eq!(
+        musli::json::to_string(&Unchecked(r#"{"a":[1,2]}"#)).unwrap(),
+        r#"{"a":[1,2]}"#
+    );
+
+    assert!(musli::json::to_string(&Unchecked("[1,")).is_err());
+    assert!(musli::json::to_string(&Unchecked("1 2")).is_err());
+    assert!(musli::json::to_string(&Unchecked("")).is_err());
+}
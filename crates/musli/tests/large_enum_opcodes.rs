@@ -0,0 +1,113 @@
+//! A generated enum with many variants, resembling a set of protocol opcodes.
+//!
+//! Many variants share an identical field layout, which the derive encodes
+//! through a shared match arm.
+
+use musli::{Decode, Encode};
+
+/// Generate the `Opcode` enum and a value for each of its variants.
+///
+/// Each group of five variants starting at opcode `n` repeats the same set of
+/// field layouts.
+macro_rules! opcodes {
+    ($($n:literal => $unit:ident, $single:ident, $pair:ident, $string:ident, $payload:ident;)*) => {
+        #[derive(Debug, Clone, PartialEq, Encode, Decode)]
+        pub enum Opcode {
+            $(
+                $unit,
+                $single { a: u32 },
+                $pair { a: u32, b: u64 },
+                $string(String),
+                $payload { id: u16, payload: Vec<u8> },
+            )*
+        }
+
+        fn opcodes() -> Vec<Opcode> {
+            vec![
+                $(
+                    Opcode::$unit,
+                    Opcode::$single { a: $n + 1 },
+                    Opcode::$pair { a: $n + 2, b: ($n + 2) * 10 },
+                    Opcode::$string(String::from(stringify!($string))),
+                    Opcode::$payload {
+                        id: $n + 4,
+                        payload: vec![($n + 4) as u8; 3],
+                    },
+                )*
+            ]
+        }
+    };
+}
+
+opcodes! {
+    0 => Op000, Op001, Op002, Op003, Op004;
+    5 => Op005, Op006, Op007, Op008, Op009;
+    10 => Op010, Op011, Op012, Op013, Op014;
+    15 => Op015, Op016, Op017, Op018, Op019;
+    20 => Op020, Op021, Op022, Op023, Op024;
+    25 => Op025, Op026, Op027, Op028, Op029;
+    30 => Op030, Op031, Op032, Op033, Op034;
+    35 => Op035, Op036, Op037, Op038, Op039;
+    40 => Op040, Op041, Op042, Op043, Op044;
+    45 => Op045, Op046, Op047, Op048, Op049;
+    50 => Op050, Op051, Op052, Op053, Op054;
+    55 => Op055, Op056, Op057, Op058, Op059;
+    60 => Op060, Op061, Op062, Op063, Op064;
+    65 => Op065, Op066, Op067, Op068, Op069;
+    70 => Op070, Op071, Op072, Op073, Op074;
+    75 => Op075, Op076, Op077, Op078, Op079;
+    80 => Op080, Op081, Op082, Op083, Op084;
+    85 => Op085, Op086, Op087, Op088, Op089;
+    90 => Op090, Op091, Op092, Op093, Op094;
+    95 => Op095, Op096, Op097, Op098, Op099;
+    100 => Op100, Op101, Op102, Op103, Op104;
+    105 => Op105, Op106, Op107, Op108, Op109;
+    110 => Op110, Op111, Op112, Op113, Op114;
+    115 => Op115, Op116, Op117, Op118, Op119;
+    120 => Op120, Op121, Op122, Op123, Op124;
+    125 => Op125, Op126, Op127, Op128, Op129;
+    130 => Op130, Op131, Op132, Op133, Op134;
+    135 => Op135, Op136, Op137, Op138, Op139;
+    140 => Op140, Op141, Op142, Op143, Op144;
+    145 => Op145, Op146, Op147, Op148, Op149;
+    150 => Op150, Op151, Op152, Op153, Op154;
+    155 => Op155, Op156, Op157, Op158, Op159;
+    160 => Op160, Op161, Op162, Op163, Op164;
+    165 => Op165, Op166, Op167, Op168, Op169;
+    170 => Op170, Op171, Op172, Op173, Op174;
+    175 => Op175, Op176, Op177, Op178, Op179;
+    180 => Op180, Op181, Op182, Op183, Op184;
+    185 => Op185, Op186, Op187, Op188, Op189;
+    190 => Op190, Op191, Op192, Op193, Op194;
+    195 => Op195, Op196, Op197, Op198, Op199;
+    200 => Op200, Op201, Op202, Op203, Op204;
+    205 => Op205, Op206, Op207, Op208, Op209;
+    210 => Op210, Op211, Op212, Op213, Op214;
+    215 => Op215, Op216, Op217, Op218, Op219;
+    220 => Op220, Op221, Op222, Op223, Op224;
+    225 => Op225, Op226, Op227, Op228, Op229;
+    230 => Op230, Op231, Op232, Op233, Op234;
+    235 => Op235, Op236, Op237, Op238, Op239;
+    240 => Op240, Op241, Op242, Op243, Op244;
+    245 => Op245, Op246, Op247, Op248, Op249;
+    250 => Op250, Op251, Op252, Op253, Op254;
+    255 => Op255, Op256, Op257, Op258, Op259;
+    260 => Op260, Op261, Op262, Op263, Op264;
+    265 => Op265, Op266, Op267, Op268, Op269;
+    270 => Op270, Op271, Op272, Op273, Op274;
+    275 => Op275, Op276, Op277, Op278, Op279;
+    280 => Op280, Op281, Op282, Op283, Op284;
+    285 => Op285, Op286, Op287, Op288, Op289;
+    290 => Op290, Op291, Op292, Op293, Op294;
+    295 => Op295, Op296, Op297, Op298, Op299;
+}
+
+#[test]
+fn opcode_roundtrip() {
+    let opcodes = opcodes();
+    assert_eq!(opcodes.len(), 300);
+
+    for opcode in opcodes {
+        musli::macros::assert_roundtrip_eq!(full, opcode.clone());
+    }
+}
@@ -0,0 +1,75 @@
+//! Test capping the size of encoded output through `LimitWriter`.
+
+use musli::writer::{LimitWriter, Writer};
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn person() -> Person {
+    Person {
+        name: String::from("Aristotle"),
+        age: 61,
+    }
+}
+
+macro_rules! test_format {
+    ($name:ident, $what:ident) => {
+        #[test]
+        fn $name() {
+            let expected = musli::$what::to_vec(&person()).unwrap();
+
+            let mut out = Vec::new();
+            musli::$what::encode(LimitWriter::new(&mut out, expected.len()), &person()).unwrap();
+            assert_eq!(out, expected);
+
+            let mut out = Vec::new();
+            let error =
+                musli::$what::encode((&mut out).limit(expected.len() - 1), &person()).unwrap_err();
+            assert!(
+                error.to_string().starts_with("Size limit exceeded"),
+                "{error}"
+            );
+            assert!(out.len() < expected.len());
+        }
+    };
+}
+
+test_format!(storage, storage);
+test_format!(wire, wire);
+test_format!(descriptive, descriptive);
+test_format!(json, json);
+
+#[test]
+fn remaining() {
+    let mut out = Vec::new();
+    let mut writer = LimitWriter::new(&mut out, 16);
+
+    musli::storage::encode(&mut writer, &person()).unwrap();
+    let written = musli::storage::to_vec(&person()).unwrap().len();
+    assert_eq!(writer.remaining(), 16 - written);
+}
+
+#[test]
+fn limit_exceeded() {
+    use musli::context;
+    use musli::writer::LimitExceeded;
+
+    let cx = context::new().with_error::<std::io::Error>();
+
+    let mut out = Vec::new();
+    let error = musli::storage::Encoding::new()
+        .encode_with(&cx, LimitWriter::new(&mut out, 4), &person())
+        .unwrap_err();
+
+    let limit = error
+        .get_ref()
+        .and_then(|e| e.downcast_ref::<LimitExceeded>())
+        .expect("expected LimitExceeded");
+
+    assert_eq!(limit.attempted(), "Aristotle".len());
+    assert!(limit.remaining() < limit.attempted());
+}
@@ -0,0 +1,83 @@
+//! Test that `#[musli(with = musli::compat::pairs)]` encodes maps as sequences
+//! of key-value pairs.
+
+#![cfg(feature = "test")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Pairs {
+    #[musli(with = musli::compat::pairs)]
+    ordered: BTreeMap<u32, String>,
+    #[musli(with = musli::compat::pairs)]
+    hashed: HashMap<(u32, u32), bool>,
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Pairs {
+            ordered: BTreeMap::from([(1, String::from("one")), (2, String::from("two"))]),
+            hashed: HashMap::from([((1, 2), true)]),
+        },
+        json = r#"{"ordered":[[1,"one"],[2,"two"]],"hashed":[[[1,2],true]]}"#
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct TextOnly {
+    #[musli(mode = Text, with = musli::compat::pairs)]
+    map: BTreeMap<u32, u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Plain {
+    map: BTreeMap<u32, u32>,
+}
+
+#[test]
+fn binary_unchanged() {
+    let map = BTreeMap::from([(3, 30), (1, 10)]);
+
+    let text_only = TextOnly { map: map.clone() };
+    let plain = Plain { map };
+
+    assert_eq!(
+        musli::storage::to_vec(&text_only).unwrap(),
+        musli::storage::to_vec(&plain).unwrap()
+    );
+
+    assert_eq!(
+        musli::wire::to_vec(&text_only).unwrap(),
+        musli::wire::to_vec(&plain).unwrap()
+    );
+
+    assert_eq!(
+        musli::descriptive::to_vec(&text_only).unwrap(),
+        musli::descriptive::to_vec(&plain).unwrap()
+    );
+
+    let json = musli::json::to_string(&text_only).unwrap();
+    assert_eq!(json, r#"{"map":[[1,10],[3,30]]}"#);
+    assert_eq!(musli::json::from_str::<TextOnly>(&json).unwrap(), text_only);
+
+    assert_eq!(
+        musli::json::to_string(&plain).unwrap(),
+        r#"{"map":{"1":10,"3":30}}"#
+    );
+}
+
+#[test]
+fn duplicate_keys() {
+    let decoded: TextOnly = musli::json::from_str(r#"{"map":[[1,10],[1,20]]}"#).unwrap();
+    assert_eq!(decoded.map, BTreeMap::from([(1, 20)]));
+}
+
+#[test]
+fn malformed_pair() {
+    assert!(musli::json::from_str::<TextOnly>(r#"{"map":[[1]]}"#).is_err());
+    assert!(musli::json::from_str::<TextOnly>(r#"{"map":{"1":10}}"#).is_err());
+}
@@ -0,0 +1,35 @@
+//! Test that map adapters encode identically to an equivalent map.
+
+use std::collections::BTreeMap;
+
+use musli::compat::{self, MapPairs};
+
+macro_rules! test_map_pairs {
+    ($($module:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $module() {
+                let pairs = [
+                    (String::from("alpha"), 1u32),
+                    (String::from("beta"), 2u32),
+                    (String::from("gamma"), 3u32),
+                ];
+
+                let map = pairs.iter().cloned().collect::<BTreeMap<_, _>>();
+                let expected = musli::$module::to_vec(&map).unwrap();
+
+                let slice = musli::$module::to_vec(&compat::as_map(&pairs)).unwrap();
+                assert_eq!(slice, expected);
+
+                let iter = MapPairs::new(map.iter());
+                let iter = musli::$module::to_vec(&iter).unwrap();
+                assert_eq!(iter, expected);
+
+                let decoded: BTreeMap<String, u32> = musli::$module::from_slice(&slice).unwrap();
+                assert_eq!(decoded, map);
+            }
+        )*
+    };
+}
+
+test_map_pairs!(storage, packed, wire, descriptive, json);
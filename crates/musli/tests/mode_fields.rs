@@ -0,0 +1,115 @@
+//! Test that `#[musli(modes(..))]` limits which modes a field is present in.
+
+use musli::json::Encoding;
+use musli::mode::Binary;
+use musli::{Decode, Encode};
+
+enum Alt {}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Record {
+    id: u32,
+    #[musli(modes(Text))]
+    label: String,
+    #[musli(modes(Binary, Alt), default = default_checksum)]
+    checksum: u32,
+}
+
+fn default_checksum() -> u32 {
+    7
+}
+
+fn record() -> Record {
+    Record {
+        id: 1,
+        label: String::from("first"),
+        checksum: 42,
+    }
+}
+
+const TEXT: Encoding = Encoding::new();
+const BINARY: Encoding<Binary> = Encoding::new().with_mode();
+const ALT: Encoding<Alt> = Encoding::new().with_mode();
+
+#[test]
+fn field_presence() {
+    assert_eq!(
+        TEXT.to_string(&record()).unwrap(),
+        r#"{"id":1,"label":"first"}"#
+    );
+
+    assert_eq!(BINARY.to_string(&record()).unwrap(), r#"{"0":1,"2":42}"#);
+    assert_eq!(ALT.to_string(&record()).unwrap(), r#"{"0":1,"2":42}"#);
+}
+
+#[test]
+fn absent_fields_are_defaulted() {
+    let json = TEXT.to_string(&record()).unwrap();
+    assert_eq!(
+        TEXT.from_str::<Record>(&json).unwrap(),
+        Record {
+            id: 1,
+            label: String::from("first"),
+            checksum: 7,
+        }
+    );
+
+    let json = ALT.to_string(&record()).unwrap();
+    assert_eq!(
+        ALT.from_str::<Record>(&json).unwrap(),
+        Record {
+            id: 1,
+            label: String::new(),
+            checksum: 42,
+        }
+    );
+}
+
+#[test]
+fn absent_fields_are_ignored() {
+    // A field which isn't part of the mode is treated as an unknown field.
+    let decoded = TEXT
+        .from_str::<Record>(r#"{"id":1,"label":"first","checksum":42}"#)
+        .unwrap();
+    assert_eq!(decoded.checksum, 7);
+
+    let bytes = musli::storage::to_vec(&record()).unwrap();
+    assert_eq!(
+        musli::storage::from_slice::<Record>(&bytes).unwrap(),
+        Record {
+            id: 1,
+            label: String::new(),
+            checksum: 42,
+        }
+    );
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Packed {
+    first: u32,
+    #[musli(modes(Text))]
+    second: u32,
+    third: u32,
+}
+
+#[test]
+fn packed() {
+    let value = Packed {
+        first: 1,
+        second: 2,
+        third: 3,
+    };
+
+    assert_eq!(TEXT.to_string(&value).unwrap(), "[1,2,3]");
+    assert_eq!(BINARY.to_string(&value).unwrap(), "[1,3]");
+
+    assert_eq!(
+        BINARY.from_str::<Packed>("[1,3]").unwrap(),
+        Packed {
+            first: 1,
+            second: 0,
+            third: 3,
+        }
+    );
+}
@@ -0,0 +1,96 @@
+//! Test negotiating which format configuration to use between old and new
+//! clients and servers.
+
+#![cfg(feature = "test")]
+
+use musli::negotiate::{self, FormatId, FormatKind};
+use musli::options::{self, Integer, Options};
+use musli::{descriptive, wire};
+
+const FIXED: Options = options::new().integer(Integer::Fixed).build();
+
+const WIRE: wire::Encoding = wire::Encoding::new();
+const WIRE_FIXED: wire::Encoding<FIXED> = wire::Encoding::new().with_options();
+const DESCRIPTIVE: descriptive::Encoding = descriptive::Encoding::new();
+
+/// A peer which hasn't been upgraded yet.
+fn old() -> Vec<FormatId> {
+    vec![WIRE.format_id()]
+}
+
+/// An upgraded peer, which prefers the new configuration but still supports
+/// the old one.
+fn new() -> Vec<FormatId> {
+    vec![WIRE_FIXED.format_id(), WIRE.format_id()]
+}
+
+/// A peer which only supports a future version of the wire format.
+fn future() -> Vec<FormatId> {
+    vec![FormatId::new(
+        FormatKind::Wire,
+        wire::FORMAT_VERSION + 1,
+        wire::OPTIONS,
+    )]
+}
+
+/// Exchange identifiers as bytes, like in a hello message.
+fn exchange(ids: &[FormatId]) -> Vec<FormatId> {
+    ids.iter()
+        .map(|id| FormatId::from_bytes(id.to_bytes()).unwrap())
+        .collect()
+}
+
+#[test]
+fn matrix() {
+    let cases = [
+        (old(), old(), Some(WIRE.format_id())),
+        (old(), new(), Some(WIRE.format_id())),
+        (new(), old(), Some(WIRE.format_id())),
+        (new(), new(), Some(WIRE_FIXED.format_id())),
+        (new(), future(), None),
+        (future(), old(), None),
+    ];
+
+    for (server, client, expected) in cases {
+        let client = exchange(&client);
+        assert_eq!(
+            negotiate::negotiate(&server, &client),
+            expected,
+            "server: {server:?}, client: {client:?}"
+        );
+    }
+}
+
+#[test]
+fn preference() {
+    let ours = [WIRE.format_id(), DESCRIPTIVE.format_id()];
+    let theirs = [DESCRIPTIVE.format_id(), WIRE.format_id()];
+
+    assert_eq!(negotiate::negotiate(&ours, &theirs), Some(WIRE.format_id()));
+    assert_eq!(
+        negotiate::negotiate(&theirs, &ours),
+        Some(DESCRIPTIVE.format_id())
+    );
+    assert_eq!(negotiate::negotiate(&ours, &[]), None);
+}
+
+#[test]
+fn identifiers() {
+    assert_ne!(WIRE.format_id(), WIRE_FIXED.format_id());
+    assert_ne!(WIRE.format_id().kind(), DESCRIPTIVE.format_id().kind());
+    assert_eq!(WIRE.format_id().version(), wire::FORMAT_VERSION);
+    assert_eq!(WIRE_FIXED.format_id().version(), wire::FORMAT_VERSION);
+    assert_eq!(
+        wire::msgpack_like().format_id().version(),
+        wire::FIXED_FLOAT_FORMAT_VERSION
+    );
+    assert_eq!(WIRE_FIXED.format_id().options(), FIXED);
+
+    // The mode doesn't affect the layout of the format.
+    enum Custom {}
+    assert_eq!(WIRE.with_mode::<Custom>().format_id(), WIRE.format_id());
+
+    let bytes = DESCRIPTIVE.format_id().to_bytes();
+    assert_eq!(bytes.len(), FormatId::LEN);
+    assert_eq!(FormatId::from_bytes(bytes), Some(DESCRIPTIVE.format_id()));
+}
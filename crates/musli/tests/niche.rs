@@ -0,0 +1,108 @@
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Tagged {
+    id: u32,
+    parent: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Plain {
+    id: u32,
+    parent: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Niche {
+    id: u32,
+    #[musli(niche = u32::MAX)]
+    parent: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct PackedNiche {
+    #[musli(niche = 0)]
+    count: Option<u64>,
+    flag: bool,
+}
+
+#[test]
+fn niche_roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Niche {
+            id: 1,
+            parent: Some(42),
+        },
+        json = r#"{"id":1,"parent":42}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Niche {
+            id: 1,
+            parent: None,
+        },
+        json = r#"{"id":1,"parent":4294967295}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        PackedNiche {
+            count: Some(10),
+            flag: true,
+        },
+        json = r#"[10,true]"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        PackedNiche {
+            count: None,
+            flag: true,
+        },
+        json = r#"[0,true]"#
+    );
+}
+
+#[test]
+fn niche_removes_tag() -> Result<(), Box<dyn std::error::Error>> {
+    let niche = musli::storage::to_vec(&Niche {
+        id: 1,
+        parent: None,
+    })?;
+
+    let plain = musli::storage::to_vec(&Plain {
+        id: 1,
+        parent: u32::MAX,
+    })?;
+
+    assert_eq!(plain, niche);
+
+    let niche = musli::storage::to_vec(&Niche {
+        id: 1,
+        parent: Some(2),
+    })?;
+
+    let plain = musli::storage::to_vec(&Plain { id: 1, parent: 2 })?;
+    assert_eq!(plain, niche);
+
+    let tagged = musli::storage::to_vec(&Tagged {
+        id: 1,
+        parent: Some(2),
+    })?;
+
+    assert!(tagged.len() > niche.len());
+    Ok(())
+}
+
+#[test]
+fn niche_value_collision() {
+    let result = musli::storage::to_vec(&Niche {
+        id: 1,
+        parent: Some(u32::MAX),
+    });
+
+    assert!(result.is_err());
+}
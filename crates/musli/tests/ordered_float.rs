@@ -0,0 +1,64 @@
+#![cfg(feature = "ordered-float")]
+
+use std::collections::BTreeMap;
+
+use musli::{Decode, Encode};
+use ordered_float::{NotNan, OrderedFloat};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Floats {
+    ordered: OrderedFloat<f64>,
+    not_nan: NotNan<f32>,
+    values: Vec<OrderedFloat<f32>>,
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Floats {
+            ordered: OrderedFloat(-2.5),
+            not_nan: NotNan::new(1.5).unwrap(),
+            values: vec![OrderedFloat(1.0), OrderedFloat(-0.25)],
+        }
+    );
+}
+
+/// JSON doesn't support floats as object keys, so this is only tested with
+/// the binary formats.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Keys {
+    ordered: OrderedFloat<f64>,
+    keys: BTreeMap<OrderedFloat<f64>, u32>,
+}
+
+#[test]
+fn map_keys() {
+    musli::macros::assert_roundtrip_eq!(
+        no_json,
+        Keys {
+            ordered: OrderedFloat(f64::NAN),
+            keys: BTreeMap::from([(OrderedFloat(-1.0), 1), (OrderedFloat(2.5), 2)]),
+        }
+    );
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn plain_floats() {
+    let json = musli::json::to_string(&OrderedFloat(1.5f64)).unwrap();
+    assert_eq!(json, "1.5");
+
+    let value: NotNan<f64> = musli::json::from_str("2.5").unwrap();
+    assert_eq!(value.into_inner(), 2.5);
+}
+
+#[test]
+fn not_nan_rejects_nan() {
+    let bytes = musli::storage::to_vec(&f64::NAN).unwrap();
+    let error = musli::storage::from_slice::<NotNan<f64>>(&bytes).unwrap_err();
+    assert!(error.to_string().contains("NaN"), "{error}");
+
+    let bytes = musli::wire::to_vec(&f32::NAN).unwrap();
+    assert!(musli::wire::from_slice::<NotNan<f32>>(&bytes).is_err());
+}
@@ -0,0 +1,144 @@
+//! Test that a header can be decoded from the leading fields of a packed
+//! message, leaving the reader positioned at the body.
+
+#![cfg(feature = "test")]
+
+use musli::reader::SliceReader;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Decode)]
+#[musli(packed)]
+struct Header {
+    kind: u8,
+    id: u32,
+}
+
+#[derive(Encode)]
+#[musli(packed)]
+struct PingMessage {
+    kind: u8,
+    id: u32,
+    body: Ping,
+}
+
+#[derive(Encode)]
+#[musli(packed)]
+struct TextMessage {
+    kind: u8,
+    id: u32,
+    body: Text,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Ping {
+    sequence: u64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct Text {
+    text: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Body {
+    Ping(Ping),
+    Text(Text),
+}
+
+macro_rules! test_format {
+    ($format:ident) => {{
+        use musli::$format::Encoding;
+
+        const ENCODING: Encoding = Encoding::new();
+
+        let mut data = Vec::new();
+
+        ENCODING
+            .encode(
+                &mut data,
+                &PingMessage {
+                    kind: 1,
+                    id: 10,
+                    body: Ping { sequence: 42 },
+                },
+            )
+            .unwrap();
+
+        ENCODING
+            .encode(
+                &mut data,
+                &TextMessage {
+                    kind: 2,
+                    id: 11,
+                    body: Text {
+                        text: String::from("Hello"),
+                        tags: vec![String::from("greeting")],
+                    },
+                },
+            )
+            .unwrap();
+
+        // Dispatch on each header in a single stream of messages.
+        let mut reader = SliceReader::new(&data);
+        let mut messages = Vec::new();
+
+        while !reader.as_slice().is_empty() {
+            let (header, rest) = ENCODING.decode_reader_prefix::<_, Header>(reader).unwrap();
+
+            let (body, rest) = match header.kind {
+                1 => {
+                    let (ping, rest) = ENCODING.decode_reader_prefix(rest).unwrap();
+                    (Body::Ping(ping), rest)
+                }
+                2 => {
+                    let (text, rest) = ENCODING.decode_reader_prefix(rest).unwrap();
+                    (Body::Text(text), rest)
+                }
+                kind => panic!("unsupported message kind {kind}"),
+            };
+
+            messages.push((header.id, body));
+            reader = rest;
+        }
+
+        assert_eq!(
+            messages,
+            [
+                (10, Body::Ping(Ping { sequence: 42 })),
+                (
+                    11,
+                    Body::Text(Text {
+                        text: String::from("Hello"),
+                        tags: vec![String::from("greeting")],
+                    })
+                ),
+            ]
+        );
+
+        // The header can be decoded on its own, even if the body is missing.
+        let (_, rest) = ENCODING
+            .decode_reader_prefix::<_, Header>(&data[..])
+            .unwrap();
+        let header_len = data.len() - rest.len();
+
+        let (header, rest) = ENCODING
+            .decode_reader_prefix::<_, Header>(&data[..header_len])
+            .unwrap();
+        assert_eq!(header, Header { kind: 1, id: 10 });
+        assert!(rest.is_empty());
+        assert!(ENCODING.decode::<_, Ping>(rest).is_err());
+    }};
+}
+
+#[test]
+fn storage() {
+    test_format!(storage);
+}
+
+#[test]
+fn packed() {
+    test_format!(packed);
+}
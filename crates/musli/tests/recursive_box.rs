@@ -0,0 +1,93 @@
+//! Test that recursive types behind `Box` roundtrip, and that decoding them
+//! respects the maximum depth configured for the context.
+
+use musli::context;
+use musli::storage::Encoding;
+use musli::{Decode, Encode};
+
+const ENCODING: Encoding = Encoding::new();
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+enum Expr {
+    Num(i64),
+    Add(Box<Expr>, Box<Expr>),
+}
+
+fn add(a: Expr, b: Expr) -> Expr {
+    Expr::Add(Box::new(a), Box::new(b))
+}
+
+/// Construct the encoding of `Add(Add(...(Num(0), Num(0))..., Num(0)),
+/// Num(0))` nested `depth` levels deep without constructing the value itself,
+/// since both encoding and dropping such a value would overflow the stack.
+fn deep(depth: usize) -> Vec<u8> {
+    const MARKER: i64 = 0x1234_5678;
+
+    let num = ENCODING.to_vec(&Expr::Num(0)).unwrap();
+    let marker = ENCODING.to_vec(&Expr::Num(MARKER)).unwrap();
+    let add = ENCODING
+        .to_vec(&add(Expr::Num(MARKER), Expr::Num(0)))
+        .unwrap();
+
+    // Split the encoding of `Add` around its first field.
+    let at = add.windows(marker.len()).position(|w| w == marker).unwrap();
+    let (prefix, suffix) = (&add[..at], &add[at + marker.len()..]);
+
+    let mut bytes = prefix.repeat(depth);
+    bytes.extend_from_slice(&num);
+    bytes.extend(suffix.repeat(depth));
+    bytes
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(full, Expr::Num(42));
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        add(
+            add(Expr::Num(1), Expr::Num(2)),
+            add(Expr::Num(3), Expr::Num(4))
+        )
+    );
+}
+
+#[test]
+fn within_depth() {
+    let expr = (0..64).fold(Expr::Num(0), |expr, _| add(expr, Expr::Num(0)));
+    let bytes = deep(64);
+    assert_eq!(bytes, ENCODING.to_vec(&expr).unwrap());
+
+    let cx = context::new().with_max_depth(64);
+    let decoded: Expr = ENCODING.from_slice_with(&cx, &bytes).unwrap();
+    assert_eq!(decoded, expr);
+
+    let cx = context::new().with_max_depth(63);
+    assert!(ENCODING.from_slice_with::<_, Expr>(&cx, &bytes).is_err());
+
+    // Sibling boxes do not accumulate towards the limit.
+    let wide = vec![add(Expr::Num(1), Expr::Num(2)); 128];
+    let bytes = ENCODING.to_vec(&wide).unwrap();
+    let cx = context::new().with_max_depth(2);
+    let decoded: Vec<Expr> = ENCODING.from_slice_with(&cx, &bytes).unwrap();
+    assert_eq!(decoded, wide);
+}
+
+#[test]
+fn exceeds_depth() {
+    let bytes = deep(1_000_000);
+
+    let cx = context::new()
+        .with_error::<musli::storage::Error>()
+        .with_max_depth(128);
+    let error = ENCODING
+        .from_slice_with::<_, Expr>(&cx, &bytes)
+        .unwrap_err();
+    assert!(
+        error.to_string().contains("Maximum depth of 128 exceeded"),
+        "{error}"
+    );
+
+    // The context can be reused once the depth has been exceeded.
+    let bytes = deep(16);
+    assert!(ENCODING.from_slice_with::<_, Expr>(&cx, &bytes).is_ok());
+}
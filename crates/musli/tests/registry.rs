@@ -0,0 +1,209 @@
+//! Test encoding and decoding trait objects through [`musli::registry!`].
+
+#![cfg(feature = "test")]
+
+use std::fmt;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use musli::registry::AsAny;
+use musli::{Decode, Encode};
+
+trait Animal: AsAny + fmt::Debug {
+    fn sound(&self) -> String;
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Dog {
+    name: String,
+}
+
+impl Animal for Dog {
+    fn sound(&self) -> String {
+        format!("{}: woof", self.name)
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Cat {
+    lives: u8,
+}
+
+impl Animal for Cat {
+    fn sound(&self) -> String {
+        format!("meow x{}", self.lives)
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Fish;
+
+impl Animal for Fish {
+    fn sound(&self) -> String {
+        String::from("...")
+    }
+}
+
+#[derive(Debug, Default)]
+struct Unknown;
+
+impl Animal for Unknown {
+    fn sound(&self) -> String {
+        String::from("?")
+    }
+}
+
+musli::registry! {
+    dyn Animal {
+        1 => Dog,
+        2 => Cat,
+    }
+}
+
+/// Same as `Animal`, but knows about more types.
+trait NewAnimal: AsAny + fmt::Debug {}
+
+impl NewAnimal for Dog {}
+impl NewAnimal for Cat {}
+impl NewAnimal for Fish {}
+
+musli::registry! {
+    dyn NewAnimal {
+        1 => Dog,
+        2 => Cat,
+        3 => Fish,
+    }
+}
+
+/// Same as `Animal`, but decodes unknown ids as `Unknown`.
+trait LenientAnimal: AsAny + fmt::Debug {
+    fn sound(&self) -> String;
+}
+
+impl LenientAnimal for Dog {
+    fn sound(&self) -> String {
+        Animal::sound(self)
+    }
+}
+
+impl LenientAnimal for Unknown {
+    fn sound(&self) -> String {
+        Animal::sound(self)
+    }
+}
+
+musli::registry! {
+    dyn LenientAnimal {
+        1 => Dog,
+        _ => Unknown,
+    }
+}
+
+#[derive(Debug, Encode, Decode)]
+struct Zoo {
+    animals: Vec<Box<dyn Animal>>,
+    #[musli(with = musli::registry::arc)]
+    mascot: Arc<dyn Animal>,
+    #[musli(with = musli::registry::rc)]
+    visitor: Rc<dyn Animal>,
+}
+
+fn zoo() -> Zoo {
+    Zoo {
+        animals: vec![
+            Box::new(Dog {
+                name: String::from("Rex"),
+            }),
+            Box::new(Cat { lives: 9 }),
+        ],
+        mascot: Arc::new(Cat { lives: 3 }),
+        visitor: Rc::new(Dog {
+            name: String::from("Fido"),
+        }),
+    }
+}
+
+fn sounds(zoo: &Zoo) -> Vec<String> {
+    let mut sounds = zoo
+        .animals
+        .iter()
+        .map(|animal| animal.sound())
+        .collect::<Vec<_>>();
+    sounds.push(zoo.mascot.sound());
+    sounds.push(zoo.visitor.sound());
+    sounds
+}
+
+#[test]
+fn roundtrip() {
+    let expected = sounds(&zoo());
+
+    let zoo: Zoo = musli::wire::from_slice(&musli::wire::to_vec(&zoo()).unwrap()).unwrap();
+    assert_eq!(sounds(&zoo), expected);
+
+    let zoo: Zoo =
+        musli::descriptive::from_slice(&musli::descriptive::to_vec(&zoo).unwrap()).unwrap();
+    assert_eq!(sounds(&zoo), expected);
+
+    let zoo: Zoo = musli::storage::from_slice(&musli::storage::to_vec(&zoo).unwrap()).unwrap();
+    assert_eq!(sounds(&zoo), expected);
+
+    let zoo: Zoo = musli::json::from_str(&musli::json::to_string(&zoo).unwrap()).unwrap();
+    assert_eq!(sounds(&zoo), expected);
+}
+
+#[test]
+fn stable_ids() {
+    let animal: Box<dyn Animal> = Box::new(Cat { lives: 9 });
+    assert_eq!(
+        musli::json::to_string(&animal).unwrap(),
+        r#"{"2":{"lives":9}}"#
+    );
+}
+
+#[test]
+fn unregistered_type() {
+    let animal: Box<dyn Animal> = Box::new(Fish);
+    let error = musli::wire::to_vec(&animal).unwrap_err();
+    assert!(
+        error
+            .to_string()
+            .contains("Value is not of a type registered for dyn Animal"),
+        "{error}"
+    );
+}
+
+#[test]
+fn unknown_id() {
+    let animal: Box<dyn NewAnimal> = Box::new(Fish);
+    let bytes = musli::wire::to_vec(&animal).unwrap();
+
+    let error = musli::wire::from_slice::<Box<dyn Animal>>(&bytes).unwrap_err();
+    assert!(
+        error.to_string().contains("Unknown id 3 for dyn Animal"),
+        "{error}"
+    );
+}
+
+#[test]
+fn fallback() {
+    let animals: Vec<Box<dyn NewAnimal>> = vec![
+        Box::new(Cat { lives: 1 }),
+        Box::new(Dog {
+            name: String::from("Rex"),
+        }),
+        Box::new(Fish),
+    ];
+
+    let bytes = musli::wire::to_vec(&animals).unwrap();
+    let decoded: Vec<Box<dyn LenientAnimal>> = musli::wire::from_slice(&bytes).unwrap();
+    assert_eq!(lenient_sounds(&decoded), ["?", "Rex: woof", "?"]);
+
+    let bytes = musli::descriptive::to_vec(&animals).unwrap();
+    let decoded: Vec<Box<dyn LenientAnimal>> = musli::descriptive::from_slice(&bytes).unwrap();
+    assert_eq!(lenient_sounds(&decoded), ["?", "Rex: woof", "?"]);
+}
+
+fn lenient_sounds(animals: &[Box<dyn LenientAnimal>]) -> Vec<String> {
+    animals.iter().map(|animal| animal.sound()).collect()
+}
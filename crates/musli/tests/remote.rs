@@ -0,0 +1,165 @@
+//! Test deriving `Encode` and `Decode` for types in other crates through
+//! `#[musli(remote = <path>)]`.
+
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+/// Stand-in for a crate which doesn't implement `Encode` or `Decode`.
+mod other {
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Point {
+        pub x: i32,
+        pub y: i32,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Shape {
+        Circle { center: Point, radius: u32 },
+        Line(Point, Point),
+        Empty,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct Wrapper<T> {
+        pub value: T,
+    }
+}
+
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+#[musli(remote = other::Point)]
+struct PointDef {
+    #[musli(mode = Text, name = "px")]
+    x: i32,
+    y: i32,
+}
+
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+#[musli(remote = other::Shape)]
+enum ShapeDef {
+    Circle {
+        #[musli(with = PointDef)]
+        center: other::Point,
+        radius: u32,
+    },
+    Line(
+        #[musli(with = PointDef)] other::Point,
+        #[musli(with = PointDef)] other::Point,
+    ),
+    Empty,
+}
+
+#[derive(Encode, Decode)]
+#[allow(dead_code)]
+#[musli(remote = other::Wrapper<u32>)]
+struct WrapperDef {
+    value: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Drawing {
+    name: String,
+    #[musli(with = PointDef)]
+    origin: other::Point,
+    #[musli(with = ShapeDef)]
+    shape: other::Shape,
+    #[musli(with = WrapperDef)]
+    layer: other::Wrapper<u32>,
+}
+
+fn drawing() -> Drawing {
+    Drawing {
+        name: String::from("sketch"),
+        origin: other::Point { x: -1, y: 2 },
+        shape: other::Shape::Line(other::Point { x: 1, y: 2 }, other::Point { x: 3, y: 4 }),
+        layer: other::Wrapper { value: 7 },
+    }
+}
+
+#[test]
+fn roundtrip() {
+    let shapes = [
+        other::Shape::Circle {
+            center: other::Point { x: 5, y: 6 },
+            radius: 10,
+        },
+        other::Shape::Line(other::Point { x: 1, y: 2 }, other::Point { x: 3, y: 4 }),
+        other::Shape::Empty,
+    ];
+
+    for shape in shapes {
+        let expected = Drawing { shape, ..drawing() };
+
+        let bytes = musli::wire::to_vec(&expected).unwrap();
+        assert_eq!(
+            musli::wire::from_slice::<Drawing>(&bytes).unwrap(),
+            expected
+        );
+
+        let bytes = musli::descriptive::to_vec(&expected).unwrap();
+        assert_eq!(
+            musli::descriptive::from_slice::<Drawing>(&bytes).unwrap(),
+            expected
+        );
+
+        let bytes = musli::storage::to_vec(&expected).unwrap();
+        assert_eq!(
+            musli::storage::from_slice::<Drawing>(&bytes).unwrap(),
+            expected
+        );
+
+        let json = musli::json::to_string(&expected).unwrap();
+        assert_eq!(musli::json::from_str::<Drawing>(&json).unwrap(), expected);
+    }
+}
+
+#[test]
+fn remote_attributes() {
+    let json = musli::json::to_string(&drawing()).unwrap();
+
+    assert_eq!(
+        json,
+        r#"{"name":"sketch","origin":{"px":-1,"y":2},"shape":{"Line":{"0":{"px":1,"y":2},"1":{"px":3,"y":4}}},"layer":{"value":7}}"#
+    );
+}
+
+#[test]
+fn same_as_local() {
+    #[derive(Encode)]
+    struct Point {
+        #[musli(mode = Text, name = "px")]
+        x: i32,
+        y: i32,
+    }
+
+    #[derive(Encode)]
+    struct Local {
+        point: Point,
+    }
+
+    #[derive(Encode)]
+    struct Remote {
+        #[musli(with = PointDef)]
+        point: other::Point,
+    }
+
+    let local = Local {
+        point: Point { x: 1, y: 2 },
+    };
+
+    let remote = Remote {
+        point: other::Point { x: 1, y: 2 },
+    };
+
+    assert_eq!(
+        musli::wire::to_vec(&remote).unwrap(),
+        musli::wire::to_vec(&local).unwrap()
+    );
+
+    assert_eq!(
+        musli::storage::to_vec(&remote).unwrap(),
+        musli::storage::to_vec(&local).unwrap()
+    );
+}
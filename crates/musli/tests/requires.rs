@@ -0,0 +1,78 @@
+//! Test types which require capabilities from the decoder they are decoded
+//! with.
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Version1 {
+    name: String,
+}
+
+/// A configuration which is expected to be decoded from data produced by newer
+/// versions, which might contain fields it doesn't know about.
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name", requires(skip))]
+struct Config {
+    name: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(requires(skip))]
+enum Event {
+    Start(Config),
+    Stop,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Version2 {
+    name: String,
+    age: u32,
+}
+
+macro_rules! test_requires {
+    ($what:ident) => {
+        #[test]
+        fn $what() -> Result<(), musli::$what::Error> {
+            let bytes = musli::$what::to_vec(&Version2 {
+                name: String::from("Aristotle"),
+                age: 61,
+            })?;
+
+            let config: Config = musli::$what::from_slice(&bytes)?;
+            assert_eq!(config.name, "Aristotle");
+
+            let events = vec![Event::Start(config), Event::Stop];
+            let bytes = musli::$what::to_vec(&events)?;
+            let actual: Vec<Event> = musli::$what::from_slice(&bytes)?;
+            assert_eq!(actual, events);
+            Ok(())
+        }
+    };
+}
+
+test_requires!(wire);
+test_requires!(descriptive);
+
+#[test]
+fn json() -> Result<(), musli::json::Error> {
+    let config: Config = musli::json::from_str(r#"{"name":"Aristotle","age":61}"#)?;
+    assert_eq!(config.name, "Aristotle");
+
+    let version1: Version1 = musli::json::from_str(r#"{"name":"Plato"}"#)?;
+    assert_eq!(version1.name, "Plato");
+    Ok(())
+}
+
+#[test]
+fn value() {
+    let value = musli::value::encode(&Version2 {
+        name: String::from("Aristotle"),
+        age: 61,
+    })
+    .unwrap();
+
+    let config: Config = musli::value::decode(&value).unwrap();
+    assert_eq!(config.name, "Aristotle");
+}
@@ -0,0 +1,227 @@
+#![cfg(feature = "test")]
+
+use musli::compat::Bytes;
+use musli::transcode::transcode;
+use musli::value::Value;
+use musli::{semantic, Allocator, Context, Decode, Decoder, Encode, Encoder};
+
+/// A tag which isn't well-known.
+const CUSTOM: u32 = 1000;
+
+#[derive(Debug, PartialEq)]
+struct Uuid([u8; 16]);
+
+impl<M> Encode<M> for Uuid
+where
+    Bytes<[u8; 16]>: Encode<M>,
+{
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_tagged(semantic::UUID, Bytes(self.0))
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Uuid
+where
+    A: Allocator,
+    Bytes<[u8; 16]>: Decode<'de, M, A>,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        match decoder.decode_tagged()? {
+            (semantic::UUID, decoder) => Ok(Uuid(decoder.decode::<Bytes<_>>()?.0)),
+            (tag, _) => Err(cx.message(format_args!("Expected a UUID, got tag {tag}"))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Custom(String);
+
+impl<M> Encode<M> for Custom {
+    type Encode = Self;
+
+    #[inline]
+    fn encode<E>(&self, encoder: E) -> Result<E::Ok, E::Error>
+    where
+        E: Encoder<Mode = M>,
+    {
+        encoder.encode_tagged(CUSTOM, &self.0)
+    }
+
+    #[inline]
+    fn as_encode(&self) -> &Self::Encode {
+        self
+    }
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Custom
+where
+    A: Allocator,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        let cx = decoder.cx();
+
+        match decoder.decode_tagged()? {
+            (CUSTOM, decoder) => Ok(Custom(decoder.decode()?)),
+            (tag, _) => Err(cx.message(format_args!("Expected a custom value, got tag {tag}"))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Record {
+    id: Uuid,
+    custom: Custom,
+    created: u64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Plain {
+    name: String,
+    values: Vec<u32>,
+    flag: Option<bool>,
+}
+
+fn record() -> Record {
+    Record {
+        id: Uuid([
+            0x67, 0xe5, 0x50, 0x44, 0x10, 0xb1, 0x42, 0x6f, 0x92, 0x47, 0xbb, 0x68, 0x0e, 0x5f,
+            0xe0, 0xc8,
+        ]),
+        custom: Custom(String::from("hello")),
+        created: 1_700_000_000,
+    }
+}
+
+#[test]
+fn descriptive_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&record())?;
+    assert_eq!(musli::descriptive::from_slice::<Record>(&bytes)?, record());
+
+    // Decoding a tagged value without asking for the tag is an error.
+    #[derive(Debug, Decode)]
+    #[musli(name_all = "name")]
+    struct Untagged {
+        #[allow(unused)]
+        custom: String,
+    }
+
+    assert!(musli::descriptive::from_slice::<Untagged>(&bytes).is_err());
+    Ok(())
+}
+
+#[test]
+fn transcode_preserves_tags() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&record())?;
+
+    let mut output = Vec::new();
+
+    transcode(
+        musli::descriptive::Encoding::new(),
+        musli::descriptive::Encoding::new(),
+        &bytes,
+        &mut output,
+    )?;
+
+    assert_eq!(output, bytes);
+
+    let value: Value<_> = musli::descriptive::from_slice(&bytes)?;
+    assert_eq!(musli::descriptive::to_vec(&value)?, bytes);
+    Ok(())
+}
+
+#[test]
+fn untagged_payloads_unchanged() -> Result<(), Box<dyn std::error::Error>> {
+    let plain = Plain {
+        name: String::from("Aristotle"),
+        values: vec![1, 2, 3],
+        flag: Some(true),
+    };
+
+    let bytes = musli::descriptive::to_vec(&plain)?;
+
+    let mut output = Vec::new();
+
+    transcode(
+        musli::descriptive::Encoding::new(),
+        musli::descriptive::Encoding::new(),
+        &bytes,
+        &mut output,
+    )?;
+
+    assert_eq!(output, bytes);
+    assert_eq!(musli::descriptive::from_slice::<Plain>(&output)?, plain);
+    Ok(())
+}
+
+#[test]
+fn json_rendering() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&record())?;
+
+    let mut json = Vec::new();
+
+    transcode(
+        musli::descriptive::Encoding::new(),
+        musli::json::Encoding::new(),
+        &bytes,
+        &mut json,
+    )?;
+
+    assert_eq!(
+        std::str::from_utf8(&json)?,
+        r#"{"id":"67e55044-10b1-426f-9247-bb680e5fe0c8","custom":{"$tag":1000,"$value":"hello"},"created":1700000000}"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn value_debug() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&record())?;
+    let value: Value<_> = musli::descriptive::from_slice(&bytes)?;
+
+    let Value::Map(entries) = &value else {
+        panic!("Expected map, got {value:?}");
+    };
+
+    assert_eq!(
+        format!("{:?}", entries[0].1),
+        r#"Tagged(uuid, 67e55044-10b1-426f-9247-bb680e5fe0c8)"#
+    );
+
+    assert_eq!(
+        format!("{:?}", entries[1].1),
+        r#"Tagged(1000, String("hello"))"#
+    );
+
+    Ok(())
+}
+
+#[test]
+fn value_roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+    let value = musli::value::encode(record())?;
+    assert_eq!(musli::value::decode::<Record>(&value)?, record());
+    Ok(())
+}
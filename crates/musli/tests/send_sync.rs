@@ -0,0 +1,18 @@
+//! Test that encodings can be shared across threads regardless of the mode
+//! they are used with, since they only hold configuration.
+
+#![cfg(feature = "test")]
+
+/// A mode which is neither `Send` nor `Sync`.
+type Mode = *mut ();
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn encodings() {
+    assert_send_sync::<musli::descriptive::Encoding<{ musli::descriptive::OPTIONS }, Mode>>();
+    assert_send_sync::<musli::json::Encoding<Mode>>();
+    assert_send_sync::<musli::packed::Encoding<{ musli::packed::OPTIONS }, Mode>>();
+    assert_send_sync::<musli::storage::Encoding<{ musli::storage::OPTIONS }, Mode>>();
+    assert_send_sync::<musli::wire::Encoding<{ musli::wire::OPTIONS }, Mode>>();
+}
@@ -0,0 +1,175 @@
+#![cfg(feature = "test")]
+
+use musli::alloc::{ArrayBuffer, Slice};
+use musli::context;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Message<'a> {
+    id: u32,
+    body: &'a str,
+}
+
+fn messages() -> Vec<Message<'static>> {
+    (0..16)
+        .map(|id| Message {
+            id,
+            body: ["ping", "pong", "hello world"][id as usize % 3],
+        })
+        .collect()
+}
+
+macro_rules! roundtrip {
+    ($name:ident, $encoding:expr, $separator:expr) => {
+        #[test]
+        fn $name() -> Result<(), Box<dyn std::error::Error>> {
+            let encoding = $encoding;
+            let messages = messages();
+
+            let mut encoder = encoding.encoder(Vec::new());
+
+            for message in &messages {
+                encoder.encode(message)?;
+            }
+
+            let bytes = encoder.into_writer();
+
+            // Same as encoding each value on its own, separated by
+            // `$separator`.
+            let mut expected = Vec::new();
+
+            for (n, message) in messages.iter().enumerate() {
+                if n > 0 {
+                    expected.extend_from_slice($separator);
+                }
+
+                encoding.encode(&mut expected, message)?;
+            }
+
+            assert_eq!(bytes, expected);
+
+            let mut decoder = encoding.decoder(&bytes);
+            let mut decoded = Vec::new();
+
+            while !decoder.is_empty() {
+                decoded.push(decoder.decode::<Message<'_>>()?);
+            }
+
+            assert_eq!(decoded, messages);
+            assert_eq!(decoder.remaining(), 0);
+            assert!(decoder.decode::<Message<'_>>().is_err());
+            Ok(())
+        }
+    };
+}
+
+roundtrip!(storage, musli::storage::Encoding::new(), b"");
+roundtrip!(packed, musli::packed::Encoding::new(), b"");
+roundtrip!(wire, musli::wire::Encoding::new(), b"");
+roundtrip!(descriptive, musli::descriptive::Encoding::new(), b"");
+roundtrip!(json, musli::json::Encoding::new(), b"\n");
+
+#[test]
+fn remaining() -> Result<(), Box<dyn std::error::Error>> {
+    const ENCODING: musli::wire::Encoding = musli::wire::Encoding::new();
+
+    let first = ENCODING.to_vec(&1u32)?;
+    let second = ENCODING.to_vec("second")?;
+
+    let mut bytes = first.clone();
+    bytes.extend_from_slice(&second);
+
+    let mut decoder = ENCODING.decoder(&bytes);
+    assert_eq!(decoder.remaining(), bytes.len());
+    assert_eq!(decoder.decode::<u32>()?, 1);
+    assert_eq!(decoder.remaining(), second.len());
+    assert_eq!(decoder.as_slice(), &second[..]);
+
+    // A failed decode leaves the session where it was.
+    assert!(decoder.decode::<u32>().is_err());
+    assert_eq!(decoder.remaining(), second.len());
+    assert_eq!(decoder.decode::<&str>()?, "second");
+    assert!(decoder.is_empty());
+    Ok(())
+}
+
+#[test]
+fn json_numbers() -> Result<(), Box<dyn std::error::Error>> {
+    const ENCODING: musli::json::Encoding = musli::json::Encoding::new();
+
+    let mut encoder = ENCODING.encoder(Vec::new());
+
+    for n in [1u32, 23, 456] {
+        encoder.encode(&n)?;
+    }
+
+    let mut bytes = encoder.into_writer();
+    assert_eq!(bytes, b"1\n23\n456");
+
+    // Trailing whitespace is not a value.
+    bytes.extend_from_slice(b" \r\n");
+
+    let mut decoder = ENCODING.decoder(&bytes);
+    let mut numbers = Vec::new();
+
+    while !decoder.is_empty() {
+        numbers.push(decoder.decode::<u32>()?);
+    }
+
+    assert_eq!(numbers, [1, 23, 456]);
+    assert_eq!(decoder.remaining(), 3);
+    Ok(())
+}
+
+#[test]
+fn shared_context() {
+    const ENCODING: musli::descriptive::Encoding = musli::descriptive::Encoding::new();
+
+    let mut buf = ArrayBuffer::new();
+    let alloc = Slice::new(&mut buf);
+    let cx = context::new_in(&alloc).with_trace();
+
+    let mut encoder = ENCODING.encoder_with(&cx, Vec::new());
+
+    for message in &messages() {
+        encoder.encode(message).unwrap();
+    }
+
+    let bytes = encoder.into_writer();
+
+    let mut decoder = ENCODING.decoder_with(&cx, &bytes);
+    let mut count = 0;
+
+    while !decoder.is_empty() {
+        decoder.decode::<Message<'_>>().unwrap();
+        count += 1;
+    }
+
+    assert_eq!(count, 16);
+
+    // Errors are reported through the shared context.
+    let mut decoder = ENCODING.decoder_with(&cx, &bytes[..3]);
+    assert!(decoder.decode::<Message<'_>>().is_err());
+    assert!(cx.report().to_string().contains("remaining"));
+}
+
+#[test]
+fn owned_context() -> Result<(), Box<dyn std::error::Error>> {
+    const ENCODING: musli::storage::Encoding = musli::storage::Encoding::new();
+
+    let mut encoder = ENCODING.encoder_with(
+        context::new().with_error::<musli::storage::Error>(),
+        Vec::new(),
+    );
+    encoder.encode(&1u32)?;
+    encoder.encode(&2u32)?;
+    let bytes = encoder.into_writer();
+
+    let mut decoder =
+        ENCODING.decoder_with(context::new().with_error::<musli::storage::Error>(), &bytes);
+    let sum = decoder.decode::<u32>()? + decoder.decode::<u32>()?;
+    assert_eq!(sum, 3);
+    assert!(decoder.is_empty());
+    Ok(())
+}
@@ -0,0 +1,132 @@
+//! Test that shared pointers are encoded by value and optionally deduplicated.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+use musli::compat::Shared;
+use musli::context;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Node {
+    name: String,
+    weight: u32,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Nodes {
+    arc: Arc<Node>,
+    rc: Rc<Node>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Graph {
+    nodes: Vec<Shared<Arc<Node>>>,
+    local: Vec<Shared<Rc<Node>>>,
+}
+
+fn node(name: &str, weight: u32) -> Node {
+    Node {
+        name: String::from(name),
+        weight,
+    }
+}
+
+#[test]
+#[cfg(feature = "test")]
+fn sized_by_value() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Nodes {
+            arc: Arc::new(node("arc", 1)),
+            rc: Rc::new(node("rc", 2)),
+        }
+    );
+}
+
+macro_rules! test_shared {
+    ($($module:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $module() {
+                use musli::$module::Encoding;
+
+                const ENCODING: Encoding = Encoding::new();
+
+                let a = Arc::new(node("a", 1));
+                let b = Arc::new(node("b", 2));
+                let c = Rc::new(node("c", 3));
+
+                let graph = Graph {
+                    nodes: vec![
+                        Shared(a.clone()),
+                        Shared(b.clone()),
+                        Shared(a.clone()),
+                        Shared(a),
+                        Shared(b),
+                    ],
+                    local: vec![Shared(c.clone()), Shared(c)],
+                };
+
+                // Without sharing, every pointer is encoded by value.
+                let plain = ENCODING.to_vec(&graph).unwrap();
+                let decoded: Graph = ENCODING.from_slice(&plain).unwrap();
+                assert_eq!(decoded, graph);
+                assert!(!Arc::ptr_eq(&decoded.nodes[0].0, &decoded.nodes[2].0));
+
+                let cx = context::new().with_error::<musli::$module::Error>().with_sharing();
+
+                let mut shared = Vec::new();
+                ENCODING.encode_with(&cx, &mut shared, &graph).unwrap();
+                assert!(shared.len() < plain.len());
+
+                let decoded: Graph = ENCODING.from_slice_with(&cx, &shared).unwrap();
+                assert_eq!(decoded, graph);
+                assert!(Arc::ptr_eq(&decoded.nodes[0].0, &decoded.nodes[2].0));
+                assert!(Arc::ptr_eq(&decoded.nodes[0].0, &decoded.nodes[3].0));
+                assert!(Arc::ptr_eq(&decoded.nodes[1].0, &decoded.nodes[4].0));
+                assert!(!Arc::ptr_eq(&decoded.nodes[0].0, &decoded.nodes[1].0));
+                assert!(Rc::ptr_eq(&decoded.local[0].0, &decoded.local[1].0));
+
+                // Re-using the context starts from an empty table.
+                let decoded: Graph = ENCODING.from_slice_with(&cx, &shared).unwrap();
+                assert_eq!(decoded, graph);
+            }
+        )*
+    };
+}
+
+test_shared!(storage, wire, descriptive, json);
+
+#[test]
+fn shared_reference_type_mismatch() {
+    use musli::storage::{Encoding, Error};
+
+    const ENCODING: Encoding = Encoding::new();
+
+    #[derive(Encode)]
+    struct Mixed {
+        first: Shared<Arc<u32>>,
+        second: Shared<Arc<u32>>,
+    }
+
+    #[derive(Debug, Decode)]
+    #[allow(unused)]
+    struct Mismatch {
+        first: Shared<Arc<u32>>,
+        second: Shared<Rc<u32>>,
+    }
+
+    let value = Arc::new(42);
+
+    let cx = context::new().with_error::<Error>().with_sharing();
+
+    let mut data = Vec::new();
+    let mixed = Mixed {
+        first: Shared(value.clone()),
+        second: Shared(value),
+    };
+    ENCODING.encode_with(&cx, &mut data, &mixed).unwrap();
+
+    assert!(ENCODING.from_slice_with::<_, Mismatch>(&cx, &data).is_err());
+}
@@ -0,0 +1,78 @@
+//! Test that decoding shared slices only allocates the container once.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+#[path = "common/alloc.rs"]
+mod alloc;
+
+use self::alloc::allocations;
+
+#[test]
+fn shared_slices() {
+    let values = (0..100u32).collect::<Vec<_>>();
+    let bytes = musli::storage::to_vec(&values).unwrap();
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Arc<[u32]>>(&bytes).unwrap());
+    assert_eq!(&value[..], &values[..]);
+    assert_eq!(n.count, 1);
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Rc<[u32]>>(&bytes).unwrap());
+    assert_eq!(&value[..], &values[..]);
+    assert_eq!(n.count, 1);
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Box<[u32]>>(&bytes).unwrap());
+    assert_eq!(&value[..], &values[..]);
+    assert_eq!(n.count, 1);
+}
+
+#[test]
+fn shared_strings() {
+    let bytes = musli::storage::to_vec("Hello World").unwrap();
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Arc<str>>(&bytes).unwrap());
+    assert_eq!(&*value, "Hello World");
+    assert_eq!(n.count, 1);
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Rc<str>>(&bytes).unwrap());
+    assert_eq!(&*value, "Hello World");
+    assert_eq!(n.count, 1);
+
+    let (value, n) = allocations(|| musli::storage::from_slice::<Box<str>>(&bytes).unwrap());
+    assert_eq!(&*value, "Hello World");
+    assert_eq!(n.count, 1);
+}
+
+#[test]
+fn shared_slice_error() {
+    let values = (0..4).map(|n| n.to_string()).collect::<Vec<_>>();
+    let bytes = musli::storage::to_vec(&values).unwrap();
+
+    let value = musli::storage::from_slice::<Arc<[String]>>(&bytes).unwrap();
+    assert_eq!(&value[..], &values[..]);
+
+    // Truncated input fails after some elements have been decoded, which
+    // must be dropped properly.
+    let result = musli::storage::from_slice::<Arc<[String]>>(&bytes[..bytes.len() - 1]);
+    assert!(result.is_err());
+}
+
+#[derive(Debug, PartialEq, musli::Encode, musli::Decode)]
+struct Shared {
+    numbers: Arc<[u32]>,
+    strings: Rc<[String]>,
+    name: Arc<str>,
+}
+
+#[test]
+fn shared_roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Shared {
+            numbers: Arc::from([1, 2, 3]),
+            strings: Rc::from([String::from("a"), String::from("b")]),
+            name: Arc::from("shared"),
+        },
+        json = r#"{"numbers":[1,2,3],"strings":["a","b"],"name":"shared"}"#
+    );
+}
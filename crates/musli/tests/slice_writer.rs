@@ -0,0 +1,64 @@
+//! Test encoding into a fixed slice, which reports the number of bytes
+//! written.
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn person() -> Person {
+    Person {
+        name: String::from("Aristotle"),
+        age: 61,
+    }
+}
+
+macro_rules! test_format {
+    ($name:ident, $what:ident) => {
+        mod $name {
+            use super::*;
+
+            #[test]
+            fn exact_fit() {
+                let expected = musli::$what::to_vec(&person()).unwrap();
+
+                let mut out = vec![0; expected.len()];
+                let written = musli::$what::to_slice(&mut out, &person()).unwrap();
+                assert_eq!(written, expected.len());
+                assert_eq!(out, expected);
+            }
+
+            #[test]
+            fn underfilled() {
+                let expected = musli::$what::to_vec(&person()).unwrap();
+
+                let mut out = vec![0xff; expected.len() + 16];
+                let written = musli::$what::to_slice(&mut out, &person()).unwrap();
+                assert_eq!(written, expected.len());
+                assert_eq!(&out[..written], &expected[..]);
+                assert!(out[written..].iter().all(|&b| b == 0xff));
+
+                let decoded: Person = musli::$what::from_slice(&out[..written]).unwrap();
+                assert_eq!(decoded, person());
+            }
+
+            #[test]
+            fn overflow() {
+                let expected = musli::$what::to_vec(&person()).unwrap();
+
+                let mut out = vec![0; expected.len() - 1];
+                let error = musli::$what::to_slice(&mut out, &person()).unwrap_err();
+                let required = format!("(at least {} bytes are required)", expected.len());
+                assert!(error.to_string().contains(&required), "{error}");
+            }
+        }
+    };
+}
+
+test_format!(storage, storage);
+test_format!(wire, wire);
+test_format!(descriptive, descriptive);
+test_format!(json, json);
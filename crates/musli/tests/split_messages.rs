@@ -0,0 +1,84 @@
+//! Test splitting concatenated self-describing messages apart without decoding
+//! them.
+
+use std::collections::HashMap;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    id: u64,
+    body: String,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+enum Event {
+    Ping,
+    Data(#[musli(bytes)] Vec<u8>),
+    Scores(HashMap<String, f64>),
+}
+
+macro_rules! test_split {
+    ($what:ident) => {
+        #[test]
+        fn $what() -> Result<(), musli::$what::Error> {
+            let mut data = Vec::new();
+            let mut ranges = Vec::new();
+
+            for n in 0..1000u64 {
+                let start = data.len();
+
+                match n % 5 {
+                    0 => musli::$what::encode(&mut data, &n)?,
+                    1 => musli::$what::encode(&mut data, &format!("message {n}"))?,
+                    2 => musli::$what::encode(
+                        &mut data,
+                        &Message {
+                            id: n,
+                            body: "x".repeat(n as usize),
+                            tags: vec![String::from("a"); n as usize % 7],
+                        },
+                    )?,
+                    3 => musli::$what::encode(&mut data, &Event::Data(vec![n as u8; n as usize]))?,
+                    _ => musli::$what::encode(
+                        &mut data,
+                        &Event::Scores(HashMap::from([(n.to_string(), n as f64)])),
+                    )?,
+                }
+
+                ranges.push(start..data.len());
+            }
+
+            let values = musli::$what::split(&data).collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(values.len(), ranges.len());
+
+            for (value, range) in values.iter().zip(&ranges) {
+                assert_eq!(*value, &data[range.clone()]);
+                assert_eq!(musli::$what::length_of(&data[range.start..])?, range.len());
+            }
+
+            let message: Message = musli::$what::from_slice(values[2])?;
+            assert_eq!(message.id, 2);
+            assert_eq!(musli::$what::from_slice::<u64>(values[995])?, 995);
+            assert_eq!(
+                musli::$what::from_slice::<Event>(values[998])?,
+                Event::Data(vec![230; 998])
+            );
+
+            // A truncated batch produces each complete value followed by an
+            // error.
+            let truncated = &data[..ranges[999].end - 1];
+            let mut split = musli::$what::split(truncated);
+            assert_eq!(split.by_ref().take(999).filter(|v| v.is_ok()).count(), 999);
+            assert!(split.next().is_some_and(|v| v.is_err()));
+            assert!(split.next().is_none());
+
+            assert!(musli::$what::split(&[]).next().is_none());
+            Ok(())
+        }
+    };
+}
+
+test_split!(wire);
+test_split!(descriptive);
@@ -0,0 +1,112 @@
+//! Test decoding storage values from input which arrives in pieces.
+
+#![cfg(feature = "test")]
+
+use musli::reader::Incremental;
+use musli::storage::Encoding;
+use musli::{Decode, Encode};
+
+const ENCODING: Encoding = Encoding::new();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    id: u32,
+    tags: Vec<String>,
+    payload: Vec<u8>,
+}
+
+fn message() -> Message {
+    Message {
+        id: 1000,
+        tags: vec![String::from("first"), String::from("second")],
+        payload: vec![7; 300],
+    }
+}
+
+#[test]
+fn every_prefix_needs_more() {
+    let data = ENCODING.to_vec(&message()).unwrap();
+
+    for len in 0..data.len() {
+        match ENCODING
+            .decode_incremental::<Message>(&data[..len])
+            .unwrap()
+        {
+            Incremental::NeedMore(n) => {
+                assert!(n > 0, "{len}");
+                assert!(len + n <= data.len(), "{len} + {n} > {}", data.len());
+            }
+            Incremental::Complete(..) => panic!("{len}: decoded from incomplete input"),
+        }
+    }
+
+    assert_eq!(
+        ENCODING.decode_incremental::<Message>(&data).unwrap(),
+        Incremental::Complete(message(), data.len())
+    );
+}
+
+#[test]
+fn feed_chunks() {
+    let mut data = ENCODING.to_vec(&message()).unwrap();
+    let first = data.len();
+    ENCODING.encode(&mut data, &message()).unwrap();
+
+    for chunk in [1, 5, 64, data.len()] {
+        let mut buf = Vec::new();
+        let mut chunks = data.chunks(chunk);
+        let mut messages = Vec::new();
+        let mut retries = 0;
+
+        while messages.len() < 2 {
+            match ENCODING.decode_incremental::<Message>(&buf).unwrap() {
+                Incremental::Complete(message, len) => {
+                    buf.drain(..len);
+                    messages.push(message);
+                }
+                Incremental::NeedMore(..) => {
+                    retries += 1;
+                    buf.extend_from_slice(chunks.next().unwrap());
+                }
+            }
+        }
+
+        assert!(buf.is_empty());
+        assert!(retries <= data.len().div_ceil(chunk));
+        assert_eq!(messages, [message(), message()]);
+    }
+
+    assert_eq!(
+        ENCODING
+            .decode_incremental::<Message>(&data[..first + 1])
+            .unwrap(),
+        Incremental::Complete(message(), first)
+    );
+}
+
+#[test]
+fn needed_length() {
+    // A string is stored as its length followed by its contents, so once the
+    // length is known the remaining number of bytes is as well.
+    let data = ENCODING.to_vec(&"a".repeat(100)).unwrap();
+    let prefix = data.len() - 100;
+
+    assert_eq!(
+        ENCODING
+            .decode_incremental::<String>(&data[..prefix + 10])
+            .unwrap(),
+        Incremental::NeedMore(90)
+    );
+}
+
+#[test]
+fn invalid_input() {
+    // Invalid input is an error, even if the input ends later on.
+    let mut data = ENCODING
+        .to_vec(&vec![String::from("hello"), String::from("world")])
+        .unwrap();
+    data[2] = 0xff;
+    data.truncate(data.len() - 2);
+
+    assert!(ENCODING.decode_incremental::<Vec<String>>(&data).is_err());
+}
@@ -0,0 +1,184 @@
+#![cfg(feature = "test")]
+
+use std::fs::{self, OpenOptions};
+use std::path::Path;
+
+use musli::storage::log::{self, LogEnd, LogReader, LogWriter};
+use musli::{Decode, Encode};
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Record {
+    id: u32,
+    name: String,
+    data: Vec<u8>,
+}
+
+fn records() -> Vec<Record> {
+    (0..4)
+        .map(|id| Record {
+            id,
+            name: format!("record {id}"),
+            data: vec![id as u8; id as usize * 3],
+        })
+        .collect()
+}
+
+/// Write the records, returning the log and the offset where each record
+/// ends.
+fn write(records: &[Record], checksum: bool) -> (Vec<u8>, Vec<usize>) {
+    let mut writer = LogWriter::new(Vec::new()).with_checksum(checksum);
+    let mut ends = Vec::new();
+    let mut end = 0;
+
+    for record in records {
+        end += writer.append(record).unwrap();
+        ends.push(end);
+    }
+
+    (writer.into_inner(), ends)
+}
+
+#[test]
+fn truncate_at_every_position() {
+    let records = records();
+
+    for checksum in [true, false] {
+        let prefix = if checksum { 9 } else { 5 };
+        let (log, ends) = write(&records, checksum);
+        assert_eq!(ends.last(), Some(&log.len()));
+
+        for cut in 0..=log.len() {
+            let mut reader = LogReader::new(&log[..cut]);
+            let mut read = Vec::new();
+
+            while let Some(record) = reader.read::<Record>().unwrap() {
+                read.push(record);
+            }
+
+            let complete = ends.iter().take_while(|&&end| end <= cut).count();
+            assert_eq!(read, records[..complete], "cut at {cut}");
+
+            let start = complete.checked_sub(1).map_or(0, |n| ends[n]);
+            assert_eq!(reader.valid_len(), start as u64, "cut at {cut}");
+
+            let expected = if cut == start {
+                LogEnd::Clean
+            } else if cut - start < prefix {
+                LogEnd::TornPrefix
+            } else {
+                LogEnd::TornPayload
+            };
+
+            assert_eq!(reader.end(), Some(expected), "cut at {cut}");
+
+            // Reading past the end keeps reporting the same end.
+            assert_eq!(reader.read_record().unwrap(), None);
+            assert_eq!(reader.end(), Some(expected));
+        }
+    }
+}
+
+#[test]
+fn corrupt_records() {
+    let records = records();
+    let (log, ends) = write(&records, true);
+
+    // Flipping any bit in the length, checksum or payload of a record is
+    // detected, and reading stops right before it.
+    for (n, &end) in ends.iter().enumerate() {
+        let start = n.checked_sub(1).map_or(0, |n| ends[n]);
+
+        for at in start + 1..end {
+            let mut log = log.clone();
+            log[at] ^= 0x10;
+
+            let mut reader = LogReader::new(&log[..]);
+
+            for record in &records[..n] {
+                assert_eq!(reader.read::<Record>().unwrap().as_ref(), Some(record));
+            }
+
+            assert_eq!(reader.read::<Record>().unwrap(), None, "flip at {at}");
+            assert_eq!(reader.valid_len(), start as u64);
+
+            // A corrupt length can make the record appear to extend past the
+            // end of the log.
+            let end = reader.end().unwrap();
+            assert!(
+                matches!(end, LogEnd::ChecksumMismatch | LogEnd::TornPayload),
+                "flip at {at}: {end:?}"
+            );
+        }
+
+        let mut log = log.clone();
+        log[start] = 0xff;
+
+        let mut reader = LogReader::new(&log[..]);
+        while reader.read_record().unwrap().is_some() {}
+        assert_eq!(reader.end(), Some(LogEnd::InvalidTag));
+        assert_eq!(reader.valid_len(), start as u64);
+    }
+}
+
+#[test]
+fn mixed_checksums() {
+    let mut writer = LogWriter::new(Vec::new());
+    writer.append(&1u32).unwrap();
+
+    let mut writer = LogWriter::new(writer.into_inner()).with_checksum(false);
+    writer.append(&2u32).unwrap();
+
+    let log = writer.into_inner();
+    let mut reader = LogReader::new(&log[..]);
+    assert_eq!(reader.read::<u32>().unwrap(), Some(1));
+    assert_eq!(reader.read::<u32>().unwrap(), Some(2));
+    assert_eq!(reader.read::<u32>().unwrap(), None);
+    assert_eq!(reader.end(), Some(LogEnd::Clean));
+    assert_eq!(reader.valid_len(), log.len() as u64);
+}
+
+#[test]
+fn recover_file() {
+    let records = records();
+    let (log, ends) = write(&records, true);
+
+    let path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("storage_log_recover.log");
+
+    for cut in [
+        0,
+        ends[1],
+        ends[1] + 3,
+        ends[2] + 12,
+        log.len() - 1,
+        log.len(),
+    ] {
+        fs::write(&path, &log[..cut]).unwrap();
+
+        let valid_len = log::recover(&path).unwrap();
+        let complete = ends.iter().take_while(|&&end| end <= cut).count();
+        let start = complete.checked_sub(1).map_or(0, |n| ends[n]);
+        assert_eq!(valid_len, start as u64, "cut at {cut}");
+        assert_eq!(fs::metadata(&path).unwrap().len(), valid_len);
+
+        // Appending after recovery produces a clean log.
+        let file = OpenOptions::new().append(true).open(&path).unwrap();
+        let mut writer = LogWriter::new(file);
+        writer.append(&records[3]).unwrap();
+        drop(writer);
+
+        let data = fs::read(&path).unwrap();
+        let mut reader = LogReader::new(&data[..]);
+        let mut read = Vec::new();
+
+        while let Some(record) = reader.read::<Record>().unwrap() {
+            read.push(record);
+        }
+
+        let mut expected = records[..complete].to_vec();
+        expected.push(records[3].clone());
+        assert_eq!(read, expected);
+        assert_eq!(reader.end(), Some(LogEnd::Clean));
+    }
+
+    fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,97 @@
+//! Test decoding the entries of an encoded map one at a time.
+#![cfg(feature = "storage")]
+
+use std::collections::BTreeMap;
+
+use musli::options::{self, Integer, Options};
+use musli::storage::{Encoding, Error};
+use musli::{Decode, Encode};
+
+const ENCODING: Encoding = Encoding::new();
+
+const FIXED_OPTIONS: Options = options::new().integer(Integer::Fixed).build();
+const FIXED: Encoding<FIXED_OPTIONS> = Encoding::new().with_options();
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+struct Record {
+    name: String,
+    scores: Vec<u32>,
+}
+
+fn records() -> BTreeMap<u64, Record> {
+    (0..1000u64)
+        .rev()
+        .map(|n| {
+            let record = Record {
+                name: format!("record {n}"),
+                scores: (0..n as u32 % 5).collect(),
+            };
+
+            (n * 7, record)
+        })
+        .collect()
+}
+
+#[test]
+fn entries_in_order() -> Result<(), Error> {
+    let map = records();
+
+    let data = ENCODING.to_vec(&map)?;
+    let entries = ENCODING.decode_map_iter::<_, u64, Record>(&data[..])?;
+    assert_eq!(entries.remaining(), map.len());
+    let actual = entries.collect::<Result<Vec<_>, _>>()?;
+    assert!(actual.iter().map(|(k, v)| (k, v)).eq(&map));
+
+    let data = FIXED.to_vec(&map)?;
+    let entries = FIXED.decode_map_iter::<_, u64, Record>(&data[..])?;
+    let actual = entries.collect::<Result<Vec<_>, _>>()?;
+    assert!(actual.iter().map(|(k, v)| (k, v)).eq(&map));
+    Ok(())
+}
+
+#[test]
+fn reader_after_map() -> Result<(), Error> {
+    let map = BTreeMap::from([(1u32, String::from("one")), (2, String::from("two"))]);
+
+    let mut data = ENCODING.to_vec(&map)?;
+    data.extend_from_slice(&[0xde, 0xad]);
+
+    let mut slice = &data[..];
+    let entries = ENCODING.decode_map_iter::<_, u32, String>(&mut slice)?;
+    assert_eq!(entries.count(), 2);
+    assert_eq!(slice, &[0xde, 0xad]);
+
+    let empty = ENCODING.to_vec(&BTreeMap::<u32, u32>::new())?;
+    let mut entries = ENCODING.decode_map_iter::<_, u32, u32>(&empty[..])?;
+    assert!(entries.next().is_none());
+
+    assert!(ENCODING.decode_map_iter::<_, u32, u32>(&[][..]).is_err());
+    Ok(())
+}
+
+#[test]
+fn error_ends_iteration() -> Result<(), Error> {
+    let map = records();
+    let data = ENCODING.to_vec(&map)?;
+
+    // Truncated input produces every complete entry, then an error.
+    let truncated = &data[..data.len() - 1];
+    let mut entries = ENCODING.decode_map_iter::<_, u64, Record>(truncated)?;
+    let complete = entries.by_ref().take(map.len() - 1);
+    assert!(complete
+        .map(|e| e.unwrap())
+        .eq(map.clone().into_iter().take(map.len() - 1)));
+    assert!(entries.next().is_some_and(|e| e.is_err()));
+    assert!(entries.next().is_none());
+    assert_eq!(entries.remaining(), 0);
+
+    // A value which isn't valid for the requested type fails in the middle of
+    // the map, after which no further entries are produced.
+    let map = BTreeMap::from([(1u32, vec![b'a']), (2, vec![0xff]), (3, vec![b'c'])]);
+    let data = ENCODING.to_vec(&map)?;
+    let mut entries = ENCODING.decode_map_iter::<_, u32, String>(&data[..])?;
+    assert_eq!(entries.next().transpose()?, Some((1, String::from("a"))));
+    assert!(entries.next().is_some_and(|e| e.is_err()));
+    assert!(entries.next().is_none());
+    Ok(())
+}
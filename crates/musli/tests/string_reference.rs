@@ -20,3 +20,49 @@ fn string_reference() {
     assert_eq!(with_str.name, "Jane Doe");
     assert_eq!(with_str.age, 42);
 }
+
+/// Test that a decoded string points into the input it was decoded from.
+fn assert_borrowed(data: &[u8], string: &str) {
+    let range = data.as_ptr_range();
+    let ptr = string.as_ptr();
+    assert!(
+        range.contains(&ptr),
+        "string was not borrowed from the input"
+    );
+}
+
+macro_rules! test_borrowed {
+    ($format:ident) => {{
+        let value = StructWithStr {
+            name: "Jane Doe with a name long enough to not be inlined anywhere",
+            age: 42,
+        };
+
+        let data = musli::$format::to_vec(&value).unwrap();
+
+        let decoded: StructWithStr<'_> = musli::$format::from_slice(&data).unwrap();
+        assert_eq!(decoded, value);
+        assert_borrowed(&data, decoded.name);
+
+        // Decoding through a mutable slice reader also borrows.
+        let mut slice = &data[..];
+        let decoded: StructWithStr<'_> = musli::$format::decode(&mut slice).unwrap();
+        assert_eq!(decoded, value);
+        assert_borrowed(&data, decoded.name);
+
+        // Invalid UTF-8 is rejected when borrowing.
+        let mut data = data.clone();
+        let at = data.windows(4).position(|w| w == b"Jane").unwrap();
+        data[at] = 0xff;
+        assert!(musli::$format::from_slice::<StructWithStr<'_>>(&data).is_err());
+    }};
+}
+
+#[test]
+fn borrowed_str() {
+    test_borrowed!(storage);
+    test_borrowed!(packed);
+    test_borrowed!(wire);
+    test_borrowed!(descriptive);
+    test_borrowed!(json);
+}
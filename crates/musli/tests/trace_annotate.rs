@@ -0,0 +1,138 @@
+use musli::alloc::Allocator;
+use musli::context;
+use musli::de::SequenceDecoder;
+use musli::{Context, Decode, Decoder, Encode};
+
+#[derive(Encode)]
+struct FromAddress {
+    zip: i64,
+}
+
+#[derive(Debug, PartialEq, Decode)]
+struct Address {
+    zip: u32,
+}
+
+#[derive(Debug, PartialEq)]
+struct Record {
+    id: u32,
+    address: Address,
+}
+
+impl<'de, M, A> Decode<'de, M, A> for Record
+where
+    A: Allocator,
+    Address: Decode<'de, M, A>,
+{
+    #[inline]
+    fn decode<D>(decoder: D) -> Result<Self, D::Error>
+    where
+        D: Decoder<'de, Mode = M, Allocator = A>,
+    {
+        decoder.decode_pack(|pack| {
+            let id = pack.next()?;
+            pack.cx().annotate("record", id);
+            let address = pack.next()?;
+            Ok(Record { id, address })
+        })
+    }
+}
+
+#[derive(Decode)]
+struct Records {
+    records: Vec<Record>,
+}
+
+#[derive(Encode)]
+struct FromRecords {
+    records: Vec<(u32, FromAddress)>,
+}
+
+#[test]
+fn trace_annotate() {
+    musli::alloc::default(|alloc| {
+        let cx = context::new_in(alloc).with_trace();
+
+        let from = FromRecords {
+            records: vec![
+                (4820, FromAddress { zip: 12345 }),
+                (4821, FromAddress { zip: -1 }),
+            ],
+        };
+
+        let encoding = musli::json::Encoding::new();
+
+        let bytes = encoding.to_vec(&FromRecords {
+            records: vec![(4819, FromAddress { zip: 54321 })],
+        });
+
+        let records = encoding
+            .from_slice::<Records>(&bytes.unwrap())
+            .unwrap()
+            .records;
+
+        assert_eq!(
+            records,
+            [Record {
+                id: 4819,
+                address: Address { zip: 54321 },
+            }]
+        );
+
+        let Ok(bytes) = encoding.to_vec_with(&cx, &from) else {
+            if let Some(error) = cx.errors().next() {
+                panic!("{error}");
+            }
+
+            unreachable!()
+        };
+
+        let Ok(..) = encoding.from_slice_with::<_, Records>(&cx, &bytes) else {
+            let error = cx.errors().next().unwrap();
+            assert_eq!(error.annotation("record"), Some("4821"));
+            assert_eq!(
+                error.annotations().collect::<Vec<_>>(),
+                [("record", "4821")]
+            );
+            assert_eq!(
+                error.to_string(),
+                ".records[1].zip: Invalid numeric (at bytes 46-47) [record = 4821]"
+            );
+            return;
+        };
+
+        panic!("decoding should fail");
+    });
+}
+
+#[test]
+fn annotations_per_error() {
+    musli::alloc::default(|alloc| {
+        let cx = context::new_in(alloc).with_trace();
+
+        cx.annotate("record", 1);
+        _ = cx.message("first");
+        cx.annotate("record", 2);
+        cx.annotate("field", "name");
+        _ = cx.message("second");
+
+        let errors = cx.errors().collect::<Vec<_>>();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(
+            errors[0].annotations().collect::<Vec<_>>(),
+            [("record", "1")]
+        );
+        assert_eq!(
+            errors[1].annotations().collect::<Vec<_>>(),
+            [("record", "2"), ("field", "name")]
+        );
+    });
+}
+
+#[test]
+fn annotations_ignored_without_trace() {
+    let cx = context::new().with_error::<std::io::Error>();
+    cx.annotate("record", 1);
+    let error = cx.message("failed");
+    assert_eq!(error.to_string(), "failed");
+}
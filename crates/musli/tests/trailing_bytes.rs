@@ -0,0 +1,98 @@
+//! Test that exact decoding rejects trailing bytes, and that partial decoding
+//! reports how many bytes a value was decoded from.
+
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Person {
+    name: String,
+    age: u32,
+}
+
+fn person(name: &str, age: u32) -> Person {
+    Person {
+        name: String::from(name),
+        age,
+    }
+}
+
+macro_rules! test_format {
+    ($format:ident) => {{
+        use musli::$format;
+
+        let first = person("Aristotle", 61);
+        let second = person("Plato", 80);
+
+        let data = $format::to_vec(&first).unwrap();
+
+        // Exact input.
+        assert_eq!($format::from_slice_exact::<Person>(&data).unwrap(), first);
+        assert_eq!(
+            $format::from_slice_partial::<Person>(&data).unwrap(),
+            (person("Aristotle", 61), data.len())
+        );
+
+        // Trailing zeros.
+        let mut zeros = data.clone();
+        zeros.extend_from_slice(&[0, 0, 0]);
+        assert_eq!($format::from_slice::<Person>(&zeros).unwrap(), first);
+
+        let error = $format::from_slice_exact::<Person>(&zeros).unwrap_err();
+        assert_eq!(error.to_string(), "3 trailing bytes after value");
+
+        // Trailing data which looks like another value.
+        let mut both = data.clone();
+        both.extend($format::to_vec(&second).unwrap());
+
+        let error = $format::from_slice_exact::<Person>(&both).unwrap_err();
+        let trailing = both.len() - data.len();
+        assert_eq!(
+            error.to_string(),
+            format!("{trailing} trailing bytes after value")
+        );
+
+        // Decoding concatenated values one after another.
+        let (a, n) = $format::from_slice_partial::<Person>(&both).unwrap();
+        let (b, m) = $format::from_slice_partial::<Person>(&both[n..]).unwrap();
+        assert_eq!(a, first);
+        assert_eq!(b, second);
+        assert_eq!(n, data.len());
+        assert_eq!(n + m, both.len());
+    }};
+}
+
+#[test]
+fn storage() {
+    test_format!(storage);
+}
+
+#[test]
+fn packed() {
+    test_format!(packed);
+}
+
+#[test]
+fn wire() {
+    test_format!(wire);
+}
+
+#[test]
+fn descriptive() {
+    test_format!(descriptive);
+}
+
+#[test]
+fn json() {
+    test_format!(json);
+
+    // Trailing whitespace is not considered to be part of the trailing data.
+    let data = b"{\"name\":\"Aristotle\",\"age\":61} \n\t\r";
+    let value = musli::json::from_slice_exact::<Person>(data).unwrap();
+    assert_eq!(value, person("Aristotle", 61));
+
+    let data = b"{\"name\":\"Aristotle\",\"age\":61} 42";
+    let error = musli::json::from_slice_exact::<Person>(data).unwrap_err();
+    assert_eq!(error.to_string(), "2 trailing bytes after value");
+}
@@ -0,0 +1,109 @@
+//! Test transcoding between formats through `musli::transcode`.
+
+use musli::transcode::transcode;
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(name_all = "name")]
+struct Person {
+    name: String,
+    age: u32,
+    tags: Vec<String>,
+    children: Vec<Person>,
+}
+
+fn person() -> Person {
+    Person {
+        name: String::from("Plato"),
+        age: 80,
+        tags: vec![String::from("philosopher")],
+        children: vec![Person {
+            name: String::from("Aristotle"),
+            age: 61,
+            tags: Vec::new(),
+            children: Vec::new(),
+        }],
+    }
+}
+
+#[test]
+fn descriptive_to_json() -> Result<(), Box<dyn std::error::Error>> {
+    let bytes = musli::descriptive::to_vec(&person())?;
+
+    let mut json = Vec::new();
+
+    transcode(
+        musli::descriptive::Encoding::new(),
+        musli::json::Encoding::new(),
+        &bytes,
+        &mut json,
+    )?;
+
+    assert_eq!(
+        std::str::from_utf8(&json)?,
+        r#"{"name":"Plato","age":80,"tags":["philosopher"],"children":[{"name":"Aristotle","age":61,"tags":[],"children":[]}]}"#
+    );
+
+    assert_eq!(musli::json::from_slice::<Person>(&json)?, person());
+    Ok(())
+}
+
+#[test]
+fn json_to_every_format() -> Result<(), Box<dyn std::error::Error>> {
+    let json = musli::json::to_vec(&person())?;
+
+    let mut bytes = Vec::new();
+
+    transcode(
+        musli::json::Encoding::new(),
+        musli::descriptive::Encoding::new(),
+        &json,
+        &mut bytes,
+    )?;
+    assert_eq!(musli::descriptive::from_slice::<Person>(&bytes)?, person());
+
+    let mut bytes = Vec::new();
+
+    transcode(
+        musli::json::Encoding::new(),
+        musli::storage::Encoding::new(),
+        &json,
+        &mut bytes,
+    )?;
+    assert_eq!(musli::storage::from_slice::<Person>(&bytes)?, person());
+
+    let mut bytes = Vec::new();
+
+    transcode(
+        musli::json::Encoding::new(),
+        musli::wire::Encoding::new(),
+        &json,
+        &mut bytes,
+    )?;
+    assert_eq!(musli::wire::from_slice::<Person>(&bytes)?, person());
+
+    let mut bytes = Vec::new();
+
+    transcode(
+        musli::json::Encoding::new(),
+        musli::json::Encoding::new(),
+        &json,
+        &mut bytes,
+    )?;
+    assert_eq!(bytes, json);
+    Ok(())
+}
+
+#[test]
+fn invalid_input() {
+    let mut output = Vec::new();
+
+    let result = transcode(
+        musli::json::Encoding::new(),
+        musli::descriptive::Encoding::new(),
+        br#"{"name": "#,
+        &mut output,
+    );
+
+    assert!(result.is_err());
+}
@@ -1,3 +1,5 @@
+use core::fmt;
+
 use musli::{Decode, Encode};
 
 #[test]
@@ -27,3 +29,108 @@ enum Enum {
 fn tuple_enum() {
     musli::macros::assert_roundtrip_eq!(full, Enum::Tuple(11, 13));
 }
+
+/// Tuples with more than 12 elements implement neither `Debug` nor
+/// `PartialEq`, so they are wrapped in a transparent newtype which does.
+macro_rules! large_tuple {
+    ($name:ident, $($index:tt),*) => {
+        #[derive(Encode, Decode)]
+        #[musli(transparent)]
+        struct $name(($(large_tuple!(@ty $index),)*));
+
+        impl $name {
+            fn new(base: u32) -> Self {
+                Self(($(base + $index,)*))
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                true $(&& (self.0).$index == (other.0).$index)*
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_list()$(.entry(&(self.0).$index))*.finish()
+            }
+        }
+    };
+
+    (@ty $index:tt) => { u32 };
+}
+
+large_tuple!(Tuple17, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16);
+large_tuple!(
+    Tuple32, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23,
+    24, 25, 26, 27, 28, 29, 30, 31
+);
+
+type Tuple12 = (
+    u8,
+    u16,
+    u32,
+    u64,
+    i8,
+    i16,
+    i32,
+    i64,
+    bool,
+    char,
+    String,
+    Option<u32>,
+);
+
+fn tuple12(base: u8) -> Tuple12 {
+    (
+        base,
+        u16::from(base) * 300,
+        u32::from(base) * 70_000,
+        u64::from(base) * 5_000_000_000,
+        -(base as i8),
+        -i16::from(base) * 300,
+        -i32::from(base) * 70_000,
+        -i64::from(base) * 5_000_000_000,
+        base % 2 == 0,
+        char::from(b'a' + base),
+        format!("element {base}"),
+        Some(u32::from(base)),
+    )
+}
+
+#[test]
+fn arities() {
+    musli::macros::assert_roundtrip_eq!(full, ());
+    musli::macros::assert_roundtrip_eq!(full, (42u32,));
+    musli::macros::assert_roundtrip_eq!(full, tuple12(1));
+    musli::macros::assert_roundtrip_eq!(full, Tuple17::new(100));
+    musli::macros::assert_roundtrip_eq!(full, Tuple32::new(1000));
+}
+
+#[test]
+fn nested_arities() {
+    musli::macros::assert_roundtrip_eq!(full, Some((42u32,)));
+    musli::macros::assert_roundtrip_eq!(full, Option::<(u32,)>::None);
+    musli::macros::assert_roundtrip_eq!(full, vec![(1u32,), (2u32,)]);
+
+    musli::macros::assert_roundtrip_eq!(full, Some(tuple12(2)));
+    musli::macros::assert_roundtrip_eq!(full, vec![tuple12(3), tuple12(4)]);
+
+    musli::macros::assert_roundtrip_eq!(full, Some(Tuple17::new(200)));
+    musli::macros::assert_roundtrip_eq!(full, Option::<Tuple17>::None);
+    musli::macros::assert_roundtrip_eq!(full, vec![Tuple17::new(300), Tuple17::new(400)]);
+
+    musli::macros::assert_roundtrip_eq!(full, Some(Tuple32::new(2000)));
+    musli::macros::assert_roundtrip_eq!(full, vec![Tuple32::new(3000), Tuple32::new(4000)]);
+}
+
+/// Tuples are encoded as sequences in every format, so they can be decoded as
+/// a sequence of the same length.
+#[test]
+fn tuples_are_sequences() {
+    let values: Vec<u32> = (100..117).collect();
+    musli::macros::assert_decode_eq!(full, Tuple17::new(100), values);
+
+    let values: Vec<u32> = (1000..1032).collect();
+    musli::macros::assert_decode_eq!(full, Tuple32::new(1000), values);
+}
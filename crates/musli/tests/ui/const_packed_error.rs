@@ -0,0 +1,30 @@
+use musli::Encode;
+
+#[derive(Encode)]
+#[musli(const_packed)]
+struct Tagged {
+    field: u32,
+}
+
+#[derive(Encode)]
+#[musli(packed, const_packed)]
+enum Enum {
+    Variant,
+}
+
+#[derive(Encode)]
+#[musli(packed, const_packed)]
+struct Allocating {
+    ok: u32,
+    values: Vec<u8>,
+    #[musli(bytes)]
+    bytes: [u16; 2],
+}
+
+#[derive(Encode)]
+#[musli(packed, const_packed)]
+struct NotConst {
+    name: String,
+}
+
+fn main() {}
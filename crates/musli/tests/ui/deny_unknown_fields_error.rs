@@ -0,0 +1,17 @@
+use musli::{Decode, Encode};
+
+#[derive(Decode)]
+#[musli(deny_unknown_fields)]
+enum Enum {
+    Variant,
+}
+
+#[derive(Encode, Decode)]
+#[musli(deny_unknown_fields)]
+struct Flatten {
+    field: u32,
+    #[musli(flatten)]
+    rest: std::collections::HashMap<String, u32>,
+}
+
+fn main() {}
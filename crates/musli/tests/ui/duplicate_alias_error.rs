@@ -0,0 +1,37 @@
+use musli::Decode;
+
+/// Alias conflicting with the name of another field.
+#[derive(Decode)]
+#[musli(name_all = "name")]
+struct Struct1 {
+    first: u32,
+    #[musli(alias = "first")]
+    second: u32,
+}
+
+/// Alias used by multiple fields.
+#[derive(Decode)]
+struct Struct2 {
+    #[musli(mode = Binary, alias = 10)]
+    first: u32,
+    #[musli(mode = Binary, alias = 10usize)]
+    second: u32,
+}
+
+/// Alias conflicting with the name of another variant.
+#[derive(Decode)]
+enum Enum {
+    First,
+    #[musli(mode = Binary, alias = 0)]
+    Second,
+}
+
+/// Alias combined with a pattern.
+#[derive(Decode)]
+struct Struct3 {
+    #[musli(mode = Binary, pattern = 2..=4, alias = 10)]
+    first: u32,
+}
+
+fn main() {
+}
@@ -0,0 +1,27 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(packed)]
+struct Packed {
+    a: u32,
+    #[musli(encode_only)]
+    b: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(transparent)]
+struct Transparent {
+    #[musli(decode_only)]
+    a: u32,
+}
+
+#[derive(Encode, Decode)]
+#[musli(packed)]
+struct PackedScoped {
+    a: u32,
+    #[musli(decode_only, skip)]
+    b: u32,
+}
+
+fn main() {
+}
@@ -0,0 +1,37 @@
+use std::collections::HashMap;
+
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(name_all = "name")]
+struct Multiple {
+    #[musli(flatten)]
+    a: HashMap<String, u32>,
+    #[musli(flatten)]
+    b: HashMap<String, u32>,
+}
+
+#[derive(Encode, Decode)]
+#[musli(packed)]
+struct Packed {
+    #[musli(flatten)]
+    a: HashMap<String, u32>,
+}
+
+#[derive(Encode, Decode)]
+#[musli(name_all = "name")]
+struct Skipped {
+    #[musli(flatten, skip)]
+    a: HashMap<String, u32>,
+}
+
+#[derive(Encode, Decode)]
+#[musli(name_all = "name")]
+enum Variant {
+    Struct {
+        #[musli(flatten)]
+        a: HashMap<String, u32>,
+    },
+}
+
+fn main() {}
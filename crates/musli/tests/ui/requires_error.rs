@@ -0,0 +1,9 @@
+use musli::Decode;
+
+#[derive(Decode)]
+#[musli(requires(borrow))]
+struct Unsupported {
+    field: u32,
+}
+
+fn main() {}
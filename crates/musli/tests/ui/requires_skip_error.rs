@@ -0,0 +1,12 @@
+use musli::{Decode, Encode};
+
+#[derive(Encode, Decode)]
+#[musli(requires(skip))]
+struct Config {
+    name: String,
+}
+
+fn main() {
+    let bytes = musli::storage::to_vec(&Config { name: String::new() }).unwrap();
+    let _: Config = musli::storage::from_slice(&bytes).unwrap();
+}
@@ -0,0 +1,85 @@
+//! Test that `#[musli(with = musli::compat::utf16)]` encodes strings as
+//! sequences of UTF-16 code units.
+
+#![cfg(feature = "test")]
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Utf16 {
+    #[musli(with = musli::compat::utf16)]
+    text: String,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Units {
+    text: Vec<u16>,
+}
+
+#[test]
+fn roundtrip() {
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Utf16 {
+            text: String::from("a€𝄞"),
+        },
+        json = r#"{"text":[97,8364,55348,56606]}"#
+    );
+
+    musli::macros::assert_roundtrip_eq!(
+        full,
+        Utf16 {
+            text: String::new(),
+        },
+        json = r#"{"text":[]}"#
+    );
+}
+
+#[test]
+fn same_as_units() {
+    let text = "hello 🎉";
+
+    let utf16 = Utf16 {
+        text: String::from(text),
+    };
+
+    let units = Units {
+        text: text.encode_utf16().collect(),
+    };
+
+    assert_eq!(
+        musli::storage::to_vec(&utf16).unwrap(),
+        musli::storage::to_vec(&units).unwrap()
+    );
+
+    assert_eq!(
+        musli::wire::to_vec(&utf16).unwrap(),
+        musli::wire::to_vec(&units).unwrap()
+    );
+
+    assert_eq!(
+        musli::descriptive::to_vec(&utf16).unwrap(),
+        musli::descriptive::to_vec(&units).unwrap()
+    );
+}
+
+#[test]
+fn lone_surrogates() {
+    for text in [
+        vec![0xd83c],
+        vec![0xdf89],
+        vec![0x61, 0xd83c, 0x61],
+        vec![0xdf89, 0xd83c],
+        vec![0xd83c, 0xd83c, 0xdf89],
+    ] {
+        let bytes = musli::storage::to_vec(&Units { text: text.clone() }).unwrap();
+        let error = musli::storage::from_slice::<Utf16>(&bytes).unwrap_err();
+        assert!(
+            error.to_string().contains("Lone surrogate"),
+            "{text:?}: {error}"
+        );
+
+        let json = musli::json::to_string(&Units { text: text.clone() }).unwrap();
+        assert!(musli::json::from_str::<Utf16>(&json).is_err(), "{text:?}");
+    }
+}
@@ -0,0 +1,144 @@
+//! Test that validation accepts and rejects the same inputs as decoding.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::fmt;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Document {
+    title: String,
+    tags: Vec<String>,
+    scores: Vec<u32>,
+    chunks: VecDeque<Box<str>>,
+    index: BTreeMap<u32, String>,
+    seen: HashSet<u64>,
+    body: Option<Box<[u8]>>,
+}
+
+fn document() -> Document {
+    Document {
+        title: String::from("Validation"),
+        tags: vec![String::from("a"), String::from("bb"), String::from("ccc")],
+        scores: vec![1, 2, 3, 4],
+        chunks: VecDeque::from([Box::from("first"), Box::from("second")]),
+        index: BTreeMap::from([(1, String::from("one")), (2, String::from("two"))]),
+        seen: HashSet::from([10, 20, 30]),
+        body: Some(Box::from(&b"body"[..])),
+    }
+}
+
+macro_rules! test_validate {
+    ($what:ident) => {{
+        let data = musli::$what::to_vec(&document())?;
+
+        musli::$what::validate::<Document>(&data)?;
+
+        for n in 0..data.len() {
+            let bytes = &data[..n];
+
+            assert_eq!(
+                musli::$what::validate::<Document>(bytes).is_ok(),
+                musli::$what::from_slice::<Document>(bytes).is_ok(),
+                concat!(stringify!($what), ": prefix of length {}"),
+                n
+            );
+        }
+    }};
+}
+
+#[test]
+fn validate_matches_decode() -> Result<(), Box<dyn std::error::Error>> {
+    test_validate!(storage);
+    test_validate!(packed);
+    test_validate!(wire);
+    test_validate!(descriptive);
+    test_validate!(json);
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+#[musli(into = String, try_from = String)]
+struct Name(String);
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.0
+    }
+}
+
+#[derive(Debug)]
+struct EmptyName;
+
+impl fmt::Display for EmptyName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "name is empty")
+    }
+}
+
+impl TryFrom<String> for Name {
+    type Error = EmptyName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            return Err(EmptyName);
+        }
+
+        Ok(Self(value))
+    }
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Named {
+    names: Vec<Name>,
+}
+
+#[test]
+fn validate_try_from() -> Result<(), Box<dyn std::error::Error>> {
+    let named = Named {
+        names: vec![Name(String::from("Aristotle")), Name(String::from("Plato"))],
+    };
+
+    let data = musli::storage::to_vec(&named)?;
+    assert!(musli::storage::from_slice::<Named>(&data).is_ok());
+    assert!(musli::storage::validate::<Named>(&data).is_ok());
+
+    let empty = Named {
+        names: vec![Name(String::from("Aristotle")), Name(String::new())],
+    };
+
+    let data = musli::storage::to_vec(&empty)?;
+    assert!(musli::storage::from_slice::<Named>(&data).is_err());
+    assert!(musli::storage::validate::<Named>(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn validate_invalid_utf8() -> Result<(), Box<dyn std::error::Error>> {
+    let mut data = musli::storage::to_vec(&String::from("hello"))?;
+    *data.last_mut().unwrap() = 0xff;
+
+    assert!(musli::storage::from_slice::<String>(&data).is_err());
+    assert!(musli::storage::validate::<String>(&data).is_err());
+    Ok(())
+}
+
+#[test]
+fn validation_discards_values() -> Result<(), Box<dyn std::error::Error>> {
+    const ENCODING: musli::wire::Encoding = musli::wire::Encoding::new();
+
+    let data = ENCODING.to_vec(&document())?;
+
+    let cx = musli::context::new()
+        .with_error::<musli::wire::Error>()
+        .with_validation();
+    let document: Document = ENCODING.from_slice_with(&cx, &data)?;
+
+    assert_eq!(document.title, "");
+    assert!(document.tags.is_empty());
+    assert!(document.chunks.is_empty());
+    assert!(document.index.is_empty());
+    assert!(document.seen.is_empty());
+    assert!(document.body.is_some());
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use std::collections::VecDeque;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Deques {
+    numbers: VecDeque<u32>,
+    strings: VecDeque<String>,
+}
+
+/// Construct a deque whose storage wraps around, so that `as_slices` returns
+/// two non-empty slices and the logical order differs from the storage order.
+fn wrapped<T>(mut push: impl FnMut(usize) -> T) -> VecDeque<T> {
+    let mut deque = VecDeque::with_capacity(8);
+
+    for n in 0..4 {
+        deque.push_back(push(n + 4));
+    }
+
+    for n in (0..4).rev() {
+        deque.push_front(push(n));
+    }
+
+    let (a, b) = deque.as_slices();
+    assert!(!a.is_empty() && !b.is_empty(), "deque storage should wrap");
+    deque
+}
+
+#[test]
+fn vec_deque_order() {
+    let decoded = musli::macros::assert_roundtrip_eq!(
+        full,
+        Deques {
+            numbers: wrapped(|n| n as u32),
+            strings: wrapped(|n| format!("s{n}")),
+        }
+    );
+
+    assert!(decoded.numbers.iter().copied().eq(0..8));
+    assert!(decoded
+        .strings
+        .iter()
+        .map(String::as_str)
+        .eq(["s0", "s1", "s2", "s3", "s4", "s5", "s6", "s7"]));
+}
+
+#[derive(Debug, PartialEq, Encode)]
+#[musli(packed)]
+struct PackedDeque {
+    #[musli(packed)]
+    numbers: VecDeque<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+#[musli(packed)]
+struct PackedArray {
+    #[musli(packed)]
+    numbers: [u32; 8],
+}
+
+#[test]
+fn packed_vec_deque_order() {
+    musli::macros::assert_decode_eq! {
+        full,
+        PackedDeque { numbers: wrapped(|n| n as u32) },
+        PackedArray { numbers: [0, 1, 2, 3, 4, 5, 6, 7] }
+    };
+}
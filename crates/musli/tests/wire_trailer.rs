@@ -0,0 +1,120 @@
+//! Test that messages with a trailer can be decoded by decoders which are not
+//! aware of it, and that decoders which are can decode messages without one.
+
+#![cfg(feature = "test")]
+
+use musli::options::{self, Integer, Options};
+use musli::wire::Encoding;
+use musli::{Decode, Encode};
+
+const DEFAULT: Encoding = Encoding::new();
+
+const FIXED_OPTIONS: Options = options::new().integer(Integer::Fixed).build();
+const FIXED: Encoding<FIXED_OPTIONS> = Encoding::new().with_options();
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Message {
+    name: String,
+    values: Vec<u32>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+struct Trailer {
+    trace: u64,
+    checksum: u32,
+}
+
+fn message() -> Message {
+    Message {
+        name: String::from("Aristotle"),
+        values: vec![1, 2, 3],
+    }
+}
+
+const TRAILER: Trailer = Trailer {
+    trace: 0xdead_beef_dead_beef,
+    checksum: 42,
+};
+
+macro_rules! test_encoding {
+    ($encoding:expr) => {{
+        let encoding = $encoding;
+
+        let mut new = Vec::new();
+        encoding
+            .encode_with_trailer(&mut new, &message(), &TRAILER)
+            .unwrap();
+        let old = encoding.to_vec(&message()).unwrap();
+
+        assert!(new.starts_with(&old));
+
+        // Old decoder, new payload.
+        assert_eq!(encoding.from_slice::<Message>(&new).unwrap(), message());
+
+        let mut reader = &new[..];
+        assert_eq!(
+            encoding.decode::<_, Message>(&mut reader).unwrap(),
+            message()
+        );
+        assert_eq!(reader, &new[old.len()..]);
+
+        // New decoder, new payload.
+        let (value, trailer) = encoding
+            .decode_with_trailer::<_, Message, Trailer>(&new[..])
+            .unwrap();
+        assert_eq!(value, message());
+        assert_eq!(trailer, Some(TRAILER));
+
+        // New decoder, old payload.
+        let (value, trailer) = encoding
+            .decode_with_trailer::<_, Message, Trailer>(&old[..])
+            .unwrap();
+        assert_eq!(value, message());
+        assert_eq!(trailer, None);
+
+        // Old decoder, old payload.
+        assert_eq!(encoding.from_slice::<Message>(&old).unwrap(), message());
+    }};
+}
+
+#[test]
+fn default_encoding() {
+    test_encoding!(DEFAULT);
+}
+
+#[test]
+fn fixed_encoding() {
+    test_encoding!(FIXED);
+}
+
+#[test]
+fn data_after_trailer() {
+    let mut data = Vec::new();
+    DEFAULT
+        .encode_with_trailer(&mut data, &message(), &TRAILER)
+        .unwrap();
+    let len = data.len();
+    data.extend_from_slice(&[1, 2, 3]);
+
+    let mut reader = &data[..];
+    let (value, trailer) = DEFAULT
+        .decode_with_trailer::<_, Message, Trailer>(&mut reader)
+        .unwrap();
+    assert_eq!(value, message());
+    assert_eq!(trailer, Some(TRAILER));
+    assert_eq!(reader, &data[len..]);
+}
+
+#[test]
+fn truncated_trailer() {
+    let mut data = Vec::new();
+    DEFAULT
+        .encode_with_trailer(&mut data, &message(), &TRAILER)
+        .unwrap();
+    data.pop();
+
+    assert!(DEFAULT
+        .decode_with_trailer::<_, Message, Trailer>(&data[..])
+        .is_err());
+    assert_eq!(DEFAULT.from_slice::<Message>(&data).unwrap(), message());
+}
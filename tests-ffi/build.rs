@@ -0,0 +1,4 @@
+fn main() {
+    println!("cargo:rerun-if-changed=c/harness.c");
+    cc::Build::new().file("c/harness.c").compile("harness");
+}
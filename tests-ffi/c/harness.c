@@ -0,0 +1,83 @@
+// A C harness which drives the functions exported through `musli::ffi`.
+//
+// Every function returns 0 on success, or the line of the first failed check.
+
+#include <stddef.h>
+#include <stdint.h>
+#include <string.h>
+
+typedef enum {
+    STATUS_OK = 0,
+    STATUS_NULL_POINTER = 1,
+    STATUS_DECODE = 2,
+    STATUS_PANIC = 3,
+} Status;
+
+typedef struct Person Person;
+
+typedef struct {
+    double x;
+    double y;
+    uint8_t tag;
+} Point;
+
+extern const char *musli_last_error(void);
+extern void musli_clear_last_error(void);
+
+extern Status musli_wire_decode_person(const uint8_t *ptr, size_t len, Person **out);
+extern void musli_wire_free_person(Person *value);
+extern const uint8_t *person_name(const Person *person, size_t *len);
+extern uint32_t person_age(const Person *person);
+
+extern Status musli_wire_decode_point(const uint8_t *ptr, size_t len, Point *out);
+
+#define CHECK(cond) do { if (!(cond)) { return __LINE__; } } while (0)
+
+int harness_person(const uint8_t *ptr, size_t len) {
+    Person *person = NULL;
+    CHECK(musli_wire_decode_person(ptr, len, &person) == STATUS_OK);
+    CHECK(person != NULL);
+    CHECK(musli_last_error() == NULL);
+
+    size_t name_len = 0;
+    const uint8_t *name = person_name(person, &name_len);
+    CHECK(name_len == strlen("Aristotle"));
+    CHECK(memcmp(name, "Aristotle", name_len) == 0);
+    CHECK(person_age(person) == 61);
+
+    musli_wire_free_person(person);
+    musli_wire_free_person(NULL);
+    return 0;
+}
+
+int harness_point(const uint8_t *ptr, size_t len) {
+    Point point = { 0.0, 0.0, 0 };
+    CHECK(musli_wire_decode_point(ptr, len, &point) == STATUS_OK);
+    CHECK(point.x == 1.5);
+    CHECK(point.y == -2.0);
+    CHECK(point.tag == 7);
+    return 0;
+}
+
+int harness_errors(const uint8_t *ptr, size_t len) {
+    Person *person = NULL;
+
+    CHECK(musli_wire_decode_person(ptr, len, &person) == STATUS_DECODE);
+    CHECK(person == NULL);
+    CHECK(musli_last_error() != NULL);
+    CHECK(strlen(musli_last_error()) > 0);
+
+    musli_clear_last_error();
+    CHECK(musli_last_error() == NULL);
+
+    CHECK(musli_wire_decode_person(NULL, 4, &person) == STATUS_NULL_POINTER);
+    CHECK(strstr(musli_last_error(), "ptr") != NULL);
+
+    CHECK(musli_wire_decode_person(ptr, len, NULL) == STATUS_NULL_POINTER);
+    CHECK(strstr(musli_last_error(), "out") != NULL);
+
+    Point point = { 1.0, 2.0, 3 };
+    CHECK(musli_wire_decode_point(ptr, len, &point) == STATUS_DECODE);
+    CHECK(point.x == 1.0 && point.y == 2.0 && point.tag == 3);
+    return 0;
+}
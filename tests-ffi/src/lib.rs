@@ -0,0 +1,56 @@
+//! Types exported through `musli::ffi`, which are exercised by the C harness
+//! in `c/harness.c`.
+
+use musli::{Decode, Encode};
+
+/// A value which is handed out as an opaque handle.
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Person {
+    pub name: String,
+    pub age: u32,
+}
+
+musli::ffi::export_decode!(
+    Person,
+    decode = musli_wire_decode_person,
+    free = musli_wire_free_person
+);
+
+/// Get the name of a person, writing its length to `len`.
+///
+/// # Safety
+///
+/// The person must have been returned by `musli_wire_decode_person`.
+#[no_mangle]
+pub unsafe extern "C" fn person_name(person: *const Person, len: *mut usize) -> *const u8 {
+    let name = &(*person).name;
+    len.write(name.len());
+    name.as_ptr()
+}
+
+/// Get the age of a person.
+///
+/// # Safety
+///
+/// The person must have been returned by `musli_wire_decode_person`.
+#[no_mangle]
+pub unsafe extern "C" fn person_age(person: *const Person) -> u32 {
+    (*person).age
+}
+
+/// A value which is decoded into a mirror declared by the caller.
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+#[repr(C)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+    pub tag: u8,
+}
+
+musli::ffi::export_decode!(Point, decode_into = musli_wire_decode_point);
+
+extern "C" {
+    pub fn harness_person(ptr: *const u8, len: usize) -> i32;
+    pub fn harness_point(ptr: *const u8, len: usize) -> i32;
+    pub fn harness_errors(ptr: *const u8, len: usize) -> i32;
+}
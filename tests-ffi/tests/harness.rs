@@ -0,0 +1,30 @@
+use tests_ffi::{harness_errors, harness_person, harness_point, Person, Point};
+
+#[test]
+fn person() {
+    let bytes = musli::wire::to_vec(&Person {
+        name: String::from("Aristotle"),
+        age: 61,
+    })
+    .unwrap();
+
+    assert_eq!(unsafe { harness_person(bytes.as_ptr(), bytes.len()) }, 0);
+}
+
+#[test]
+fn point() {
+    let bytes = musli::wire::to_vec(&Point {
+        x: 1.5,
+        y: -2.0,
+        tag: 7,
+    })
+    .unwrap();
+
+    assert_eq!(unsafe { harness_point(bytes.as_ptr(), bytes.len()) }, 0);
+}
+
+#[test]
+fn errors() {
+    let bytes = musli::wire::to_vec(&String::from("not a struct")).unwrap();
+    assert_eq!(unsafe { harness_errors(bytes.as_ptr(), bytes.len()) }, 0);
+}
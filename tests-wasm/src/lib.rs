@@ -0,0 +1,133 @@
+//! A fixed model which is encoded and decoded as JSON, used to keep track of
+//! how much code Müsli contributes to a WebAssembly module.
+//!
+//! This is built with the `wasm-size` profile by `tests/size.rs`, which
+//! compares the size of the resulting library against `baseline.txt`. When
+//! built with `--cfg musli_size` the crate doesn't use the standard library, so
+//! that the machinery it pulls in, like backtraces on native targets, doesn't
+//! drown out the code coming from Müsli.
+
+#![cfg_attr(musli_size, no_std)]
+
+extern crate alloc;
+
+use core::slice;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use musli::{Decode, Encode};
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Order {
+    pub id: u64,
+    pub customer: Customer,
+    pub lines: Vec<Line>,
+    pub status: Status,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Customer {
+    pub name: String,
+    pub email: String,
+    pub vip: bool,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub struct Line {
+    pub sku: String,
+    pub quantity: u32,
+    pub price: f64,
+}
+
+#[derive(Debug, PartialEq, Encode, Decode)]
+pub enum Status {
+    Pending,
+    Shipped { tracking: String },
+    Cancelled(String),
+}
+
+/// Decode an order from JSON and encode it back into `output`.
+///
+/// Returns the number of bytes written, `-1` if the input could not be
+/// decoded, and `-2` if the output didn't fit.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, and `output` must point to
+/// `output_cap` writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn musli_order_roundtrip(
+    input: *const u8,
+    input_len: usize,
+    output: *mut u8,
+    output_cap: usize,
+) -> isize {
+    let input = slice::from_raw_parts(input, input_len);
+    let output = slice::from_raw_parts_mut(output, output_cap);
+
+    let Ok(order) = musli::json::from_slice::<Order>(input) else {
+        return -1;
+    };
+
+    match musli::json::to_slice(output, &order) {
+        Ok(len) => len as isize,
+        Err(..) => -2,
+    }
+}
+
+#[cfg(musli_size)]
+#[panic_handler]
+fn panic(_: &core::panic::PanicInfo<'_>) -> ! {
+    loop {}
+}
+
+#[cfg(musli_size)]
+#[global_allocator]
+static ALLOCATOR: bump::Bump = bump::Bump::new();
+
+#[cfg(musli_size)]
+mod bump {
+    use core::alloc::{GlobalAlloc, Layout};
+    use core::cell::UnsafeCell;
+    use core::ptr;
+
+    const SIZE: usize = 1 << 20;
+
+    /// A bump allocator which never frees, which is enough to roundtrip a
+    /// handful of values while keeping the allocator itself out of the way.
+    pub(super) struct Bump {
+        memory: UnsafeCell<[u8; SIZE]>,
+        used: UnsafeCell<usize>,
+    }
+
+    // SAFETY: The module is single-threaded.
+    unsafe impl Sync for Bump {}
+
+    impl Bump {
+        pub(super) const fn new() -> Self {
+            Self {
+                memory: UnsafeCell::new([0; SIZE]),
+                used: UnsafeCell::new(0),
+            }
+        }
+    }
+
+    unsafe impl GlobalAlloc for Bump {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let base = self.memory.get().cast::<u8>();
+            let used = &mut *self.used.get();
+            let start = (base as usize + *used).next_multiple_of(layout.align()) - base as usize;
+
+            if start + layout.size() > SIZE {
+                return ptr::null_mut();
+            }
+
+            *used = start + layout.size();
+            base.add(start)
+        }
+
+        unsafe fn dealloc(&self, _: *mut u8, _: Layout) {}
+    }
+}
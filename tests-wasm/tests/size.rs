@@ -0,0 +1,181 @@
+//! Guard against growth in the amount of code Müsli contributes to a binary.
+//!
+//! This builds the library in this crate with the `wasm-size` profile for every
+//! target listed in `baseline.txt` which is installed, and fails if it has
+//! grown by more than 10% compared to the recorded size.
+//!
+//! Since the size depends on the compiler, sizes are only checked when built
+//! with the same version of rustc as they were recorded with. Otherwise, or if
+//! no size has been recorded for a target, the size is only printed.
+//!
+//! To record new sizes, run this with `MUSLI_SIZE_BLESS=1`.
+
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The largest allowed growth in percent.
+const TOLERANCE: u64 = 10;
+
+const BASELINE: &str = "baseline.txt";
+
+#[test]
+fn size() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let baseline_path = manifest_dir.join(BASELINE);
+    let baseline = fs::read_to_string(&baseline_path).unwrap();
+    let bless = env::var_os("MUSLI_SIZE_BLESS").is_some();
+    let version = rustc_version();
+
+    let mut header = String::new();
+    let mut entries = Vec::new();
+
+    for line in baseline.lines() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            writeln!(header, "{line}").unwrap();
+            continue;
+        }
+
+        let mut it = line.split_whitespace();
+        let target = it.next().unwrap().to_owned();
+        let recorded = it
+            .next()
+            .zip(it.next())
+            .map(|(version, size)| (version.to_owned(), size.parse::<u64>().unwrap()));
+        entries.push((target, recorded));
+    }
+
+    let mut failures = Vec::new();
+
+    for (target, recorded) in &mut entries {
+        if !is_installed(target) {
+            println!("{target}: skipped since the target is not installed");
+            continue;
+        }
+
+        let actual = build(manifest_dir, target);
+
+        if bless {
+            println!("{target}: recorded {actual} bytes with rustc {version}");
+            *recorded = Some((version.clone(), actual));
+            continue;
+        }
+
+        match recorded {
+            Some((recorded_version, expected)) if *recorded_version == version => {
+                let expected = *expected;
+                let limit = expected + expected * TOLERANCE / 100;
+                println!("{target}: {actual} bytes (baseline {expected}, limit {limit})");
+
+                if actual > limit {
+                    failures.push(format!(
+                        "{target}: {actual} bytes is more than {TOLERANCE}% larger than the baseline of {expected} bytes"
+                    ));
+                }
+            }
+            Some((recorded_version, expected)) => {
+                println!(
+                    "{target}: {actual} bytes (baseline {expected} recorded with rustc {recorded_version}, not checked with rustc {version})"
+                );
+            }
+            None => {
+                println!("{target}: {actual} bytes (no baseline recorded)");
+            }
+        }
+    }
+
+    if bless {
+        let mut out = header;
+
+        for (target, recorded) in &entries {
+            match recorded {
+                Some((version, size)) => writeln!(out, "{target} {version} {size}").unwrap(),
+                None => writeln!(out, "{target}").unwrap(),
+            }
+        }
+
+        fs::write(&baseline_path, out).unwrap();
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{}\nIf the growth is expected, run with MUSLI_SIZE_BLESS=1 to record new sizes",
+        failures.join("\n")
+    );
+}
+
+fn rustc() -> Command {
+    Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+}
+
+/// The release of rustc in use, like `1.85.0`.
+fn rustc_version() -> String {
+    let output = rustc().arg("-vV").output().unwrap();
+    assert!(output.status.success(), "rustc -vV failed");
+
+    let output = String::from_utf8(output.stdout).unwrap();
+
+    output
+        .lines()
+        .find_map(|line| line.strip_prefix("release: "))
+        .expect("missing release in rustc -vV")
+        .to_owned()
+}
+
+fn is_installed(target: &str) -> bool {
+    let Ok(output) = rustc()
+        .args(["--print", "target-libdir", "--target", target])
+        .output()
+    else {
+        return false;
+    };
+
+    if !output.status.success() {
+        return false;
+    }
+
+    let libdir = String::from_utf8(output.stdout).unwrap();
+    Path::new(libdir.trim()).is_dir()
+}
+
+/// Build the library for the given target and return its size in bytes.
+fn build(manifest_dir: &Path, target: &str) -> u64 {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("size");
+
+    let status = Command::new(env::var_os("CARGO").unwrap_or_else(|| "cargo".into()))
+        .arg("build")
+        .arg("--manifest-path")
+        .arg(manifest_dir.join("Cargo.toml"))
+        .args(["--lib", "--profile", "wasm-size", "--target", target])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env("RUSTFLAGS", "--cfg musli_size")
+        .status()
+        .unwrap();
+
+    assert!(status.success(), "{target}: build failed");
+
+    let path = artifact(&target_dir, target);
+    fs::metadata(&path)
+        .unwrap_or_else(|error| panic!("{}: {error}", path.display()))
+        .len()
+}
+
+fn artifact(target_dir: &Path, target: &str) -> PathBuf {
+    let name = if target.starts_with("wasm") {
+        "tests_wasm.wasm"
+    } else if target.contains("windows") {
+        "tests_wasm.dll"
+    } else if target.contains("apple") {
+        "libtests_wasm.dylib"
+    } else {
+        "libtests_wasm.so"
+    };
+
+    target_dir.join(target).join("wasm-size").join(name)
+}
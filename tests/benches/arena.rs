@@ -0,0 +1,76 @@
+use criterion::Criterion;
+
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+use musli::alloc::{Arena, ArenaAllocator, ArenaSlice, ArenaStr};
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+use musli::{Decode, Encode};
+
+// The string fields of the `Allocated` model decoded into owned strings.
+#[derive(Encode, Decode)]
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+struct Owned {
+    string: String,
+    strings: Vec<String>,
+    bytes: Vec<u8>,
+}
+
+// The same model decoded into an arena.
+#[derive(Decode)]
+#[musli(decode_bound<'de, A> = {A: ArenaAllocator<'a>})]
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+struct InArena<'a> {
+    string: ArenaStr<'a>,
+    strings: ArenaSlice<'a, ArenaStr<'a>>,
+    bytes: ArenaSlice<'a, u8>,
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    #[allow(unused)]
+    let mut g = c.benchmark_group("arena");
+
+    #[cfg(all(feature = "musli-storage", feature = "alloc"))]
+    {
+        use std::hint::black_box;
+
+        let inputs = (0..100)
+            .map(|n| {
+                let value = Owned {
+                    string: format!("string number {n}"),
+                    strings: (0..32).map(|m| format!("item {n}-{m}")).collect(),
+                    bytes: (0..64).collect(),
+                };
+
+                musli::storage::to_vec(&value).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        g.bench_function("musli/owned", |b| {
+            b.iter(|| {
+                for input in &inputs {
+                    black_box(musli::storage::from_slice::<Owned>(input).unwrap());
+                }
+            });
+        });
+
+        let mut arena = Arena::new();
+
+        g.bench_function("musli/arena", |b| {
+            b.iter(|| {
+                {
+                    let cx = musli::context::new_in(&arena);
+
+                    for input in &inputs {
+                        let value: InArena<'_> =
+                            musli::storage::DEFAULT.from_slice_with(&cx, input).unwrap();
+                        black_box((value.string, value.strings, value.bytes));
+                    }
+                }
+
+                arena.reset();
+            });
+        });
+    }
+}
+
+criterion::criterion_group!(benches, criterion_benchmark);
+criterion::criterion_main!(benches);
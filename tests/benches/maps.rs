@@ -0,0 +1,62 @@
+use criterion::Criterion;
+
+#[cfg(all(feature = "musli-storage", feature = "std"))]
+use std::collections::HashMap;
+#[cfg(all(feature = "musli-storage", feature = "std"))]
+use std::hash::{BuildHasherDefault, Hasher};
+
+// A simple FNV-1a hasher, standing in for custom hashers like fxhash.
+#[derive(Default)]
+#[cfg(all(feature = "musli-storage", feature = "std"))]
+struct Fnv(u64);
+
+#[cfg(all(feature = "musli-storage", feature = "std"))]
+impl Hasher for Fnv {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 = (self.0 ^ u64::from(b)).wrapping_mul(0x100000001b3);
+        }
+    }
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    #[allow(unused)]
+    let mut g = c.benchmark_group("maps");
+
+    #[cfg(all(feature = "musli-storage", feature = "std"))]
+    {
+        use std::hint::black_box;
+
+        let map = (0..100_000u32)
+            .map(|n| (format!("key-{n}"), n))
+            .collect::<HashMap<_, _>>();
+
+        let input = musli::storage::to_vec(&map).unwrap();
+
+        g.bench_function("musli/hash-map", |b| {
+            b.iter(|| {
+                black_box(musli::storage::from_slice::<HashMap<String, u32>>(&input).unwrap());
+            });
+        });
+
+        g.bench_function("musli/hash-map-fnv", |b| {
+            b.iter(|| {
+                black_box(
+                    musli::storage::from_slice::<HashMap<String, u32, BuildHasherDefault<Fnv>>>(
+                        &input,
+                    )
+                    .unwrap(),
+                );
+            });
+        });
+    }
+}
+
+criterion::criterion_group!(benches, criterion_benchmark);
+criterion::criterion_main!(benches);
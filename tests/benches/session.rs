@@ -0,0 +1,102 @@
+//! Benchmark encoding and decoding many small values one after another,
+//! either through one call per value or through a single session.
+
+use criterion::Criterion;
+
+#[cfg(all(feature = "musli-storage", feature = "musli-wire"))]
+use musli::{Decode, Encode};
+
+#[cfg(all(feature = "musli-storage", feature = "musli-wire"))]
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+struct Point {
+    x: u32,
+    y: u32,
+    weight: f32,
+}
+
+tests::miri! {
+    #[cfg(all(feature = "musli-storage", feature = "musli-wire"))]
+    const VALUES: u32 = 1_000_000, 10;
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    #[allow(unused)]
+    let mut g = c.benchmark_group("session");
+
+    #[cfg(all(feature = "musli-storage", feature = "musli-wire"))]
+    {
+        use std::hint::black_box;
+
+        let points = (0..VALUES)
+            .map(|n| Point {
+                x: n,
+                y: n.wrapping_mul(31),
+                weight: n as f32 / 2.0,
+            })
+            .collect::<Vec<_>>();
+
+        macro_rules! bench {
+            ($what:ident) => {{
+                const ENCODING: musli::$what::Encoding = musli::$what::Encoding::new();
+
+                let mut output = Vec::with_capacity(points.len() * 16);
+
+                g.bench_function(concat!(stringify!($what), "/encode-each"), |b| {
+                    b.iter(|| {
+                        output.clear();
+
+                        for point in &points {
+                            ENCODING.encode(&mut output, point).unwrap();
+                        }
+
+                        black_box(&output);
+                    });
+                });
+
+                g.bench_function(concat!(stringify!($what), "/encode-session"), |b| {
+                    b.iter(|| {
+                        output.clear();
+
+                        let mut encoder = ENCODING.encoder(&mut output);
+
+                        for point in &points {
+                            encoder.encode(point).unwrap();
+                        }
+
+                        black_box(&output);
+                    });
+                });
+
+                let input = output;
+
+                g.bench_function(concat!(stringify!($what), "/decode-each"), |b| {
+                    b.iter(|| {
+                        let mut rest = &input[..];
+
+                        while !rest.is_empty() {
+                            let (point, read) = ENCODING.from_slice_partial::<Point>(rest).unwrap();
+                            rest = &rest[read..];
+                            black_box(point);
+                        }
+                    });
+                });
+
+                g.bench_function(concat!(stringify!($what), "/decode-session"), |b| {
+                    b.iter(|| {
+                        let mut decoder = ENCODING.decoder(&input);
+
+                        while !decoder.is_empty() {
+                            black_box(decoder.decode::<Point>().unwrap());
+                        }
+                    });
+                });
+            }};
+        }
+
+        bench!(storage);
+        bench!(wire);
+    }
+}
+
+criterion::criterion_group!(benches, criterion_benchmark);
+criterion::criterion_main!(benches);
@@ -0,0 +1,98 @@
+//! Benchmark decoding a [`LargeStruct`] from the payload of a newer producer,
+//! which requires skipping over unknown fields, against decoding a payload
+//! with no unknown fields.
+
+use std::hint::black_box;
+
+use criterion::Criterion;
+
+use tests::models::*;
+use tests::utils;
+use tests::Generate;
+
+tests::miri! {
+    const VALUES: usize = 10, 1;
+}
+
+/// Call the given macro with every framework in the matrix which supports
+/// skipping unknown fields.
+macro_rules! skip_matrix {
+    ($call:ident) => {
+        #[cfg(feature = "musli-wire")]
+        $call!(musli_wire);
+        #[cfg(feature = "musli-descriptive")]
+        $call!(musli_descriptive);
+        #[cfg(feature = "musli-json")]
+        $call!(musli_json);
+    };
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    let mut rng = tests::rng();
+
+    let v2 = (0..VALUES)
+        .map(|_| LargeStructV2::generate(&mut rng))
+        .collect::<Vec<_>>();
+
+    let v1 = v2.iter().map(LargeStructV2::to_v1).collect::<Vec<_>>();
+
+    #[allow(unused)]
+    let mut g = c.benchmark_group("skip/large");
+
+    macro_rules! bench {
+        ($framework:ident) => {{
+            if utils::$framework::is_enabled() {
+                let mut frameworks = Vec::with_capacity(VALUES * 2);
+
+                for _ in 0..VALUES * 2 {
+                    frameworks.push(utils::$framework::new());
+                }
+
+                let mut states = Vec::with_capacity(frameworks.len());
+
+                for framework in &mut frameworks {
+                    states.push(framework.state());
+                }
+
+                let (known, unknown) = states.split_at_mut(VALUES);
+
+                let mut known_inputs = Vec::with_capacity(VALUES);
+
+                for (value, state) in v1.iter().zip(known) {
+                    state.reset(10000, value);
+                    known_inputs.push(state.encode(value).unwrap());
+                }
+
+                let mut unknown_inputs = Vec::with_capacity(VALUES);
+
+                for ((value, expected), state) in v2.iter().zip(&v1).zip(unknown) {
+                    state.reset(10000, value);
+                    let mut input = state.encode(value).unwrap();
+                    assert_eq!(input.decode::<LargeStruct>().unwrap(), *expected);
+                    unknown_inputs.push(input);
+                }
+
+                g.bench_function(concat!(stringify!($framework), "/known"), |b| {
+                    b.iter(|| {
+                        for data in &mut known_inputs {
+                            black_box(data.decode::<LargeStruct>().unwrap());
+                        }
+                    });
+                });
+
+                g.bench_function(concat!(stringify!($framework), "/unknown"), |b| {
+                    b.iter(|| {
+                        for data in &mut unknown_inputs {
+                            black_box(data.decode::<LargeStruct>().unwrap());
+                        }
+                    });
+                });
+            }
+        }};
+    }
+
+    skip_matrix!(bench);
+}
+
+criterion::criterion_group!(benches, criterion_benchmark);
+criterion::criterion_main!(benches);
@@ -0,0 +1,64 @@
+use criterion::Criterion;
+
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+use musli::{Decode, Encode};
+
+// Strings decoded into owned values, which copies them out of the input.
+#[derive(Encode, Decode)]
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+struct Owned {
+    strings: Vec<String>,
+}
+
+// The same strings borrowed from the input, which only validates them.
+#[derive(Decode)]
+#[cfg(all(feature = "musli-storage", feature = "alloc"))]
+struct Borrowed<'de> {
+    strings: Vec<&'de str>,
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    #[allow(unused)]
+    let mut g = c.benchmark_group("strings");
+
+    #[cfg(all(feature = "musli-storage", feature = "alloc"))]
+    {
+        use std::hint::black_box;
+
+        // A mix of ASCII and multi-byte strings of varying length.
+        let inputs = (0..100)
+            .map(|n| {
+                let value = Owned {
+                    strings: (0..32)
+                        .map(|m| "héllo wörld ".repeat(1 + (n + m) % 16))
+                        .collect(),
+                };
+
+                musli::storage::to_vec(&value).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        g.bench_function("musli/owned", |b| {
+            b.iter(|| {
+                for input in &inputs {
+                    black_box(musli::storage::from_slice::<Owned>(input).unwrap().strings);
+                }
+            });
+        });
+
+        g.bench_function("musli/borrowed", |b| {
+            b.iter(|| {
+                for input in &inputs {
+                    black_box(
+                        musli::storage::from_slice::<Borrowed<'_>>(input)
+                            .unwrap()
+                            .strings,
+                    );
+                }
+            });
+        });
+    }
+}
+
+criterion::criterion_group!(benches, criterion_benchmark);
+criterion::criterion_main!(benches);
@@ -1,14 +1,16 @@
+#[cfg(feature = "musli-zerocopy")]
+use criterion::measurement::WallTime;
 use criterion::Criterion;
 
 #[cfg(feature = "musli-zerocopy")]
-use musli_zerocopy::{OwnedBuf, Ref, ZeroCopy};
+use musli_zerocopy::{OwnedBuf, Ref, ValidateTree, ZeroCopy};
 #[cfg(feature = "rkyv")]
 use rkyv::rancor::Failure;
 #[cfg(feature = "rkyv")]
 use rkyv::{Archive, Serialize};
 
 // Musli zero copy
-#[derive(ZeroCopy, Debug)]
+#[derive(ZeroCopy, ValidateTree, Debug)]
 #[repr(C)]
 #[cfg(feature = "musli-zerocopy")]
 struct DataMusli {
@@ -65,6 +67,229 @@ fn criterion_benchmark(c: &mut Criterion) {
         });
     });
 
+    #[cfg(feature = "musli-zerocopy")]
+    g.bench_function("musli/trusted", |b| {
+        use std::hint::black_box;
+
+        let mut buf = OwnedBuf::new();
+
+        let mut vec = Vec::new();
+
+        for _ in 0..vec_size {
+            let str = format!("some_long_string{}", some_long_str);
+            vec.push(buf.store_slice(str.as_bytes()));
+        }
+
+        let slice = buf.store_slice(&vec);
+        let root = buf.store(&DataMusli { slice });
+
+        // Validation happens once up front, outside of the hot path.
+        let trusted = buf.trust(root).unwrap();
+
+        b.iter(|| {
+            let mut len = 0;
+
+            // SAFETY: Every reference is reachable from the validated root.
+            let slice = unsafe { trusted.load(trusted.root().slice) };
+
+            for item in slice.iter() {
+                len += black_box(unsafe { trusted.load(*item) }).len();
+            }
+
+            len
+        });
+    });
+
+    #[cfg(feature = "musli-zerocopy")]
+    {
+        use musli_zerocopy::swiss;
+        use std::hint::black_box;
+        use std::ops::ControlFlow;
+
+        let mut buf = OwnedBuf::new();
+        let entries = (0..vec_size).map(|n| (n, n * 2)).collect::<Vec<_>>();
+        let map = swiss::store_map(&mut buf, entries).unwrap();
+
+        g.bench_function("musli/swiss-get", |b| {
+            b.iter(|| {
+                let mut sum = 0;
+
+                for key in 0..vec_size {
+                    sum += black_box(map.get(&buf, &key).unwrap().unwrap());
+                }
+
+                sum
+            });
+        });
+
+        g.bench_function("musli/swiss-scan", |b| {
+            b.iter(|| {
+                let mut sum = 0;
+
+                let _ = map.for_each_value(&buf, |value| {
+                    sum += black_box(value);
+                    Ok::<_, musli_zerocopy::Error>(ControlFlow::<()>::Continue(()))
+                });
+
+                sum
+            });
+        });
+    }
+
+    #[cfg(feature = "musli-zerocopy")]
+    {
+        use musli_zerocopy::phf::{self, Fnv1a, PhfHasher, Sip13};
+        use std::hint::black_box;
+
+        fn bench_u64<H>(g: &mut criterion::BenchmarkGroup<'_, WallTime>, name: &str, len: u64)
+        where
+            H: PhfHasher,
+        {
+            let mut buf = OwnedBuf::new();
+            let entries = (0..len).map(|n| (n, n * 2)).collect::<Vec<_>>();
+            let map = phf::store_map_with_hasher::<H, _, _, _, _>(&mut buf, entries).unwrap();
+            let map = buf.bind(map).unwrap();
+
+            g.bench_function(name, |b| {
+                b.iter(|| {
+                    let mut sum = 0;
+
+                    for key in 0..len {
+                        sum += black_box(map.get(&key).unwrap().unwrap());
+                    }
+
+                    sum
+                });
+            });
+        }
+
+        fn bench_bytes<H>(g: &mut criterion::BenchmarkGroup<'_, WallTime>, name: &str, len: u64)
+        where
+            H: PhfHasher,
+        {
+            let mut buf = OwnedBuf::new();
+
+            let keys = (0..len)
+                .map(|n| {
+                    let mut key = [0u8; 64];
+                    key[..8].copy_from_slice(&n.to_le_bytes());
+                    key
+                })
+                .collect::<Vec<_>>();
+
+            let entries = keys
+                .iter()
+                .enumerate()
+                .map(|(n, key)| (buf.store_slice(key), n as u64))
+                .collect::<Vec<_>>();
+
+            let map = phf::store_map_with_hasher::<H, _, _, _, _>(&mut buf, entries).unwrap();
+            let map = buf.bind(map).unwrap();
+
+            g.bench_function(name, |b| {
+                b.iter(|| {
+                    let mut sum = 0;
+
+                    for key in &keys {
+                        sum += black_box(map.get(&key[..]).unwrap().unwrap());
+                    }
+
+                    sum
+                });
+            });
+        }
+
+        bench_u64::<Sip13>(&mut g, "musli/phf-sip13-8", vec_size);
+        bench_u64::<Fnv1a>(&mut g, "musli/phf-fnv1a-8", vec_size);
+        bench_bytes::<Sip13>(&mut g, "musli/phf-sip13-64", vec_size);
+        bench_bytes::<Fnv1a>(&mut g, "musli/phf-fnv1a-64", vec_size);
+    }
+
+    #[cfg(feature = "musli-zerocopy")]
+    {
+        use musli_zerocopy::bitset;
+        use std::hint::black_box;
+
+        const BITS: usize = 10_000_000;
+
+        let mut buf = OwnedBuf::new();
+        let mut builder = bitset::Builder::with_len(BITS);
+
+        for index in (0..BITS).step_by(7) {
+            builder.insert(index);
+        }
+
+        let set = builder.build(&mut buf).unwrap();
+
+        g.bench_function("musli/bitset-contains", |b| {
+            b.iter(|| {
+                let mut count = 0;
+
+                for index in (0..BITS).step_by(101) {
+                    count += black_box(set.contains(&buf, index).unwrap()) as usize;
+                }
+
+                count
+            });
+        });
+
+        g.bench_function("musli/bitset-iter", |b| {
+            b.iter(|| {
+                let mut sum = 0;
+
+                for index in set.iter_ones(&buf).unwrap() {
+                    sum += black_box(index);
+                }
+
+                sum
+            });
+        });
+    }
+
+    #[cfg(feature = "musli-zerocopy")]
+    {
+        use musli_zerocopy::trie::{self, Flavor};
+        use std::hint::black_box;
+
+        // Keys where every node is wide, like a trie over a large alphabet.
+        let keys = (0..=255u8)
+            .flat_map(|a| (0..=255u8).map(move |b| [a, b]))
+            .collect::<Vec<_>>();
+
+        fn bench_trie<F>(
+            g: &mut criterion::BenchmarkGroup<'_, WallTime>,
+            name: &str,
+            keys: &[[u8; 2]],
+        ) where
+            F: Flavor,
+        {
+            let mut buf = OwnedBuf::new();
+            let mut builder = trie::Builder::<u32, F>::with_flavor();
+
+            for (n, key) in keys.iter().enumerate() {
+                let key = buf.store_slice(key);
+                builder.insert(&buf, key, n as u32).unwrap();
+            }
+
+            let trie = builder.build(&mut buf).unwrap();
+
+            g.bench_function(name, |b| {
+                b.iter(|| {
+                    let mut sum = 0;
+
+                    for key in keys {
+                        sum += black_box(trie.get(&buf, key).unwrap().unwrap()[0]);
+                    }
+
+                    sum
+                });
+            });
+        }
+
+        bench_trie::<trie::DefaultFlavor>(&mut g, "musli/trie-wide", &keys);
+        bench_trie::<trie::HashedFlavor>(&mut g, "musli/trie-wide-hashed", &keys);
+    }
+
     #[cfg(feature = "rkyv")]
     g.bench_function("rkyv/unchecked", |b| {
         use std::hint::black_box;
@@ -395,6 +395,103 @@ impl PartialEq<LargeStruct> for &LargeStruct {
     }
 }
 
+/// A newer version of [`LargeStruct`] with additional fields of various kinds.
+///
+/// The leading fields are the same as in [`LargeStruct`], so an older reader
+/// decoding this as a [`LargeStruct`] has to skip over the rest.
+#[derive(Debug, Clone, PartialEq, Generate)]
+#[cfg_attr(feature = "musli", derive(Encode, Decode), musli(mode = Packed, packed))]
+pub struct LargeStructV2 {
+    #[generate(range = PRIMITIVES_RANGE)]
+    #[cfg(feature = "alloc")]
+    primitives: Vec<Primitives>,
+    #[cfg(all(feature = "alloc", not(feature = "no-vec"), not(feature = "no-tuple")))]
+    #[generate(range = PRIMITIVES_RANGE)]
+    tuples: Vec<(Tuples, Tuples)>,
+    #[generate(range = MEDIUM_RANGE)]
+    #[cfg(all(
+        feature = "alloc",
+        any(not(feature = "no-empty"), not(feature = "no-nonunit-variant"))
+    ))]
+    medium_vec: Vec<MediumEnum>,
+    #[cfg(all(
+        feature = "std",
+        not(feature = "no-map"),
+        not(feature = "no-string-key")
+    ))]
+    #[generate(range = MEDIUM_RANGE)]
+    medium_map: HashMap<String, MediumEnum>,
+    #[cfg(all(
+        feature = "std",
+        not(feature = "no-map"),
+        not(feature = "no-string-key")
+    ))]
+    string_keys: HashMap<String, u64>,
+    #[cfg(all(
+        feature = "std",
+        not(feature = "no-map"),
+        not(feature = "no-number-key")
+    ))]
+    number_map: HashMap<u32, u64>,
+    #[cfg(all(feature = "alloc", not(feature = "no-tuple")))]
+    number_vec: Vec<(u32, u64)>,
+    extra_primitives: Primitives,
+    #[cfg(feature = "alloc")]
+    #[cfg_attr(feature = "musli", musli(bytes))]
+    #[generate(range = MEDIUM_RANGE)]
+    extra_bytes: Vec<u8>,
+    #[cfg(feature = "alloc")]
+    #[generate(range = MEDIUM_RANGE)]
+    extra_strings: Vec<String>,
+    #[cfg(feature = "alloc")]
+    #[generate(range = SMALL_FIELDS)]
+    extra_nested: Vec<Vec<Primitives>>,
+    #[cfg(all(
+        feature = "std",
+        not(feature = "no-map"),
+        not(feature = "no-string-key")
+    ))]
+    #[generate(range = MEDIUM_RANGE)]
+    extra_map: HashMap<String, Vec<u32>>,
+}
+
+impl LargeStructV2 {
+    /// The [`LargeStruct`] an older reader is expected to decode.
+    pub fn to_v1(&self) -> LargeStruct {
+        LargeStruct {
+            #[cfg(feature = "alloc")]
+            primitives: self.primitives.clone(),
+            #[cfg(all(feature = "alloc", not(feature = "no-vec"), not(feature = "no-tuple")))]
+            tuples: self.tuples.clone(),
+            #[cfg(all(
+                feature = "alloc",
+                any(not(feature = "no-empty"), not(feature = "no-nonunit-variant"))
+            ))]
+            medium_vec: self.medium_vec.clone(),
+            #[cfg(all(
+                feature = "std",
+                not(feature = "no-map"),
+                not(feature = "no-string-key")
+            ))]
+            medium_map: self.medium_map.clone(),
+            #[cfg(all(
+                feature = "std",
+                not(feature = "no-map"),
+                not(feature = "no-string-key")
+            ))]
+            string_keys: self.string_keys.clone(),
+            #[cfg(all(
+                feature = "std",
+                not(feature = "no-map"),
+                not(feature = "no-number-key")
+            ))]
+            number_map: self.number_map.clone(),
+            #[cfg(all(feature = "alloc", not(feature = "no-tuple")))]
+            number_vec: self.number_vec.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Generate)]
 #[cfg_attr(feature = "musli", derive(Encode, Decode), musli(mode = Packed, packed))]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
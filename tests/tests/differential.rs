@@ -0,0 +1,115 @@
+//! Differential tests which move the same value across format boundaries
+//! through the dynamic [`Value`] layer, to catch drift in how the data model
+//! is represented by different formats.
+//!
+//! [`Value`]: musli::value::Value
+
+#![cfg(all(
+    feature = "musli-descriptive",
+    feature = "musli-json",
+    feature = "musli-value",
+    feature = "musli-wire"
+))]
+
+use std::fmt;
+
+use musli::alloc::System;
+use musli::context::{self, DefaultContext, Ignore, Trace};
+use musli::de::DecodeOwned;
+use musli::mode::Binary;
+use musli::value::Value;
+use musli::Encode;
+use tests::models::*;
+use tests::Generate;
+
+tests::miri! {
+    const ITER: usize = 100, 2;
+}
+
+type Cx = DefaultContext<System, Trace, Ignore>;
+
+/// Run a single step of a chain, panicking with the trace of where decoding
+/// diverged if it fails.
+#[track_caller]
+fn step<T, E>(name: &str, chain: &str, index: usize, f: impl FnOnce(&Cx) -> Result<T, E>) -> T {
+    let cx = context::new().with_trace();
+
+    match f(&cx) {
+        Ok(value) => value,
+        Err(..) => {
+            let report = cx.report().to_string();
+            panic!("{name}[{index}]: {chain}: {report}");
+        }
+    }
+}
+
+#[track_caller]
+fn differential<T>(name: &str)
+where
+    T: fmt::Debug + PartialEq + Generate + Encode<Binary> + DecodeOwned<Binary, System>,
+{
+    let json = musli::json::Encoding::new().with_mode::<Binary>();
+    let descriptive = musli::descriptive::Encoding::new();
+    let wire = musli::wire::Encoding::new();
+
+    let mut rng = tests::rng();
+
+    for index in 0..ITER {
+        let expected = T::generate(&mut rng);
+
+        // descriptive -> value -> json -> typed
+        let chain = "descriptive -> value -> json -> typed";
+        let bytes = step(name, chain, index, |cx| {
+            descriptive.to_vec_with(cx, &expected)
+        });
+        let value: Value<System> = step(name, chain, index, |cx| {
+            descriptive.from_slice_with(cx, &bytes)
+        });
+        let bytes = step(name, chain, index, |cx| json.to_vec_with(cx, &value));
+        let actual: T = step(name, chain, index, |cx| json.from_slice_with(cx, &bytes));
+        assert_eq!(actual, expected, "{name}[{index}]: {chain}");
+
+        // json -> value -> descriptive -> value -> typed
+        //
+        // JSON stores map keys as strings, so the typed value can't be
+        // recovered by descriptive directly. Instead we check that descriptive
+        // preserves the value which was produced by JSON.
+        let chain = "json -> value -> descriptive -> value -> typed";
+        let bytes = step(name, chain, index, |cx| json.to_vec_with(cx, &expected));
+        let value: Value<System> = step(name, chain, index, |cx| json.from_slice_with(cx, &bytes));
+        let bytes = step(name, chain, index, |cx| descriptive.to_vec_with(cx, &value));
+        let roundtrip: Value<System> = step(name, chain, index, |cx| {
+            descriptive.from_slice_with(cx, &bytes)
+        });
+        assert_eq!(roundtrip, value, "{name}[{index}]: {chain}");
+        let actual: T = step(name, chain, index, |cx| {
+            musli::value::decode_with::<_, _, Binary>(cx, &roundtrip)
+        });
+        assert_eq!(actual, expected, "{name}[{index}]: {chain}");
+
+        // wire -> typed -> descriptive -> value -> typed
+        let chain = "wire -> typed -> descriptive -> value -> typed";
+        let bytes = step(name, chain, index, |cx| wire.to_vec_with(cx, &expected));
+        let typed: T = step(name, chain, index, |cx| wire.from_slice_with(cx, &bytes));
+        let bytes = step(name, chain, index, |cx| descriptive.to_vec_with(cx, &typed));
+        let value: Value<System> = step(name, chain, index, |cx| {
+            descriptive.from_slice_with(cx, &bytes)
+        });
+        let actual: T = step(name, chain, index, |cx| {
+            musli::value::decode_with::<_, _, Binary>(cx, &value)
+        });
+        assert_eq!(actual, expected, "{name}[{index}]: {chain}");
+    }
+}
+
+macro_rules! differential {
+    ($name:ident, $ty:ty $(, $($tt:tt)*)?) => {
+        #[test]
+        fn $name() {
+            differential::<$ty>(stringify!($name));
+        }
+    };
+}
+
+tests::types!(differential);
+differential!(tuples, Tuples);
@@ -0,0 +1,108 @@
+//! Compare the size of the wire format configured with
+//! [`musli::wire::msgpack_like`] against the default wire format and
+//! MessagePack, using the data from the benchmarks.
+
+#![cfg(feature = "musli-wire")]
+
+use std::fmt;
+
+use musli::alloc::System;
+use musli::de::DecodeOwned;
+use musli::mode::Binary;
+use musli::Encode;
+use tests::models::*;
+use tests::Generate;
+
+tests::miri! {
+    const ITER: usize = 100, 2;
+}
+
+/// The total size of the values encoded with the default options and with
+/// [`musli::wire::msgpack_like`].
+#[track_caller]
+fn sizes<T>(name: &str, values: &[T]) -> (usize, usize)
+where
+    T: fmt::Debug + PartialEq + Encode<Binary> + DecodeOwned<Binary, System>,
+{
+    let default = musli::wire::DEFAULT;
+    let msgpack_like = musli::wire::msgpack_like();
+
+    let mut default_len = 0;
+    let mut msgpack_like_len = 0;
+
+    for (index, expected) in values.iter().enumerate() {
+        default_len += default.to_vec(expected).unwrap().len();
+
+        let bytes = msgpack_like.to_vec(expected).unwrap();
+        msgpack_like_len += bytes.len();
+
+        let actual: T = msgpack_like.from_slice(&bytes).unwrap();
+        assert_eq!(&actual, expected, "{name}[{index}]");
+    }
+
+    (default_len, msgpack_like_len)
+}
+
+fn generate<T>() -> Vec<T>
+where
+    T: Generate,
+{
+    let mut rng = tests::rng();
+    (0..ITER).map(|_| T::generate(&mut rng)).collect()
+}
+
+macro_rules! msgpack_like {
+    ($name:ident, $ty:ty $(, $($tt:tt)*)?) => {
+        #[test]
+        fn $name() {
+            let values = generate::<$ty>();
+            let (default, msgpack_like) = sizes(stringify!($name), &values);
+
+            assert!(
+                msgpack_like <= default,
+                "{}: {msgpack_like} > {default}",
+                stringify!($name)
+            );
+        }
+    };
+}
+
+tests::types!(msgpack_like);
+
+#[test]
+fn floats() {
+    let encoding = musli::wire::msgpack_like();
+
+    // The same sizes as a float32 and a float64 in MessagePack.
+    assert_eq!(encoding.to_vec(&1.5f32).unwrap().len(), 5);
+    assert_eq!(encoding.to_vec(&1.5f64).unwrap().len(), 9);
+
+    let values = generate::<Mesh>();
+    let (default, msgpack_like) = sizes("mesh", &values);
+    assert!(msgpack_like < default, "mesh: {msgpack_like} >= {default}");
+}
+
+/// Meshes are mostly made up of floats, so this compares how they are stored
+/// without being affected by the differences in how integers are encoded.
+#[cfg(feature = "rmp-serde")]
+#[test]
+fn rmp_serde_parity() {
+    let values = generate::<Mesh>();
+    let (_, msgpack_like) = sizes("mesh", &values);
+
+    let mut compact = 0;
+    let mut named = 0;
+
+    for value in &values {
+        compact += rmp_serde::to_vec(value).unwrap().len();
+        named += rmp_serde::to_vec_named(value).unwrap().len();
+    }
+
+    // Field names are larger than field indexes, but structs encoded as arrays
+    // without any field identifiers are smaller.
+    assert!(msgpack_like <= named, "{msgpack_like} > {named}");
+    assert!(
+        msgpack_like * 2 <= compact * 3,
+        "{msgpack_like} is more than 1.5 times {compact}"
+    );
+}
@@ -0,0 +1,37 @@
+//! Test that an older reader can decode the payload of a newer producer by
+//! skipping over the fields it doesn't know about.
+
+#![cfg(all(
+    feature = "musli-descriptive",
+    feature = "musli-json",
+    feature = "musli-wire"
+))]
+
+use tests::models::*;
+use tests::Generate;
+
+tests::miri! {
+    const ITER: usize = 100, 2;
+}
+
+#[test]
+fn large_struct_v2() {
+    let mut rng = tests::rng();
+
+    for index in 0..ITER {
+        let value = LargeStructV2::generate(&mut rng);
+        let expected = value.to_v1();
+
+        let bytes = musli::wire::to_vec(&value).unwrap();
+        let actual: LargeStruct = musli::wire::from_slice(&bytes).unwrap();
+        assert_eq!(actual, expected, "wire[{index}]");
+
+        let bytes = musli::descriptive::to_vec(&value).unwrap();
+        let actual: LargeStruct = musli::descriptive::from_slice(&bytes).unwrap();
+        assert_eq!(actual, expected, "descriptive[{index}]");
+
+        let json = musli::json::to_string(&value).unwrap();
+        let actual: LargeStruct = musli::json::from_str(&json).unwrap();
+        assert_eq!(actual, expected, "json[{index}]");
+    }
+}